@@ -35,6 +35,13 @@ pub struct BuiltFile {
     pub interns: Interns,
 }
 
+pub struct CheckedFile {
+    pub problems: Problems,
+    pub total_time: Duration,
+    /// The files of the user modules that were checked
+    pub module_paths: Vec<PathBuf>,
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn build_file<'a>(
     arena: &'a Bump,
@@ -47,7 +54,9 @@ pub fn build_file<'a>(
     linking_strategy: LinkingStrategy,
     precompiled: bool,
     threading: Threading,
+    render: RenderTarget,
     wasm_dev_stack_bytes: Option<u32>,
+    cache_dir: Option<PathBuf>,
 ) -> Result<BuiltFile, LoadingProblem<'a>> {
     let compilation_start = Instant::now();
     let target_info = TargetInfo::from(target);
@@ -57,10 +66,10 @@ pub fn build_file<'a>(
 
     let load_config = LoadConfig {
         target_info,
-        // TODO: expose this from CLI?
-        render: RenderTarget::ColorTerminal,
+        render,
         threading,
         exec_mode: ExecutionMode::Executable,
+        cache_dir,
     };
     let loaded = roc_load::load_and_monomorphize(
         arena,
@@ -101,6 +110,12 @@ pub fn build_file<'a>(
         binary_path.set_extension(extension);
     }
 
+    // The surgical linker writes the shared library to exactly this path, so give it the usual
+    // extension. The legacy linker chooses the extension itself, e.g. `.so.1.0` on Linux.
+    if (linking_strategy, link_type) == (LinkingStrategy::Surgical, LinkType::Dylib) {
+        binary_path.set_extension("so");
+    }
+
     let host_input_path = if let EntryPoint::Executable { platform_path, .. } = &loaded.entry_point
     {
         cwd.join(platform_path)
@@ -137,6 +152,8 @@ pub fn build_file<'a>(
 
     let preprocessed_host_path = if emit_wasm {
         host_input_path.with_file_name("preprocessedhost.o")
+    } else if link_type == LinkType::Dylib {
+        host_input_path.with_file_name("preprocessedhost.so")
     } else {
         host_input_path.with_file_name("preprocessedhost")
     };
@@ -151,6 +168,8 @@ pub fn build_file<'a>(
         target,
         exposed_values,
         exposed_closure_types,
+        link_type,
+        render,
     );
 
     // TODO try to move as much of this linking as possible to the precompiled
@@ -212,7 +231,7 @@ pub fn build_file<'a>(
     // This only needs to be mutable for report_problems. This can't be done
     // inside a nested scope without causing a borrow error!
     let mut loaded = loaded;
-    let problems = program::report_problems_monomorphized(&mut loaded);
+    let problems = program::report_problems_monomorphized(&mut loaded, render);
     let expectations = std::mem::take(&mut loaded.expectations);
     let loaded = loaded;
 
@@ -298,7 +317,13 @@ pub fn build_file<'a>(
     let link_start = Instant::now();
     let problems = match (linking_strategy, link_type) {
         (LinkingStrategy::Surgical, _) => {
-            roc_linker::link_preprocessed_host(target, &host_input_path, app_o_file, &binary_path);
+            roc_linker::link_preprocessed_host(
+                target,
+                &host_input_path,
+                app_o_file,
+                &binary_path,
+                link_type,
+            );
             problems
         }
         (LinkingStrategy::Additive, _) | (LinkingStrategy::Legacy, LinkType::None) => {
@@ -375,11 +400,13 @@ fn spawn_rebuild_thread(
     target: &Triple,
     exported_symbols: Vec<String>,
     exported_closure_types: Vec<String>,
+    link_type: LinkType,
+    render: RenderTarget,
 ) -> std::thread::JoinHandle<u128> {
     let thread_local_target = target.clone();
     std::thread::spawn(move || {
         if !precompiled {
-            println!("🔨 Rebuilding host...");
+            crate::report_status(render, "🔨 Rebuilding host...");
         }
 
         let rebuild_host_start = Instant::now();
@@ -404,6 +431,7 @@ fn spawn_rebuild_thread(
                         preprocessed_host_path.as_path(),
                         exported_symbols,
                         exported_closure_types,
+                        link_type,
                     );
                 }
                 LinkingStrategy::Legacy => {
@@ -432,7 +460,9 @@ pub fn check_file(
     roc_file_path: PathBuf,
     emit_timings: bool,
     threading: Threading,
-) -> Result<(program::Problems, Duration), LoadingProblem> {
+    render: RenderTarget,
+    cache_dir: Option<PathBuf>,
+) -> Result<CheckedFile, LoadingProblem> {
     let compilation_start = Instant::now();
 
     // only used for generating errors. We don't do code generation, so hardcoding should be fine
@@ -444,10 +474,10 @@ pub fn check_file(
 
     let load_config = LoadConfig {
         target_info,
-        // TODO: expose this from CLI?
-        render: RenderTarget::ColorTerminal,
+        render,
         threading,
         exec_mode: ExecutionMode::Check,
+        cache_dir,
     };
     let mut loaded =
         roc_load::load_and_typecheck(arena, roc_file_path, subs_by_module, load_config)?;
@@ -495,8 +525,9 @@ pub fn check_file(
         println!("Finished checking in {} ms\n", compilation_end.as_millis(),);
     }
 
-    Ok((
-        program::report_problems_typechecked(&mut loaded),
-        compilation_end,
-    ))
+    Ok(CheckedFile {
+        problems: program::report_problems_typechecked(&mut loaded, render),
+        total_time: compilation_end,
+        module_paths: crate::watch::module_paths(&loaded.sources),
+    })
 }
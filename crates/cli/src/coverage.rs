@@ -0,0 +1,164 @@
+//! Reporting which code `roc test --coverage` ran: as an lcov tracefile, which editors and CI
+//! services understand, and as a summary per module in the terminal.
+//!
+//! The compiler counts the executions of every function and of every branch of an `if` or `when`.
+//! A line is attributed the count of the innermost function or branch it is part of, so a line
+//! that no expect ran has a count of 0.
+use roc_region::all::{LineColumnRegion, Region};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// A function or branch, and how often it ran.
+#[derive(Debug, Clone)]
+pub struct CoverageCount {
+    pub region: LineColumnRegion,
+    pub count: u64,
+    /// The name of the function, if this is the entry point of one
+    pub function: Option<String>,
+}
+
+/// The coverage of one module.
+#[derive(Debug)]
+pub struct FileCoverage {
+    pub module: String,
+    pub path: PathBuf,
+    /// The executions of each 0-based line that is part of a function or branch
+    lines: BTreeMap<u32, u64>,
+    /// The name, 0-based line and executions of each function
+    functions: Vec<(String, u32, u64)>,
+}
+
+impl FileCoverage {
+    pub fn new(module: String, path: PathBuf, mut counts: Vec<CoverageCount>) -> Self {
+        // the outermost regions first, so the counts of nested branches overwrite them
+        counts.sort_by_key(|count| {
+            let LineColumnRegion { start, end } = count.region;
+            (
+                std::cmp::Reverse(end.line - start.line),
+                std::cmp::Reverse(end.column),
+                start.column,
+            )
+        });
+
+        let mut lines = BTreeMap::new();
+        let mut functions = Vec::new();
+
+        for count in counts {
+            for line in count.region.start.line..=count.region.end.line {
+                lines.insert(line, count.count);
+            }
+
+            if let Some(name) = count.function {
+                functions.push((name, count.region.start.line, count.count));
+            }
+        }
+
+        functions.sort_by_key(|(_, line, _)| *line);
+
+        Self {
+            module,
+            path,
+            lines,
+            functions,
+        }
+    }
+
+    fn lines_hit(&self) -> usize {
+        self.lines.values().filter(|count| **count > 0).count()
+    }
+
+    fn functions_hit(&self) -> usize {
+        self.functions
+            .iter()
+            .filter(|(_, _, count)| *count > 0)
+            .count()
+    }
+}
+
+/// Whether a region of source code is part of any of the given regions, e.g. of the top-level
+/// `expect`s, which are not counted as code under test.
+pub fn is_within(region: Region, regions: &[Region]) -> bool {
+    regions.iter().any(|outer| outer.contains(&region))
+}
+
+/// Writes an lcov tracefile. Lines and functions are 1-based, as lcov expects.
+pub fn write_lcov<W: Write>(writer: &mut W, files: &[FileCoverage]) -> io::Result<()> {
+    for file in files {
+        writeln!(writer, "TN:")?;
+        writeln!(writer, "SF:{}", file.path.display())?;
+
+        for (name, line, _) in &file.functions {
+            writeln!(writer, "FN:{},{}", line + 1, name)?;
+        }
+        for (name, _, count) in &file.functions {
+            writeln!(writer, "FNDA:{},{}", count, name)?;
+        }
+        writeln!(writer, "FNF:{}", file.functions.len())?;
+        writeln!(writer, "FNH:{}", file.functions_hit())?;
+
+        for (line, count) in &file.lines {
+            writeln!(writer, "DA:{},{}", line + 1, count)?;
+        }
+        writeln!(writer, "LF:{}", file.lines.len())?;
+        writeln!(writer, "LH:{}", file.lines_hit())?;
+
+        writeln!(writer, "end_of_record")?;
+    }
+
+    Ok(())
+}
+
+/// Writes the share of lines and functions that ran, per module and in total.
+pub fn write_summary<W: Write>(writer: &mut W, files: &[FileCoverage]) -> io::Result<()> {
+    let name_width = files
+        .iter()
+        .map(|file| file.module.len())
+        .chain(std::iter::once("Total".len()))
+        .max()
+        .unwrap_or_default();
+
+    let write_row =
+        |writer: &mut W, name: &str, lines: (usize, usize), functions: (usize, usize)| {
+            writeln!(
+                writer,
+                "  {:<name_width$}  lines {:>6}  functions {:>6}",
+                name,
+                percentage(lines),
+                percentage(functions),
+                name_width = name_width,
+            )
+        };
+
+    writeln!(writer, "Coverage:")?;
+
+    let mut total_lines = (0, 0);
+    let mut total_functions = (0, 0);
+
+    for file in files {
+        let lines = (file.lines_hit(), file.lines.len());
+        let functions = (file.functions_hit(), file.functions.len());
+
+        write_row(writer, &file.module, lines, functions)?;
+
+        total_lines = (total_lines.0 + lines.0, total_lines.1 + lines.1);
+        total_functions = (
+            total_functions.0 + functions.0,
+            total_functions.1 + functions.1,
+        );
+    }
+
+    if files.len() > 1 {
+        write_row(writer, "Total", total_lines, total_functions)?;
+    }
+
+    Ok(())
+}
+
+fn percentage((hit, found): (usize, usize)) -> String {
+    if found == 0 {
+        "-".to_string()
+    } else {
+        format!("{:.1}%", hit as f64 * 100.0 / found as f64)
+    }
+}
@@ -1,18 +1,23 @@
 use std::ffi::OsStr;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
 use crate::FormatMode;
 use bumpalo::Bump;
-use roc_error_macros::{internal_error, user_error};
+use roc_error_macros::internal_error;
 use roc_fmt::def::fmt_defs;
 use roc_fmt::module::fmt_module;
 use roc_fmt::spaces::RemoveSpaces;
 use roc_fmt::{Ast, Buf};
+use roc_module::symbol::Interns;
 use roc_parse::{
     module::{self, module_defs},
-    parser::{Parser, SyntaxError},
+    parser::{Parser, SourceError, SyntaxError},
     state::State,
 };
+use roc_region::all::LineInfo;
+use roc_reporting::report::{parse_problem, RocDocAllocator, DEFAULT_PALETTE};
+use similar::TextDiff;
 
 fn flatten_directories(files: std::vec::Vec<PathBuf>) -> std::vec::Vec<PathBuf> {
     let mut to_flatten = files;
@@ -59,99 +64,281 @@ fn is_roc_file(path: &Path) -> bool {
     return matches!(ext, Some("roc"));
 }
 
+/// Why a source file could not be formatted
+enum FormatProblem<'a> {
+    /// The source has a syntax error
+    ParsingFailed(SyntaxError<'a>),
+    /// A formatter bug: the formatted code does not parse
+    InvalidResult {
+        formatted: String,
+        problem: SyntaxError<'a>,
+    },
+    /// A formatter bug: the formatted code parses to a different tree
+    ChangedAst {
+        formatted: String,
+        ast_before: String,
+        ast_after: String,
+    },
+    /// A formatter bug: formatting the formatted code changes it again
+    Unstable {
+        formatted: String,
+        reformatted: String,
+    },
+}
+
 pub fn format(files: std::vec::Vec<PathBuf>, mode: FormatMode) -> Result<(), String> {
     let files = flatten_directories(files);
 
+    let mut unformatted_files = vec![];
+    let mut failed_files = vec![];
+
     for file in files {
         let arena = Bump::new();
 
-        let src = std::fs::read_to_string(&file).unwrap();
+        let src = match std::fs::read_to_string(&file) {
+            Ok(src) => src,
+            Err(error) => {
+                eprintln!("I could not read {}: {}\n", file.display(), error);
+                failed_files.push(file);
+                continue;
+            }
+        };
+
+        let formatted = match format_src(&arena, &src) {
+            Ok(formatted) => formatted,
+            Err(FormatProblem::ParsingFailed(problem)) => {
+                // Report it, and go on with the other files
+                eprintln!("{}", parse_problem_report(&src, &file, problem));
+                failed_files.push(file);
+                continue;
+            }
+            Err(problem) => report_formatting_bug(Some(&file), problem),
+        };
+
+        match mode {
+            FormatMode::CheckOnly => {
+                if formatted != src {
+                    print!("{}", unified_diff(&file, &src, &formatted));
+                    unformatted_files.push(file);
+                }
+            }
+
+            FormatMode::Format => {
+                // If all the checks passed, actually write out the new file.
+                if let Err(error) = std::fs::write(&file, &formatted) {
+                    eprintln!("I could not write {}: {}\n", file.display(), error);
+                    failed_files.push(file);
+                }
+            }
+        }
+    }
+
+    let mut messages = vec![];
+
+    if !unformatted_files.is_empty() {
+        messages.push(file_list(
+            "These files need to be reformatted:",
+            &unformatted_files,
+        ));
+    }
+
+    if !failed_files.is_empty() {
+        messages.push(file_list("I could not format these files:", &failed_files));
+    }
+
+    if messages.is_empty() {
+        Ok(())
+    } else {
+        Err(messages.join("\n\n"))
+    }
+}
+
+/// Formats the source code from stdin. In `FormatMode::Format`, the result goes to stdout.
+pub fn format_stdin(mode: FormatMode) -> Result<(), String> {
+    let mut src = String::new();
+
+    std::io::stdin()
+        .read_to_string(&mut src)
+        .map_err(|error| format!("I could not read from stdin: {}", error))?;
 
-        let ast = arena.alloc(parse_all(&arena, &src).unwrap_or_else(|e| {
-            user_error!("Unexpected parse failure when parsing this formatting:\n\n{:?}\n\nParse error was:\n\n{:?}\n\n", src, e)
-        }));
-        let mut buf = Buf::new_in(&arena);
-        fmt_all(&mut buf, ast);
+    let arena = Bump::new();
+    let path = Path::new("stdin");
+
+    let formatted = match format_src(&arena, &src) {
+        Ok(formatted) => formatted,
+        Err(FormatProblem::ParsingFailed(problem)) => {
+            return Err(parse_problem_report(&src, path, problem));
+        }
+        Err(problem) => report_formatting_bug(None, problem),
+    };
+
+    match mode {
+        FormatMode::CheckOnly => {
+            if formatted != src {
+                print!("{}", unified_diff(path, &src, &formatted));
+
+                return Err("The code from stdin needs to be reformatted.".to_string());
+            }
+        }
+
+        FormatMode::Format => {
+            print!("{}", formatted);
+        }
+    }
+
+    Ok(())
+}
+
+/// Formats a module, and checks that the result means the same and is stable.
+fn format_src<'a>(arena: &'a Bump, src: &'a str) -> Result<String, FormatProblem<'a>> {
+    let ast = arena.alloc(parse_all(arena, src).map_err(FormatProblem::ParsingFailed)?);
+    let mut buf = Buf::new_in(arena);
+    fmt_all(&mut buf, ast);
+
+    let formatted = arena.alloc_str(buf.as_str());
+
+    let reparsed_ast = match parse_all(arena, formatted) {
+        Ok(reparsed_ast) => arena.alloc(reparsed_ast),
+        Err(problem) => {
+            return Err(FormatProblem::InvalidResult {
+                formatted: formatted.to_string(),
+                problem,
+            });
+        }
+    };
+
+    let ast_normalized = ast.remove_spaces(arena);
+    let reparsed_ast_normalized = reparsed_ast.remove_spaces(arena);
+
+    // HACK!
+    // We compare the debug format strings of the ASTs, because I'm finding in practice that _somewhere_ deep inside the ast,
+    // the PartialEq implementation is returning `false` even when the Debug-formatted impl is exactly the same.
+    // I don't have the patience to debug this right now, so let's leave it for another day...
+    // TODO: fix PartialEq impl on ast types
+    if format!("{:?}", ast_normalized) != format!("{:?}", reparsed_ast_normalized) {
+        return Err(FormatProblem::ChangedAst {
+            formatted: formatted.to_string(),
+            ast_before: format!("{:#?}\n", ast),
+            ast_after: format!("{:#?}\n", reparsed_ast),
+        });
+    }
+
+    // Now verify that the resultant formatting is _stable_ - i.e. that it doesn't change again if re-formatted
+    let mut reformatted_buf = Buf::new_in(arena);
+    fmt_all(&mut reformatted_buf, reparsed_ast);
+    if formatted != reformatted_buf.as_str() {
+        return Err(FormatProblem::Unstable {
+            formatted: formatted.to_string(),
+            reformatted: reformatted_buf.as_str().to_string(),
+        });
+    }
+
+    Ok(formatted.to_string())
+}
+
+/// Formatting bugs are ours rather than the user's, so we stop. Unless the code came from stdin,
+/// we write the intermediate results next to the file, for debugging purposes.
+fn report_formatting_bug(file: Option<&Path>, problem: FormatProblem) -> ! {
+    let write_debug_file = |extension: &str, contents: &str| match file {
+        Some(file) => {
+            let mut debug_file = file.to_path_buf();
+            debug_file.set_extension(extension);
+            std::fs::write(&debug_file, contents).unwrap();
+
+            debug_file.display().to_string()
+        }
+        None => "(not written, since the code came from stdin)".to_string(),
+    };
+
+    match problem {
+        FormatProblem::ParsingFailed(problem) => {
+            internal_error!("Parse failures are not formatting bugs: {:?}", problem)
+        }
+        FormatProblem::InvalidResult { formatted, problem } => {
+            let fail_file = write_debug_file("roc-format-failed", &formatted);
 
-        let reparsed_ast = arena.alloc(parse_all(&arena, buf.as_str()).unwrap_or_else(|e| {
-            let mut fail_file = file.clone();
-            fail_file.set_extension("roc-format-failed");
-            std::fs::write(&fail_file, buf.as_str()).unwrap();
             internal_error!(
                 "Formatting bug; formatted code isn't valid\n\n\
                 I wrote the incorrect result to this file for debugging purposes:\n{}\n\n\
                 Parse error was: {:?}\n\n",
-                fail_file.display(),
-                e
+                fail_file,
+                problem
             );
-        }));
-
-        let ast_normalized = ast.remove_spaces(&arena);
-        let reparsed_ast_normalized = reparsed_ast.remove_spaces(&arena);
-
-        // HACK!
-        // We compare the debug format strings of the ASTs, because I'm finding in practice that _somewhere_ deep inside the ast,
-        // the PartialEq implementation is returning `false` even when the Debug-formatted impl is exactly the same.
-        // I don't have the patience to debug this right now, so let's leave it for another day...
-        // TODO: fix PartialEq impl on ast types
-        if format!("{:?}", ast_normalized) != format!("{:?}", reparsed_ast_normalized) {
-            let mut fail_file = file.clone();
-            fail_file.set_extension("roc-format-failed");
-            std::fs::write(&fail_file, buf.as_str()).unwrap();
-
-            let mut before_file = file.clone();
-            before_file.set_extension("roc-format-failed-ast-before");
-            std::fs::write(&before_file, &format!("{:#?}\n", ast)).unwrap();
-
-            let mut after_file = file.clone();
-            after_file.set_extension("roc-format-failed-ast-after");
-            std::fs::write(&after_file, &format!("{:#?}\n", reparsed_ast)).unwrap();
+        }
+        FormatProblem::ChangedAst {
+            formatted,
+            ast_before,
+            ast_after,
+        } => {
+            let fail_file = write_debug_file("roc-format-failed", &formatted);
+            let before_file = write_debug_file("roc-format-failed-ast-before", &ast_before);
+            let after_file = write_debug_file("roc-format-failed-ast-after", &ast_after);
 
             internal_error!(
                 "Formatting bug; formatting didn't reparse as the same tree\n\n\
                 I wrote the incorrect result to this file for debugging purposes:\n{}\n\n\
                 I wrote the tree before and after formatting to these files for debugging purposes:\n{}\n{}\n\n",
-                fail_file.display(),
-                before_file.display(),
-                after_file.display());
+                fail_file,
+                before_file,
+                after_file);
         }
-
-        // Now verify that the resultant formatting is _stable_ - i.e. that it doesn't change again if re-formatted
-        let mut reformatted_buf = Buf::new_in(&arena);
-        fmt_all(&mut reformatted_buf, reparsed_ast);
-        if buf.as_str() != reformatted_buf.as_str() {
-            let mut unstable_1_file = file.clone();
-            unstable_1_file.set_extension("roc-format-unstable-1");
-            std::fs::write(&unstable_1_file, buf.as_str()).unwrap();
-
-            let mut unstable_2_file = file.clone();
-            unstable_2_file.set_extension("roc-format-unstable-2");
-            std::fs::write(&unstable_2_file, reformatted_buf.as_str()).unwrap();
+        FormatProblem::Unstable {
+            formatted,
+            reformatted,
+        } => {
+            let unstable_1_file = write_debug_file("roc-format-unstable-1", &formatted);
+            let unstable_2_file = write_debug_file("roc-format-unstable-2", &reformatted);
 
             internal_error!(
                 "Formatting bug; formatting is not stable. Reformatting the formatted file changed it again.\n\n\
                 I wrote the result of formatting to this file for debugging purposes:\n{}\n\n\
                 I wrote the result of double-formatting here:\n{}\n\n",
-                unstable_1_file.display(),
-                unstable_2_file.display());
+                unstable_1_file,
+                unstable_2_file);
         }
+    }
+}
 
-        match mode {
-            FormatMode::CheckOnly => {
-                // If we notice that this file needs to be formatted, return early
-                if buf.as_str() != src {
-                    return Err("One or more files need to be reformatted.".to_string());
-                }
-            }
+fn parse_problem_report(src: &str, path: &Path, problem: SyntaxError) -> String {
+    // The module did not parse, so there are no module ids yet.
+    let mut interns = Interns::default();
+    let home = interns.module_ids.get_or_insert(&"".into());
 
-            FormatMode::Format => {
-                // If all the checks above passed, actually write out the new file.
-                std::fs::write(&file, buf.as_str()).unwrap();
-            }
-        }
+    let src_lines: Vec<&str> = src.split('\n').collect();
+    let line_info = LineInfo::new(src);
+    let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+
+    let problem = SourceError {
+        problem,
+        bytes: src.as_bytes(),
     }
+    .into_file_error(path.to_path_buf());
+    let report = parse_problem(&alloc, &line_info, path.to_path_buf(), 0, problem);
 
-    Ok(())
+    let mut buf = String::new();
+    report.render_color_terminal(&mut buf, &alloc, &DEFAULT_PALETTE);
+
+    buf
+}
+
+/// A unified diff from the source to its formatted version, like `diff -u` prints.
+fn unified_diff(path: &Path, src: &str, formatted: &str) -> String {
+    let name = path.display().to_string();
+
+    TextDiff::from_lines(src, formatted)
+        .unified_diff()
+        .header(&name, &name)
+        .to_string()
+}
+
+fn file_list(heading: &str, files: &[PathBuf]) -> String {
+    let mut message = heading.to_string();
+
+    for file in files {
+        message.push_str(&format!("\n    {}", file.display()));
+    }
+
+    message
 }
 
 fn parse_all<'a>(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, SyntaxError<'a>> {
@@ -4,16 +4,24 @@ extern crate const_format;
 use build::BuiltFile;
 use bumpalo::Bump;
 use clap::{Arg, ArgMatches, Command, ValueSource};
+use coverage::{CoverageCount, FileCoverage};
 use roc_build::link::{LinkType, LinkingStrategy};
-use roc_collections::VecMap;
+use roc_collections::{MutMap, VecMap};
 use roc_error_macros::{internal_error, user_error};
 use roc_gen_llvm::llvm::build::LlvmBackendMode;
 use roc_gen_llvm::run_roc::RocCallResult;
 use roc_gen_llvm::run_roc_dylib;
-use roc_load::{ExecutionMode, Expectations, LoadConfig, LoadingProblem, Threading};
-use roc_module::symbol::{Interns, ModuleId};
+use roc_load::{
+    default_cache_dir, ExecutionMode, Expectations, LoadConfig, LoadingProblem, PropertyTestConfig,
+    TestConfig, Threading,
+};
+use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::ir::OptLevel;
-use roc_repl_expect::run::{expect_mono_module_to_dylib, roc_dev_expect};
+use roc_region::all::{LineInfo, Region};
+use roc_repl_expect::run::{
+    expect_mono_module_to_dylib, read_coverage_counters, roc_dev_expect, ExpectOutcome,
+};
+use roc_reporting::report::RenderTarget;
 use roc_target::TargetInfo;
 use std::env;
 use std::ffi::{CString, OsStr};
@@ -21,17 +29,21 @@ use std::io;
 use std::os::raw::{c_char, c_int};
 use std::path::{Path, PathBuf};
 use std::process;
-use std::time::Instant;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use target_lexicon::BinaryFormat;
 use target_lexicon::{
     Architecture, Environment, OperatingSystem, Triple, Vendor, X86_32Architecture,
 };
 #[cfg(not(target_os = "linux"))]
 use tempfile::TempDir;
+use test_report::{ExpectFilter, ExpectLocation, TestFormat};
 
 pub mod build;
+mod coverage;
 mod format;
-pub use format::format;
+mod test_report;
+pub mod watch;
+pub use format::{format, format_stdin};
 
 const DEFAULT_ROC_FILENAME: &str = "main.roc";
 
@@ -58,7 +70,17 @@ pub const FLAG_TIME: &str = "time";
 pub const FLAG_LINKER: &str = "linker";
 pub const FLAG_PRECOMPILED: &str = "precompiled-host";
 pub const FLAG_CHECK: &str = "check";
+pub const FLAG_NO_CACHE: &str = "no-cache";
+pub const FLAG_WATCH: &str = "watch";
+pub const FLAG_REPORT_FORMAT: &str = "report-format";
 pub const FLAG_WASM_STACK_SIZE_KB: &str = "wasm-stack-size-kb";
+pub const FLAG_LANG: &str = "lang";
+pub const FLAG_SEED: &str = "seed";
+pub const FLAG_ITERATIONS: &str = "iterations";
+pub const FLAG_FILTER: &str = "filter";
+pub const FLAG_STDIN: &str = "stdin";
+pub const FLAG_FORMAT: &str = "format";
+pub const FLAG_COVERAGE: &str = "coverage";
 pub const ROC_FILE: &str = "ROC_FILE";
 pub const ROC_DIR: &str = "ROC_DIR";
 pub const GLUE_FILE: &str = "GLUE_FILE";
@@ -92,7 +114,7 @@ pub fn build_app<'a>() -> Command<'a> {
 
     let flag_debug = Arg::new(FLAG_DEBUG)
         .long(FLAG_DEBUG)
-        .help("Store LLVM debug information in the generated program.")
+        .help("Store debug information in the generated program, so debuggers and profilers can show Roc source locations. Uses DWARF for native targets, and a name section and source map for WebAssembly. Not supported by the --dev backend for native targets yet. Implies --linker=legacy, because the surgical linker does not keep debug information yet.")
         .required(false);
 
     let flag_time = Arg::new(FLAG_TIME)
@@ -102,7 +124,7 @@ pub fn build_app<'a>() -> Command<'a> {
 
     let flag_linker = Arg::new(FLAG_LINKER)
         .long(FLAG_LINKER)
-        .help("Sets which linker to use. The surgical linker is enabled by default only when building for wasm32 or x86_64 Linux, because those are the only targets it currently supports. Otherwise the legacy linker is used by default.")
+        .help("Sets which linker to use. The surgical linker is enabled by default only when building for wasm32 or x86_64 Linux, because those are the only targets it currently supports. Otherwise, or with --debug, the legacy linker is used by default.")
         .possible_values(["surgical", "legacy"])
         .required(false);
 
@@ -112,6 +134,23 @@ pub fn build_app<'a>() -> Command<'a> {
         .possible_values(["true", "false"])
         .required(false);
 
+    let flag_no_cache = Arg::new(FLAG_NO_CACHE)
+        .long(FLAG_NO_CACHE)
+        .help("Type-check every module again, instead of reusing the types of modules that did not change since the last build.")
+        .required(false);
+
+    let flag_watch = Arg::new(FLAG_WATCH)
+        .long(FLAG_WATCH)
+        .help("Keep running, and run again whenever one of the loaded .roc files changes.")
+        .required(false);
+
+    let flag_report_format = Arg::new(FLAG_REPORT_FORMAT)
+        .long(FLAG_REPORT_FORMAT)
+        .help("How to report problems and failed expectations: as text for people to read, or as JSON, one object per line, for tools to consume.")
+        .takes_value(true)
+        .possible_values(["human", "json"])
+        .required(false);
+
     let flag_wasm_stack_size_kb = Arg::new(FLAG_WASM_STACK_SIZE_KB)
         .long(FLAG_WASM_STACK_SIZE_KB)
         .help("Stack size in kilobytes for wasm32 target. Only applies when --dev also provided.")
@@ -146,6 +185,8 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_precompiled.clone())
+            .arg(flag_no_cache.clone())
+            .arg(flag_report_format.clone())
             .arg(flag_wasm_stack_size_kb.clone())
             .arg(
                 Arg::new(FLAG_TARGET)
@@ -185,9 +226,58 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_precompiled.clone())
+            .arg(flag_no_cache.clone())
+            .arg(flag_report_format.clone())
+            .arg(flag_watch.clone())
+            .arg(
+                Arg::new(FLAG_SEED)
+                    .long(FLAG_SEED)
+                    .help("The seed for generating the inputs of property tests (`expect`s of a function). The same seed always generates the same inputs. Defaults to a random seed, which is printed when a test fails.")
+                    .takes_value(true)
+                    .validator(|s| s.parse::<u64>())
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_ITERATIONS)
+                    .long(FLAG_ITERATIONS)
+                    .help("How many inputs to generate for each property test.")
+                    .takes_value(true)
+                    .validator(|s| s.parse::<u32>())
+                    .default_value("100")
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_FILTER)
+                    .long(FLAG_FILTER)
+                    .help("Only run the `expect`s of modules whose name contains the given text. Add a line, like `Parser:12`, or a range of lines, like `Parser:10-20`, to only run the `expect`s on those lines. Can be given more than once, to run the `expect`s matching any of the filters.")
+                    .takes_value(true)
+                    .multiple_occurrences(true)
+                    .validator(ExpectFilter::parse)
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_FORMAT)
+                    .long(FLAG_FORMAT)
+                    .help("How to report the results: as text for people to read, as JUnit XML, in the Test Anything Protocol, or as JSON, one object per `expect` on a line of its own. The results of every `expect` that ran are listed with their module, region and duration, and the details of failures.")
+                    .takes_value(true)
+                    .possible_values(TestFormat::OPTIONS)
+                    .default_value("human")
+                    .required(false)
+            )
+            .arg(
+                Arg::new(FLAG_COVERAGE)
+                    .long(FLAG_COVERAGE)
+                    .help("Count how often each function and branch runs, then summarize which share of each module's lines and functions the `expect`s ran, and write the counts to an lcov file. The file is `lcov.info` unless another is given, like `--coverage=build/lcov.info`. Functions that no `expect` uses aren't compiled, so they aren't part of the report.")
+                    .takes_value(true)
+                    .min_values(0)
+                    .require_equals(true)
+                    .default_missing_value("lcov.info")
+                    .allow_invalid_utf8(true)
+                    .required(false)
+            )
             .arg(
                 Arg::new(ROC_FILE)
-                    .help("The .roc file for the main module")
+                    .help("The .roc file for the main module, or an interface module to test on its own")
                     .allow_invalid_utf8(true)
                     .required(false)
                     .default_value(DEFAULT_ROC_FILENAME)
@@ -207,6 +297,8 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(flag_time.clone())
             .arg(flag_linker.clone())
             .arg(flag_precompiled.clone())
+            .arg(flag_no_cache.clone())
+            .arg(flag_report_format.clone())
             .arg(roc_file_to_run.clone())
             .arg(args_for_app.clone())
         )
@@ -221,7 +313,14 @@ pub fn build_app<'a>() -> Command<'a> {
             .arg(
                 Arg::new(FLAG_CHECK)
                     .long(FLAG_CHECK)
-                    .help("Checks that specified files are formatted. If formatting is needed, it will return a non-zero exit code.")
+                    .help("Checks that specified files are formatted. If formatting is needed, it will print a diff and return a non-zero exit code.")
+                    .required(false),
+            )
+            .arg(
+                Arg::new(FLAG_STDIN)
+                    .long(FLAG_STDIN)
+                    .help("Format the code from stdin, and print the result to stdout")
+                    .conflicts_with(DIRECTORY_OR_FILES)
                     .required(false),
             )
         )
@@ -231,6 +330,9 @@ pub fn build_app<'a>() -> Command<'a> {
             .about("Check the code for problems, but doesn’t build or run it")
             .arg(flag_time.clone())
             .arg(flag_max_threads.clone())
+            .arg(flag_no_cache.clone())
+            .arg(flag_report_format.clone())
+            .arg(flag_watch)
             .arg(
                 Arg::new(ROC_FILE)
                     .help("The .roc file of an app to check")
@@ -245,7 +347,7 @@ pub fn build_app<'a>() -> Command<'a> {
                 .arg(Arg::new(DIRECTORY_OR_FILES)
                     .multiple_values(true)
                     .required(false)
-                    .help("The directory or files to build documentation for")
+                    .help("The directory or files to build documentation for. A platform's main.roc, or a directory that has one, is documented as a whole package, along with the packages it depends on.")
                     .allow_invalid_utf8(true)
                 )
         )
@@ -259,10 +361,18 @@ pub fn build_app<'a>() -> Command<'a> {
             )
             .arg(
                 Arg::new(GLUE_FILE)
-                    .help("The filename for the generated glue code. Currently, this must be a .rs file because only Rust glue generation is supported so far.")
+                    .help("The filename for the generated glue code. Unless --lang is given, the language is chosen by its extension: .rs for Rust, or .h for C.")
                     .allow_invalid_utf8(true)
                     .required(true)
             )
+            .arg(
+                Arg::new(FLAG_LANG)
+                    .long(FLAG_LANG)
+                    .help("Which language to generate glue code for, regardless of the glue file's extension")
+                    .takes_value(true)
+                    .possible_values(roc_glue::Lang::OPTIONS)
+                    .required(false)
+            )
         )
         .trailing_var_arg(true)
         .arg(flag_optimize)
@@ -273,6 +383,8 @@ pub fn build_app<'a>() -> Command<'a> {
         .arg(flag_time)
         .arg(flag_linker)
         .arg(flag_precompiled)
+        .arg(flag_no_cache)
+        .arg(flag_report_format)
         .arg(roc_file_to_run.required(false))
         .arg(args_for_app);
 
@@ -292,6 +404,26 @@ pub fn build_app<'a>() -> Command<'a> {
     }
 }
 
+/// How problems should be reported, as chosen with `--report-format`.
+pub fn render_target(matches: &ArgMatches) -> RenderTarget {
+    match matches.value_of(FLAG_REPORT_FORMAT) {
+        Some("json") => RenderTarget::Json,
+        _ => RenderTarget::ColorTerminal,
+    }
+}
+
+/// Prints a message that is meant for people rather than tools. When reporting problems as JSON,
+/// it goes to stderr without colors, so that stdout only contains the reports.
+pub fn report_status(render: RenderTarget, message: &str) {
+    match render {
+        RenderTarget::Json => {
+            let message = strip_ansi_escapes::strip(message).unwrap();
+            eprintln!("{}", String::from_utf8_lossy(&message))
+        }
+        RenderTarget::ColorTerminal | RenderTarget::Generic => println!("{}", message),
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum BuildConfig {
     BuildOnly,
@@ -307,8 +439,6 @@ pub enum FormatMode {
 const SHM_SIZE: i64 = 1024;
 
 pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
-    let start_time = Instant::now();
-    let arena = Bump::new();
     let filename = matches.value_of_os(ROC_FILE).unwrap();
     let opt_level = match (
         matches.is_present(FLAG_OPTIMIZE),
@@ -352,9 +482,78 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
         process::exit(1);
     }
 
+    let cache_dir = if matches.is_present(FLAG_NO_CACHE) {
+        None
+    } else {
+        default_cache_dir()
+    };
+
+    let render = render_target(matches);
+
+    let property_test_config = PropertyTestConfig {
+        seed: match matches.value_of(FLAG_SEED) {
+            Some(seed) => seed.parse().unwrap(),
+            None => random_seed(),
+        },
+        iterations: matches.value_of(FLAG_ITERATIONS).unwrap().parse().unwrap(),
+    };
+
+    let filters: Vec<ExpectFilter> = match matches.values_of(FLAG_FILTER) {
+        Some(filters) => filters
+            .map(|filter| ExpectFilter::parse(filter).unwrap())
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let format = match matches.value_of(FLAG_FORMAT) {
+        Some("junit") => TestFormat::Junit,
+        Some("tap") => TestFormat::Tap,
+        Some("json") => TestFormat::Json,
+        _ => TestFormat::Human,
+    };
+
+    let coverage_file = matches.value_of_os(FLAG_COVERAGE).map(Path::new);
+
+    let test_once = || {
+        test_file(
+            path,
+            &triple,
+            opt_level,
+            threading,
+            render,
+            cache_dir.clone(),
+            property_test_config,
+            &filters,
+            format,
+            coverage_file,
+        )
+    };
+
+    if matches.is_present(FLAG_WATCH) {
+        watch::watch(path, || test_once().1)
+    } else {
+        Ok(test_once().0)
+    }
+}
+
+/// Runs the expects of a file that match any of the filters (or all of them, if there are no
+/// filters), returning the exit code and the files of the modules that were loaded.
+#[allow(clippy::too_many_arguments)]
+fn test_file(
+    path: &Path,
+    target: &Triple,
+    opt_level: OptLevel,
+    threading: Threading,
+    render: RenderTarget,
+    cache_dir: Option<PathBuf>,
+    property_test_config: PropertyTestConfig,
+    filters: &[ExpectFilter],
+    format: TestFormat,
+    coverage_file: Option<&Path>,
+) -> (i32, Option<Vec<PathBuf>>) {
+    let start_time = Instant::now();
+    let arena = Bump::new();
     let arena = &arena;
-    let target = &triple;
-    let opt_level = opt_level;
     let target_info = TargetInfo::from(target);
 
     // Step 1: compile the app and generate the .o file
@@ -362,22 +561,80 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
 
     let load_config = LoadConfig {
         target_info,
-        // TODO: expose this from CLI?
-        render: roc_reporting::report::RenderTarget::ColorTerminal,
+        render,
         threading,
-        exec_mode: ExecutionMode::Test,
+        exec_mode: ExecutionMode::Test(TestConfig {
+            property_tests: property_test_config,
+            coverage: coverage_file.is_some(),
+        }),
+        cache_dir,
     };
-    let loaded =
-        roc_load::load_and_monomorphize(arena, path.to_path_buf(), subs_by_module, load_config)
-            .unwrap();
+    let mut loaded = match roc_load::load_and_monomorphize(
+        arena,
+        path.to_path_buf(),
+        subs_by_module,
+        load_config,
+    ) {
+        Ok(loaded) => loaded,
+        Err(LoadingProblem::FormattedReport(report)) => {
+            print!("{}", report);
 
-    let mut loaded = loaded;
+            return (1, None);
+        }
+        Err(other) => {
+            // With --watch, this is reported like any other problem, and the tests run again after the next change.
+            eprintln!("Loading the tests failed with error:\n{:?}", other);
+
+            return (1, None);
+        }
+    };
+
+    roc_build::program::report_problems_monomorphized(&mut loaded, render);
+
+    let module_paths = watch::module_paths(&loaded.sources);
     let mut expectations = std::mem::take(&mut loaded.expectations);
+    let property_expects = std::mem::take(&mut loaded.property_expects);
     let loaded = loaded;
 
     let interns = loaded.interns.clone();
 
-    let (lib, expects) = expect_mono_module_to_dylib(
+    let line_infos: MutMap<ModuleId, (PathBuf, LineInfo)> = loaded
+        .sources
+        .iter()
+        .map(|(module_id, (path, source))| (*module_id, (path.clone(), LineInfo::new(source))))
+        .collect();
+    let locate = |symbol: Symbol, region: Region| {
+        let module_id = symbol.module_id();
+        let (path, line_info) = &line_infos[&module_id];
+
+        ExpectLocation {
+            module: interns.module_name(module_id).as_str().to_string(),
+            path: path.clone(),
+            region: line_info.convert_region(region),
+        }
+    };
+
+    let (coverage_points, coverage_functions) = if coverage_file.is_some() {
+        let mut expect_regions: MutMap<ModuleId, Vec<Region>> = MutMap::default();
+        for (symbol, region) in loaded.toplevel_expects.iter() {
+            let regions = expect_regions.entry(symbol.module_id()).or_default();
+            regions.push(*region);
+        }
+
+        let (mut points, functions) = roc_mono::ir::coverage_points(loaded.procedures.values());
+
+        // the code of the expects themselves isn't code under test
+        points.retain(|point| match expect_regions.get(&point.module_id) {
+            Some(regions) => !coverage::is_within(point.region, regions),
+            None => true,
+        });
+
+        (points, functions)
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let (lib, mut expects) = expect_mono_module_to_dylib(
         arena,
         target.clone(),
         loaded,
@@ -386,10 +643,33 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     )
     .unwrap();
 
-    let arena = &bumpalo::Bump::new();
-    let interns = arena.alloc(interns);
+    if !filters.is_empty() {
+        expects.retain(|expect| {
+            let location = locate(expect.symbol, expect.region);
 
-    let mut writer = std::io::stdout();
+            filters.iter().any(|filter| filter.matches(&location))
+        });
+    }
+
+    // In the machine-readable formats, the results are only reported once all expects ran,
+    // with their failures rendered without colors.
+    let (mut writer, expect_render): (Box<dyn io::Write>, _) = match format {
+        TestFormat::Human => (Box::new(io::stdout()), render),
+        TestFormat::Junit | TestFormat::Tap | TestFormat::Json => {
+            (Box::new(io::sink()), RenderTarget::Generic)
+        }
+    };
+    let status = |message: &str| match format {
+        TestFormat::Human => report_status(render, message),
+        TestFormat::Junit | TestFormat::Tap | TestFormat::Json => {
+            // stderr may well be captured into the same CI log, which has no use for colors
+            let message = strip_ansi_escapes::strip(message).unwrap();
+            eprintln!("{}", String::from_utf8_lossy(&message))
+        }
+    };
+
+    let arena = &bumpalo::Bump::new();
+    let interns_ref = arena.alloc(interns.clone());
 
     let mut shared_buffer = vec![0u8; SHM_SIZE as usize];
 
@@ -398,11 +678,11 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
     let slice = (shared_buffer.as_mut_ptr(), shared_buffer.len());
     unsafe { set_shared_buffer(slice, &mut result) };
 
-    let (failed, passed) = roc_repl_expect::run::run_expects(
+    let results = roc_repl_expect::run::run_expects(
         &mut writer,
-        roc_reporting::report::RenderTarget::ColorTerminal,
+        expect_render,
         arena,
-        interns,
+        interns_ref,
         &lib,
         &mut expectations,
         shared_buffer.as_mut_ptr(),
@@ -412,16 +692,95 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
 
     let total_time = start_time.elapsed();
 
+    let failed = results
+        .iter()
+        .filter(|result| result.outcome != ExpectOutcome::Passed)
+        .count();
+    let passed = results.len() - failed;
+
+    let results: Vec<_> = results
+        .into_iter()
+        .map(|result| (locate(result.symbol, result.region), result))
+        .collect();
+    test_report::write_results(&mut io::stdout(), format, &results).unwrap();
+
+    if let Some(coverage_file) = coverage_file {
+        let counts = read_coverage_counters(&lib, &coverage_points);
+
+        let mut counts_by_module: MutMap<ModuleId, Vec<CoverageCount>> = MutMap::default();
+        for (point, count) in coverage_points.iter().zip(counts) {
+            let (_, line_info) = &line_infos[&point.module_id];
+            let region = line_info.convert_region(point.region);
+
+            let function = coverage_functions
+                .iter()
+                .find(|(function, _)| function == point)
+                .map(|(_, symbol)| {
+                    let name = symbol.as_str(&interns);
+
+                    // anonymous functions have generated names, which are numbers
+                    if name.starts_with(|c: char| c.is_ascii_digit()) {
+                        format!("<lambda>:{}", region.start.line + 1)
+                    } else {
+                        name.to_string()
+                    }
+                });
+
+            counts_by_module
+                .entry(point.module_id)
+                .or_default()
+                .push(CoverageCount {
+                    region,
+                    count,
+                    function,
+                });
+        }
+
+        let mut files: Vec<FileCoverage> = counts_by_module
+            .into_iter()
+            .map(|(module_id, counts)| {
+                let (path, _) = &line_infos[&module_id];
+                let module = interns.module_name(module_id).as_str().to_string();
+
+                FileCoverage::new(module, path.clone(), counts)
+            })
+            .collect();
+        files.sort_by(|a, b| a.module.cmp(&b.module));
+
+        let write_lcov = std::fs::File::create(coverage_file)
+            .and_then(|mut file| coverage::write_lcov(&mut file, &files));
+
+        let mut summary = Vec::new();
+        coverage::write_summary(&mut summary, &files).unwrap();
+        status(String::from_utf8_lossy(&summary).trim_end());
+
+        match write_lcov {
+            Ok(()) => status(&format!(
+                "Wrote the coverage report to {}",
+                coverage_file.display()
+            )),
+            Err(error) => status(&format!(
+                "Could not write the coverage report to {}: {}",
+                coverage_file.display(),
+                error
+            )),
+        }
+    }
+
     if failed == 0 && passed == 0 {
         // TODO print this in a more nicely formatted way!
-        println!("No expectations were found.");
+        if filters.is_empty() {
+            status("No expectations were found.");
+        } else {
+            status("No expectations matched the filters.");
+        }
 
         // If no tests ran, treat that as an error. This is perhaps
         // briefly annoying at the very beginning of a project when
         // you actually have zero tests, but it can save you from
         // having a change to your CI script accidentally stop
         // running tests altogether!
-        Ok(2)
+        (2, Some(module_paths))
     } else {
         let failed_color = if failed == 0 {
             32 // green
@@ -429,15 +788,35 @@ pub fn test(matches: &ArgMatches, triple: Triple) -> io::Result<i32> {
             31 // red
         };
 
-        println!(
+        status(&format!(
             "\n\x1B[{failed_color}m{failed}\x1B[39m failed and \x1B[32m{passed}\x1B[39m passed in {} ms.\n",
             total_time.as_millis(),
-        );
+        ));
+
+        let failed_property_test = results.iter().any(|(_, result)| {
+            result.outcome != ExpectOutcome::Passed && property_expects.contains(&result.symbol)
+        });
 
-        Ok((failed > 0) as i32)
+        if failed_property_test {
+            status(&format!(
+                "Property tests used seed {0}; run with `--seed {0}` to generate the same inputs again.\n",
+                property_test_config.seed,
+            ));
+        }
+
+        ((failed > 0) as i32, Some(module_paths))
     }
 }
 
+/// A seed for property tests that is different every run, unless one was given explicitly.
+fn random_seed() -> u64 {
+    let since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    since_epoch.as_nanos() as u64 ^ process::id() as u64
+}
+
 pub fn build(
     matches: &ArgMatches,
     config: BuildConfig,
@@ -462,6 +841,12 @@ pub fn build(
     };
     let emit_debug_info = matches.is_present(FLAG_DEBUG);
     let emit_timings = matches.is_present(FLAG_TIME);
+    let render = render_target(matches);
+    let cache_dir = if matches.is_present(FLAG_NO_CACHE) {
+        None
+    } else {
+        default_cache_dir()
+    };
 
     let threading = match matches
         .value_of(FLAG_MAX_THREADS)
@@ -476,10 +861,16 @@ pub fn build(
     let wasm_dev_backend = matches!(opt_level, OptLevel::Development)
         && matches!(triple.architecture, Architecture::Wasm32);
 
+    // The surgical linker does not preserve the debug sections of the app yet.
+    if emit_debug_info && !wasm_dev_backend && matches.value_of(FLAG_LINKER) == Some("surgical") {
+        user_error!("`--debug` cannot be used with the surgical linker yet; use `--linker=legacy`, or leave out `--linker` to pick it automatically")
+    }
+
     let linking_strategy = if wasm_dev_backend {
         LinkingStrategy::Additive
     } else if !roc_linker::supported(link_type, &triple)
         || matches.value_of(FLAG_LINKER) == Some("legacy")
+        || emit_debug_info
     {
         LinkingStrategy::Legacy
     } else {
@@ -532,7 +923,9 @@ pub fn build(
         linking_strategy,
         precompiled,
         threading,
+        render,
         wasm_dev_stack_bytes,
+        cache_dir,
     );
 
     match res_binary_path {
@@ -554,41 +947,10 @@ pub fn build(
                     // since the process is about to exit anyway.
                     std::mem::forget(arena);
 
-                    println!(
-                        "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms while successfully building:\n\n    {}",
-                        if problems.errors == 0 {
-                            32 // green
-                        } else {
-                            33 // yellow
-                        },
-                        problems.errors,
-                        if problems.errors == 1 {
-                            "error"
-                        } else {
-                            "errors"
-                        },
-                        if problems.warnings == 0 {
-                            32 // green
-                        } else {
-                            33 // yellow
-                        },
-                        problems.warnings,
-                        if problems.warnings == 1 {
-                            "warning"
-                        } else {
-                            "warnings"
-                        },
-                        total_time.as_millis(),
-                        generated_filename.to_str().unwrap()
-                    );
-
-                    // Return a nonzero exit code if there were problems
-                    Ok(problems.exit_code())
-                }
-                BuildAndRun => {
-                    if problems.errors > 0 || problems.warnings > 0 {
-                        println!(
-                            "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.\n\nRunning program anyway…\n\n\x1B[36m{}\x1B[39m",
+                    report_status(
+                        render,
+                        &format!(
+                            "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms while successfully building:\n\n    {}",
                             if problems.errors == 0 {
                                 32 // green
                             } else {
@@ -612,7 +974,44 @@ pub fn build(
                                 "warnings"
                             },
                             total_time.as_millis(),
-                            "─".repeat(80)
+                            generated_filename.to_str().unwrap()
+                        ),
+                    );
+
+                    // Return a nonzero exit code if there were problems
+                    Ok(problems.exit_code())
+                }
+                BuildAndRun => {
+                    if problems.errors > 0 || problems.warnings > 0 {
+                        report_status(
+                            render,
+                            &format!(
+                                "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.\n\nRunning program anyway…\n\n\x1B[36m{}\x1B[39m",
+                                if problems.errors == 0 {
+                                    32 // green
+                                } else {
+                                    33 // yellow
+                                },
+                                problems.errors,
+                                if problems.errors == 1 {
+                                    "error"
+                                } else {
+                                    "errors"
+                                },
+                                if problems.warnings == 0 {
+                                    32 // green
+                                } else {
+                                    33 // yellow
+                                },
+                                problems.warnings,
+                                if problems.warnings == 1 {
+                                    "warning"
+                                } else {
+                                    "warnings"
+                                },
+                                total_time.as_millis(),
+                                "─".repeat(80)
+                            ),
                         );
                     }
 
@@ -628,6 +1027,7 @@ pub fn build(
                         &mut bytes,
                         expectations,
                         interns,
+                        render,
                     );
                     std::mem::forget(bytes);
                     x
@@ -635,16 +1035,19 @@ pub fn build(
                 BuildAndRunIfNoErrors => {
                     if problems.errors == 0 {
                         if problems.warnings > 0 {
-                            println!(
-                                "\x1B[32m0\x1B[39m errors and \x1B[33m{}\x1B[39m {} found in {} ms.\n\nRunning program…\n\n\x1B[36m{}\x1B[39m",
-                                problems.warnings,
-                                if problems.warnings == 1 {
-                                    "warning"
-                                } else {
-                                    "warnings"
-                                },
-                                total_time.as_millis(),
-                                "─".repeat(80)
+                            report_status(
+                                render,
+                                &format!(
+                                    "\x1B[32m0\x1B[39m errors and \x1B[33m{}\x1B[39m {} found in {} ms.\n\nRunning program…\n\n\x1B[36m{}\x1B[39m",
+                                    problems.warnings,
+                                    if problems.warnings == 1 {
+                                        "warning"
+                                    } else {
+                                        "warnings"
+                                    },
+                                    total_time.as_millis(),
+                                    "─".repeat(80)
+                                ),
                             );
                         }
 
@@ -660,6 +1063,7 @@ pub fn build(
                             &mut bytes,
                             expectations,
                             interns,
+                            render,
                         );
                         std::mem::forget(bytes);
                         x
@@ -697,7 +1101,7 @@ pub fn build(
                             output.push_str(&filename.to_string_lossy());
                         }
 
-                        println!("{}\x1B[39m", output);
+                        report_status(render, &format!("{}\x1B[39m", output));
 
                         Ok(problems.exit_code())
                     }
@@ -723,6 +1127,7 @@ fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
     binary_bytes: &mut [u8],
     expectations: VecMap<ModuleId, Expectations>,
     interns: Interns,
+    render: RenderTarget,
 ) -> io::Result<i32> {
     match triple.architecture {
         Architecture::Wasm32 => {
@@ -761,7 +1166,15 @@ fn roc_run<'a, I: IntoIterator<Item = &'a OsStr>>(
 
             Ok(0)
         }
-        _ => roc_run_native(arena, opt_level, args, binary_bytes, expectations, interns),
+        _ => roc_run_native(
+            arena,
+            opt_level,
+            args,
+            binary_bytes,
+            expectations,
+            interns,
+            render,
+        ),
     }
 }
 
@@ -816,6 +1229,7 @@ fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
     binary_bytes: &mut [u8],
     expectations: VecMap<ModuleId, Expectations>,
     interns: Interns,
+    render: RenderTarget,
 ) -> std::io::Result<i32> {
     use bumpalo::collections::CollectIn;
 
@@ -837,7 +1251,7 @@ fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
 
         match opt_level {
             OptLevel::Development => {
-                roc_run_native_debug(executable, &argv, &envp, expectations, interns)
+                roc_run_native_debug(executable, &argv, &envp, expectations, interns, render)
             }
             OptLevel::Normal | OptLevel::Size | OptLevel::Optimize => {
                 roc_run_native_fast(executable, &argv, &envp);
@@ -927,6 +1341,7 @@ unsafe fn roc_run_native_debug(
     envp: &[*const c_char],
     mut expectations: VecMap<ModuleId, Expectations>,
     interns: Interns,
+    render: RenderTarget,
 ) {
     use signal_hook::{consts::signal::SIGCHLD, consts::signal::SIGUSR1, iterator::Signals};
 
@@ -997,6 +1412,7 @@ unsafe fn roc_run_native_debug(
 
                         roc_dev_expect(
                             &mut std::io::stdout(),
+                            render,
                             arena,
                             &mut expectations,
                             interns,
@@ -1094,6 +1510,7 @@ fn roc_run_native<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(
     binary_bytes: &mut [u8],
     _expectations: VecMap<ModuleId, Expectations>,
     _interns: Interns,
+    _render: RenderTarget,
 ) -> io::Result<i32> {
     use bumpalo::collections::CollectIn;
 
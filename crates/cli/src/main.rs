@@ -1,13 +1,16 @@
 use roc_build::link::LinkType;
-use roc_cli::build::check_file;
+use roc_cli::build::{check_file, CheckedFile};
+use roc_cli::watch::watch;
 use roc_cli::{
-    build_app, format, test, BuildConfig, FormatMode, Target, CMD_BUILD, CMD_CHECK, CMD_DOCS,
-    CMD_EDIT, CMD_FORMAT, CMD_GLUE, CMD_REPL, CMD_RUN, CMD_TEST, CMD_VERSION, DIRECTORY_OR_FILES,
-    FLAG_CHECK, FLAG_LIB, FLAG_NO_LINK, FLAG_TARGET, FLAG_TIME, GLUE_FILE, ROC_FILE,
+    build_app, format, format_stdin, render_target, report_status, test, BuildConfig, FormatMode,
+    Target, CMD_BUILD, CMD_CHECK, CMD_DOCS, CMD_EDIT, CMD_FORMAT, CMD_GLUE, CMD_REPL, CMD_RUN,
+    CMD_TEST, CMD_VERSION, DIRECTORY_OR_FILES, FLAG_CHECK, FLAG_LANG, FLAG_LIB, FLAG_NO_CACHE,
+    FLAG_NO_LINK, FLAG_STDIN, FLAG_TARGET, FLAG_TIME, FLAG_WATCH, GLUE_FILE, ROC_FILE,
 };
-use roc_docs::generate_docs_html;
+use roc_docs::{generate_docs_html, generate_package_docs_html, read_package_root};
 use roc_error_macros::user_error;
-use roc_load::{LoadingProblem, Threading};
+use roc_load::{default_cache_dir, LoadingProblem, Threading};
+use roc_reporting::report::RenderTarget;
 use std::fs::{self, FileType};
 use std::io;
 use std::path::{Path, PathBuf};
@@ -68,12 +71,21 @@ fn main() -> io::Result<()> {
             let input_path = Path::new(matches.value_of_os(ROC_FILE).unwrap());
             let output_path = Path::new(matches.value_of_os(GLUE_FILE).unwrap());
 
-            if Some("rs") == output_path.extension().and_then(OsStr::to_str) {
-                roc_glue::generate(input_path, output_path)
-            } else {
-                eprintln!("Currently, `roc glue` only supports generating Rust glue files (with the .rs extension). In the future, the plan is to decouple `roc glue` from any particular output format, by having it accept a second .roc file which gets executed as a plugin to generate glue code for any desired language. However, this has not yet been implemented, and for now only .rs is supported.");
+            let lang = match matches.value_of(FLAG_LANG) {
+                Some(lang) => lang.parse().ok(),
+                None => output_path
+                    .extension()
+                    .and_then(OsStr::to_str)
+                    .and_then(roc_glue::Lang::from_extension),
+            };
 
-                Ok(1)
+            match lang {
+                Some(lang) => roc_glue::generate(input_path, output_path, lang),
+                None => {
+                    eprintln!("`roc glue` could not tell which language to generate glue code for from the extension of {}. Either use a .rs file for Rust or a .h file for C, or pass --lang with one of: {}", output_path.display(), roc_glue::Lang::OPTIONS.join(", "));
+
+                    Ok(1)
+                }
             }
         }
         Some((CMD_BUILD, matches)) => {
@@ -97,8 +109,6 @@ fn main() -> io::Result<()> {
             )?)
         }
         Some((CMD_CHECK, matches)) => {
-            let arena = bumpalo::Bump::new();
-
             let emit_timings = matches.is_present(FLAG_TIME);
             let filename = matches.value_of_os(ROC_FILE).unwrap();
             let roc_file_path = PathBuf::from(filename);
@@ -112,46 +122,28 @@ fn main() -> io::Result<()> {
                 Some(n) => Threading::AtMost(n),
             };
 
-            match check_file(&arena, roc_file_path, emit_timings, threading) {
-                Ok((problems, total_time)) => {
-                    println!(
-                        "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.",
-                        if problems.errors == 0 {
-                            32 // green
-                        } else {
-                            33 // yellow
-                        },
-                        problems.errors,
-                        if problems.errors == 1 {
-                            "error"
-                        } else {
-                            "errors"
-                        },
-                        if problems.warnings == 0 {
-                            32 // green
-                        } else {
-                            33 // yellow
-                        },
-                        problems.warnings,
-                        if problems.warnings == 1 {
-                            "warning"
-                        } else {
-                            "warnings"
-                        },
-                        total_time.as_millis(),
-                    );
-
-                    Ok(problems.exit_code())
-                }
+            let cache_dir = if matches.is_present(FLAG_NO_CACHE) {
+                None
+            } else {
+                default_cache_dir()
+            };
 
-                Err(LoadingProblem::FormattedReport(report)) => {
-                    print!("{}", report);
+            let render = render_target(matches);
 
-                    Ok(1)
-                }
-                Err(other) => {
-                    panic!("build_file failed with error:\n{:?}", other);
-                }
+            let check_once = || {
+                check(
+                    &roc_file_path,
+                    emit_timings,
+                    threading,
+                    render,
+                    cache_dir.clone(),
+                )
+            };
+
+            if matches.is_present(FLAG_WATCH) {
+                watch(&roc_file_path, || check_once().1)
+            } else {
+                Ok(check_once().0)
             }
         }
         Some((CMD_REPL, _)) => {
@@ -181,75 +173,94 @@ fn main() -> io::Result<()> {
         Some((CMD_DOCS, matches)) => {
             let maybe_values = matches.values_of_os(DIRECTORY_OR_FILES);
 
-            let mut values: Vec<OsString> = Vec::new();
+            // A platform's main.roc, or a directory that has one, is documented as a package.
+            let package_root = match maybe_values.clone() {
+                None => read_package_root(&std::env::current_dir()?),
+                Some(mut os_values) if os_values.len() == 1 => {
+                    read_package_root(Path::new(os_values.next().unwrap()))
+                }
+                Some(_) => None,
+            };
 
-            match maybe_values {
-                None => {
-                    let mut os_string_values: Vec<OsString> = Vec::new();
-                    read_all_roc_files(
-                        &std::env::current_dir()?.as_os_str().to_os_string(),
-                        &mut os_string_values,
-                    )?;
-                    for os_string in os_string_values {
-                        values.push(os_string);
+            if let Some(package_root) = package_root {
+                generate_package_docs_html(&package_root, Path::new(roc_docs::BUILD_DIR));
+            } else {
+                let mut values: Vec<OsString> = Vec::new();
+
+                match maybe_values {
+                    None => {
+                        let mut os_string_values: Vec<OsString> = Vec::new();
+                        read_all_roc_files(
+                            &std::env::current_dir()?.as_os_str().to_os_string(),
+                            &mut os_string_values,
+                        )?;
+                        for os_string in os_string_values {
+                            values.push(os_string);
+                        }
                     }
-                }
-                Some(os_values) => {
-                    for os_str in os_values {
-                        values.push(os_str.to_os_string());
+                    Some(os_values) => {
+                        for os_str in os_values {
+                            values.push(os_str.to_os_string());
+                        }
                     }
                 }
-            }
 
-            let mut roc_files = Vec::new();
+                let mut roc_files = Vec::new();
 
-            // Populate roc_files
-            for os_str in values {
-                let metadata = fs::metadata(os_str.clone())?;
-                roc_files_recursive(os_str.as_os_str(), metadata.file_type(), &mut roc_files)?;
-            }
+                // Populate roc_files
+                for os_str in values {
+                    let metadata = fs::metadata(os_str.clone())?;
+                    roc_files_recursive(os_str.as_os_str(), metadata.file_type(), &mut roc_files)?;
+                }
 
-            generate_docs_html(roc_files);
+                generate_docs_html(roc_files);
+            }
 
             Ok(0)
         }
         Some((CMD_FORMAT, matches)) => {
-            let maybe_values = matches.values_of_os(DIRECTORY_OR_FILES);
-
-            let mut values: Vec<OsString> = Vec::new();
+            let format_mode = match matches.is_present(FLAG_CHECK) {
+                true => FormatMode::CheckOnly,
+                false => FormatMode::Format,
+            };
 
-            match maybe_values {
-                None => {
-                    let mut os_string_values: Vec<OsString> = Vec::new();
-                    read_all_roc_files(
-                        &std::env::current_dir()?.as_os_str().to_os_string(),
-                        &mut os_string_values,
-                    )?;
-                    for os_string in os_string_values {
-                        values.push(os_string);
+            let format_result = if matches.is_present(FLAG_STDIN) {
+                format_stdin(format_mode)
+            } else {
+                let maybe_values = matches.values_of_os(DIRECTORY_OR_FILES);
+
+                let mut values: Vec<OsString> = Vec::new();
+
+                match maybe_values {
+                    None => {
+                        let mut os_string_values: Vec<OsString> = Vec::new();
+                        read_all_roc_files(
+                            &std::env::current_dir()?.as_os_str().to_os_string(),
+                            &mut os_string_values,
+                        )?;
+                        for os_string in os_string_values {
+                            values.push(os_string);
+                        }
                     }
-                }
-                Some(os_values) => {
-                    for os_str in os_values {
-                        values.push(os_str.to_os_string());
+                    Some(os_values) => {
+                        for os_str in os_values {
+                            values.push(os_str.to_os_string());
+                        }
                     }
                 }
-            }
 
-            let mut roc_files = Vec::new();
+                let mut roc_files = Vec::new();
 
-            // Populate roc_files
-            for os_str in values {
-                let metadata = fs::metadata(os_str.clone())?;
-                roc_files_recursive(os_str.as_os_str(), metadata.file_type(), &mut roc_files)?;
-            }
+                // Populate roc_files
+                for os_str in values {
+                    let metadata = fs::metadata(os_str.clone())?;
+                    roc_files_recursive(os_str.as_os_str(), metadata.file_type(), &mut roc_files)?;
+                }
 
-            let format_mode = match matches.is_present(FLAG_CHECK) {
-                true => FormatMode::CheckOnly,
-                false => FormatMode::Format,
+                format(roc_files, format_mode)
             };
 
-            let format_exit_code = match format(roc_files, format_mode) {
+            let format_exit_code = match format_result {
                 Ok(_) => 0,
                 Err(message) => {
                     eprintln!("{}", message);
@@ -273,6 +284,75 @@ fn main() -> io::Result<()> {
     std::process::exit(exit_code);
 }
 
+/// Checks the file, returning the exit code and the files of the modules that were checked.
+fn check(
+    roc_file_path: &Path,
+    emit_timings: bool,
+    threading: Threading,
+    render: RenderTarget,
+    cache_dir: Option<PathBuf>,
+) -> (i32, Option<Vec<PathBuf>>) {
+    let arena = bumpalo::Bump::new();
+
+    match check_file(
+        &arena,
+        roc_file_path.to_path_buf(),
+        emit_timings,
+        threading,
+        render,
+        cache_dir,
+    ) {
+        Ok(CheckedFile {
+            problems,
+            total_time,
+            module_paths,
+        }) => {
+            report_status(
+                render,
+                &format!(
+                    "\x1B[{}m{}\x1B[39m {} and \x1B[{}m{}\x1B[39m {} found in {} ms.",
+                    if problems.errors == 0 {
+                        32 // green
+                    } else {
+                        33 // yellow
+                    },
+                    problems.errors,
+                    if problems.errors == 1 {
+                        "error"
+                    } else {
+                        "errors"
+                    },
+                    if problems.warnings == 0 {
+                        32 // green
+                    } else {
+                        33 // yellow
+                    },
+                    problems.warnings,
+                    if problems.warnings == 1 {
+                        "warning"
+                    } else {
+                        "warnings"
+                    },
+                    total_time.as_millis(),
+                ),
+            );
+
+            (problems.exit_code(), Some(module_paths))
+        }
+
+        Err(LoadingProblem::FormattedReport(report)) => {
+            print!("{}", report);
+
+            (1, None)
+        }
+        Err(other) => {
+            eprintln!("Checking the file failed with error:\n{:?}", other);
+
+            (1, None)
+        }
+    }
+}
+
 fn read_all_roc_files(
     dir: &OsString,
     roc_file_paths: &mut Vec<OsString>,
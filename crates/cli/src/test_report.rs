@@ -0,0 +1,315 @@
+//! Selecting the top-level `expect`s that `roc test` runs, and reporting their results in the
+//! formats that CI systems understand.
+use roc_region::all::LineColumnRegion;
+use roc_repl_expect::run::{ExpectOutcome, ExpectResult};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How `roc test` reports its results, as chosen with `--format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestFormat {
+    /// Reports of the failures, followed by a summary.
+    Human,
+    /// A JUnit XML document with a test suite per module.
+    Junit,
+    /// The Test Anything Protocol, version 13.
+    Tap,
+    /// One JSON object per expect, on a line of its own.
+    Json,
+}
+
+impl TestFormat {
+    pub const OPTIONS: &'static [&'static str] = &["human", "junit", "tap", "json"];
+}
+
+/// Where a top-level `expect` is. Its name in reports is the module name and the line it starts
+/// on, e.g. `Parser:12`.
+#[derive(Debug, Clone)]
+pub struct ExpectLocation {
+    pub module: String,
+    pub path: PathBuf,
+    pub region: LineColumnRegion,
+}
+
+impl ExpectLocation {
+    pub fn name(&self) -> String {
+        format!("{}:{}", self.module, self.line())
+    }
+
+    /// The 1-based line the expect starts on
+    fn line(&self) -> u32 {
+        self.region.start.line + 1
+    }
+}
+
+/// A `--filter` of the expects to run: a part of a module name, optionally followed by a line
+/// (`Parser:12`) or an inclusive range of lines (`Parser:10-20`) that the expect must overlap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectFilter {
+    module: String,
+    lines: Option<(u32, u32)>,
+}
+
+impl ExpectFilter {
+    pub fn parse(filter: &str) -> Result<Self, String> {
+        let (module, lines) = match filter.rsplit_once(':') {
+            None => (filter, None),
+            Some((module, lines)) => {
+                let parse_line = |line: &str| match line.trim().parse::<u32>() {
+                    Ok(line) if line > 0 => Ok(line),
+                    _ => Err(format!(
+                        "`{}` is not a line number; lines are counted from 1",
+                        line
+                    )),
+                };
+
+                let lines = match lines.split_once('-') {
+                    None => {
+                        let line = parse_line(lines)?;
+                        (line, line)
+                    }
+                    Some((start, end)) => (parse_line(start)?, parse_line(end)?),
+                };
+
+                if lines.0 > lines.1 {
+                    return Err(format!(
+                        "the range of lines {}-{} is empty",
+                        lines.0, lines.1
+                    ));
+                }
+
+                (module, Some(lines))
+            }
+        };
+
+        Ok(Self {
+            module: module.to_string(),
+            lines,
+        })
+    }
+
+    pub fn matches(&self, location: &ExpectLocation) -> bool {
+        if !location.module.contains(&self.module) {
+            return false;
+        }
+
+        match self.lines {
+            None => true,
+            Some((start, end)) => {
+                let first_line = location.region.start.line + 1;
+                let last_line = location.region.end.line + 1;
+
+                first_line <= end && start <= last_line
+            }
+        }
+    }
+}
+
+/// Writes the results in one of the machine-readable formats. In the `Human` format the failures
+/// were already reported while the expects ran, so this writes nothing.
+pub fn write_results<W: Write>(
+    writer: &mut W,
+    format: TestFormat,
+    results: &[(ExpectLocation, ExpectResult)],
+) -> io::Result<()> {
+    match format {
+        TestFormat::Human => Ok(()),
+        TestFormat::Junit => write_junit(writer, results),
+        TestFormat::Tap => write_tap(writer, results),
+        TestFormat::Json => write_json(writer, results),
+    }
+}
+
+fn write_junit<W: Write>(
+    writer: &mut W,
+    results: &[(ExpectLocation, ExpectResult)],
+) -> io::Result<()> {
+    let count = |results: &[&(ExpectLocation, ExpectResult)], failed: bool| {
+        results
+            .iter()
+            .filter(|(_, result)| match result.outcome {
+                ExpectOutcome::Passed => false,
+                ExpectOutcome::Failed(_) => failed,
+                ExpectOutcome::Crashed(_) => !failed,
+            })
+            .count()
+    };
+    let total_time = |results: &[&(ExpectLocation, ExpectResult)]| {
+        results
+            .iter()
+            .map(|(_, result)| result.duration)
+            .sum::<Duration>()
+    };
+
+    // one test suite per module, in the order their first expect ran
+    let mut suites: Vec<(&str, Vec<&(ExpectLocation, ExpectResult)>)> = Vec::new();
+    for entry in results {
+        let module = entry.0.module.as_str();
+        match suites.iter_mut().find(|(name, _)| *name == module) {
+            Some((_, entries)) => entries.push(entry),
+            None => suites.push((module, vec![entry])),
+        }
+    }
+
+    let all: Vec<_> = results.iter().collect();
+
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<testsuites name="roc test" tests="{}" failures="{}" errors="{}" time="{:.6}">"#,
+        all.len(),
+        count(&all, true),
+        count(&all, false),
+        total_time(&all).as_secs_f64(),
+    )?;
+
+    for (module, entries) in suites {
+        writeln!(
+            writer,
+            r#"  <testsuite name="{}" tests="{}" failures="{}" errors="{}" time="{:.6}">"#,
+            xml_escape(module),
+            entries.len(),
+            count(&entries, true),
+            count(&entries, false),
+            total_time(&entries).as_secs_f64(),
+        )?;
+
+        for (location, result) in entries {
+            write!(
+                writer,
+                r#"    <testcase name="{}" classname="{}" file="{}" line="{}" time="{:.6}""#,
+                xml_escape(&location.name()),
+                xml_escape(module),
+                xml_escape(&location.path.to_string_lossy()),
+                location.line(),
+                result.duration.as_secs_f64(),
+            )?;
+
+            match &result.outcome {
+                ExpectOutcome::Passed => writeln!(writer, "/>")?,
+                ExpectOutcome::Failed(report) => {
+                    writeln!(writer, ">")?;
+                    writeln!(
+                        writer,
+                        r#"      <failure message="This expectation failed.">{}</failure>"#,
+                        xml_escape(report.trim_end())
+                    )?;
+                    writeln!(writer, "    </testcase>")?;
+                }
+                ExpectOutcome::Crashed(report) => {
+                    writeln!(writer, ">")?;
+                    writeln!(
+                        writer,
+                        r#"      <error message="This expectation crashed while running.">{}</error>"#,
+                        xml_escape(report.trim_end())
+                    )?;
+                    writeln!(writer, "    </testcase>")?;
+                }
+            }
+        }
+
+        writeln!(writer, "  </testsuite>")?;
+    }
+
+    writeln!(writer, "</testsuites>")
+}
+
+fn write_tap<W: Write>(
+    writer: &mut W,
+    results: &[(ExpectLocation, ExpectResult)],
+) -> io::Result<()> {
+    writeln!(writer, "TAP version 13")?;
+    writeln!(writer, "1..{}", results.len())?;
+
+    for (number, (location, result)) in results.iter().enumerate() {
+        let (status, report) = match &result.outcome {
+            ExpectOutcome::Passed => ("ok", None),
+            ExpectOutcome::Failed(report) => ("not ok", Some(("failed", report))),
+            ExpectOutcome::Crashed(report) => ("not ok", Some(("crashed", report))),
+        };
+
+        writeln!(writer, "{} {} - {}", status, number + 1, location.name())?;
+
+        // a YAML block with the details, indented to belong to this test point
+        writeln!(writer, "  ---")?;
+        writeln!(
+            writer,
+            "  file: {}",
+            yaml_string(&location.path.to_string_lossy())
+        )?;
+        writeln!(writer, "  line: {}", location.line())?;
+        writeln!(writer, "  duration_ms: {:.3}", duration_ms(result.duration))?;
+        if let Some((outcome, report)) = report {
+            writeln!(writer, "  outcome: {}", outcome)?;
+            writeln!(writer, "  message: |")?;
+            for line in report.trim_end().lines() {
+                writeln!(writer, "    {}", line)?;
+            }
+        }
+        writeln!(writer, "  ...")?;
+    }
+
+    Ok(())
+}
+
+fn write_json<W: Write>(
+    writer: &mut W,
+    results: &[(ExpectLocation, ExpectResult)],
+) -> io::Result<()> {
+    for (location, result) in results {
+        let (outcome, report) = match &result.outcome {
+            ExpectOutcome::Passed => ("passed", None),
+            ExpectOutcome::Failed(report) => ("failed", Some(report.trim_end())),
+            ExpectOutcome::Crashed(report) => ("crashed", Some(report.trim_end())),
+        };
+
+        // lines and columns are 1-based, like in `--report-format json`
+        let region = location.region;
+        let json = serde_json::json!({
+            "name": location.name(),
+            "module": location.module,
+            "file": location.path.to_string_lossy(),
+            "region": {
+                "start": { "line": region.start.line + 1, "column": region.start.column + 1 },
+                "end": { "line": region.end.line + 1, "column": region.end.column + 1 },
+            },
+            "outcome": outcome,
+            "duration_ms": duration_ms(result.duration),
+            "message": report,
+        });
+
+        writeln!(writer, "{}", json)?;
+    }
+
+    Ok(())
+}
+
+fn duration_ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // XML 1.0 does not allow most control characters, even escaped
+            '\n' | '\r' | '\t' => escaped.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn yaml_string(text: &str) -> String {
+    // a JSON string is also a valid YAML string
+    serde_json::Value::from(text).to_string()
+}
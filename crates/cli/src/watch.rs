@@ -0,0 +1,178 @@
+//! Re-running `roc check` and `roc test` whenever a module they loaded changes.
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use roc_collections::MutMap;
+use roc_module::symbol::ModuleId;
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+/// Saving several files at once should only re-run once,
+/// so after a change we wait this long for more of them.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Calls `run`, and calls it again every time one of the files it loaded changes.
+///
+/// `run` returns the files of the modules it loaded, or `None` when loading failed before the
+/// module graph was known; then the files of the previous run keep being watched.
+/// This only returns when watching fails.
+pub fn watch<F>(root: &Path, mut run: F) -> io::Result<i32>
+where
+    F: FnMut() -> Option<Vec<PathBuf>>,
+{
+    let (sender, receiver) = channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(to_io_error)?;
+
+    let mut files = BTreeSet::new();
+    let mut watched_dirs = BTreeSet::new();
+
+    loop {
+        if let Some(module_paths) = run() {
+            files = module_paths
+                .iter()
+                .filter_map(|path| path.canonicalize().ok())
+                .collect();
+        }
+
+        // The root module is watched even when it could not be loaded, so that fixing it re-runs.
+        if let Ok(root) = root.canonicalize() {
+            files.insert(root);
+        }
+
+        // Editors often save by replacing a file rather than writing to it,
+        // which only shows up as a change to the directory it is in.
+        let dirs: BTreeSet<PathBuf> = files
+            .iter()
+            .filter_map(|file| file.parent().map(Path::to_path_buf))
+            .collect();
+
+        for dir in watched_dirs.difference(&dirs) {
+            // The directory may have been removed since, in which case it is no longer watched anyway.
+            let _ = watcher.unwatch(dir);
+        }
+
+        for dir in dirs.difference(&watched_dirs) {
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .map_err(to_io_error)?;
+        }
+
+        watched_dirs = dirs;
+
+        println!(
+            "\nWatching {} {} for changes…",
+            files.len(),
+            if files.len() == 1 { "file" } else { "files" }
+        );
+
+        wait_for_change(&receiver, &files)?;
+
+        // Clear the terminal, so that only reports about the latest changes are shown.
+        print!("\x1B[2J\x1B[H");
+    }
+}
+
+fn wait_for_change(
+    receiver: &Receiver<notify::Result<Event>>,
+    files: &BTreeSet<PathBuf>,
+) -> io::Result<()> {
+    let is_change = |event: notify::Result<Event>| -> io::Result<bool> {
+        let event = event.map_err(to_io_error)?;
+
+        Ok(!matches!(event.kind, EventKind::Access(_))
+            && event.paths.iter().any(|path| files.contains(path)))
+    };
+
+    loop {
+        let event = receiver.recv().map_err(to_io_error)?;
+
+        if is_change(event)? {
+            break;
+        }
+    }
+
+    // Drop the rest of the events from this save.
+    while let Ok(event) = receiver.recv_timeout(DEBOUNCE) {
+        is_change(event)?;
+    }
+
+    Ok(())
+}
+
+/// The files of the user modules in a loaded program, to be watched for changes.
+pub fn module_paths(sources: &MutMap<ModuleId, (PathBuf, Box<str>)>) -> Vec<PathBuf> {
+    sources
+        .iter()
+        .filter(|(module_id, _)| !module_id.is_builtin())
+        .map(|(_, (path, _))| path.clone())
+        .collect()
+}
+
+fn to_io_error(error: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, error)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use notify::event::{AccessKind, ModifyKind};
+    use roc_module::symbol::ModuleIds;
+    use std::sync::mpsc::Sender;
+
+    fn send(sender: &Sender<notify::Result<Event>>, kind: EventKind, path: &str) {
+        sender
+            .send(Ok(Event::new(kind).add_path(PathBuf::from(path))))
+            .unwrap();
+    }
+
+    #[test]
+    fn module_paths_skip_builtins() {
+        let main = ModuleIds::default().get_or_insert(&"Main".into());
+
+        let mut sources = MutMap::default();
+        sources.insert(ModuleId::LIST, (PathBuf::from("List.roc"), "".into()));
+        sources.insert(main, (PathBuf::from("/app/Main.roc"), "".into()));
+
+        assert_eq!(module_paths(&sources), vec![PathBuf::from("/app/Main.roc")]);
+    }
+
+    #[test]
+    fn changes_to_other_files_are_ignored() {
+        let (sender, receiver) = channel();
+        let files = BTreeSet::from([PathBuf::from("/app/Main.roc")]);
+
+        send(
+            &sender,
+            EventKind::Modify(ModifyKind::Any),
+            "/app/Other.roc",
+        );
+        send(&sender, EventKind::Access(AccessKind::Any), "/app/Main.roc");
+        drop(sender);
+
+        // Nothing changed before the watcher went away.
+        assert!(wait_for_change(&receiver, &files).is_err());
+    }
+
+    #[test]
+    fn one_save_is_one_change() {
+        let (sender, receiver) = channel();
+        let files = BTreeSet::from([
+            PathBuf::from("/app/Main.roc"),
+            PathBuf::from("/app/Dep.roc"),
+        ]);
+
+        // Saving all files in an editor
+        send(&sender, EventKind::Modify(ModifyKind::Any), "/app/Main.roc");
+        send(&sender, EventKind::Modify(ModifyKind::Any), "/app/Dep.roc");
+        send(&sender, EventKind::Modify(ModifyKind::Any), "/app/Main.roc");
+
+        assert!(wait_for_change(&receiver, &files).is_ok());
+        assert!(receiver.try_recv().is_err());
+
+        // A later save is a change of its own.
+        send(&sender, EventKind::Modify(ModifyKind::Any), "/app/Dep.roc");
+
+        assert!(wait_for_change(&receiver, &files).is_ok());
+    }
+}
@@ -16,7 +16,7 @@ mod cli_run {
     };
     use const_format::concatcp;
     use indoc::indoc;
-    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_FORMAT, CMD_RUN};
+    use roc_cli::{CMD_BUILD, CMD_CHECK, CMD_FORMAT, CMD_RUN, CMD_TEST};
     use roc_test_utils::assert_multiline_str_eq;
     use serial_test::serial;
     use std::iter;
@@ -27,6 +27,8 @@ mod cli_run {
     const OPTIMIZE_FLAG: &str = concatcp!("--", roc_cli::FLAG_OPTIMIZE);
     const LINKER_FLAG: &str = concatcp!("--", roc_cli::FLAG_LINKER);
     const CHECK_FLAG: &str = concatcp!("--", roc_cli::FLAG_CHECK);
+    const STDIN_FLAG: &str = concatcp!("--", roc_cli::FLAG_STDIN);
+    const DEBUG_FLAG: &str = concatcp!("--", roc_cli::FLAG_DEBUG);
     const PRECOMPILED_HOST: &str = concatcp!("--", roc_cli::FLAG_PRECOMPILED, "=true");
     #[allow(dead_code)]
     const TARGET_FLAG: &str = concatcp!("--", roc_cli::FLAG_TARGET);
@@ -948,6 +950,101 @@ mod cli_run {
         );
     }
 
+    #[test]
+    #[serial(debug_info)]
+    #[cfg(target_os = "linux")]
+    fn debug_info_points_at_roc_source() {
+        let file = fixture_file("debug-info", "Main.roc");
+
+        run_roc_on(&file, [CMD_BUILD, DEBUG_FLAG, "--max-threads=1"], &[], None);
+
+        let executable = file.with_file_name("debug-info");
+        let out = run_cmd(
+            "llvm-dwarfdump",
+            iter::empty(),
+            &["--debug-info", executable.to_str().unwrap()],
+        );
+
+        assert!(out.status.success(), "llvm-dwarfdump failed: {:?}", out);
+
+        // `greeting = greet "Greeting"` is on line 4 of Greeting.roc
+        let greeting = out
+            .stdout
+            .split("DW_TAG_subprogram")
+            .find(|subprogram| subprogram.contains("\"Greeting.greeting\""))
+            .unwrap_or_else(|| panic!("no subprogram for Greeting.greeting in:\n{}", out.stdout));
+
+        assert!(greeting.contains("Greeting.roc"), "{}", greeting);
+        assert!(greeting.contains("DW_AT_decl_line\t(4)"), "{}", greeting);
+
+        let out = run_cmd(
+            "llvm-dwarfdump",
+            iter::empty(),
+            &["--debug-line", executable.to_str().unwrap()],
+        );
+
+        assert!(out.status.success(), "llvm-dwarfdump failed: {:?}", out);
+
+        // the line table of the app is the one that mentions Greeting.roc
+        let line_table = out
+            .stdout
+            .split("debug_line[")
+            .find(|table| table.contains("\"Greeting.roc\""))
+            .unwrap_or_else(|| panic!("no line table for Greeting.roc in:\n{}", out.stdout));
+
+        let mut greeting_file_index = None;
+        let mut file_index = None;
+        for line in line_table.lines() {
+            let line = line.trim();
+            if let Some(index) = line.strip_prefix("file_names[") {
+                file_index = index.trim_end_matches("]:").trim().parse::<u32>().ok();
+            } else if line == "name: \"Greeting.roc\"" {
+                greeting_file_index = file_index;
+            }
+        }
+        let greeting_file_index = greeting_file_index
+            .unwrap_or_else(|| panic!("no file entry for Greeting.roc in:\n{}", line_table))
+            .to_string();
+
+        // `message = Str.concat "I am " name` is on line 8 of Greeting.roc, inside the body of `greet`
+        let has_statement_row = line_table.lines().any(|row| {
+            let columns: Vec<&str> = row.split_whitespace().collect();
+            columns.len() > 3
+                && columns[0].starts_with("0x")
+                && columns[1] == "8"
+                && columns[3] == greeting_file_index
+        });
+
+        assert!(
+            has_statement_row,
+            "no row for line 8 of Greeting.roc in:\n{}",
+            line_table
+        );
+    }
+
+    #[test]
+    fn debug_info_with_surgical_linker() {
+        let file = fixture_file("debug-info", "Main.roc");
+        let out = run_roc(
+            [
+                CMD_BUILD,
+                file.to_str().unwrap(),
+                DEBUG_FLAG,
+                LINKER_FLAG,
+                "surgical",
+            ],
+            &[],
+        );
+
+        assert!(!out.status.success());
+        assert!(
+            out.stderr
+                .contains("`--debug` cannot be used with the surgical linker yet"),
+            "{:?}",
+            out.stderr
+        );
+    }
+
     #[test]
     fn known_type_error() {
         check_compile_error(
@@ -1020,6 +1117,32 @@ mod cli_run {
         );
     }
 
+    #[test]
+    fn unused_import_as_json() {
+        let file = known_bad_file("UnusedImport.roc");
+        let out = run_roc(
+            [CMD_CHECK, file.to_str().unwrap(), "--report-format=json"],
+            &[],
+        );
+
+        let reports: Vec<serde_json::Value> = out
+            .stdout
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0]["severity"], "warning");
+        assert_eq!(reports[0]["title"], "UNUSED IMPORT");
+
+        assert!(!out.stderr.contains('\x1B'), "{:?}", out.stderr);
+        assert!(
+            out.stderr.contains("0 errors and 1 warning found in"),
+            "{:?}",
+            out.stderr
+        );
+    }
+
     #[test]
     fn unknown_generates_with() {
         check_compile_error(
@@ -1044,6 +1167,185 @@ mod cli_run {
         );
     }
 
+    #[test]
+    fn test_interface_module_as_tap() {
+        let file = fixture_file("expects", "Counter.roc");
+        let out = run_roc(
+            [
+                CMD_TEST,
+                file.to_str().unwrap(),
+                "--format=tap",
+                "--filter=Counter:9-12",
+            ],
+            &[],
+        );
+
+        assert!(!out.status.success());
+
+        // leave out the indented details, which contain durations
+        let test_points: Vec<&str> = out
+            .stdout
+            .lines()
+            .filter(|line| !line.starts_with("  "))
+            .collect();
+
+        assert_eq!(
+            test_points,
+            [
+                "TAP version 13",
+                "1..2",
+                "not ok 1 - Counter:10",
+                "ok 2 - Counter:12",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interface_module_as_junit() {
+        let file = fixture_file("expects", "Counter.roc");
+        let out = run_roc([CMD_TEST, file.to_str().unwrap(), "--format=junit"], &[]);
+
+        assert!(!out.status.success());
+        // the summary on stderr is for CI logs, which have no use for colors
+        assert!(!out.stderr.contains('\x1B'), "{:?}", out.stderr);
+        assert!(out.stderr.contains("1 failed and 2 passed"));
+
+        let lines: Vec<&str> = out.stdout.lines().map(|line| line.trim_start()).collect();
+
+        assert_eq!(lines[0], r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        assert!(lines[1]
+            .starts_with(r#"<testsuites name="roc test" tests="3" failures="1" errors="0""#));
+        assert!(
+            lines[2].starts_with(r#"<testsuite name="Counter" tests="3" failures="1" errors="0""#)
+        );
+
+        let test_cases: Vec<&str> = lines
+            .iter()
+            .filter(|line| line.starts_with("<testcase"))
+            .map(|line| line.split(r#"" "#).next().unwrap())
+            .collect();
+        assert_eq!(
+            test_cases,
+            [
+                r#"<testcase name="Counter:8"#,
+                r#"<testcase name="Counter:10"#,
+                r#"<testcase name="Counter:12"#,
+            ]
+        );
+
+        let failures = lines
+            .iter()
+            .filter(|line| line.starts_with(r#"<failure message="This expectation failed.">"#))
+            .count();
+        assert_eq!(failures, 1);
+        assert_eq!(lines.last(), Some(&"</testsuites>"));
+    }
+
+    #[test]
+    fn test_interface_module_as_json() {
+        let file = fixture_file("expects", "Counter.roc");
+        let out = run_roc(
+            [
+                CMD_TEST,
+                file.to_str().unwrap(),
+                "--format=json",
+                "--filter=Counter:10",
+            ],
+            &[],
+        );
+
+        assert!(!out.status.success());
+        assert!(!out.stderr.contains('\x1B'), "{:?}", out.stderr);
+
+        let results: Vec<serde_json::Value> = out
+            .stdout
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(results.len(), 1);
+
+        let result = &results[0];
+        assert_eq!(result["name"], "Counter:10");
+        assert_eq!(result["module"], "Counter");
+        assert_eq!(result["region"]["start"]["line"], 10);
+        assert_eq!(result["outcome"], "failed");
+        assert!(result["duration_ms"].is_f64());
+
+        let message = result["message"].as_str().unwrap();
+        assert!(message.contains("This expectation failed:"), "{}", message);
+        assert!(!message.contains('\x1B'), "{:?}", message);
+    }
+
+    #[test]
+    fn test_filter() {
+        let file = fixture_file("expects", "Counter.roc");
+        let run = |filters: &[&str]| {
+            let mut args = vec![CMD_TEST, file.to_str().unwrap(), "--format=tap"];
+            args.extend(filters.iter().copied());
+            run_roc(args, &[])
+        };
+        let test_points = |out: &Out| -> Vec<String> {
+            out.stdout
+                .lines()
+                .filter(|line| line.starts_with("ok") || line.starts_with("not ok"))
+                .map(|line| line.to_string())
+                .collect()
+        };
+
+        // a part of the module name selects all of its expects
+        let out = run(&["--filter=Count"]);
+        assert_eq!(test_points(&out).len(), 3);
+
+        // a range of lines selects the expects that overlap it
+        let out = run(&["--filter=Counter:7-8", "--filter=Counter:12"]);
+        assert!(out.status.success());
+        assert_eq!(test_points(&out), ["ok 1 - Counter:8", "ok 2 - Counter:12"]);
+
+        // filters matching nothing are an error, so CI doesn't quietly run no tests
+        let out = run(&["--filter=Parser"]);
+        assert_eq!(out.status.code(), Some(2));
+        assert!(out.stderr.contains("No expectations matched the filters."));
+
+        // filters that aren't valid are rejected before anything is compiled
+        for filter in [
+            "--filter=Counter:0",
+            "--filter=Counter:x",
+            "--filter=Counter:12-10",
+        ] {
+            let out = run(&[filter]);
+            assert!(!out.status.success());
+            assert!(out.stderr.contains("Invalid value"), "{}", out.stderr);
+        }
+    }
+
+    #[test]
+    fn test_coverage() {
+        let file = fixture_file("expects", "Sign.roc");
+        let dir = tempfile::tempdir().unwrap();
+        let lcov_file = dir.path().join("lcov.info");
+        let out = run_roc(
+            [
+                CMD_TEST,
+                file.to_str().unwrap(),
+                &format!("--coverage={}", lcov_file.display()),
+            ],
+            &[],
+        );
+
+        assert!(out.status.success());
+        assert!(out.stdout.contains("Coverage:"));
+
+        let lcov = std::fs::read_to_string(&lcov_file).unwrap();
+        let records: Vec<&str> = lcov.lines().collect();
+
+        assert!(records.contains(&"FNDA:2,sign"));
+        // each branch of the `if` ran once, except for the last one
+        assert!(records.contains(&"DA:8,1"));
+        assert!(records.contains(&"DA:10,1"));
+        assert!(records.contains(&"DA:12,0"));
+    }
+
     #[test]
     fn format_check_good() {
         check_format_check_as_expected(&fixture_file("format", "Formatted.roc"), true);
@@ -1062,6 +1364,52 @@ mod cli_run {
         // This doesn't fail, since only "Formatted.roc" and non-roc files are present in this folder
         check_format_check_as_expected(&fixtures_dir("format/formatted_directory"), true);
     }
+
+    #[test]
+    fn format_check_prints_diff() {
+        let file = fixture_file("format", "NotFormatted.roc");
+        let out = run_roc([CMD_FORMAT, file.to_str().unwrap(), CHECK_FLAG], &[]);
+
+        assert!(!out.status.success());
+        assert!(out.stdout.contains("@@ -1,6 +1,6 @@"));
+        assert!(out.stdout.contains("-  provides [main] to pf"));
+        assert!(out.stdout.contains("+    provides [main] to pf"));
+        assert!(out.stderr.contains("These files need to be reformatted:"));
+    }
+
+    #[test]
+    fn format_stdin() {
+        let src = std::fs::read_to_string(fixture_file("format", "NotFormatted.roc")).unwrap();
+        let formatted = std::fs::read_to_string(fixture_file("format", "Formatted.roc")).unwrap();
+
+        let out = run_roc([CMD_FORMAT, STDIN_FLAG], &[&src]);
+
+        assert!(out.status.success());
+        assert_eq!(out.stdout, formatted);
+    }
+
+    #[test]
+    fn format_check_continues_after_parse_error() {
+        let out = run_roc(
+            [
+                CMD_FORMAT,
+                fixtures_dir("format/parse_error").to_str().unwrap(),
+                CHECK_FLAG,
+            ],
+            &[],
+        );
+
+        assert!(!out.status.success());
+
+        // The parse error is reported...
+        let stderr = strip_colors(&out.stderr);
+        assert!(stderr.contains("MISSING EXPRESSION"));
+        assert!(stderr.contains("I could not format these files:"));
+
+        // ...and the other file is still checked.
+        assert!(out.stdout.contains("+    provides [main] to pf"));
+        assert!(stderr.contains("These files need to be reformatted:"));
+    }
 }
 
 #[allow(dead_code)]
@@ -0,0 +1,9 @@
+interface Greeting exposes [greeting] imports []
+
+greeting : Str
+greeting = greet "Greeting"
+
+greet : Str -> Str
+greet = \name ->
+    message = Str.concat "I am " name
+    Str.concat message ".greeting"
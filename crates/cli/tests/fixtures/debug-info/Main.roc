@@ -0,0 +1,7 @@
+app "debug-info"
+    packages { pf: "platform/main.roc" }
+    imports [Greeting]
+    provides [main] to pf
+
+main : Str
+main = Greeting.greeting
@@ -0,0 +1,9 @@
+platform "multi-module"
+    requires {}{ main : Str }
+    exposes []
+    packages {}
+    imports []
+    provides [mainForHost]
+
+mainForHost : Str
+mainForHost = main
@@ -0,0 +1,12 @@
+interface Counter
+    exposes [increment]
+    imports []
+
+increment : U64 -> U64
+increment = \n -> n + 1
+
+expect increment 1 == 2
+
+expect increment 1 == 3
+
+expect increment 41 == 42
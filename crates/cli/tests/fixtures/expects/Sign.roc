@@ -0,0 +1,16 @@
+interface Sign
+    exposes [sign]
+    imports []
+
+sign : I64 -> Str
+sign = \n ->
+    if n > 0 then
+        "positive"
+    else if n < 0 then
+        "negative"
+    else
+        "zero"
+
+expect sign 5 == "positive"
+
+expect sign -5 == "negative"
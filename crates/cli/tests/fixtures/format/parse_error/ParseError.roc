@@ -0,0 +1,6 @@
+app "parse-error"
+    packages { pf: "platform/main.roc" } imports []
+    provides [main] to pf
+
+main : Str
+main =
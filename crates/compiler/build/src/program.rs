@@ -1,10 +1,12 @@
 pub use roc_gen_llvm::llvm::build::FunctionIterator;
 use roc_gen_llvm::llvm::build::{module_from_builtins, LlvmBackendMode};
+use roc_gen_llvm::llvm::debug_info::DebugSources;
 use roc_gen_llvm::llvm::externs::add_default_roc_externs;
 use roc_load::{EntryPoint, LoadedModule, MonomorphizedModule};
 use roc_module::symbol::{Interns, ModuleId};
 use roc_mono::ir::OptLevel;
 use roc_region::all::LineInfo;
+use roc_reporting::report::RenderTarget;
 use roc_solve_problem::TypeError;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
@@ -19,23 +21,28 @@ pub struct CodeGenTiming {
     pub emit_o_file: Duration,
 }
 
-pub fn report_problems_monomorphized(loaded: &mut MonomorphizedModule) -> Problems {
+pub fn report_problems_monomorphized(
+    loaded: &mut MonomorphizedModule,
+    render: RenderTarget,
+) -> Problems {
     report_problems_help(
         loaded.total_problems(),
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        render,
     )
 }
 
-pub fn report_problems_typechecked(loaded: &mut LoadedModule) -> Problems {
+pub fn report_problems_typechecked(loaded: &mut LoadedModule, render: RenderTarget) -> Problems {
     report_problems_help(
         loaded.total_problems(),
         &loaded.sources,
         &loaded.interns,
         &mut loaded.can_problems,
         &mut loaded.type_problems,
+        render,
     )
 }
 
@@ -62,6 +69,7 @@ fn report_problems_help(
     interns: &Interns,
     can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
     type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    render: RenderTarget,
 ) -> Problems {
     use roc_reporting::report::{
         can_problem, type_problem, Report, RocDocAllocator, Severity::*, DEFAULT_PALETTE,
@@ -90,7 +98,7 @@ fn report_problems_help(
             let severity = report.severity;
             let mut buf = String::new();
 
-            report.render_color_terminal(&mut buf, &alloc, &palette);
+            report.render(render, &mut buf, &alloc, &palette);
 
             match severity {
                 Warning => {
@@ -109,7 +117,7 @@ fn report_problems_help(
                 let severity = report.severity;
                 let mut buf = String::new();
 
-                report.render_color_terminal(&mut buf, &alloc, &palette);
+                report.render(render, &mut buf, &alloc, &palette);
 
                 match severity {
                     Warning => {
@@ -123,6 +131,18 @@ fn report_problems_help(
         }
     }
 
+    if let RenderTarget::Json = render {
+        // Tools reading JSON get every problem, one per line, and nothing else.
+        for report in errors.iter().chain(warnings.iter()) {
+            print!("{}", report);
+        }
+
+        return Problems {
+            errors: errors.len(),
+            warnings: warnings.len(),
+        };
+    }
+
     let problems_reported;
 
     // Only print warnings if there are no errors
@@ -183,6 +203,7 @@ pub fn gen_from_mono_module(
             loaded,
             target,
             app_o_file,
+            emit_debug_info,
             preprocessed_host_path,
             wasm_dev_stack_bytes,
         ),
@@ -204,7 +225,7 @@ pub fn gen_from_mono_module_llvm(
     use crate::target::{self, convert_opt_level};
     use inkwell::attributes::{Attribute, AttributeLoc};
     use inkwell::context::Context;
-    use inkwell::module::Linkage;
+    use inkwell::module::{FlagBehavior, Linkage};
     use inkwell::targets::{FileType, RelocMode};
 
     let code_gen_start = Instant::now();
@@ -253,12 +274,23 @@ pub fn gen_from_mono_module_llvm(
     let (dibuilder, compile_unit) = roc_gen_llvm::llvm::build::Env::new_debug_info(module);
     let (mpm, _fpm) = roc_gen_llvm::llvm::build::construct_optimization_passes(module, opt_level);
 
+    let debug_sources = if emit_debug_info {
+        Some(DebugSources::new(
+            &dibuilder,
+            &loaded.sources,
+            loaded.procedures.values(),
+        ))
+    } else {
+        None
+    };
+
     // Compile and add all the Procs before adding main
     let env = roc_gen_llvm::llvm::build::Env {
         arena,
         builder: &builder,
         dibuilder: &dibuilder,
         compile_unit: &compile_unit,
+        debug_sources: debug_sources.as_ref(),
         context: &context,
         interns: loaded.interns,
         module,
@@ -288,8 +320,21 @@ pub fn gen_from_mono_module_llvm(
 
     env.dibuilder.finalize();
 
-    // we don't use the debug info, and it causes weird errors.
-    module.strip_debug_info();
+    if emit_debug_info {
+        // LLVM silently drops debug info from modules that don't say which version they use
+        if module.get_flag("Debug Info Version").is_none() {
+            module.add_basic_value_flag(
+                "Debug Info Version",
+                FlagBehavior::Warning,
+                context
+                    .i32_type()
+                    .const_int(inkwell::debug_info::debug_metadata_version() as u64, false),
+            );
+        }
+    } else {
+        // without --debug, the debug info only consists of placeholders
+        module.strip_debug_info();
+    }
 
     // Uncomment this to see the module's optimized LLVM instruction output:
     // env.module.print_to_stderr();
@@ -314,98 +359,27 @@ pub fn gen_from_mono_module_llvm(
     let code_gen = code_gen_start.elapsed();
     let emit_o_file_start = Instant::now();
 
-    // annotate the LLVM IR output with debug info
-    // so errors are reported with the line number of the LLVM source
-    if emit_debug_info {
-        module.strip_debug_info();
-
-        let mut app_ll_dbg_file = PathBuf::from(roc_file_path);
-        app_ll_dbg_file.set_extension("dbg.ll");
-
-        let mut app_bc_file = PathBuf::from(roc_file_path);
-        app_bc_file.set_extension("bc");
-
-        use std::process::Command;
-
-        // write the ll code to a file, so we can modify it
-        module.print_to_file(&app_ll_file).unwrap();
-
-        // run the debugir https://github.com/vaivaswatha/debugir tool
-        match Command::new("debugir")
-            .args(&["-instnamer", app_ll_file.to_str().unwrap()])
-            .output()
-        {
-            Ok(_) => {}
-            Err(error) => {
-                use std::io::ErrorKind;
-                match error.kind() {
-                    ErrorKind::NotFound => panic!(
-                        r"I could not find the `debugir` tool on the PATH, install it from https://github.com/vaivaswatha/debugir"
-                    ),
-                    _ => panic!("{:?}", error),
-                }
-            }
-        }
-
-        use target_lexicon::Architecture;
-        match target.architecture {
-            Architecture::X86_64
-            | Architecture::X86_32(_)
-            | Architecture::Aarch64(_)
-            | Architecture::Wasm32 => {
-                let ll_to_bc = Command::new("llvm-as")
-                    .args(&[
-                        app_ll_dbg_file.to_str().unwrap(),
-                        "-o",
-                        app_bc_file.to_str().unwrap(),
-                    ])
-                    .output()
-                    .unwrap();
-
-                assert!(ll_to_bc.stderr.is_empty(), "{:#?}", ll_to_bc);
-
-                let llc_args = &[
-                    "-relocation-model=pic",
-                    "-filetype=obj",
-                    app_bc_file.to_str().unwrap(),
-                    "-o",
-                    app_o_file.to_str().unwrap(),
-                ];
-
-                // write the .o file. Note that this builds the .o for the local machine,
-                // and ignores the `target_machine` entirely.
-                //
-                // different systems name this executable differently, so we shotgun for
-                // the most common ones and then give up.
-                let bc_to_object = Command::new("llc").args(llc_args).output().unwrap();
-
-                assert!(bc_to_object.stderr.is_empty(), "{:#?}", bc_to_object);
-            }
-            _ => unreachable!(),
+    // Emit the .o file
+    use target_lexicon::Architecture;
+    match target.architecture {
+        Architecture::X86_64 | Architecture::X86_32(_) | Architecture::Aarch64(_) => {
+            let reloc = RelocMode::PIC;
+            let target_machine =
+                target::target_machine(target, convert_opt_level(opt_level), reloc).unwrap();
+
+            target_machine
+                .write_to_file(env.module, FileType::Object, app_o_file)
+                .expect("Writing .o file failed");
         }
-    } else {
-        // Emit the .o file
-        use target_lexicon::Architecture;
-        match target.architecture {
-            Architecture::X86_64 | Architecture::X86_32(_) | Architecture::Aarch64(_) => {
-                let reloc = RelocMode::PIC;
-                let target_machine =
-                    target::target_machine(target, convert_opt_level(opt_level), reloc).unwrap();
-
-                target_machine
-                    .write_to_file(env.module, FileType::Object, app_o_file)
-                    .expect("Writing .o file failed");
-            }
-            Architecture::Wasm32 => {
-                // Useful for debugging
-                // module.print_to_file(app_ll_file);
-                module.write_bitcode_to_path(app_o_file);
-            }
-            _ => panic!(
-                "TODO gracefully handle unsupported architecture: {:?}",
-                target.architecture
-            ),
+        Architecture::Wasm32 => {
+            // Useful for debugging
+            // module.print_to_file(app_ll_file);
+            module.write_bitcode_to_path(app_o_file);
         }
+        _ => panic!(
+            "TODO gracefully handle unsupported architecture: {:?}",
+            target.architecture
+        ),
     }
 
     let emit_o_file = emit_o_file_start.elapsed();
@@ -421,6 +395,7 @@ pub fn gen_from_mono_module_dev(
     loaded: MonomorphizedModule,
     target: &target_lexicon::Triple,
     app_o_file: &Path,
+    emit_debug_info: bool,
     preprocessed_host_path: &Path,
     wasm_dev_stack_bytes: Option<u32>,
 ) -> CodeGenTiming {
@@ -431,6 +406,7 @@ pub fn gen_from_mono_module_dev(
             arena,
             loaded,
             app_o_file,
+            emit_debug_info,
             preprocessed_host_path,
             wasm_dev_stack_bytes,
         ),
@@ -447,6 +423,7 @@ pub fn gen_from_mono_module_dev(
     loaded: MonomorphizedModule,
     target: &target_lexicon::Triple,
     app_o_file: &Path,
+    _emit_debug_info: bool,
     _host_input_path: &Path,
     _wasm_dev_stack_bytes: Option<u32>,
 ) -> CodeGenTiming {
@@ -465,6 +442,7 @@ fn gen_from_mono_module_dev_wasm32(
     arena: &bumpalo::Bump,
     loaded: MonomorphizedModule,
     app_o_file: &Path,
+    emit_debug_info: bool,
     preprocessed_host_path: &Path,
     wasm_dev_stack_bytes: Option<u32>,
 ) -> CodeGenTiming {
@@ -473,6 +451,7 @@ fn gen_from_mono_module_dev_wasm32(
         module_id,
         procedures,
         mut interns,
+        ref sources,
         ..
     } = loaded;
 
@@ -488,6 +467,7 @@ fn gen_from_mono_module_dev_wasm32(
         module_id,
         exposed_to_host,
         stack_bytes: wasm_dev_stack_bytes.unwrap_or(roc_gen_wasm::Env::DEFAULT_STACK_BYTES),
+        debug_sources: if emit_debug_info { Some(sources) } else { None },
     };
 
     let host_bytes = std::fs::read(preprocessed_host_path).unwrap_or_else(|_| {
@@ -55,6 +55,13 @@ comptime {
     exportListFn(list.listIsUnique, "is_unique");
 }
 
+// Dict Module
+const hash = @import("hash.zig");
+
+comptime {
+    exportDictFn(hash.wyhash_roclist, "hash_bytes");
+}
+
 // Num Module
 const num = @import("num.zig");
 
@@ -70,20 +77,37 @@ comptime {
     inline for (INTEGERS) |T, i| {
         num.exportPow(T, ROC_BUILTINS ++ "." ++ NUM ++ ".pow_int.");
         num.exportDivCeil(T, ROC_BUILTINS ++ "." ++ NUM ++ ".div_ceil.");
+        num.exportDivTrunc(T, ROC_BUILTINS ++ "." ++ NUM ++ ".div_trunc.");
+        num.exportRem(T, ROC_BUILTINS ++ "." ++ NUM ++ ".rem.");
+        num.exportIsMultipleOf(T, ROC_BUILTINS ++ "." ++ NUM ++ ".is_multiple_of.");
+
+        num.exportShiftLeftBy(T, ROC_BUILTINS ++ "." ++ NUM ++ ".shift_left_by.");
+        num.exportShiftRightBy(T, ROC_BUILTINS ++ "." ++ NUM ++ ".shift_right_by.");
+        num.exportShiftRightZeroFillBy(T, ROC_BUILTINS ++ "." ++ NUM ++ ".shift_right_zero_fill_by.");
+
+        num.exportToFloat(T, f32, ROC_BUILTINS ++ "." ++ NUM ++ ".to_f32.");
+        num.exportToFloat(T, f64, ROC_BUILTINS ++ "." ++ NUM ++ ".to_f64.");
 
         num.exportRoundF32(T, ROC_BUILTINS ++ "." ++ NUM ++ ".round_f32.");
         num.exportRoundF64(T, ROC_BUILTINS ++ "." ++ NUM ++ ".round_f64.");
+        num.exportCeilingF32(T, ROC_BUILTINS ++ "." ++ NUM ++ ".ceiling_f32.");
+        num.exportCeilingF64(T, ROC_BUILTINS ++ "." ++ NUM ++ ".ceiling_f64.");
+        num.exportFloorF32(T, ROC_BUILTINS ++ "." ++ NUM ++ ".floor_f32.");
+        num.exportFloorF64(T, ROC_BUILTINS ++ "." ++ NUM ++ ".floor_f64.");
 
         num.exportAddWithOverflow(T, ROC_BUILTINS ++ "." ++ NUM ++ ".add_with_overflow.");
         num.exportAddOrPanic(T, ROC_BUILTINS ++ "." ++ NUM ++ ".add_or_panic.");
+        num.exportAddWrappedInt(T, ROC_BUILTINS ++ "." ++ NUM ++ ".add_wrapped.");
         num.exportAddSaturatedInt(T, ROC_BUILTINS ++ "." ++ NUM ++ ".add_saturated.");
 
         num.exportSubWithOverflow(T, ROC_BUILTINS ++ "." ++ NUM ++ ".sub_with_overflow.");
         num.exportSubOrPanic(T, ROC_BUILTINS ++ "." ++ NUM ++ ".sub_or_panic.");
+        num.exportSubWrappedInt(T, ROC_BUILTINS ++ "." ++ NUM ++ ".sub_wrapped.");
         num.exportSubSaturatedInt(T, ROC_BUILTINS ++ "." ++ NUM ++ ".sub_saturated.");
 
         num.exportMulWithOverflow(T, WIDEINTS[i], ROC_BUILTINS ++ "." ++ NUM ++ ".mul_with_overflow.");
         num.exportMulOrPanic(T, WIDEINTS[i], ROC_BUILTINS ++ "." ++ NUM ++ ".mul_or_panic.");
+        num.exportMulWrappedInt(T, ROC_BUILTINS ++ "." ++ NUM ++ ".mul_wrapped.");
         num.exportMulSaturatedInt(T, WIDEINTS[i], ROC_BUILTINS ++ "." ++ NUM ++ ".mul_saturated.");
     }
 
@@ -105,6 +129,7 @@ comptime {
 
         num.exportPow(T, ROC_BUILTINS ++ "." ++ NUM ++ ".pow.");
         num.exportLog(T, ROC_BUILTINS ++ "." ++ NUM ++ ".log.");
+        num.exportSqrt(T, ROC_BUILTINS ++ "." ++ NUM ++ ".sqrt.");
 
         num.exportAddWithOverflow(T, ROC_BUILTINS ++ "." ++ NUM ++ ".add_with_overflow.");
         num.exportSubWithOverflow(T, ROC_BUILTINS ++ "." ++ NUM ++ ".sub_with_overflow.");
@@ -162,6 +187,7 @@ comptime {
     exportUtilsFn(utils.increfC, "incref");
     exportUtilsFn(utils.decrefC, "decref");
     exportUtilsFn(utils.decrefCheckNullC, "decref_check_null");
+    exportUtilsFn(utils.decNoopC, "dec_noop");
     exportUtilsFn(utils.allocateWithRefcountC, "allocate_with_refcount");
 
     @export(utils.panic, .{ .name = "roc_builtins.utils." ++ "panic", .linkage = .Weak });
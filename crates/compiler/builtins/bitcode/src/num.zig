@@ -158,6 +158,15 @@ pub fn exportLog(comptime T: type, comptime name: []const u8) void {
     @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
 }
 
+pub fn exportSqrt(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(input: T) callconv(.C) T {
+            return @sqrt(input);
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
 pub fn exportRoundF32(comptime T: type, comptime name: []const u8) void {
     comptime var f = struct {
         fn func(input: f32) callconv(.C) T {
@@ -176,6 +185,42 @@ pub fn exportRoundF64(comptime T: type, comptime name: []const u8) void {
     @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
 }
 
+pub fn exportCeilingF32(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(input: f32) callconv(.C) T {
+            return @floatToInt(T, (@ceil(input)));
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
+pub fn exportCeilingF64(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(input: f64) callconv(.C) T {
+            return @floatToInt(T, (@ceil(input)));
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
+pub fn exportFloorF32(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(input: f32) callconv(.C) T {
+            return @floatToInt(T, (@floor(input)));
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
+pub fn exportFloorF64(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(input: f64) callconv(.C) T {
+            return @floatToInt(T, (@floor(input)));
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
 pub fn exportDivCeil(comptime T: type, comptime name: []const u8) void {
     comptime var f = struct {
         fn func(a: T, b: T) callconv(.C) T {
@@ -185,6 +230,98 @@ pub fn exportDivCeil(comptime T: type, comptime name: []const u8) void {
     @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
 }
 
+pub fn exportDivTrunc(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(a: T, b: T) callconv(.C) T {
+            return math.divTrunc(T, a, b) catch @panic("TODO runtime exception for dividing by 0!");
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
+pub fn exportRem(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(a: T, b: T) callconv(.C) T {
+            if (b == 0) {
+                @panic("TODO runtime exception for dividing by 0!");
+            }
+            // minInt % -1 overflows in the division, but the remainder is 0
+            if (@typeInfo(T).Int.signedness == .signed and b == -1) {
+                return 0;
+            }
+            return @rem(a, b);
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
+pub fn exportIsMultipleOf(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(a: T, b: T) callconv(.C) bool {
+            if (b == 0) {
+                return a == 0;
+            }
+            if (@typeInfo(T).Int.signedness == .signed and b == -1) {
+                return true;
+            }
+            return @rem(a, b) == 0;
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
+// Shifting by the width of the type or more shifts out every bit of the number
+fn shiftAmount(comptime T: type, bits: T) ?math.Log2Int(T) {
+    const U = std.meta.Int(.unsigned, @bitSizeOf(T));
+    const amount = @bitCast(U, bits);
+    if (amount >= @bitSizeOf(T)) {
+        return null;
+    }
+    return @intCast(math.Log2Int(T), amount);
+}
+
+pub fn exportShiftLeftBy(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(bits: T, num: T) callconv(.C) T {
+            const amount = shiftAmount(T, bits) orelse return 0;
+            return num << amount;
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
+pub fn exportShiftRightBy(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(bits: T, num: T) callconv(.C) T {
+            // arithmetic shift, even for unsigned integers
+            const S = std.meta.Int(.signed, @bitSizeOf(T));
+            const amount = shiftAmount(T, bits) orelse @bitSizeOf(T) - 1;
+            return @bitCast(T, @bitCast(S, num) >> amount);
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
+pub fn exportShiftRightZeroFillBy(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(bits: T, num: T) callconv(.C) T {
+            const U = std.meta.Int(.unsigned, @bitSizeOf(T));
+            const amount = shiftAmount(T, bits) orelse return 0;
+            return @bitCast(T, @bitCast(U, num) >> amount);
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
+pub fn exportToFloat(comptime T: type, comptime F: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(input: T) callconv(.C) F {
+            return @intToFloat(F, input);
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
 pub fn ToIntCheckedResult(comptime T: type) type {
     // On the Roc side we sort by alignment; putting the errorcode last
     // always works out (no number with smaller alignment than 1).
@@ -294,6 +431,15 @@ pub fn exportAddOrPanic(comptime T: type, comptime name: []const u8) void {
     @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
 }
 
+pub fn exportAddWrappedInt(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(self: T, other: T) callconv(.C) T {
+            return self +% other;
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
 fn subWithOverflow(comptime T: type, self: T, other: T) WithOverflow(T) {
     switch (@typeInfo(T)) {
         .Int => {
@@ -353,6 +499,15 @@ pub fn exportSubOrPanic(comptime T: type, comptime name: []const u8) void {
     @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
 }
 
+pub fn exportSubWrappedInt(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(self: T, other: T) callconv(.C) T {
+            return self -% other;
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
+
 fn mulWithOverflow(comptime T: type, comptime W: type, self: T, other: T) WithOverflow(T) {
     switch (@typeInfo(T)) {
         .Int => {
@@ -460,3 +615,12 @@ pub fn exportMulOrPanic(comptime T: type, comptime W: type, comptime name: []con
     }.func;
     @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
 }
+
+pub fn exportMulWrappedInt(comptime T: type, comptime name: []const u8) void {
+    comptime var f = struct {
+        fn func(self: T, other: T) callconv(.C) T {
+            return self *% other;
+        }
+    }.func;
+    @export(f, .{ .name = name ++ @typeName(T), .linkage = .Strong });
+}
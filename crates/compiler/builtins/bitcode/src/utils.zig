@@ -170,6 +170,9 @@ pub fn decrefCheckNullC(
     }
 }
 
+// A `Dec` that does nothing, for lists whose elements are not reference counted.
+pub fn decNoopC(_: ?[*]u8) callconv(.C) void {}
+
 pub fn decref(
     bytes_or_null: ?[*]u8,
     data_bytes: usize,
@@ -0,0 +1,403 @@
+interface Arbitrary
+    exposes [
+        Arbitrary,
+        Rng,
+        arbitrary,
+        check,
+        draw,
+        chooseTag,
+        list,
+        str,
+        u8,
+        u16,
+        u32,
+        u64,
+        u128,
+        i8,
+        i16,
+        i32,
+        i64,
+        i128,
+        nat,
+        f32,
+        f64,
+        dec,
+    ]
+    imports [
+        List,
+        Str,
+    ]
+
+## A value that can be generated at random, for property-based testing.
+##
+## The [Arbitrary] ability can be derived for records, tag unions and opaque
+## types, as long as all the values they contain can be generated as well.
+## Functions and empty tag unions can not be generated.
+##
+## A top-level `expect` whose condition is a function, like
+##
+##     expect \list -> List.len (List.reverse list) == List.len list
+##
+## is a property test: `roc test` calls the function with many generated
+## arguments, and reports the simplest arguments it could find for which the
+## function returns `False`.
+Arbitrary has
+    arbitrary : Rng -> { value : a, rng : Rng } | a has Arbitrary
+
+## The source of the choices a generator makes.
+##
+## Every value is generated from a sequence of choices, each a [U64] that is
+## made with [draw]. Generators are written so that smaller choices give
+## simpler values, and a choice of zero gives the simplest value of all. This
+## lets [check] shrink a failing input by looking for smaller choices that
+## still make the property fail, without knowing anything about the type of
+## the input.
+##
+## An [Rng] also has a size budget, which [list], [str] and [chooseTag] use up,
+## so that generated values can not grow forever.
+Rng := {
+    state : U64,
+    random : Bool,
+    replay : List U64,
+    choices : List U64,
+    budget : Nat,
+}
+
+## Makes a choice between `0` and `bound`, inclusive.
+##
+## Random choices are biased towards small numbers, because those are much more
+## likely to hit edge cases than uniformly distributed ones.
+draw : Rng, U64 -> { value : U64, rng : Rng }
+draw = \@Rng rng, bound ->
+    choice =
+        if rng.random then
+            next = randomChoice rng.state
+
+            value =
+                if bound == Num.maxU64 then
+                    next.value
+                else
+                    Num.rem next.value (bound + 1)
+
+            { value, state: next.state }
+        else
+            when List.get rng.replay (List.len rng.choices) is
+                Ok replayed if replayed > bound -> { value: bound, state: rng.state }
+                Ok replayed -> { value: replayed, state: rng.state }
+                Err OutOfBounds -> { value: 0, state: rng.state }
+
+    {
+        value: choice.value,
+        rng: @Rng { rng & state: choice.state, choices: List.append rng.choices choice.value },
+    }
+
+## Chooses which one of `count` tags to generate. Derived implementations of
+## [Arbitrary] for tag unions use this, with the tags ordered by their number
+## of payloads.
+##
+## Once the size budget of the [Rng] is used up, the first tag is always
+## chosen, so that recursive tag unions stop growing.
+chooseTag : Rng, Nat -> { value : Nat, rng : Rng }
+chooseTag = \@Rng rng, count ->
+    if rng.budget == 0 then
+        { value: 0, rng: @Rng rng }
+    else
+        choice = draw (@Rng { rng & budget: rng.budget - 1 }) (Num.toU64 count - 1)
+
+        { value: Num.toNat choice.value, rng: choice.rng }
+
+## Generates a list of [Arbitrary] elements.
+##
+## Before each element, a choice is made whether to add another one, so that
+## shrinking can remove elements from anywhere in the list.
+list : Rng -> { value : List a, rng : Rng } | a has Arbitrary
+list = \rng -> listHelp rng []
+
+listHelp : Rng, List a -> { value : List a, rng : Rng } | a has Arbitrary
+listHelp = \@Rng rng, elems ->
+    if rng.budget == 0 then
+        { value: elems, rng: @Rng rng }
+    else
+        more = draw (@Rng { rng & budget: rng.budget - 1 }) 7
+
+        if more.value == 0 then
+            { value: elems, rng: more.rng }
+        else
+            listAppend (arbitrary more.rng) elems
+
+# Generated values are passed to helpers like this one, rather than bound to a
+# name, so that their type is never generalized away from the type we need.
+listAppend : { value : a, rng : Rng }, List a -> { value : List a, rng : Rng } | a has Arbitrary
+listAppend = \{ value, rng }, elems -> listHelp rng (List.append elems value)
+
+## Generates a string of printable ASCII characters.
+str : Rng -> { value : Str, rng : Rng }
+str = \rng ->
+    bytes = strHelp rng []
+
+    when Str.fromUtf8 bytes.value is
+        Ok string -> { value: string, rng: bytes.rng }
+        Err _ -> { value: "", rng: bytes.rng }
+
+strHelp : Rng, List U8 -> { value : List U8, rng : Rng }
+strHelp = \@Rng rng, bytes ->
+    if rng.budget == 0 then
+        { value: bytes, rng: @Rng rng }
+    else
+        more = draw (@Rng { rng & budget: rng.budget - 1 }) 7
+
+        if more.value == 0 then
+            { value: bytes, rng: more.rng }
+        else
+            # the 95 printable characters, starting at 'a' for the smallest choice
+            char = draw more.rng 94
+            byte = 32 + Num.rem (char.value + 65) 95
+
+            strHelp char.rng (List.append bytes (Num.toU8 byte))
+
+u8 : Rng -> { value : U8, rng : Rng }
+u8 = \rng -> mapValue (draw rng (Num.toU64 Num.maxU8)) Num.toU8
+
+u16 : Rng -> { value : U16, rng : Rng }
+u16 = \rng -> mapValue (draw rng (Num.toU64 Num.maxU16)) Num.toU16
+
+u32 : Rng -> { value : U32, rng : Rng }
+u32 = \rng -> mapValue (draw rng (Num.toU64 Num.maxU32)) Num.toU32
+
+u64 : Rng -> { value : U64, rng : Rng }
+u64 = \rng -> draw rng Num.maxU64
+
+u128 : Rng -> { value : U128, rng : Rng }
+u128 = \rng ->
+    upper = draw rng Num.maxU64
+    lower = draw upper.rng Num.maxU64
+
+    {
+        value: Num.bitwiseOr (Num.shiftLeftBy 64 (Num.toU128 upper.value)) (Num.toU128 lower.value),
+        rng: lower.rng,
+    }
+
+nat : Rng -> { value : Nat, rng : Rng }
+nat = \rng -> mapValue (draw rng Num.maxU64) Num.toNat
+
+# Signed integers are generated from their zigzag encoding, which maps
+# 0, 1, 2, 3, 4, ... to 0, -1, 1, -2, 2, ... so that smaller choices give
+# numbers closer to zero.
+i8 : Rng -> { value : I8, rng : Rng }
+i8 = \rng -> mapValue (draw rng (Num.toU64 Num.maxU8)) \n -> Num.toI8 (zigzag n)
+
+i16 : Rng -> { value : I16, rng : Rng }
+i16 = \rng -> mapValue (draw rng (Num.toU64 Num.maxU16)) \n -> Num.toI16 (zigzag n)
+
+i32 : Rng -> { value : I32, rng : Rng }
+i32 = \rng -> mapValue (draw rng (Num.toU64 Num.maxU32)) \n -> Num.toI32 (zigzag n)
+
+i64 : Rng -> { value : I64, rng : Rng }
+i64 = \rng -> mapValue (draw rng Num.maxU64) zigzag
+
+i128 : Rng -> { value : I128, rng : Rng }
+i128 = \rng ->
+    unsigned = u128 rng
+    n = unsigned.value
+
+    {
+        value: Num.bitwiseXor (Num.toI128 (Num.shiftRightZfBy 1 n)) (Num.neg (Num.toI128 (Num.bitwiseAnd n 1))),
+        rng: unsigned.rng,
+    }
+
+zigzag : U64 -> I64
+zigzag = \n ->
+    Num.bitwiseXor (Num.toI64 (Num.shiftRightZfBy 1 n)) (Num.neg (Num.toI64 (Num.bitwiseAnd n 1)))
+
+# Fractions are generated as a whole part and a number of 1024ths, so that
+# the simplest fractions are whole numbers.
+f32 : Rng -> { value : F32, rng : Rng }
+f32 = \rng ->
+    whole = i32 rng
+    fraction = draw whole.rng 1023
+
+    { value: Num.toF32 whole.value + Num.toF32 fraction.value / 1024, rng: fraction.rng }
+
+f64 : Rng -> { value : F64, rng : Rng }
+f64 = \rng ->
+    whole = i32 rng
+    fraction = draw whole.rng 1023
+
+    { value: Num.toF64 whole.value + Num.toF64 fraction.value / 1024, rng: fraction.rng }
+
+dec : Rng -> { value : Dec, rng : Rng }
+dec = \rng ->
+    whole = i32 rng
+    fraction = draw whole.rng 1023
+
+    { value: Num.toFrac whole.value + Num.toFrac fraction.value / 1024, rng: fraction.rng }
+
+mapValue : { value : a, rng : Rng }, (a -> b) -> { value : b, rng : Rng }
+mapValue = \{ value, rng }, f -> { value: f value, rng }
+
+## Checks a property against `iterations` generated inputs, and returns the
+## simplest input it could find for which the property does not hold. If the
+## property holds for every input, the last input that was tried is returned.
+## At least one input is always tried.
+##
+## The same `seed` always gives the same inputs. Inputs start out small, and
+## get bigger with every iteration.
+##
+## A property test like `expect \x -> x + 0 == x` is checked with this
+## function, and then fails exactly when the returned input makes its
+## condition `False`.
+check : { seed : U64, iterations : Nat }, (a -> Bool) -> a | a has Arbitrary
+check = \{ seed, iterations }, property ->
+    checkHelp (splitMix seed).value 1 iterations property
+
+checkHelp : U64, Nat, Nat, (a -> Bool) -> a | a has Arbitrary
+checkHelp = \state, budget, remaining, property ->
+    rng = @Rng { state, random: True, replay: [], choices: [], budget }
+
+    checkGenerated (arbitrary rng) budget remaining property
+
+checkGenerated : { value : a, rng : Rng }, Nat, Nat, (a -> Bool) -> a | a has Arbitrary
+checkGenerated = \{ value, rng }, budget, remaining, property ->
+    if !(property value) then
+        shrink (choicesOf rng) value budget property
+    else if remaining <= 1 then
+        value
+    else
+        nextBudget = if budget < maxBudget then budget + 1 else budget
+
+        checkHelp (stateOf rng) nextBudget (remaining - 1) property
+
+maxBudget : Nat
+maxBudget = 100
+
+stateOf : Rng -> U64
+stateOf = \@Rng { state } -> state
+
+choicesOf : Rng -> List U64
+choicesOf = \@Rng { choices } -> choices
+
+## Shrinks a failing input, given the choices it was generated from. Every
+## step replays simpler choices, and keeps them if the input they generate
+## still fails. Since the choices get simpler with every step, this always
+## terminates.
+shrink : List U64, a, Nat, (a -> Bool) -> a | a has Arbitrary
+shrink = \choices, value, budget, property ->
+    when shrinkAt choices 0 budget property is
+        Ok simpler -> shrink simpler.choices simpler.value budget property
+        Err NotFound -> value
+
+shrinkAt : List U64, Nat, Nat, (a -> Bool) -> Result { choices : List U64, value : a } [NotFound] | a has Arbitrary
+shrinkAt = \choices, index, budget, property ->
+    if index >= List.len choices then
+        Err NotFound
+    else
+        found =
+            List.walkUntil (candidatesAt choices index) (Err NotFound) \state, candidate ->
+                rng = @Rng { state: 0, random: False, replay: candidate, choices: [], budget }
+
+                when keepIfSimpler (arbitrary rng) choices property is
+                    Ok simpler -> Break (Ok simpler)
+                    Err NotSimpler -> Continue state
+
+        when found is
+            Ok simpler -> Ok simpler
+            Err NotFound -> shrinkAt choices (index + 1) budget property
+
+## Simpler versions of `choices` at `index`: first with chunks of choices
+## removed, then with the choice at `index` replaced by smaller ones.
+candidatesAt : List U64, Nat -> List (List U64)
+candidatesAt = \choices, index ->
+    length = List.len choices
+
+    removals =
+        List.walk [8, 4, 2, 1] [] \candidates, count ->
+            if index + count <= length then
+                before = List.takeFirst choices index
+                after = List.sublist choices { start: index + count, len: length - index - count }
+
+                List.append candidates (List.concat before after)
+            else
+                candidates
+
+    when List.get choices index is
+        Ok choice if choice > 0 ->
+            smaller = List.map (smallerChoices choice 1 [0]) \c -> List.set choices index c
+
+            List.concat removals smaller
+
+        _ -> removals
+
+## Keeps a replayed input if it was generated from simpler choices than the
+## current input, and still makes the property fail.
+keepIfSimpler : { value : a, rng : Rng }, List U64, (a -> Bool) -> Result { choices : List U64, value : a } [NotSimpler] | a has Arbitrary
+keepIfSimpler = \{ value, rng }, choices, property ->
+    replayedChoices = choicesOf rng
+
+    if isSimpler replayedChoices choices && !(property value) then
+        Ok { choices: replayedChoices, value }
+    else
+        Err NotSimpler
+
+## `0`, then choices closer and closer to `choice`: `choice - choice / 2`,
+## `choice - choice / 4`, and so on, up to `choice - 1`.
+smallerChoices : U64, U64, List U64 -> List U64
+smallerChoices = \choice, shift, candidates ->
+    if shift >= 64 then
+        candidates
+    else
+        distance = Num.shiftRightZfBy shift choice
+
+        if distance == 0 then
+            candidates
+        else
+            smallerChoices choice (shift + 1) (List.append candidates (choice - distance))
+
+## Choices are simpler if there are fewer of them, or if there are as many but
+## the first one that differs is smaller.
+isSimpler : List U64, List U64 -> Bool
+isSimpler = \left, right ->
+    if List.len left == List.len right then
+        order =
+            List.map2 left right Num.compare
+            |> List.walkUntil EQ \_, elemOrder ->
+                when elemOrder is
+                    EQ -> Continue EQ
+                    _ -> Break elemOrder
+
+        order == LT
+    else
+        List.len left < List.len right
+
+## A random choice, made with SplitMix64.
+randomChoice : U64 -> { value : U64, state : U64 }
+randomChoice = \state ->
+    width = splitMix state
+    bits = splitMix width.state
+
+    # Pick the number of bits first, so that small numbers are likely.
+    numBits = Num.rem width.value 65
+
+    value =
+        if numBits == 0 then
+            0
+        else
+            Num.shiftRightZfBy (64 - numBits) bits.value
+
+    { value, state: bits.state }
+
+splitMix : U64 -> { value : U64, state : U64 }
+splitMix = \state ->
+    next = Num.addWrap state golden
+    z1 = Num.mulWrap (Num.bitwiseXor next (Num.shiftRightZfBy 30 next)) mix1
+    z2 = Num.mulWrap (Num.bitwiseXor z1 (Num.shiftRightZfBy 27 z1)) mix2
+
+    { value: Num.bitwiseXor z2 (Num.shiftRightZfBy 31 z2), state: next }
+
+golden : U64
+golden = 0x9e37_79b9_7f4a_7c15
+
+mix1 : U64
+mix1 = 0xbf58_476d_1ce4_e5b9
+
+mix2 : U64
+mix2 = 0x94d0_49bb_1331_11eb
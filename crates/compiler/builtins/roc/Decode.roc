@@ -22,16 +22,35 @@ interface Decode
         bool,
         string,
         list,
+        record,
+        tag,
         custom,
         decodeWith,
         fromBytesPartial,
         fromBytes,
+        DecodeErrorInfo,
+        DecodePathSegment,
     ]
     imports [
         List,
+        Str,
     ]
 
-DecodeError : [TooShort]
+## Why decoding failed. `TooShort` means the input ended before a complete value was decoded, and
+## `Unexpected` means the input held something other than what the decoder expected.
+DecodeError : [TooShort DecodeErrorInfo, Unexpected DecodeErrorInfo]
+
+## Where decoding failed and why.
+##
+## `offset` is the number of bytes into the decoder's input where the problem was found, and `path`
+## is the sequence of record fields, tag payloads, and list elements leading from the decoded value
+## to the part of it that could not be decoded. `expected` and `found` describe, in prose, what the
+## decoder was looking for and what was there instead.
+DecodeErrorInfo : { offset : Nat, path : List DecodePathSegment, expected : Str, found : Str }
+
+## A step into a decoded value: a record field or a tag by name, or a list element or tag payload
+## by index.
+DecodePathSegment : [Field Str, Index Nat]
 
 DecodeResult val : { result : Result val DecodeError, rest : List U8 }
 
@@ -57,6 +76,8 @@ DecoderFormatting has
     bool : Decoder Bool fmt | fmt has DecoderFormatting
     string : Decoder Str fmt | fmt has DecoderFormatting
     list : Decoder elem fmt -> Decoder (List elem) fmt | fmt has DecoderFormatting
+    record : state, (state, Str -> [Keep (Decoder state fmt), Skip]), (state -> Result val DecodeError) -> Decoder val fmt | fmt has DecoderFormatting
+    tag : state, (state, Str, Nat -> [Keep (Decoder state fmt), Skip]), (state, Str -> Result val DecodeError) -> Decoder val fmt | fmt has DecoderFormatting
 
 custom : (List U8, fmt -> DecodeResult val) -> Decoder val fmt | fmt has DecoderFormatting
 custom = \decode -> @Decoder decode
@@ -71,9 +92,26 @@ fromBytes : List U8, fmt -> Result val [Leftover (List U8)]DecodeError | val has
 fromBytes = \bytes, fmt ->
     when fromBytesPartial bytes fmt is
         { result, rest } ->
-            if List.isEmpty rest then
-                when result is
-                    Ok val -> Ok val
-                    Err TooShort -> Err TooShort
-            else
-                Err (Leftover rest)
+            when result is
+                Ok val -> if List.isEmpty rest then Ok val else Err (Leftover rest)
+                # Report where decoding failed, rather than everything after that as leftover.
+                Err (TooShort info) -> Err (TooShort info)
+                Err (Unexpected info) -> Err (Unexpected info)
+
+# The helpers below are only used by derived decoders, which can refer to them even though they
+# aren't exposed (see `DERIVED_CODE_HELPERS`).
+#
+# The error for a record whose field `name` was not found in the input.
+missingField : Str -> DecodeError
+missingField = \name ->
+    Unexpected { offset: 0, path: [Field name], expected: "a value for this field", found: "no value" }
+
+# The error for a tag `name` whose payload at `index` was not found in the input.
+missingPayload : Str, Nat -> DecodeError
+missingPayload = \name, index ->
+    Unexpected { offset: 0, path: [Field name, Index index], expected: "a value for this payload", found: "no value" }
+
+# The error for a tag `name` that is not one of the tags of the decoded tag union.
+unknownTag : Str -> DecodeError
+unknownTag = \name ->
+    Unexpected { offset: 0, path: [], expected: "a tag of this union", found: "the tag \(name)" }
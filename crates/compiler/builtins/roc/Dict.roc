@@ -20,6 +20,7 @@ interface Dict
         Bool.{ Bool },
         Result.{ Result },
         List,
+        Hash.{ Hash, Hasher },
     ]
 
 ## A [dictionary](https://en.wikipedia.org/wiki/Associative_array) that lets you can associate keys with values.
@@ -63,118 +64,421 @@ interface Dict
 ## [Dict.remove] does: it removes an element and moves the most recent insertion into the vacated spot.
 ##
 ## This move is done as a performance optimization, and it lets [remove] have
-## [constant time complexity](https://en.wikipedia.org/wiki/Time_complexity#Constant_time). ##
+## [constant time complexity](https://en.wikipedia.org/wiki/Time_complexity#Constant_time).
+##
+## ### Hashing
+##
+## A dictionary is a [hash table](https://en.wikipedia.org/wiki/Hash_table): [Dict.get], [Dict.insert],
+## [Dict.remove] and [Dict.contains] take (amortized) constant time, no matter how many entries the
+## dictionary holds. For this to work, its keys must implement the [Hash] ability. Records, tag unions,
+## strings, lists and integers all do so automatically; an opaque type can use `has [Hash]` to derive it.
 ##
 ## ### Equality
 ##
 ## When comparing two dictionaries for equality, they are `==` only if their both their contents and their
 ## orderings match. This preserves the property that if `dict1 == dict2`, you should be able to rely on
 ## `fn dict1 == fn dict2` also being `True`, even if `fn` relies on the dictionary's ordering.
-Dict k v := List [Pair k v]
+##
+# The entries live in `data`, in insertion order (modulo [remove]), which is what [walk], [keys] and
+# [values] iterate over. `buckets` is an open-addressing index into `data` that uses linear probing:
+# an empty bucket holds 0, an occupied one holds the index of its entry in `data` plus one.
+#
+# Roc's `==` compares the structure of an opaque type, so `buckets` must only depend on `data`, never on
+# the operations that built it. That's why the number of buckets is always `bucketCountFor (List.len data)`
+# (0 or a power of two), and why entries are placed Robin Hood style: within a probe sequence, entries
+# are ordered by how far they are from their home bucket, then by their index in `data`.
+#
+# Hosts build the buckets the same way (see `RocDict` in roc_std), so changing how keys are
+# hashed or probed here has to be done there too.
+Dict k v := {
+    buckets : List Nat,
+    data : List [Pair k v],
+}
 
 ## An empty dictionary.
 empty : Dict k v
-empty = @Dict []
+empty = @Dict { buckets: [], data: [] }
 
+## Returns an empty dictionary with space pre-allocated for `n` entries.
 withCapacity : Nat -> Dict k v
-withCapacity = \n -> @Dict (List.withCapacity n)
+withCapacity = \n ->
+    @Dict { buckets: [], data: List.withCapacity n }
 
-get : Dict k v, k -> Result v [KeyNotFound]*
-get = \@Dict list, needle ->
-    when List.findFirst list (\Pair key _ -> key == needle) is
-        Ok (Pair _ v) ->
-            Ok v
+get : Dict k v, k -> Result v [KeyNotFound]* | k has Hash
+get = \@Dict { buckets, data }, key ->
+    when findSlot buckets data key is
+        Found _ dataIndex ->
+            when List.get data dataIndex is
+                Ok (Pair _ v) ->
+                    Ok v
 
-        Err NotFound ->
+                Err OutOfBounds ->
+                    Err KeyNotFound
+
+        Vacant _ ->
             Err KeyNotFound
 
 walk : Dict k v, state, (state, k, v -> state) -> state
-walk = \@Dict list, initialState, transform ->
-    List.walk list initialState (\state, Pair k v -> transform state k v)
+walk = \@Dict { data }, initialState, transform ->
+    List.walk data initialState (\state, Pair k v -> transform state k v)
 
-insert : Dict k v, k, v -> Dict k v
-insert = \@Dict list, k, v ->
-    when List.findFirstIndex list (\Pair key _ -> key == k) is
-        Err NotFound ->
-            insertFresh (@Dict list) k v
+insert : Dict k v, k, v -> Dict k v | k has Hash
+insert = \@Dict { buckets, data }, key, value ->
+    when findSlot buckets data key is
+        Found _ dataIndex ->
+            @Dict { buckets, data: List.set data dataIndex (Pair key value) }
 
-        Ok index ->
-            list
-            |> List.set index (Pair k v)
-            |> @Dict
+        Vacant _ ->
+            insertFresh (@Dict { buckets, data }) key value
 
 len : Dict k v -> Nat
-len = \@Dict list ->
-    List.len list
-
-remove : Dict k v, k -> Dict k v
-remove = \@Dict list, key ->
-    when List.findFirstIndex list (\Pair k _ -> k == key) is
-        Err NotFound ->
-            @Dict list
-
-        Ok index ->
-            lastIndex = List.len list - 1
-
-            list
-            |> List.swap index lastIndex
-            |> List.dropLast
-            |> @Dict
-
-contains : Dict k v, k -> Bool
-contains = \@Dict list, needle ->
-    step = \_, Pair key _val ->
-        if key == needle then
-            Break {}
-        else
-            Continue {}
+len = \@Dict { data } ->
+    List.len data
+
+remove : Dict k v, k -> Dict k v | k has Hash
+remove = \@Dict { buckets, data }, key ->
+    when findSlot buckets data key is
+        Vacant _ ->
+            @Dict { buckets, data }
+
+        Found bucketIndex dataIndex ->
+            lastIndex = List.len data - 1
+            newData =
+                data
+                |> List.swap dataIndex lastIndex
+                |> List.dropLast
+
+            if bucketCountFor lastIndex != List.len buckets then
+                @Dict { buckets: rehash newData (bucketCountFor lastIndex), data: newData }
+            else
+                cleared = removeBucket buckets data bucketIndex
+
+                if dataIndex == lastIndex then
+                    @Dict { buckets: cleared, data: newData }
+                else
+                    # The most recent insertion moves into the vacated spot. Its place among
+                    # the buckets depends on its index, so it's taken out and put back in.
+                    when List.get data lastIndex is
+                        Ok (Pair lastKey _) ->
+                            movedBuckets =
+                                when findSlot cleared data lastKey is
+                                    Found lastBucketIndex _ ->
+                                        removeBucket cleared data lastBucketIndex
+                                        |> placeEntry newData dataIndex
 
-    when List.iterate list {} step is
-        Continue _ -> False
-        Break _ -> True
+                                    Vacant _ ->
+                                        # the last entry is still in the buckets, so this can't happen
+                                        rehash newData (List.len buckets)
 
-single : k, v -> Dict k v
+                            @Dict { buckets: movedBuckets, data: newData }
+
+                        Err OutOfBounds ->
+                            @Dict { buckets: cleared, data: newData }
+
+contains : Dict k v, k -> Bool | k has Hash
+contains = \@Dict { buckets, data }, key ->
+    when findSlot buckets data key is
+        Found _ _ -> True
+        Vacant _ -> False
+
+single : k, v -> Dict k v | k has Hash
 single = \key, value ->
-    @Dict [Pair key value]
+    insert empty key value
 
 ## Returns a [List] of the dictionary's keys.
 keys : Dict k v -> List k
-keys = \@Dict list ->
-    List.map list (\Pair k _ -> k)
+keys = \@Dict { data } ->
+    List.map data (\Pair k _ -> k)
 
 ## Returns a [List] of the Dict's values
 values : Dict k v -> List v
-values = \@Dict list ->
-    List.map list (\Pair _ v -> v)
+values = \@Dict { data } ->
+    List.map data (\Pair _ v -> v)
 
 # union : Dict k v, Dict k v -> Dict k v
-insertAll : Dict k v, Dict k v -> Dict k v
-insertAll = \xs, @Dict ys ->
+insertAll : Dict k v, Dict k v -> Dict k v | k has Hash
+insertAll = \xs, @Dict { data: ys } ->
     List.walk ys xs (\state, Pair k v -> Dict.insertIfVacant state k v)
 
 # intersection : Dict k v, Dict k v -> Dict k v
-keepShared : Dict k v, Dict k v -> Dict k v
-keepShared = \@Dict xs, ys ->
-    List.keepIf xs (\Pair k _ -> Dict.contains ys k)
-    |> @Dict
+keepShared : Dict k v, Dict k v -> Dict k v | k has Hash
+keepShared = \xs, ys ->
+    walk xs empty \state, k, v ->
+        if Dict.contains ys k then
+            Dict.insert state k v
+        else
+            state
 
 # difference : Dict k v, Dict k v -> Dict k v
-removeAll : Dict k v, Dict k v -> Dict k v
-removeAll = \xs, @Dict ys ->
+removeAll : Dict k v, Dict k v -> Dict k v | k has Hash
+removeAll = \xs, @Dict { data: ys } ->
     List.walk ys xs (\state, Pair k _ -> Dict.remove state k)
 
 ## Internal helper function to insert a new association
 ##
 ## Precondition: `k` should not exist in the Dict yet.
-insertFresh : Dict k v, k, v -> Dict k v
-insertFresh = \@Dict list, k, v ->
-    list
-    |> List.append (Pair k v)
-    |> @Dict
+insertFresh : Dict k v, k, v -> Dict k v | k has Hash
+insertFresh = \@Dict { buckets, data }, key, value ->
+    newLen = List.len data + 1
+    newData = List.append data (Pair key value)
 
-insertIfVacant : Dict k v, k, v -> Dict k v
+    if bucketCountFor newLen != List.len buckets then
+        @Dict { buckets: rehash newData (bucketCountFor newLen), data: newData }
+    else
+        @Dict { buckets: placeEntry buckets newData (newLen - 1), data: newData }
+
+insertIfVacant : Dict k v, k, v -> Dict k v | k has Hash
 insertIfVacant = \dict, key, value ->
     if Dict.contains dict key then
         dict
     else
         Dict.insert dict key value
+
+emptyBucket : Nat
+emptyBucket = 0
+
+## The number of buckets a non-empty dictionary has at least.
+minBucketCount : Nat
+minBucketCount = 8
+
+## Whether `n` entries would exceed the maximum load factor (7/8) of a table with
+## `bucketCount` buckets. Because the table is never completely full, probing for
+## a key always terminates at an empty bucket.
+exceedsMaxLoad : Nat, Nat -> Bool
+exceedsMaxLoad = \n, bucketCount ->
+    n * 8 > bucketCount * 7
+
+## The smallest number of buckets that can hold `n` entries.
+bucketCountFor : Nat -> Nat
+bucketCountFor = \n ->
+    if n == 0 then
+        0
+    else
+        bucketCountForHelp n minBucketCount
+
+bucketCountForHelp : Nat, Nat -> Nat
+bucketCountForHelp = \n, bucketCount ->
+    if exceedsMaxLoad n bucketCount then
+        bucketCountForHelp n (bucketCount * 2)
+    else
+        bucketCount
+
+## Finds the bucket of the given key, or the empty bucket where it should be inserted.
+findSlot : List Nat, List [Pair k v], k -> [Found Nat Nat, Vacant Nat] | k has Hash
+findSlot = \buckets, data, key ->
+    if List.isEmpty buckets then
+        Vacant 0
+    else
+        findSlotHelp buckets data key (homeBucket buckets key)
+
+findSlotHelp : List Nat, List [Pair k v], k, Nat -> [Found Nat Nat, Vacant Nat]
+findSlotHelp = \buckets, data, key, bucketIndex ->
+    when List.get buckets bucketIndex is
+        Ok slot ->
+            if slot == emptyBucket then
+                Vacant bucketIndex
+            else
+                when List.get data (slot - 1) is
+                    Ok (Pair storedKey _) ->
+                        if storedKey == key then
+                            Found bucketIndex (slot - 1)
+                        else
+                            findSlotHelp buckets data key (nextBucket buckets bucketIndex)
+
+                    Err OutOfBounds ->
+                        # every occupied bucket points to an entry, so this can't happen
+                        Vacant bucketIndex
+
+        Err OutOfBounds ->
+            Vacant bucketIndex
+
+## Builds the buckets for the given entries from scratch.
+rehash : List [Pair k v], Nat -> List Nat | k has Hash
+rehash = \data, bucketCount ->
+    rehashHelp (List.repeat emptyBucket bucketCount) data 0
+
+rehashHelp : List Nat, List [Pair k v], Nat -> List Nat | k has Hash
+rehashHelp = \buckets, data, dataIndex ->
+    if dataIndex < List.len data then
+        rehashHelp (placeEntry buckets data dataIndex) data (dataIndex + 1)
+    else
+        buckets
+
+## Puts the entry at `dataIndex` into the buckets. Entries that are further from their home
+## bucket go first, and entries that are just as far go in the order of their index, so the
+## buckets end up the same no matter in which order the entries were placed.
+placeEntry : List Nat, List [Pair k v], Nat -> List Nat | k has Hash
+placeEntry = \buckets, data, dataIndex ->
+    when List.get data dataIndex is
+        Ok (Pair key _) ->
+            placeEntryHelp buckets data (dataIndex + 1) (homeBucket buckets key) 0
+
+        Err OutOfBounds ->
+            buckets
+
+placeEntryHelp : List Nat, List [Pair k v], Nat, Nat, Nat -> List Nat | k has Hash
+placeEntryHelp = \buckets, data, slot, bucketIndex, distance ->
+    when List.get buckets bucketIndex is
+        Ok resident ->
+            if resident == emptyBucket then
+                List.set buckets bucketIndex slot
+            else
+                residentDistance = probeDistance buckets (slotHome buckets data resident) bucketIndex
+
+                if residentDistance < distance || (residentDistance == distance && resident > slot) then
+                    # this entry takes the bucket, and the resident moves further along
+                    List.set buckets bucketIndex slot
+                    |> placeEntryHelp data resident (nextBucket buckets bucketIndex) (residentDistance + 1)
+                else
+                    placeEntryHelp buckets data slot (nextBucket buckets bucketIndex) (distance + 1)
+
+        Err OutOfBounds ->
+            buckets
+
+## Empties the bucket at index `hole`, then shifts the entries that follow it in the same
+## probe sequence back by one, until it reaches an empty bucket or an entry in its home bucket.
+removeBucket : List Nat, List [Pair k v], Nat -> List Nat | k has Hash
+removeBucket = \buckets, data, hole ->
+    removeBucketHelp (List.set buckets hole emptyBucket) data hole (nextBucket buckets hole)
+
+removeBucketHelp : List Nat, List [Pair k v], Nat, Nat -> List Nat | k has Hash
+removeBucketHelp = \buckets, data, hole, bucketIndex ->
+    when List.get buckets bucketIndex is
+        Ok slot ->
+            if slot == emptyBucket then
+                buckets
+            else if slotHome buckets data slot == bucketIndex then
+                buckets
+            else
+                buckets
+                |> List.set hole slot
+                |> List.set bucketIndex emptyBucket
+                |> removeBucketHelp data bucketIndex (nextBucket buckets bucketIndex)
+
+        Err OutOfBounds ->
+            buckets
+
+## The home bucket of the entry that an occupied bucket holding `slot` points to.
+slotHome : List Nat, List [Pair k v], Nat -> Nat | k has Hash
+slotHome = \buckets, data, slot ->
+    when List.get data (slot - 1) is
+        Ok (Pair key _) ->
+            homeBucket buckets key
+
+        Err OutOfBounds ->
+            # every occupied bucket points to an entry, so this can't happen
+            0
+
+## The bucket where probing for the given key starts.
+homeBucket : List Nat, k -> Nat | k has Hash
+homeBucket = \buckets, key ->
+    mask = Num.toU64 (List.len buckets - 1)
+
+    hashKey key
+    |> Num.bitwiseAnd mask
+    |> Num.toNat
+
+nextBucket : List Nat, Nat -> Nat
+nextBucket = \buckets, bucketIndex ->
+    Num.bitwiseAnd (bucketIndex + 1) (List.len buckets - 1)
+
+## The number of steps it takes to probe from bucket `from` to bucket `to`.
+probeDistance : List Nat, Nat, Nat -> Nat
+probeDistance = \buckets, from, to ->
+    bucketCount = List.len buckets
+
+    Num.bitwiseAnd (to + bucketCount - from) (bucketCount - 1)
+
+hashKey : k -> U64 | k has Hash
+hashKey = \key ->
+    @LowLevelHasher defaultSeed
+    |> Hash.hash key
+    |> Hash.complete
+
+# The hasher used for dictionary keys. Byte sequences are hashed with the wyhash
+# implementation in the builtins' `hash.zig`, while integers are mixed in directly,
+# using the same mixing function wyhash uses.
+LowLevelHasher := U64 has [
+         Hasher {
+             addBytes: addBytes,
+             addU8: addU8,
+             addU16: addU16,
+             addU32: addU32,
+             addU64: addU64,
+             addU128: addU128,
+             addI8: addI8,
+             addI16: addI16,
+             addI32: addI32,
+             addI64: addI64,
+             addI128: addI128,
+             complete: complete,
+         },
+     ]
+
+# Calls `wyhash` from `hash.zig`, using the first argument as the seed
+hashBytesLowlevel : U64, List U8 -> U64
+
+defaultSeed : U64
+defaultSeed = 0x526F_6352_616E_643F
+
+wyp0 : U64
+wyp0 = 0xa076_1d64_78bd_642f
+
+wyp1 : U64
+wyp1 = 0xe703_7ed1_a0b4_28db
+
+wyp2 : U64
+wyp2 = 0x8ebc_6af0_9c88_c6e3
+
+wymix : U64, U64 -> U64
+wymix = \a, b ->
+    product = Num.mulWrap (Num.toU128 a) (Num.toU128 b)
+    lower = Num.toU64 product
+    upper = Num.toU64 (Num.shiftRightZfBy 64 product)
+
+    Num.bitwiseXor lower upper
+
+addBytes : LowLevelHasher, List U8 -> LowLevelHasher
+addBytes = \@LowLevelHasher state, bytes ->
+    @LowLevelHasher (hashBytesLowlevel state bytes)
+
+addU8 : LowLevelHasher, U8 -> LowLevelHasher
+addU8 = \hasher, n -> addU64 hasher (Num.toU64 n)
+
+addU16 : LowLevelHasher, U16 -> LowLevelHasher
+addU16 = \hasher, n -> addU64 hasher (Num.toU64 n)
+
+addU32 : LowLevelHasher, U32 -> LowLevelHasher
+addU32 = \hasher, n -> addU64 hasher (Num.toU64 n)
+
+addU64 : LowLevelHasher, U64 -> LowLevelHasher
+addU64 = \@LowLevelHasher state, n ->
+    @LowLevelHasher (wymix (Num.bitwiseXor state wyp0) (Num.bitwiseXor n wyp1))
+
+addU128 : LowLevelHasher, U128 -> LowLevelHasher
+addU128 = \hasher, n ->
+    lower = Num.toU64 n
+    upper = Num.toU64 (Num.shiftRightZfBy 64 n)
+
+    hasher
+    |> addU64 lower
+    |> addU64 upper
+
+addI8 : LowLevelHasher, I8 -> LowLevelHasher
+addI8 = \hasher, n -> addU8 hasher (Num.toU8 n)
+
+addI16 : LowLevelHasher, I16 -> LowLevelHasher
+addI16 = \hasher, n -> addU16 hasher (Num.toU16 n)
+
+addI32 : LowLevelHasher, I32 -> LowLevelHasher
+addI32 = \hasher, n -> addU32 hasher (Num.toU32 n)
+
+addI64 : LowLevelHasher, I64 -> LowLevelHasher
+addI64 = \hasher, n -> addU64 hasher (Num.toU64 n)
+
+addI128 : LowLevelHasher, I128 -> LowLevelHasher
+addI128 = \hasher, n -> addU128 hasher (Num.toU128 n)
+
+complete : LowLevelHasher -> U64
+complete = \@LowLevelHasher state ->
+    wymix state wyp2
@@ -0,0 +1,78 @@
+interface Hash
+    exposes [
+        Hash,
+        Hasher,
+        hash,
+        addBytes,
+        addU8,
+        addU16,
+        addU32,
+        addU64,
+        addU128,
+        addI8,
+        addI16,
+        addI32,
+        addI64,
+        addI128,
+        complete,
+        hashStrBytes,
+        hashList,
+        hashNat,
+    ]
+    imports [
+        List,
+        Str,
+    ]
+
+## A value that can be hashed.
+##
+## The [Hash] ability can be derived for records, tag unions and opaque types,
+## as long as all the values they contain can be hashed as well. Functions and
+## floating-point numbers can not be hashed.
+Hash has
+    hash : hasher, a -> hasher | a has Hash, hasher has Hasher
+
+## Describes a hashing algorithm that is fed bytes and produces an integer hash.
+##
+## The [Hasher] ability describes general-purpose hashers. It only allows
+## emission of 64-bit unsigned integer hashes. It is not suitable for
+## cryptographically-secure hashing.
+##
+## Note that [hash] does not produce a hash value itself; the hasher must be
+## [complete]d in order to extract the hash value.
+Hasher has
+    addBytes : a, List U8 -> a | a has Hasher
+    addU8 : a, U8 -> a | a has Hasher
+    addU16 : a, U16 -> a | a has Hasher
+    addU32 : a, U32 -> a | a has Hasher
+    addU64 : a, U64 -> a | a has Hasher
+    addU128 : a, U128 -> a | a has Hasher
+    addI8 : a, I8 -> a | a has Hasher
+    addI16 : a, I16 -> a | a has Hasher
+    addI32 : a, I32 -> a | a has Hasher
+    addI64 : a, I64 -> a | a has Hasher
+    addI128 : a, I128 -> a | a has Hasher
+    complete : a -> U64 | a has Hasher
+
+## Adds a string into a [Hasher] by hashing its UTF-8 bytes.
+##
+## The length of the string is hashed first, so that e.g. the records
+## `{ a: "ab", b: "c" }` and `{ a: "a", b: "bc" }` hash differently.
+hashStrBytes : a, Str -> a | a has Hasher
+hashStrBytes = \hasher, s ->
+    hasher
+    |> hashNat (Str.countUtf8Bytes s)
+    |> addBytes (Str.toUtf8 s)
+
+## Adds a list of [Hash]able elements to a [Hasher] by hashing each element,
+## prefixed by the length of the list.
+hashList : a, List b -> a | a has Hasher, b has Hash
+hashList = \hasher, lst ->
+    List.walk lst (hashNat hasher (List.len lst)) \accumHasher, elem ->
+        hash accumHasher elem
+
+## Adds a [Nat] to a [Hasher]. The number is always hashed as a [U64], so that
+## hashes don't depend on the pointer width of the target.
+hashNat : a, Nat -> a | a has Hasher
+hashNat = \hasher, n ->
+    addU64 hasher (Num.toU64 n)
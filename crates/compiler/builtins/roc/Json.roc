@@ -16,6 +16,8 @@ interface Json
         Decode,
         Decode.{
             DecoderFormatting,
+            DecodeError,
+            DecodePathSegment,
         },
     ]
 
@@ -57,6 +59,8 @@ Json := {} has [
              bool: decodeBool,
              string: decodeString,
              list: decodeList,
+             record: decodeRecord,
+             tag: decodeTag,
          },
      ]
 
@@ -211,91 +215,91 @@ decodeU8 = Decode.custom \bytes, @Json {} ->
 
     when Str.fromUtf8 taken |> Result.try Str.toU8 is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a U8"), rest }
 
 decodeU16 = Decode.custom \bytes, @Json {} ->
     { taken, rest } = takeDigits bytes
 
     when Str.fromUtf8 taken |> Result.try Str.toU16 is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a U16"), rest }
 
 decodeU32 = Decode.custom \bytes, @Json {} ->
     { taken, rest } = takeDigits bytes
 
     when Str.fromUtf8 taken |> Result.try Str.toU32 is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a U32"), rest }
 
 decodeU64 = Decode.custom \bytes, @Json {} ->
     { taken, rest } = takeDigits bytes
 
     when Str.fromUtf8 taken |> Result.try Str.toU64 is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a U64"), rest }
 
 decodeU128 = Decode.custom \bytes, @Json {} ->
     { taken, rest } = takeDigits bytes
 
     when Str.fromUtf8 taken |> Result.try Str.toU128 is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a U128"), rest }
 
 decodeI8 = Decode.custom \bytes, @Json {} ->
     { taken, rest } = takeDigits bytes
 
     when Str.fromUtf8 taken |> Result.try Str.toI8 is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a I8"), rest }
 
 decodeI16 = Decode.custom \bytes, @Json {} ->
     { taken, rest } = takeDigits bytes
 
     when Str.fromUtf8 taken |> Result.try Str.toI16 is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a I16"), rest }
 
 decodeI32 = Decode.custom \bytes, @Json {} ->
     { taken, rest } = takeDigits bytes
 
     when Str.fromUtf8 taken |> Result.try Str.toI32 is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a I32"), rest }
 
 decodeI64 = Decode.custom \bytes, @Json {} ->
     { taken, rest } = takeDigits bytes
 
     when Str.fromUtf8 taken |> Result.try Str.toI64 is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a I64"), rest }
 
 decodeI128 = Decode.custom \bytes, @Json {} ->
     { taken, rest } = takeDigits bytes
 
     when Str.fromUtf8 taken |> Result.try Str.toI128 is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a I128"), rest }
 
 decodeF32 = Decode.custom \bytes, @Json {} ->
     { taken, rest } = takeFloat bytes
 
     when Str.fromUtf8 taken |> Result.try Str.toF32 is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a F32"), rest }
 
 decodeF64 = Decode.custom \bytes, @Json {} ->
     { taken, rest } = takeFloat bytes
 
     when Str.fromUtf8 taken |> Result.try Str.toF64 is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a F64"), rest }
 
 decodeDec = Decode.custom \bytes, @Json {} ->
     { taken, rest } = takeFloat bytes
 
     when Str.fromUtf8 taken |> Result.try Str.toDec is
         Ok n -> { result: Ok n, rest }
-        Err _ -> { result: Err TooShort, rest }
+        Err _ -> { result: Err (errorAt bytes bytes "a Dec"), rest }
 
 decodeBool = Decode.custom \bytes, @Json {} ->
     { before: maybeFalse, others: afterFalse } = List.split bytes 5
@@ -314,7 +318,7 @@ decodeBool = Decode.custom \bytes, @Json {} ->
         then
             { result: Ok True, rest: afterTrue }
         else
-            { result: Err TooShort, rest: bytes }
+            { result: Err (errorAt bytes bytes "a Bool"), rest: bytes }
 
 decodeString = Decode.custom \bytes, @Json {} ->
     { before, others: afterStartingQuote } = List.split bytes 1
@@ -327,13 +331,18 @@ decodeString = Decode.custom \bytes, @Json {} ->
 
         when Str.fromUtf8 strSequence is
             Ok s ->
-                { others: afterEndingQuote } = List.split rest 1
+                { before: maybeEndingQuote, others: afterEndingQuote } = List.split rest 1
 
-                { result: Ok s, rest: afterEndingQuote }
+                if
+                    maybeEndingQuote == [asciiByte '"']
+                then
+                    { result: Ok s, rest: afterEndingQuote }
+                else
+                    { result: Err (errorAt bytes rest "a closing `\"`"), rest }
 
-            Err _ -> { result: Err TooShort, rest }
+            Err _ -> { result: Err (errorAt bytes afterStartingQuote "a UTF-8 string"), rest }
     else
-        { result: Err TooShort, rest: bytes }
+        { result: Err (errorAt bytes bytes "a string"), rest: bytes }
 
 decodeList = \decodeElem -> Decode.custom \bytes, @Json {} ->
         decodeElems = \chunk, accum ->
@@ -351,7 +360,10 @@ decodeList = \decodeElem -> Decode.custom \bytes, @Json {} ->
                             else
                                 Done (List.append accum val) rest
 
-                        Err e -> Errored e rest
+                        Err e ->
+                            path = [Index (List.len accum)]
+
+                            Errored (nestError e (offsetOf bytes chunk) path) rest
 
         { before, others: afterStartingBrace } = List.split bytes 1
 
@@ -369,6 +381,245 @@ decodeList = \decodeElem -> Decode.custom \bytes, @Json {} ->
                     then
                         { result: Ok vals, rest: afterEndingBrace }
                     else
-                        { result: Err TooShort, rest }
+                        { result: Err (errorAt bytes rest "`,` or `]`"), rest }
+        else
+            { result: Err (errorAt bytes bytes "a list"), rest: bytes }
+
+# Takes a `"name":` prefix, as found before record fields and tag payloads, from `chunk`, a suffix
+# of the decoder input `bytes`.
+takeFieldName = \bytes, chunk ->
+    { before, others: afterStartingQuote } = List.split chunk 1
+
+    if
+        before == [asciiByte '"']
+    then
+        { taken: nameBytes, rest } = takeWhile afterStartingQuote \n -> n != asciiByte '"'
+        { before: afterName, others: afterColon } = List.split rest 2
+
+        if
+            afterName == [asciiByte '"', asciiByte ':']
+        then
+            when Str.fromUtf8 nameBytes is
+                Ok name -> Ok { name, rest: afterColon }
+                Err _ -> Err (errorAt bytes afterStartingQuote "a UTF-8 name")
+        else
+            Err (errorAt bytes rest "`\":`")
+    else
+        Err (errorAt bytes chunk "a name in quotes")
+
+# Skips over one JSON value, for record fields the decoder is not interested in. Fails only when
+# the input ends before the value does.
+skipValue = \bytes ->
+    helper = \chunk, depth, inString ->
+        when List.first chunk is
+            Err _ -> if depth == 0 && !inString then Ok chunk else Err TooShort
+            Ok b ->
+                { others } = List.split chunk 1
+
+                if inString then
+                    if b == 92 then
+                        # A backslash escapes the next character, which may be a `"` that doesn't end the string
+                        helper (List.drop others 1) depth True
+                    else if b == asciiByte '"' then
+                        if depth == 0 then Ok others else helper others depth False
+                    else
+                        helper others depth True
+                else if b == asciiByte '"' then
+                    helper others depth True
+                else if b == asciiByte '[' || b == asciiByte '{' then
+                    helper others (depth + 1) False
+                else if b == asciiByte ']' || b == asciiByte '}' then
+                    if depth == 0 then
+                        # The end of the enclosing record; the skipped value was a scalar.
+                        Ok chunk
+                    else if depth == 1 then
+                        Ok others
+                    else
+                        helper others (depth - 1) False
+                else if depth == 0 && b == asciiByte ',' then
+                    Ok chunk
+                else
+                    helper others depth False
+
+    helper bytes 0 False
+
+decodeRecord = \initialState, stepField, finalizer -> Decode.custom \bytes, @Json {} ->
+        # Idea: decode `{"f1":v1,"f2":v2}` by handing each value to the decoder `stepField` picks for its key
+        decodeFields = \state, chunk ->
+            when takeFieldName bytes chunk is
+                Err e -> Errored e chunk
+                Ok { name, rest: afterName } ->
+                    valueResult =
+                        when stepField state name is
+                            Keep valueDecoder -> Decode.decodeWith afterName valueDecoder (@Json {})
+                            Skip ->
+                                when skipValue afterName is
+                                    Ok rest -> { result: Ok state, rest }
+                                    Err TooShort -> { result: Err (errorAt afterName [] "the end of the value"), rest: [] }
+
+                    when valueResult is
+                        { result, rest } ->
+                            when result is
+                                Ok newState ->
+                                    { before: afterValue, others } = List.split rest 1
+
+                                    if
+                                        afterValue == [asciiByte ',']
+                                    then
+                                        decodeFields newState others
+                                    else
+                                        Done newState rest
+
+                                Err e ->
+                                    path = [Field name]
+
+                                    Errored (nestError e (offsetOf bytes afterName) path) rest
+
+        { before, others: afterStartingBrace } = List.split bytes 1
+
+        if
+            before == [asciiByte '{']
+        then
+            { before: maybeEmpty } = List.split afterStartingBrace 1
+
+            fieldsResult =
+                if
+                    maybeEmpty == [asciiByte '}']
+                then
+                    Done initialState afterStartingBrace
+                else
+                    decodeFields initialState afterStartingBrace
+
+            when fieldsResult is
+                Errored e rest -> { result: Err e, rest }
+                Done state rest ->
+                    { before: maybeEndingBrace, others: afterEndingBrace } = List.split rest 1
+
+                    if
+                        maybeEndingBrace == [asciiByte '}']
+                    then
+                        result =
+                            finalizer state
+                            |> Result.mapErr \e -> nestError e (offsetOf bytes rest) []
+
+                        { result, rest: afterEndingBrace }
+                    else
+                        { result: Err (errorAt bytes rest "`,` or `}`"), rest }
+        else
+            { result: Err (errorAt bytes bytes "a record"), rest: bytes }
+
+decodeTag = \initialState, stepPayload, finalizer -> Decode.custom \bytes, @Json {} ->
+        # Idea: decode `{"A":[v1,v2]}` by handing each payload value to the decoder `stepPayload`
+        # picks for its tag name and position
+        decodePayload = \state, name, index, chunk ->
+            path = [Field name, Index index]
+
+            when stepPayload state name index is
+                Skip ->
+                    # There is no such payload; the tag must have ended already.
+                    Errored (nestError (errorAt chunk chunk "`]`") (offsetOf bytes chunk) path) chunk
+
+                Keep payloadDecoder ->
+                    when Decode.decodeWith chunk payloadDecoder (@Json {}) is
+                        { result, rest } ->
+                            when result is
+                                Ok newState ->
+                                    { before: afterPayload, others } = List.split rest 1
+
+                                    if
+                                        afterPayload == [asciiByte ',']
+                                    then
+                                        decodePayload newState name (index + 1) others
+                                    else
+                                        Done newState rest
+
+                                Err e -> Errored (nestError e (offsetOf bytes chunk) path) rest
+
+        { before, others: afterStartingBrace } = List.split bytes 1
+
+        if
+            before == [asciiByte '{']
+        then
+            when takeFieldName bytes afterStartingBrace is
+                Err e -> { result: Err e, rest: afterStartingBrace }
+                Ok { name, rest: afterName } ->
+                    { before: maybeStartingBracket, others: afterStartingBracket } = List.split afterName 1
+
+                    if
+                        maybeStartingBracket == [asciiByte '[']
+                    then
+                        { before: maybeEmpty } = List.split afterStartingBracket 1
+
+                        payloadResult =
+                            if
+                                maybeEmpty == [asciiByte ']']
+                            then
+                                Done initialState afterStartingBracket
+                            else
+                                decodePayload initialState name 0 afterStartingBracket
+
+                        when payloadResult is
+                            Errored e rest -> { result: Err e, rest }
+                            Done state rest ->
+                                { before: maybeEnd, others: afterEnd } = List.split rest 2
+
+                                if
+                                    maybeEnd == [asciiByte ']', asciiByte '}']
+                                then
+                                    result =
+                                        finalizer state name
+                                        |> Result.mapErr \e -> nestError e (offsetOf bytes afterStartingBrace) []
+
+                                    { result, rest: afterEnd }
+                                else
+                                    { result: Err (errorAt bytes rest "`,` or `]}`"), rest }
+                    else
+                        { result: Err (errorAt bytes afterName "`[`"), rest: afterName }
         else
-            { result: Err TooShort, rest: bytes }
+            { result: Err (errorAt bytes bytes "a tag"), rest: bytes }
+
+# The number of bytes of the decoder input `bytes` that come before `rest`, a suffix of it.
+offsetOf = \bytes, rest ->
+    List.len bytes - List.len rest
+
+# The error for a decoder that was given `bytes` and expected something else at `rest`, a suffix
+# of `bytes`.
+errorAt = \bytes, rest, expected ->
+    info = { offset: offsetOf bytes rest, path: [], expected, found: describeFound rest }
+
+    if List.isEmpty rest then TooShort info else Unexpected info
+
+# Makes an error reported by the decoder of a nested value relative to the input of its parent
+# decoder: `offset` is where the nested decoder's input starts in the parent's input, and `path`
+# leads from the parent's value to the nested one.
+nestError : DecodeError, Nat, List DecodePathSegment -> DecodeError
+nestError = \error, offset, path ->
+    nest = \info -> { info & offset: info.offset + offset, path: List.concat path info.path }
+
+    when error is
+        TooShort info -> TooShort (nest info)
+        Unexpected info -> Unexpected (nest info)
+
+# Describes what is at the start of `bytes`, for error messages.
+describeFound = \bytes ->
+    when List.first bytes is
+        Err _ -> "the end of the input"
+        Ok first ->
+            { taken } = takeWhile bytes \b -> !(List.contains tokenDelimiters b)
+            token = if List.isEmpty taken then [first] else (List.split taken maxFoundLen).before
+
+            when Str.fromUtf8 token is
+                Ok str -> "`\(str)`"
+                Err _ -> "invalid UTF-8"
+
+# How many bytes of an unexpected token to show in an error message.
+maxFoundLen = 20
+
+tokenDelimiters = [
+    asciiByte ',',
+    asciiByte ':',
+    asciiByte '[',
+    asciiByte ']',
+    asciiByte '{',
+    asciiByte '}',
+]
@@ -2,10 +2,10 @@ use std::num::NonZeroU32;
 
 use roc_collections::{all::MutMap, VecMap, VecSet};
 use roc_error_macros::internal_error;
-use roc_module::symbol::{ModuleId, Symbol};
-use roc_region::all::Region;
+use roc_module::symbol::{Interns, ModuleId, Symbol};
+use roc_region::all::{Position, Region};
 use roc_types::{
-    subs::Variable,
+    subs::{Subs, Variable},
     types::{MemberImpl, Type},
 };
 
@@ -469,6 +469,265 @@ impl IAbilitiesStore<Resolved> {
     pub fn get_resolved(&self, id: SpecializationId) -> Option<Symbol> {
         self.resolved_specializations.get(&id).copied()
     }
+
+    /// Writes the store for the on-disk type cache, as a sequence of little-endian `u32`s.
+    pub fn serialize(&self, bytes: &mut Vec<u8>) {
+        let mut writer = WordWriter(bytes);
+        self.serialize_abilities(&mut writer, |writer, typ| writer.variable(typ.0));
+
+        let Self {
+            specializations,
+            next_specialization_id,
+            resolved_specializations,
+            ..
+        } = self;
+
+        writer.len(specializations.len());
+        for (symbol, info) in specializations {
+            writer.symbol(*symbol);
+            writer.symbol(info.symbol);
+            writer.len(info.specialization_lambda_sets.len());
+            for (region, var) in info.specialization_lambda_sets.iter() {
+                writer.u32(*region as u32);
+                writer.variable(*var);
+            }
+        }
+
+        writer.len(resolved_specializations.len());
+        for (id, symbol) in resolved_specializations {
+            writer.u32(id.0.get());
+            writer.symbol(*symbol);
+        }
+
+        writer.u32(next_specialization_id.get());
+    }
+
+    /// Reads a store written by [`Self::serialize`], whose variables are in `subs`.
+    /// Returns `None` if the bytes are not such a store.
+    pub fn deserialize(bytes: &[u8], subs: &Subs) -> Option<Self> {
+        let mut reader = WordReader {
+            bytes,
+            vars: subs.len(),
+        };
+
+        let mut store = Self::deserialize_abilities(&mut reader, |reader| {
+            Some(ResolvedMemberType(reader.variable()?))
+        })?;
+
+        store.specializations = reader.many(|reader| {
+            let symbol = reader.symbol()?;
+            let specialization = reader.symbol()?;
+            let sets = reader.many(|reader| {
+                let region = u8::try_from(reader.u32()?).ok()?;
+
+                Some((region, reader.variable()?))
+            })?;
+
+            Some((symbol, MemberSpecializationInfo::new(specialization, sets)))
+        })?;
+
+        store.resolved_specializations = reader.many(|reader| {
+            let id = SpecializationId(NonZeroU32::new(reader.u32()?)?);
+
+            Some((id, reader.symbol()?))
+        })?;
+
+        store.next_specialization_id = NonZeroU32::new(reader.u32()?)?;
+
+        if !reader.bytes.is_empty() {
+            return None;
+        }
+
+        Some(store)
+    }
+}
+
+impl<Phase: ResolvePhase> IAbilitiesStore<Phase> {
+    /// Writes the abilities of the store and their declared implementations,
+    /// using `write_member_type` for the types of ability members.
+    fn serialize_abilities(
+        &self,
+        writer: &mut WordWriter,
+        write_member_type: impl Fn(&mut WordWriter, &Phase::MemberType),
+    ) {
+        let Self {
+            members_of_ability,
+            specialization_to_root,
+            ability_members,
+            declared_implementations,
+            ..
+        } = self;
+
+        writer.len(members_of_ability.len());
+        for (ability, members) in members_of_ability {
+            writer.symbol(*ability);
+            writer.len(members.len());
+            for member in members {
+                writer.symbol(*member);
+            }
+        }
+
+        writer.len(specialization_to_root.len());
+        for (symbol, impl_key) in specialization_to_root {
+            writer.symbol(*symbol);
+            writer.impl_key(*impl_key);
+        }
+
+        writer.len(ability_members.len());
+        for (member, data) in ability_members {
+            writer.symbol(*member);
+            writer.symbol(data.parent_ability);
+            writer.region(data.region);
+            write_member_type(writer, &data.typ);
+        }
+
+        writer.len(declared_implementations.len());
+        for (impl_key, member_impl) in declared_implementations {
+            writer.impl_key(*impl_key);
+            match member_impl {
+                MemberImpl::Impl(symbol) => {
+                    writer.u32(0);
+                    writer.symbol(*symbol);
+                }
+                MemberImpl::Derived => writer.u32(1),
+                MemberImpl::Error => writer.u32(2),
+            }
+        }
+    }
+
+    /// Reads what [`Self::serialize_abilities`] wrote into an otherwise empty store.
+    fn deserialize_abilities(
+        reader: &mut WordReader,
+        read_member_type: impl Fn(&mut WordReader) -> Option<Phase::MemberType>,
+    ) -> Option<Self> {
+        let members_of_ability = reader.many(|reader| {
+            let ability = reader.symbol()?;
+            let members = reader.many(WordReader::symbol)?;
+
+            Some((ability, members))
+        })?;
+
+        let specialization_to_root =
+            reader.many(|reader| Some((reader.symbol()?, reader.impl_key()?)))?;
+
+        let ability_members = reader.many(|reader| {
+            let member = reader.symbol()?;
+            let data = AbilityMemberData {
+                parent_ability: reader.symbol()?,
+                region: reader.region()?,
+                typ: read_member_type(reader)?,
+            };
+
+            Some((member, data))
+        })?;
+
+        let declared_implementations = reader.many(|reader| {
+            let impl_key = reader.impl_key()?;
+            let member_impl = match reader.u32()? {
+                0 => MemberImpl::Impl(reader.symbol()?),
+                1 => MemberImpl::Derived,
+                2 => MemberImpl::Error,
+                _ => return None,
+            };
+
+            Some((impl_key, member_impl))
+        })?;
+
+        Some(Self {
+            members_of_ability,
+            specialization_to_root,
+            ability_members,
+            declared_implementations,
+            ..Default::default()
+        })
+    }
+}
+
+struct WordWriter<'a>(&'a mut Vec<u8>);
+
+impl WordWriter<'_> {
+    fn u32(&mut self, word: u32) {
+        self.0.extend(word.to_le_bytes());
+    }
+
+    fn len(&mut self, len: usize) {
+        self.u32(len as u32);
+    }
+
+    fn symbol(&mut self, symbol: Symbol) {
+        self.u32(symbol.module_id().to_u32());
+        self.u32(symbol.ident_id().index() as u32);
+    }
+
+    fn impl_key(&mut self, impl_key: ImplKey) {
+        self.symbol(impl_key.opaque);
+        self.symbol(impl_key.ability_member);
+    }
+
+    fn region(&mut self, region: Region) {
+        self.u32(region.start().offset);
+        self.u32(region.end().offset);
+    }
+
+    fn variable(&mut self, var: Variable) {
+        self.u32(var.index());
+    }
+}
+
+/// Every read is checked, so that a damaged cache entry can never produce an invalid store.
+struct WordReader<'a> {
+    bytes: &'a [u8],
+    /// The number of variables in the subs of the module
+    vars: usize,
+}
+
+impl WordReader<'_> {
+    fn u32(&mut self) -> Option<u32> {
+        let word = self.bytes.get(..4)?;
+        self.bytes = &self.bytes[4..];
+
+        Some(u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+    }
+
+    fn many<T, C: FromIterator<T>>(
+        &mut self,
+        mut read: impl FnMut(&mut Self) -> Option<T>,
+    ) -> Option<C> {
+        let len = self.u32()?;
+
+        (0..len).map(|_| read(self)).collect()
+    }
+
+    fn symbol(&mut self) -> Option<Symbol> {
+        let module_id = ModuleId::from_u32(self.u32()?)?;
+
+        Some(Interns::from_index(module_id, self.u32()?))
+    }
+
+    fn impl_key(&mut self) -> Option<ImplKey> {
+        Some(ImplKey {
+            opaque: self.symbol()?,
+            ability_member: self.symbol()?,
+        })
+    }
+
+    fn region(&mut self) -> Option<Region> {
+        let start = self.u32()?;
+        let end = self.u32()?;
+
+        Some(Region::new(Position::new(start), Position::new(end)))
+    }
+
+    fn variable(&mut self) -> Option<Variable> {
+        let index = self.u32()?;
+
+        if (index as usize) < self.vars {
+            // Safety: the variable was just checked to be in bounds of the subs.
+            Some(unsafe { Variable::from_index(index) })
+        } else {
+            None
+        }
+    }
 }
 
 impl IAbilitiesStore<Pending> {
@@ -560,6 +819,37 @@ impl IAbilitiesStore<Pending> {
         debug_assert!(self.resolved_specializations.is_empty());
     }
 
+    /// Writes a store of imported abilities, like the ones made by [`Self::closure_from_imported`],
+    /// for the on-disk type cache.
+    pub fn serialize(&self, bytes: &mut Vec<u8>) {
+        if !self.specializations.is_empty() {
+            internal_error!("Specializations are only imported while solving, so a store with them can not be cached");
+        }
+
+        let mut writer = WordWriter(bytes);
+        self.serialize_abilities(&mut writer, |_, typ| match typ {
+            PendingMemberType::Imported => {}
+            PendingMemberType::Local { .. } => {
+                internal_error!("Only imported ability members can be cached")
+            }
+        });
+    }
+
+    /// Reads a store written by [`Self::serialize`].
+    /// Returns `None` if the bytes are not such a store.
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        let mut reader = WordReader { bytes, vars: 0 };
+
+        let store =
+            Self::deserialize_abilities(&mut reader, |_| Some(PendingMemberType::Imported))?;
+
+        if !reader.bytes.is_empty() {
+            return None;
+        }
+
+        Some(store)
+    }
+
     pub fn resolve_for_module<Ctx, VarOfSymbol, ImportVar>(
         self,
         my_module: ModuleId,
@@ -84,7 +84,6 @@ macro_rules! map_symbol_to_lowlevel_and_arity {
                 LowLevel::NumDivUnchecked => unreachable!(),
 
                 // these are used internally and not tied to a symbol
-                LowLevel::Hash => unimplemented!(),
                 LowLevel::PtrCast => unimplemented!(),
                 LowLevel::RefCountInc => unimplemented!(),
                 LowLevel::RefCountDec => unimplemented!(),
@@ -195,6 +194,7 @@ map_symbol_to_lowlevel_and_arity! {
     And; BOOL_AND; 2,
     Or; BOOL_OR; 2,
     Not; BOOL_NOT; 1,
+    HashBytes; DICT_HASH_BYTES_LOWLEVEL; 2,
     BoxExpr; BOX_BOX_FUNCTION; 1,
     UnboxExpr; BOX_UNBOX; 1,
     Unreachable; LIST_UNREACHABLE; 1,
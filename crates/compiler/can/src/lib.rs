@@ -17,6 +17,7 @@ pub mod num;
 pub mod operator;
 pub mod pattern;
 pub mod procedure;
+pub mod property;
 pub mod scope;
 pub mod string;
 pub mod traverse;
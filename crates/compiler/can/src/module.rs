@@ -5,6 +5,7 @@ use crate::effect_module::HostedGeneratedFunctions;
 use crate::env::Env;
 use crate::expr::{ClosureData, Declarations, Expr, Output, PendingDerives};
 use crate::pattern::{BindingsFromPattern, Pattern};
+use crate::property::PropertyTestConfig;
 use crate::scope::Scope;
 use bumpalo::Bump;
 use roc_collections::{MutMap, SendMap, VecMap, VecSet};
@@ -272,6 +273,7 @@ pub fn canonicalize_module_defs<'a>(
     exposed_imports: MutMap<Ident, (Symbol, Region)>,
     exposed_symbols: &VecSet<Symbol>,
     symbols_from_requires: &[(Loc<Symbol>, Loc<TypeAnnotation<'a>>)],
+    property_test_config: PropertyTestConfig,
     var_store: &mut VarStore,
 ) -> ModuleOutput {
     let mut can_exposed_imports = MutMap::default();
@@ -292,6 +294,10 @@ pub fn canonicalize_module_defs<'a>(
     let generated_info =
         GeneratedInfo::from_header_for(&mut env, &mut scope, var_store, header_for);
 
+    // Turn property tests into plain expects, so their arguments are generated (and their
+    // operators desugared) like any other code.
+    crate::property::desugar_property_expects(arena, loc_defs, property_test_config);
+
     // Desugar operators (convert them to Apply calls, taking into account
     // operator precedence and associativity rules), before doing other canonicalization.
     //
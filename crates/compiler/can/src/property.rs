@@ -0,0 +1,261 @@
+//! Property tests: top-level `expect`s whose condition is a function.
+//!
+//! ```roc
+//! expect \list, n -> List.len (List.takeFirst list n) <= n
+//! ```
+//!
+//! is desugared, before canonicalization, into
+//!
+//! ```roc
+//! #property1 = \list, n -> List.len (List.takeFirst list n) <= n
+//!
+//! expect
+//!     { list, n } = Arbitrary.check { seed, iterations } \{ list: #x1, n: #x2 } -> #property1 #x1 #x2
+//!
+//!     #property1 list n
+//! ```
+//!
+//! `Arbitrary.check` returns the simplest failing input it can find, or a passing one if there is
+//! none, so the expect fails exactly when the property does. Because the arguments are bound by a
+//! def inside the expect, a failure reports the values they had.
+use bumpalo::collections::Vec;
+use bumpalo::Bump;
+use roc_module::called_via::CalledVia;
+use roc_module::symbol::Symbol;
+use roc_parse::ast::{AssignedField, Collection, Defs, Expr, Pattern, ValueDef};
+use roc_region::all::{Loc, Region};
+
+/// How `Arbitrary.check` generates inputs for property tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropertyTestConfig {
+    /// The same seed always generates the same inputs.
+    pub seed: u64,
+    /// How many inputs to try before a property is considered to hold.
+    pub iterations: u32,
+}
+
+impl Default for PropertyTestConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            iterations: 100,
+        }
+    }
+}
+
+/// Desugar every top-level property test in `defs` into an `expect` of a plain condition.
+pub fn desugar_property_expects<'a>(
+    arena: &'a Bump,
+    defs: &mut Defs<'a>,
+    config: PropertyTestConfig,
+) {
+    let mut num_properties = 0;
+
+    for index in 0..defs.tags.len() {
+        let value_index = match defs.tags[index].split() {
+            Ok(_) => continue,
+            Err(value_index) => value_index.index(),
+        };
+
+        let (condition, preceding_comment) = match defs.value_defs[value_index] {
+            ValueDef::Expect {
+                condition,
+                preceding_comment,
+            } => (condition, preceding_comment),
+            _ => continue,
+        };
+
+        let (args, body) = match strip_spaces(&condition.value) {
+            Expr::Closure(args, body) => (*args, *body),
+            _ => continue,
+        };
+
+        num_properties += 1;
+
+        let property_name =
+            bumpalo::format!(in arena, "#property{}", num_properties).into_bump_str();
+        let property = ValueDef::Body(
+            arena.alloc(Loc::at(
+                condition.region,
+                Pattern::Identifier(property_name),
+            )),
+            arena.alloc(Loc::at(condition.region, Expr::Closure(args, body))),
+        );
+        defs.push_value_def(property, defs.regions[index], &[], &[]);
+
+        let condition = property_condition(arena, property_name, args, condition.region, config);
+        let expect = ValueDef::Expect {
+            condition: arena.alloc(condition),
+            preceding_comment,
+        };
+        let region = defs.regions[index];
+        defs.replace_with_value_def(index, expect, region);
+    }
+}
+
+/// Whether the canonical body of a top-level `expect` generates its inputs with `Arbitrary.check`,
+/// as the desugared property tests do. Only these depend on the seed.
+pub fn is_property_expect(body: &Loc<crate::expr::Expr>) -> bool {
+    use crate::expr::Expr;
+
+    match &body.value {
+        Expr::LetNonRec(def, _) => match &def.loc_expr.value {
+            Expr::Call(boxed, _, _) => matches!(boxed.1.value, Expr::Var(Symbol::ARBITRARY_CHECK)),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn property_condition<'a>(
+    arena: &'a Bump,
+    property_name: &'a str,
+    args: &'a [Loc<Pattern<'a>>],
+    region: Region,
+    config: PropertyTestConfig,
+) -> Loc<Expr<'a>> {
+    // Arguments that are plain identifiers keep their name, so a failure shows it
+    let arg_names: Vec<&'a str> = Vec::from_iter_in(
+        args.iter()
+            .enumerate()
+            .map(|(i, arg)| match strip_pattern(&arg.value) {
+                Pattern::Identifier(ident) => *ident,
+                _ => bumpalo::format!(in arena, "#arg{}", i + 1).into_bump_str(),
+            }),
+        arena,
+    );
+    let inner_names: Vec<&'a str> = Vec::from_iter_in(
+        (1..=args.len()).map(|i| bumpalo::format!(in arena, "#x{}", i).into_bump_str()),
+        arena,
+    );
+
+    // \{ a1: #x1, ..., an: #xn } -> #property #x1 ... #xn
+    let inner_fields = Vec::from_iter_in(
+        arg_names
+            .iter()
+            .zip(inner_names.iter())
+            .map(|(name, inner)| {
+                Loc::at(
+                    region,
+                    Pattern::RequiredField(
+                        name,
+                        arena.alloc(Loc::at(region, Pattern::Identifier(inner))),
+                    ),
+                )
+            }),
+        arena,
+    );
+    let inner_pattern = Loc::at(
+        region,
+        Pattern::RecordDestructure(Collection::with_items(inner_fields.into_bump_slice())),
+    );
+    let inner_call = call(arena, region, property_name, &inner_names);
+    let inner_closure = Expr::Closure(
+        arena.alloc([inner_pattern]),
+        arena.alloc(Loc::at(region, inner_call)),
+    );
+
+    // Arbitrary.check { seed, iterations } (\{ ... } -> ...)
+    let config_fields = [
+        ("seed", config.seed.to_string()),
+        ("iterations", config.iterations.to_string()),
+    ]
+    .map(|(label, value)| {
+        let value = Expr::Num(arena.alloc_str(&value));
+        Loc::at(
+            region,
+            AssignedField::RequiredValue(
+                Loc::at(region, label),
+                &[],
+                arena.alloc(Loc::at(region, value)),
+            ),
+        )
+    });
+    let config_record = Expr::Record(Collection::with_items(arena.alloc(config_fields)));
+    let check = Expr::Apply(
+        arena.alloc(Loc::at(
+            region,
+            Expr::Var {
+                module_name: "Arbitrary",
+                ident: "check",
+            },
+        )),
+        arena.alloc([
+            &*arena.alloc(Loc::at(region, config_record)),
+            &*arena.alloc(Loc::at(region, inner_closure)),
+        ]),
+        CalledVia::Space,
+    );
+
+    // { a1, ..., an } = Arbitrary.check ...
+    let outer_fields = Vec::from_iter_in(
+        arg_names
+            .iter()
+            .map(|name| Loc::at(region, Pattern::Identifier(name))),
+        arena,
+    );
+    let outer_pattern = Loc::at(
+        region,
+        Pattern::RecordDestructure(Collection::with_items(outer_fields.into_bump_slice())),
+    );
+    let mut defs = Defs::default();
+    defs.push_value_def(
+        ValueDef::Body(
+            arena.alloc(outer_pattern),
+            arena.alloc(Loc::at(region, check)),
+        ),
+        region,
+        &[],
+        &[],
+    );
+
+    // #property a1 ... an
+    let outer_call = call(arena, region, property_name, &arg_names);
+
+    Loc::at(
+        region,
+        Expr::Defs(arena.alloc(defs), arena.alloc(Loc::at(region, outer_call))),
+    )
+}
+
+fn call<'a>(arena: &'a Bump, region: Region, function: &'a str, args: &[&'a str]) -> Expr<'a> {
+    let function = arena.alloc(Loc::at(
+        region,
+        Expr::Var {
+            module_name: "",
+            ident: function,
+        },
+    ));
+    let args = Vec::from_iter_in(
+        args.iter().map(|ident| {
+            &*arena.alloc(Loc::at(
+                region,
+                Expr::Var {
+                    module_name: "",
+                    ident,
+                },
+            ))
+        }),
+        arena,
+    );
+
+    Expr::Apply(function, args.into_bump_slice(), CalledVia::Space)
+}
+
+fn strip_spaces<'a, 'b>(expr: &'b Expr<'a>) -> &'b Expr<'a> {
+    match expr {
+        Expr::SpaceBefore(expr, _) | Expr::SpaceAfter(expr, _) | Expr::ParensAround(expr) => {
+            strip_spaces(expr)
+        }
+        _ => expr,
+    }
+}
+
+fn strip_pattern<'a, 'b>(pattern: &'b Pattern<'a>) -> &'b Pattern<'a> {
+    match pattern {
+        Pattern::SpaceBefore(pattern, _) | Pattern::SpaceAfter(pattern, _) => {
+            strip_pattern(pattern)
+        }
+        _ => pattern,
+    }
+}
@@ -1,7 +1,7 @@
 //! Traversals over the can ast.
 
 use roc_module::{ident::Lowercase, symbol::Symbol};
-use roc_region::all::{Loc, Region};
+use roc_region::all::{Loc, Position, Region};
 use roc_types::{subs::Variable, types::MemberImpl};
 
 use crate::{
@@ -520,6 +520,153 @@ pub fn find_type_at(region: Region, decls: &Declarations) -> Option<Variable> {
     visitor.typ
 }
 
+/// Whether `region` contains `position`, counting the position just past its end as inside it,
+/// which is where the cursor is after typing a name.
+fn region_contains_position(region: Region, position: Position) -> bool {
+    region.contains(&Region::new(position, position))
+}
+
+/// Finds the innermost expression or typed pattern containing `position`,
+/// and returns its region and type.
+pub fn find_closest_type_at(
+    position: Position,
+    decls: &Declarations,
+) -> Option<(Region, Variable)> {
+    let mut visitor = Finder {
+        position,
+        found: None,
+    };
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        position: Position,
+        found: Option<(Region, Variable)>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, region: Region) -> bool {
+            region_contains_position(region, self.position)
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            if self.should_visit(region) {
+                self.found = Some((region, var));
+                walk_expr(self, expr, var);
+            }
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, opt_var: Option<Variable>) {
+            if self.should_visit(region) {
+                if let Some(var) = opt_var {
+                    self.found = Some((region, var));
+                }
+                walk_pattern(self, pattern);
+            }
+        }
+    }
+}
+
+/// Finds the symbol that is looked up or defined at `position`, if any.
+pub fn find_symbol_at(position: Position, decls: &Declarations) -> Option<Loc<Symbol>> {
+    let mut visitor = Finder {
+        position,
+        found: None,
+    };
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        position: Position,
+        found: Option<Loc<Symbol>>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, region: Region) -> bool {
+            region_contains_position(region, self.position)
+        }
+
+        fn visit_expr(&mut self, expr: &Expr, region: Region, var: Variable) {
+            if self.should_visit(region) {
+                match expr {
+                    Expr::Var(symbol) | Expr::AbilityMember(symbol, _, _) => {
+                        self.found = Some(Loc::at(region, *symbol));
+                    }
+                    _ => walk_expr(self, expr, var),
+                }
+            }
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+            if self.should_visit(region) {
+                match pattern {
+                    Pattern::Identifier(symbol)
+                    | Pattern::Shadowed(_, _, symbol)
+                    | Pattern::AbilityMemberSpecialization { ident: symbol, .. } => {
+                        self.found = Some(Loc::at(region, *symbol));
+                    }
+                    _ => walk_pattern(self, pattern),
+                }
+            }
+        }
+
+        fn visit_record_destruct(&mut self, destruct: &RecordDestruct, region: Region) {
+            if self.should_visit(region) {
+                match destruct.typ {
+                    DestructType::Guard(..) => walk_record_destruct(self, destruct),
+                    _ => self.found = Some(Loc::at(region, destruct.symbol)),
+                }
+            }
+        }
+    }
+}
+
+/// Finds the region of the pattern that introduces `symbol`, if it is defined in `decls`.
+pub fn find_definition_of(symbol: Symbol, decls: &Declarations) -> Option<Region> {
+    let mut visitor = Finder {
+        symbol,
+        found: None,
+    };
+    visitor.visit_decls(decls);
+    return visitor.found;
+
+    struct Finder {
+        symbol: Symbol,
+        found: Option<Region>,
+    }
+
+    impl Visitor for Finder {
+        fn should_visit(&mut self, _region: Region) -> bool {
+            self.found.is_none()
+        }
+
+        fn visit_pattern(&mut self, pattern: &Pattern, region: Region, _opt_var: Option<Variable>) {
+            if self.should_visit(region) {
+                match pattern {
+                    Pattern::Identifier(symbol)
+                    | Pattern::Shadowed(_, _, symbol)
+                    | Pattern::AbilityMemberSpecialization { ident: symbol, .. }
+                        if *symbol == self.symbol =>
+                    {
+                        self.found = Some(region);
+                    }
+                    _ => walk_pattern(self, pattern),
+                }
+            }
+        }
+
+        fn visit_record_destruct(&mut self, destruct: &RecordDestruct, region: Region) {
+            if self.should_visit(region) {
+                if destruct.symbol == self.symbol {
+                    self.found = Some(region);
+                } else {
+                    walk_record_destruct(self, destruct);
+                }
+            }
+        }
+    }
+}
+
 /// Given an ability Foo has foo : ..., returns (T, foo1) if the symbol at the given region is a
 /// symbol foo1 that specializes foo for T. Otherwise if the symbol is foo but the specialization
 /// is unknown, (Foo, foo) is returned. Otherwise [None] is returned.
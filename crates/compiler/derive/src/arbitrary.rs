@@ -0,0 +1,321 @@
+//! Derivers for the `Arbitrary` ability.
+
+use std::iter::once;
+
+use roc_can::def::Def;
+use roc_can::expr::{Expr, IntValue};
+use roc_can::num::{IntBound, IntLitWidth};
+use roc_can::pattern::Pattern;
+use roc_derive_key::arbitrary::FlatArbitraryKey;
+use roc_error_macros::internal_error;
+use roc_module::ident::{Lowercase, TagName};
+use roc_module::symbol::Symbol;
+use roc_region::all::Loc;
+use roc_types::subs::Variable;
+
+use crate::util::{call_builtin, call_builtin_member, closure, when_with_fallback, Env};
+use crate::util::{RecordType, TagUnion};
+use crate::DerivedBody;
+
+pub(crate) fn derive_arbitrary(
+    env: &mut Env<'_>,
+    key: FlatArbitraryKey,
+    def_symbol: Symbol,
+) -> DerivedBody {
+    let (body, body_type) = match key {
+        FlatArbitraryKey::Generator(generator) => arbitrary_generator(env, def_symbol, generator),
+        FlatArbitraryKey::Record(fields) => arbitrary_record(env, def_symbol, fields),
+        FlatArbitraryKey::TagUnion(tags) => arbitrary_tag_union(env, def_symbol, tags),
+    };
+
+    let specialization_lambda_sets =
+        env.get_specialization_lambda_sets(body_type, Symbol::ARBITRARY_ARBITRARY);
+
+    DerivedBody {
+        body,
+        body_type,
+        specialization_lambda_sets,
+    }
+}
+
+fn arbitrary_generator(env: &mut Env<'_>, fn_name: Symbol, generator: Symbol) -> (Expr, Variable) {
+    // arbitrary_u8 : Rng -> { value : U8, rng : Rng }
+    // arbitrary_u8 = \rng -> Arbitrary.u8 rng
+    let rng_var = env.subs.fresh_unnamed_flex_var();
+    let rng_sym = env.new_symbol("rng");
+
+    let body = call_builtin(env, generator, vec![(rng_var, Expr::Var(rng_sym))]);
+
+    let (fn_var, clos) = closure(
+        env,
+        fn_name,
+        vec![],
+        vec![(rng_var, Pattern::Identifier(rng_sym))],
+        body,
+    );
+
+    (clos, fn_var)
+}
+
+fn arbitrary_record(
+    env: &mut Env<'_>,
+    fn_name: Symbol,
+    fields: Vec<Lowercase>,
+) -> (Expr, Variable) {
+    // Suppose rcd = { f1, ..., fn }.
+    // Build a generalized type t_rcd = { f1: t1, ..., fn: tn }, with fresh t1, ..., tn,
+    // so that we can re-use the derived impl for many records of the same fields.
+    //
+    // Then a generator for this record is
+    //
+    // arbitrary_rcd : Rng -> { value : { f1 : t1, ..., fn : tn }, rng : Rng } | t1 has Arbitrary, ...
+    // arbitrary_rcd = \rng ->
+    //   f1 = Arbitrary.arbitrary rng
+    //   ...
+    //   fn = Arbitrary.arbitrary f_n1.rng
+    //   { value: { f1: f1.value, ..., fn: fn.value }, rng: fn.rng }
+    let field_vars: Vec<_> = (fields.into_iter())
+        .map(|name| (name, env.subs.fresh_unnamed_flex_var()))
+        .collect();
+    let record = RecordType::new(env, field_vars);
+
+    let rng_var = env.subs.fresh_unnamed_flex_var();
+    let rng_sym = env.new_symbol("rng");
+
+    let (defs, values, last_rng) =
+        generate_all(env, (rng_var, Expr::Var(rng_sym)), record.fields.clone());
+
+    let fields = (record.fields.iter())
+        .map(|(name, _)| name.clone())
+        .zip(values)
+        .collect();
+    let body = generated(env, (record.var, record.record(fields)), last_rng);
+    let body = let_defs(defs, body);
+
+    let (fn_var, clos) = closure(
+        env,
+        fn_name,
+        vec![],
+        vec![(rng_var, Pattern::Identifier(rng_sym))],
+        body,
+    );
+
+    (clos, fn_var)
+}
+
+fn arbitrary_tag_union(
+    env: &mut Env<'_>,
+    fn_name: Symbol,
+    mut tags: Vec<(TagName, u16)>,
+) -> (Expr, Variable) {
+    // Suppose tags = [ A p11 .. p1n, ..., Q pq1 .. pqm ], ordered by the number of payloads.
+    // Build a generalized type t_tags = [ A t11 .. t1n, ..., Q tq1 .. tqm ],
+    // with fresh t1, ..., tqm, so that we can re-use the derived impl for many
+    // unions of the same tags and payloads.
+    //
+    // Then a generator for this tag union is
+    //
+    // arbitrary_union : Rng -> { value : [ A t11 .. t1n, ..., Q tq1 .. tqm ], rng : Rng } | t11 has Arbitrary, ...
+    // arbitrary_union = \rng ->
+    //   tag = Arbitrary.chooseTag rng q
+    //   when tag.value is
+    //     0 ->
+    //       x11 = Arbitrary.arbitrary tag.rng
+    //       ...
+    //       { value: A x11.value .. x1n.value, rng: x1n.rng }
+    //     ...
+    //     _ -> { value: Q ..., rng: ... }
+    //
+    // Once the size budget of the rng is used up, `chooseTag` always chooses the first tag, which
+    // has the fewest payloads; that way, recursive tag unions stop growing. A union of one tag
+    // doesn't need to choose at all.
+    tags.sort_by(|(t1, a1), (t2, a2)| a1.cmp(a2).then_with(|| t1.cmp(t2)));
+
+    let tags_with_vars: Vec<_> = (tags.iter())
+        .map(|(name, arity)| {
+            let payload_vars = std::iter::repeat_with(|| env.subs.fresh_unnamed_flex_var())
+                .take(*arity as usize)
+                .collect();
+            (name.clone(), payload_vars)
+        })
+        .collect();
+    let union = TagUnion::new(env, tags_with_vars);
+
+    let rng_var = env.subs.fresh_unnamed_flex_var();
+    let rng_sym = env.new_symbol("rng");
+
+    let generate_tag = |env: &mut Env<'_>, tag_name: &TagName, rng: (Variable, Expr)| {
+        let (_, payload_vars, _) = union.get(tag_name.0.as_str());
+        let payloads = (payload_vars.iter().enumerate())
+            .map(|(i, var)| (format!("x{}", i + 1).into(), *var))
+            .collect();
+
+        let (defs, values, last_rng) = generate_all(env, rng, payloads);
+
+        let tag = union.tag(tag_name.0.as_str(), values);
+        let_defs(defs, generated(env, (union.var, tag), last_rng))
+    };
+
+    let (body_var, body) = if let [(tag_name, _)] = tags.as_slice() {
+        generate_tag(env, tag_name, (rng_var, Expr::Var(rng_sym)))
+    } else {
+        // tag = Arbitrary.chooseTag rng q
+        let tag_sym = env.new_symbol("tag");
+        let num_tags = Expr::Int(
+            Variable::NAT,
+            Variable::NATURAL,
+            format!("{}", tags.len()).into_boxed_str(),
+            IntValue::I128((tags.len() as i128).to_ne_bytes()),
+            IntBound::Exact(IntLitWidth::Nat),
+        );
+        let (choice_var, choose_tag) = call_builtin(
+            env,
+            Symbol::ARBITRARY_CHOOSE_TAG,
+            vec![(rng_var, Expr::Var(rng_sym)), (Variable::NAT, num_tags)],
+        );
+        let choice = RecordType::new(
+            env,
+            vec![("rng".into(), rng_var), ("value".into(), Variable::NAT)],
+        );
+        env.unify(choice_var, choice.var);
+        let choice_def = let_def(tag_sym, choice.var, choose_tag);
+
+        let mut branches: Vec<_> = (tags.iter().enumerate())
+            .map(|(i, (tag_name, _))| {
+                let pattern = Pattern::IntLiteral(
+                    Variable::NAT,
+                    Variable::NATURAL,
+                    format!("{}", i).into_boxed_str(),
+                    IntValue::I128((i as i128).to_ne_bytes()),
+                    IntBound::Exact(IntLitWidth::Nat),
+                );
+                let rng = access(env, &choice, tag_sym, "rng");
+                let (branch_var, branch) = generate_tag(env, tag_name, rng);
+                (pattern, branch_var, branch)
+            })
+            .collect();
+
+        let (_, result_var, fallback) = branches.pop().unwrap();
+        let branches = (branches.into_iter())
+            .map(|(pattern, branch_var, branch)| {
+                env.unify(branch_var, result_var);
+                (pattern, branch)
+            })
+            .collect();
+
+        // when tag.value is ...
+        let chosen = access(env, &choice, tag_sym, "value");
+        let body = when_with_fallback(chosen, branches, fallback, result_var);
+
+        let_defs(vec![choice_def], (result_var, body))
+    };
+
+    let (fn_var, clos) = closure(
+        env,
+        fn_name,
+        vec![],
+        vec![(rng_var, Pattern::Identifier(rng_sym))],
+        (body_var, body),
+    );
+
+    (clos, fn_var)
+}
+
+/// Builds the defs
+///
+///   x1 = Arbitrary.arbitrary rng
+///   x2 = Arbitrary.arbitrary x1.rng
+///   ...
+///   xn = Arbitrary.arbitrary x_n1.rng
+///
+/// for the given names x1, ..., xn and types of the values to generate, and returns them along
+/// with the generated values x1.value, ..., xn.value and the final rng xn.rng.
+fn generate_all(
+    env: &mut Env<'_>,
+    rng: (Variable, Expr),
+    values: Vec<(Lowercase, Variable)>,
+) -> (Vec<Def>, Vec<Expr>, (Variable, Expr)) {
+    let (rng_var, _) = rng;
+
+    let mut defs = Vec::with_capacity(values.len());
+    let mut generated_values = Vec::with_capacity(values.len());
+    let mut rng = rng;
+
+    for (name, value_var) in values {
+        let sym = env.new_symbol(name.as_str());
+
+        // Arbitrary.arbitrary rng : { value : t, rng : Rng }
+        let (result_var, call) = call_builtin_member(env, Symbol::ARBITRARY_ARBITRARY, vec![rng]);
+        let result = RecordType::new(
+            env,
+            vec![("rng".into(), rng_var), ("value".into(), value_var)],
+        );
+        env.unify(result_var, result.var);
+
+        defs.push(let_def(sym, result.var, call));
+
+        let (_, value) = access(env, &result, sym, "value");
+        generated_values.push(value);
+        rng = access(env, &result, sym, "rng");
+    }
+
+    (defs, generated_values, rng)
+}
+
+/// Builds `{ value: value, rng: rng }`.
+fn generated(
+    env: &mut Env<'_>,
+    value: (Variable, Expr),
+    rng: (Variable, Expr),
+) -> (Variable, Expr) {
+    let (value_var, value) = value;
+    let (rng_var, rng) = rng;
+
+    let result = RecordType::new(
+        env,
+        vec![("rng".into(), rng_var), ("value".into(), value_var)],
+    );
+    let record = result.record(vec![("rng".into(), rng), ("value".into(), value)]);
+
+    (result.var, record)
+}
+
+/// Builds `sym.field`, where `sym` is a value of the given record type.
+fn access(env: &mut Env<'_>, record: &RecordType, sym: Symbol, field: &str) -> (Variable, Expr) {
+    let field: Lowercase = field.into();
+    let field_var = match record.fields.iter().find(|(name, _)| *name == field) {
+        Some((_, var)) => *var,
+        None => internal_error!("{} is not a field of this record", field),
+    };
+
+    let access = Expr::Access {
+        record_var: record.var,
+        ext_var: env.subs.fresh_unnamed_flex_var(),
+        field_var,
+        loc_expr: Box::new(Loc::at_zero(Expr::Var(sym))),
+        field,
+    };
+
+    (field_var, access)
+}
+
+fn let_def(sym: Symbol, var: Variable, expr: Expr) -> Def {
+    Def {
+        loc_pattern: Loc::at_zero(Pattern::Identifier(sym)),
+        loc_expr: Loc::at_zero(expr),
+        expr_var: var,
+        pattern_vars: once((sym, var)).collect(),
+        annotation: None,
+    }
+}
+
+/// Builds `def1 ... defn body`.
+fn let_defs(defs: Vec<Def>, body: (Variable, Expr)) -> (Variable, Expr) {
+    let (body_var, body) = body;
+
+    let body = (defs.into_iter()).rev().fold(body, |body, def| {
+        Expr::LetNonRec(Box::new(def), Box::new(Loc::at_zero(body)))
+    });
+
+    (body_var, body)
+}
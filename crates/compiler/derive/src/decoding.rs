@@ -1,18 +1,23 @@
 //! Derivers for the `Decoding` ability.
 
-use roc_can::expr::{AnnotatedMark, ClosureData, Expr, Recursive};
-use roc_can::pattern::Pattern;
+use roc_can::expr::{AnnotatedMark, ClosureData, Expr, Field, IntValue, Recursive};
+use roc_can::num::{IntBound, IntLitWidth};
+use roc_can::pattern::{DestructType, Pattern, RecordDestruct};
+use roc_collections::SendMap;
 use roc_derive_key::decoding::FlatDecodableKey;
 use roc_error_macros::internal_error;
 use roc_module::called_via::CalledVia;
+use roc_module::ident::{Lowercase, TagName};
 use roc_module::symbol::Symbol;
-use roc_region::all::Loc;
+use roc_region::all::{Loc, Region};
 use roc_types::subs::{
     Content, FlatType, GetSubsSlice, LambdaSet, OptVariable, SubsSlice, UnionLambdas, Variable,
 };
 use roc_types::types::AliasKind;
 
-use crate::util::Env;
+use crate::util::{
+    call_builtin, call_builtin_member, closure, when, when_with_fallback, Env, RecordType, TagUnion,
+};
 use crate::{synth_var, DerivedBody};
 
 pub(crate) fn derive_decoder(
@@ -22,6 +27,8 @@ pub(crate) fn derive_decoder(
 ) -> DerivedBody {
     let (body, body_type) = match key {
         FlatDecodableKey::List() => decoder_list(env, def_symbol),
+        FlatDecodableKey::Record(fields) => decoder_record(env, def_symbol, fields),
+        FlatDecodableKey::TagUnion(tags) => decoder_tag_union(env, def_symbol, tags),
     };
 
     let specialization_lambda_sets =
@@ -256,3 +263,807 @@ fn decoder_list(env: &mut Env<'_>, _def_symbol: Symbol) -> (Expr, Variable) {
 
     (decode_custom_call, decoder_var)
 }
+
+fn decoder_record(
+    env: &mut Env<'_>,
+    _def_symbol: Symbol,
+    fields: Vec<Lowercase>,
+) -> (Expr, Variable) {
+    // Suppose rcd = { f1, ..., fn }. Build
+    //
+    //   def_symbol : Decoder { f1: t1, ..., fn: tn } fmt | t1 has Decoding, ..., tn has Decoding, fmt has DecoderFormatting
+    //   def_symbol = Decode.custom \bytes, fmt ->
+    //     Decode.decodeWith bytes (Decode.record initialState stepField finalizer) fmt
+    //
+    // with fresh t1, ..., tn, so that we can re-use the derived impl for many records of the same
+    // fields. The state of the decoder holds the value of each field, once it has been decoded:
+    //
+    //   initialState = { f1: Err NoField, ..., fn: Err NoField }
+    //
+    //   stepField = \state, field ->
+    //     when field is
+    //       "f1" -> Keep (<decoder of t1 that stores its result in `state.f1`>)
+    //       ...
+    //       "fn" -> Keep (<decoder of tn that stores its result in `state.fn`>)
+    //       _ -> Skip
+    //
+    //   finalizer = \state ->
+    //     when state is
+    //       { f1: Ok f1, ..., fn: Ok fn } -> Ok { f1, ..., fn }
+    //       { f1: Err _, .. } -> Err (Decode.missingField "f1")
+    //       ...
+    //       _ -> Err (Decode.missingField "fn")
+    let no_field = TagUnion::new(env, vec![(tag_name("NoField"), vec![])]);
+    let slots: Vec<_> = fields
+        .into_iter()
+        .map(|field| Slot::new(env, field, &no_field))
+        .collect();
+    let state = state_record(env, &slots);
+
+    // { f1: t1, ..., fn: tn }
+    let decoded = RecordType::new(
+        env,
+        (slots.iter())
+            .map(|slot| (slot.label.clone(), slot.val_var))
+            .collect(),
+    );
+
+    let (decoder_var, decoder) = decode_custom(env, vec![], |env, bytes, fmt| {
+        let initial_state = initial_state(&state, &slots, &no_field);
+        let step_field = record_step_field(env, &state, &slots);
+        let finalizer = record_finalizer(env, &state, &slots, &decoded);
+
+        // Decode.record initialState stepField finalizer
+        let record_decoder = call_builtin_member(
+            env,
+            Symbol::DECODE_RECORD,
+            vec![(state.var, initial_state), step_field, finalizer],
+        );
+
+        call_builtin(
+            env,
+            Symbol::DECODE_DECODE_WITH,
+            vec![bytes, record_decoder, fmt],
+        )
+    });
+
+    (decoder, decoder_var)
+}
+
+fn decoder_tag_union(
+    env: &mut Env<'_>,
+    _def_symbol: Symbol,
+    tags: Vec<(TagName, u16)>,
+) -> (Expr, Variable) {
+    // Suppose tags = [ A p11 .. p1n, ..., Q pq1 .. pqm ]. Build
+    //
+    //   def_symbol : Decoder [ A t11 .. t1n, ..., Q tq1 .. tqm ] fmt | t11 has Decoding, ..., fmt has DecoderFormatting
+    //   def_symbol = Decode.custom \bytes, fmt ->
+    //     Decode.decodeWith bytes (Decode.tag initialState stepPayload finalizer) fmt
+    //
+    // with fresh t11, ..., tqm, so that we can re-use the derived impl for many unions of the same
+    // tags and payloads. The state of the decoder holds the value of each payload of each tag,
+    // once it has been decoded:
+    //
+    //   initialState = { A_0: Err NoField, ..., A_n-1: Err NoField, ..., Q_m-1: Err NoField }
+    //
+    //   stepPayload = \state, name, index ->
+    //     when name is
+    //       "A" ->
+    //         when index is
+    //           0 -> Keep (<decoder of t11 that stores its result in `state.A_0`>)
+    //           ...
+    //           _ -> Skip
+    //       ...
+    //       _ -> Skip
+    //
+    //   finalizer = \state, name ->
+    //     when name is
+    //       "A" ->
+    //         when state is
+    //           { A_0: Ok x11, ..., A_n-1: Ok x1n } -> Ok (A x11 .. x1n)
+    //           { A_0: Err _, .. } -> Err (Decode.missingPayload "A" 0)
+    //           ...
+    //           _ -> Err (Decode.missingPayload "A" (n - 1))
+    //       ...
+    //       _ -> Err (Decode.unknownTag name)
+    let no_field = TagUnion::new(env, vec![(tag_name("NoField"), vec![])]);
+    let tag_slots: Vec<(TagName, Vec<Slot>)> = tags
+        .into_iter()
+        .map(|(tag_name, arity)| {
+            let slots = (0..arity)
+                .map(|index| {
+                    let label = format!("{}_{}", tag_name.0.as_str(), index).into();
+                    Slot::new(env, label, &no_field)
+                })
+                .collect();
+            (tag_name, slots)
+        })
+        .collect();
+    let all_slots: Vec<_> = (tag_slots.iter())
+        .flat_map(|(_, slots)| slots.iter())
+        .cloned()
+        .collect();
+    let state = state_record(env, &all_slots);
+
+    // [ A t11 .. t1n, ..., Q tq1 .. tqm ]
+    let decoded = TagUnion::new(
+        env,
+        (tag_slots.iter())
+            .map(|(tag_name, slots)| {
+                let payload_vars = slots.iter().map(|slot| slot.val_var).collect();
+                (tag_name.clone(), payload_vars)
+            })
+            .collect(),
+    );
+
+    let (decoder_var, decoder) = decode_custom(env, vec![], |env, bytes, fmt| {
+        let initial_state = initial_state(&state, &all_slots, &no_field);
+        let step_payload = tag_step_payload(env, &state, &tag_slots);
+        let finalizer = tag_finalizer(env, &state, &tag_slots, &decoded);
+
+        // Decode.tag initialState stepPayload finalizer
+        let tag_decoder = call_builtin_member(
+            env,
+            Symbol::DECODE_TAG,
+            vec![(state.var, initial_state), step_payload, finalizer],
+        );
+
+        call_builtin(
+            env,
+            Symbol::DECODE_DECODE_WITH,
+            vec![bytes, tag_decoder, fmt],
+        )
+    });
+
+    (decoder, decoder_var)
+}
+
+/// A value being decoded, which is kept in the decoder state as `[Err [NoField], Ok val]` until
+/// it has been decoded.
+#[derive(Clone)]
+struct Slot {
+    /// The label of the slot in the decoder state.
+    label: Lowercase,
+    val_var: Variable,
+    /// The type `[Err [NoField], Ok val]`.
+    union: TagUnion,
+}
+
+impl Slot {
+    fn new(env: &mut Env<'_>, label: Lowercase, no_field: &TagUnion) -> Self {
+        let val_var = env.subs.fresh_unnamed_flex_var();
+        let union = TagUnion::new(
+            env,
+            vec![
+                (tag_name("Err"), vec![no_field.var]),
+                (tag_name("Ok"), vec![val_var]),
+            ],
+        );
+
+        Self {
+            label,
+            val_var,
+            union,
+        }
+    }
+}
+
+/// The type `{ l1: [Err [NoField], Ok t1], ..., ln: [Err [NoField], Ok tn] }` of a decoder state
+/// holding the given slots.
+fn state_record(env: &mut Env<'_>, slots: &[Slot]) -> RecordType {
+    RecordType::new(
+        env,
+        (slots.iter())
+            .map(|slot| (slot.label.clone(), slot.union.var))
+            .collect(),
+    )
+}
+
+/// Builds `{ l1: Err NoField, ..., ln: Err NoField }`.
+fn initial_state(state: &RecordType, slots: &[Slot], no_field: &TagUnion) -> Expr {
+    state.record(
+        (slots.iter())
+            .map(|slot| {
+                let no_field = no_field.tag("NoField", vec![]);
+                (slot.label.clone(), slot.union.tag("Err", vec![no_field]))
+            })
+            .collect(),
+    )
+}
+
+/// Builds
+///
+/// ```text
+/// \state, field ->
+///   when field is
+///     "f1" -> Keep (<decoder of t1 that stores its result in `state.f1`>)
+///     ...
+///     _ -> Skip
+/// ```
+fn record_step_field(env: &mut Env<'_>, state: &RecordType, slots: &[Slot]) -> (Variable, Expr) {
+    let state_sym = env.new_symbol("state");
+    let field_sym = env.new_symbol("field");
+
+    // [Keep (Decoder state fmt), Skip]
+    let state_decoder_var = env.subs.fresh_unnamed_flex_var();
+    let keep_or_skip = TagUnion::new(
+        env,
+        vec![
+            (tag_name("Keep"), vec![state_decoder_var]),
+            (tag_name("Skip"), vec![]),
+        ],
+    );
+
+    let branches = (slots.iter())
+        .map(|slot| {
+            let (decoder_var, decoder) = decode_slot(env, state, state_sym, slot);
+            env.unify(decoder_var, state_decoder_var);
+
+            (
+                Pattern::StrLiteral(slot.label.as_str().into()),
+                keep_or_skip.tag("Keep", vec![decoder]),
+            )
+        })
+        .collect();
+
+    let body = when_with_fallback(
+        (Variable::STR, Expr::Var(field_sym)),
+        branches,
+        keep_or_skip.tag("Skip", vec![]),
+        keep_or_skip.var,
+    );
+
+    let fn_name = env.new_symbol("stepField");
+    closure(
+        env,
+        fn_name,
+        vec![],
+        vec![
+            (state.var, Pattern::Identifier(state_sym)),
+            (Variable::STR, Pattern::Identifier(field_sym)),
+        ],
+        (keep_or_skip.var, body),
+    )
+}
+
+/// Builds
+///
+/// ```text
+/// \state ->
+///   when state is
+///     { f1: Ok f1, ..., fn: Ok fn } -> Ok { f1, ..., fn }
+///     { f1: Err _, .. } -> Err (Decode.missingField "f1")
+///     ...
+///     _ -> Err (Decode.missingField "fn")
+/// ```
+fn record_finalizer(
+    env: &mut Env<'_>,
+    state: &RecordType,
+    slots: &[Slot],
+    decoded: &RecordType,
+) -> (Variable, Expr) {
+    let state_sym = env.new_symbol("state");
+    let result = FinalizerResult::new(env, decoded.var);
+
+    let body = finalize_slots(
+        env,
+        state,
+        state_sym,
+        slots,
+        &result,
+        |vals| {
+            let fields = (slots.iter())
+                .map(|slot| slot.label.clone())
+                .zip(vals)
+                .collect();
+            decoded.record(fields)
+        },
+        |env, index| missing_field(env, &slots[index].label),
+    );
+
+    let fn_name = env.new_symbol("finalizer");
+    closure(
+        env,
+        fn_name,
+        vec![],
+        vec![(state.var, Pattern::Identifier(state_sym))],
+        (result.union.var, body),
+    )
+}
+
+/// Builds
+///
+/// ```text
+/// \state, name, index ->
+///   when name is
+///     "A" ->
+///       when index is
+///         0 -> Keep (<decoder of t11 that stores its result in `state.A_0`>)
+///         ...
+///         _ -> Skip
+///     ...
+///     _ -> Skip
+/// ```
+fn tag_step_payload(
+    env: &mut Env<'_>,
+    state: &RecordType,
+    tag_slots: &[(TagName, Vec<Slot>)],
+) -> (Variable, Expr) {
+    let state_sym = env.new_symbol("state");
+    let name_sym = env.new_symbol("name");
+    let index_sym = env.new_symbol("index");
+
+    // [Keep (Decoder state fmt), Skip]
+    let state_decoder_var = env.subs.fresh_unnamed_flex_var();
+    let keep_or_skip = TagUnion::new(
+        env,
+        vec![
+            (tag_name("Keep"), vec![state_decoder_var]),
+            (tag_name("Skip"), vec![]),
+        ],
+    );
+
+    let branches = (tag_slots.iter())
+        // A tag without payloads has nothing to decode, so it may as well be skipped.
+        .filter(|(_, slots)| !slots.is_empty())
+        .map(|(tag_name, slots)| {
+            let index_branches = (slots.iter().enumerate())
+                .map(|(index, slot)| {
+                    let (decoder_var, decoder) = decode_slot(env, state, state_sym, slot);
+                    env.unify(decoder_var, state_decoder_var);
+
+                    let index_pattern = Pattern::IntLiteral(
+                        Variable::NAT,
+                        Variable::NATURAL,
+                        index.to_string().into_boxed_str(),
+                        IntValue::I128((index as i128).to_ne_bytes()),
+                        IntBound::Exact(IntLitWidth::Nat),
+                    );
+
+                    (index_pattern, keep_or_skip.tag("Keep", vec![decoder]))
+                })
+                .collect();
+
+            let decode_payload = when_with_fallback(
+                (Variable::NAT, Expr::Var(index_sym)),
+                index_branches,
+                keep_or_skip.tag("Skip", vec![]),
+                keep_or_skip.var,
+            );
+
+            (
+                Pattern::StrLiteral(tag_name.0.as_str().into()),
+                decode_payload,
+            )
+        })
+        .collect();
+
+    let body = when_with_fallback(
+        (Variable::STR, Expr::Var(name_sym)),
+        branches,
+        keep_or_skip.tag("Skip", vec![]),
+        keep_or_skip.var,
+    );
+
+    let fn_name = env.new_symbol("stepPayload");
+    closure(
+        env,
+        fn_name,
+        vec![],
+        vec![
+            (state.var, Pattern::Identifier(state_sym)),
+            (Variable::STR, Pattern::Identifier(name_sym)),
+            (Variable::NAT, Pattern::Identifier(index_sym)),
+        ],
+        (keep_or_skip.var, body),
+    )
+}
+
+/// Builds
+///
+/// ```text
+/// \state, name ->
+///   when name is
+///     "A" ->
+///       when state is
+///         { A_0: Ok x11, ..., A_n-1: Ok x1n } -> Ok (A x11 .. x1n)
+///         { A_0: Err _, .. } -> Err (Decode.missingPayload "A" 0)
+///         ...
+///         _ -> Err (Decode.missingPayload "A" (n - 1))
+///     ...
+///     _ -> Err (Decode.unknownTag name)
+/// ```
+fn tag_finalizer(
+    env: &mut Env<'_>,
+    state: &RecordType,
+    tag_slots: &[(TagName, Vec<Slot>)],
+    decoded: &TagUnion,
+) -> (Variable, Expr) {
+    let state_sym = env.new_symbol("state");
+    let name_sym = env.new_symbol("name");
+    let result = FinalizerResult::new(env, decoded.var);
+
+    let branches = (tag_slots.iter())
+        .map(|(tag_name, slots)| {
+            let finalize_tag = finalize_slots(
+                env,
+                state,
+                state_sym,
+                slots,
+                &result,
+                |vals| decoded.tag(tag_name.0.as_str(), vals),
+                |env, index| missing_payload(env, tag_name, index),
+            );
+
+            (
+                Pattern::StrLiteral(tag_name.0.as_str().into()),
+                finalize_tag,
+            )
+        })
+        .collect();
+
+    // Decode.unknownTag name
+    let unknown_tag = call_builtin(
+        env,
+        Symbol::DECODE_UNKNOWN_TAG,
+        vec![(Variable::STR, Expr::Var(name_sym))],
+    );
+    let unknown_tag = result.err(env, unknown_tag);
+
+    let body = when_with_fallback(
+        (Variable::STR, Expr::Var(name_sym)),
+        branches,
+        unknown_tag,
+        result.union.var,
+    );
+
+    let fn_name = env.new_symbol("finalizer");
+    closure(
+        env,
+        fn_name,
+        vec![],
+        vec![
+            (state.var, Pattern::Identifier(state_sym)),
+            (Variable::STR, Pattern::Identifier(name_sym)),
+        ],
+        (result.union.var, body),
+    )
+}
+
+/// Builds a decoder of the value of `slot` that stores it in the decoder state `state_sym`:
+///
+/// ```text
+/// Decode.custom \bytes, fmt ->
+///   when Decode.decodeWith bytes Decode.decoder fmt is
+///     { result, rest } ->
+///       decoded =
+///         when result is
+///           Ok val -> Ok { state & label: Ok val }
+///           Err e -> Err e
+///
+///       { result: decoded, rest }
+/// ```
+///
+/// where `decoded` is inlined.
+fn decode_slot(
+    env: &mut Env<'_>,
+    state: &RecordType,
+    state_sym: Symbol,
+    slot: &Slot,
+) -> (Variable, Expr) {
+    decode_custom(env, vec![(state_sym, state.var)], |env, bytes, fmt| {
+        let result_sym = env.new_symbol("result");
+        let rest_sym = env.new_symbol("rest");
+        let val_sym = env.new_symbol("val");
+        let err_sym = env.new_symbol("e");
+
+        // Decode.decodeWith bytes Decode.decoder fmt : DecodeResult val
+        let decoder = decoder_of(env, slot.val_var);
+        let (decode_result_var, decode_result) =
+            call_builtin(env, Symbol::DECODE_DECODE_WITH, vec![bytes, decoder, fmt]);
+
+        // { result: [Err e, Ok val], rest: List U8 }
+        let err_var = env.subs.fresh_unnamed_flex_var();
+        let rest_var = env.subs.fresh_unnamed_flex_var();
+        let in_result = TagUnion::new(
+            env,
+            vec![
+                (tag_name("Err"), vec![err_var]),
+                (tag_name("Ok"), vec![slot.val_var]),
+            ],
+        );
+        let in_decode_result = RecordType::new(
+            env,
+            vec![("result".into(), in_result.var), ("rest".into(), rest_var)],
+        );
+        env.unify(decode_result_var, in_decode_result.var);
+
+        // { result: [Err e, Ok state], rest: List U8 }
+        let out_result = TagUnion::new(
+            env,
+            vec![
+                (tag_name("Err"), vec![err_var]),
+                (tag_name("Ok"), vec![state.var]),
+            ],
+        );
+        let out_decode_result = RecordType::new(
+            env,
+            vec![("result".into(), out_result.var), ("rest".into(), rest_var)],
+        );
+
+        // { state & label: Ok val }
+        let updated_state = Expr::Update {
+            record_var: state.var,
+            ext_var: state.ext_without(env, std::slice::from_ref(&slot.label)),
+            symbol: state_sym,
+            updates: SendMap::from_iter([(
+                slot.label.clone(),
+                Field {
+                    var: slot.union.var,
+                    region: Region::zero(),
+                    loc_expr: Box::new(Loc::at_zero(
+                        slot.union.tag("Ok", vec![Expr::Var(val_sym)]),
+                    )),
+                },
+            )]),
+        };
+
+        // when result is
+        //   Ok val -> Ok { state & label: Ok val }
+        //   Err e -> Err e
+        let decoded = when(
+            (in_result.var, Expr::Var(result_sym)),
+            vec![
+                (
+                    in_result.pattern(env, "Ok", vec![Pattern::Identifier(val_sym)]),
+                    out_result.tag("Ok", vec![updated_state]),
+                ),
+                (
+                    in_result.pattern(env, "Err", vec![Pattern::Identifier(err_sym)]),
+                    out_result.tag("Err", vec![Expr::Var(err_sym)]),
+                ),
+            ],
+            out_result.var,
+        );
+
+        let decode_result_pattern = Pattern::RecordDestructure {
+            whole_var: in_decode_result.var,
+            ext_var: Variable::EMPTY_RECORD,
+            destructs: vec![
+                Loc::at_zero(RecordDestruct {
+                    var: rest_var,
+                    label: "rest".into(),
+                    symbol: rest_sym,
+                    typ: DestructType::Required,
+                }),
+                Loc::at_zero(RecordDestruct {
+                    var: in_result.var,
+                    label: "result".into(),
+                    symbol: result_sym,
+                    typ: DestructType::Required,
+                }),
+            ],
+        };
+        let body = out_decode_result.record(vec![
+            ("result".into(), decoded),
+            ("rest".into(), Expr::Var(rest_sym)),
+        ]);
+
+        (
+            out_decode_result.var,
+            when(
+                (decode_result_var, decode_result),
+                vec![(decode_result_pattern, body)],
+                out_decode_result.var,
+            ),
+        )
+    })
+}
+
+/// Builds
+///
+/// ```text
+/// when state is
+///   { l1: Ok x1, ..., ln: Ok xn } -> Ok (make_ok [x1, ..., xn])
+///   { l1: Err _, .. } -> Err (missing 1)
+///   ...
+///   _ -> Err (missing n)
+/// ```
+///
+/// or just `Ok (make_ok [])` if there are no slots to look at.
+fn finalize_slots(
+    env: &mut Env<'_>,
+    state: &RecordType,
+    state_sym: Symbol,
+    slots: &[Slot],
+    result: &FinalizerResult,
+    make_ok: impl FnOnce(Vec<Expr>) -> Expr,
+    mut missing: impl FnMut(&mut Env<'_>, usize) -> (Variable, Expr),
+) -> Expr {
+    let other_slots = match slots.split_last() {
+        Some((_last_slot, other_slots)) => other_slots,
+        None => return result.ok(make_ok(vec![])),
+    };
+
+    let val_syms: Vec<_> = (slots.iter())
+        .map(|slot| env.new_symbol(slot.label.as_str()))
+        .collect();
+
+    let labels: Vec<_> = slots.iter().map(|slot| slot.label.clone()).collect();
+    let destructs = (slots.iter())
+        .zip(val_syms.iter())
+        .map(|(slot, val_sym)| {
+            let ok_pattern = slot
+                .union
+                .pattern(env, "Ok", vec![Pattern::Identifier(*val_sym)]);
+            slot_destruct(env, slot, ok_pattern)
+        })
+        .collect();
+    let all_found_pattern = Pattern::RecordDestructure {
+        whole_var: state.var,
+        ext_var: state.ext_without(env, &labels),
+        destructs,
+    };
+    let all_found = result.ok(make_ok(val_syms.into_iter().map(Expr::Var).collect()));
+
+    let mut branches = vec![(all_found_pattern, all_found)];
+
+    // The first slot that has not been decoded is the one reported missing; if it is not any of
+    // the others, it must be the last one.
+    for (index, slot) in other_slots.iter().enumerate() {
+        let not_found_pattern = slot.union.pattern(env, "Err", vec![Pattern::Underscore]);
+        let destructs = vec![slot_destruct(env, slot, not_found_pattern)];
+        let pattern = Pattern::RecordDestructure {
+            whole_var: state.var,
+            ext_var: state.ext_without(env, std::slice::from_ref(&slot.label)),
+            destructs,
+        };
+
+        let error = missing(env, index);
+        branches.push((pattern, result.err(env, error)));
+    }
+    let last_missing = missing(env, other_slots.len());
+    let last_missing = result.err(env, last_missing);
+
+    when_with_fallback(
+        (state.var, Expr::Var(state_sym)),
+        branches,
+        last_missing,
+        result.union.var,
+    )
+}
+
+/// Builds the pattern `label: pattern` matching a slot of the decoder state.
+fn slot_destruct(env: &mut Env<'_>, slot: &Slot, pattern: Pattern) -> Loc<RecordDestruct> {
+    Loc::at_zero(RecordDestruct {
+        var: slot.union.var,
+        label: slot.label.clone(),
+        symbol: env.unique_symbol(),
+        typ: DestructType::Guard(slot.union.var, Loc::at_zero(pattern)),
+    })
+}
+
+/// The type `[Err DecodeError, Ok val]` of the result of a finalizer.
+struct FinalizerResult {
+    union: TagUnion,
+    err_var: Variable,
+}
+
+impl FinalizerResult {
+    fn new(env: &mut Env<'_>, val_var: Variable) -> Self {
+        let err_var = env.subs.fresh_unnamed_flex_var();
+        let union = TagUnion::new(
+            env,
+            vec![
+                (tag_name("Err"), vec![err_var]),
+                (tag_name("Ok"), vec![val_var]),
+            ],
+        );
+
+        Self { union, err_var }
+    }
+
+    /// `Ok val`
+    fn ok(&self, val: Expr) -> Expr {
+        self.union.tag("Ok", vec![val])
+    }
+
+    /// `Err error`
+    fn err(&self, env: &mut Env<'_>, error: (Variable, Expr)) -> Expr {
+        let (error_var, error) = error;
+        env.unify(error_var, self.err_var);
+
+        self.union.tag("Err", vec![error])
+    }
+}
+
+/// Builds `Decode.missingField "label"`.
+fn missing_field(env: &mut Env<'_>, label: &Lowercase) -> (Variable, Expr) {
+    call_builtin(
+        env,
+        Symbol::DECODE_MISSING_FIELD,
+        vec![(Variable::STR, Expr::Str(label.as_str().into()))],
+    )
+}
+
+/// Builds `Decode.missingPayload "tag_name" index`.
+fn missing_payload(env: &mut Env<'_>, tag_name: &TagName, index: usize) -> (Variable, Expr) {
+    let index = Expr::Int(
+        Variable::NAT,
+        Variable::NATURAL,
+        index.to_string().into_boxed_str(),
+        IntValue::I128((index as i128).to_ne_bytes()),
+        IntBound::Exact(IntLitWidth::Nat),
+    );
+
+    call_builtin(
+        env,
+        Symbol::DECODE_MISSING_PAYLOAD,
+        vec![
+            (Variable::STR, Expr::Str(tag_name.0.as_str().into())),
+            (Variable::NAT, index),
+        ],
+    )
+}
+
+fn tag_name(name: &str) -> TagName {
+    TagName(name.into())
+}
+
+/// Builds `Decode.decoder : Decoder val fmt`.
+fn decoder_of(env: &mut Env<'_>, val_var: Variable) -> (Variable, Expr) {
+    // Decoder val fmt | val has Decoding, fmt has DecoderFormatting
+    let decoder_var = env.import_builtin_symbol_var(Symbol::DECODE_DECODER);
+
+    let this_val_var = match env.subs.get_content_without_compacting(decoder_var) {
+        Content::Alias(Symbol::DECODE_DECODER_OPAQUE, vars, _, AliasKind::Opaque)
+            if vars.type_variables_len == 2 =>
+        {
+            env.subs.get_subs_slice(vars.type_variables())[0]
+        }
+        _ => internal_error!("Decode.decode not an opaque type"),
+    };
+
+    env.unify(this_val_var, val_var);
+
+    (
+        decoder_var,
+        Expr::AbilityMember(Symbol::DECODE_DECODER, None, decoder_var),
+    )
+}
+
+/// Builds `Decode.custom \bytes, fmt -> body`, where `build_body` is given the `bytes` and `fmt`
+/// arguments to make the body from, and the closure captures `captures`.
+fn decode_custom(
+    env: &mut Env<'_>,
+    captures: Vec<(Symbol, Variable)>,
+    build_body: impl FnOnce(&mut Env<'_>, (Variable, Expr), (Variable, Expr)) -> (Variable, Expr),
+) -> (Variable, Expr) {
+    let bytes_sym = env.new_symbol("bytes");
+    let bytes_var = Variable::LIST_U8;
+    let fmt_sym = env.new_symbol("fmt");
+    let fmt_var = env.subs.fresh_unnamed_flex_var();
+
+    let body = build_body(
+        env,
+        (bytes_var, Expr::Var(bytes_sym)),
+        (fmt_var, Expr::Var(fmt_sym)),
+    );
+
+    // \bytes, fmt -> body
+    let fn_name = env.new_symbol("custom");
+    let custom_lambda = closure(
+        env,
+        fn_name,
+        captures,
+        vec![
+            (bytes_var, Pattern::Identifier(bytes_sym)),
+            (fmt_var, Pattern::Identifier(fmt_sym)),
+        ],
+        body,
+    );
+
+    // Decode.custom \bytes, fmt -> body
+    call_builtin(env, Symbol::DECODE_CUSTOM, vec![custom_lambda])
+}
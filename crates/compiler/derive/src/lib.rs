@@ -16,8 +16,10 @@ use roc_types::subs::{
 };
 use util::Env;
 
+mod arbitrary;
 mod decoding;
 mod encoding;
+mod hash;
 
 mod util;
 
@@ -77,6 +79,10 @@ fn build_derived_body(
         DeriveKey::Decoder(decoder_key) => {
             decoding::derive_decoder(&mut env, decoder_key, derived_symbol)
         }
+        DeriveKey::Hash(hash_key) => hash::derive_hash(&mut env, hash_key, derived_symbol),
+        DeriveKey::Arbitrary(arbitrary_key) => {
+            arbitrary::derive_arbitrary(&mut env, arbitrary_key, derived_symbol)
+        }
     };
 
     let def = Def {
@@ -139,6 +145,11 @@ impl DerivedModule {
         self.map.iter()
     }
 
+    /// Whether `symbol` names a derived implementation, rather than a symbol local to one.
+    pub fn is_derived_impl(&self, symbol: Symbol) -> bool {
+        self.map.values().any(|(derived, _, _)| *derived == symbol)
+    }
+
     /// Generate a unique symbol. This should only be used when generating code inside the Derived
     /// module; other modules should use [`Self::get_or_insert`] to generate a symbol for a derived
     /// ability member usage.
@@ -1,9 +1,22 @@
+use std::iter::once;
+
+use roc_can::expr::{
+    AnnotatedMark, ClosureData, Expr, Field, Recursive, WhenBranch, WhenBranchPattern,
+};
+use roc_can::pattern::Pattern;
 use roc_can::{abilities::SpecializationLambdaSets, module::ExposedByModule};
 use roc_error_macros::internal_error;
+use roc_module::called_via::CalledVia;
+use roc_module::ident::{Lowercase, TagName};
 use roc_module::symbol::{IdentIds, Symbol};
-use roc_types::subs::{instantiate_rigids, Subs, Variable};
+use roc_region::all::{Loc, Region};
+use roc_types::subs::{
+    instantiate_rigids, Content, ExhaustiveMark, FlatType, LambdaSet, OptVariable, RecordFields,
+    RedundantMark, Subs, SubsSlice, UnionLambdas, UnionTags, Variable, VariableSubsSlice,
+};
+use roc_types::types::RecordField;
 
-use crate::DERIVED_SYNTH;
+use crate::{synth_var, DERIVED_SYNTH};
 
 /// An environment representing the Derived_synth module, for use in building derived
 /// implementations.
@@ -153,3 +166,327 @@ impl Env<'_> {
         }
     }
 }
+
+/// A closed tag union type, along with its tags, so that values and patterns of the tags can be
+/// built.
+#[derive(Clone)]
+pub(crate) struct TagUnion {
+    pub var: Variable,
+    /// Sorted by tag name, as in the union.
+    pub tags: Vec<(TagName, Vec<Variable>, Variable)>,
+}
+
+impl TagUnion {
+    pub fn new(env: &mut Env<'_>, mut tags: Vec<(TagName, Vec<Variable>)>) -> Self {
+        tags.sort_by(|(t1, _), (t2, _)| t1.cmp(t2));
+
+        // Each tag is built and matched on with the rest of the union as its extension.
+        let tags_with_exts = (0..tags.len())
+            .map(|i| {
+                let (tag_name, payload_vars) = tags[i].clone();
+                let other_tags = (tags.iter().enumerate())
+                    .filter(|(j, _)| *j != i)
+                    .map(|(_, tag)| tag.clone());
+                let ext_var = synth_tag_union(env, other_tags);
+                (tag_name, payload_vars, ext_var)
+            })
+            .collect();
+        let var = synth_tag_union(env, tags);
+
+        Self {
+            var,
+            tags: tags_with_exts,
+        }
+    }
+
+    pub fn get(&self, tag_name: &str) -> (TagName, &[Variable], Variable) {
+        match self
+            .tags
+            .iter()
+            .find(|(name, _, _)| name.0.as_str() == tag_name)
+        {
+            Some((name, payload_vars, ext_var)) => (name.clone(), payload_vars, *ext_var),
+            None => internal_error!("{} is not a tag of this union", tag_name),
+        }
+    }
+
+    /// Builds `tag_name arg1 .. argn`.
+    pub fn tag(&self, tag_name: &str, arguments: Vec<Expr>) -> Expr {
+        let (name, payload_vars, ext_var) = self.get(tag_name);
+        debug_assert_eq!(payload_vars.len(), arguments.len());
+
+        Expr::Tag {
+            variant_var: self.var,
+            ext_var,
+            name,
+            arguments: (payload_vars.iter().copied())
+                .zip(arguments)
+                .map(|(var, arg)| (var, Loc::at_zero(arg)))
+                .collect(),
+        }
+    }
+
+    /// Builds the pattern `tag_name p1 .. pn`.
+    pub fn pattern(&self, _env: &mut Env<'_>, tag_name: &str, arguments: Vec<Pattern>) -> Pattern {
+        let (name, payload_vars, ext_var) = self.get(tag_name);
+        debug_assert_eq!(payload_vars.len(), arguments.len());
+
+        Pattern::AppliedTag {
+            whole_var: self.var,
+            ext_var,
+            tag_name: name,
+            arguments: (payload_vars.iter().copied())
+                .zip(arguments)
+                .map(|(var, arg)| (var, Loc::at_zero(arg)))
+                .collect(),
+        }
+    }
+}
+
+/// A closed record type, along with its fields.
+pub(crate) struct RecordType {
+    pub var: Variable,
+    pub fields: Vec<(Lowercase, Variable)>,
+}
+
+impl RecordType {
+    pub fn new(env: &mut Env<'_>, mut fields: Vec<(Lowercase, Variable)>) -> Self {
+        fields.sort_by(|(f1, _), (f2, _)| f1.cmp(f2));
+        let var = synth_record(env, fields.clone());
+
+        Self { var, fields }
+    }
+
+    /// The type of this record without the fields `labels`, for use as the extension of a record
+    /// update or pattern of those fields.
+    pub fn ext_without(&self, env: &mut Env<'_>, labels: &[Lowercase]) -> Variable {
+        let other_fields = (self.fields.iter())
+            .filter(|(label, _)| !labels.contains(label))
+            .cloned()
+            .collect();
+        synth_record(env, other_fields)
+    }
+
+    /// Builds `{ l1: e1, ..., ln: en }`.
+    pub fn record(&self, fields: Vec<(Lowercase, Expr)>) -> Expr {
+        if self.fields.is_empty() {
+            return Expr::EmptyRecord;
+        }
+
+        let fields = (fields.into_iter())
+            .map(|(label, expr)| {
+                let var = match self.fields.iter().find(|(l, _)| *l == label) {
+                    Some((_, var)) => *var,
+                    None => internal_error!("{} is not a field of this record", label),
+                };
+                let field = Field {
+                    var,
+                    region: Region::zero(),
+                    loc_expr: Box::new(Loc::at_zero(expr)),
+                };
+                (label, field)
+            })
+            .collect();
+
+        Expr::Record {
+            record_var: self.var,
+            fields,
+        }
+    }
+}
+
+/// The closed tag union type `[ tag1 args1, ..., tagn argsn ]`; `tags` must be sorted by name.
+pub(crate) fn synth_tag_union(
+    env: &mut Env<'_>,
+    tags: impl IntoIterator<Item = (TagName, Vec<Variable>)>,
+) -> Variable {
+    let tags: Vec<_> = tags.into_iter().collect();
+    if tags.is_empty() {
+        return Variable::EMPTY_TAG_UNION;
+    }
+
+    let union_tags = UnionTags::insert_into_subs(env.subs, tags);
+    synth_var(
+        env.subs,
+        Content::Structure(FlatType::TagUnion(union_tags, Variable::EMPTY_TAG_UNION)),
+    )
+}
+
+/// The closed record type `{ f1: t1, ..., fn: tn }`; `fields` must be sorted by label.
+pub(crate) fn synth_record(env: &mut Env<'_>, fields: Vec<(Lowercase, Variable)>) -> Variable {
+    if fields.is_empty() {
+        return Variable::EMPTY_RECORD;
+    }
+
+    let fields = RecordFields::insert_into_subs(
+        env.subs,
+        (fields.into_iter()).map(|(label, var)| (label, RecordField::Required(var))),
+    );
+    synth_var(
+        env.subs,
+        Content::Structure(FlatType::Record(fields, Variable::EMPTY_RECORD)),
+    )
+}
+
+/// Builds `when cond is p1 -> e1 ... pn -> en`.
+pub(crate) fn when(
+    cond: (Variable, Expr),
+    branches: Vec<(Pattern, Expr)>,
+    expr_var: Variable,
+) -> Expr {
+    let (cond_var, cond) = cond;
+
+    let branches = (branches.into_iter())
+        .map(|(pattern, value)| WhenBranch {
+            patterns: vec![WhenBranchPattern {
+                pattern: Loc::at_zero(pattern),
+                degenerate: false,
+            }],
+            value: Loc::at_zero(value),
+            guard: None,
+            redundant: RedundantMark::known_non_redundant(),
+        })
+        .collect();
+
+    Expr::When {
+        loc_cond: Box::new(Loc::at_zero(cond)),
+        cond_var,
+        expr_var,
+        region: Region::zero(),
+        branches,
+        branches_cond_var: cond_var,
+        exhaustive: ExhaustiveMark::known_exhaustive(),
+    }
+}
+
+/// Builds `when cond is p1 -> e1 ... pn -> en; _ -> fallback`, or just `fallback` if there are no
+/// other branches.
+pub(crate) fn when_with_fallback(
+    cond: (Variable, Expr),
+    mut branches: Vec<(Pattern, Expr)>,
+    fallback: Expr,
+    expr_var: Variable,
+) -> Expr {
+    if branches.is_empty() {
+        return fallback;
+    }
+
+    branches.push((Pattern::Underscore, fallback));
+    when(cond, branches, expr_var)
+}
+
+/// Builds the closure `\arg1, ..., argn -> body` named `fn_name`, capturing `captures`.
+pub(crate) fn closure(
+    env: &mut Env<'_>,
+    fn_name: Symbol,
+    captures: Vec<(Symbol, Variable)>,
+    arguments: Vec<(Variable, Pattern)>,
+    body: (Variable, Expr),
+) -> (Variable, Expr) {
+    let (body_var, body) = body;
+
+    // Create fn_var for ambient capture; we fix it up below.
+    let fn_var = synth_var(env.subs, Content::Error);
+
+    // -[[fn_name captures]]->
+    let capture_vars: Vec<_> = captures.iter().map(|(_, var)| *var).collect();
+    let fn_name_labels = UnionLambdas::insert_into_subs(env.subs, once((fn_name, capture_vars)));
+    let fn_clos_var = synth_var(
+        env.subs,
+        Content::LambdaSet(LambdaSet {
+            solved: fn_name_labels,
+            recursion_var: OptVariable::NONE,
+            unspecialized: SubsSlice::default(),
+            ambient_function: fn_var,
+        }),
+    );
+
+    // arg1, ..., argn -[[fn_name captures]]-> body
+    let args_slice =
+        VariableSubsSlice::insert_into_subs(env.subs, arguments.iter().map(|(var, _)| *var));
+    env.subs.set_content(
+        fn_var,
+        Content::Structure(FlatType::Func(args_slice, fn_clos_var, body_var)),
+    );
+
+    let clos = Expr::Closure(ClosureData {
+        function_type: fn_var,
+        closure_type: fn_clos_var,
+        return_type: body_var,
+        name: fn_name,
+        captured_symbols: captures,
+        recursive: Recursive::NotRecursive,
+        arguments: (arguments.into_iter())
+            .map(|(var, pattern)| {
+                (
+                    var,
+                    AnnotatedMark::known_exhaustive(),
+                    Loc::at_zero(pattern),
+                )
+            })
+            .collect(),
+        loc_body: Box::new(Loc::at_zero(body)),
+    });
+
+    (fn_var, clos)
+}
+
+/// Builds `symbol arg1 .. argn`, where `symbol` is a builtin function.
+pub(crate) fn call_builtin(
+    env: &mut Env<'_>,
+    symbol: Symbol,
+    arguments: Vec<(Variable, Expr)>,
+) -> (Variable, Expr) {
+    call_builtin_help(env, symbol, arguments, |_| Expr::Var(symbol))
+}
+
+/// Builds `member arg1 .. argn`, where `member` is a builtin ability member.
+pub(crate) fn call_builtin_member(
+    env: &mut Env<'_>,
+    member: Symbol,
+    arguments: Vec<(Variable, Expr)>,
+) -> (Variable, Expr) {
+    call_builtin_help(env, member, arguments, |fn_var| {
+        Expr::AbilityMember(member, None, fn_var)
+    })
+}
+
+pub(crate) fn call_builtin_help(
+    env: &mut Env<'_>,
+    symbol: Symbol,
+    arguments: Vec<(Variable, Expr)>,
+    head: impl FnOnce(Variable) -> Expr,
+) -> (Variable, Expr) {
+    // symbol : a1, ..., an -[uls]-> r
+    let exposed_fn_var = env.import_builtin_symbol_var(symbol);
+
+    // (typeof arg1), ..., (typeof argn) -[clos]-> ret
+    let this_args_slice =
+        VariableSubsSlice::insert_into_subs(env.subs, arguments.iter().map(|(var, _)| *var));
+    let this_clos_var = env.subs.fresh_unnamed_flex_var();
+    let this_ret_var = env.subs.fresh_unnamed_flex_var();
+    let this_fn_var = synth_var(
+        env.subs,
+        Content::Structure(FlatType::Func(this_args_slice, this_clos_var, this_ret_var)),
+    );
+
+    //   a1,              ..., an              -[uls]->  r
+    // ~ (typeof arg1),   ..., (typeof argn)   -[clos]-> ret
+    env.unify(exposed_fn_var, this_fn_var);
+
+    let fn_data = Box::new((
+        this_fn_var,
+        Loc::at_zero(head(this_fn_var)),
+        this_clos_var,
+        this_ret_var,
+    ));
+    let call = Expr::Call(
+        fn_data,
+        (arguments.into_iter())
+            .map(|(var, arg)| (var, Loc::at_zero(arg)))
+            .collect(),
+        CalledVia::Space,
+    );
+
+    (this_ret_var, call)
+}
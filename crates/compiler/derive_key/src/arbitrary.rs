@@ -0,0 +1,151 @@
+use roc_error_macros::internal_error;
+use roc_module::{
+    ident::{Lowercase, TagName},
+    symbol::Symbol,
+};
+use roc_types::subs::{Content, FlatType, GetSubsSlice, Subs, Variable};
+
+use crate::{
+    util::{check_derivable_ext_var, debug_name_record, debug_name_tag},
+    DeriveError,
+};
+
+#[derive(Hash, PartialEq, Eq, Debug, Clone)]
+pub enum FlatArbitraryKey {
+    /// Values generated by one of the generators of the `Arbitrary` module, like `Arbitrary.u8`.
+    /// These are plain functions rather than ability members, so unlike the immediates of the
+    /// other abilities they are wrapped in a derived implementation that calls them.
+    Generator(Symbol),
+    // Unfortunate that we must allocate here, c'est la vie
+    Record(Vec<Lowercase>),
+    TagUnion(Vec<(TagName, u16)>),
+}
+
+impl FlatArbitraryKey {
+    pub(crate) fn debug_name(&self) -> String {
+        match self {
+            FlatArbitraryKey::Generator(generator) => generator_name(*generator).to_string(),
+            FlatArbitraryKey::Record(fields) => debug_name_record(fields),
+            FlatArbitraryKey::TagUnion(tags) => debug_name_tag(tags),
+        }
+    }
+}
+
+impl FlatArbitraryKey {
+    pub(crate) fn from_var(subs: &Subs, var: Variable) -> Result<FlatArbitraryKey, DeriveError> {
+        use DeriveError::*;
+        use FlatArbitraryKey::*;
+        match *subs.get_content_without_compacting(var) {
+            Content::Structure(flat_type) => match flat_type {
+                FlatType::Apply(sym, _) => match sym {
+                    Symbol::LIST_LIST => Ok(Generator(Symbol::ARBITRARY_LIST)),
+                    Symbol::STR_STR => Ok(Generator(Symbol::ARBITRARY_STR)),
+                    _ => Err(Underivable),
+                },
+                FlatType::Record(fields, ext) => {
+                    // An unbound extension is treated as closed, so that records destructured by a
+                    // pattern can be generated.
+                    check_derivable_ext_var(subs, ext, |ext| {
+                        matches!(
+                            ext,
+                            Content::Structure(FlatType::EmptyRecord) | Content::FlexVar(_)
+                        )
+                    })?;
+
+                    let mut field_names: Vec<_> =
+                        subs.get_subs_slice(fields.field_names()).to_vec();
+                    field_names.sort();
+
+                    Ok(Record(field_names))
+                }
+                FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
+                    // As with the other derivers, the recursion var doesn't matter: the derived
+                    // implementation only looks at the surface of the tag union type, and leaves
+                    // the payload types generic for the monomorphizer to fill in.
+                    check_derivable_ext_var(subs, ext, |ext| {
+                        matches!(
+                            ext,
+                            Content::Structure(FlatType::EmptyTagUnion) | Content::FlexVar(_)
+                        )
+                    })?;
+
+                    if tags.is_empty() {
+                        // There are no values to generate.
+                        return Err(Underivable);
+                    }
+
+                    let mut tag_names_and_payload_sizes: Vec<_> = tags
+                        .iter_all()
+                        .map(|(name_index, payload_slice_index)| {
+                            let payload_slice = subs[payload_slice_index];
+                            let payload_size = payload_slice.length;
+                            let name = &subs[name_index];
+                            (name.clone(), payload_size)
+                        })
+                        .collect();
+                    tag_names_and_payload_sizes.sort_by(|(t1, _), (t2, _)| t1.cmp(t2));
+                    Ok(TagUnion(tag_names_and_payload_sizes))
+                }
+                FlatType::FunctionOrTagUnion(name_index, _, _) => {
+                    Ok(TagUnion(vec![(subs[name_index].clone(), 0)]))
+                }
+                FlatType::EmptyRecord => Ok(Record(vec![])),
+                // There are no values of the empty tag union to generate.
+                FlatType::EmptyTagUnion => Err(Underivable),
+                //
+                FlatType::Erroneous(_) => Err(Underivable),
+                FlatType::Func(..) => Err(Underivable),
+            },
+            Content::Alias(sym, _, real_var, _) => match sym {
+                Symbol::NUM_U8 | Symbol::NUM_UNSIGNED8 => Ok(Generator(Symbol::ARBITRARY_U8)),
+                Symbol::NUM_U16 | Symbol::NUM_UNSIGNED16 => Ok(Generator(Symbol::ARBITRARY_U16)),
+                Symbol::NUM_U32 | Symbol::NUM_UNSIGNED32 => Ok(Generator(Symbol::ARBITRARY_U32)),
+                Symbol::NUM_U64 | Symbol::NUM_UNSIGNED64 => Ok(Generator(Symbol::ARBITRARY_U64)),
+                Symbol::NUM_U128 | Symbol::NUM_UNSIGNED128 => Ok(Generator(Symbol::ARBITRARY_U128)),
+                Symbol::NUM_I8 | Symbol::NUM_SIGNED8 => Ok(Generator(Symbol::ARBITRARY_I8)),
+                Symbol::NUM_I16 | Symbol::NUM_SIGNED16 => Ok(Generator(Symbol::ARBITRARY_I16)),
+                Symbol::NUM_I32 | Symbol::NUM_SIGNED32 => Ok(Generator(Symbol::ARBITRARY_I32)),
+                Symbol::NUM_I64 | Symbol::NUM_SIGNED64 => Ok(Generator(Symbol::ARBITRARY_I64)),
+                Symbol::NUM_I128 | Symbol::NUM_SIGNED128 => Ok(Generator(Symbol::ARBITRARY_I128)),
+                Symbol::NUM_NAT | Symbol::NUM_NATURAL => Ok(Generator(Symbol::ARBITRARY_NAT)),
+                Symbol::NUM_DEC | Symbol::NUM_DECIMAL => Ok(Generator(Symbol::ARBITRARY_DEC)),
+                Symbol::NUM_F32 | Symbol::NUM_BINARY32 => Ok(Generator(Symbol::ARBITRARY_F32)),
+                Symbol::NUM_F64 | Symbol::NUM_BINARY64 => Ok(Generator(Symbol::ARBITRARY_F64)),
+                // NB: I believe it is okay to unwrap opaques here because derivers are only used
+                // by the backend, and the backend treats opaques like structural aliases.
+                _ => Self::from_var(subs, real_var),
+            },
+            Content::RangedNumber(_) => Err(Underivable),
+            //
+            Content::RecursionVar { .. } => Err(Underivable),
+            Content::Error => Err(Underivable),
+            Content::FlexVar(_)
+            | Content::RigidVar(_)
+            | Content::FlexAbleVar(_, _)
+            | Content::RigidAbleVar(_, _) => Err(UnboundVar),
+            Content::LambdaSet(_) => Err(Underivable),
+        }
+    }
+}
+
+fn generator_name(generator: Symbol) -> &'static str {
+    match generator {
+        Symbol::ARBITRARY_LIST => "list",
+        Symbol::ARBITRARY_STR => "str",
+        Symbol::ARBITRARY_U8 => "u8",
+        Symbol::ARBITRARY_U16 => "u16",
+        Symbol::ARBITRARY_U32 => "u32",
+        Symbol::ARBITRARY_U64 => "u64",
+        Symbol::ARBITRARY_U128 => "u128",
+        Symbol::ARBITRARY_I8 => "i8",
+        Symbol::ARBITRARY_I16 => "i16",
+        Symbol::ARBITRARY_I32 => "i32",
+        Symbol::ARBITRARY_I64 => "i64",
+        Symbol::ARBITRARY_I128 => "i128",
+        Symbol::ARBITRARY_NAT => "nat",
+        Symbol::ARBITRARY_DEC => "dec",
+        Symbol::ARBITRARY_F32 => "f32",
+        Symbol::ARBITRARY_F64 => "f64",
+        _ => internal_error!("{:?} is not a generator", generator),
+    }
+}
@@ -1,7 +1,13 @@
-use roc_module::symbol::Symbol;
-use roc_types::subs::{Content, FlatType, Subs, Variable};
+use roc_module::{
+    ident::{Lowercase, TagName},
+    symbol::Symbol,
+};
+use roc_types::subs::{Content, FlatType, GetSubsSlice, Subs, Variable};
 
-use crate::DeriveError;
+use crate::{
+    util::{check_derivable_ext_var, debug_name_record, debug_name_tag},
+    DeriveError,
+};
 
 #[derive(Hash)]
 pub enum FlatDecodable {
@@ -12,12 +18,18 @@ pub enum FlatDecodable {
 #[derive(Hash, PartialEq, Eq, Debug, Clone)]
 pub enum FlatDecodableKey {
     List(/* takes one variable */),
+
+    // Unfortunate that we must allocate here, c'est la vie
+    Record(Vec<Lowercase>),
+    TagUnion(Vec<(TagName, u16)>),
 }
 
 impl FlatDecodableKey {
     pub(crate) fn debug_name(&self) -> String {
         match self {
             FlatDecodableKey::List() => "list".to_string(),
+            FlatDecodableKey::Record(fields) => debug_name_record(fields),
+            FlatDecodableKey::TagUnion(tags) => debug_name_tag(tags),
         }
     }
 }
@@ -33,21 +45,42 @@ impl FlatDecodable {
                     Symbol::STR_STR => Ok(Immediate(Symbol::DECODE_STRING)),
                     _ => Err(Underivable),
                 },
-                FlatType::Record(_fields, _ext) => {
-                    Err(Underivable) // yet
-                }
-                FlatType::TagUnion(_tags, _ext) | FlatType::RecursiveTagUnion(_, _tags, _ext) => {
-                    Err(Underivable) // yet
-                }
-                FlatType::FunctionOrTagUnion(_name_index, _, _) => {
-                    Err(Underivable) // yet
-                }
-                FlatType::EmptyRecord => {
-                    Err(Underivable) // yet
+                FlatType::Record(fields, ext) => {
+                    check_derivable_ext_var(subs, ext, |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyRecord))
+                    })?;
+
+                    let mut field_names: Vec<_> =
+                        subs.get_subs_slice(fields.field_names()).to_vec();
+                    field_names.sort();
+
+                    Ok(Key(FlatDecodableKey::Record(field_names)))
                 }
-                FlatType::EmptyTagUnion => {
-                    Err(Underivable) // yet
+                FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
+                    // As with encoding and hashing, the recursion var doesn't matter: the derived
+                    // implementation only looks at the surface of the tag union type, and leaves
+                    // the payload types generic for the monomorphizer to fill in.
+                    check_derivable_ext_var(subs, ext, |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyTagUnion))
+                    })?;
+
+                    let mut tag_names_and_payload_sizes: Vec<_> = tags
+                        .iter_all()
+                        .map(|(name_index, payload_slice_index)| {
+                            let payload_slice = subs[payload_slice_index];
+                            let payload_size = payload_slice.length;
+                            let name = &subs[name_index];
+                            (name.clone(), payload_size)
+                        })
+                        .collect();
+                    tag_names_and_payload_sizes.sort_by(|(t1, _), (t2, _)| t1.cmp(t2));
+                    Ok(Key(FlatDecodableKey::TagUnion(tag_names_and_payload_sizes)))
                 }
+                FlatType::FunctionOrTagUnion(name_index, _, _) => Ok(Key(
+                    FlatDecodableKey::TagUnion(vec![(subs[name_index].clone(), 0)]),
+                )),
+                FlatType::EmptyRecord => Ok(Key(FlatDecodableKey::Record(vec![]))),
+                FlatType::EmptyTagUnion => Ok(Key(FlatDecodableKey::TagUnion(vec![]))),
                 //
                 FlatType::Erroneous(_) => Err(Underivable),
                 FlatType::Func(..) => Err(Underivable),
@@ -0,0 +1,131 @@
+use roc_module::{
+    ident::{Lowercase, TagName},
+    symbol::Symbol,
+};
+use roc_types::subs::{Content, FlatType, GetSubsSlice, Subs, Variable};
+
+use crate::{
+    util::{check_derivable_ext_var, debug_name_record, debug_name_tag},
+    DeriveError,
+};
+
+#[derive(Hash)]
+pub enum FlatHash {
+    /// A member of the `Hasher` ability, like `addU8`.
+    Immediate(Symbol),
+    /// A function of the `Hash` module that is not an ability member, like `hashStrBytes`.
+    SingleLambdaSetImmediate(Symbol),
+    Key(FlatHashKey),
+}
+
+#[derive(Hash, PartialEq, Eq, Debug, Clone)]
+pub enum FlatHashKey {
+    // Unfortunate that we must allocate here, c'est la vie
+    Record(Vec<Lowercase>),
+    TagUnion(Vec<(TagName, u16)>),
+}
+
+impl FlatHashKey {
+    pub(crate) fn debug_name(&self) -> String {
+        match self {
+            FlatHashKey::Record(fields) => debug_name_record(fields),
+            FlatHashKey::TagUnion(tags) => debug_name_tag(tags),
+        }
+    }
+}
+
+impl FlatHash {
+    pub(crate) fn from_var(subs: &Subs, var: Variable) -> Result<FlatHash, DeriveError> {
+        use DeriveError::*;
+        use FlatHash::*;
+        match *subs.get_content_without_compacting(var) {
+            Content::Structure(flat_type) => match flat_type {
+                FlatType::Apply(sym, _) => match sym {
+                    Symbol::LIST_LIST => Ok(SingleLambdaSetImmediate(Symbol::HASH_HASH_LIST)),
+                    Symbol::STR_STR => Ok(SingleLambdaSetImmediate(Symbol::HASH_HASH_STR_BYTES)),
+                    _ => Err(Underivable),
+                },
+                FlatType::Record(fields, ext) => {
+                    check_derivable_ext_var(subs, ext, |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyRecord))
+                    })?;
+
+                    let mut field_names: Vec<_> =
+                        subs.get_subs_slice(fields.field_names()).to_vec();
+                    field_names.sort();
+
+                    Ok(Key(FlatHashKey::Record(field_names)))
+                }
+                FlatType::TagUnion(tags, ext) | FlatType::RecursiveTagUnion(_, tags, ext) => {
+                    // The recursion var doesn't matter, because the derived implementation will only
+                    // look on the surface of the tag union type, and more over the payloads of the
+                    // arguments will be left generic for the monomorphizer to fill in with the
+                    // appropriate type. That is,
+                    //   [ A t1, B t1 t2 ]
+                    // and
+                    //   [ A t1, B t1 t2 ] as R
+                    // look the same on the surface, because `R` is only somewhere inside of the
+                    // `t`-prefixed payload types.
+                    check_derivable_ext_var(subs, ext, |ext| {
+                        matches!(ext, Content::Structure(FlatType::EmptyTagUnion))
+                    })?;
+
+                    let mut tag_names_and_payload_sizes: Vec<_> = tags
+                        .iter_all()
+                        .map(|(name_index, payload_slice_index)| {
+                            let payload_slice = subs[payload_slice_index];
+                            let payload_size = payload_slice.length;
+                            let name = &subs[name_index];
+                            (name.clone(), payload_size)
+                        })
+                        .collect();
+                    tag_names_and_payload_sizes.sort_by(|(t1, _), (t2, _)| t1.cmp(t2));
+                    Ok(Key(FlatHashKey::TagUnion(tag_names_and_payload_sizes)))
+                }
+                FlatType::FunctionOrTagUnion(name_index, _, _) => Ok(Key(FlatHashKey::TagUnion(
+                    vec![(subs[name_index].clone(), 0)],
+                ))),
+                FlatType::EmptyRecord => Ok(Key(FlatHashKey::Record(vec![]))),
+                FlatType::EmptyTagUnion => Ok(Key(FlatHashKey::TagUnion(vec![]))),
+                //
+                FlatType::Erroneous(_) => Err(Underivable),
+                FlatType::Func(..) => Err(Underivable),
+            },
+            Content::Alias(sym, _, real_var, _) => match sym {
+                Symbol::NUM_U8 | Symbol::NUM_UNSIGNED8 => Ok(Immediate(Symbol::HASH_ADD_U8)),
+                Symbol::NUM_U16 | Symbol::NUM_UNSIGNED16 => Ok(Immediate(Symbol::HASH_ADD_U16)),
+                Symbol::NUM_U32 | Symbol::NUM_UNSIGNED32 => Ok(Immediate(Symbol::HASH_ADD_U32)),
+                Symbol::NUM_U64 | Symbol::NUM_UNSIGNED64 => Ok(Immediate(Symbol::HASH_ADD_U64)),
+                Symbol::NUM_U128 | Symbol::NUM_UNSIGNED128 => Ok(Immediate(Symbol::HASH_ADD_U128)),
+                Symbol::NUM_I8 | Symbol::NUM_SIGNED8 => Ok(Immediate(Symbol::HASH_ADD_I8)),
+                Symbol::NUM_I16 | Symbol::NUM_SIGNED16 => Ok(Immediate(Symbol::HASH_ADD_I16)),
+                Symbol::NUM_I32 | Symbol::NUM_SIGNED32 => Ok(Immediate(Symbol::HASH_ADD_I32)),
+                Symbol::NUM_I64 | Symbol::NUM_SIGNED64 => Ok(Immediate(Symbol::HASH_ADD_I64)),
+                Symbol::NUM_I128 | Symbol::NUM_SIGNED128 => Ok(Immediate(Symbol::HASH_ADD_I128)),
+                Symbol::NUM_NAT | Symbol::NUM_NATURAL => {
+                    Ok(SingleLambdaSetImmediate(Symbol::HASH_HASH_NAT))
+                }
+                // Floats and decimals have no sensible notion of hashing; for example, NaN is
+                // not equal to itself.
+                Symbol::NUM_DEC
+                | Symbol::NUM_DECIMAL
+                | Symbol::NUM_F32
+                | Symbol::NUM_BINARY32
+                | Symbol::NUM_F64
+                | Symbol::NUM_BINARY64 => Err(Underivable),
+                // NOTE: I believe it is okay to unwrap opaques here because derivers are only used
+                // by the backend, and the backend treats opaques like structural aliases.
+                _ => Self::from_var(subs, real_var),
+            },
+            Content::RangedNumber(_) => Err(Underivable),
+            //
+            Content::RecursionVar { .. } => Err(Underivable),
+            Content::Error => Err(Underivable),
+            Content::FlexVar(_)
+            | Content::RigidVar(_)
+            | Content::FlexAbleVar(_, _)
+            | Content::RigidAbleVar(_, _) => Err(UnboundVar),
+            Content::LambdaSet(_) => Err(Underivable),
+        }
+    }
+}
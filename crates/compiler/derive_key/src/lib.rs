@@ -9,15 +9,24 @@
 //!   between e.g. required and optional record fields.
 //! - `Decoding` is like encoding, but has some differences. For one, it *does* need to distinguish
 //!   between required and optional record fields.
+//! - `Hash` is like encoding, in that it keys on the surface types of records and tag unions, but
+//!   numbers are hashed directly rather than through a separate implementation.
+//! - `Arbitrary` keys on surface types like hashing does, and generates numbers, strings and lists
+//!   with the generators of the `Arbitrary` module.
 //!
 //! For these reasons the content keying is based on a strategy as well, which are the variants of
 //! [`DeriveKey`].
 
+pub mod arbitrary;
 pub mod decoding;
 pub mod encoding;
+pub mod hash;
+mod util;
 
+use arbitrary::FlatArbitraryKey;
 use decoding::{FlatDecodable, FlatDecodableKey};
 use encoding::{FlatEncodable, FlatEncodableKey};
+use hash::{FlatHash, FlatHashKey};
 
 use roc_module::symbol::Symbol;
 use roc_types::subs::{Subs, Variable};
@@ -36,6 +45,8 @@ pub enum DeriveError {
 pub enum DeriveKey {
     ToEncoder(FlatEncodableKey),
     Decoder(FlatDecodableKey),
+    Hash(FlatHashKey),
+    Arbitrary(FlatArbitraryKey),
 }
 
 impl DeriveKey {
@@ -43,6 +54,8 @@ impl DeriveKey {
         match self {
             DeriveKey::ToEncoder(key) => format!("toEncoder_{}", key.debug_name()),
             DeriveKey::Decoder(key) => format!("decoder_{}", key.debug_name()),
+            DeriveKey::Hash(key) => format!("hash_{}", key.debug_name()),
+            DeriveKey::Arbitrary(key) => format!("arbitrary_{}", key.debug_name()),
         }
     }
 }
@@ -52,6 +65,9 @@ pub enum Derived {
     /// If a derived implementation name is well-known ahead-of-time, we can inline the symbol
     /// directly rather than associating a key for an implementation to be made later on.
     Immediate(Symbol),
+    /// Like [`Derived::Immediate`], but the symbol is a function that is not an ability member,
+    /// so its type has a single lambda set, which is the one of the function itself.
+    SingleLambdaSetImmediate(Symbol),
     /// Key of the derived implementation to use. This allows association of derived implementation
     /// names to a key, when the key is known ahead-of-time but the implementation (and it's name)
     /// is yet-to-be-made.
@@ -63,6 +79,8 @@ pub enum Derived {
 pub enum DeriveBuiltin {
     ToEncoder,
     Decoder,
+    Hash,
+    Arbitrary,
 }
 
 impl TryFrom<Symbol> for DeriveBuiltin {
@@ -72,6 +90,8 @@ impl TryFrom<Symbol> for DeriveBuiltin {
         match value {
             Symbol::ENCODE_TO_ENCODER => Ok(DeriveBuiltin::ToEncoder),
             Symbol::DECODE_DECODER => Ok(DeriveBuiltin::Decoder),
+            Symbol::HASH_HASH => Ok(DeriveBuiltin::Hash),
+            Symbol::ARBITRARY_ARBITRARY => Ok(DeriveBuiltin::Arbitrary),
             _ => Err(value),
         }
     }
@@ -92,6 +112,17 @@ impl Derived {
                 FlatDecodable::Immediate(imm) => Ok(Derived::Immediate(imm)),
                 FlatDecodable::Key(repr) => Ok(Derived::Key(DeriveKey::Decoder(repr))),
             },
+            DeriveBuiltin::Hash => match hash::FlatHash::from_var(subs, var)? {
+                FlatHash::Immediate(imm) => Ok(Derived::Immediate(imm)),
+                FlatHash::SingleLambdaSetImmediate(imm) => {
+                    Ok(Derived::SingleLambdaSetImmediate(imm))
+                }
+                FlatHash::Key(repr) => Ok(Derived::Key(DeriveKey::Hash(repr))),
+            },
+            DeriveBuiltin::Arbitrary => {
+                let repr = FlatArbitraryKey::from_var(subs, var)?;
+                Ok(Derived::Key(DeriveKey::Arbitrary(repr)))
+            }
         }
     }
 }
@@ -7,6 +7,7 @@ pub mod def;
 pub mod expr;
 pub mod module;
 pub mod pattern;
+pub mod range;
 pub mod spaces;
 
 use bumpalo::{collections::String, Bump};
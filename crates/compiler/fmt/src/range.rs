@@ -0,0 +1,198 @@
+//! Formatting part of a module, e.g. the selection in an editor, or the def the cursor is in.
+//!
+//! Only whole top-level defs are formatted. The comments and blank lines between them are not part
+//! of any def's region, so they are left exactly as they were.
+
+use crate::annotation::Formattable;
+use crate::spaces::RemoveSpaces;
+use crate::{Ast, Buf};
+use bumpalo::collections::Vec;
+use bumpalo::Bump;
+use roc_parse::ast::{Defs, TypeDef, ValueDef};
+use roc_parse::module::{self, module_defs};
+use roc_parse::parser::{Parser, SyntaxError};
+use roc_parse::state::State;
+use roc_region::all::{Position, Region};
+
+/// Replace the source code in `region` with `new_text`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextEdit<'a> {
+    pub region: Region,
+    pub new_text: &'a str,
+}
+
+#[derive(Debug)]
+pub enum FormatProblem<'a> {
+    /// The module has a syntax error.
+    ParsingFailed(SyntaxError<'a>),
+    /// A formatter bug: the module does not parse anymore after the edits.
+    InvalidResult {
+        edited: &'a str,
+        problem: SyntaxError<'a>,
+    },
+    /// A formatter bug: the module parses to a different tree after the edits.
+    ChangedAst { edited: &'a str },
+    /// A formatter bug: formatting the edited defs again changes them again.
+    Unstable { edited: &'a str },
+}
+
+/// The edits that format the top-level defs overlapping `range`, ordered by their position in
+/// `src`. An empty range formats the def it is in, or touches. Defs that are already formatted get
+/// no edit, so this returns no edits at all when there is nothing to do.
+pub fn format_range<'a>(
+    arena: &'a Bump,
+    src: &'a str,
+    range: Region,
+) -> Result<Vec<'a, TextEdit<'a>>, FormatProblem<'a>> {
+    let ast = parse_all(arena, src).map_err(FormatProblem::ParsingFailed)?;
+
+    let selected: std::vec::Vec<usize> = (0..ast.defs.len())
+        .filter(|index| overlaps(def_region(&ast.defs, *index), range))
+        .collect();
+
+    let mut edits = Vec::new_in(arena);
+
+    for index in selected.iter().copied() {
+        let region = extend_over_trailing_spaces(src, def_region(&ast.defs, index));
+        let new_text = format_def(arena, &ast.defs, index);
+
+        if new_text != slice(src, region) {
+            edits.push(TextEdit { region, new_text });
+        }
+    }
+
+    if edits.is_empty() {
+        return Ok(edits);
+    }
+
+    // The same checks as `roc format` does on whole files: the edited module must parse to the
+    // same tree, and formatting the edited defs again must not change them.
+    let edited = arena.alloc_str(&apply_edits(src, &edits));
+
+    let reparsed_ast = match parse_all(arena, edited) {
+        Ok(reparsed_ast) => reparsed_ast,
+        Err(problem) => return Err(FormatProblem::InvalidResult { edited, problem }),
+    };
+
+    // We compare the debug format strings of the ASTs, because the PartialEq implementation of
+    // some ast types returns `false` even when the Debug-formatted impl is exactly the same.
+    let ast_normalized = without_comment_regions(ast.remove_spaces(arena));
+    let reparsed_ast_normalized = without_comment_regions(reparsed_ast.remove_spaces(arena));
+
+    if format!("{:?}", ast_normalized) != format!("{:?}", reparsed_ast_normalized) {
+        return Err(FormatProblem::ChangedAst { edited });
+    }
+
+    for index in selected {
+        let region = extend_over_trailing_spaces(edited, def_region(&reparsed_ast.defs, index));
+
+        if format_def(arena, &reparsed_ast.defs, index) != slice(edited, region) {
+            return Err(FormatProblem::Unstable { edited });
+        }
+    }
+
+    Ok(edits)
+}
+
+/// The source code with the edits made. The edits must be ordered, and not overlap.
+pub fn apply_edits(src: &str, edits: &[TextEdit]) -> String {
+    let mut result = String::with_capacity(src.len());
+    let mut offset = 0;
+
+    for edit in edits {
+        let start = edit.region.start().offset as usize;
+
+        debug_assert!(offset <= start, "edits overlap, or are out of order");
+
+        result.push_str(&src[offset..start]);
+        result.push_str(edit.new_text);
+
+        offset = edit.region.end().offset as usize;
+    }
+
+    result.push_str(&src[offset..]);
+
+    result
+}
+
+fn parse_all<'a>(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, SyntaxError<'a>> {
+    let (module, state) = module::parse_header(arena, State::new(src.as_bytes()))
+        .map_err(|e| SyntaxError::Header(e.problem))?;
+
+    let (_, defs, _) = module_defs().parse(arena, state).map_err(|(_, e, _)| e)?;
+
+    Ok(Ast { module, defs })
+}
+
+fn format_def<'a>(arena: &'a Bump, defs: &Defs<'a>, index: usize) -> &'a str {
+    let mut buf = Buf::new_in(arena);
+
+    // Top-level defs are never indented.
+    match defs.defs().nth(index) {
+        Some(Ok(type_def)) => type_def.format(&mut buf, 0),
+        Some(Err(value_def)) => value_def.format(&mut buf, 0),
+        None => unreachable!("def index {} is out of bounds", index),
+    }
+
+    // An expect makes sure it starts on a line of its own, which a top-level def always does.
+    let text = buf.into_bump_str();
+
+    text.strip_prefix('\n').unwrap_or(text)
+}
+
+/// The region of the comment before an expect is not a `Loc`, so `remove_spaces` keeps it, but it
+/// moves whenever a def before the expect is formatted.
+fn without_comment_regions(mut ast: Ast) -> Ast {
+    for value_def in ast.defs.value_defs.iter_mut() {
+        match value_def {
+            ValueDef::Expect {
+                preceding_comment, ..
+            }
+            | ValueDef::ExpectFx {
+                preceding_comment, ..
+            } => *preceding_comment = Region::zero(),
+            _ => {}
+        }
+    }
+
+    ast
+}
+
+/// The parser leaves the `has` clause of an opaque type out of the def's region.
+fn def_region(defs: &Defs, index: usize) -> Region {
+    let region = defs.regions[index];
+
+    match defs.defs().nth(index) {
+        Some(Ok(TypeDef::Opaque {
+            derived: Some(derived),
+            ..
+        })) => Region::span_across(&region, &derived.region),
+        _ => region,
+    }
+}
+
+fn overlaps(def_region: Region, range: Region) -> bool {
+    if range.is_empty() {
+        def_region.start() <= range.start() && range.start() <= def_region.end()
+    } else {
+        def_region.start() < range.end() && range.start() < def_region.end()
+    }
+}
+
+/// Trailing spaces after a def are removed, unless a comment follows them on the same line.
+fn extend_over_trailing_spaces(src: &str, region: Region) -> Region {
+    let end = region.end().offset as usize;
+    let rest = &src[end..];
+    let spaces = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+
+    match rest[spaces..].chars().next() {
+        None | Some('\n') | Some('\r') => {
+            Region::new(region.start(), Position::new((end + spaces) as u32))
+        }
+        Some(_) => region,
+    }
+}
+
+fn slice(src: &str, region: Region) -> &str {
+    &src[region.start().offset as usize..region.end().offset as usize]
+}
@@ -9,11 +9,13 @@ mod test_fmt {
     use roc_fmt::annotation::{Formattable, Newlines, Parens};
     use roc_fmt::def::fmt_defs;
     use roc_fmt::module::fmt_module;
+    use roc_fmt::range::{apply_edits, FormatProblem};
     use roc_fmt::Buf;
     use roc_parse::ast::Module;
     use roc_parse::module::{self, module_defs};
     use roc_parse::parser::Parser;
     use roc_parse::state::State;
+    use roc_region::all::{Position, Region};
     use roc_test_utils::{assert_multiline_str_eq, workspace_root};
 
     // Not intended to be used directly in tests; please use expr_formats_to or expr_formats_same
@@ -5457,6 +5459,196 @@ mod test_fmt {
         ));
     }
 
+    // RANGE FORMATTING
+
+    /// Formats the defs overlapping `range`, checks that doing that again changes nothing,
+    /// and returns the result.
+    fn format_range(input: &str, range: Region) -> String {
+        let arena = Bump::new();
+
+        let edits = match roc_fmt::range::format_range(&arena, input, range) {
+            Ok(edits) => edits,
+            Err(problem) => panic!("Unexpected problem formatting a range: {:?}", problem),
+        };
+        let output = apply_edits(input, &edits);
+
+        match roc_fmt::range::format_range(&arena, &output, range) {
+            Ok(edits) => assert_eq!(edits.as_slice(), &[]),
+            Err(problem) => panic!("Unexpected problem formatting a range: {:?}", problem),
+        }
+
+        output
+    }
+
+    /// The region of `selected`, which must occur exactly once in `input`.
+    fn selection(input: &str, selected: &str) -> Region {
+        assert_eq!(input.matches(selected).count(), 1);
+
+        let start = input.find(selected).unwrap();
+
+        Region::new(
+            Position::new(start as u32),
+            Position::new((start + selected.len()) as u32),
+        )
+    }
+
+    #[test]
+    fn format_range_only_formats_selected_def() {
+        let input = indoc!(
+            r#"
+            interface Foo exposes [] imports []
+
+            # the first def
+            a =   1
+
+
+
+            # the second def
+            b = \x ->   x  +  1 # trailing comment
+            ## the third def
+            c : Str
+            c =
+                      "c"
+            "#
+        );
+
+        let output = format_range(input, selection(input, "x  +  1"));
+
+        assert_multiline_str_eq!(
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                # the first def
+                a =   1
+
+
+
+                # the second def
+                b = \x -> x + 1 # trailing comment
+                ## the third def
+                c : Str
+                c =
+                          "c"
+                "#
+            ),
+            output.as_str()
+        );
+    }
+
+    #[test]
+    fn format_range_across_defs() {
+        let input = indoc!(
+            r#"
+            interface Foo exposes [] imports []
+
+            a =   1
+
+            b =   2
+
+            c =   3
+            "#
+        );
+
+        let output = format_range(input, selection(input, "1\n\nb"));
+
+        assert_multiline_str_eq!(
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                a = 1
+
+                b = 2
+
+                c =   3
+                "#
+            ),
+            output.as_str()
+        );
+    }
+
+    #[test]
+    fn format_range_at_cursor() {
+        let input = indoc!(
+            r#"
+            interface Foo exposes [] imports []
+
+            Age := U32   has [Eq]
+
+            # is it old?
+            expect   isOld 5   ==   Bool.false
+            "#
+        );
+
+        // A cursor at the end of a def formats it, like after typing
+        let cursor = selection(input, "Bool.false").end();
+
+        let output = format_range(input, Region::new(cursor, cursor));
+
+        assert_multiline_str_eq!(
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                Age := U32   has [Eq]
+
+                # is it old?
+                expect isOld 5 == Bool.false
+                "#
+            ),
+            output.as_str()
+        );
+
+        let cursor = selection(input, "Age").start();
+
+        let output = format_range(input, Region::new(cursor, cursor));
+
+        assert_multiline_str_eq!(
+            indoc!(
+                r#"
+                interface Foo exposes [] imports []
+
+                Age := U32 has [Eq]
+
+                # is it old?
+                expect   isOld 5   ==   Bool.false
+                "#
+            ),
+            output.as_str()
+        );
+    }
+
+    #[test]
+    fn format_range_outside_defs() {
+        let input = indoc!(
+            r#"
+            interface Foo exposes [] imports []
+
+            a =   1
+
+            # b is next
+            b =   2
+            "#
+        );
+
+        let output = format_range(input, selection(input, "# b is"));
+
+        assert_multiline_str_eq!(input, output.as_str());
+    }
+
+    #[test]
+    fn format_range_parse_error() {
+        let arena = Bump::new();
+        let input = "interface Foo exposes [] imports []\n\na =\n";
+        let range = Region::new(Position::new(0), Position::new(input.len() as u32));
+
+        assert!(matches!(
+            roc_fmt::range::format_range(&arena, input, range),
+            Err(FormatProblem::ParsingFailed(_))
+        ));
+    }
+
     // this is a parse error atm
     //    #[test]
     //    fn multiline_apply() {
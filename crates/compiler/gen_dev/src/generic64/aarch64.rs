@@ -1,10 +1,17 @@
 use crate::generic64::{storage::StorageManager, Assembler, CallConv, RegTrait};
-use crate::Relocation;
+use crate::{
+    sign_extended_int_builtins, single_register_floats, single_register_int_builtins,
+    single_register_integers, single_register_layouts, Relocation,
+};
 use bumpalo::collections::Vec;
 use packed_struct::prelude::*;
+use roc_builtins::bitcode::{FloatWidth, IntWidth};
 use roc_error_macros::internal_error;
 use roc_module::symbol::Symbol;
-use roc_mono::layout::Layout;
+use roc_mono::layout::{Builtin, Layout, UnionLayout};
+use roc_target::TargetInfo;
+
+const TARGET_INFO: TargetInfo = TargetInfo::default_aarch64();
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 #[allow(dead_code)]
@@ -101,7 +108,40 @@ impl AArch64GeneralReg {
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 #[allow(dead_code)]
-pub enum AArch64FloatReg {}
+pub enum AArch64FloatReg {
+    V0 = 0,
+    V1 = 1,
+    V2 = 2,
+    V3 = 3,
+    V4 = 4,
+    V5 = 5,
+    V6 = 6,
+    V7 = 7,
+    V8 = 8,
+    V9 = 9,
+    V10 = 10,
+    V11 = 11,
+    V12 = 12,
+    V13 = 13,
+    V14 = 14,
+    V15 = 15,
+    V16 = 16,
+    V17 = 17,
+    V18 = 18,
+    V19 = 19,
+    V20 = 20,
+    V21 = 21,
+    V22 = 22,
+    V23 = 23,
+    V24 = 24,
+    V25 = 25,
+    V26 = 26,
+    V27 = 27,
+    V28 = 28,
+    V29 = 29,
+    V30 = 30,
+    V31 = 31,
+}
 impl RegTrait for AArch64FloatReg {
     fn value(&self) -> u8 {
         *self as u8
@@ -109,21 +149,40 @@ impl RegTrait for AArch64FloatReg {
 }
 impl std::fmt::Display for AArch64FloatReg {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "TODO",)
+        write!(f, "v{}", self.id())
+    }
+}
+
+impl AArch64FloatReg {
+    #[inline(always)]
+    fn id(&self) -> u8 {
+        *self as u8
     }
 }
 
 #[derive(Copy, Clone)]
 pub struct AArch64Assembler {}
 
-// AArch64Call may need to eventually be split by OS,
-// but I think with how we use it, they may all be the same.
+// AArch64Call follows AAPCS64 and AArch64AppleCall follows Apple's variant of it.
+// The only difference that matters to us: Apple packs arguments passed on the stack
+// to their natural size and alignment, while AAPCS64 gives every argument at least an 8 byte slot.
 #[derive(Copy, Clone)]
-pub struct AArch64Call {}
+pub struct AArch64CallConv<const APPLE_STACK_ARGS: bool> {}
+
+pub type AArch64Call = AArch64CallConv<false>;
+pub type AArch64AppleCall = AArch64CallConv<true>;
 
 const STACK_ALIGNMENT: u8 = 16;
 
-impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64Call {
+// IP0 and IP1 are never handed out by the storage manager.
+// The assembler uses IP0 to build offsets and immediates that do not fit in an instruction.
+// IP1 is used as a buffer when moving data around for function calls.
+const TMP_OFFSET_REG: AArch64GeneralReg = AArch64GeneralReg::IP0;
+const TMP_REG: AArch64GeneralReg = AArch64GeneralReg::IP1;
+
+impl<const APPLE_STACK_ARGS: bool> CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler>
+    for AArch64CallConv<APPLE_STACK_ARGS>
+{
     const BASE_PTR_REG: AArch64GeneralReg = AArch64GeneralReg::FP;
     const STACK_PTR_REG: AArch64GeneralReg = AArch64GeneralReg::ZRSP;
 
@@ -147,6 +206,7 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
         // Don't use platform register: AArch64GeneralReg::PR,
         // Don't use link register: AArch64GeneralReg::LR,
         // Don't use zero register/stack pointer: AArch64GeneralReg::ZRSP,
+        // Don't use the assembler scratch registers: AArch64GeneralReg::IP0, AArch64GeneralReg::IP1,
 
         // Use callee saved regs last.
         AArch64GeneralReg::X19,
@@ -175,12 +235,57 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
         AArch64GeneralReg::X13,
         AArch64GeneralReg::X14,
         AArch64GeneralReg::X15,
-        AArch64GeneralReg::IP0,
-        AArch64GeneralReg::IP1,
     ];
-    const FLOAT_PARAM_REGS: &'static [AArch64FloatReg] = &[];
+    const FLOAT_PARAM_REGS: &'static [AArch64FloatReg] = &[
+        AArch64FloatReg::V0,
+        AArch64FloatReg::V1,
+        AArch64FloatReg::V2,
+        AArch64FloatReg::V3,
+        AArch64FloatReg::V4,
+        AArch64FloatReg::V5,
+        AArch64FloatReg::V6,
+        AArch64FloatReg::V7,
+    ];
     const FLOAT_RETURN_REGS: &'static [AArch64FloatReg] = Self::FLOAT_PARAM_REGS;
-    const FLOAT_DEFAULT_FREE_REGS: &'static [AArch64FloatReg] = &[];
+    const FLOAT_DEFAULT_FREE_REGS: &'static [AArch64FloatReg] = &[
+        // The regs we want to use first should be at the end of this vec.
+        // We will use pop to get which reg to use next
+
+        // Use callee saved regs last.
+        AArch64FloatReg::V8,
+        AArch64FloatReg::V9,
+        AArch64FloatReg::V10,
+        AArch64FloatReg::V11,
+        AArch64FloatReg::V12,
+        AArch64FloatReg::V13,
+        AArch64FloatReg::V14,
+        AArch64FloatReg::V15,
+        // Use caller saved regs first.
+        AArch64FloatReg::V0,
+        AArch64FloatReg::V1,
+        AArch64FloatReg::V2,
+        AArch64FloatReg::V3,
+        AArch64FloatReg::V4,
+        AArch64FloatReg::V5,
+        AArch64FloatReg::V6,
+        AArch64FloatReg::V7,
+        AArch64FloatReg::V16,
+        AArch64FloatReg::V17,
+        AArch64FloatReg::V18,
+        AArch64FloatReg::V19,
+        AArch64FloatReg::V20,
+        AArch64FloatReg::V21,
+        AArch64FloatReg::V22,
+        AArch64FloatReg::V23,
+        AArch64FloatReg::V24,
+        AArch64FloatReg::V25,
+        AArch64FloatReg::V26,
+        AArch64FloatReg::V27,
+        AArch64FloatReg::V28,
+        AArch64FloatReg::V29,
+        AArch64FloatReg::V30,
+        AArch64FloatReg::V31,
+    ];
 
     const SHADOW_SPACE_SIZE: u8 = 0;
 
@@ -201,8 +306,20 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
         )
     }
     #[inline(always)]
-    fn float_callee_saved(_reg: &AArch64FloatReg) -> bool {
-        todo!("AArch64 FloatRegs");
+    fn float_callee_saved(reg: &AArch64FloatReg) -> bool {
+        // Only the bottom 64 bits of these registers must be preserved.
+        // That is fine because we never use more than 64 bits of a float register.
+        matches!(
+            reg,
+            AArch64FloatReg::V8
+                | AArch64FloatReg::V9
+                | AArch64FloatReg::V10
+                | AArch64FloatReg::V11
+                | AArch64FloatReg::V12
+                | AArch64FloatReg::V13
+                | AArch64FloatReg::V14
+                | AArch64FloatReg::V15
+        )
     }
 
     #[inline(always)]
@@ -213,9 +330,21 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
         requested_stack_size: i32,
         fn_call_stack_size: i32,
     ) -> i32 {
+        // Push the frame record and point the frame pointer at it.
+        // This is the equivalent of `push rbp; mov rbp, rsp` on x86_64,
+        // so args passed on the stack start 16 bytes above the frame pointer.
+        stp_reg64_reg64_reg64_pre_imm7(
+            buf,
+            AArch64GeneralReg::FP,
+            AArch64GeneralReg::LR,
+            AArch64GeneralReg::ZRSP,
+            -2,
+        );
+        AArch64Assembler::mov_reg64_reg64(buf, AArch64GeneralReg::FP, AArch64GeneralReg::ZRSP);
+
         // Full size is upcast to i64 to make sure we don't overflow here.
         let full_stack_size = match requested_stack_size
-            .checked_add(8 * (saved_general_regs.len() + saved_float_regs.len()) as i32 + 8) // The extra 8 is space to store the frame pointer.
+            .checked_add(8 * (saved_general_regs.len() + saved_float_regs.len()) as i32)
             .and_then(|size| size.checked_add(fn_call_stack_size))
         {
             Some(size) => size,
@@ -233,11 +362,6 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
         };
         if let Some(aligned_stack_size) = full_stack_size.checked_add(offset as i32) {
             if aligned_stack_size > 0 {
-                AArch64Assembler::mov_reg64_reg64(
-                    buf,
-                    AArch64GeneralReg::FP,
-                    AArch64GeneralReg::ZRSP,
-                );
                 AArch64Assembler::sub_reg64_reg64_imm32(
                     buf,
                     AArch64GeneralReg::ZRSP,
@@ -245,21 +369,15 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
                     aligned_stack_size,
                 );
 
-                // All the following stores could be optimized by using `STP` to store pairs.
-                let mut offset = aligned_stack_size;
-                offset -= 8;
-                AArch64Assembler::mov_stack32_reg64(buf, offset, AArch64GeneralReg::LR);
-                offset -= 8;
-                AArch64Assembler::mov_stack32_reg64(buf, offset, AArch64GeneralReg::FP);
-
-                offset = aligned_stack_size - fn_call_stack_size;
+                // Put values at the top of the stack to avoid conflicts with previously saved variables.
+                let mut offset = aligned_stack_size - fn_call_stack_size;
                 for reg in saved_general_regs {
+                    AArch64Assembler::mov_base32_reg64(buf, -offset, *reg);
                     offset -= 8;
-                    AArch64Assembler::mov_base32_reg64(buf, offset, *reg);
                 }
                 for reg in saved_float_regs {
+                    AArch64Assembler::mov_base32_freg64(buf, -offset, *reg);
                     offset -= 8;
-                    AArch64Assembler::mov_base32_freg64(buf, offset, *reg);
                 }
                 aligned_stack_size
             } else {
@@ -279,21 +397,14 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
         fn_call_stack_size: i32,
     ) {
         if aligned_stack_size > 0 {
-            // All the following stores could be optimized by using `STP` to store pairs.
-            let mut offset = aligned_stack_size;
-            offset -= 8;
-            AArch64Assembler::mov_reg64_stack32(buf, AArch64GeneralReg::LR, offset);
-            offset -= 8;
-            AArch64Assembler::mov_reg64_stack32(buf, AArch64GeneralReg::FP, offset);
-
-            offset = aligned_stack_size - fn_call_stack_size;
+            let mut offset = aligned_stack_size - fn_call_stack_size;
             for reg in saved_general_regs {
+                AArch64Assembler::mov_reg64_base32(buf, *reg, -offset);
                 offset -= 8;
-                AArch64Assembler::mov_reg64_base32(buf, *reg, offset);
             }
             for reg in saved_float_regs {
+                AArch64Assembler::mov_freg64_base32(buf, *reg, -offset);
                 offset -= 8;
-                AArch64Assembler::mov_freg64_base32(buf, *reg, offset);
             }
             AArch64Assembler::add_reg64_reg64_imm32(
                 buf,
@@ -302,87 +413,529 @@ impl CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler> for AArch64C
                 aligned_stack_size,
             );
         }
+        ldp_reg64_reg64_reg64_post_imm7(
+            buf,
+            AArch64GeneralReg::FP,
+            AArch64GeneralReg::LR,
+            AArch64GeneralReg::ZRSP,
+            2,
+        );
     }
 
     #[inline(always)]
     fn load_args<'a>(
-        _buf: &mut Vec<'a, u8>,
-        _storage_manager: &mut StorageManager<
+        buf: &mut Vec<'a, u8>,
+        storage_manager: &mut StorageManager<
             'a,
             AArch64GeneralReg,
             AArch64FloatReg,
             AArch64Assembler,
-            AArch64Call,
+            Self,
         >,
-        _args: &'a [(Layout<'a>, Symbol)],
-        _ret_layout: &Layout<'a>,
+        args: &'a [(Layout<'a>, Symbol)],
+        ret_layout: &Layout<'a>,
     ) {
-        todo!("Loading args for AArch64");
+        let mut arg_offset = Self::SHADOW_SPACE_SIZE as i32 + 16; // 16 is the size of the pushed frame record.
+        let mut general_i = 0;
+        let mut float_i = 0;
+        if Self::returns_via_arg_pointer(ret_layout) {
+            // Unlike x86_64, the result pointer has a dedicated register instead of taking the first param reg.
+            storage_manager.ret_pointer_arg(AArch64GeneralReg::XR);
+        }
+        for (layout, sym) in args.iter() {
+            let stack_size = layout.stack_size(TARGET_INFO);
+            match layout {
+                single_register_integers!() => {
+                    if general_i < Self::GENERAL_PARAM_REGS.len() {
+                        storage_manager.general_reg_arg(sym, Self::GENERAL_PARAM_REGS[general_i]);
+                        general_i += 1;
+                    } else {
+                        arg_offset = Self::load_primitive_stack_arg(
+                            buf,
+                            storage_manager,
+                            sym,
+                            layout,
+                            arg_offset,
+                        );
+                    }
+                }
+                single_register_floats!() => {
+                    if float_i < Self::FLOAT_PARAM_REGS.len() {
+                        storage_manager.float_reg_arg(sym, Self::FLOAT_PARAM_REGS[float_i]);
+                        float_i += 1;
+                    } else {
+                        arg_offset = Self::load_primitive_stack_arg(
+                            buf,
+                            storage_manager,
+                            sym,
+                            layout,
+                            arg_offset,
+                        );
+                    }
+                }
+                _ if stack_size == 0 => {
+                    storage_manager.no_data_arg(sym);
+                }
+                x => match Self::composite_class(x) {
+                    CompositeClass::FloatRegs(width, count)
+                        if float_i + count <= Self::FLOAT_PARAM_REGS.len() =>
+                    {
+                        let base_offset = storage_manager.claim_stack_area(sym, stack_size);
+                        let regs = &Self::FLOAT_PARAM_REGS[float_i..float_i + count];
+                        AArch64Assembler::store_float_regs(buf, width, base_offset, regs);
+                        float_i += count;
+                    }
+                    CompositeClass::GeneralRegs(count)
+                        if Self::first_general_reg(general_i, x) + count
+                            <= Self::GENERAL_PARAM_REGS.len() =>
+                    {
+                        general_i = Self::first_general_reg(general_i, x);
+                        let base_offset = storage_manager.claim_stack_area(sym, stack_size);
+                        let regs = &Self::GENERAL_PARAM_REGS[general_i..general_i + count];
+                        for (i, reg) in regs.iter().enumerate() {
+                            AArch64Assembler::mov_base32_reg64(
+                                buf,
+                                base_offset + 8 * i as i32,
+                                *reg,
+                            );
+                        }
+                        general_i += count;
+                    }
+                    CompositeClass::FloatRegs(..) => {
+                        // Once a float aggregate goes on the stack, no more floats go in registers.
+                        float_i = Self::FLOAT_PARAM_REGS.len();
+                        let (offset, next_offset) = Self::stack_arg_slot(arg_offset, x);
+                        storage_manager.complex_stack_arg(sym, offset, stack_size);
+                        arg_offset = next_offset;
+                    }
+                    CompositeClass::GeneralRegs(_) => {
+                        // Once a composite goes on the stack, no more integers go in registers.
+                        general_i = Self::GENERAL_PARAM_REGS.len();
+                        let (offset, next_offset) = Self::stack_arg_slot(arg_offset, x);
+                        storage_manager.complex_stack_arg(sym, offset, stack_size);
+                        arg_offset = next_offset;
+                    }
+                    CompositeClass::Indirect => {
+                        // The caller passed a pointer to the value, copy it into our own stack frame.
+                        let base_offset = storage_manager.claim_stack_area(sym, stack_size);
+                        if general_i < Self::GENERAL_PARAM_REGS.len() {
+                            let ptr_reg = Self::GENERAL_PARAM_REGS[general_i];
+                            general_i += 1;
+                            for i in (0..stack_size as i32).step_by(8) {
+                                AArch64Assembler::mov_reg64_mem64_offset32(
+                                    buf, TMP_REG, ptr_reg, i,
+                                );
+                                AArch64Assembler::mov_base32_reg64(buf, base_offset + i, TMP_REG);
+                            }
+                        } else {
+                            let (offset, next_offset) = Self::stack_arg_slot(arg_offset, x);
+                            for i in (0..stack_size as i32).step_by(8) {
+                                AArch64Assembler::mov_reg64_base32(buf, TMP_REG, offset);
+                                AArch64Assembler::mov_reg64_mem64_offset32(
+                                    buf, TMP_REG, TMP_REG, i,
+                                );
+                                AArch64Assembler::mov_base32_reg64(buf, base_offset + i, TMP_REG);
+                            }
+                            arg_offset = next_offset;
+                        }
+                    }
+                },
+            }
+        }
     }
 
     #[inline(always)]
     fn store_args<'a>(
-        _buf: &mut Vec<'a, u8>,
-        _storage_manager: &mut StorageManager<
+        buf: &mut Vec<'a, u8>,
+        storage_manager: &mut StorageManager<
             'a,
             AArch64GeneralReg,
             AArch64FloatReg,
             AArch64Assembler,
-            AArch64Call,
+            Self,
         >,
-        _dst: &Symbol,
-        _args: &[Symbol],
-        _arg_layouts: &[Layout<'a>],
-        _ret_layout: &Layout<'a>,
+        dst: &Symbol,
+        args: &[Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
     ) {
-        todo!("Storing args for AArch64");
+        let mut tmp_stack_offset = Self::SHADOW_SPACE_SIZE as i32;
+        let mut general_i = 0;
+        let mut float_i = 0;
+        if Self::returns_via_arg_pointer(ret_layout) {
+            // Save space on the stack for the result we will be return.
+            let base_offset =
+                storage_manager.claim_stack_area(dst, ret_layout.stack_size(TARGET_INFO));
+            // Set the result location reg to the address base + offset.
+            AArch64Assembler::add_reg64_reg64_imm32(
+                buf,
+                AArch64GeneralReg::XR,
+                AArch64GeneralReg::FP,
+                base_offset,
+            );
+        }
+        for (sym, layout) in args.iter().zip(arg_layouts.iter()) {
+            match layout {
+                single_register_integers!() => {
+                    if general_i < Self::GENERAL_PARAM_REGS.len() {
+                        storage_manager.load_to_specified_general_reg(
+                            buf,
+                            sym,
+                            Self::GENERAL_PARAM_REGS[general_i],
+                        );
+                        general_i += 1;
+                    } else {
+                        // Copy to stack using the scratch reg as buffer.
+                        storage_manager.load_to_specified_general_reg(buf, sym, TMP_REG);
+                        tmp_stack_offset = Self::store_primitive_stack_arg(
+                            buf,
+                            layout,
+                            tmp_stack_offset,
+                            TMP_REG.id(),
+                        );
+                    }
+                }
+                single_register_floats!() => {
+                    if float_i < Self::FLOAT_PARAM_REGS.len() {
+                        storage_manager.load_to_specified_float_reg(
+                            buf,
+                            sym,
+                            Self::FLOAT_PARAM_REGS[float_i],
+                        );
+                        float_i += 1;
+                    } else {
+                        // Copy to stack using a caller saved reg that is not a param reg as buffer.
+                        // All caller saved regs were freed before calling, so it is empty right now.
+                        storage_manager.load_to_specified_float_reg(buf, sym, AArch64FloatReg::V16);
+                        tmp_stack_offset = Self::store_primitive_stack_arg(
+                            buf,
+                            layout,
+                            tmp_stack_offset,
+                            AArch64FloatReg::V16.id(),
+                        );
+                    }
+                }
+                x if x.stack_size(TARGET_INFO) == 0 => {}
+                x => {
+                    let (base_offset, size) = storage_manager.stack_offset_and_size(sym);
+                    debug_assert_eq!(base_offset % 8, 0);
+                    let class = Self::composite_class(x);
+                    match class {
+                        CompositeClass::FloatRegs(width, count)
+                            if float_i + count <= Self::FLOAT_PARAM_REGS.len() =>
+                        {
+                            let regs = &Self::FLOAT_PARAM_REGS[float_i..float_i + count];
+                            AArch64Assembler::load_float_regs(buf, width, base_offset, regs);
+                            float_i += count;
+                        }
+                        CompositeClass::GeneralRegs(count)
+                            if Self::first_general_reg(general_i, x) + count
+                                <= Self::GENERAL_PARAM_REGS.len() =>
+                        {
+                            general_i = Self::first_general_reg(general_i, x);
+                            let regs = &Self::GENERAL_PARAM_REGS[general_i..general_i + count];
+                            for (i, reg) in regs.iter().enumerate() {
+                                AArch64Assembler::mov_reg64_base32(
+                                    buf,
+                                    *reg,
+                                    base_offset + 8 * i as i32,
+                                );
+                            }
+                            general_i += count;
+                        }
+                        CompositeClass::FloatRegs(..) | CompositeClass::GeneralRegs(_) => {
+                            // Once a composite goes on the stack, no more of its kind go in registers.
+                            if let CompositeClass::FloatRegs(..) = class {
+                                float_i = Self::FLOAT_PARAM_REGS.len();
+                            } else {
+                                general_i = Self::GENERAL_PARAM_REGS.len();
+                            }
+                            // Just copy onto the stack.
+                            let (offset, next_offset) = Self::stack_arg_slot(tmp_stack_offset, x);
+                            for i in (0..size as i32).step_by(8) {
+                                AArch64Assembler::mov_reg64_base32(buf, TMP_REG, base_offset + i);
+                                AArch64Assembler::mov_stack32_reg64(buf, offset + i, TMP_REG);
+                            }
+                            tmp_stack_offset = next_offset;
+                        }
+                        CompositeClass::Indirect => {
+                            // Pass a pointer to the value.
+                            // AAPCS64 allows the callee to modify that memory,
+                            // but neither Roc procs nor the zig builtins ever do, so we skip making a copy.
+                            if general_i < Self::GENERAL_PARAM_REGS.len() {
+                                AArch64Assembler::add_reg64_reg64_imm32(
+                                    buf,
+                                    Self::GENERAL_PARAM_REGS[general_i],
+                                    AArch64GeneralReg::FP,
+                                    base_offset,
+                                );
+                                general_i += 1;
+                            } else {
+                                AArch64Assembler::add_reg64_reg64_imm32(
+                                    buf,
+                                    TMP_REG,
+                                    AArch64GeneralReg::FP,
+                                    base_offset,
+                                );
+                                let (offset, next_offset) =
+                                    Self::stack_arg_slot(tmp_stack_offset, x);
+                                AArch64Assembler::mov_stack32_reg64(buf, offset, TMP_REG);
+                                tmp_stack_offset = next_offset;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        storage_manager.update_fn_call_stack_size(tmp_stack_offset as u32);
     }
 
     fn return_complex_symbol<'a>(
-        _buf: &mut Vec<'a, u8>,
-        _storage_manager: &mut StorageManager<
+        buf: &mut Vec<'a, u8>,
+        storage_manager: &mut StorageManager<
             'a,
             AArch64GeneralReg,
             AArch64FloatReg,
             AArch64Assembler,
-            AArch64Call,
+            Self,
         >,
-        _sym: &Symbol,
-        _layout: &Layout<'a>,
+        sym: &Symbol,
+        layout: &Layout<'a>,
     ) {
-        todo!("Returning complex symbols for AArch64");
+        match layout {
+            single_register_layouts!() => {
+                internal_error!("single register layouts are not complex symbols");
+            }
+            x if x.stack_size(TARGET_INFO) == 0 => {}
+            x => match Self::composite_class(x) {
+                CompositeClass::FloatRegs(width, count) => {
+                    let (base_offset, _) = storage_manager.stack_offset_and_size(sym);
+                    let regs = &Self::FLOAT_RETURN_REGS[..count];
+                    AArch64Assembler::load_float_regs(buf, width, base_offset, regs);
+                }
+                CompositeClass::GeneralRegs(count) => {
+                    let (base_offset, _) = storage_manager.stack_offset_and_size(sym);
+                    debug_assert_eq!(base_offset % 8, 0);
+                    for (i, reg) in Self::GENERAL_RETURN_REGS[..count].iter().enumerate() {
+                        AArch64Assembler::mov_reg64_base32(buf, *reg, base_offset + 8 * i as i32);
+                    }
+                }
+                CompositeClass::Indirect => {
+                    // This is a large type returned via the result location register.
+                    // Unlike x86_64, the address does not need to be returned.
+                    storage_manager.copy_symbol_to_arg_pointer(buf, sym, layout);
+                }
+            },
+        }
     }
 
     fn load_returned_complex_symbol<'a>(
-        _buf: &mut Vec<'a, u8>,
-        _storage_manager: &mut StorageManager<
+        buf: &mut Vec<'a, u8>,
+        storage_manager: &mut StorageManager<
             'a,
             AArch64GeneralReg,
             AArch64FloatReg,
             AArch64Assembler,
-            AArch64Call,
+            Self,
         >,
-        _sym: &Symbol,
-        _layout: &Layout<'a>,
+        sym: &Symbol,
+        layout: &Layout<'a>,
     ) {
-        todo!("Loading returned complex symbols for AArch64");
+        match layout {
+            single_register_layouts!() => {
+                internal_error!("single register layouts are not complex symbols");
+            }
+            x if x.stack_size(TARGET_INFO) == 0 => {}
+            x => match Self::composite_class(x) {
+                CompositeClass::FloatRegs(width, count) => {
+                    let offset = storage_manager.claim_stack_area(sym, x.stack_size(TARGET_INFO));
+                    let regs = &Self::FLOAT_RETURN_REGS[..count];
+                    AArch64Assembler::store_float_regs(buf, width, offset, regs);
+                }
+                CompositeClass::GeneralRegs(count) => {
+                    let offset = storage_manager.claim_stack_area(sym, x.stack_size(TARGET_INFO));
+                    for (i, reg) in Self::GENERAL_RETURN_REGS[..count].iter().enumerate() {
+                        AArch64Assembler::mov_base32_reg64(buf, offset + 8 * i as i32, *reg);
+                    }
+                }
+                CompositeClass::Indirect => {
+                    // This should have been recieved via the result location register.
+                    // That means the value is already loaded onto the stack area we allocated before the call.
+                    // Nothing to do.
+                }
+            },
+        }
     }
 }
 
+/// How AAPCS64 passes a value that does not fit in a single register.
+#[derive(Clone, Copy, Debug)]
+enum CompositeClass {
+    /// A homogeneous floating-point aggregate, passed in consecutive float registers.
+    FloatRegs(FloatWidth, usize),
+    /// A composite of at most 16 bytes, passed in consecutive general registers.
+    GeneralRegs(usize),
+    /// A larger composite, passed as a pointer to memory.
+    Indirect,
+}
+
+impl<const APPLE_STACK_ARGS: bool> AArch64CallConv<APPLE_STACK_ARGS> {
+    fn returns_via_arg_pointer(ret_layout: &Layout) -> bool {
+        // details here: https://github.com/ARM-software/abi-aa/blob/main/aapcs64/aapcs64.rst
+        !matches!(ret_layout, single_register_layouts!())
+            && ret_layout.stack_size(TARGET_INFO) > 0
+            && matches!(Self::composite_class(ret_layout), CompositeClass::Indirect)
+    }
+
+    fn composite_class(layout: &Layout) -> CompositeClass {
+        if let Some((width, count)) = Self::homogeneous_float_aggregate(layout) {
+            CompositeClass::FloatRegs(width, count)
+        } else {
+            let size = layout.stack_size(TARGET_INFO);
+            if size <= 16 {
+                CompositeClass::GeneralRegs(round_up_to_8(size) as usize / 8)
+            } else {
+                CompositeClass::Indirect
+            }
+        }
+    }
+
+    /// AAPCS64 rule C.9: a value with 16 byte alignment, like I128 or Dec,
+    /// starts at an even numbered general register.
+    fn first_general_reg(general_i: usize, layout: &Layout) -> usize {
+        if layout.alignment_bytes(TARGET_INFO) == 16 {
+            (general_i + 1) & !1
+        } else {
+            general_i
+        }
+    }
+
+    /// Returns the offset an argument passed on the stack goes to and the offset after its slot,
+    /// given the offset after the previous stack argument.
+    fn stack_arg_slot(arg_offset: i32, layout: &Layout) -> (i32, i32) {
+        let (size, alignment) = match layout {
+            single_register_layouts!() if APPLE_STACK_ARGS => {
+                let size = layout.stack_size(TARGET_INFO);
+                (size, size)
+            }
+            single_register_layouts!() => (8, 8),
+            x => match Self::composite_class(x) {
+                CompositeClass::Indirect => (8, 8),
+                _ => (
+                    round_up_to_8(x.stack_size(TARGET_INFO)),
+                    x.alignment_bytes(TARGET_INFO).max(8),
+                ),
+            },
+        };
+        let (size, alignment) = (size as i32, alignment as i32);
+        let offset = (arg_offset + alignment - 1) / alignment * alignment;
+        (offset, offset + size)
+    }
+
+    /// Loads a primitive the caller passed on the stack, and returns the offset after its slot.
+    fn load_primitive_stack_arg<'a>(
+        buf: &mut Vec<'a, u8>,
+        storage_manager: &mut StorageManager<
+            'a,
+            AArch64GeneralReg,
+            AArch64FloatReg,
+            AArch64Assembler,
+            Self,
+        >,
+        sym: &Symbol,
+        layout: &Layout<'a>,
+        arg_offset: i32,
+    ) -> i32 {
+        let (offset, next_offset) = Self::stack_arg_slot(arg_offset, layout);
+        let size = layout.stack_size(TARGET_INFO);
+        if size == 8 {
+            storage_manager.primitive_stack_arg(sym, offset);
+            return next_offset;
+        }
+        // Only the bytes of the value are defined, the rest of the slot may even belong to other arguments.
+        match layout {
+            single_register_floats!() => {
+                let reg = storage_manager.claim_float_reg(buf, sym);
+                AArch64Assembler::load_store(
+                    buf,
+                    LoadStoreOp::LoadF32,
+                    reg.id(),
+                    AArch64GeneralReg::FP,
+                    offset,
+                );
+            }
+            Layout::Builtin(sign_extended_int_builtins!()) => {
+                let reg = storage_manager.claim_general_reg(buf, sym);
+                AArch64Assembler::movsx_reg64_base32(buf, reg, offset, size as u8);
+            }
+            _ => {
+                let reg = storage_manager.claim_general_reg(buf, sym);
+                AArch64Assembler::movzx_reg64_base32(buf, reg, offset, size as u8);
+            }
+        }
+        next_offset
+    }
+
+    /// Stores a primitive in `reg` to the stack to pass it to a call, and returns the offset after its slot.
+    fn store_primitive_stack_arg(
+        buf: &mut Vec<'_, u8>,
+        layout: &Layout,
+        arg_offset: i32,
+        reg: u8,
+    ) -> i32 {
+        let (offset, next_offset) = Self::stack_arg_slot(arg_offset, layout);
+        let op = match (layout, next_offset - offset) {
+            (single_register_floats!(), 4) => LoadStoreOp::StoreF32,
+            (single_register_floats!(), _) => LoadStoreOp::StoreF64,
+            (_, 1) => LoadStoreOp::Store8,
+            (_, 2) => LoadStoreOp::Store16,
+            (_, 4) => LoadStoreOp::Store32,
+            _ => LoadStoreOp::Store64,
+        };
+        AArch64Assembler::load_store(buf, op, reg, AArch64GeneralReg::ZRSP, offset);
+        next_offset
+    }
+
+    /// Returns the float width and member count if the layout is a struct of one to four floats of the same width.
+    fn homogeneous_float_aggregate(layout: &Layout) -> Option<(FloatWidth, usize)> {
+        match layout {
+            Layout::Struct { field_layouts, .. } if (1..=4).contains(&field_layouts.len()) => {
+                let width = match field_layouts[0] {
+                    Layout::Builtin(Builtin::Float(
+                        width @ (FloatWidth::F32 | FloatWidth::F64),
+                    )) => width,
+                    _ => return None,
+                };
+                field_layouts
+                    .iter()
+                    .all(|field| *field == Layout::Builtin(Builtin::Float(width)))
+                    .then(|| (width, field_layouts.len()))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[inline(always)]
+fn round_up_to_8(size: u32) -> u32 {
+    (size + 7) & !7
+}
+
 impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     #[inline(always)]
-    fn abs_reg64_reg64(_buf: &mut Vec<'_, u8>, _dst: AArch64GeneralReg, _src: AArch64GeneralReg) {
-        todo!("abs_reg64_reg64 for AArch64");
+    fn abs_reg64_reg64(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, src: AArch64GeneralReg) {
+        cmp_reg64_imm12(buf, src, 0);
+        cneg_reg64_reg64_cond(buf, dst, src, ConditionCode::LT);
     }
 
     #[inline(always)]
     fn abs_freg64_freg64(
-        _buf: &mut Vec<'_, u8>,
+        buf: &mut Vec<'_, u8>,
         _relocs: &mut Vec<'_, Relocation>,
-        _dst: AArch64FloatReg,
-        _src: AArch64FloatReg,
+        dst: AArch64FloatReg,
+        src: AArch64FloatReg,
     ) {
-        todo!("abs_reg64_reg64 for AArch64");
+        fabs_freg_freg(buf, FloatWidth::F64, dst, src);
     }
 
     #[inline(always)]
@@ -392,13 +945,7 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         src: AArch64GeneralReg,
         imm32: i32,
     ) {
-        if imm32 < 0 {
-            todo!("immediate addition with values less than 0");
-        } else if imm32 < 0xFFF {
-            add_reg64_reg64_imm12(buf, dst, src, imm32 as u16);
-        } else {
-            todo!("immediate additions with values greater than 12bits");
-        }
+        Self::add_sub_reg64_reg64_imm32(buf, false, dst, src, imm32);
     }
     #[inline(always)]
     fn add_reg64_reg64_reg64(
@@ -411,79 +958,179 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     }
     #[inline(always)]
     fn add_freg32_freg32_freg32(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src1: AArch64FloatReg,
-        _src2: AArch64FloatReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src1: AArch64FloatReg,
+        src2: AArch64FloatReg,
     ) {
-        todo!("adding floats for AArch64");
+        fadd_freg_freg_freg(buf, FloatWidth::F32, dst, src1, src2);
     }
     #[inline(always)]
     fn add_freg64_freg64_freg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src1: AArch64FloatReg,
-        _src2: AArch64FloatReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src1: AArch64FloatReg,
+        src2: AArch64FloatReg,
+    ) {
+        fadd_freg_freg_freg(buf, FloatWidth::F64, dst, src1, src2);
+    }
+
+    #[inline(always)]
+    fn call(buf: &mut Vec<'_, u8>, relocs: &mut Vec<'_, Relocation>, fn_name: String) {
+        bl_imm26(buf, 0);
+        relocs.push(Relocation::LinkedFunction {
+            offset: buf.len() as u64 - 4,
+            name: fn_name,
+        });
+    }
+
+    #[inline(always)]
+    fn function_pointer(
+        buf: &mut Vec<'_, u8>,
+        relocs: &mut Vec<'_, Relocation>,
+        fn_name: String,
+        dst: AArch64GeneralReg,
+    ) {
+        // The linker fills in the page of the function and the offset within that page.
+        // The relocation points at the `ADRP`, the `ADD` is expected to directly follow it.
+        buf.reserve(8);
+        adrp_reg64_imm21(buf, dst, 0);
+        add_reg64_reg64_imm12(buf, dst, dst, 0);
+        relocs.push(Relocation::LinkedFunction {
+            offset: buf.len() as u64 - 8,
+            name: fn_name,
+        });
+    }
+
+    #[inline(always)]
+    fn and_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+    ) {
+        and_reg64_reg64_reg64(buf, dst, src1, src2);
+    }
+
+    #[inline(always)]
+    fn or_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+    ) {
+        orr_reg64_reg64_reg64(buf, dst, src1, src2);
+    }
+
+    #[inline(always)]
+    fn xor_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+    ) {
+        eor_reg64_reg64_reg64(buf, dst, src1, src2);
+    }
+
+    #[inline(always)]
+    fn shl_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+    ) {
+        lsl_reg64_reg64_reg64(buf, dst, src1, src2);
+    }
+
+    #[inline(always)]
+    fn shr_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("adding floats for AArch64");
+        lsr_reg64_reg64_reg64(buf, dst, src1, src2);
     }
 
     #[inline(always)]
-    fn call(_buf: &mut Vec<'_, u8>, _relocs: &mut Vec<'_, Relocation>, _fn_name: String) {
-        todo!("calling functions literal for AArch64");
+    fn sar_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+    ) {
+        asr_reg64_reg64_reg64(buf, dst, src1, src2);
     }
 
     #[inline(always)]
     fn imul_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("register multiplication for AArch64");
+        mul_reg64_reg64_reg64(buf, dst, src1, src2);
     }
 
     #[inline(always)]
-    fn jmp_imm32(_buf: &mut Vec<'_, u8>, _offset: i32) -> usize {
-        todo!("jump instructions for AArch64");
+    fn jmp_imm32(buf: &mut Vec<'_, u8>, offset: i32) -> usize {
+        // Unlike x86_64, branch offsets are relative to the start of the branch instruction.
+        let start = buf.len();
+        b_imm26(buf, offset);
+        start
     }
 
     #[inline(always)]
     fn tail_call(buf: &mut Vec<'_, u8>) -> u64 {
-        Self::jmp_imm32(buf, 0);
-        buf.len() as u64 - 4 // TODO is 4 the correct offset in ARM?
+        Self::jmp_imm32(buf, 0) as u64
     }
 
     #[inline(always)]
     fn jne_reg64_imm64_imm32(
-        _buf: &mut Vec<'_, u8>,
-        _reg: AArch64GeneralReg,
-        _imm: u64,
-        _offset: i32,
+        buf: &mut Vec<'_, u8>,
+        reg: AArch64GeneralReg,
+        imm: u64,
+        offset: i32,
     ) -> usize {
-        todo!("jump not equal instructions for AArch64");
+        if imm <= 0xFFF {
+            cmp_reg64_imm12(buf, reg, imm as u16);
+        } else {
+            Self::mov_reg64_imm64(buf, TMP_REG, imm as i64);
+            cmp_reg64_reg64(buf, reg, TMP_REG);
+        }
+        let start = buf.len();
+        b_cond_imm19(buf, ConditionCode::NE, offset);
+        start
     }
 
     #[inline(always)]
     fn mov_freg32_imm32(
-        _buf: &mut Vec<'_, u8>,
+        buf: &mut Vec<'_, u8>,
         _relocs: &mut Vec<'_, Relocation>,
-        _dst: AArch64FloatReg,
-        _imm: f32,
+        dst: AArch64FloatReg,
+        imm: f32,
     ) {
-        todo!("loading f32 literal for AArch64");
+        // Build the bits in a general register instead of loading them from a data section.
+        Self::mov_reg64_imm64(buf, TMP_REG, imm.to_bits() as i64);
+        fmov_freg_reg(buf, FloatWidth::F32, dst, TMP_REG);
     }
     #[inline(always)]
     fn mov_freg64_imm64(
-        _buf: &mut Vec<'_, u8>,
+        buf: &mut Vec<'_, u8>,
         _relocs: &mut Vec<'_, Relocation>,
-        _dst: AArch64FloatReg,
-        _imm: f64,
+        dst: AArch64FloatReg,
+        imm: f64,
     ) {
-        todo!("loading f64 literal for AArch64");
+        // Build the bits in a general register instead of loading them from a data section.
+        Self::mov_reg64_imm64(buf, TMP_REG, imm.to_bits() as i64);
+        fmov_freg_reg(buf, FloatWidth::F64, dst, TMP_REG);
     }
     #[inline(always)]
     fn mov_reg64_imm64(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, imm: i64) {
+        if (-0x1_0000..0).contains(&imm) {
+            movn_reg64_imm16(buf, dst, !imm as u16, 0);
+            return;
+        }
         let mut remaining = imm as u64;
         movz_reg64_imm16(buf, dst, remaining as u16, 0);
         remaining >>= 16;
@@ -500,43 +1147,59 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         }
     }
     #[inline(always)]
-    fn mov_freg64_freg64(_buf: &mut Vec<'_, u8>, _dst: AArch64FloatReg, _src: AArch64FloatReg) {
-        todo!("moving data between float registers for AArch64");
+    fn mov_freg64_freg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64FloatReg) {
+        if dst != src {
+            fmov_freg_freg(buf, FloatWidth::F64, dst, src);
+        }
     }
     #[inline(always)]
     fn mov_reg64_reg64(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, src: AArch64GeneralReg) {
-        mov_reg64_reg64(buf, dst, src);
+        if dst == AArch64GeneralReg::ZRSP || src == AArch64GeneralReg::ZRSP {
+            // `ORR` would read the zero register, `ADD` reads the stack pointer.
+            add_reg64_reg64_imm12(buf, dst, src, 0);
+        } else {
+            mov_reg64_reg64(buf, dst, src);
+        }
     }
-
     #[inline(always)]
-    fn mov_freg64_base32(_buf: &mut Vec<'_, u8>, _dst: AArch64FloatReg, _offset: i32) {
-        todo!("loading floating point reg from base offset for AArch64");
+    fn mov_freg64_base32(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, offset: i32) {
+        Self::load_store(
+            buf,
+            LoadStoreOp::LoadF64,
+            dst.id(),
+            AArch64GeneralReg::FP,
+            offset,
+        );
     }
     #[inline(always)]
     fn mov_reg64_base32(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, offset: i32) {
-        if offset < 0 {
-            todo!("negative base offsets for AArch64");
-        } else if offset < (0xFFF << 8) {
-            debug_assert!(offset % 8 == 0);
-            ldr_reg64_reg64_imm12(buf, dst, AArch64GeneralReg::FP, (offset as u16) >> 3);
-        } else {
-            todo!("base offsets over 32k for AArch64");
-        }
+        Self::load_store(
+            buf,
+            LoadStoreOp::Load64,
+            dst.id(),
+            AArch64GeneralReg::FP,
+            offset,
+        );
     }
     #[inline(always)]
-    fn mov_base32_freg64(_buf: &mut Vec<'_, u8>, _offset: i32, _src: AArch64FloatReg) {
-        todo!("saving floating point reg to base offset for AArch64");
+    fn mov_base32_freg64(buf: &mut Vec<'_, u8>, offset: i32, src: AArch64FloatReg) {
+        Self::load_store(
+            buf,
+            LoadStoreOp::StoreF64,
+            src.id(),
+            AArch64GeneralReg::FP,
+            offset,
+        );
     }
     #[inline(always)]
     fn mov_base32_reg64(buf: &mut Vec<'_, u8>, offset: i32, src: AArch64GeneralReg) {
-        if offset < 0 {
-            todo!("negative base offsets for AArch64");
-        } else if offset < (0xFFF << 8) {
-            debug_assert!(offset % 8 == 0);
-            str_reg64_reg64_imm12(buf, src, AArch64GeneralReg::FP, (offset as u16) >> 3);
-        } else {
-            todo!("base offsets over 32k for AArch64");
-        }
+        Self::load_store(
+            buf,
+            LoadStoreOp::Store64,
+            src.id(),
+            AArch64GeneralReg::FP,
+            offset,
+        );
     }
 
     #[inline(always)]
@@ -546,14 +1209,7 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         src: AArch64GeneralReg,
         offset: i32,
     ) {
-        if offset < 0 {
-            todo!("negative mem offsets for AArch64");
-        } else if offset < (0xFFF << 8) {
-            debug_assert!(offset % 8 == 0);
-            ldr_reg64_reg64_imm12(buf, dst, src, (offset as u16) >> 3);
-        } else {
-            todo!("mem offsets over 32k for AArch64");
-        }
+        Self::load_store(buf, LoadStoreOp::Load64, dst.id(), src, offset);
     }
     #[inline(always)]
     fn mov_mem64_offset32_reg64(
@@ -562,191 +1218,382 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
         offset: i32,
         src: AArch64GeneralReg,
     ) {
-        if offset < 0 {
-            todo!("negative mem offsets for AArch64");
-        } else if offset < (0xFFF << 8) {
-            debug_assert!(offset % 8 == 0);
-            str_reg64_reg64_imm12(buf, src, dst, (offset as u16) >> 3);
-        } else {
-            todo!("mem offsets over 32k for AArch64");
-        }
+        Self::load_store(buf, LoadStoreOp::Store64, src.id(), dst, offset);
     }
 
     #[inline(always)]
-    fn movsx_reg64_base32(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, offset: i32, size: u8) {
+    fn movsx_reg64_mem64_offset32(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src: AArch64GeneralReg,
+        offset: i32,
+        size: u8,
+    ) {
         debug_assert!(size <= 8);
-        if size == 8 {
-            Self::mov_reg64_base32(buf, dst, offset);
-        } else if size == 4 {
-            todo!("sign extending 4 byte values");
-        } else if size == 2 {
-            todo!("sign extending 2 byte values");
-        } else if size == 1 {
-            todo!("sign extending 1 byte values");
-        } else {
-            internal_error!("Invalid size for sign extension: {}", size);
-        }
+        let op = match size {
+            8 => LoadStoreOp::Load64,
+            4 => LoadStoreOp::LoadSigned32,
+            2 => LoadStoreOp::LoadSigned16,
+            1 => LoadStoreOp::LoadSigned8,
+            _ => internal_error!("Invalid size for sign extension: {}", size),
+        };
+        Self::load_store(buf, op, dst.id(), src, offset);
     }
     #[inline(always)]
-    fn movzx_reg64_base32(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, offset: i32, size: u8) {
+    fn movzx_reg64_mem64_offset32(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src: AArch64GeneralReg,
+        offset: i32,
+        size: u8,
+    ) {
         debug_assert!(size <= 8);
-        if size == 8 {
-            Self::mov_reg64_base32(buf, dst, offset);
-        } else if size == 4 {
-            todo!("zero extending 4 byte values");
-        } else if size == 2 {
-            todo!("zero extending 2 byte values");
-        } else if size == 1 {
-            todo!("zero extending 1 byte values");
-        } else {
-            internal_error!("Invalid size for zero extension: {}", size);
-        }
+        // Loads into the 32 bit view of a register zero the upper bits.
+        let op = match size {
+            8 => LoadStoreOp::Load64,
+            4 => LoadStoreOp::Load32,
+            2 => LoadStoreOp::Load16,
+            1 => LoadStoreOp::Load8,
+            _ => internal_error!("Invalid size for zero extension: {}", size),
+        };
+        Self::load_store(buf, op, dst.id(), src, offset);
+    }
+    #[inline(always)]
+    fn mov_sized_mem64_offset32_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        offset: i32,
+        src: AArch64GeneralReg,
+        size: u8,
+    ) {
+        let op = match size {
+            8 => LoadStoreOp::Store64,
+            4 => LoadStoreOp::Store32,
+            2 => LoadStoreOp::Store16,
+            1 => LoadStoreOp::Store8,
+            _ => internal_error!("Invalid size for a sized move: {}", size),
+        };
+        Self::load_store(buf, op, src.id(), dst, offset);
     }
 
     #[inline(always)]
-    fn mov_freg64_stack32(_buf: &mut Vec<'_, u8>, _dst: AArch64FloatReg, _offset: i32) {
-        todo!("loading floating point reg from stack for AArch64");
+    fn mov_freg64_mem64_offset32(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src: AArch64GeneralReg,
+        offset: i32,
+    ) {
+        Self::load_store(buf, LoadStoreOp::LoadF64, dst.id(), src, offset);
     }
     #[inline(always)]
-    fn mov_reg64_stack32(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, offset: i32) {
-        if offset < 0 {
-            todo!("negative stack offsets for AArch64");
-        } else if offset < (0xFFF << 8) {
-            debug_assert!(offset % 8 == 0);
-            ldr_reg64_reg64_imm12(buf, dst, AArch64GeneralReg::ZRSP, (offset as u16) >> 3);
-        } else {
-            todo!("stack offsets over 32k for AArch64");
-        }
+    fn mov_mem64_offset32_freg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        offset: i32,
+        src: AArch64FloatReg,
+    ) {
+        Self::load_store(buf, LoadStoreOp::StoreF64, src.id(), dst, offset);
+    }
+    #[inline(always)]
+    fn mov_freg32_mem32_offset32(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src: AArch64GeneralReg,
+        offset: i32,
+    ) {
+        Self::load_store(buf, LoadStoreOp::LoadF32, dst.id(), src, offset);
     }
     #[inline(always)]
-    fn mov_stack32_freg64(_buf: &mut Vec<'_, u8>, _offset: i32, _src: AArch64FloatReg) {
-        todo!("saving floating point reg to stack for AArch64");
+    fn mov_mem32_offset32_freg32(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        offset: i32,
+        src: AArch64FloatReg,
+    ) {
+        Self::load_store(buf, LoadStoreOp::StoreF32, src.id(), dst, offset);
     }
+
     #[inline(always)]
-    fn mov_stack32_reg64(buf: &mut Vec<'_, u8>, offset: i32, src: AArch64GeneralReg) {
-        if offset < 0 {
-            todo!("negative stack offsets for AArch64");
-        } else if offset < (0xFFF << 8) {
-            debug_assert!(offset % 8 == 0);
-            str_reg64_reg64_imm12(buf, src, AArch64GeneralReg::ZRSP, (offset as u16) >> 3);
-        } else {
-            todo!("stack offsets over 32k for AArch64");
-        }
+    fn movsx_reg64_base32(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, offset: i32, size: u8) {
+        Self::movsx_reg64_mem64_offset32(buf, dst, AArch64GeneralReg::FP, offset, size)
     }
     #[inline(always)]
-    fn neg_reg64_reg64(_buf: &mut Vec<'_, u8>, _dst: AArch64GeneralReg, _src: AArch64GeneralReg) {
-        todo!("neg for AArch64");
+    fn movzx_reg64_base32(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, offset: i32, size: u8) {
+        Self::movzx_reg64_mem64_offset32(buf, dst, AArch64GeneralReg::FP, offset, size)
     }
 
     #[inline(always)]
-    fn sub_reg64_reg64_imm32(
+    fn movsx_reg64_reg64(
         buf: &mut Vec<'_, u8>,
         dst: AArch64GeneralReg,
         src: AArch64GeneralReg,
-        imm32: i32,
+        size: u8,
     ) {
-        if imm32 < 0 {
-            todo!("immediate subtractions with values less than 0");
-        } else if imm32 < 0xFFF {
-            sub_reg64_reg64_imm12(buf, dst, src, imm32 as u16);
-        } else {
-            todo!("immediate subtractions with values greater than 12bits");
+        debug_assert!(size <= 8);
+        match size {
+            8 => Self::mov_reg64_reg64(buf, dst, src),
+            4 | 2 | 1 => sxt_reg64_reg64(buf, dst, src, size),
+            _ => internal_error!("Invalid size for sign extension: {}", size),
+        }
+    }
+    #[inline(always)]
+    fn movzx_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src: AArch64GeneralReg,
+        size: u8,
+    ) {
+        debug_assert!(size <= 8);
+        match size {
+            8 => Self::mov_reg64_reg64(buf, dst, src),
+            4 => mov_reg32_reg32(buf, dst, src),
+            2 | 1 => uxt_reg32_reg32(buf, dst, src, size),
+            _ => internal_error!("Invalid size for zero extension: {}", size),
         }
     }
+
+    #[inline(always)]
+    fn mov_freg64_stack32(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, offset: i32) {
+        Self::load_store(
+            buf,
+            LoadStoreOp::LoadF64,
+            dst.id(),
+            AArch64GeneralReg::ZRSP,
+            offset,
+        );
+    }
+    #[inline(always)]
+    fn mov_reg64_stack32(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, offset: i32) {
+        Self::load_store(
+            buf,
+            LoadStoreOp::Load64,
+            dst.id(),
+            AArch64GeneralReg::ZRSP,
+            offset,
+        );
+    }
+    #[inline(always)]
+    fn mov_stack32_freg64(buf: &mut Vec<'_, u8>, offset: i32, src: AArch64FloatReg) {
+        Self::load_store(
+            buf,
+            LoadStoreOp::StoreF64,
+            src.id(),
+            AArch64GeneralReg::ZRSP,
+            offset,
+        );
+    }
+    #[inline(always)]
+    fn mov_stack32_reg64(buf: &mut Vec<'_, u8>, offset: i32, src: AArch64GeneralReg) {
+        Self::load_store(
+            buf,
+            LoadStoreOp::Store64,
+            src.id(),
+            AArch64GeneralReg::ZRSP,
+            offset,
+        );
+    }
+    #[inline(always)]
+    fn neg_reg64_reg64(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, src: AArch64GeneralReg) {
+        sub_reg64_reg64_reg64(buf, dst, AArch64GeneralReg::ZRSP, src);
+    }
+    #[inline(always)]
+    fn sub_freg32_freg32_freg32(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src1: AArch64FloatReg,
+        src2: AArch64FloatReg,
+    ) {
+        fsub_freg_freg_freg(buf, FloatWidth::F32, dst, src1, src2);
+    }
+    #[inline(always)]
+    fn sub_freg64_freg64_freg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src1: AArch64FloatReg,
+        src2: AArch64FloatReg,
+    ) {
+        fsub_freg_freg_freg(buf, FloatWidth::F64, dst, src1, src2);
+    }
+    #[inline(always)]
+    fn mul_freg32_freg32_freg32(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src1: AArch64FloatReg,
+        src2: AArch64FloatReg,
+    ) {
+        fmul_freg_freg_freg(buf, FloatWidth::F32, dst, src1, src2);
+    }
+    #[inline(always)]
+    fn mul_freg64_freg64_freg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src1: AArch64FloatReg,
+        src2: AArch64FloatReg,
+    ) {
+        fmul_freg_freg_freg(buf, FloatWidth::F64, dst, src1, src2);
+    }
+    #[inline(always)]
+    fn div_freg32_freg32_freg32(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src1: AArch64FloatReg,
+        src2: AArch64FloatReg,
+    ) {
+        fdiv_freg_freg_freg(buf, FloatWidth::F32, dst, src1, src2);
+    }
+    #[inline(always)]
+    fn div_freg64_freg64_freg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64FloatReg,
+        src1: AArch64FloatReg,
+        src2: AArch64FloatReg,
+    ) {
+        fdiv_freg_freg_freg(buf, FloatWidth::F64, dst, src1, src2);
+    }
+    #[inline(always)]
+    fn idiv_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+    ) {
+        sdiv_reg64_reg64_reg64(buf, dst, src1, src2);
+    }
+    #[inline(always)]
+    fn udiv_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+    ) {
+        udiv_reg64_reg64_reg64(buf, dst, src1, src2);
+    }
+    #[inline(always)]
+    fn irem_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+    ) {
+        // There is no remainder instruction: `src1 - (src1 / src2) * src2`.
+        sdiv_reg64_reg64_reg64(buf, TMP_OFFSET_REG, src1, src2);
+        msub_reg64_reg64_reg64_reg64(buf, dst, TMP_OFFSET_REG, src2, src1);
+    }
+    #[inline(always)]
+    fn urem_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
+    ) {
+        // There is no remainder instruction: `src1 - (src1 / src2) * src2`.
+        udiv_reg64_reg64_reg64(buf, TMP_OFFSET_REG, src1, src2);
+        msub_reg64_reg64_reg64_reg64(buf, dst, TMP_OFFSET_REG, src2, src1);
+    }
+
+    #[inline(always)]
+    fn sub_reg64_reg64_imm32(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src: AArch64GeneralReg,
+        imm32: i32,
+    ) {
+        Self::add_sub_reg64_reg64_imm32(buf, true, dst, src, imm32);
+    }
     #[inline(always)]
     fn sub_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers subtractions for AArch64");
+        sub_reg64_reg64_reg64(buf, dst, src1, src2);
     }
 
     #[inline(always)]
     fn eq_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers equality for AArch64");
+        cmp_reg64_reg64(buf, src1, src2);
+        cset_reg64_cond(buf, dst, ConditionCode::EQ);
     }
 
     #[inline(always)]
     fn neq_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers non-equality for AArch64");
+        cmp_reg64_reg64(buf, src1, src2);
+        cset_reg64_cond(buf, dst, ConditionCode::NE);
     }
 
     #[inline(always)]
     fn lt_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers less than for AArch64");
+        cmp_reg64_reg64(buf, src1, src2);
+        cset_reg64_cond(buf, dst, ConditionCode::LT);
     }
 
     #[inline(always)]
-    fn to_float_freg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src: AArch64GeneralReg,
+    fn gt_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers to float for AArch64");
+        cmp_reg64_reg64(buf, src1, src2);
+        cset_reg64_cond(buf, dst, ConditionCode::GT);
     }
 
     #[inline(always)]
-    fn to_float_freg32_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src: AArch64GeneralReg,
-    ) {
-        todo!("registers to float for AArch64");
+    fn to_float_freg64_reg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64GeneralReg) {
+        scvtf_freg_reg64(buf, FloatWidth::F64, dst, src);
     }
 
     #[inline(always)]
-    fn to_float_freg32_freg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src: AArch64FloatReg,
-    ) {
-        todo!("registers to float for AArch64");
+    fn to_float_freg32_reg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64GeneralReg) {
+        scvtf_freg_reg64(buf, FloatWidth::F32, dst, src);
     }
 
     #[inline(always)]
-    fn to_float_freg64_freg32(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64FloatReg,
-        _src: AArch64FloatReg,
-    ) {
-        todo!("registers to float for AArch64");
+    fn to_float_freg32_freg64(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64FloatReg) {
+        fcvt_freg_freg(buf, FloatWidth::F32, dst, src);
+    }
+
+    #[inline(always)]
+    fn to_float_freg64_freg32(buf: &mut Vec<'_, u8>, dst: AArch64FloatReg, src: AArch64FloatReg) {
+        fcvt_freg_freg(buf, FloatWidth::F64, dst, src);
     }
 
     #[inline(always)]
     fn lte_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers less than or equal for AArch64");
+        cmp_reg64_reg64(buf, src1, src2);
+        cset_reg64_cond(buf, dst, ConditionCode::LE);
     }
 
     #[inline(always)]
     fn gte_reg64_reg64_reg64(
-        _buf: &mut Vec<'_, u8>,
-        _dst: AArch64GeneralReg,
-        _src1: AArch64GeneralReg,
-        _src2: AArch64GeneralReg,
+        buf: &mut Vec<'_, u8>,
+        dst: AArch64GeneralReg,
+        src1: AArch64GeneralReg,
+        src2: AArch64GeneralReg,
     ) {
-        todo!("registers greater than or equal for AArch64");
+        cmp_reg64_reg64(buf, src1, src2);
+        cset_reg64_cond(buf, dst, ConditionCode::GE);
     }
 
     #[inline(always)]
@@ -755,7 +1602,159 @@ impl Assembler<AArch64GeneralReg, AArch64FloatReg> for AArch64Assembler {
     }
 }
 
-impl AArch64Assembler {}
+/// The width, register bank, and direction of a load or store.
+#[derive(Clone, Copy, Debug)]
+#[allow(dead_code)]
+enum LoadStoreOp {
+    Load8,
+    Load16,
+    Load32,
+    Load64,
+    LoadSigned8,
+    LoadSigned16,
+    LoadSigned32,
+    LoadF32,
+    LoadF64,
+    Store8,
+    Store16,
+    Store32,
+    Store64,
+    StoreF32,
+    StoreF64,
+}
+
+impl LoadStoreOp {
+    /// Returns the `size`, `V` and `opc` fields shared by all load/store register encodings.
+    #[inline(always)]
+    fn fields(&self) -> (u8, bool, u8) {
+        match self {
+            LoadStoreOp::Store8 => (0b00, false, 0b00),
+            LoadStoreOp::Load8 => (0b00, false, 0b01),
+            LoadStoreOp::LoadSigned8 => (0b00, false, 0b10),
+            LoadStoreOp::Store16 => (0b01, false, 0b00),
+            LoadStoreOp::Load16 => (0b01, false, 0b01),
+            LoadStoreOp::LoadSigned16 => (0b01, false, 0b10),
+            LoadStoreOp::Store32 => (0b10, false, 0b00),
+            LoadStoreOp::Load32 => (0b10, false, 0b01),
+            LoadStoreOp::LoadSigned32 => (0b10, false, 0b10),
+            LoadStoreOp::Store64 => (0b11, false, 0b00),
+            LoadStoreOp::Load64 => (0b11, false, 0b01),
+            LoadStoreOp::StoreF32 => (0b10, true, 0b00),
+            LoadStoreOp::LoadF32 => (0b10, true, 0b01),
+            LoadStoreOp::StoreF64 => (0b11, true, 0b00),
+            LoadStoreOp::LoadF64 => (0b11, true, 0b01),
+        }
+    }
+}
+
+impl AArch64Assembler {
+    /// Adds or subtracts an immediate, splitting it up if it does not fit a single instruction.
+    #[inline(always)]
+    fn add_sub_reg64_reg64_imm32(
+        buf: &mut Vec<'_, u8>,
+        sub: bool,
+        dst: AArch64GeneralReg,
+        src: AArch64GeneralReg,
+        imm32: i32,
+    ) {
+        let (sub, imm) = if imm32 < 0 {
+            (!sub, imm32.unsigned_abs())
+        } else {
+            (sub, imm32 as u32)
+        };
+        if imm <= 0xFFF && sub {
+            sub_reg64_reg64_imm12(buf, dst, src, imm as u16);
+        } else if imm <= 0xFFF {
+            add_reg64_reg64_imm12(buf, dst, src, imm as u16);
+        } else if imm <= 0xFF_FFFF {
+            add_sub_reg64_reg64_imm12(buf, sub, dst, src, (imm >> 12) as u16, true);
+            if imm & 0xFFF != 0 {
+                add_sub_reg64_reg64_imm12(buf, sub, dst, dst, (imm & 0xFFF) as u16, false);
+            }
+        } else if dst != AArch64GeneralReg::ZRSP && src != AArch64GeneralReg::ZRSP {
+            Self::mov_reg64_imm64(buf, TMP_OFFSET_REG, imm as i64);
+            if sub {
+                sub_reg64_reg64_reg64(buf, dst, src, TMP_OFFSET_REG);
+            } else {
+                add_reg64_reg64_reg64(buf, dst, src, TMP_OFFSET_REG);
+            }
+        } else {
+            internal_error!("Ran out of stack space");
+        }
+    }
+
+    /// Loads or stores `reg` at `base + offset` using the shortest encoding that can hold the offset.
+    /// Offsets that do not fit any immediate form are built in a scratch register.
+    #[inline(always)]
+    fn load_store(
+        buf: &mut Vec<'_, u8>,
+        op: LoadStoreOp,
+        reg: u8,
+        base: AArch64GeneralReg,
+        offset: i32,
+    ) {
+        let (size, _, _) = op.fields();
+        let scale = 1 << size;
+        if offset >= 0 && offset % scale == 0 && offset / scale <= 0xFFF {
+            load_store_imm12(buf, op, reg, base, (offset / scale) as u16);
+        } else if (-256..256).contains(&offset) {
+            load_store_imm9(buf, op, reg, base, offset as i16);
+        } else {
+            Self::mov_reg64_imm64(buf, TMP_OFFSET_REG, offset as i64);
+            load_store_reg64_offset(buf, op, reg, base, TMP_OFFSET_REG);
+        }
+    }
+
+    /// Stores consecutive float registers to `FP + offset`, packed at the width of the floats.
+    #[inline(always)]
+    fn store_float_regs(
+        buf: &mut Vec<'_, u8>,
+        width: FloatWidth,
+        offset: i32,
+        regs: &[AArch64FloatReg],
+    ) {
+        let op = match width {
+            FloatWidth::F32 => LoadStoreOp::StoreF32,
+            FloatWidth::F64 => LoadStoreOp::StoreF64,
+            FloatWidth::F128 => internal_error!("F128 is not supported"),
+        };
+        let step = width.stack_size() as i32;
+        for (i, reg) in regs.iter().enumerate() {
+            Self::load_store(
+                buf,
+                op,
+                reg.id(),
+                AArch64GeneralReg::FP,
+                offset + step * i as i32,
+            );
+        }
+    }
+
+    /// Loads consecutive float registers from `FP + offset`, packed at the width of the floats.
+    #[inline(always)]
+    fn load_float_regs(
+        buf: &mut Vec<'_, u8>,
+        width: FloatWidth,
+        offset: i32,
+        regs: &[AArch64FloatReg],
+    ) {
+        let op = match width {
+            FloatWidth::F32 => LoadStoreOp::LoadF32,
+            FloatWidth::F64 => LoadStoreOp::LoadF64,
+            FloatWidth::F128 => internal_error!("F128 is not supported"),
+        };
+        let step = width.stack_size() as i32;
+        for (i, reg) in regs.iter().enumerate() {
+            Self::load_store(
+                buf,
+                op,
+                reg.id(),
+                AArch64GeneralReg::FP,
+                offset + step * i as i32,
+            );
+        }
+    }
+}
 
 // Instructions
 // ARM manual section C3
@@ -951,6 +1950,7 @@ impl LogicalShiftedRegister {
         rm: AArch64GeneralReg,
         rn: AArch64GeneralReg,
         rd: AArch64GeneralReg,
+        sf: bool,
     ) -> Self {
         debug_assert!(imm6 <= 0b111111);
 
@@ -974,130 +1974,938 @@ impl LogicalShiftedRegister {
             shift: shift.id().into(),
             fixed: 0b01010.into(),
             op: op.into(),
-            // true for 64 bit addition
-            // false for 32 bit addition
-            sf: true,
+            // true for 64 bit operations
+            // false for 32 bit operations
+            sf,
         }
     }
 }
 
 #[derive(PackedStruct)]
-pub struct UnconditionalBranchRegister {
-    fixed: Integer<u8, packed_bits::Bits<7>>,
-    z: bool,
-    fixed2: bool,
-    op: Integer<u8, packed_bits::Bits<2>>,
-    fixed3: Integer<u8, packed_bits::Bits<5>>,
-    fixed4: Integer<u8, packed_bits::Bits<4>>,
-    a: bool,
-    m: bool,
-    rn: Integer<u8, packed_bits::Bits<5>>,
-    fixed5: Integer<u8, packed_bits::Bits<5>>,
+#[packed_struct(endian = "msb")]
+pub struct Bitfield {
+    sf: bool,
+    opc: Integer<u8, packed_bits::Bits<2>>,
+    fixed: Integer<u8, packed_bits::Bits<6>>, // = 0b100110,
+    n: bool,
+    immr: Integer<u8, packed_bits::Bits<6>>,
+    imms: Integer<u8, packed_bits::Bits<6>>,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
 }
 
-impl Aarch64Bytes for UnconditionalBranchRegister {}
+impl Aarch64Bytes for Bitfield {}
 
-impl UnconditionalBranchRegister {
+impl Bitfield {
+    /// opc = 0b00 means SBFM
+    /// opc = 0b10 means UBFM
     #[inline(always)]
-    fn new(op: u8, rn: AArch64GeneralReg) -> Self {
-        debug_assert!(op <= 0b11);
+    fn new(
+        opc: u8,
+        immr: u8,
+        imms: u8,
+        rn: AArch64GeneralReg,
+        rd: AArch64GeneralReg,
+        sf: bool,
+    ) -> Self {
+        debug_assert!(opc <= 0b11);
+        debug_assert!(immr <= 0b111111);
+        debug_assert!(imms <= 0b111111);
 
         Self {
-            fixed5: 0b00000.into(),
-            rn: rn.id().into(),
-            m: false,
-            a: false,
-            fixed4: 0b0000.into(),
-            fixed3: 0b11111.into(),
-            op: op.into(),
-            fixed2: false,
-            z: false,
-            fixed: 0b1101011.into(),
+            reg_d: rd.id().into(),
+            reg_n: rn.id().into(),
+            imms: imms.into(),
+            immr: immr.into(),
+            // N must match sf.
+            n: sf,
+            fixed: 0b100110.into(),
+            opc: opc.into(),
+            sf,
         }
     }
 }
 
-// Uses unsigned Offset
-// opc = 0b01 means load
-// opc = 0b00 means store
-#[derive(PackedStruct, Debug)]
+#[derive(PackedStruct)]
 #[packed_struct(endian = "msb")]
-pub struct LoadStoreRegisterImmediate {
-    size: Integer<u8, packed_bits::Bits<2>>,
-    fixed: Integer<u8, packed_bits::Bits<3>>, // = 0b111,
-    fixed2: bool,
-    fixed3: Integer<u8, packed_bits::Bits<2>>,
-    opc: Integer<u8, packed_bits::Bits<2>>,
-    imm12: Integer<u16, packed_bits::Bits<12>>,
-    rn: Integer<u8, packed_bits::Bits<5>>,
-    rt: Integer<u8, packed_bits::Bits<5>>,
+pub struct DataProcessingTwoSource {
+    sf: bool,
+    fixed: bool,
+    s: bool,
+    fixed2: Integer<u8, packed_bits::Bits<8>>, // = 0b11010110,
+    reg_m: Integer<u8, packed_bits::Bits<5>>,
+    opcode: Integer<u8, packed_bits::Bits<6>>,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
 }
 
-impl Aarch64Bytes for LoadStoreRegisterImmediate {}
+impl Aarch64Bytes for DataProcessingTwoSource {}
 
-impl LoadStoreRegisterImmediate {
+impl DataProcessingTwoSource {
     #[inline(always)]
-    fn new(size: u8, opc: u8, imm12: u16, rn: AArch64GeneralReg, rt: AArch64GeneralReg) -> Self {
-        debug_assert!(size <= 0b11);
-        debug_assert!(imm12 <= 0xFFF);
+    fn new(
+        opcode: u8,
+        rm: AArch64GeneralReg,
+        rn: AArch64GeneralReg,
+        rd: AArch64GeneralReg,
+    ) -> Self {
+        debug_assert!(opcode <= 0b111111);
 
         Self {
-            rt: rt.id().into(),
-            rn: rn.id().into(),
-            imm12: imm12.into(),
-            opc: opc.into(),
-            fixed3: 0b01.into(),
-            fixed2: false,
-            fixed: 0b111.into(),
-            size: size.into(),
+            reg_d: rd.id().into(),
+            reg_n: rn.id().into(),
+            opcode: opcode.into(),
+            reg_m: rm.id().into(),
+            fixed2: 0b11010110.into(),
+            s: false,
+            fixed: false,
+            sf: true,
         }
     }
+}
 
-    #[inline(always)]
-    fn new_load(size: u8, imm12: u16, rn: AArch64GeneralReg, rt: AArch64GeneralReg) -> Self {
-        Self::new(size, 0b01, imm12, rn, rt)
+#[derive(PackedStruct)]
+#[packed_struct(endian = "msb")]
+pub struct DataProcessingThreeSource {
+    sf: bool,
+    op54: Integer<u8, packed_bits::Bits<2>>,
+    fixed: Integer<u8, packed_bits::Bits<5>>, // = 0b11011,
+    op31: Integer<u8, packed_bits::Bits<3>>,
+    reg_m: Integer<u8, packed_bits::Bits<5>>,
+    o0: bool,
+    reg_a: Integer<u8, packed_bits::Bits<5>>,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for DataProcessingThreeSource {}
+
+impl DataProcessingThreeSource {
+    /// o0 = false means MADD
+    /// o0 = true means MSUB
+    #[inline(always)]
+    fn new(
+        o0: bool,
+        rm: AArch64GeneralReg,
+        ra: AArch64GeneralReg,
+        rn: AArch64GeneralReg,
+        rd: AArch64GeneralReg,
+    ) -> Self {
+        Self {
+            reg_d: rd.id().into(),
+            reg_n: rn.id().into(),
+            reg_a: ra.id().into(),
+            o0,
+            reg_m: rm.id().into(),
+            op31: 0b000.into(),
+            fixed: 0b11011.into(),
+            op54: 0b00.into(),
+            sf: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[allow(dead_code)]
+enum ConditionCode {
+    EQ = 0b0000,
+    NE = 0b0001,
+    HS = 0b0010,
+    LO = 0b0011,
+    MI = 0b0100,
+    PL = 0b0101,
+    VS = 0b0110,
+    VC = 0b0111,
+    HI = 0b1000,
+    LS = 0b1001,
+    GE = 0b1010,
+    LT = 0b1011,
+    GT = 0b1100,
+    LE = 0b1101,
+    AL = 0b1110,
+}
+
+impl ConditionCode {
+    #[inline(always)]
+    fn id(&self) -> u8 {
+        *self as u8
+    }
+
+    /// The condition that holds exactly when this one does not.
+    #[inline(always)]
+    fn invert(&self) -> u8 {
+        debug_assert!(!matches!(self, ConditionCode::AL));
+        self.id() ^ 1
+    }
+}
+
+#[derive(PackedStruct)]
+#[packed_struct(endian = "msb")]
+pub struct ConditionalSelect {
+    sf: bool,
+    op: bool,
+    s: bool,
+    fixed: Integer<u8, packed_bits::Bits<8>>, // = 0b11010100,
+    reg_m: Integer<u8, packed_bits::Bits<5>>,
+    cond: Integer<u8, packed_bits::Bits<4>>,
+    op2: Integer<u8, packed_bits::Bits<2>>,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for ConditionalSelect {}
+
+impl ConditionalSelect {
+    /// op = false, op2 = 0b01 means CSINC
+    /// op = true, op2 = 0b01 means CSNEG
+    #[inline(always)]
+    fn new(
+        op: bool,
+        op2: u8,
+        cond: u8,
+        rm: AArch64GeneralReg,
+        rn: AArch64GeneralReg,
+        rd: AArch64GeneralReg,
+    ) -> Self {
+        debug_assert!(op2 <= 0b11);
+        debug_assert!(cond <= 0b1111);
+
+        Self {
+            reg_d: rd.id().into(),
+            reg_n: rn.id().into(),
+            op2: op2.into(),
+            cond: cond.into(),
+            reg_m: rm.id().into(),
+            fixed: 0b11010100.into(),
+            s: false,
+            op,
+            sf: true,
+        }
     }
+}
+
+#[derive(PackedStruct)]
+#[packed_struct(endian = "msb")]
+pub struct ConditionalBranchImmediate {
+    fixed: Integer<u8, packed_bits::Bits<7>>, // = 0b0101010,
+    o1: bool,
+    imm19: Integer<u32, packed_bits::Bits<19>>,
+    o0: bool,
+    cond: Integer<u8, packed_bits::Bits<4>>,
+}
+
+impl Aarch64Bytes for ConditionalBranchImmediate {}
+
+impl ConditionalBranchImmediate {
+    #[inline(always)]
+    fn new(cond: ConditionCode, offset: i32) -> Self {
+        debug_assert!(offset % 4 == 0);
+        debug_assert!((-(1 << 20)..(1 << 20)).contains(&offset));
+
+        Self {
+            cond: cond.id().into(),
+            o0: false,
+            imm19: (((offset >> 2) as u32) & 0x7FFFF).into(),
+            o1: false,
+            fixed: 0b0101010.into(),
+        }
+    }
+}
+
+#[derive(PackedStruct)]
+#[packed_struct(endian = "msb")]
+pub struct UnconditionalBranchImmediate {
+    op: bool,                                 // link
+    fixed: Integer<u8, packed_bits::Bits<5>>, // = 0b00101,
+    imm26: Integer<u32, packed_bits::Bits<26>>,
+}
+
+impl Aarch64Bytes for UnconditionalBranchImmediate {}
+
+impl UnconditionalBranchImmediate {
+    #[inline(always)]
+    fn new(op: bool, offset: i32) -> Self {
+        debug_assert!(offset % 4 == 0);
+        debug_assert!((-(1 << 27)..(1 << 27)).contains(&offset));
+
+        Self {
+            imm26: (((offset >> 2) as u32) & 0x3FF_FFFF).into(),
+            fixed: 0b00101.into(),
+            op,
+        }
+    }
+}
+
+#[derive(PackedStruct)]
+pub struct UnconditionalBranchRegister {
+    fixed: Integer<u8, packed_bits::Bits<7>>,
+    z: bool,
+    fixed2: bool,
+    op: Integer<u8, packed_bits::Bits<2>>,
+    fixed3: Integer<u8, packed_bits::Bits<5>>,
+    fixed4: Integer<u8, packed_bits::Bits<4>>,
+    a: bool,
+    m: bool,
+    rn: Integer<u8, packed_bits::Bits<5>>,
+    fixed5: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for UnconditionalBranchRegister {}
+
+impl UnconditionalBranchRegister {
+    #[inline(always)]
+    fn new(op: u8, rn: AArch64GeneralReg) -> Self {
+        debug_assert!(op <= 0b11);
+
+        Self {
+            fixed5: 0b00000.into(),
+            rn: rn.id().into(),
+            m: false,
+            a: false,
+            fixed4: 0b0000.into(),
+            fixed3: 0b11111.into(),
+            op: op.into(),
+            fixed2: false,
+            z: false,
+            fixed: 0b1101011.into(),
+        }
+    }
+}
+
+#[derive(PackedStruct)]
+#[packed_struct(endian = "msb")]
+pub struct PCRelAddressing {
+    op: bool, // page
+    immlo: Integer<u8, packed_bits::Bits<2>>,
+    fixed: Integer<u8, packed_bits::Bits<5>>, // = 0b10000,
+    immhi: Integer<u32, packed_bits::Bits<19>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for PCRelAddressing {}
+
+impl PCRelAddressing {
+    #[inline(always)]
+    fn new(op: bool, imm21: i32, rd: AArch64GeneralReg) -> Self {
+        debug_assert!((-(1 << 20)..(1 << 20)).contains(&imm21));
+
+        let imm21 = imm21 as u32;
+        Self {
+            reg_d: rd.id().into(),
+            immhi: ((imm21 >> 2) & 0x7FFFF).into(),
+            fixed: 0b10000.into(),
+            immlo: ((imm21 & 0b11) as u8).into(),
+            op,
+        }
+    }
+}
+
+// Uses unsigned Offset
+// opc = 0b01 means load
+// opc = 0b00 means store
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct LoadStoreRegisterImmediate {
+    size: Integer<u8, packed_bits::Bits<2>>,
+    fixed: Integer<u8, packed_bits::Bits<3>>,  // = 0b111,
+    v: bool,                                   // float register
+    fixed2: Integer<u8, packed_bits::Bits<2>>, // = 0b01,
+    opc: Integer<u8, packed_bits::Bits<2>>,
+    imm12: Integer<u16, packed_bits::Bits<12>>,
+    rn: Integer<u8, packed_bits::Bits<5>>,
+    rt: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for LoadStoreRegisterImmediate {}
+
+impl LoadStoreRegisterImmediate {
+    #[inline(always)]
+    fn new(op: LoadStoreOp, imm12: u16, rn: AArch64GeneralReg, rt: u8) -> Self {
+        debug_assert!(imm12 <= 0xFFF);
+        debug_assert!(rt <= 0b11111);
+        let (size, v, opc) = op.fields();
+
+        Self {
+            rt: rt.into(),
+            rn: rn.id().into(),
+            imm12: imm12.into(),
+            opc: opc.into(),
+            fixed2: 0b01.into(),
+            v,
+            fixed: 0b111.into(),
+            size: size.into(),
+        }
+    }
+}
+
+// Uses a signed unscaled offset (LDUR/STUR).
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct LoadStoreRegisterUnscaled {
+    size: Integer<u8, packed_bits::Bits<2>>,
+    fixed: Integer<u8, packed_bits::Bits<3>>,  // = 0b111,
+    v: bool,                                   // float register
+    fixed2: Integer<u8, packed_bits::Bits<2>>, // = 0b00,
+    opc: Integer<u8, packed_bits::Bits<2>>,
+    fixed3: bool, // = 0b0,
+    imm9: Integer<u16, packed_bits::Bits<9>>,
+    fixed4: Integer<u8, packed_bits::Bits<2>>, // = 0b00,
+    rn: Integer<u8, packed_bits::Bits<5>>,
+    rt: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for LoadStoreRegisterUnscaled {}
+
+impl LoadStoreRegisterUnscaled {
+    #[inline(always)]
+    fn new(op: LoadStoreOp, imm9: i16, rn: AArch64GeneralReg, rt: u8) -> Self {
+        debug_assert!((-256..256).contains(&imm9));
+        debug_assert!(rt <= 0b11111);
+        let (size, v, opc) = op.fields();
+
+        Self {
+            rt: rt.into(),
+            rn: rn.id().into(),
+            fixed4: 0b00.into(),
+            imm9: ((imm9 as u16) & 0x1FF).into(),
+            fixed3: false,
+            opc: opc.into(),
+            fixed2: 0b00.into(),
+            v,
+            fixed: 0b111.into(),
+            size: size.into(),
+        }
+    }
+}
+
+// Uses a register offset without extension or shift.
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct LoadStoreRegisterOffset {
+    size: Integer<u8, packed_bits::Bits<2>>,
+    fixed: Integer<u8, packed_bits::Bits<3>>,  // = 0b111,
+    v: bool,                                   // float register
+    fixed2: Integer<u8, packed_bits::Bits<2>>, // = 0b00,
+    opc: Integer<u8, packed_bits::Bits<2>>,
+    fixed3: bool, // = 0b1,
+    rm: Integer<u8, packed_bits::Bits<5>>,
+    option: Integer<u8, packed_bits::Bits<3>>, // = 0b011, LSL
+    s: bool,                                   // shift
+    fixed4: Integer<u8, packed_bits::Bits<2>>, // = 0b10,
+    rn: Integer<u8, packed_bits::Bits<5>>,
+    rt: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for LoadStoreRegisterOffset {}
+
+impl LoadStoreRegisterOffset {
+    #[inline(always)]
+    fn new(op: LoadStoreOp, rm: AArch64GeneralReg, rn: AArch64GeneralReg, rt: u8) -> Self {
+        debug_assert!(rt <= 0b11111);
+        let (size, v, opc) = op.fields();
+
+        Self {
+            rt: rt.into(),
+            rn: rn.id().into(),
+            fixed4: 0b10.into(),
+            s: false,
+            option: 0b011.into(),
+            rm: rm.id().into(),
+            fixed3: true,
+            opc: opc.into(),
+            fixed2: 0b00.into(),
+            v,
+            fixed: 0b111.into(),
+            size: size.into(),
+        }
+    }
+}
+
+#[derive(PackedStruct, Debug)]
+#[packed_struct(endian = "msb")]
+pub struct LoadStorePairIndexed {
+    opc: Integer<u8, packed_bits::Bits<2>>,   // = 0b10, 64 bit
+    fixed: Integer<u8, packed_bits::Bits<3>>, // = 0b101,
+    v: bool,
+    mode: Integer<u8, packed_bits::Bits<3>>, // = 0b001 post-index, 0b011 pre-index
+    l: bool,                                 // load
+    imm7: Integer<u8, packed_bits::Bits<7>>,
+    rt2: Integer<u8, packed_bits::Bits<5>>,
+    rn: Integer<u8, packed_bits::Bits<5>>,
+    rt: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for LoadStorePairIndexed {}
+
+impl LoadStorePairIndexed {
+    /// imm7 is the offset divided by 8.
+    #[inline(always)]
+    fn new(
+        pre_index: bool,
+        load: bool,
+        imm7: i8,
+        rt2: AArch64GeneralReg,
+        rn: AArch64GeneralReg,
+        rt: AArch64GeneralReg,
+    ) -> Self {
+        debug_assert!((-64..64).contains(&imm7));
+
+        Self {
+            rt: rt.id().into(),
+            rn: rn.id().into(),
+            rt2: rt2.id().into(),
+            imm7: ((imm7 as u8) & 0x7F).into(),
+            l: load,
+            mode: if pre_index { 0b011 } else { 0b001 }.into(),
+            v: false,
+            fixed: 0b101.into(),
+            opc: 0b10.into(),
+        }
+    }
+}
+
+#[inline(always)]
+fn float_type(width: FloatWidth) -> u8 {
+    match width {
+        FloatWidth::F32 => 0b00,
+        FloatWidth::F64 => 0b01,
+        FloatWidth::F128 => internal_error!("F128 is not supported"),
+    }
+}
+
+#[derive(PackedStruct)]
+#[packed_struct(endian = "msb")]
+pub struct FloatDataProcessingOneSource {
+    fixed: Integer<u8, packed_bits::Bits<3>>,  // = 0b000,
+    fixed2: Integer<u8, packed_bits::Bits<5>>, // = 0b11110,
+    ftype: Integer<u8, packed_bits::Bits<2>>,
+    fixed3: bool, // = 0b1,
+    opcode: Integer<u8, packed_bits::Bits<6>>,
+    fixed4: Integer<u8, packed_bits::Bits<5>>, // = 0b10000,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for FloatDataProcessingOneSource {}
+
+impl FloatDataProcessingOneSource {
+    /// ftype is the width of the source register.
+    #[inline(always)]
+    fn new(opcode: u8, ftype: u8, rn: AArch64FloatReg, rd: AArch64FloatReg) -> Self {
+        debug_assert!(opcode <= 0b111111);
+        debug_assert!(ftype <= 0b11);
+
+        Self {
+            reg_d: rd.id().into(),
+            reg_n: rn.id().into(),
+            fixed4: 0b10000.into(),
+            opcode: opcode.into(),
+            fixed3: true,
+            ftype: ftype.into(),
+            fixed2: 0b11110.into(),
+            fixed: 0b000.into(),
+        }
+    }
+}
+
+#[derive(PackedStruct)]
+#[packed_struct(endian = "msb")]
+pub struct FloatDataProcessingTwoSource {
+    fixed: Integer<u8, packed_bits::Bits<3>>,  // = 0b000,
+    fixed2: Integer<u8, packed_bits::Bits<5>>, // = 0b11110,
+    ftype: Integer<u8, packed_bits::Bits<2>>,
+    fixed3: bool, // = 0b1,
+    reg_m: Integer<u8, packed_bits::Bits<5>>,
+    opcode: Integer<u8, packed_bits::Bits<4>>,
+    fixed4: Integer<u8, packed_bits::Bits<2>>, // = 0b10,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for FloatDataProcessingTwoSource {}
+
+impl FloatDataProcessingTwoSource {
+    /// opcode = 0b0000 means FMUL
+    /// opcode = 0b0001 means FDIV
+    /// opcode = 0b0010 means FADD
+    /// opcode = 0b0011 means FSUB
+    #[inline(always)]
+    fn new(
+        opcode: u8,
+        width: FloatWidth,
+        rm: AArch64FloatReg,
+        rn: AArch64FloatReg,
+        rd: AArch64FloatReg,
+    ) -> Self {
+        debug_assert!(opcode <= 0b1111);
+
+        Self {
+            reg_d: rd.id().into(),
+            reg_n: rn.id().into(),
+            fixed4: 0b10.into(),
+            opcode: opcode.into(),
+            reg_m: rm.id().into(),
+            fixed3: true,
+            ftype: float_type(width).into(),
+            fixed2: 0b11110.into(),
+            fixed: 0b000.into(),
+        }
+    }
+}
+
+#[derive(PackedStruct)]
+#[packed_struct(endian = "msb")]
+pub struct FloatIntegerConversion {
+    sf: bool,
+    fixed: bool,                               // = 0b0,
+    s: bool,                                   // = 0b0,
+    fixed2: Integer<u8, packed_bits::Bits<5>>, // = 0b11110,
+    ftype: Integer<u8, packed_bits::Bits<2>>,
+    fixed3: bool, // = 0b1,
+    rmode: Integer<u8, packed_bits::Bits<2>>,
+    opcode: Integer<u8, packed_bits::Bits<3>>,
+    fixed4: Integer<u8, packed_bits::Bits<6>>, // = 0b000000,
+    reg_n: Integer<u8, packed_bits::Bits<5>>,
+    reg_d: Integer<u8, packed_bits::Bits<5>>,
+}
+
+impl Aarch64Bytes for FloatIntegerConversion {}
+
+impl FloatIntegerConversion {
+    /// opcode = 0b010 with rmode = 0b00 means SCVTF
+    /// opcode = 0b111 with rmode = 0b00 means FMOV from a general register
+    #[inline(always)]
+    fn new(sf: bool, width: FloatWidth, rmode: u8, opcode: u8, rn: u8, rd: u8) -> Self {
+        debug_assert!(rmode <= 0b11);
+        debug_assert!(opcode <= 0b111);
+
+        Self {
+            reg_d: rd.into(),
+            reg_n: rn.into(),
+            fixed4: 0b000000.into(),
+            opcode: opcode.into(),
+            rmode: rmode.into(),
+            fixed3: true,
+            ftype: float_type(width).into(),
+            fixed2: 0b11110.into(),
+            s: false,
+            fixed: false,
+            sf,
+        }
+    }
+}
+
+// Below here are the functions for all of the assembly instructions.
+// Their names are based on the instruction and operators combined.
+// You should call `buf.reserve()` if you push or extend more than once.
+// Unit tests are added at the bottom of the file to ensure correct asm generation.
+// Please keep these in alphanumeric order.
+// Float instructions take the width of their registers because `Sn` and `Dn` share an encoding.
+
+/// `ADD Xd, Xn, imm12` -> Add Xn and imm12 and place the result into Xd.
+#[inline(always)]
+fn add_reg64_reg64_imm12(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src: AArch64GeneralReg,
+    imm12: u16,
+) {
+    let inst = ArithmeticImmediate::new(false, false, dst, src, imm12, false);
+
+    buf.extend(inst.bytes());
+}
+
+/// `ADD Xd, Xm, Xn` -> Add Xm and Xn and place the result into Xd.
+#[inline(always)]
+fn add_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = ArithmeticShifted::new(false, false, ShiftType::LSL, 0, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `ADD/SUB Xd, Xn, imm12{, LSL #12}` -> Add or subtract the optionally shifted imm12 to Xn and place the result into Xd.
+#[inline(always)]
+fn add_sub_reg64_reg64_imm12(
+    buf: &mut Vec<'_, u8>,
+    sub: bool,
+    dst: AArch64GeneralReg,
+    src: AArch64GeneralReg,
+    imm12: u16,
+    shift: bool,
+) {
+    let inst = ArithmeticImmediate::new(sub, false, dst, src, imm12, shift);
+
+    buf.extend(inst.bytes());
+}
+
+/// `ADRP Xd, imm21` -> Place the address of the 4KB page imm21 pages away from the current one into Xd.
+#[inline(always)]
+fn adrp_reg64_imm21(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, imm21: i32) {
+    let inst = PCRelAddressing::new(true, imm21, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `AND Xd, Xn, Xm` -> Bitwise and Xn and Xm and place the result into Xd.
+#[inline(always)]
+fn and_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst =
+        LogicalShiftedRegister::new(LogicalOp::AND, ShiftType::LSL, 0, src2, src1, dst, true);
+
+    buf.extend(inst.bytes());
+}
+
+/// `ASR Xd, Xn, Xm` -> Arithmetic shift Xn right by Xm and place the result into Xd.
+#[inline(always)]
+fn asr_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = DataProcessingTwoSource::new(0b001010, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `B.cond imm19` -> Jump by offset bytes if the condition holds.
+#[inline(always)]
+fn b_cond_imm19(buf: &mut Vec<'_, u8>, cond: ConditionCode, offset: i32) {
+    let inst = ConditionalBranchImmediate::new(cond, offset);
+
+    buf.extend(inst.bytes());
+}
+
+/// `B imm26` -> Jump by offset bytes.
+#[inline(always)]
+fn b_imm26(buf: &mut Vec<'_, u8>, offset: i32) {
+    let inst = UnconditionalBranchImmediate::new(false, offset);
+
+    buf.extend(inst.bytes());
+}
+
+/// `BL imm26` -> Jump by offset bytes and place the return address into LR.
+#[inline(always)]
+fn bl_imm26(buf: &mut Vec<'_, u8>, offset: i32) {
+    let inst = UnconditionalBranchImmediate::new(true, offset);
+
+    buf.extend(inst.bytes());
+}
+
+/// `CMP Xn, imm12` -> Compare Xn to imm12, setting the flags.
+#[inline(always)]
+fn cmp_reg64_imm12(buf: &mut Vec<'_, u8>, src: AArch64GeneralReg, imm12: u16) {
+    // CMP is equivalent to `SUBS XZR, Xn, imm12` in AARCH64.
+    let inst = ArithmeticImmediate::new(true, true, AArch64GeneralReg::ZRSP, src, imm12, false);
+
+    buf.extend(inst.bytes());
+}
+
+/// `CMP Xn, Xm` -> Compare Xn to Xm, setting the flags.
+#[inline(always)]
+fn cmp_reg64_reg64(buf: &mut Vec<'_, u8>, src1: AArch64GeneralReg, src2: AArch64GeneralReg) {
+    // CMP is equivalent to `SUBS XZR, Xn, Xm` in AARCH64.
+    let inst = ArithmeticShifted::new(
+        true,
+        true,
+        ShiftType::LSL,
+        0,
+        src2,
+        src1,
+        AArch64GeneralReg::ZRSP,
+    );
 
-    #[inline(always)]
-    fn new_store(size: u8, imm12: u16, rn: AArch64GeneralReg, rt: AArch64GeneralReg) -> Self {
-        Self::new(size, 0b00, imm12, rn, rt)
-    }
+    buf.extend(inst.bytes());
 }
 
-// Below here are the functions for all of the assembly instructions.
-// Their names are based on the instruction and operators combined.
-// You should call `buf.reserve()` if you push or extend more than once.
-// Unit tests are added at the bottom of the file to ensure correct asm generation.
-// Please keep these in alphanumeric order.
-
-/// `ADD Xd, Xn, imm12` -> Add Xn and imm12 and place the result into Xd.
+/// `CNEG Xd, Xn, cond` -> Place the negation of Xn into Xd if the condition holds, otherwise Xn.
 #[inline(always)]
-fn add_reg64_reg64_imm12(
+fn cneg_reg64_reg64_cond(
     buf: &mut Vec<'_, u8>,
     dst: AArch64GeneralReg,
     src: AArch64GeneralReg,
-    imm12: u16,
+    cond: ConditionCode,
 ) {
-    let inst = ArithmeticImmediate::new(false, false, dst, src, imm12, false);
+    // CNEG is equivalent to `CSNEG Xd, Xn, Xn, invert(cond)` in AARCH64.
+    let inst = ConditionalSelect::new(true, 0b01, cond.invert(), src, src, dst);
 
     buf.extend(inst.bytes());
 }
 
-/// `ADD Xd, Xm, Xn` -> Add Xm and Xn and place the result into Xd.
+/// `CSET Xd, cond` -> Place 1 into Xd if the condition holds, otherwise 0.
 #[inline(always)]
-fn add_reg64_reg64_reg64(
+fn cset_reg64_cond(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, cond: ConditionCode) {
+    // CSET is equivalent to `CSINC Xd, XZR, XZR, invert(cond)` in AARCH64.
+    let inst = ConditionalSelect::new(
+        false,
+        0b01,
+        cond.invert(),
+        AArch64GeneralReg::ZRSP,
+        AArch64GeneralReg::ZRSP,
+        dst,
+    );
+
+    buf.extend(inst.bytes());
+}
+
+/// `EOR Xd, Xn, Xm` -> Bitwise exclusive or Xn and Xm and place the result into Xd.
+#[inline(always)]
+fn eor_reg64_reg64_reg64(
     buf: &mut Vec<'_, u8>,
     dst: AArch64GeneralReg,
     src1: AArch64GeneralReg,
     src2: AArch64GeneralReg,
 ) {
-    let inst = ArithmeticShifted::new(false, false, ShiftType::LSL, 0, src2, src1, dst);
+    let inst =
+        LogicalShiftedRegister::new(LogicalOp::EOR, ShiftType::LSL, 0, src2, src1, dst, true);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FABS Vd, Vn` -> Place the absolute value of Vn into Vd.
+#[inline(always)]
+fn fabs_freg_freg(
+    buf: &mut Vec<'_, u8>,
+    width: FloatWidth,
+    dst: AArch64FloatReg,
+    src: AArch64FloatReg,
+) {
+    let inst = FloatDataProcessingOneSource::new(0b000001, float_type(width), src, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FADD Vd, Vn, Vm` -> Add Vn and Vm and place the result into Vd.
+#[inline(always)]
+fn fadd_freg_freg_freg(
+    buf: &mut Vec<'_, u8>,
+    width: FloatWidth,
+    dst: AArch64FloatReg,
+    src1: AArch64FloatReg,
+    src2: AArch64FloatReg,
+) {
+    let inst = FloatDataProcessingTwoSource::new(0b0010, width, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FCVT Vd, Vn` -> Convert Vn to the width of Vd and place the result into Vd.
+/// Note: width is the width of the destination.
+#[inline(always)]
+fn fcvt_freg_freg(
+    buf: &mut Vec<'_, u8>,
+    width: FloatWidth,
+    dst: AArch64FloatReg,
+    src: AArch64FloatReg,
+) {
+    // The encoding takes the source type and has the destination type in the opcode.
+    let (opcode, ftype) = match width {
+        FloatWidth::F32 => (0b000100, float_type(FloatWidth::F64)),
+        FloatWidth::F64 => (0b000101, float_type(FloatWidth::F32)),
+        FloatWidth::F128 => internal_error!("F128 is not supported"),
+    };
+    let inst = FloatDataProcessingOneSource::new(opcode, ftype, src, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FDIV Vd, Vn, Vm` -> Divide Vn by Vm and place the result into Vd.
+#[inline(always)]
+fn fdiv_freg_freg_freg(
+    buf: &mut Vec<'_, u8>,
+    width: FloatWidth,
+    dst: AArch64FloatReg,
+    src1: AArch64FloatReg,
+    src2: AArch64FloatReg,
+) {
+    let inst = FloatDataProcessingTwoSource::new(0b0001, width, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FMOV Vd, Vn` -> Move Vn to Vd.
+#[inline(always)]
+fn fmov_freg_freg(
+    buf: &mut Vec<'_, u8>,
+    width: FloatWidth,
+    dst: AArch64FloatReg,
+    src: AArch64FloatReg,
+) {
+    let inst = FloatDataProcessingOneSource::new(0b000000, float_type(width), src, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FMOV Vd, Xn` -> Move the bits of Xn (Wn for single precision) to Vd.
+#[inline(always)]
+fn fmov_freg_reg(
+    buf: &mut Vec<'_, u8>,
+    width: FloatWidth,
+    dst: AArch64FloatReg,
+    src: AArch64GeneralReg,
+) {
+    let sf = matches!(width, FloatWidth::F64);
+    let inst = FloatIntegerConversion::new(sf, width, 0b00, 0b111, src.id(), dst.id());
+
+    buf.extend(inst.bytes());
+}
+
+/// `FMUL Vd, Vn, Vm` -> Multiply Vn and Vm and place the result into Vd.
+#[inline(always)]
+fn fmul_freg_freg_freg(
+    buf: &mut Vec<'_, u8>,
+    width: FloatWidth,
+    dst: AArch64FloatReg,
+    src1: AArch64FloatReg,
+    src2: AArch64FloatReg,
+) {
+    let inst = FloatDataProcessingTwoSource::new(0b0000, width, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `FSUB Vd, Vn, Vm` -> Subtract Vm from Vn and place the result into Vd.
+#[inline(always)]
+fn fsub_freg_freg_freg(
+    buf: &mut Vec<'_, u8>,
+    width: FloatWidth,
+    dst: AArch64FloatReg,
+    src1: AArch64FloatReg,
+    src2: AArch64FloatReg,
+) {
+    let inst = FloatDataProcessingTwoSource::new(0b0011, width, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `LDP Xt1, Xt2, [Xn], #offset` -> Load a pair from Xn, then add offset to Xn. ZRSP is SP.
+/// Note: imm7 is the offset divided by 8.
+#[inline(always)]
+fn ldp_reg64_reg64_reg64_post_imm7(
+    buf: &mut Vec<'_, u8>,
+    dst1: AArch64GeneralReg,
+    dst2: AArch64GeneralReg,
+    base: AArch64GeneralReg,
+    imm7: i8,
+) {
+    let inst = LoadStorePairIndexed::new(false, true, imm7, dst2, base, dst1);
 
     buf.extend(inst.bytes());
 }
 
 /// `LDR Xt, [Xn, #offset]` -> Load Xn + Offset Xt. ZRSP is SP.
 /// Note: imm12 is the offest divided by 8.
+#[allow(dead_code)]
 #[inline(always)]
 fn ldr_reg64_reg64_imm12(
     buf: &mut Vec<'_, u8>,
@@ -1105,7 +2913,92 @@ fn ldr_reg64_reg64_imm12(
     base: AArch64GeneralReg,
     imm12: u16,
 ) {
-    let inst = LoadStoreRegisterImmediate::new_load(0b11, imm12, base, dst);
+    load_store_imm12(buf, LoadStoreOp::Load64, dst.id(), base, imm12);
+}
+
+/// `LDR/STR Rt, [Xn, #offset]` -> Load or store Rt at Xn + Offset. ZRSP is SP.
+/// Note: imm12 is the offest divided by the access size.
+#[inline(always)]
+fn load_store_imm12(
+    buf: &mut Vec<'_, u8>,
+    op: LoadStoreOp,
+    reg: u8,
+    base: AArch64GeneralReg,
+    imm12: u16,
+) {
+    let inst = LoadStoreRegisterImmediate::new(op, imm12, base, reg);
+
+    buf.extend(inst.bytes());
+}
+
+/// `LDUR/STUR Rt, [Xn, #offset]` -> Load or store Rt at Xn + Offset. ZRSP is SP.
+/// Note: imm9 is not scaled.
+#[inline(always)]
+fn load_store_imm9(
+    buf: &mut Vec<'_, u8>,
+    op: LoadStoreOp,
+    reg: u8,
+    base: AArch64GeneralReg,
+    imm9: i16,
+) {
+    let inst = LoadStoreRegisterUnscaled::new(op, imm9, base, reg);
+
+    buf.extend(inst.bytes());
+}
+
+/// `LDR/STR Rt, [Xn, Xm]` -> Load or store Rt at Xn + Xm. ZRSP is SP for Xn.
+#[inline(always)]
+fn load_store_reg64_offset(
+    buf: &mut Vec<'_, u8>,
+    op: LoadStoreOp,
+    reg: u8,
+    base: AArch64GeneralReg,
+    offset: AArch64GeneralReg,
+) {
+    let inst = LoadStoreRegisterOffset::new(op, offset, base, reg);
+
+    buf.extend(inst.bytes());
+}
+
+/// `LSL Xd, Xn, Xm` -> Logical shift Xn left by Xm and place the result into Xd.
+#[inline(always)]
+fn lsl_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = DataProcessingTwoSource::new(0b001000, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `LSR Xd, Xn, Xm` -> Logical shift Xn right by Xm and place the result into Xd.
+#[inline(always)]
+fn lsr_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = DataProcessingTwoSource::new(0b001001, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `MOV Wd, Wm` -> Move Wm to Wd, zeroing the upper 32 bits of Xd.
+#[inline(always)]
+fn mov_reg32_reg32(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, src: AArch64GeneralReg) {
+    // MOV is equvalent to `ORR Wd, WZR, WM` in AARCH64.
+    let inst = LogicalShiftedRegister::new(
+        LogicalOp::ORR,
+        ShiftType::LSL,
+        0,
+        src,
+        AArch64GeneralReg::ZRSP,
+        dst,
+        false,
+    );
 
     buf.extend(inst.bytes());
 }
@@ -1121,6 +3014,7 @@ fn mov_reg64_reg64(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, src: AArch64Ge
         src,
         AArch64GeneralReg::ZRSP,
         dst,
+        true,
     );
 
     buf.extend(inst.bytes());
@@ -1134,6 +3028,14 @@ fn movk_reg64_imm16(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, imm16: u16, h
     buf.extend(inst.bytes());
 }
 
+/// `MOVN Xd, imm16` -> Moves the inverse of an optionally shifted imm16 to Xd.
+#[inline(always)]
+fn movn_reg64_imm16(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, imm16: u16, hw: u8) {
+    let inst = MoveWideImmediate::new(0b00, dst, imm16, hw, true);
+
+    buf.extend(inst.bytes());
+}
+
 /// `MOVZ Xd, imm16` -> Zeros Xd and moves an optionally shifted imm16 to Xd.
 #[inline(always)]
 fn movz_reg64_imm16(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, imm16: u16, hw: u8) {
@@ -1142,29 +3044,167 @@ fn movz_reg64_imm16(buf: &mut Vec<'_, u8>, dst: AArch64GeneralReg, imm16: u16, h
     buf.extend(inst.bytes());
 }
 
-/// `STR Xt, [Xn, #offset]` -> Store Xt to Xn + Offset. ZRSP is SP.
-/// Note: imm12 is the offest divided by 8.
+/// `MSUB Xd, Xn, Xm, Xa` -> Subtract the product of Xn and Xm from Xa and place the result into Xd.
+#[inline(always)]
+fn msub_reg64_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+    src3: AArch64GeneralReg,
+) {
+    let inst = DataProcessingThreeSource::new(true, src2, src3, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `MUL Xd, Xn, Xm` -> Multiply Xn and Xm and place the result into Xd.
+#[inline(always)]
+fn mul_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    // MUL is equivalent to `MADD Xd, Xn, Xm, XZR` in AARCH64.
+    let inst = DataProcessingThreeSource::new(false, src2, AArch64GeneralReg::ZRSP, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `ORR Xd, Xn, Xm` -> Bitwise or Xn and Xm and place the result into Xd.
+#[inline(always)]
+fn orr_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst =
+        LogicalShiftedRegister::new(LogicalOp::ORR, ShiftType::LSL, 0, src2, src1, dst, true);
+
+    buf.extend(inst.bytes());
+}
+
+/// `SCVTF Vd, Xn` -> Convert the signed integer Xn to a float and place the result into Vd.
+#[inline(always)]
+fn scvtf_freg_reg64(
+    buf: &mut Vec<'_, u8>,
+    width: FloatWidth,
+    dst: AArch64FloatReg,
+    src: AArch64GeneralReg,
+) {
+    let inst = FloatIntegerConversion::new(true, width, 0b00, 0b010, src.id(), dst.id());
+
+    buf.extend(inst.bytes());
+}
+
+/// `SDIV Xd, Xn, Xm` -> Divide the signed Xn by Xm and place the result into Xd.
+#[inline(always)]
+fn sdiv_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = DataProcessingTwoSource::new(0b000011, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `STP Xt1, Xt2, [Xn, #offset]!` -> Add offset to Xn, then store a pair to Xn. ZRSP is SP.
+/// Note: imm7 is the offset divided by 8.
+#[inline(always)]
+fn stp_reg64_reg64_reg64_pre_imm7(
+    buf: &mut Vec<'_, u8>,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+    base: AArch64GeneralReg,
+    imm7: i8,
+) {
+    let inst = LoadStorePairIndexed::new(true, false, imm7, src2, base, src1);
+
+    buf.extend(inst.bytes());
+}
+
+/// `STR Xt, [Xn, #offset]` -> Store Xt to Xn + Offset. ZRSP is SP.
+/// Note: imm12 is the offest divided by 8.
+#[allow(dead_code)]
+#[inline(always)]
+fn str_reg64_reg64_imm12(
+    buf: &mut Vec<'_, u8>,
+    src: AArch64GeneralReg,
+    base: AArch64GeneralReg,
+    imm12: u16,
+) {
+    load_store_imm12(buf, LoadStoreOp::Store64, src.id(), base, imm12);
+}
+
+/// `SUB Xd, Xn, imm12` -> Subtract Xn and imm12 and place the result into Xd.
+#[inline(always)]
+fn sub_reg64_reg64_imm12(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src: AArch64GeneralReg,
+    imm12: u16,
+) {
+    let inst = ArithmeticImmediate::new(true, false, dst, src, imm12, false);
+
+    buf.extend(inst.bytes());
+}
+
+/// `SUB Xd, Xn, Xm` -> Subtract Xm from Xn and place the result into Xd.
+#[inline(always)]
+fn sub_reg64_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
+) {
+    let inst = ArithmeticShifted::new(true, false, ShiftType::LSL, 0, src2, src1, dst);
+
+    buf.extend(inst.bytes());
+}
+
+/// `SXTB/SXTH/SXTW Xd, Wn` -> Sign extend the lowest size bytes of Wn into Xd.
+#[inline(always)]
+fn sxt_reg64_reg64(
+    buf: &mut Vec<'_, u8>,
+    dst: AArch64GeneralReg,
+    src: AArch64GeneralReg,
+    size: u8,
+) {
+    debug_assert!(matches!(size, 1 | 2 | 4));
+    // SXT is equivalent to `SBFM Xd, Xn, #0, #(bits - 1)` in AARCH64.
+    let inst = Bitfield::new(0b00, 0, size * 8 - 1, src, dst, true);
+
+    buf.extend(inst.bytes());
+}
+
+/// `UDIV Xd, Xn, Xm` -> Divide the unsigned Xn by Xm and place the result into Xd.
 #[inline(always)]
-fn str_reg64_reg64_imm12(
+fn udiv_reg64_reg64_reg64(
     buf: &mut Vec<'_, u8>,
-    src: AArch64GeneralReg,
-    base: AArch64GeneralReg,
-    imm12: u16,
+    dst: AArch64GeneralReg,
+    src1: AArch64GeneralReg,
+    src2: AArch64GeneralReg,
 ) {
-    let inst = LoadStoreRegisterImmediate::new_store(0b11, imm12, base, src);
+    let inst = DataProcessingTwoSource::new(0b000010, src2, src1, dst);
 
     buf.extend(inst.bytes());
 }
 
-/// `SUB Xd, Xn, imm12` -> Subtract Xn and imm12 and place the result into Xd.
+/// `UXTB/UXTH Wd, Wn` -> Zero extend the lowest size bytes of Wn into Xd.
 #[inline(always)]
-fn sub_reg64_reg64_imm12(
+fn uxt_reg32_reg32(
     buf: &mut Vec<'_, u8>,
     dst: AArch64GeneralReg,
     src: AArch64GeneralReg,
-    imm12: u16,
+    size: u8,
 ) {
-    let inst = ArithmeticImmediate::new(true, false, dst, src, imm12, false);
+    debug_assert!(matches!(size, 1 | 2));
+    // UXT is equivalent to `UBFM Wd, Wn, #0, #(bits - 1)` in AARCH64.
+    let inst = Bitfield::new(0b10, 0, size * 8 - 1, src, dst, false);
 
     buf.extend(inst.bytes());
 }
@@ -1205,6 +3245,26 @@ mod tests {
                 _ => format!("{}", self),
             }
         }
+
+        fn capstone_string_32bit(&self, zrsp_kind: ZRSPKind) -> String {
+            match self {
+                AArch64GeneralReg::ZRSP => match zrsp_kind {
+                    UsesZR => "wzr".to_owned(),
+                    UsesSP => "wsp".to_owned(),
+                },
+                _ => format!("w{}", self.id()),
+            }
+        }
+    }
+
+    impl AArch64FloatReg {
+        fn capstone_string(&self, width: FloatWidth) -> String {
+            match width {
+                FloatWidth::F32 => format!("s{}", self.id()),
+                FloatWidth::F64 => format!("d{}", self.id()),
+                FloatWidth::F128 => format!("q{}", self.id()),
+            }
+        }
     }
 
     const TEST_U16: u16 = 0x1234;
@@ -1246,27 +3306,567 @@ mod tests {
         AArch64GeneralReg::ZRSP,
     ];
 
-    fn setup_capstone_and_arena<T>(
-        arena: &bumpalo::Bump,
-    ) -> (bumpalo::collections::Vec<T>, Capstone) {
-        let buf = bumpalo::vec![in arena];
-        let cs = Capstone::new()
-            .arm64()
-            .mode(arch::arm64::ArchMode::Arm)
-            .detail(true)
-            .build()
-            .expect("Failed to create Capstone object");
-        (buf, cs)
+    const ALL_FLOAT_REGS: &[AArch64FloatReg] = &[
+        AArch64FloatReg::V0,
+        AArch64FloatReg::V1,
+        AArch64FloatReg::V2,
+        AArch64FloatReg::V3,
+        AArch64FloatReg::V4,
+        AArch64FloatReg::V5,
+        AArch64FloatReg::V6,
+        AArch64FloatReg::V7,
+        AArch64FloatReg::V8,
+        AArch64FloatReg::V9,
+        AArch64FloatReg::V10,
+        AArch64FloatReg::V11,
+        AArch64FloatReg::V12,
+        AArch64FloatReg::V13,
+        AArch64FloatReg::V14,
+        AArch64FloatReg::V15,
+        AArch64FloatReg::V16,
+        AArch64FloatReg::V17,
+        AArch64FloatReg::V18,
+        AArch64FloatReg::V19,
+        AArch64FloatReg::V20,
+        AArch64FloatReg::V21,
+        AArch64FloatReg::V22,
+        AArch64FloatReg::V23,
+        AArch64FloatReg::V24,
+        AArch64FloatReg::V25,
+        AArch64FloatReg::V26,
+        AArch64FloatReg::V27,
+        AArch64FloatReg::V28,
+        AArch64FloatReg::V29,
+        AArch64FloatReg::V30,
+        AArch64FloatReg::V31,
+    ];
+
+    const ALL_FLOAT_WIDTHS: &[FloatWidth] = &[FloatWidth::F32, FloatWidth::F64];
+
+    fn setup_capstone_and_arena<T>(
+        arena: &bumpalo::Bump,
+    ) -> (bumpalo::collections::Vec<T>, Capstone) {
+        let buf = bumpalo::vec![in arena];
+        let cs = Capstone::new()
+            .arm64()
+            .mode(arch::arm64::ArchMode::Arm)
+            .detail(true)
+            .build()
+            .expect("Failed to create Capstone object");
+        (buf, cs)
+    }
+
+    #[test]
+    fn test_add_reg64_reg64_reg64() {
+        disassembler_test!(
+            add_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "add {}, {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_add_reg64_reg64_imm12() {
+        disassembler_test!(
+            add_reg64_reg64_imm12,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| format!(
+                "add {}, {}, #0x{:x}",
+                reg1.capstone_string(UsesSP),
+                reg2.capstone_string(UsesSP),
+                imm
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_add_sub_reg64_reg64_imm12() {
+        disassembler_test!(
+            |buf: &mut Vec<'_, u8>, sub, dst, src| add_sub_reg64_reg64_imm12(
+                buf, sub, dst, src, 0x123, true
+            ),
+            |sub, reg1: AArch64GeneralReg, reg2: AArch64GeneralReg| format!(
+                "{} {}, {}, #0x123, lsl #12",
+                if sub { "sub" } else { "add" },
+                reg1.capstone_string(UsesSP),
+                reg2.capstone_string(UsesSP)
+            ),
+            [false, true],
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_add_reg64_reg64_imm32() {
+        disassembler_test!(
+            AArch64Assembler::add_reg64_reg64_imm32,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| match imm {
+                0x10 => format!(
+                    "add {}, {}, #0x10",
+                    reg1.capstone_string(UsesSP),
+                    reg2.capstone_string(UsesSP)
+                ),
+                -0x10 => format!(
+                    "sub {}, {}, #0x10",
+                    reg1.capstone_string(UsesSP),
+                    reg2.capstone_string(UsesSP)
+                ),
+                0x12345 => format!(
+                    "add {}, {}, #0x12, lsl #12\nadd {}, {}, #0x345",
+                    reg1.capstone_string(UsesSP),
+                    reg2.capstone_string(UsesSP),
+                    reg1.capstone_string(UsesSP),
+                    reg1.capstone_string(UsesSP)
+                ),
+                _ => unreachable!(),
+            },
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [0x10, -0x10, 0x12345]
+        );
+    }
+
+    #[test]
+    fn test_adrp_reg64_imm21() {
+        disassembler_test!(
+            adrp_reg64_imm21,
+            |reg1: AArch64GeneralReg, imm| format!(
+                "adrp {}, #0x{:x}",
+                reg1.capstone_string(UsesZR),
+                imm << 12
+            ),
+            ALL_GENERAL_REGS,
+            [0x12]
+        );
+    }
+
+    #[test]
+    fn test_and_reg64_reg64_reg64() {
+        disassembler_test!(
+            and_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "and {}, {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_asr_reg64_reg64_reg64() {
+        disassembler_test!(
+            asr_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "asr {}, {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_b_cond_imm19() {
+        disassembler_test!(
+            b_cond_imm19,
+            |cond, imm| format!(
+                "b.{} #0x{:x}",
+                match cond {
+                    ConditionCode::EQ => "eq",
+                    ConditionCode::NE => "ne",
+                    ConditionCode::LT => "lt",
+                    ConditionCode::GE => "ge",
+                    _ => unreachable!(),
+                },
+                imm
+            ),
+            [
+                ConditionCode::EQ,
+                ConditionCode::NE,
+                ConditionCode::LT,
+                ConditionCode::GE
+            ],
+            [0x10, 0x1234]
+        );
+    }
+
+    #[test]
+    fn test_b_imm26() {
+        disassembler_test!(b_imm26, |imm| format!("b #0x{:x}", imm), [0x10, 0x123458]);
+    }
+
+    #[test]
+    fn test_bl_imm26() {
+        disassembler_test!(bl_imm26, |imm| format!("bl #0x{:x}", imm), [0x10, 0x123458]);
+    }
+
+    #[test]
+    fn test_cmp_reg64_imm12() {
+        disassembler_test!(
+            cmp_reg64_imm12,
+            |reg1: AArch64GeneralReg, imm| format!(
+                "cmp {}, #0x{:x}",
+                reg1.capstone_string(UsesSP),
+                imm
+            ),
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_cmp_reg64_reg64() {
+        disassembler_test!(
+            cmp_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg| format!(
+                "cmp {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_cneg_reg64_reg64_cond() {
+        disassembler_test!(
+            |buf, reg1, reg2| cneg_reg64_reg64_cond(buf, reg1, reg2, ConditionCode::LT),
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg| format!(
+                "cneg {}, {}, lt",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_cset_reg64_cond() {
+        disassembler_test!(
+            cset_reg64_cond,
+            |reg1: AArch64GeneralReg, cond| format!(
+                "cset {}, {}",
+                reg1.capstone_string(UsesZR),
+                match cond {
+                    ConditionCode::EQ => "eq",
+                    ConditionCode::NE => "ne",
+                    ConditionCode::LT => "lt",
+                    ConditionCode::GT => "gt",
+                    ConditionCode::LE => "le",
+                    ConditionCode::GE => "ge",
+                    _ => unreachable!(),
+                }
+            ),
+            ALL_GENERAL_REGS,
+            [
+                ConditionCode::EQ,
+                ConditionCode::NE,
+                ConditionCode::LT,
+                ConditionCode::GT,
+                ConditionCode::LE,
+                ConditionCode::GE
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eor_reg64_reg64_reg64() {
+        disassembler_test!(
+            eor_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "eor {}, {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_fabs_freg_freg() {
+        disassembler_test!(
+            fabs_freg_freg,
+            |width, reg1: AArch64FloatReg, reg2: AArch64FloatReg| format!(
+                "fabs {}, {}",
+                reg1.capstone_string(width),
+                reg2.capstone_string(width)
+            ),
+            ALL_FLOAT_WIDTHS,
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_fadd_freg_freg_freg() {
+        for width in ALL_FLOAT_WIDTHS {
+            disassembler_test!(
+                |buf, reg1, reg2, reg3| fadd_freg_freg_freg(buf, *width, reg1, reg2, reg3),
+                |reg1: AArch64FloatReg, reg2: AArch64FloatReg, reg3: AArch64FloatReg| format!(
+                    "fadd {}, {}, {}",
+                    reg1.capstone_string(*width),
+                    reg2.capstone_string(*width),
+                    reg3.capstone_string(*width)
+                ),
+                ALL_FLOAT_REGS,
+                ALL_FLOAT_REGS,
+                ALL_FLOAT_REGS
+            );
+        }
+    }
+
+    #[test]
+    fn test_fcvt_freg_freg() {
+        disassembler_test!(
+            fcvt_freg_freg,
+            |width, reg1: AArch64FloatReg, reg2: AArch64FloatReg| format!(
+                "fcvt {}, {}",
+                reg1.capstone_string(width),
+                reg2.capstone_string(match width {
+                    FloatWidth::F32 => FloatWidth::F64,
+                    _ => FloatWidth::F32,
+                })
+            ),
+            ALL_FLOAT_WIDTHS,
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_fdiv_freg_freg_freg() {
+        for width in ALL_FLOAT_WIDTHS {
+            disassembler_test!(
+                |buf, reg1, reg2, reg3| fdiv_freg_freg_freg(buf, *width, reg1, reg2, reg3),
+                |reg1: AArch64FloatReg, reg2: AArch64FloatReg, reg3: AArch64FloatReg| format!(
+                    "fdiv {}, {}, {}",
+                    reg1.capstone_string(*width),
+                    reg2.capstone_string(*width),
+                    reg3.capstone_string(*width)
+                ),
+                ALL_FLOAT_REGS,
+                ALL_FLOAT_REGS,
+                ALL_FLOAT_REGS
+            );
+        }
+    }
+
+    #[test]
+    fn test_fmov_freg_freg() {
+        disassembler_test!(
+            fmov_freg_freg,
+            |width, reg1: AArch64FloatReg, reg2: AArch64FloatReg| format!(
+                "fmov {}, {}",
+                reg1.capstone_string(width),
+                reg2.capstone_string(width)
+            ),
+            ALL_FLOAT_WIDTHS,
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_fmov_freg_reg() {
+        disassembler_test!(
+            fmov_freg_reg,
+            |width, reg1: AArch64FloatReg, reg2: AArch64GeneralReg| format!(
+                "fmov {}, {}",
+                reg1.capstone_string(width),
+                match width {
+                    FloatWidth::F32 => reg2.capstone_string_32bit(UsesZR),
+                    _ => reg2.capstone_string(UsesZR),
+                }
+            ),
+            ALL_FLOAT_WIDTHS,
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_fmul_freg_freg_freg() {
+        for width in ALL_FLOAT_WIDTHS {
+            disassembler_test!(
+                |buf, reg1, reg2, reg3| fmul_freg_freg_freg(buf, *width, reg1, reg2, reg3),
+                |reg1: AArch64FloatReg, reg2: AArch64FloatReg, reg3: AArch64FloatReg| format!(
+                    "fmul {}, {}, {}",
+                    reg1.capstone_string(*width),
+                    reg2.capstone_string(*width),
+                    reg3.capstone_string(*width)
+                ),
+                ALL_FLOAT_REGS,
+                ALL_FLOAT_REGS,
+                ALL_FLOAT_REGS
+            );
+        }
+    }
+
+    #[test]
+    fn test_fsub_freg_freg_freg() {
+        for width in ALL_FLOAT_WIDTHS {
+            disassembler_test!(
+                |buf, reg1, reg2, reg3| fsub_freg_freg_freg(buf, *width, reg1, reg2, reg3),
+                |reg1: AArch64FloatReg, reg2: AArch64FloatReg, reg3: AArch64FloatReg| format!(
+                    "fsub {}, {}, {}",
+                    reg1.capstone_string(*width),
+                    reg2.capstone_string(*width),
+                    reg3.capstone_string(*width)
+                ),
+                ALL_FLOAT_REGS,
+                ALL_FLOAT_REGS,
+                ALL_FLOAT_REGS
+            );
+        }
+    }
+
+    #[test]
+    fn test_ldp_reg64_reg64_reg64_post_imm7() {
+        disassembler_test!(
+            |buf, reg1, reg2, imm| ldp_reg64_reg64_reg64_post_imm7(
+                buf,
+                reg1,
+                reg2,
+                AArch64GeneralReg::ZRSP,
+                imm
+            ),
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm: i8| format!(
+                "ldp {}, {}, [sp], #0x{:x}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                imm << 3
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [2]
+        );
+    }
+
+    #[test]
+    fn test_ldr_reg64_reg64_imm12() {
+        disassembler_test!(
+            ldr_reg64_reg64_imm12,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| format!(
+                "ldr {}, [{}, #0x{:x}]",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesSP),
+                imm << 3
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [0x123]
+        );
+    }
+
+    #[test]
+    fn test_load_store_imm12() {
+        disassembler_test!(
+            |buf, op, reg1: AArch64GeneralReg, reg2| load_store_imm12(
+                buf,
+                op,
+                reg1.id(),
+                reg2,
+                0x123
+            ),
+            |op, reg1: AArch64GeneralReg, reg2: AArch64GeneralReg| {
+                let (name, reg, scale) = match op {
+                    LoadStoreOp::Load8 => ("ldrb", reg1.capstone_string_32bit(UsesZR), 1),
+                    LoadStoreOp::LoadSigned16 => ("ldrsh", reg1.capstone_string(UsesZR), 2),
+                    LoadStoreOp::LoadSigned32 => ("ldrsw", reg1.capstone_string(UsesZR), 4),
+                    LoadStoreOp::Store32 => ("str", reg1.capstone_string_32bit(UsesZR), 4),
+                    LoadStoreOp::StoreF64 => ("str", format!("d{}", reg1.id()), 8),
+                    _ => unreachable!(),
+                };
+                format!(
+                    "{} {}, [{}, #0x{:x}]",
+                    name,
+                    reg,
+                    reg2.capstone_string(UsesSP),
+                    0x123 * scale
+                )
+            },
+            [
+                LoadStoreOp::Load8,
+                LoadStoreOp::LoadSigned16,
+                LoadStoreOp::LoadSigned32,
+                LoadStoreOp::Store32,
+                LoadStoreOp::StoreF64
+            ],
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
     }
 
     #[test]
-    fn test_add_reg64_reg64_reg64() {
+    fn test_load_store_imm9() {
         disassembler_test!(
-            add_reg64_reg64_reg64,
+            |buf, op, reg1: AArch64GeneralReg, reg2| load_store_imm9(
+                buf,
+                op,
+                reg1.id(),
+                reg2,
+                -0x10
+            ),
+            |op, reg1: AArch64GeneralReg, reg2: AArch64GeneralReg| {
+                let (name, reg) = match op {
+                    LoadStoreOp::Load64 => ("ldur", reg1.capstone_string(UsesZR)),
+                    LoadStoreOp::Store64 => ("stur", reg1.capstone_string(UsesZR)),
+                    LoadStoreOp::LoadF32 => ("ldur", format!("s{}", reg1.id())),
+                    _ => unreachable!(),
+                };
+                format!(
+                    "{} {}, [{}, #-0x10]",
+                    name,
+                    reg,
+                    reg2.capstone_string(UsesSP)
+                )
+            },
+            [
+                LoadStoreOp::Load64,
+                LoadStoreOp::Store64,
+                LoadStoreOp::LoadF32
+            ],
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_load_store_reg64_offset() {
+        disassembler_test!(
+            |buf, reg1: AArch64GeneralReg, reg2, reg3| load_store_reg64_offset(
+                buf,
+                LoadStoreOp::Load64,
+                reg1.id(),
+                reg2,
+                reg3
+            ),
             |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
-                "add {}, {}, {}",
+                "ldr {}, [{}, {}]",
                 reg1.capstone_string(UsesZR),
-                reg2.capstone_string(UsesZR),
+                reg2.capstone_string(UsesSP),
                 reg3.capstone_string(UsesZR)
             ),
             ALL_GENERAL_REGS,
@@ -1276,34 +3876,90 @@ mod tests {
     }
 
     #[test]
-    fn test_add_reg64_reg64_imm12() {
+    fn test_lsl_reg64_reg64_reg64() {
         disassembler_test!(
-            add_reg64_reg64_imm12,
-            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| format!(
-                "add {}, {}, #0x{:x}",
-                reg1.capstone_string(UsesSP),
-                reg2.capstone_string(UsesSP),
-                imm
+            lsl_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "lsl {}, {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
             ),
             ALL_GENERAL_REGS,
             ALL_GENERAL_REGS,
-            [0x123]
+            ALL_GENERAL_REGS
         );
     }
 
     #[test]
-    fn test_ldr_reg64_reg64_imm12() {
+    fn test_lsr_reg64_reg64_reg64() {
         disassembler_test!(
-            ldr_reg64_reg64_imm12,
-            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm| format!(
-                "ldr {}, [{}, #0x{:x}]",
+            lsr_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "lsr {}, {}, {}",
                 reg1.capstone_string(UsesZR),
-                reg2.capstone_string(UsesSP),
-                imm << 3
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
             ),
             ALL_GENERAL_REGS,
             ALL_GENERAL_REGS,
-            [0x123]
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_mov_reg32_reg32() {
+        disassembler_test!(
+            mov_reg32_reg32,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg| format!(
+                "mov {}, {}",
+                reg1.capstone_string_32bit(UsesZR),
+                reg2.capstone_string_32bit(UsesZR),
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_mov_reg64_base32() {
+        disassembler_test!(
+            AArch64Assembler::mov_reg64_base32,
+            |reg1: AArch64GeneralReg, offset| match offset {
+                0x10 => format!("ldr {}, [x29, #0x10]", reg1.capstone_string(UsesZR)),
+                -0x10 => format!("ldur {}, [x29, #-0x10]", reg1.capstone_string(UsesZR)),
+                -0x1234 => format!(
+                    "mov x16, #-0x1234\nldr {}, [x29, x16]",
+                    reg1.capstone_string(UsesZR)
+                ),
+                _ => unreachable!(),
+            },
+            ALL_GENERAL_REGS,
+            [0x10, -0x10, -0x1234]
+        );
+    }
+
+    #[test]
+    fn test_mov_base32_freg64() {
+        disassembler_test!(
+            AArch64Assembler::mov_base32_freg64,
+            |offset, reg1: AArch64FloatReg| match offset {
+                0x10 => format!(
+                    "str {}, [x29, #0x10]",
+                    reg1.capstone_string(FloatWidth::F64)
+                ),
+                -0x10 => format!(
+                    "stur {}, [x29, #-0x10]",
+                    reg1.capstone_string(FloatWidth::F64)
+                ),
+                -0x1234 => format!(
+                    "mov x16, #-0x1234\nstr {}, [x29, x16]",
+                    reg1.capstone_string(FloatWidth::F64)
+                ),
+                _ => unreachable!(),
+            },
+            [0x10, -0x10, -0x1234],
+            ALL_FLOAT_REGS
         );
     }
 
@@ -1341,6 +3997,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_movn_reg64_imm16() {
+        disassembler_test!(
+            movn_reg64_imm16,
+            |reg1: AArch64GeneralReg, imm, hw| format!(
+                "mov {}, #-0x{:x}",
+                reg1.capstone_string(UsesZR),
+                ((imm as i64) << (hw * 16)) + 1
+            ),
+            ALL_GENERAL_REGS,
+            [TEST_U16],
+            [0, 1, 2]
+        );
+    }
+
     #[test]
     fn test_movz_reg64_imm16() {
         disassembler_test!(
@@ -1357,6 +4028,123 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_msub_reg64_reg64_reg64_reg64() {
+        disassembler_test!(
+            |buf, reg1, reg2, reg3| msub_reg64_reg64_reg64_reg64(
+                buf,
+                reg1,
+                reg2,
+                reg3,
+                AArch64GeneralReg::X3
+            ),
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "msub {}, {}, {}, x3",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_mul_reg64_reg64_reg64() {
+        disassembler_test!(
+            mul_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "mul {}, {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_orr_reg64_reg64_reg64() {
+        disassembler_test!(
+            orr_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| {
+                if reg2 == AArch64GeneralReg::ZRSP {
+                    format!(
+                        "mov {}, {}",
+                        reg1.capstone_string(UsesZR),
+                        reg3.capstone_string(UsesZR)
+                    )
+                } else {
+                    format!(
+                        "orr {}, {}, {}",
+                        reg1.capstone_string(UsesZR),
+                        reg2.capstone_string(UsesZR),
+                        reg3.capstone_string(UsesZR)
+                    )
+                }
+            },
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_scvtf_freg_reg64() {
+        disassembler_test!(
+            scvtf_freg_reg64,
+            |width, reg1: AArch64FloatReg, reg2: AArch64GeneralReg| format!(
+                "scvtf {}, {}",
+                reg1.capstone_string(width),
+                reg2.capstone_string(UsesZR)
+            ),
+            ALL_FLOAT_WIDTHS,
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_sdiv_reg64_reg64_reg64() {
+        disassembler_test!(
+            sdiv_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "sdiv {}, {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_stp_reg64_reg64_reg64_pre_imm7() {
+        disassembler_test!(
+            |buf, reg1, reg2, imm| stp_reg64_reg64_reg64_pre_imm7(
+                buf,
+                reg1,
+                reg2,
+                AArch64GeneralReg::ZRSP,
+                imm
+            ),
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, imm: i8| format!(
+                "stp {}, {}, [sp, #-0x{:x}]!",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                -(imm as i32) << 3
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [-2]
+        );
+    }
+
     #[test]
     fn test_str_reg64_reg64_imm12() {
         disassembler_test!(
@@ -1389,6 +4177,84 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sub_reg64_reg64_reg64() {
+        disassembler_test!(
+            sub_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| {
+                if reg2 == AArch64GeneralReg::ZRSP {
+                    format!(
+                        "neg {}, {}",
+                        reg1.capstone_string(UsesZR),
+                        reg3.capstone_string(UsesZR)
+                    )
+                } else {
+                    format!(
+                        "sub {}, {}, {}",
+                        reg1.capstone_string(UsesZR),
+                        reg2.capstone_string(UsesZR),
+                        reg3.capstone_string(UsesZR)
+                    )
+                }
+            },
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_sxt_reg64_reg64() {
+        disassembler_test!(
+            sxt_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, size| format!(
+                "sxt{} {}, {}",
+                match size {
+                    1 => "b",
+                    2 => "h",
+                    _ => "w",
+                },
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string_32bit(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [1, 2, 4]
+        );
+    }
+
+    #[test]
+    fn test_udiv_reg64_reg64_reg64() {
+        disassembler_test!(
+            udiv_reg64_reg64_reg64,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, reg3: AArch64GeneralReg| format!(
+                "udiv {}, {}, {}",
+                reg1.capstone_string(UsesZR),
+                reg2.capstone_string(UsesZR),
+                reg3.capstone_string(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_uxt_reg32_reg32() {
+        disassembler_test!(
+            uxt_reg32_reg32,
+            |reg1: AArch64GeneralReg, reg2: AArch64GeneralReg, size| format!(
+                "uxt{} {}, {}",
+                if size == 1 { "b" } else { "h" },
+                reg1.capstone_string_32bit(UsesZR),
+                reg2.capstone_string_32bit(UsesZR)
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [1, 2]
+        );
+    }
+
     #[test]
     fn test_ret_reg64() {
         disassembler_test!(
@@ -1401,4 +4267,152 @@ mod tests {
             ALL_GENERAL_REGS
         );
     }
+
+    fn test_env<'a>(arena: &'a bumpalo::Bump) -> crate::Env<'a> {
+        crate::Env {
+            arena,
+            module_id: roc_module::symbol::ModuleId::NUM,
+            exposed_to_host: Default::default(),
+            lazy_literals: false,
+            generate_allocators: false,
+        }
+    }
+
+    #[test]
+    fn test_store_args_u8_i128() {
+        // A call to the C function `f(uint8_t, __int128)` passes the u8 in x0.
+        // The i128 skips x1 and goes in x2 and x3, because it has to start at an even register.
+        let arena = bumpalo::Bump::new();
+        let env = test_env(&arena);
+        let mut storage_manager = crate::generic64::storage::new_storage_manager::<
+            AArch64GeneralReg,
+            AArch64FloatReg,
+            AArch64Assembler,
+            AArch64Call,
+        >(&env, TARGET_INFO);
+        storage_manager.reset();
+        let mut buf = bumpalo::vec![in &arena];
+
+        let small = Symbol::DEV_TMP;
+        let big = Symbol::DEV_TMP2;
+        storage_manager.primitive_stack_arg(&small, 16);
+        storage_manager.claim_stack_area(&big, 16);
+        let (big_offset, _) = storage_manager.stack_offset_and_size(&big);
+
+        AArch64Call::store_args(
+            &mut buf,
+            &mut storage_manager,
+            &Symbol::DEV_TMP3,
+            &[small, big],
+            &[
+                Layout::Builtin(Builtin::Int(IntWidth::U8)),
+                Layout::Builtin(Builtin::Int(IntWidth::I128)),
+            ],
+            &Layout::Builtin(Builtin::Int(IntWidth::I64)),
+        );
+
+        let mut expected = bumpalo::vec![in &arena];
+        AArch64Assembler::mov_reg64_base32(&mut expected, AArch64GeneralReg::X0, 16);
+        AArch64Assembler::mov_reg64_base32(&mut expected, AArch64GeneralReg::X2, big_offset);
+        AArch64Assembler::mov_reg64_base32(&mut expected, AArch64GeneralReg::X3, big_offset + 8);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_load_args_u8_i128() {
+        let arena = bumpalo::Bump::new();
+        let env = test_env(&arena);
+        let mut storage_manager = crate::generic64::storage::new_storage_manager::<
+            AArch64GeneralReg,
+            AArch64FloatReg,
+            AArch64Assembler,
+            AArch64Call,
+        >(&env, TARGET_INFO);
+        storage_manager.reset();
+        let mut buf = bumpalo::vec![in &arena];
+
+        let small = Symbol::DEV_TMP;
+        let big = Symbol::DEV_TMP2;
+        let args = arena.alloc([
+            (Layout::Builtin(Builtin::Int(IntWidth::U8)), small),
+            (Layout::Builtin(Builtin::Int(IntWidth::I128)), big),
+        ]);
+        AArch64Call::load_args(
+            &mut buf,
+            &mut storage_manager,
+            args,
+            &Layout::Builtin(Builtin::Int(IntWidth::I64)),
+        );
+        let (big_offset, _) = storage_manager.stack_offset_and_size(&big);
+
+        let mut expected = bumpalo::vec![in &arena];
+        AArch64Assembler::mov_base32_reg64(&mut expected, big_offset, AArch64GeneralReg::X2);
+        AArch64Assembler::mov_base32_reg64(&mut expected, big_offset + 8, AArch64GeneralReg::X3);
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_stack_arg_slots() {
+        let u8_layout = Layout::Builtin(Builtin::Int(IntWidth::U8));
+        let u16_layout = Layout::Builtin(Builtin::Int(IntWidth::U16));
+        let f32_layout = Layout::Builtin(Builtin::Float(FloatWidth::F32));
+        let i128_layout = Layout::Builtin(Builtin::Int(IntWidth::I128));
+
+        // AAPCS64 gives every argument at least an 8 byte slot.
+        assert_eq!(AArch64Call::stack_arg_slot(0, &u8_layout), (0, 8));
+        assert_eq!(AArch64Call::stack_arg_slot(8, &u8_layout), (8, 16));
+        assert_eq!(AArch64Call::stack_arg_slot(16, &f32_layout), (16, 24));
+        assert_eq!(AArch64Call::stack_arg_slot(8, &i128_layout), (16, 32));
+
+        // Apple packs them to their natural size and alignment.
+        assert_eq!(AArch64AppleCall::stack_arg_slot(0, &u8_layout), (0, 1));
+        assert_eq!(AArch64AppleCall::stack_arg_slot(1, &u8_layout), (1, 2));
+        assert_eq!(AArch64AppleCall::stack_arg_slot(1, &u16_layout), (2, 4));
+        assert_eq!(AArch64AppleCall::stack_arg_slot(5, &f32_layout), (8, 12));
+        assert_eq!(AArch64AppleCall::stack_arg_slot(2, &i128_layout), (16, 32));
+    }
+
+    #[test]
+    fn test_store_args_many_u8() {
+        // With more than 8 small arguments, the rest go on the stack.
+        fn call_stack_size<CC>() -> u32
+        where
+            CC: CallConv<AArch64GeneralReg, AArch64FloatReg, AArch64Assembler>,
+        {
+            let arena = bumpalo::Bump::new();
+            let env = test_env(&arena);
+            let mut storage_manager = crate::generic64::storage::new_storage_manager::<
+                AArch64GeneralReg,
+                AArch64FloatReg,
+                AArch64Assembler,
+                CC,
+            >(&env, TARGET_INFO);
+            storage_manager.reset();
+            let mut buf = bumpalo::vec![in &arena];
+
+            let mut ident_ids = roc_module::symbol::IdentIds::default();
+            let args: std::vec::Vec<Symbol> = (0..10)
+                .map(|i| {
+                    let ident_id = ident_ids.add_str(&format!("arg{}", i));
+                    Symbol::new(roc_module::symbol::ModuleId::NUM, ident_id)
+                })
+                .collect();
+            for (i, sym) in args.iter().enumerate() {
+                storage_manager.primitive_stack_arg(sym, 16 + 8 * i as i32);
+            }
+            let layouts = [Layout::Builtin(Builtin::Int(IntWidth::U8)); 10];
+            CC::store_args(
+                &mut buf,
+                &mut storage_manager,
+                &Symbol::DEV_TMP,
+                &args,
+                &layouts,
+                &Layout::Builtin(Builtin::Int(IntWidth::U8)),
+            );
+            storage_manager.fn_call_stack_size()
+        }
+
+        assert_eq!(call_stack_size::<AArch64Call>(), 16);
+        assert_eq!(call_stack_size::<AArch64AppleCall>(), 2);
+    }
 }
@@ -1,17 +1,19 @@
 use crate::{
-    single_register_floats, single_register_int_builtins, single_register_integers, Backend, Env,
-    Relocation,
+    sign_extended_int_builtins, single_register_floats, single_register_int_builtins,
+    single_register_integers, Backend, Env, PassedProc, Relocation, WrapperKind, WrapperProc,
 };
-use bumpalo::collections::Vec;
+use bumpalo::{collections::Vec, Bump};
 use roc_builtins::bitcode::{self, FloatWidth, IntWidth};
 use roc_collections::all::MutMap;
 use roc_error_macros::internal_error;
 use roc_module::symbol::{Interns, Symbol};
-use roc_mono::code_gen_help::CodeGenHelp;
+use roc_mono::code_gen_help::{CodeGenHelp, HelperOp};
 use roc_mono::ir::{
-    BranchInfo, JoinPointId, ListLiteralElement, Literal, Param, ProcLayout, SelfRecursive, Stmt,
+    BranchInfo, HigherOrderLowLevel, JoinPointId, ListLiteralElement, Literal, Param,
+    PassedFunction, ProcLayout, SelfRecursive, Stmt,
 };
-use roc_mono::layout::{Builtin, Layout, TagIdIntType, UnionLayout};
+use roc_mono::layout::{Builtin, Layout, LayoutIds, TagIdIntType, UnionLayout};
+use roc_mono::low_level::HigherOrder;
 use roc_target::TargetInfo;
 use std::marker::PhantomData;
 
@@ -24,6 +26,12 @@ pub(crate) mod x86_64;
 use storage::StorageManager;
 
 const REFCOUNT_ONE: u64 = i64::MIN as u64;
+const BOOL_LAYOUT: Layout<'static> = Layout::Builtin(Builtin::Bool);
+const U8_LAYOUT: Layout<'static> = Layout::Builtin(Builtin::Int(IntWidth::U8));
+const U32_LAYOUT: Layout<'static> = Layout::Builtin(Builtin::Int(IntWidth::U32));
+const U64_LAYOUT: Layout<'static> = Layout::Builtin(Builtin::Int(IntWidth::U64));
+/// The bits of a recursive union pointer that can hold its tag id, like in the llvm backend.
+const TAG_ID_POINTER_MASK: i64 = 0b111;
 // TODO: on all number functions double check and deal with over/underflow.
 
 pub trait CallConv<GeneralReg: RegTrait, FloatReg: RegTrait, ASM: Assembler<GeneralReg, FloatReg>>:
@@ -143,8 +151,23 @@ pub trait Assembler<GeneralReg: RegTrait, FloatReg: RegTrait>: Sized + Copy {
         src2: GeneralReg,
     );
 
+    fn and_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
     fn call(buf: &mut Vec<'_, u8>, relocs: &mut Vec<'_, Relocation>, fn_name: String);
 
+    /// Loads the address of the function `fn_name` into `dst`.
+    fn function_pointer(
+        buf: &mut Vec<'_, u8>,
+        relocs: &mut Vec<'_, Relocation>,
+        fn_name: String,
+        dst: GeneralReg,
+    );
+
     /// Jumps by an offset of offset bytes unconditionally.
     /// It should always generate the same number of bytes to enable replacement if offset changes.
     /// It returns the base offset to calculate the jump from (generally the instruction after the jump).
@@ -197,6 +220,59 @@ pub trait Assembler<GeneralReg: RegTrait, FloatReg: RegTrait>: Sized + Copy {
         src: GeneralReg,
     );
 
+    /// Sign extends the data at `src + offset` with `size` as it copies it to `dst`
+    /// size must be less than or equal to 8.
+    fn movsx_reg64_mem64_offset32(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src: GeneralReg,
+        offset: i32,
+        size: u8,
+    );
+    /// Zero extends the data at `src + offset` with `size` as it copies it to `dst`
+    /// size must be less than or equal to 8.
+    fn movzx_reg64_mem64_offset32(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src: GeneralReg,
+        offset: i32,
+        size: u8,
+    );
+    /// Copies the lowest `size` bytes of `src` to `dst + offset`
+    /// size must be less than or equal to 8.
+    fn mov_sized_mem64_offset32_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        offset: i32,
+        src: GeneralReg,
+        size: u8,
+    );
+
+    fn mov_freg64_mem64_offset32(
+        buf: &mut Vec<'_, u8>,
+        dst: FloatReg,
+        src: GeneralReg,
+        offset: i32,
+    );
+    fn mov_mem64_offset32_freg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        offset: i32,
+        src: FloatReg,
+    );
+    fn mov_freg32_mem32_offset32(
+        buf: &mut Vec<'_, u8>,
+        dst: FloatReg,
+        src: GeneralReg,
+        offset: i32,
+    );
+    fn mov_mem32_offset32_freg32(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        offset: i32,
+        src: FloatReg,
+    );
+
     /// Sign extends the data at `offset` with `size` as it copies it to `dst`
     /// size must be less than or equal to 8.
     fn movsx_reg64_base32(buf: &mut Vec<'_, u8>, dst: GeneralReg, offset: i32, size: u8);
@@ -204,6 +280,13 @@ pub trait Assembler<GeneralReg: RegTrait, FloatReg: RegTrait>: Sized + Copy {
     /// size must be less than or equal to 8.
     fn movzx_reg64_base32(buf: &mut Vec<'_, u8>, dst: GeneralReg, offset: i32, size: u8);
 
+    /// Sign extends the lowest `size` bytes of `src` as it copies them to `dst`
+    /// size must be less than or equal to 8.
+    fn movsx_reg64_reg64(buf: &mut Vec<'_, u8>, dst: GeneralReg, src: GeneralReg, size: u8);
+    /// Zero extends the lowest `size` bytes of `src` as it copies them to `dst`
+    /// size must be less than or equal to 8.
+    fn movzx_reg64_reg64(buf: &mut Vec<'_, u8>, dst: GeneralReg, src: GeneralReg, size: u8);
+
     fn mov_freg64_stack32(buf: &mut Vec<'_, u8>, dst: FloatReg, offset: i32);
     fn mov_reg64_stack32(buf: &mut Vec<'_, u8>, dst: GeneralReg, offset: i32);
     fn mov_stack32_freg64(buf: &mut Vec<'_, u8>, offset: i32, src: FloatReg);
@@ -217,6 +300,114 @@ pub trait Assembler<GeneralReg: RegTrait, FloatReg: RegTrait>: Sized + Copy {
         src2: GeneralReg,
     );
 
+    fn or_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
+    fn xor_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
+    /// Shifts `src1` left by `src2` bits.
+    fn shl_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
+    /// Shifts `src1` right by `src2` bits, filling with zeros.
+    fn shr_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
+    /// Shifts `src1` right by `src2` bits, filling with the sign bit.
+    fn sar_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
+    fn sub_freg32_freg32_freg32(
+        buf: &mut Vec<'_, u8>,
+        dst: FloatReg,
+        src1: FloatReg,
+        src2: FloatReg,
+    );
+
+    fn sub_freg64_freg64_freg64(
+        buf: &mut Vec<'_, u8>,
+        dst: FloatReg,
+        src1: FloatReg,
+        src2: FloatReg,
+    );
+
+    fn mul_freg32_freg32_freg32(
+        buf: &mut Vec<'_, u8>,
+        dst: FloatReg,
+        src1: FloatReg,
+        src2: FloatReg,
+    );
+
+    fn mul_freg64_freg64_freg64(
+        buf: &mut Vec<'_, u8>,
+        dst: FloatReg,
+        src1: FloatReg,
+        src2: FloatReg,
+    );
+
+    fn div_freg32_freg32_freg32(
+        buf: &mut Vec<'_, u8>,
+        dst: FloatReg,
+        src1: FloatReg,
+        src2: FloatReg,
+    );
+
+    fn div_freg64_freg64_freg64(
+        buf: &mut Vec<'_, u8>,
+        dst: FloatReg,
+        src1: FloatReg,
+        src2: FloatReg,
+    );
+
+    fn idiv_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
+    fn udiv_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
+    fn irem_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
+    fn urem_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
     fn sub_reg64_reg64_imm32(buf: &mut Vec<'_, u8>, dst: GeneralReg, src1: GeneralReg, imm32: i32);
     fn sub_reg64_reg64_reg64(
         buf: &mut Vec<'_, u8>,
@@ -246,6 +437,13 @@ pub trait Assembler<GeneralReg: RegTrait, FloatReg: RegTrait>: Sized + Copy {
         src2: GeneralReg,
     );
 
+    fn gt_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: GeneralReg,
+        src1: GeneralReg,
+        src2: GeneralReg,
+    );
+
     fn to_float_freg32_reg64(buf: &mut Vec<'_, u8>, dst: FloatReg, src: GeneralReg);
 
     fn to_float_freg64_reg64(buf: &mut Vec<'_, u8>, dst: FloatReg, src: GeneralReg);
@@ -292,6 +490,7 @@ pub struct Backend64Bit<
     interns: &'a mut Interns,
     helper_proc_gen: CodeGenHelp<'a>,
     helper_proc_symbols: Vec<'a, (Symbol, ProcLayout<'a>)>,
+    wrapper_procs: Vec<'a, WrapperProc<'a>>,
     buf: Vec<'a, u8>,
     relocs: Vec<'a, Relocation>,
     proc_name: Option<String>,
@@ -326,6 +525,7 @@ pub fn new_backend_64bit<
         interns,
         helper_proc_gen: CodeGenHelp::new(env.arena, target_info, env.module_id),
         helper_proc_symbols: bumpalo::vec![in env.arena],
+        wrapper_procs: bumpalo::vec![in env.arena],
         proc_name: None,
         is_self_recursive: None,
         buf: bumpalo::vec![in env.arena],
@@ -365,6 +565,106 @@ impl<
     fn helper_proc_symbols(&self) -> &Vec<'a, (Symbol, ProcLayout<'a>)> {
         &self.helper_proc_symbols
     }
+    fn wrapper_procs_mut(&mut self) -> &mut Vec<'a, WrapperProc<'a>> {
+        &mut self.wrapper_procs
+    }
+    fn wrapper_procs(&self) -> &Vec<'a, WrapperProc<'a>> {
+        &self.wrapper_procs
+    }
+
+    fn build_wrapper_proc(&mut self, wrapper: &WrapperProc<'a>) -> (Vec<u8>, Vec<Relocation>) {
+        self.reset(wrapper.name.clone(), SelfRecursive::NotSelfRecursive);
+        let arena = self.env.arena;
+        match &wrapper.kind {
+            WrapperKind::Mapper(passed_proc) | WrapperKind::Compare(passed_proc) => {
+                let is_mapper = matches!(wrapper.kind, WrapperKind::Mapper(_));
+                let value_count =
+                    passed_proc.arg_layouts.len() - passed_proc.closure_layout.is_some() as usize;
+
+                // The closure data pointer comes first, then a pointer per value, then the output pointer of a mapper.
+                let ptr_count = 1 + value_count + is_mapper as usize;
+                let mut params = Vec::with_capacity_in(ptr_count, arena);
+                for _ in 0..ptr_count {
+                    params.push((U64_LAYOUT, self.unique_symbol()));
+                }
+                let params = params.into_bump_slice();
+                let ret_layout = if is_mapper {
+                    Layout::UNIT
+                } else {
+                    passed_proc.ret_layout
+                };
+                self.load_args(params, &ret_layout);
+
+                let mut args = Vec::with_capacity_in(passed_proc.arg_layouts.len(), arena);
+                for (i, layout) in passed_proc.arg_layouts.iter().enumerate() {
+                    // The closure data is the last argument of the passed proc.
+                    let (_, ptr) = if i < value_count {
+                        params[1 + i]
+                    } else {
+                        params[0]
+                    };
+                    let value = self.unique_symbol();
+                    self.expr_unbox(&value, &ptr, layout);
+                    args.push(value);
+                }
+
+                let result = self.unique_symbol();
+                self.build_fn_call(
+                    &result,
+                    passed_proc.name.clone(),
+                    &args,
+                    passed_proc.arg_layouts,
+                    &passed_proc.ret_layout,
+                );
+                if is_mapper {
+                    let (_, out) = params[ptr_count - 1];
+                    self.storage_manager.with_tmp_general_reg(
+                        &mut self.buf,
+                        |storage_manager, buf, ptr_reg| {
+                            storage_manager.load_to_specified_general_reg(buf, &out, ptr_reg);
+                            storage_manager.copy_symbol_to_ptr(
+                                buf,
+                                ptr_reg,
+                                0,
+                                &result,
+                                &passed_proc.ret_layout,
+                            );
+                        },
+                    );
+                } else {
+                    self.return_symbol(&result, &passed_proc.ret_layout);
+                }
+            }
+            WrapperKind::IncN { inc_proc, layout } => {
+                let data = self.unique_symbol();
+                let count = self.unique_symbol();
+                let params = arena.alloc([(U64_LAYOUT, data), (U64_LAYOUT, count)]);
+                self.load_args(params, &Layout::UNIT);
+                if let Some(inc_proc) = inc_proc {
+                    let value = self.unique_symbol();
+                    self.expr_unbox(&value, &data, layout);
+                    let unit = self.unique_symbol();
+                    self.build_fn_call(
+                        &unit,
+                        inc_proc.clone(),
+                        &[value, count],
+                        &[*layout, U64_LAYOUT],
+                        &Layout::UNIT,
+                    );
+                }
+            }
+            WrapperKind::Dec { dec_proc, layout } => {
+                let elem_ptr = self.unique_symbol();
+                let params = arena.alloc([(U64_LAYOUT, elem_ptr)]);
+                self.load_args(params, &Layout::UNIT);
+                let value = self.unique_symbol();
+                self.expr_unbox(&value, &elem_ptr, layout);
+                let unit = self.unique_symbol();
+                self.build_fn_call(&unit, dec_proc.clone(), &[value], &[*layout], &Layout::UNIT);
+            }
+        }
+        self.finalize()
+    }
 
     fn reset(&mut self, name: String, is_self_recursive: SelfRecursive) {
         self.proc_name = Some(name);
@@ -536,8 +836,11 @@ impl<
         // move return value to dst.
         match ret_layout {
             single_register_integers!() => {
+                // Only the bits of the return register that the layout covers are defined.
+                let size = ret_layout.stack_size(self.storage_manager.target_info()) as u8;
+                let signed = matches!(ret_layout, Layout::Builtin(sign_extended_int_builtins!()));
                 let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
-                ASM::mov_reg64_reg64(&mut self.buf, dst_reg, CC::GENERAL_RETURN_REGS[0]);
+                self.extend_int_reg(dst_reg, CC::GENERAL_RETURN_REGS[0], size, signed);
             }
             single_register_floats!() => {
                 let dst_reg = self.storage_manager.claim_float_reg(&mut self.buf, dst);
@@ -746,6 +1049,18 @@ impl<
                     .load_to_general_reg(&mut self.buf, src2);
                 ASM::imul_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
             }
+            Layout::Builtin(Builtin::Float(FloatWidth::F64)) => {
+                let dst_reg = self.storage_manager.claim_float_reg(&mut self.buf, dst);
+                let src1_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src1);
+                let src2_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src2);
+                ASM::mul_freg64_freg64_freg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+            }
+            Layout::Builtin(Builtin::Float(FloatWidth::F32)) => {
+                let dst_reg = self.storage_manager.claim_float_reg(&mut self.buf, dst);
+                let src1_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src1);
+                let src2_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src2);
+                ASM::mul_freg32_freg32_freg32(&mut self.buf, dst_reg, src1_reg, src2_reg);
+            }
             x => todo!("NumMul: layout, {:?}", x),
         }
     }
@@ -773,13 +1088,25 @@ impl<
                     .load_to_general_reg(&mut self.buf, src2);
                 ASM::sub_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
             }
+            Layout::Builtin(Builtin::Float(FloatWidth::F64)) => {
+                let dst_reg = self.storage_manager.claim_float_reg(&mut self.buf, dst);
+                let src1_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src1);
+                let src2_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src2);
+                ASM::sub_freg64_freg64_freg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+            }
+            Layout::Builtin(Builtin::Float(FloatWidth::F32)) => {
+                let dst_reg = self.storage_manager.claim_float_reg(&mut self.buf, dst);
+                let src1_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src1);
+                let src2_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src2);
+                ASM::sub_freg32_freg32_freg32(&mut self.buf, dst_reg, src1_reg, src2_reg);
+            }
             x => todo!("NumSub: layout, {:?}", x),
         }
     }
 
     fn build_eq(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, arg_layout: &Layout<'a>) {
         match arg_layout {
-            Layout::Builtin(single_register_int_builtins!()) => {
+            Layout::Builtin(Builtin::Bool | single_register_int_builtins!()) => {
                 let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
                 let src1_reg = self
                     .storage_manager
@@ -789,6 +1116,54 @@ impl<
                     .load_to_general_reg(&mut self.buf, src2);
                 ASM::eq_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
             }
+            Layout::Builtin(Builtin::Int(IntWidth::I128 | IntWidth::U128) | Builtin::Decimal) => {
+                // The values are equal if both of their halves are equal.
+                let (src1_offset, _) = self.storage_manager.stack_offset_and_size(src1);
+                let (src2_offset, _) = self.storage_manager.stack_offset_and_size(src2);
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                self.storage_manager.with_tmp_general_reg(
+                    &mut self.buf,
+                    |storage_manager, buf, tmp_reg1| {
+                        storage_manager.with_tmp_general_reg(
+                            buf,
+                            |_storage_manager, buf, tmp_reg2| {
+                                ASM::mov_reg64_base32(buf, tmp_reg1, src1_offset);
+                                ASM::mov_reg64_base32(buf, tmp_reg2, src2_offset);
+                                ASM::eq_reg64_reg64_reg64(buf, dst_reg, tmp_reg1, tmp_reg2);
+                                ASM::mov_reg64_base32(buf, tmp_reg1, src1_offset + 8);
+                                ASM::mov_reg64_base32(buf, tmp_reg2, src2_offset + 8);
+                                ASM::eq_reg64_reg64_reg64(buf, tmp_reg1, tmp_reg1, tmp_reg2);
+                                ASM::and_reg64_reg64_reg64(buf, dst_reg, dst_reg, tmp_reg1);
+                            },
+                        );
+                    },
+                );
+            }
+            Layout::Builtin(Builtin::Str) => {
+                self.build_fn_call(
+                    dst,
+                    bitcode::STR_EQUAL.to_string(),
+                    &[*src1, *src2],
+                    &[*arg_layout, *arg_layout],
+                    &BOOL_LAYOUT,
+                );
+            }
+            Layout::Builtin(Builtin::List(_))
+            | Layout::Struct { .. }
+            | Layout::Union(_)
+            | Layout::LambdaSet(_)
+            | Layout::Boxed(_) => {
+                // Like the other backends, structural equality is generated as a helper proc.
+                let arguments = self.env.arena.alloc([*src1, *src2]);
+                let (eq_expr, new_specializations) = {
+                    let module_id = self.env.module_id;
+                    let ident_ids = self.interns.all_ident_ids.get_mut(&module_id).unwrap();
+                    self.helper_proc_gen
+                        .call_specialized_equals(ident_ids, arg_layout, arguments)
+                };
+                self.helper_proc_symbols.extend(new_specializations);
+                self.build_expr(dst, &eq_expr, &BOOL_LAYOUT);
+            }
             x => todo!("NumEq: layout, {:?}", x),
         }
     }
@@ -805,7 +1180,18 @@ impl<
                     .load_to_general_reg(&mut self.buf, src2);
                 ASM::neq_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
             }
-            x => todo!("NumNeq: layout, {:?}", x),
+            _ => {
+                // Flip the result of checking for equality.
+                self.build_eq(dst, src1, src2, arg_layout);
+                let dst_reg = self.storage_manager.load_to_general_reg(&mut self.buf, dst);
+                self.storage_manager.with_tmp_general_reg(
+                    &mut self.buf,
+                    |_storage_manager, buf, tmp_reg| {
+                        ASM::mov_reg64_imm64(buf, tmp_reg, 1);
+                        ASM::xor_reg64_reg64_reg64(buf, dst_reg, dst_reg, tmp_reg);
+                    },
+                );
+            }
         }
     }
 
@@ -930,57 +1316,449 @@ impl<
         }
     }
 
-    fn build_list_len(&mut self, dst: &Symbol, list: &Symbol) {
-        self.storage_manager.list_len(&mut self.buf, dst, list);
-    }
-
-    fn build_list_get_unsafe(
+    fn build_num_gt(
         &mut self,
         dst: &Symbol,
-        list: &Symbol,
-        index: &Symbol,
-        ret_layout: &Layout<'a>,
+        src1: &Symbol,
+        src2: &Symbol,
+        arg_layout: &Layout<'a>,
     ) {
-        let (base_offset, _) = self.storage_manager.stack_offset_and_size(list);
-        let index_reg = self
-            .storage_manager
-            .load_to_general_reg(&mut self.buf, index);
-        let ret_stack_size = ret_layout.stack_size(self.storage_manager.target_info());
-        // TODO: This can be optimized with smarter instructions.
-        // Also can probably be moved into storage manager at least partly.
-        self.storage_manager.with_tmp_general_reg(
-            &mut self.buf,
-            |storage_manager, buf, list_ptr| {
-                ASM::mov_reg64_base32(buf, list_ptr, base_offset as i32);
-                storage_manager.with_tmp_general_reg(buf, |storage_manager, buf, tmp| {
-                    ASM::mov_reg64_imm64(buf, tmp, ret_stack_size as i64);
-                    ASM::imul_reg64_reg64_reg64(buf, tmp, tmp, index_reg);
-                    ASM::add_reg64_reg64_reg64(buf, tmp, tmp, list_ptr);
-                    match ret_layout {
-                        single_register_integers!() if ret_stack_size == 8 => {
-                            let dst_reg = storage_manager.claim_general_reg(buf, dst);
-                            ASM::mov_reg64_mem64_offset32(buf, dst_reg, tmp, 0);
-                        }
-                        x => internal_error!("Loading list element with layout: {:?}", x),
-                    }
-                });
-            },
-        );
+        match arg_layout {
+            Layout::Builtin(single_register_int_builtins!()) => {
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let src1_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src1);
+                let src2_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src2);
+                ASM::gt_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+            }
+            // `a > b` is the same as `b < a`.
+            _ => self.build_num_lt(dst, src2, src1, arg_layout),
+        }
     }
 
-    fn build_list_replace_unsafe(
-        &mut self,
-        dst: &Symbol,
-        args: &'a [Symbol],
-        arg_layouts: &[Layout<'a>],
-        ret_layout: &Layout<'a>,
-    ) {
-        // We want to delegate to the zig builtin, but it takes some extra parameters.
-        // Firstly, it takes the alignment of the list.
-        // Secondly, it takes the stack size of an element.
-        // Thirdly, it takes a pointer that it will write the output element to.
-        let list = args[0];
-        let list_layout = arg_layouts[0];
+    fn build_num_div(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, layout: &Layout<'a>) {
+        match layout {
+            Layout::Builtin(Builtin::Int(int_width)) if int_width.stack_size() <= 8 => {
+                let size = int_width.stack_size() as u8;
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let src1_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src1);
+                let src2_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src2);
+                if int_width.is_signed() {
+                    ASM::idiv_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+                } else {
+                    ASM::udiv_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+                }
+                self.extend_int_reg(dst_reg, dst_reg, size, int_width.is_signed());
+            }
+            Layout::Builtin(Builtin::Int(int_width)) => {
+                self.build_fn_call(
+                    dst,
+                    bitcode::NUM_DIV_TRUNC[*int_width].to_string(),
+                    &[*src1, *src2],
+                    &[*layout, *layout],
+                    layout,
+                );
+            }
+            Layout::Builtin(Builtin::Decimal) => {
+                self.build_fn_call(
+                    dst,
+                    bitcode::DEC_DIV.to_string(),
+                    &[*src1, *src2],
+                    &[*layout, *layout],
+                    layout,
+                );
+            }
+            Layout::Builtin(Builtin::Float(FloatWidth::F64)) => {
+                let dst_reg = self.storage_manager.claim_float_reg(&mut self.buf, dst);
+                let src1_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src1);
+                let src2_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src2);
+                ASM::div_freg64_freg64_freg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+            }
+            Layout::Builtin(Builtin::Float(FloatWidth::F32)) => {
+                let dst_reg = self.storage_manager.claim_float_reg(&mut self.buf, dst);
+                let src1_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src1);
+                let src2_reg = self.storage_manager.load_to_float_reg(&mut self.buf, src2);
+                ASM::div_freg32_freg32_freg32(&mut self.buf, dst_reg, src1_reg, src2_reg);
+            }
+            x => internal_error!("NumDiv: invalid layout, {:?}", x),
+        }
+    }
+
+    fn build_num_rem(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, layout: &Layout<'a>) {
+        match layout {
+            Layout::Builtin(Builtin::Int(int_width)) if int_width.stack_size() <= 8 => {
+                let size = int_width.stack_size() as u8;
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let src1_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src1);
+                let src2_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src2);
+                if int_width.is_signed() {
+                    ASM::irem_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+                } else {
+                    ASM::urem_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+                }
+                self.extend_int_reg(dst_reg, dst_reg, size, int_width.is_signed());
+            }
+            Layout::Builtin(Builtin::Int(int_width)) => {
+                self.build_fn_call(
+                    dst,
+                    bitcode::NUM_REM[*int_width].to_string(),
+                    &[*src1, *src2],
+                    &[*layout, *layout],
+                    layout,
+                );
+            }
+            x => internal_error!("NumRem: invalid layout, {:?}", x),
+        }
+    }
+
+    fn build_num_bitwise_and(
+        &mut self,
+        dst: &Symbol,
+        src1: &Symbol,
+        src2: &Symbol,
+        layout: &Layout<'a>,
+    ) {
+        match layout {
+            Layout::Builtin(Builtin::Bool | single_register_int_builtins!()) => {
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let src1_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src1);
+                let src2_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src2);
+                ASM::and_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+            }
+            Layout::Builtin(Builtin::Int(IntWidth::I128 | IntWidth::U128)) => {
+                self.build_wide_bitwise_op(dst, src1, src2, ASM::and_reg64_reg64_reg64);
+            }
+            x => internal_error!("NumBitwiseAnd: invalid layout, {:?}", x),
+        }
+    }
+
+    fn build_num_bitwise_or(
+        &mut self,
+        dst: &Symbol,
+        src1: &Symbol,
+        src2: &Symbol,
+        layout: &Layout<'a>,
+    ) {
+        match layout {
+            Layout::Builtin(Builtin::Bool | single_register_int_builtins!()) => {
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let src1_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src1);
+                let src2_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src2);
+                ASM::or_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+            }
+            Layout::Builtin(Builtin::Int(IntWidth::I128 | IntWidth::U128)) => {
+                self.build_wide_bitwise_op(dst, src1, src2, ASM::or_reg64_reg64_reg64);
+            }
+            x => internal_error!("NumBitwiseOr: invalid layout, {:?}", x),
+        }
+    }
+
+    fn build_num_bitwise_xor(
+        &mut self,
+        dst: &Symbol,
+        src1: &Symbol,
+        src2: &Symbol,
+        layout: &Layout<'a>,
+    ) {
+        match layout {
+            Layout::Builtin(Builtin::Bool | single_register_int_builtins!()) => {
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let src1_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src1);
+                let src2_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, src2);
+                ASM::xor_reg64_reg64_reg64(&mut self.buf, dst_reg, src1_reg, src2_reg);
+            }
+            Layout::Builtin(Builtin::Int(IntWidth::I128 | IntWidth::U128)) => {
+                self.build_wide_bitwise_op(dst, src1, src2, ASM::xor_reg64_reg64_reg64);
+            }
+            x => internal_error!("NumBitwiseXor: invalid layout, {:?}", x),
+        }
+    }
+
+    fn build_num_shift_left(
+        &mut self,
+        dst: &Symbol,
+        value: &Symbol,
+        amount: &Symbol,
+        layout: &Layout<'a>,
+    ) {
+        match layout {
+            Layout::Builtin(Builtin::Int(int_width)) if int_width.stack_size() <= 8 => {
+                let size = int_width.stack_size() as u8;
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let value_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, value);
+                let amount_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, amount);
+                ASM::shl_reg64_reg64_reg64(&mut self.buf, dst_reg, value_reg, amount_reg);
+                self.extend_int_reg(dst_reg, dst_reg, size, int_width.is_signed());
+            }
+            Layout::Builtin(Builtin::Int(int_width)) => {
+                // The zig builtin takes the amount to shift by first.
+                self.build_fn_call(
+                    dst,
+                    bitcode::NUM_SHIFT_LEFT_BY[*int_width].to_string(),
+                    &[*amount, *value],
+                    &[*layout, *layout],
+                    layout,
+                );
+            }
+            x => internal_error!("NumShiftLeftBy: invalid layout, {:?}", x),
+        }
+    }
+
+    fn build_num_shift_right(
+        &mut self,
+        dst: &Symbol,
+        value: &Symbol,
+        amount: &Symbol,
+        layout: &Layout<'a>,
+    ) {
+        match layout {
+            Layout::Builtin(Builtin::Int(int_width)) if int_width.stack_size() <= 8 => {
+                let size = int_width.stack_size() as u8;
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let value_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, value);
+                let amount_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, amount);
+                // The value has to be sign extended for the bits shifted in to be correct.
+                ASM::movsx_reg64_reg64(&mut self.buf, dst_reg, value_reg, size);
+                ASM::sar_reg64_reg64_reg64(&mut self.buf, dst_reg, dst_reg, amount_reg);
+                self.extend_int_reg(dst_reg, dst_reg, size, int_width.is_signed());
+            }
+            Layout::Builtin(Builtin::Int(int_width)) => {
+                // The zig builtin takes the amount to shift by first.
+                self.build_fn_call(
+                    dst,
+                    bitcode::NUM_SHIFT_RIGHT_BY[*int_width].to_string(),
+                    &[*amount, *value],
+                    &[*layout, *layout],
+                    layout,
+                );
+            }
+            x => internal_error!("NumShiftRightBy: invalid layout, {:?}", x),
+        }
+    }
+
+    fn build_num_shift_right_zf(
+        &mut self,
+        dst: &Symbol,
+        value: &Symbol,
+        amount: &Symbol,
+        layout: &Layout<'a>,
+    ) {
+        match layout {
+            Layout::Builtin(Builtin::Int(int_width)) if int_width.stack_size() <= 8 => {
+                let size = int_width.stack_size() as u8;
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let value_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, value);
+                let amount_reg = self
+                    .storage_manager
+                    .load_to_general_reg(&mut self.buf, amount);
+                // The value has to be zero extended for the bits shifted in to be correct.
+                ASM::movzx_reg64_reg64(&mut self.buf, dst_reg, value_reg, size);
+                ASM::shr_reg64_reg64_reg64(&mut self.buf, dst_reg, dst_reg, amount_reg);
+                self.extend_int_reg(dst_reg, dst_reg, size, int_width.is_signed());
+            }
+            Layout::Builtin(Builtin::Int(int_width)) => {
+                // The zig builtin takes the amount to shift by first.
+                self.build_fn_call(
+                    dst,
+                    bitcode::NUM_SHIFT_RIGHT_ZF_BY[*int_width].to_string(),
+                    &[*amount, *value],
+                    &[*layout, *layout],
+                    layout,
+                );
+            }
+            x => internal_error!("NumShiftRightZfBy: invalid layout, {:?}", x),
+        }
+    }
+
+    fn build_num_int_cast(
+        &mut self,
+        dst: &Symbol,
+        src: &Symbol,
+        source_layout: &Layout<'a>,
+        target_layout: &Layout<'a>,
+    ) {
+        match (source_layout, target_layout) {
+            (
+                Layout::Builtin(Builtin::Int(source_width)),
+                Layout::Builtin(Builtin::Int(target_width)),
+            ) if source_width.stack_size() <= 8 && target_width.stack_size() <= 8 => {
+                // Like llvm, widening extends based on the signedness of the target
+                // and narrowing truncates, which here means re-extending the remaining bits.
+                let size = source_width.stack_size().min(target_width.stack_size()) as u8;
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                let src_reg = self.storage_manager.load_to_general_reg(&mut self.buf, src);
+                self.extend_int_reg(dst_reg, src_reg, size, target_width.is_signed());
+            }
+            (
+                Layout::Builtin(Builtin::Int(source_width)),
+                Layout::Builtin(Builtin::Int(target_width)),
+            ) if source_width.stack_size() <= 8 => {
+                // Widening to 128 bits, the upper half is filled with the sign of the extended value.
+                let size = source_width.stack_size() as u8;
+                let signed = target_width.is_signed();
+                let src_reg = self.storage_manager.load_to_general_reg(&mut self.buf, src);
+                let base_offset = self.storage_manager.claim_stack_area(dst, 16);
+                self.storage_manager.with_tmp_general_reg(
+                    &mut self.buf,
+                    |storage_manager, buf, tmp_reg| {
+                        if signed {
+                            ASM::movsx_reg64_reg64(buf, tmp_reg, src_reg, size);
+                            ASM::mov_base32_reg64(buf, base_offset, tmp_reg);
+                            storage_manager.with_tmp_general_reg(buf, |_, buf, shift_reg| {
+                                ASM::mov_reg64_imm64(buf, shift_reg, 63);
+                                ASM::sar_reg64_reg64_reg64(buf, tmp_reg, tmp_reg, shift_reg);
+                            });
+                        } else {
+                            ASM::movzx_reg64_reg64(buf, tmp_reg, src_reg, size);
+                            ASM::mov_base32_reg64(buf, base_offset, tmp_reg);
+                            ASM::mov_reg64_imm64(buf, tmp_reg, 0);
+                        }
+                        ASM::mov_base32_reg64(buf, base_offset + 8, tmp_reg);
+                    },
+                );
+            }
+            (Layout::Builtin(Builtin::Int(_)), Layout::Builtin(Builtin::Int(target_width)))
+                if target_width.stack_size() <= 8 =>
+            {
+                // Narrowing from 128 bits only keeps the lower half.
+                let size = target_width.stack_size() as u8;
+                let (base_offset, _) = self.storage_manager.stack_offset_and_size(src);
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+                ASM::mov_reg64_base32(&mut self.buf, dst_reg, base_offset);
+                self.extend_int_reg(dst_reg, dst_reg, size, target_width.is_signed());
+            }
+            (Layout::Builtin(Builtin::Int(_)), Layout::Builtin(Builtin::Int(_))) => {
+                // Both are 128 bits, so the bits stay the same.
+                let (src_offset, _) = self.storage_manager.stack_offset_and_size(src);
+                let base_offset = self.storage_manager.claim_stack_area(dst, 16);
+                self.storage_manager.with_tmp_general_reg(
+                    &mut self.buf,
+                    |_storage_manager, buf, tmp_reg| {
+                        for i in [0, 8] {
+                            ASM::mov_reg64_base32(buf, tmp_reg, src_offset + i);
+                            ASM::mov_base32_reg64(buf, base_offset + i, tmp_reg);
+                        }
+                    },
+                );
+            }
+            x => internal_error!("NumIntCast: invalid layouts, {:?}", x),
+        }
+    }
+
+    fn build_list_len(&mut self, dst: &Symbol, list: &Symbol) {
+        self.storage_manager.list_len(&mut self.buf, dst, list);
+    }
+
+    fn build_list_get_capacity(&mut self, dst: &Symbol, list: &Symbol) {
+        self.storage_manager.list_capacity(&mut self.buf, dst, list);
+    }
+
+    fn build_list_with_capacity(
+        &mut self,
+        dst: &Symbol,
+        capacity: &Symbol,
+        capacity_layout: &Layout<'a>,
+        ret_layout: &Layout<'a>,
+    ) {
+        let elem_layout = list_element_layout(ret_layout);
+        self.load_layout_alignment(&Symbol::DEV_TMP, elem_layout);
+        self.load_layout_stack_size(&Symbol::DEV_TMP2, elem_layout);
+
+        let lowlevel_args = bumpalo::vec![
+        in self.env.arena;
+            *capacity,
+            Symbol::DEV_TMP,
+            Symbol::DEV_TMP2,
+        ];
+        let lowlevel_arg_layouts = bumpalo::vec![
+        in self.env.arena;
+            *capacity_layout,
+            U32_LAYOUT,
+            U64_LAYOUT,
+        ];
+        self.build_fn_call(
+            dst,
+            bitcode::LIST_WITH_CAPACITY.to_string(),
+            &lowlevel_args,
+            &lowlevel_arg_layouts,
+            ret_layout,
+        );
+        self.free_symbol(&Symbol::DEV_TMP);
+        self.free_symbol(&Symbol::DEV_TMP2);
+    }
+
+    fn build_list_get_unsafe(
+        &mut self,
+        dst: &Symbol,
+        list: &Symbol,
+        index: &Symbol,
+        ret_layout: &Layout<'a>,
+    ) {
+        let (base_offset, _) = self.storage_manager.stack_offset_and_size(list);
+        let ret_stack_size = ret_layout.stack_size(self.storage_manager.target_info());
+        // TODO: This can be optimized with smarter instructions.
+        self.storage_manager.with_tmp_general_reg(
+            &mut self.buf,
+            |storage_manager, buf, elem_ptr| {
+                ASM::mov_reg64_base32(buf, elem_ptr, base_offset);
+                storage_manager.with_tmp_general_reg(buf, |storage_manager, buf, tmp| {
+                    // The index is loaded after the temporary registers are taken, so it can't evict them.
+                    let index_reg = storage_manager.load_to_general_reg(buf, index);
+                    ASM::mov_reg64_imm64(buf, tmp, ret_stack_size as i64);
+                    ASM::imul_reg64_reg64_reg64(buf, tmp, tmp, index_reg);
+                    ASM::add_reg64_reg64_reg64(buf, elem_ptr, elem_ptr, tmp);
+                });
+                storage_manager.load_to_symbol_from_ptr(buf, dst, ret_layout, elem_ptr, 0);
+            },
+        );
+    }
+
+    fn build_list_replace_unsafe(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    ) {
+        // We want to delegate to the zig builtin, but it takes some extra parameters.
+        // Firstly, it takes the alignment of the list.
+        // Secondly, it takes the stack size of an element.
+        // Thirdly, it takes a pointer that it will write the output element to.
+        let list = args[0];
+        let list_layout = arg_layouts[0];
         let index = args[1];
         let index_layout = arg_layouts[1];
         let elem = args[2];
@@ -1043,60 +1821,522 @@ impl<
             )
         };
 
-        // Load address of output element into register.
-        let reg = self
-            .storage_manager
-            .claim_general_reg(&mut self.buf, &Symbol::DEV_TMP4);
-        ASM::add_reg64_reg64_imm32(&mut self.buf, reg, CC::BASE_PTR_REG, out_elem_offset);
+        // Load address of output element into register.
+        let reg = self
+            .storage_manager
+            .claim_general_reg(&mut self.buf, &Symbol::DEV_TMP4);
+        ASM::add_reg64_reg64_imm32(&mut self.buf, reg, CC::BASE_PTR_REG, out_elem_offset);
+
+        let lowlevel_args = bumpalo::vec![
+        in self.env.arena;
+            list,
+            Symbol::DEV_TMP,
+            index,
+            Symbol::DEV_TMP2,
+            Symbol::DEV_TMP3,
+            Symbol::DEV_TMP4,
+         ];
+        let lowlevel_arg_layouts = bumpalo::vec![
+        in self.env.arena;
+                list_layout,
+                *u32_layout,
+                index_layout,
+                *u64_layout,
+                *u64_layout,
+                *u64_layout,
+        ];
+
+        self.build_fn_call(
+            &Symbol::DEV_TMP5,
+            bitcode::LIST_REPLACE.to_string(),
+            &lowlevel_args,
+            &lowlevel_arg_layouts,
+            &list_layout,
+        );
+        self.free_symbol(&Symbol::DEV_TMP);
+        self.free_symbol(&Symbol::DEV_TMP2);
+        self.free_symbol(&Symbol::DEV_TMP3);
+        self.free_symbol(&Symbol::DEV_TMP4);
+
+        // Copy from list to the output record.
+        self.storage_manager.copy_symbol_to_stack_offset(
+            &mut self.buf,
+            out_list_offset,
+            &Symbol::DEV_TMP5,
+            &list_layout,
+        );
+
+        self.free_symbol(&Symbol::DEV_TMP5);
+    }
+
+    fn build_list_reserve(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    ) {
+        let list = args[0];
+        let list_layout = arg_layouts[0];
+        let spare = args[1];
+        let spare_layout = arg_layouts[1];
+
+        let elem_layout = list_element_layout(&list_layout);
+        self.load_layout_alignment(&Symbol::DEV_TMP, elem_layout);
+        self.load_layout_stack_size(&Symbol::DEV_TMP2, elem_layout);
+        self.load_update_mode_immutable(&Symbol::DEV_TMP3);
+
+        let lowlevel_args = bumpalo::vec![
+        in self.env.arena;
+            list,
+            Symbol::DEV_TMP,
+            spare,
+            Symbol::DEV_TMP2,
+            Symbol::DEV_TMP3,
+        ];
+        let lowlevel_arg_layouts = bumpalo::vec![
+        in self.env.arena;
+            list_layout,
+            U32_LAYOUT,
+            spare_layout,
+            U64_LAYOUT,
+            U8_LAYOUT,
+        ];
+        self.build_fn_call(
+            dst,
+            bitcode::LIST_RESERVE.to_string(),
+            &lowlevel_args,
+            &lowlevel_arg_layouts,
+            ret_layout,
+        );
+        self.free_symbol(&Symbol::DEV_TMP);
+        self.free_symbol(&Symbol::DEV_TMP2);
+        self.free_symbol(&Symbol::DEV_TMP3);
+    }
+
+    fn build_list_append_unsafe(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    ) {
+        let list = args[0];
+        let list_layout = arg_layouts[0];
+        let elem = args[1];
+        let elem_layout = arg_layouts[1];
+
+        // The element is passed by pointer.
+        self.load_symbol_address(&Symbol::DEV_TMP, &elem, &elem_layout);
+        self.load_layout_stack_size(&Symbol::DEV_TMP2, &elem_layout);
+
+        let lowlevel_args = bumpalo::vec![
+        in self.env.arena;
+            list,
+            Symbol::DEV_TMP,
+            Symbol::DEV_TMP2,
+        ];
+        let lowlevel_arg_layouts = bumpalo::vec![
+        in self.env.arena;
+            list_layout,
+            U64_LAYOUT,
+            U64_LAYOUT,
+        ];
+        self.build_fn_call(
+            dst,
+            bitcode::LIST_APPEND_UNSAFE.to_string(),
+            &lowlevel_args,
+            &lowlevel_arg_layouts,
+            ret_layout,
+        );
+        self.free_symbol(&Symbol::DEV_TMP);
+        self.free_symbol(&Symbol::DEV_TMP2);
+    }
+
+    fn build_list_prepend(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    ) {
+        let list = args[0];
+        let list_layout = arg_layouts[0];
+        let elem = args[1];
+        let elem_layout = arg_layouts[1];
+
+        self.load_layout_alignment(&Symbol::DEV_TMP, &elem_layout);
+        // The element is passed by pointer.
+        self.load_symbol_address(&Symbol::DEV_TMP2, &elem, &elem_layout);
+        self.load_layout_stack_size(&Symbol::DEV_TMP3, &elem_layout);
+
+        let lowlevel_args = bumpalo::vec![
+        in self.env.arena;
+            list,
+            Symbol::DEV_TMP,
+            Symbol::DEV_TMP2,
+            Symbol::DEV_TMP3,
+        ];
+        let lowlevel_arg_layouts = bumpalo::vec![
+        in self.env.arena;
+            list_layout,
+            U32_LAYOUT,
+            U64_LAYOUT,
+            U64_LAYOUT,
+        ];
+        self.build_fn_call(
+            dst,
+            bitcode::LIST_PREPEND.to_string(),
+            &lowlevel_args,
+            &lowlevel_arg_layouts,
+            ret_layout,
+        );
+        self.free_symbol(&Symbol::DEV_TMP);
+        self.free_symbol(&Symbol::DEV_TMP2);
+        self.free_symbol(&Symbol::DEV_TMP3);
+    }
+
+    fn build_list_concat(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    ) {
+        let elem_layout = list_element_layout(ret_layout);
+        self.load_layout_alignment(&Symbol::DEV_TMP, elem_layout);
+        self.load_layout_stack_size(&Symbol::DEV_TMP2, elem_layout);
+
+        let lowlevel_args = bumpalo::vec![
+        in self.env.arena;
+            args[0],
+            args[1],
+            Symbol::DEV_TMP,
+            Symbol::DEV_TMP2,
+        ];
+        let lowlevel_arg_layouts = bumpalo::vec![
+        in self.env.arena;
+            arg_layouts[0],
+            arg_layouts[1],
+            U32_LAYOUT,
+            U64_LAYOUT,
+        ];
+        self.build_fn_call(
+            dst,
+            bitcode::LIST_CONCAT.to_string(),
+            &lowlevel_args,
+            &lowlevel_arg_layouts,
+            ret_layout,
+        );
+        self.free_symbol(&Symbol::DEV_TMP);
+        self.free_symbol(&Symbol::DEV_TMP2);
+    }
+
+    fn build_list_swap(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    ) {
+        let list_layout = arg_layouts[0];
+        let elem_layout = list_element_layout(&list_layout);
+        self.load_layout_alignment(&Symbol::DEV_TMP, elem_layout);
+        self.load_layout_stack_size(&Symbol::DEV_TMP2, elem_layout);
+        self.load_update_mode_immutable(&Symbol::DEV_TMP3);
+
+        let lowlevel_args = bumpalo::vec![
+        in self.env.arena;
+            args[0],
+            Symbol::DEV_TMP,
+            Symbol::DEV_TMP2,
+            args[1],
+            args[2],
+            Symbol::DEV_TMP3,
+        ];
+        let lowlevel_arg_layouts = bumpalo::vec![
+        in self.env.arena;
+            list_layout,
+            U32_LAYOUT,
+            U64_LAYOUT,
+            arg_layouts[1],
+            arg_layouts[2],
+            U8_LAYOUT,
+        ];
+        self.build_fn_call(
+            dst,
+            bitcode::LIST_SWAP.to_string(),
+            &lowlevel_args,
+            &lowlevel_arg_layouts,
+            ret_layout,
+        );
+        self.free_symbol(&Symbol::DEV_TMP);
+        self.free_symbol(&Symbol::DEV_TMP2);
+        self.free_symbol(&Symbol::DEV_TMP3);
+    }
+
+    fn build_list_sublist(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    ) {
+        let list_layout = arg_layouts[0];
+        let elem_layout = list_element_layout(&list_layout);
+        self.load_layout_alignment(&Symbol::DEV_TMP, elem_layout);
+        self.load_layout_stack_size(&Symbol::DEV_TMP2, elem_layout);
+        self.load_element_dec_function(&Symbol::DEV_TMP3, elem_layout);
+
+        let lowlevel_args = bumpalo::vec![
+        in self.env.arena;
+            args[0],
+            Symbol::DEV_TMP,
+            Symbol::DEV_TMP2,
+            args[1],
+            args[2],
+            Symbol::DEV_TMP3,
+        ];
+        let lowlevel_arg_layouts = bumpalo::vec![
+        in self.env.arena;
+            list_layout,
+            U32_LAYOUT,
+            U64_LAYOUT,
+            arg_layouts[1],
+            arg_layouts[2],
+            U64_LAYOUT,
+        ];
+        self.build_fn_call(
+            dst,
+            bitcode::LIST_SUBLIST.to_string(),
+            &lowlevel_args,
+            &lowlevel_arg_layouts,
+            ret_layout,
+        );
+        self.free_symbol(&Symbol::DEV_TMP);
+        self.free_symbol(&Symbol::DEV_TMP2);
+        self.free_symbol(&Symbol::DEV_TMP3);
+    }
+
+    fn build_list_drop_at(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    ) {
+        let list_layout = arg_layouts[0];
+        let elem_layout = list_element_layout(&list_layout);
+        self.load_layout_alignment(&Symbol::DEV_TMP, elem_layout);
+        self.load_layout_stack_size(&Symbol::DEV_TMP2, elem_layout);
+        self.load_element_dec_function(&Symbol::DEV_TMP3, elem_layout);
 
         let lowlevel_args = bumpalo::vec![
         in self.env.arena;
-            list,
+            args[0],
             Symbol::DEV_TMP,
-            index,
             Symbol::DEV_TMP2,
+            args[1],
             Symbol::DEV_TMP3,
-            Symbol::DEV_TMP4,
-         ];
+        ];
         let lowlevel_arg_layouts = bumpalo::vec![
         in self.env.arena;
-                list_layout,
-                *u32_layout,
-                index_layout,
-                *u64_layout,
-                *u64_layout,
-                *u64_layout,
+            list_layout,
+            U32_LAYOUT,
+            U64_LAYOUT,
+            arg_layouts[1],
+            U64_LAYOUT,
         ];
-
         self.build_fn_call(
-            &Symbol::DEV_TMP5,
-            bitcode::LIST_REPLACE.to_string(),
+            dst,
+            bitcode::LIST_DROP_AT.to_string(),
             &lowlevel_args,
             &lowlevel_arg_layouts,
-            &list_layout,
+            ret_layout,
         );
         self.free_symbol(&Symbol::DEV_TMP);
         self.free_symbol(&Symbol::DEV_TMP2);
         self.free_symbol(&Symbol::DEV_TMP3);
-        self.free_symbol(&Symbol::DEV_TMP4);
+    }
 
-        // Copy from list to the output record.
-        self.storage_manager.copy_symbol_to_stack_offset(
-            &mut self.buf,
-            out_list_offset,
-            &Symbol::DEV_TMP5,
-            &list_layout,
+    fn build_higher_order_lowlevel(
+        &mut self,
+        dst: &Symbol,
+        higher_order: &HigherOrderLowLevel<'a>,
+        ret_layout: &Layout<'a>,
+    ) {
+        let HigherOrderLowLevel {
+            op,
+            passed_function,
+            ..
+        } = higher_order;
+        let PassedFunction {
+            name,
+            argument_layouts,
+            return_layout,
+            captured_environment,
+            owns_captured_environment,
+            ..
+        } = passed_function;
+
+        let closure_layout = match self.layout_map.get(captured_environment) {
+            Some(Layout::LambdaSet(lambda_set)) if lambda_set.is_represented().is_some() => {
+                Some(lambda_set.runtime_representation())
+            }
+            // A lambda set without closure data is only passed around as Unit.
+            Some(Layout::LambdaSet(_))
+            | Some(Layout::Struct {
+                field_layouts: &[], ..
+            }) => None,
+            x => internal_error!("Closure data has an invalid layout: {:?}", x),
+        };
+
+        let layout_id = LayoutIds::default().get(name.name(), return_layout);
+        let passed_proc = PassedProc {
+            name: self.symbol_to_string(name.name(), layout_id),
+            arg_layouts: argument_layouts,
+            ret_layout: *return_layout,
+            closure_layout,
+        };
+        let (lists, fn_name, wrapper_name, wrapper_kind) = match *op {
+            HigherOrder::ListMap { xs } => (
+                self.env.arena.alloc([xs]) as &[_],
+                bitcode::LIST_MAP,
+                format!("{}_mapper", passed_proc.name),
+                WrapperKind::Mapper(passed_proc),
+            ),
+            HigherOrder::ListMap2 { xs, ys } => (
+                self.env.arena.alloc([xs, ys]) as &[_],
+                bitcode::LIST_MAP2,
+                format!("{}_mapper", passed_proc.name),
+                WrapperKind::Mapper(passed_proc),
+            ),
+            HigherOrder::ListMap3 { xs, ys, zs } => (
+                self.env.arena.alloc([xs, ys, zs]) as &[_],
+                bitcode::LIST_MAP3,
+                format!("{}_mapper", passed_proc.name),
+                WrapperKind::Mapper(passed_proc),
+            ),
+            HigherOrder::ListMap4 { xs, ys, zs, ws } => (
+                self.env.arena.alloc([xs, ys, zs, ws]) as &[_],
+                bitcode::LIST_MAP4,
+                format!("{}_mapper", passed_proc.name),
+                WrapperKind::Mapper(passed_proc),
+            ),
+            HigherOrder::ListSortWith { xs } => (
+                self.env.arena.alloc([xs]) as &[_],
+                bitcode::LIST_SORT_WITH,
+                format!("{}_compare", passed_proc.name),
+                WrapperKind::Compare(passed_proc),
+            ),
+        };
+        let elem_layouts = Vec::from_iter_in(
+            lists
+                .iter()
+                .map(|list| *list_element_layout(&self.layout_map[list])),
+            self.env.arena,
         );
+        let ret_elem_layout = *list_element_layout(ret_layout);
 
-        self.free_symbol(&Symbol::DEV_TMP5);
+        let mut args = Vec::from_iter_in(lists.iter().copied(), self.env.arena);
+        let mut arg_layouts = Vec::from_iter_in(
+            lists.iter().map(|list| self.layout_map[list]),
+            self.env.arena,
+        );
+
+        let caller = self.unique_symbol();
+        self.load_wrapper_proc_pointer(&caller, wrapper_name, wrapper_kind);
+        args.push(caller);
+        arg_layouts.push(U64_LAYOUT);
+
+        // The closure data is passed by pointer, and zig passes it back to the wrapper.
+        let data = self.unique_symbol();
+        match closure_layout {
+            Some(layout) => self.load_symbol_address(&data, captured_environment, &layout),
+            None => self.load_literal(&data, &U64_LAYOUT, &Literal::Int(0i128.to_ne_bytes())),
+        }
+        args.push(data);
+        arg_layouts.push(U64_LAYOUT);
+
+        let inc_n_data = self.unique_symbol();
+        self.load_closure_inc_n_function(&inc_n_data, closure_layout);
+        args.push(inc_n_data);
+        arg_layouts.push(U64_LAYOUT);
+
+        let data_is_owned = self.unique_symbol();
+        self.load_literal(
+            &data_is_owned,
+            &BOOL_LAYOUT,
+            &Literal::Bool(*owns_captured_environment),
+        );
+        args.push(data_is_owned);
+        arg_layouts.push(BOOL_LAYOUT);
+
+        let alignment = self.unique_symbol();
+        self.load_layout_alignment(&alignment, &ret_elem_layout);
+        args.push(alignment);
+        arg_layouts.push(U32_LAYOUT);
+
+        for elem_layout in elem_layouts.iter() {
+            let width = self.unique_symbol();
+            self.load_layout_stack_size(&width, elem_layout);
+            args.push(width);
+            arg_layouts.push(U64_LAYOUT);
+        }
+        if !matches!(op, HigherOrder::ListSortWith { .. }) {
+            let width = self.unique_symbol();
+            self.load_layout_stack_size(&width, &ret_elem_layout);
+            args.push(width);
+            arg_layouts.push(U64_LAYOUT);
+        }
+
+        // Map2 and up drop the elements past the end of the shortest list.
+        if lists.len() > 1 {
+            for elem_layout in elem_layouts.iter() {
+                let dec = self.unique_symbol();
+                self.load_element_dec_function(&dec, elem_layout);
+                args.push(dec);
+                arg_layouts.push(U64_LAYOUT);
+            }
+        }
+
+        self.build_fn_call(dst, fn_name.to_string(), &args, &arg_layouts, ret_layout);
+        for sym in &args[lists.len()..] {
+            self.free_symbol(sym);
+        }
     }
 
     fn build_ptr_cast(&mut self, dst: &Symbol, src: &Symbol) {
-        let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
-        self.storage_manager
-            .ensure_symbol_on_stack(&mut self.buf, src);
-        let (offset, _) = self.storage_manager.stack_offset_and_size(src);
-        ASM::add_reg64_reg64_imm32(&mut self.buf, dst_reg, CC::BASE_PTR_REG, offset);
+        if self.storage_manager.is_stored_primitive(src) {
+            // The source already is a pointer, like the refcount helpers pass around, so only its type changes.
+            let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+            let src_reg = self.storage_manager.load_to_general_reg(&mut self.buf, src);
+            ASM::mov_reg64_reg64(&mut self.buf, dst_reg, src_reg);
+        } else {
+            let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+            self.storage_manager
+                .ensure_symbol_on_stack(&mut self.buf, src);
+            let (offset, _) = self.storage_manager.stack_offset_and_size(src);
+            ASM::add_reg64_reg64_imm32(&mut self.buf, dst_reg, CC::BASE_PTR_REG, offset);
+        }
+    }
+
+    fn build_unreachable(&mut self, dst: &Symbol, layout: &Layout<'a>) {
+        // The value is never read, so it only needs a place to live.
+        match layout {
+            single_register_integers!() => {
+                self.storage_manager.claim_general_reg(&mut self.buf, dst);
+            }
+            single_register_floats!() => {
+                self.storage_manager.claim_float_reg(&mut self.buf, dst);
+            }
+            _ => match layout.stack_size(self.storage_manager.target_info()) {
+                0 => self.storage_manager.no_data(dst),
+                size => {
+                    self.storage_manager.claim_stack_area(dst, size);
+                }
+            },
+        }
     }
 
     fn create_empty_array(&mut self, sym: &Symbol) {
@@ -1158,7 +2398,8 @@ impl<
         // fill refcount at -8.
         self.storage_manager.with_tmp_general_reg(
             &mut self.buf,
-            |_storage_manager, buf, tmp_reg| {
+            |storage_manager, buf, tmp_reg| {
+                let ptr_reg = storage_manager.load_to_general_reg(buf, &Symbol::DEV_TMP3);
                 ASM::mov_reg64_imm64(buf, tmp_reg, REFCOUNT_ONE as i64);
                 ASM::mov_mem64_offset32_reg64(buf, ptr_reg, -8, tmp_reg);
             },
@@ -1179,32 +2420,20 @@ impl<
                     &Symbol::DEV_TMP
                 }
             };
-            // TODO: Expand to all types.
-            match elem_layout {
-                Layout::Builtin(Builtin::Int(IntWidth::I64 | IntWidth::U64)) => {
-                    let sym_reg = self
-                        .storage_manager
-                        .load_to_general_reg(&mut self.buf, elem_sym);
-                    ASM::mov_mem64_offset32_reg64(&mut self.buf, ptr_reg, elem_offset, sym_reg);
-                }
-                _ if elem_size == 0 => {}
-                _ if elem_size > 8 => {
-                    let (from_offset, size) = self.storage_manager.stack_offset_and_size(elem_sym);
-                    debug_assert!(from_offset % 8 == 0);
-                    debug_assert!(size % 8 == 0);
-                    debug_assert_eq!(size as u64, elem_size);
-                    self.storage_manager.with_tmp_general_reg(
-                        &mut self.buf,
-                        |_storage_manager, buf, tmp_reg| {
-                            for i in (0..size as i32).step_by(8) {
-                                ASM::mov_reg64_base32(buf, tmp_reg, from_offset + i);
-                                ASM::mov_mem64_offset32_reg64(buf, ptr_reg, elem_offset, tmp_reg);
-                            }
-                        },
+            // The list pointer is loaded into a temporary register so copying the element can't evict it.
+            self.storage_manager.with_tmp_general_reg(
+                &mut self.buf,
+                |storage_manager, buf, tmp_reg| {
+                    storage_manager.load_to_specified_general_reg(buf, &Symbol::DEV_TMP3, tmp_reg);
+                    storage_manager.copy_symbol_to_ptr(
+                        buf,
+                        tmp_reg,
+                        elem_offset,
+                        elem_sym,
+                        elem_layout,
                     );
-                }
-                x => todo!("copying data to list with layout, {:?}", x),
-            }
+                },
+            );
             elem_offset += elem_size as i32;
             if elem_sym == &Symbol::DEV_TMP {
                 self.free_symbol(elem_sym);
@@ -1216,6 +2445,7 @@ impl<
             &mut self.buf,
             |storage_manager, buf, tmp_reg| {
                 let base_offset = storage_manager.claim_stack_area(sym, 24);
+                let ptr_reg = storage_manager.load_to_general_reg(buf, &Symbol::DEV_TMP3);
                 ASM::mov_base32_reg64(buf, base_offset, ptr_reg);
 
                 ASM::mov_reg64_imm64(buf, tmp_reg, elems.len() as i64);
@@ -1251,7 +2481,7 @@ impl<
         union_layout: &UnionLayout<'a>,
     ) {
         match union_layout {
-            UnionLayout::NonRecursive(tag_layouts) | UnionLayout::Recursive(tag_layouts) => {
+            UnionLayout::NonRecursive(tag_layouts) => {
                 self.storage_manager.load_field_at_index(
                     sym,
                     structure,
@@ -1259,13 +2489,105 @@ impl<
                     tag_layouts[tag_id as usize],
                 );
             }
-            x => todo!("loading from union type: {:?}", x),
+            _ => {
+                // The fields of recursive unions live on the heap, one after the other.
+                let target_info = self.storage_manager.target_info();
+                let field_layouts = union_field_layouts(union_layout, tag_id);
+                let field_offset: u32 = field_layouts
+                    .iter()
+                    .take(index as usize)
+                    .map(|layout| layout.stack_size(target_info))
+                    .sum();
+                let field_layout = &field_layouts[index as usize];
+                let stores_tag_id_in_pointer = union_layout.stores_tag_id_in_pointer(target_info);
+                self.storage_manager.with_tmp_general_reg(
+                    &mut self.buf,
+                    |storage_manager, buf, ptr_reg| {
+                        storage_manager.load_to_specified_general_reg(buf, structure, ptr_reg);
+                        if stores_tag_id_in_pointer {
+                            Self::clear_tag_id_bits(storage_manager, buf, ptr_reg);
+                        }
+                        storage_manager.load_to_symbol_from_ptr(
+                            buf,
+                            sym,
+                            field_layout,
+                            ptr_reg,
+                            field_offset as i32,
+                        );
+                    },
+                );
+            }
         }
     }
 
     fn get_tag_id(&mut self, sym: &Symbol, structure: &Symbol, union_layout: &UnionLayout<'a>) {
-        self.storage_manager
-            .load_union_tag_id(&mut self.buf, sym, structure, union_layout);
+        let target_info = self.storage_manager.target_info();
+        match union_layout {
+            UnionLayout::NonRecursive(_) => {
+                self.storage_manager
+                    .load_union_tag_id(&mut self.buf, sym, structure, union_layout);
+            }
+            UnionLayout::NonNullableUnwrapped(_) => {
+                self.load_literal(
+                    sym,
+                    &union_layout.tag_id_layout(),
+                    &Literal::Int(0i128.to_ne_bytes()),
+                );
+            }
+            UnionLayout::NullableUnwrapped { nullable_id, .. } => {
+                // Only the nullable tag is a null pointer.
+                self.load_literal(
+                    &Symbol::DEV_TMP,
+                    &U64_LAYOUT,
+                    &Literal::Int(0i128.to_ne_bytes()),
+                );
+                if *nullable_id {
+                    self.build_eq(sym, structure, &Symbol::DEV_TMP, &U64_LAYOUT);
+                } else {
+                    self.build_neq(sym, structure, &Symbol::DEV_TMP, &U64_LAYOUT);
+                }
+                self.free_symbol(&Symbol::DEV_TMP);
+            }
+            UnionLayout::Recursive(_) | UnionLayout::NullableWrapped { .. } => {
+                let arena = self.env.arena;
+                let nullable_id = match union_layout {
+                    UnionLayout::NullableWrapped { nullable_id, .. } => Some(*nullable_id),
+                    _ => None,
+                };
+                let tag_id_offset = union_layout.tag_id_offset(target_info).unwrap();
+                let stores_tag_id_as_data = union_layout.stores_tag_id_as_data(target_info);
+                let id_size = union_layout.discriminant().stack_size() as u8;
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, sym);
+                self.storage_manager.with_tmp_general_reg(
+                    &mut self.buf,
+                    |storage_manager, buf, ptr_reg| {
+                        storage_manager.load_to_specified_general_reg(buf, structure, ptr_reg);
+                        let read_tag_id = |buf: &mut Vec<'a, u8>| {
+                            if stores_tag_id_as_data {
+                                ASM::movzx_reg64_mem64_offset32(
+                                    buf,
+                                    dst_reg,
+                                    ptr_reg,
+                                    tag_id_offset as i32,
+                                    id_size,
+                                );
+                            } else {
+                                ASM::mov_reg64_imm64(buf, dst_reg, TAG_ID_POINTER_MASK);
+                                ASM::and_reg64_reg64_reg64(buf, dst_reg, dst_reg, ptr_reg);
+                            }
+                        };
+                        match nullable_id {
+                            Some(nullable_id) => {
+                                // A null pointer has no data to read the tag id from.
+                                ASM::mov_reg64_imm64(buf, dst_reg, nullable_id as i64);
+                                Self::if_reg_neq_imm64(arena, buf, ptr_reg, 0, read_tag_id);
+                            }
+                            None => read_tag_id(buf),
+                        }
+                    },
+                );
+            }
+        }
     }
 
     fn tag(
@@ -1274,9 +2596,99 @@ impl<
         fields: &'a [Symbol],
         union_layout: &UnionLayout<'a>,
         tag_id: TagIdIntType,
+        reuse: Option<Symbol>,
     ) {
-        self.storage_manager
-            .create_union(&mut self.buf, sym, union_layout, fields, tag_id)
+        let target_info = self.storage_manager.target_info();
+        match union_layout {
+            UnionLayout::NonRecursive(_) => {
+                self.storage_manager
+                    .create_union(&mut self.buf, sym, union_layout, fields, tag_id);
+            }
+            _ if union_layout.tag_is_null(tag_id) => {
+                let reg = self.storage_manager.claim_general_reg(&mut self.buf, sym);
+                ASM::mov_reg64_imm64(&mut self.buf, reg, 0);
+            }
+            _ => {
+                let field_layouts = union_field_layouts(union_layout, tag_id);
+                let (data_size, _) = union_layout.data_size_and_alignment(target_info);
+                let alignment = union_layout.allocation_alignment_bytes(target_info);
+                let slot = self.allocate_with_refcount(data_size, alignment, reuse);
+
+                // Copy the fields into the allocation.
+                let tag_id_offset = union_layout.tag_id_offset(target_info);
+                let stores_tag_id_as_data = union_layout.stores_tag_id_as_data(target_info);
+                let id_size = union_layout.discriminant().stack_size() as u8;
+                self.storage_manager.with_tmp_general_reg(
+                    &mut self.buf,
+                    |storage_manager, buf, ptr_reg| {
+                        ASM::mov_reg64_base32(buf, ptr_reg, slot);
+                        let mut field_offset = 0;
+                        for (field, field_layout) in fields.iter().zip(field_layouts.iter()) {
+                            storage_manager.copy_symbol_to_ptr(
+                                buf,
+                                ptr_reg,
+                                field_offset,
+                                field,
+                                field_layout,
+                            );
+                            field_offset += field_layout.stack_size(target_info) as i32;
+                        }
+                        if stores_tag_id_as_data {
+                            storage_manager.with_tmp_general_reg(buf, |_, buf, tag_id_reg| {
+                                ASM::mov_reg64_imm64(buf, tag_id_reg, tag_id as i64);
+                                ASM::mov_sized_mem64_offset32_reg64(
+                                    buf,
+                                    ptr_reg,
+                                    tag_id_offset.unwrap() as i32,
+                                    tag_id_reg,
+                                    id_size,
+                                );
+                            });
+                        }
+                    },
+                );
+
+                let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, sym);
+                ASM::mov_reg64_base32(&mut self.buf, dst_reg, slot);
+                if union_layout.stores_tag_id_in_pointer(target_info) && tag_id != 0 {
+                    self.storage_manager.with_tmp_general_reg(
+                        &mut self.buf,
+                        |_storage_manager, buf, tag_id_reg| {
+                            ASM::mov_reg64_imm64(buf, tag_id_reg, tag_id as i64);
+                            ASM::or_reg64_reg64_reg64(buf, dst_reg, dst_reg, tag_id_reg);
+                        },
+                    );
+                }
+                self.free_symbol(&Symbol::DEV_TMP3);
+            }
+        }
+    }
+
+    fn expr_box(&mut self, sym: &Symbol, value: &Symbol, inner_layout: &Layout<'a>) {
+        let target_info = self.storage_manager.target_info();
+        let size = inner_layout.stack_size(target_info);
+        let alignment = inner_layout.alignment_bytes(target_info);
+        let slot = self.allocate_with_refcount(size, alignment, None);
+        self.storage_manager.with_tmp_general_reg(
+            &mut self.buf,
+            |storage_manager, buf, ptr_reg| {
+                ASM::mov_reg64_base32(buf, ptr_reg, slot);
+                storage_manager.copy_symbol_to_ptr(buf, ptr_reg, 0, value, inner_layout);
+            },
+        );
+        let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, sym);
+        ASM::mov_reg64_base32(&mut self.buf, dst_reg, slot);
+        self.free_symbol(&Symbol::DEV_TMP3);
+    }
+
+    fn expr_unbox(&mut self, sym: &Symbol, boxed: &Symbol, inner_layout: &Layout<'a>) {
+        self.storage_manager.with_tmp_general_reg(
+            &mut self.buf,
+            |storage_manager, buf, ptr_reg| {
+                storage_manager.load_to_specified_general_reg(buf, boxed, ptr_reg);
+                storage_manager.load_to_symbol_from_ptr(buf, sym, inner_layout, ptr_reg, 0);
+            },
+        );
     }
 
     fn load_literal(&mut self, sym: &Symbol, layout: &Layout<'a>, lit: &Literal<'a>) {
@@ -1341,6 +2753,28 @@ impl<
                     },
                 );
             }
+            (
+                Literal::Int(bytes) | Literal::U128(bytes) | Literal::Decimal(bytes),
+                Layout::Builtin(Builtin::Int(IntWidth::I128 | IntWidth::U128) | Builtin::Decimal),
+            ) => {
+                self.storage_manager.with_tmp_general_reg(
+                    &mut self.buf,
+                    |storage_manager, buf, reg| {
+                        let base_offset = storage_manager.claim_stack_area(sym, 16);
+
+                        let mut num_bytes = [0; 8];
+                        num_bytes.copy_from_slice(&bytes[..8]);
+                        let num = i64::from_ne_bytes(num_bytes);
+                        ASM::mov_reg64_imm64(buf, reg, num);
+                        ASM::mov_base32_reg64(buf, base_offset, reg);
+
+                        num_bytes.copy_from_slice(&bytes[8..]);
+                        let num = i64::from_ne_bytes(num_bytes);
+                        ASM::mov_reg64_imm64(buf, reg, num);
+                        ASM::mov_base32_reg64(buf, base_offset + 8, reg);
+                    },
+                );
+            }
             x => todo!("loading literal, {:?}", x),
         }
     }
@@ -1410,6 +2844,299 @@ impl<
             self.buf[jmp_location as usize + i] = *byte;
         }
     }
+
+    /// Extends the lowest `size` bytes of `src` into `dst`, either with the sign bit or with zeros.
+    fn extend_int_reg(&mut self, dst: GeneralReg, src: GeneralReg, size: u8, signed: bool) {
+        if signed {
+            ASM::movsx_reg64_reg64(&mut self.buf, dst, src, size);
+        } else {
+            ASM::movzx_reg64_reg64(&mut self.buf, dst, src, size);
+        }
+    }
+
+    /// Clears the tag id that recursive unions can store in the lowest bits of their pointer.
+    fn clear_tag_id_bits(
+        storage_manager: &mut StorageManager<'a, GeneralReg, FloatReg, ASM, CC>,
+        buf: &mut Vec<'a, u8>,
+        ptr_reg: GeneralReg,
+    ) {
+        storage_manager.with_tmp_general_reg(buf, |_storage_manager, buf, mask_reg| {
+            ASM::mov_reg64_imm64(buf, mask_reg, !TAG_ID_POINTER_MASK);
+            ASM::and_reg64_reg64_reg64(buf, ptr_reg, ptr_reg, mask_reg);
+        });
+    }
+
+    /// Builds `f` so that it only runs when `reg` is equal to `value`.
+    fn if_reg_eq_imm64<F: FnOnce(&mut Vec<'a, u8>)>(
+        arena: &'a Bump,
+        buf: &mut Vec<'a, u8>,
+        reg: GeneralReg,
+        value: u64,
+        f: F,
+    ) {
+        // Jump past `f` if the values are not equal.
+        // Since we don't know the offset yet, set it to 0 and overwrite later.
+        let jne_location = buf.len();
+        let start_offset = ASM::jne_reg64_imm64_imm32(buf, reg, value, 0);
+        f(buf);
+
+        let mut tmp = bumpalo::vec![in arena];
+        let jne_offset = buf.len() - start_offset;
+        ASM::jne_reg64_imm64_imm32(&mut tmp, reg, value, jne_offset as i32);
+        buf[jne_location..jne_location + tmp.len()].copy_from_slice(&tmp);
+    }
+
+    /// Builds `f` so that it only runs when `reg` is not equal to `value`.
+    fn if_reg_neq_imm64<F: FnOnce(&mut Vec<'a, u8>)>(
+        arena: &'a Bump,
+        buf: &mut Vec<'a, u8>,
+        reg: GeneralReg,
+        value: u64,
+        f: F,
+    ) {
+        // Jump to `f` if the values are not equal, otherwise jump past it.
+        // Since we don't know the offsets yet, set them to 0 and overwrite later.
+        let jne_location = buf.len();
+        let jne_start_offset = ASM::jne_reg64_imm64_imm32(buf, reg, value, 0);
+        let jmp_location = buf.len();
+        let jmp_start_offset = ASM::jmp_imm32(buf, 0x1234_5678);
+        f(buf);
+
+        let mut tmp = bumpalo::vec![in arena];
+        let jne_offset = jmp_start_offset - jne_start_offset;
+        ASM::jne_reg64_imm64_imm32(&mut tmp, reg, value, jne_offset as i32);
+        buf[jne_location..jne_location + tmp.len()].copy_from_slice(&tmp);
+
+        tmp.clear();
+        let jmp_offset = buf.len() - jmp_start_offset;
+        ASM::jmp_imm32(&mut tmp, jmp_offset as i32);
+        buf[jmp_location..jmp_location + tmp.len()].copy_from_slice(&tmp);
+    }
+
+    /// Allocates `size` bytes with a refcount in front of them, like the zig builtins do.
+    /// If `reuse` is given and not null, its allocation is used instead.
+    /// The data pointer is stored in `Symbol::DEV_TMP3`, whose stack offset is returned.
+    /// The caller must free `Symbol::DEV_TMP3`.
+    fn allocate_with_refcount(&mut self, size: u32, alignment: u32, reuse: Option<Symbol>) -> i32 {
+        // The allocation is a call, so values in caller saved registers have to be saved first.
+        self.storage_manager
+            .push_used_caller_saved_regs_to_stack(&mut self.buf);
+        let slot = self.storage_manager.claim_stack_area(&Symbol::DEV_TMP3, 8);
+
+        let relocs = &mut self.relocs;
+        let mut allocate = |buf: &mut Vec<'a, u8>| {
+            ASM::mov_reg64_imm64(buf, CC::GENERAL_PARAM_REGS[0], size as i64);
+            ASM::mov_reg64_imm64(buf, CC::GENERAL_PARAM_REGS[1], alignment as i64);
+            ASM::call(
+                buf,
+                relocs,
+                bitcode::UTILS_ALLOCATE_WITH_REFCOUNT.to_string(),
+            );
+            ASM::mov_base32_reg64(buf, slot, CC::GENERAL_RETURN_REGS[0]);
+        };
+        match reuse {
+            Some(reuse) => {
+                let arena = self.env.arena;
+                self.storage_manager.with_tmp_general_reg(
+                    &mut self.buf,
+                    |storage_manager, buf, reuse_reg| {
+                        storage_manager.load_to_specified_general_reg(buf, &reuse, reuse_reg);
+                        Self::clear_tag_id_bits(storage_manager, buf, reuse_reg);
+                        ASM::mov_base32_reg64(buf, slot, reuse_reg);
+                        // Reset gives a null pointer when the allocation can't be reused.
+                        Self::if_reg_eq_imm64(arena, buf, reuse_reg, 0, allocate);
+                    },
+                );
+            }
+            None => allocate(&mut self.buf),
+        }
+        self.storage_manager
+            .update_fn_call_stack_size(CC::SHADOW_SPACE_SIZE as u32);
+        slot
+    }
+
+    /// Applies a bitwise `op` to two 128 bit values, one eightbyte at a time.
+    fn build_wide_bitwise_op(
+        &mut self,
+        dst: &Symbol,
+        src1: &Symbol,
+        src2: &Symbol,
+        op: fn(&mut Vec<'a, u8>, GeneralReg, GeneralReg, GeneralReg),
+    ) {
+        let (src1_offset, _) = self.storage_manager.stack_offset_and_size(src1);
+        let (src2_offset, _) = self.storage_manager.stack_offset_and_size(src2);
+        let base_offset = self.storage_manager.claim_stack_area(dst, 16);
+        self.storage_manager.with_tmp_general_reg(
+            &mut self.buf,
+            |storage_manager, buf, tmp_reg1| {
+                storage_manager.with_tmp_general_reg(buf, |_storage_manager, buf, tmp_reg2| {
+                    for i in [0, 8] {
+                        ASM::mov_reg64_base32(buf, tmp_reg1, src1_offset + i);
+                        ASM::mov_reg64_base32(buf, tmp_reg2, src2_offset + i);
+                        op(buf, tmp_reg1, tmp_reg1, tmp_reg2);
+                        ASM::mov_base32_reg64(buf, base_offset + i, tmp_reg1);
+                    }
+                });
+            },
+        );
+    }
+
+    /// Loads the alignment of `layout` into `sym` as a U32, which is how the zig builtins expect it.
+    fn load_layout_alignment(&mut self, sym: &Symbol, layout: &Layout<'a>) {
+        let alignment = layout.alignment_bytes(self.storage_manager.target_info());
+        self.load_literal(
+            sym,
+            &U32_LAYOUT,
+            &Literal::Int((alignment as i128).to_ne_bytes()),
+        );
+    }
+
+    /// Loads the stack size of `layout` into `sym` as a U64, which is how the zig builtins expect it.
+    fn load_layout_stack_size(&mut self, sym: &Symbol, layout: &Layout<'a>) {
+        let stack_size = layout.stack_size(self.storage_manager.target_info());
+        self.load_literal(
+            sym,
+            &U64_LAYOUT,
+            &Literal::Int((stack_size as i128).to_ne_bytes()),
+        );
+    }
+
+    /// Loads the `Immutable` update mode into `sym`.
+    fn load_update_mode_immutable(&mut self, sym: &Symbol) {
+        self.load_literal(sym, &U8_LAYOUT, &Literal::Int(0i128.to_ne_bytes()));
+    }
+
+    /// Loads the address of `src` into `dst`. Zero sized symbols get a null pointer.
+    fn load_symbol_address(&mut self, dst: &Symbol, src: &Symbol, layout: &Layout<'a>) {
+        let dst_reg = self.storage_manager.claim_general_reg(&mut self.buf, dst);
+        if layout.stack_size(self.storage_manager.target_info()) == 0 {
+            ASM::mov_reg64_imm64(&mut self.buf, dst_reg, 0);
+        } else {
+            self.storage_manager
+                .ensure_symbol_on_stack(&mut self.buf, src);
+            let (offset, _) = self.storage_manager.stack_offset_and_size(src);
+            ASM::add_reg64_reg64_imm32(&mut self.buf, dst_reg, CC::BASE_PTR_REG, offset);
+        }
+    }
+
+    /// Loads a pointer to the function the zig builtins call to decrement the elements they drop.
+    fn load_element_dec_function(&mut self, sym: &Symbol, elem_layout: &Layout<'a>) {
+        if elem_layout.contains_refcounted() {
+            let dec_proc = self.refcount_proc_name(*elem_layout, HelperOp::Dec);
+            let kind = WrapperKind::Dec {
+                dec_proc: dec_proc.clone(),
+                layout: *elem_layout,
+            };
+            self.load_wrapper_proc_pointer(sym, format!("{}_wrapper", dec_proc), kind);
+        } else {
+            let reg = self.storage_manager.claim_general_reg(&mut self.buf, sym);
+            ASM::function_pointer(
+                &mut self.buf,
+                &mut self.relocs,
+                bitcode::UTILS_DEC_NOOP.to_string(),
+                reg,
+            );
+        }
+    }
+
+    /// Loads a pointer to the function the zig builtins call to increment the closure data.
+    fn load_closure_inc_n_function(&mut self, sym: &Symbol, closure_layout: Option<Layout<'a>>) {
+        match closure_layout {
+            Some(layout) if layout.contains_refcounted() => {
+                let inc_proc = self.refcount_proc_name(layout, HelperOp::Inc);
+                let kind = WrapperKind::IncN {
+                    inc_proc: Some(inc_proc.clone()),
+                    layout,
+                };
+                self.load_wrapper_proc_pointer(sym, format!("{}_wrapper", inc_proc), kind);
+            }
+            _ => {
+                let kind = WrapperKind::IncN {
+                    inc_proc: None,
+                    layout: Layout::UNIT,
+                };
+                self.load_wrapper_proc_pointer(sym, "dev_inc_n_noop".to_string(), kind);
+            }
+        }
+    }
+
+    /// Loads a pointer to a wrapper proc into `sym`.
+    /// The wrapper is built along with the helper procs once all procs of the module are built.
+    fn load_wrapper_proc_pointer(&mut self, sym: &Symbol, name: String, kind: WrapperKind<'a>) {
+        if !self
+            .wrapper_procs
+            .iter()
+            .any(|wrapper| wrapper.name == name)
+        {
+            let symbol = self.unique_symbol();
+            self.wrapper_procs.push(WrapperProc {
+                symbol,
+                name: name.clone(),
+                kind,
+            });
+        }
+        let reg = self.storage_manager.claim_general_reg(&mut self.buf, sym);
+        ASM::function_pointer(&mut self.buf, &mut self.relocs, name, reg);
+    }
+
+    /// Generates the refcounting helper proc for `layout` and returns its name.
+    fn refcount_proc_name(&mut self, layout: Layout<'a>, op: HelperOp) -> String {
+        let ident_ids = self
+            .interns
+            .all_ident_ids
+            .get_mut(&self.env.module_id)
+            .unwrap();
+        let (proc_symbol, new_specializations) = self
+            .helper_proc_gen
+            .gen_refcount_proc(ident_ids, layout, op);
+        self.helper_proc_symbols.extend(new_specializations);
+        let layout_id = LayoutIds::default().get(proc_symbol, &Layout::UNIT);
+        self.symbol_to_string(proc_symbol, layout_id)
+    }
+
+    /// Creates a new symbol that is unique within the module.
+    fn unique_symbol(&mut self) -> Symbol {
+        let ident_ids = self
+            .interns
+            .all_ident_ids
+            .get_mut(&self.env.module_id)
+            .unwrap();
+        Symbol::new(self.env.module_id, ident_ids.gen_unique())
+    }
+}
+
+/// Returns the layouts of the fields of a tag in a recursive union.
+fn union_field_layouts<'a>(
+    union_layout: &UnionLayout<'a>,
+    tag_id: TagIdIntType,
+) -> &'a [Layout<'a>] {
+    match union_layout {
+        UnionLayout::NonRecursive(tag_layouts) | UnionLayout::Recursive(tag_layouts) => {
+            tag_layouts[tag_id as usize]
+        }
+        UnionLayout::NonNullableUnwrapped(field_layouts) => field_layouts,
+        UnionLayout::NullableWrapped {
+            nullable_id,
+            other_tags,
+        } => {
+            // The nullable tag has no fields, so it is left out of the other tags.
+            debug_assert_ne!(tag_id, *nullable_id);
+            if tag_id < *nullable_id {
+                other_tags[tag_id as usize]
+            } else {
+                other_tags[tag_id as usize - 1]
+            }
+        }
+        UnionLayout::NullableUnwrapped { other_fields, .. } => other_fields,
+    }
+}
+
+/// Returns the layout of the elements of a list.
+fn list_element_layout<'a>(list_layout: &Layout<'a>) -> &'a Layout<'a> {
+    match list_layout {
+        Layout::Builtin(Builtin::List(elem_layout)) => elem_layout,
+        x => internal_error!("Expected a list layout, found: {:?}", x),
+    }
 }
 
 #[macro_export]
@@ -1445,7 +3172,15 @@ macro_rules! single_register_int_builtins {
 #[macro_export]
 macro_rules! single_register_integers {
     () => {
-        Layout::Builtin(Builtin::Bool | single_register_int_builtins!()) | Layout::RecursivePointer
+        Layout::Builtin(Builtin::Bool | single_register_int_builtins!())
+            | Layout::RecursivePointer
+            | Layout::Boxed(_)
+            | Layout::Union(
+                UnionLayout::Recursive(_)
+                    | UnionLayout::NonNullableUnwrapped(_)
+                    | UnionLayout::NullableWrapped { .. }
+                    | UnionLayout::NullableUnwrapped { .. },
+            )
     };
 }
 
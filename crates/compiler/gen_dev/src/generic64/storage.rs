@@ -398,18 +398,14 @@ impl<
             }
             Stack(ReferencedPrimitive {
                 base_offset, size, ..
-            }) if base_offset % 8 == 0 && size == 8 => {
-                // The primitive is aligned and the data is exactly 8 bytes, treat it like regular stack.
+            }) => {
                 let reg = self.get_float_reg(buf);
-                ASM::mov_freg64_base32(buf, reg, base_offset);
+                Self::load_referenced_float(buf, reg, base_offset, size);
                 self.float_used_regs.push((reg, *sym));
                 self.symbol_storage_map.insert(*sym, Reg(Float(reg)));
                 self.free_reference(sym);
                 reg
             }
-            Stack(ReferencedPrimitive { .. }) => {
-                todo!("loading referenced primitives")
-            }
             Stack(Complex { .. }) => {
                 internal_error!("Cannot load large values into float registers: {}", sym)
             }
@@ -456,13 +452,15 @@ impl<
                 ASM::mov_reg64_base32(buf, reg, *base_offset);
             }
             Stack(ReferencedPrimitive {
-                base_offset, size, ..
-            }) if base_offset % 8 == 0 && *size == 8 => {
-                // The primitive is aligned and the data is exactly 8 bytes, treat it like regular stack.
-                ASM::mov_reg64_base32(buf, reg, *base_offset);
-            }
-            Stack(ReferencedPrimitive { .. }) => {
-                todo!("loading referenced primitives")
+                base_offset,
+                size,
+                sign_extend,
+            }) => {
+                if *sign_extend {
+                    ASM::movsx_reg64_base32(buf, reg, *base_offset, *size as u8);
+                } else {
+                    ASM::movzx_reg64_base32(buf, reg, *base_offset, *size as u8);
+                }
             }
             Stack(Complex { .. }) => {
                 internal_error!("Cannot load large values into general registers: {}", sym)
@@ -506,12 +504,8 @@ impl<
             }
             Stack(ReferencedPrimitive {
                 base_offset, size, ..
-            }) if base_offset % 8 == 0 && *size == 8 => {
-                // The primitive is aligned and the data is exactly 8 bytes, treat it like regular stack.
-                ASM::mov_freg64_base32(buf, reg, *base_offset);
-            }
-            Stack(ReferencedPrimitive { .. }) => {
-                todo!("loading referenced primitives")
+            }) => {
+                Self::load_referenced_float(buf, reg, *base_offset, *size);
             }
             Stack(Complex { .. }) => {
                 internal_error!("Cannot load large values into float registers: {}", sym)
@@ -522,6 +516,126 @@ impl<
         }
     }
 
+    /// Loads a float that is part of a larger structure on the stack.
+    fn load_referenced_float(buf: &mut Vec<'a, u8>, reg: FloatReg, base_offset: i32, size: u32) {
+        match size {
+            8 => ASM::mov_freg64_base32(buf, reg, base_offset),
+            4 => ASM::mov_freg32_mem32_offset32(buf, reg, CC::BASE_PTR_REG, base_offset),
+            x => internal_error!("Invalid size for a referenced float: {}", x),
+        }
+    }
+
+    /// Loads a value with the specified layout from `ptr_reg + offset` into `sym`.
+    /// Primitives are loaded into a register. Everything else is copied to the stack.
+    /// `ptr_reg` must be a temporary register so that it can not be reclaimed while loading.
+    pub fn load_to_symbol_from_ptr(
+        &mut self,
+        buf: &mut Vec<'a, u8>,
+        sym: &Symbol,
+        layout: &Layout<'a>,
+        ptr_reg: GeneralReg,
+        offset: i32,
+    ) {
+        match layout {
+            single_register_integers!() => {
+                let size = layout.stack_size(self.target_info) as u8;
+                let reg = self.claim_general_reg(buf, sym);
+                if matches!(layout, Layout::Builtin(sign_extended_int_builtins!())) {
+                    ASM::movsx_reg64_mem64_offset32(buf, reg, ptr_reg, offset, size);
+                } else {
+                    ASM::movzx_reg64_mem64_offset32(buf, reg, ptr_reg, offset, size);
+                }
+            }
+            Layout::Builtin(Builtin::Float(FloatWidth::F64)) => {
+                let reg = self.claim_float_reg(buf, sym);
+                ASM::mov_freg64_mem64_offset32(buf, reg, ptr_reg, offset);
+            }
+            Layout::Builtin(Builtin::Float(FloatWidth::F32)) => {
+                let reg = self.claim_float_reg(buf, sym);
+                ASM::mov_freg32_mem32_offset32(buf, reg, ptr_reg, offset);
+            }
+            _ => match layout.stack_size(self.target_info) {
+                0 => self.no_data(sym),
+                size => {
+                    let base_offset = self.claim_stack_area(sym, size);
+                    self.copy_bytes(buf, CC::BASE_PTR_REG, base_offset, ptr_reg, offset, size);
+                }
+            },
+        }
+    }
+
+    /// Copies the value of `sym` with the specified layout to `ptr_reg + offset`.
+    /// Only the bytes of the layout are written.
+    /// `ptr_reg` must be a temporary register so that it can not be reclaimed while copying.
+    pub fn copy_symbol_to_ptr(
+        &mut self,
+        buf: &mut Vec<'a, u8>,
+        ptr_reg: GeneralReg,
+        offset: i32,
+        sym: &Symbol,
+        layout: &Layout<'a>,
+    ) {
+        match layout {
+            single_register_integers!() => {
+                let size = layout.stack_size(self.target_info) as u8;
+                let reg = self.load_to_general_reg(buf, sym);
+                ASM::mov_sized_mem64_offset32_reg64(buf, ptr_reg, offset, reg, size);
+            }
+            Layout::Builtin(Builtin::Float(FloatWidth::F64)) => {
+                let reg = self.load_to_float_reg(buf, sym);
+                ASM::mov_mem64_offset32_freg64(buf, ptr_reg, offset, reg);
+            }
+            Layout::Builtin(Builtin::Float(FloatWidth::F32)) => {
+                let reg = self.load_to_float_reg(buf, sym);
+                ASM::mov_mem32_offset32_freg32(buf, ptr_reg, offset, reg);
+            }
+            _ if layout.stack_size(self.target_info) == 0 => {}
+            _ => {
+                let (from_offset, size) = self.stack_offset_and_size(sym);
+                debug_assert_eq!(size, layout.stack_size(self.target_info));
+                self.copy_bytes(buf, ptr_reg, offset, CC::BASE_PTR_REG, from_offset, size);
+            }
+        }
+    }
+
+    /// Copies `size` bytes from `src_reg + src_offset` to `dst_reg + dst_offset`.
+    /// It uses the largest moves that fit in the remaining bytes.
+    fn copy_bytes(
+        &mut self,
+        buf: &mut Vec<'a, u8>,
+        dst_reg: GeneralReg,
+        dst_offset: i32,
+        src_reg: GeneralReg,
+        src_offset: i32,
+        size: u32,
+    ) {
+        self.with_tmp_general_reg(buf, |_storage_manager, buf, tmp_reg| {
+            let mut copied = 0;
+            while copied < size {
+                let remaining = size - copied;
+                let chunk = if remaining >= 8 {
+                    8
+                } else if remaining >= 4 {
+                    4
+                } else if remaining >= 2 {
+                    2
+                } else {
+                    1
+                };
+                let i = copied as i32;
+                ASM::movzx_reg64_mem64_offset32(buf, tmp_reg, src_reg, src_offset + i, chunk as u8);
+                ASM::mov_sized_mem64_offset32_reg64(
+                    buf,
+                    dst_reg,
+                    dst_offset + i,
+                    tmp_reg,
+                    chunk as u8,
+                );
+                copied += chunk;
+            }
+        });
+    }
+
     /// Loads a field from a struct or tag union.
     /// This is lazy by default. It will not copy anything around.
     pub fn load_field_at_index(
@@ -627,6 +741,22 @@ impl<
         );
     }
 
+    // Loads the dst to be the last 64 bits of a list (its capacity).
+    pub fn list_capacity(&mut self, _buf: &mut Vec<'a, u8>, dst: &Symbol, list: &Symbol) {
+        let owned_data = self.remove_allocation_for_sym(list);
+        self.allocation_map.insert(*list, Rc::clone(&owned_data));
+        self.allocation_map.insert(*dst, owned_data);
+        let (list_offset, _) = self.stack_offset_and_size(list);
+        self.symbol_storage_map.insert(
+            *dst,
+            Stack(ReferencedPrimitive {
+                base_offset: list_offset + 16,
+                size: 8,
+                sign_extend: false,
+            }),
+        );
+    }
+
     /// Creates a struct on the stack, moving the data in fields into the struct.
     pub fn create_struct(
         &mut self,
@@ -670,9 +800,7 @@ impl<
                 let (data_size, data_alignment) =
                     union_layout.data_size_and_alignment(self.target_info);
                 let id_offset = data_size - data_alignment;
-                if data_alignment < 8 || data_alignment % 8 != 0 {
-                    todo!("small/unaligned tagging");
-                }
+                let id_size = union_layout.discriminant().stack_size();
                 let base_offset = self.claim_stack_area(sym, data_size);
                 let mut current_offset = base_offset;
                 for (field, field_layout) in
@@ -684,8 +812,13 @@ impl<
                 }
                 self.with_tmp_general_reg(buf, |_symbol_storage, buf, reg| {
                     ASM::mov_reg64_imm64(buf, reg, tag_id as i64);
-                    debug_assert!((base_offset + id_offset as i32) % 8 == 0);
-                    ASM::mov_base32_reg64(buf, base_offset + id_offset as i32, reg);
+                    ASM::mov_sized_mem64_offset32_reg64(
+                        buf,
+                        CC::BASE_PTR_REG,
+                        base_offset + id_offset as i32,
+                        reg,
+                        id_size as u8,
+                    );
                 });
             }
             x => todo!("creating unions with layout: {:?}", x),
@@ -714,7 +847,7 @@ impl<
     /// Copies a symbol to the specified stack offset. This is used for things like filling structs.
     /// The offset is not guarenteed to be perfectly aligned, it follows Roc's alignment plan.
     /// This means that, for example 2 I32s might be back to back on the stack.
-    /// Always interact with the stack using aligned 64bit movement.
+    /// Only the bytes of the layout are written, so neighbouring fields are left untouched.
     pub fn copy_symbol_to_stack_offset(
         &mut self,
         buf: &mut Vec<'a, u8>,
@@ -722,36 +855,11 @@ impl<
         sym: &Symbol,
         layout: &Layout<'a>,
     ) {
-        match layout {
-            Layout::Builtin(Builtin::Int(IntWidth::I64 | IntWidth::U64)) => {
-                debug_assert_eq!(to_offset % 8, 0);
-                let reg = self.load_to_general_reg(buf, sym);
-                ASM::mov_base32_reg64(buf, to_offset, reg);
-            }
-            Layout::Builtin(Builtin::Float(FloatWidth::F64)) => {
-                debug_assert_eq!(to_offset % 8, 0);
-                let reg = self.load_to_float_reg(buf, sym);
-                ASM::mov_base32_freg64(buf, to_offset, reg);
-            }
-            _ if layout.stack_size(self.target_info) == 0 => {}
-            // TODO: Verify this is always true.
-            // The dev backend does not deal with refcounting and does not care about if data is safe to memcpy.
-            // It is just temporarily storing the value due to needing to free registers.
-            // Later, it will be reloaded and stored in refcounted as needed.
-            _ if layout.stack_size(self.target_info) > 8 => {
-                let (from_offset, size) = self.stack_offset_and_size(sym);
-                debug_assert!(from_offset % 8 == 0);
-                debug_assert!(size % 8 == 0);
-                debug_assert_eq!(size, layout.stack_size(self.target_info));
-                self.with_tmp_general_reg(buf, |_storage_manager, buf, reg| {
-                    for i in (0..size as i32).step_by(8) {
-                        ASM::mov_reg64_base32(buf, reg, from_offset + i);
-                        ASM::mov_base32_reg64(buf, to_offset + i, reg);
-                    }
-                });
-            }
-            x => todo!("copying data to the stack with layout, {:?}", x),
-        }
+        // TODO: Verify this is always true.
+        // The dev backend does not deal with refcounting and does not care about if data is safe to memcpy.
+        // It is just temporarily storing the value due to needing to free registers.
+        // Later, it will be reloaded and stored in refcounted as needed.
+        self.copy_symbol_to_ptr(buf, CC::BASE_PTR_REG, to_offset, sym, layout);
     }
 
     #[allow(dead_code)]
@@ -956,6 +1064,11 @@ impl<
 
     /// Specifies a no data exists.
     pub fn no_data_arg(&mut self, sym: &Symbol) {
+        self.no_data(sym);
+    }
+
+    /// Specifies a symbol has no data, because its layout is zero sized.
+    pub fn no_data(&mut self, sym: &Symbol) {
         self.symbol_storage_map.insert(*sym, NoData);
     }
 
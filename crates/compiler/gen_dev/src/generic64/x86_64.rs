@@ -7,7 +7,7 @@ use bumpalo::collections::Vec;
 use roc_builtins::bitcode::{FloatWidth, IntWidth};
 use roc_error_macros::internal_error;
 use roc_module::symbol::Symbol;
-use roc_mono::layout::{Builtin, Layout};
+use roc_mono::layout::{Builtin, Layout, UnionLayout};
 use roc_target::TargetInfo;
 
 const TARGET_INFO: TargetInfo = TargetInfo::default_x86_64();
@@ -252,7 +252,7 @@ impl CallConv<X86_64GeneralReg, X86_64FloatReg, X86_64Assembler> for X86_64Syste
 
     #[inline(always)]
     fn load_args<'a>(
-        _buf: &mut Vec<'a, u8>,
+        buf: &mut Vec<'a, u8>,
         storage_manager: &mut StorageManager<
             'a,
             X86_64GeneralReg,
@@ -300,7 +300,40 @@ impl CallConv<X86_64GeneralReg, X86_64FloatReg, X86_64Assembler> for X86_64Syste
                     arg_offset += stack_size as i32;
                 }
                 x => {
-                    todo!("Loading args with layout {:?}", x);
+                    let classes = Self::eightbyte_classes(x);
+                    let general_count = count_class(classes, EightbyteClass::Integer);
+                    let float_count = count_class(classes, EightbyteClass::Sse);
+                    if general_i + general_count <= Self::GENERAL_PARAM_REGS.len()
+                        && float_i + float_count <= Self::FLOAT_PARAM_REGS.len()
+                    {
+                        let base_offset = storage_manager.claim_stack_area(sym, stack_size);
+                        for (i, class) in classes.iter().enumerate() {
+                            let offset = base_offset + 8 * i as i32;
+                            match class {
+                                EightbyteClass::Integer => {
+                                    X86_64Assembler::mov_base32_reg64(
+                                        buf,
+                                        offset,
+                                        Self::GENERAL_PARAM_REGS[general_i],
+                                    );
+                                    general_i += 1;
+                                }
+                                EightbyteClass::Sse => {
+                                    X86_64Assembler::mov_base32_freg64(
+                                        buf,
+                                        offset,
+                                        Self::FLOAT_PARAM_REGS[float_i],
+                                    );
+                                    float_i += 1;
+                                }
+                            }
+                        }
+                    } else {
+                        // Without enough registers for every eightbyte, the whole value goes on the stack.
+                        arg_offset = align_stack_arg(arg_offset, x);
+                        storage_manager.complex_stack_arg(sym, arg_offset, stack_size);
+                        arg_offset += round_up_to_8(stack_size) as i32;
+                    }
                 }
             }
         }
@@ -408,7 +441,53 @@ impl CallConv<X86_64GeneralReg, X86_64FloatReg, X86_64Assembler> for X86_64Syste
                     tmp_stack_offset += size as i32;
                 }
                 x => {
-                    todo!("calling with arg type, {:?}", x);
+                    let classes = Self::eightbyte_classes(x);
+                    let general_count = count_class(classes, EightbyteClass::Integer);
+                    let float_count = count_class(classes, EightbyteClass::Sse);
+                    let (base_offset, size) = storage_manager.stack_offset_and_size(sym);
+                    debug_assert_eq!(base_offset % 8, 0);
+                    if general_i + general_count <= Self::GENERAL_PARAM_REGS.len()
+                        && float_i + float_count <= Self::FLOAT_PARAM_REGS.len()
+                    {
+                        for (i, class) in classes.iter().enumerate() {
+                            let offset = base_offset + 8 * i as i32;
+                            match class {
+                                EightbyteClass::Integer => {
+                                    X86_64Assembler::mov_reg64_base32(
+                                        buf,
+                                        Self::GENERAL_PARAM_REGS[general_i],
+                                        offset,
+                                    );
+                                    general_i += 1;
+                                }
+                                EightbyteClass::Sse => {
+                                    X86_64Assembler::mov_freg64_base32(
+                                        buf,
+                                        Self::FLOAT_PARAM_REGS[float_i],
+                                        offset,
+                                    );
+                                    float_i += 1;
+                                }
+                            }
+                        }
+                    } else {
+                        // Without enough registers for every eightbyte, the whole value goes on the stack.
+                        // Use return reg as buffer because it will be empty right now.
+                        tmp_stack_offset = align_stack_arg(tmp_stack_offset, x);
+                        for i in (0..size as i32).step_by(8) {
+                            X86_64Assembler::mov_reg64_base32(
+                                buf,
+                                Self::GENERAL_RETURN_REGS[0],
+                                base_offset + i,
+                            );
+                            X86_64Assembler::mov_stack32_reg64(
+                                buf,
+                                tmp_stack_offset + i,
+                                Self::GENERAL_RETURN_REGS[0],
+                            );
+                        }
+                        tmp_stack_offset += round_up_to_8(size) as i32;
+                    }
                 }
             }
         }
@@ -433,29 +512,30 @@ impl CallConv<X86_64GeneralReg, X86_64FloatReg, X86_64Assembler> for X86_64Syste
             }
             x if x.stack_size(TARGET_INFO) == 0 => {}
             x if !Self::returns_via_arg_pointer(x) => {
-                let (base_offset, size) = storage_manager.stack_offset_and_size(sym);
+                let (base_offset, _) = storage_manager.stack_offset_and_size(sym);
                 debug_assert_eq!(base_offset % 8, 0);
-                if size <= 8 {
-                    X86_64Assembler::mov_reg64_base32(
-                        buf,
-                        Self::GENERAL_RETURN_REGS[0],
-                        base_offset,
-                    );
-                } else if size <= 16 {
-                    X86_64Assembler::mov_reg64_base32(
-                        buf,
-                        Self::GENERAL_RETURN_REGS[0],
-                        base_offset,
-                    );
-                    X86_64Assembler::mov_reg64_base32(
-                        buf,
-                        Self::GENERAL_RETURN_REGS[1],
-                        base_offset + 8,
-                    );
-                } else {
-                    internal_error!(
-                        "types that don't return via arg pointer must be less than 16 bytes"
-                    );
+                let mut general_i = 0;
+                let mut float_i = 0;
+                for (i, class) in Self::eightbyte_classes(x).iter().enumerate() {
+                    let offset = base_offset + 8 * i as i32;
+                    match class {
+                        EightbyteClass::Integer => {
+                            X86_64Assembler::mov_reg64_base32(
+                                buf,
+                                Self::GENERAL_RETURN_REGS[general_i],
+                                offset,
+                            );
+                            general_i += 1;
+                        }
+                        EightbyteClass::Sse => {
+                            X86_64Assembler::mov_freg64_base32(
+                                buf,
+                                Self::FLOAT_RETURN_REGS[float_i],
+                                offset,
+                            );
+                            float_i += 1;
+                        }
+                    }
                 }
             }
             _ => {
@@ -490,20 +570,29 @@ impl CallConv<X86_64GeneralReg, X86_64FloatReg, X86_64Assembler> for X86_64Syste
             x if x.stack_size(TARGET_INFO) == 0 => {}
             x if !Self::returns_via_arg_pointer(x) => {
                 let size = layout.stack_size(TARGET_INFO);
-                let offset = storage_manager.claim_stack_area(sym, size);
-                if size <= 8 {
-                    X86_64Assembler::mov_base32_reg64(buf, offset, Self::GENERAL_RETURN_REGS[0]);
-                } else if size <= 16 {
-                    X86_64Assembler::mov_base32_reg64(buf, offset, Self::GENERAL_RETURN_REGS[0]);
-                    X86_64Assembler::mov_base32_reg64(
-                        buf,
-                        offset + 8,
-                        Self::GENERAL_RETURN_REGS[1],
-                    );
-                } else {
-                    internal_error!(
-                        "types that don't return via arg pointer must be less than 16 bytes"
-                    );
+                let base_offset = storage_manager.claim_stack_area(sym, size);
+                let mut general_i = 0;
+                let mut float_i = 0;
+                for (i, class) in Self::eightbyte_classes(x).iter().enumerate() {
+                    let offset = base_offset + 8 * i as i32;
+                    match class {
+                        EightbyteClass::Integer => {
+                            X86_64Assembler::mov_base32_reg64(
+                                buf,
+                                offset,
+                                Self::GENERAL_RETURN_REGS[general_i],
+                            );
+                            general_i += 1;
+                        }
+                        EightbyteClass::Sse => {
+                            X86_64Assembler::mov_base32_freg64(
+                                buf,
+                                offset,
+                                Self::FLOAT_RETURN_REGS[float_i],
+                            );
+                            float_i += 1;
+                        }
+                    }
                 }
             }
             _ => {
@@ -515,12 +604,79 @@ impl CallConv<X86_64GeneralReg, X86_64FloatReg, X86_64Assembler> for X86_64Syste
     }
 }
 
+/// The register class the System V ABI gives to each eightbyte of a value of at most 16 bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EightbyteClass {
+    /// Passed in a general register.
+    Integer,
+    /// Passed in a float register, because the eightbyte only holds floats.
+    Sse,
+}
+
 impl X86_64SystemV {
     fn returns_via_arg_pointer(ret_layout: &Layout) -> bool {
         // TODO: This will need to be more complex/extended to fully support the calling convention.
         // details here: https://github.com/hjl-tools/x86-psABI/wiki/x86-64-psABI-1.0.pdf
         ret_layout.stack_size(TARGET_INFO) > 16
     }
+
+    /// Classifies the eightbytes of a value that is passed or returned in registers.
+    fn eightbyte_classes(layout: &Layout) -> &'static [EightbyteClass] {
+        use EightbyteClass::*;
+
+        let size = layout.stack_size(TARGET_INFO);
+        debug_assert!(size <= 16);
+        let mut classes = [Sse, Sse];
+        Self::classify_eightbytes(layout, 0, &mut classes);
+        match (round_up_to_8(size) / 8, classes) {
+            (0, _) => &[],
+            (1, [Integer, _]) => &[Integer],
+            (1, [Sse, _]) => &[Sse],
+            (_, [Integer, Integer]) => &[Integer, Integer],
+            (_, [Integer, Sse]) => &[Integer, Sse],
+            (_, [Sse, Integer]) => &[Sse, Integer],
+            (_, [Sse, Sse]) => &[Sse, Sse],
+        }
+    }
+
+    /// An eightbyte is `Sse` if all the data in it is a float, so any other data makes it `Integer`.
+    fn classify_eightbytes(layout: &Layout, offset: u32, classes: &mut [EightbyteClass; 2]) {
+        match layout {
+            single_register_floats!() => {}
+            Layout::Struct { field_layouts, .. } => {
+                // Like the storage manager, fields are stored one after the other.
+                let mut field_offset = offset;
+                for field_layout in field_layouts.iter() {
+                    Self::classify_eightbytes(field_layout, field_offset, classes);
+                    field_offset += field_layout.stack_size(TARGET_INFO);
+                }
+            }
+            _ => {
+                let end = offset + layout.stack_size(TARGET_INFO);
+                for eightbyte in (offset / 8)..round_up_to_8(end) / 8 {
+                    classes[eightbyte as usize] = EightbyteClass::Integer;
+                }
+            }
+        }
+    }
+}
+
+fn count_class(classes: &[EightbyteClass], class: EightbyteClass) -> usize {
+    classes.iter().filter(|c| **c == class).count()
+}
+
+/// Values aligned to 16 bytes, like `I128`, start at a 16 byte aligned stack slot.
+fn align_stack_arg(offset: i32, layout: &Layout) -> i32 {
+    if layout.alignment_bytes(TARGET_INFO) > 8 {
+        (offset + 15) & !15
+    } else {
+        offset
+    }
+}
+
+#[inline(always)]
+fn round_up_to_8(size: u32) -> u32 {
+    (size + 7) & !7
 }
 
 impl CallConv<X86_64GeneralReg, X86_64FloatReg, X86_64Assembler> for X86_64WindowsFastcall {
@@ -989,6 +1145,23 @@ impl Assembler<X86_64GeneralReg, X86_64FloatReg> for X86_64Assembler {
         }
     }
 
+    #[inline(always)]
+    fn and_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        if dst == src1 {
+            and_reg64_reg64(buf, dst, src2);
+        } else if dst == src2 {
+            and_reg64_reg64(buf, dst, src1);
+        } else {
+            mov_reg64_reg64(buf, dst, src1);
+            and_reg64_reg64(buf, dst, src2);
+        }
+    }
+
     #[inline(always)]
     fn call(buf: &mut Vec<'_, u8>, relocs: &mut Vec<'_, Relocation>, fn_name: String) {
         buf.extend(&[0xE8, 0x00, 0x00, 0x00, 0x00]);
@@ -998,6 +1171,20 @@ impl Assembler<X86_64GeneralReg, X86_64FloatReg> for X86_64Assembler {
         });
     }
 
+    #[inline(always)]
+    fn function_pointer(
+        buf: &mut Vec<'_, u8>,
+        relocs: &mut Vec<'_, Relocation>,
+        fn_name: String,
+        dst: X86_64GeneralReg,
+    ) {
+        lea_reg64_rip_offset32(buf, dst, 0);
+        relocs.push(Relocation::LinkedFunction {
+            offset: buf.len() as u64 - 4,
+            name: fn_name,
+        });
+    }
+
     #[inline(always)]
     fn imul_reg64_reg64_reg64(
         buf: &mut Vec<'_, u8>,
@@ -1009,6 +1196,70 @@ impl Assembler<X86_64GeneralReg, X86_64FloatReg> for X86_64Assembler {
         imul_reg64_reg64(buf, dst, src2);
     }
 
+    #[inline(always)]
+    fn or_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        if dst == src1 {
+            or_reg64_reg64(buf, dst, src2);
+        } else if dst == src2 {
+            or_reg64_reg64(buf, dst, src1);
+        } else {
+            mov_reg64_reg64(buf, dst, src1);
+            or_reg64_reg64(buf, dst, src2);
+        }
+    }
+
+    #[inline(always)]
+    fn xor_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        if dst == src1 {
+            xor_reg64_reg64(buf, dst, src2);
+        } else if dst == src2 {
+            xor_reg64_reg64(buf, dst, src1);
+        } else {
+            mov_reg64_reg64(buf, dst, src1);
+            xor_reg64_reg64(buf, dst, src2);
+        }
+    }
+
+    #[inline(always)]
+    fn shl_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        shift_reg64_reg64_reg64(shl_reg64_cl, buf, dst, src1, src2);
+    }
+
+    #[inline(always)]
+    fn shr_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        shift_reg64_reg64_reg64(shr_reg64_cl, buf, dst, src1, src2);
+    }
+
+    #[inline(always)]
+    fn sar_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        shift_reg64_reg64_reg64(sar_reg64_cl, buf, dst, src1, src2);
+    }
+
     #[inline(always)]
     fn jmp_imm32(buf: &mut Vec<'_, u8>, offset: i32) -> usize {
         jmp_imm32(buf, offset);
@@ -1113,33 +1364,131 @@ impl Assembler<X86_64GeneralReg, X86_64FloatReg> for X86_64Assembler {
     }
 
     #[inline(always)]
-    fn movsx_reg64_base32(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, offset: i32, size: u8) {
+    fn movsx_reg64_mem64_offset32(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src: X86_64GeneralReg,
+        offset: i32,
+        size: u8,
+    ) {
         debug_assert!(size <= 8);
-        if size == 8 {
-            Self::mov_reg64_base32(buf, dst, offset);
-        } else if size == 4 {
-            todo!("sign extending 4 byte values");
-        } else if size == 2 {
-            todo!("sign extending 2 byte values");
-        } else if size == 1 {
-            todo!("sign extending 1 byte values");
-        } else {
-            internal_error!("Invalid size for sign extension: {}", size);
+        match size {
+            8 => mov_reg64_base64_offset32(buf, dst, src, offset),
+            4 => movsxd_reg64_base32_offset32(buf, dst, src, offset),
+            2 => movsx_reg64_base16_offset32(buf, dst, src, offset),
+            1 => movsx_reg64_base8_offset32(buf, dst, src, offset),
+            _ => internal_error!("Invalid size for sign extension: {}", size),
         }
     }
     #[inline(always)]
-    fn movzx_reg64_base32(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, offset: i32, size: u8) {
+    fn movzx_reg64_mem64_offset32(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src: X86_64GeneralReg,
+        offset: i32,
+        size: u8,
+    ) {
         debug_assert!(size <= 8);
-        if size == 8 {
-            Self::mov_reg64_base32(buf, dst, offset);
-        } else if size == 4 {
-            todo!("zero extending 4 byte values");
-        } else if size == 2 {
-            todo!("zero extending 2 byte values");
-        } else if size == 1 {
-            movzx_reg64_base8_offset32(buf, dst, X86_64GeneralReg::RBP, offset);
-        } else {
-            internal_error!("Invalid size for zero extension: {}", size);
+        match size {
+            8 => mov_reg64_base64_offset32(buf, dst, src, offset),
+            // Writing the 32 bit view of a register zeros the upper bits.
+            4 => mov_reg32_base32_offset32(buf, dst, src, offset),
+            2 => movzx_reg64_base16_offset32(buf, dst, src, offset),
+            1 => movzx_reg64_base8_offset32(buf, dst, src, offset),
+            _ => internal_error!("Invalid size for zero extension: {}", size),
+        }
+    }
+    #[inline(always)]
+    fn mov_sized_mem64_offset32_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        offset: i32,
+        src: X86_64GeneralReg,
+        size: u8,
+    ) {
+        match size {
+            8 => mov_base64_offset32_reg64(buf, dst, offset, src),
+            4 => mov_base32_offset32_reg32(buf, dst, offset, src),
+            2 => mov_base16_offset32_reg16(buf, dst, offset, src),
+            1 => mov_base8_offset32_reg8(buf, dst, offset, src),
+            _ => internal_error!("Invalid size for a sized move: {}", size),
+        }
+    }
+
+    #[inline(always)]
+    fn mov_freg64_mem64_offset32(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64FloatReg,
+        src: X86_64GeneralReg,
+        offset: i32,
+    ) {
+        movsd_freg64_base64_offset32(buf, dst, src, offset)
+    }
+    #[inline(always)]
+    fn mov_mem64_offset32_freg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        offset: i32,
+        src: X86_64FloatReg,
+    ) {
+        movsd_base64_offset32_freg64(buf, dst, offset, src)
+    }
+    #[inline(always)]
+    fn mov_freg32_mem32_offset32(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64FloatReg,
+        src: X86_64GeneralReg,
+        offset: i32,
+    ) {
+        movss_freg32_base32_offset32(buf, dst, src, offset)
+    }
+    #[inline(always)]
+    fn mov_mem32_offset32_freg32(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        offset: i32,
+        src: X86_64FloatReg,
+    ) {
+        movss_base32_offset32_freg32(buf, dst, offset, src)
+    }
+
+    #[inline(always)]
+    fn movsx_reg64_base32(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, offset: i32, size: u8) {
+        Self::movsx_reg64_mem64_offset32(buf, dst, X86_64GeneralReg::RBP, offset, size)
+    }
+    #[inline(always)]
+    fn movzx_reg64_base32(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, offset: i32, size: u8) {
+        Self::movzx_reg64_mem64_offset32(buf, dst, X86_64GeneralReg::RBP, offset, size)
+    }
+
+    #[inline(always)]
+    fn movsx_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src: X86_64GeneralReg,
+        size: u8,
+    ) {
+        match size {
+            8 => mov_reg64_reg64(buf, dst, src),
+            4 => movsxd_reg64_reg32(buf, dst, src),
+            2 => movsx_reg64_reg16(buf, dst, src),
+            1 => movsx_reg64_reg8(buf, dst, src),
+            _ => internal_error!("Invalid size for sign extension: {}", size),
+        }
+    }
+    #[inline(always)]
+    fn movzx_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src: X86_64GeneralReg,
+        size: u8,
+    ) {
+        match size {
+            8 => mov_reg64_reg64(buf, dst, src),
+            4 => mov_reg32_reg32(buf, dst, src),
+            2 => movzx_reg64_reg16(buf, dst, src),
+            1 => movzx_reg64_reg8(buf, dst, src),
+            _ => internal_error!("Invalid size for zero extension: {}", size),
         }
     }
 
@@ -1167,103 +1516,220 @@ impl Assembler<X86_64GeneralReg, X86_64FloatReg> for X86_64Assembler {
     }
 
     #[inline(always)]
-    fn sub_reg64_reg64_imm32(
+    fn sub_freg32_freg32_freg32(
         buf: &mut Vec<'_, u8>,
-        dst: X86_64GeneralReg,
-        src1: X86_64GeneralReg,
-        imm32: i32,
+        dst: X86_64FloatReg,
+        src1: X86_64FloatReg,
+        src2: X86_64FloatReg,
     ) {
-        mov_reg64_reg64(buf, dst, src1);
-        sub_reg64_imm32(buf, dst, imm32);
+        movss_freg32_freg32(buf, dst, src1);
+        subss_freg32_freg32(buf, dst, src2);
     }
+
     #[inline(always)]
-    fn sub_reg64_reg64_reg64(
+    fn sub_freg64_freg64_freg64(
         buf: &mut Vec<'_, u8>,
-        dst: X86_64GeneralReg,
-        src1: X86_64GeneralReg,
-        src2: X86_64GeneralReg,
+        dst: X86_64FloatReg,
+        src1: X86_64FloatReg,
+        src2: X86_64FloatReg,
     ) {
-        mov_reg64_reg64(buf, dst, src1);
-        sub_reg64_reg64(buf, dst, src2);
+        movsd_freg64_freg64(buf, dst, src1);
+        subsd_freg64_freg64(buf, dst, src2);
     }
 
     #[inline(always)]
-    fn eq_reg64_reg64_reg64(
+    fn mul_freg32_freg32_freg32(
         buf: &mut Vec<'_, u8>,
-        dst: X86_64GeneralReg,
-        src1: X86_64GeneralReg,
-        src2: X86_64GeneralReg,
+        dst: X86_64FloatReg,
+        src1: X86_64FloatReg,
+        src2: X86_64FloatReg,
     ) {
-        cmp_reg64_reg64(buf, src1, src2);
-        sete_reg64(buf, dst);
+        movss_freg32_freg32(buf, dst, src1);
+        mulss_freg32_freg32(buf, dst, src2);
     }
 
     #[inline(always)]
-    fn neq_reg64_reg64_reg64(
+    fn mul_freg64_freg64_freg64(
         buf: &mut Vec<'_, u8>,
-        dst: X86_64GeneralReg,
-        src1: X86_64GeneralReg,
-        src2: X86_64GeneralReg,
+        dst: X86_64FloatReg,
+        src1: X86_64FloatReg,
+        src2: X86_64FloatReg,
     ) {
-        cmp_reg64_reg64(buf, src1, src2);
-        setne_reg64(buf, dst);
+        movsd_freg64_freg64(buf, dst, src1);
+        mulsd_freg64_freg64(buf, dst, src2);
     }
 
     #[inline(always)]
-    fn lt_reg64_reg64_reg64(
+    fn div_freg32_freg32_freg32(
         buf: &mut Vec<'_, u8>,
-        dst: X86_64GeneralReg,
-        src1: X86_64GeneralReg,
-        src2: X86_64GeneralReg,
+        dst: X86_64FloatReg,
+        src1: X86_64FloatReg,
+        src2: X86_64FloatReg,
     ) {
-        cmp_reg64_reg64(buf, src1, src2);
-        setl_reg64(buf, dst);
-    }
-
-    #[inline(always)]
-    fn to_float_freg32_reg64(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64GeneralReg) {
-        cvtsi2ss_freg64_reg64(buf, dst, src);
-    }
-
-    #[inline(always)]
-    fn to_float_freg32_freg64(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64FloatReg) {
-        cvtsd2ss_freg32_freg64(buf, dst, src);
-    }
-
-    #[inline(always)]
-    fn to_float_freg64_freg32(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64FloatReg) {
-        cvtss2sd_freg64_freg32(buf, dst, src);
+        movss_freg32_freg32(buf, dst, src1);
+        divss_freg32_freg32(buf, dst, src2);
     }
 
     #[inline(always)]
-    fn to_float_freg64_reg64(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64GeneralReg) {
-        cvtsi2sd_freg64_reg64(buf, dst, src);
+    fn div_freg64_freg64_freg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64FloatReg,
+        src1: X86_64FloatReg,
+        src2: X86_64FloatReg,
+    ) {
+        movsd_freg64_freg64(buf, dst, src1);
+        divsd_freg64_freg64(buf, dst, src2);
     }
 
     #[inline(always)]
-    fn lte_reg64_reg64_reg64(
+    fn idiv_reg64_reg64_reg64(
         buf: &mut Vec<'_, u8>,
         dst: X86_64GeneralReg,
         src1: X86_64GeneralReg,
         src2: X86_64GeneralReg,
     ) {
-        cmp_reg64_reg64(buf, src1, src2);
-        setle_reg64(buf, dst);
+        div_reg64_reg64_reg64_help(buf, dst, src1, src2, true, false);
     }
 
     #[inline(always)]
-    fn gte_reg64_reg64_reg64(
+    fn udiv_reg64_reg64_reg64(
         buf: &mut Vec<'_, u8>,
         dst: X86_64GeneralReg,
         src1: X86_64GeneralReg,
         src2: X86_64GeneralReg,
     ) {
-        cmp_reg64_reg64(buf, src1, src2);
-        setge_reg64(buf, dst);
+        div_reg64_reg64_reg64_help(buf, dst, src1, src2, false, false);
     }
 
     #[inline(always)]
-    fn ret(buf: &mut Vec<'_, u8>) {
+    fn irem_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        div_reg64_reg64_reg64_help(buf, dst, src1, src2, true, true);
+    }
+
+    #[inline(always)]
+    fn urem_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        div_reg64_reg64_reg64_help(buf, dst, src1, src2, false, true);
+    }
+
+    #[inline(always)]
+    fn sub_reg64_reg64_imm32(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        imm32: i32,
+    ) {
+        mov_reg64_reg64(buf, dst, src1);
+        sub_reg64_imm32(buf, dst, imm32);
+    }
+    #[inline(always)]
+    fn sub_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        mov_reg64_reg64(buf, dst, src1);
+        sub_reg64_reg64(buf, dst, src2);
+    }
+
+    #[inline(always)]
+    fn eq_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        cmp_reg64_reg64(buf, src1, src2);
+        sete_reg64(buf, dst);
+    }
+
+    #[inline(always)]
+    fn neq_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        cmp_reg64_reg64(buf, src1, src2);
+        setne_reg64(buf, dst);
+    }
+
+    #[inline(always)]
+    fn lt_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        cmp_reg64_reg64(buf, src1, src2);
+        setl_reg64(buf, dst);
+    }
+
+    #[inline(always)]
+    fn gt_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        cmp_reg64_reg64(buf, src1, src2);
+        setg_reg64(buf, dst);
+    }
+
+    #[inline(always)]
+    fn to_float_freg32_reg64(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64GeneralReg) {
+        cvtsi2ss_freg64_reg64(buf, dst, src);
+    }
+
+    #[inline(always)]
+    fn to_float_freg32_freg64(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64FloatReg) {
+        cvtsd2ss_freg32_freg64(buf, dst, src);
+    }
+
+    #[inline(always)]
+    fn to_float_freg64_freg32(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64FloatReg) {
+        cvtss2sd_freg64_freg32(buf, dst, src);
+    }
+
+    #[inline(always)]
+    fn to_float_freg64_reg64(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64GeneralReg) {
+        cvtsi2sd_freg64_reg64(buf, dst, src);
+    }
+
+    #[inline(always)]
+    fn lte_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        cmp_reg64_reg64(buf, src1, src2);
+        setle_reg64(buf, dst);
+    }
+
+    #[inline(always)]
+    fn gte_reg64_reg64_reg64(
+        buf: &mut Vec<'_, u8>,
+        dst: X86_64GeneralReg,
+        src1: X86_64GeneralReg,
+        src2: X86_64GeneralReg,
+    ) {
+        cmp_reg64_reg64(buf, src1, src2);
+        setge_reg64(buf, dst);
+    }
+
+    #[inline(always)]
+    fn ret(buf: &mut Vec<'_, u8>) {
         ret(buf);
     }
 }
@@ -1305,6 +1771,100 @@ fn add_reg_extension<T: RegTrait>(reg: T, byte: u8) -> u8 {
     }
 }
 
+// helper function for scalar SSE instructions like `ADDSD` and `DIVSS`
+#[inline(always)]
+fn sse_binop_freg_freg(
+    prefix: u8,
+    op_code: u8,
+    buf: &mut Vec<'_, u8>,
+    dst: X86_64FloatReg,
+    src: X86_64FloatReg,
+) {
+    let dst_high = dst as u8 > 7;
+    let dst_mod = dst as u8 % 8;
+    let src_high = src as u8 > 7;
+    let src_mod = src as u8 % 8;
+    if dst_high || src_high {
+        buf.extend(&[
+            prefix,
+            0x40 | ((dst_high as u8) << 2) | (src_high as u8),
+            0x0F,
+            op_code,
+            0xC0 | (dst_mod << 3) | (src_mod),
+        ])
+    } else {
+        buf.extend(&[prefix, 0x0F, op_code, 0xC0 | (dst_mod << 3) | (src_mod)])
+    }
+}
+
+// Division takes its dividend in `RDX:RAX` and leaves the quotient in `RAX` and the remainder in `RDX`.
+// `RAX`, `RCX` and `RDX` are saved on the stack around the division so that they can be used freely.
+#[inline(always)]
+fn div_reg64_reg64_reg64_help(
+    buf: &mut Vec<'_, u8>,
+    dst: X86_64GeneralReg,
+    src1: X86_64GeneralReg,
+    src2: X86_64GeneralReg,
+    signed: bool,
+    remainder: bool,
+) {
+    use X86_64GeneralReg::*;
+    push_reg64(buf, RDX);
+    push_reg64(buf, RAX);
+    push_reg64(buf, RCX);
+    push_reg64(buf, src2);
+    push_reg64(buf, src1);
+    pop_reg64(buf, RAX);
+    pop_reg64(buf, RCX);
+    if signed {
+        cqo(buf);
+        idiv_reg64(buf, RCX);
+    } else {
+        xor_reg64_reg64(buf, RDX, RDX);
+        div_reg64(buf, RCX);
+    }
+    let result = if remainder { RDX } else { RAX };
+    mov_reg64_reg64(buf, dst, result);
+    // Restore the saved registers, except for the one that now holds the result.
+    for reg in [RCX, RAX, RDX] {
+        if reg == dst {
+            add_reg64_imm32(buf, RSP, 8);
+        } else {
+            pop_reg64(buf, reg);
+        }
+    }
+}
+
+// The shift instructions take their shift amount in `CL`.
+// `RCX` is saved on the stack around the shift so that it can be used freely.
+#[inline(always)]
+fn shift_reg64_reg64_reg64(
+    shift_reg64_cl: fn(&mut Vec<'_, u8>, X86_64GeneralReg),
+    buf: &mut Vec<'_, u8>,
+    dst: X86_64GeneralReg,
+    src1: X86_64GeneralReg,
+    src2: X86_64GeneralReg,
+) {
+    use X86_64GeneralReg::*;
+    if dst == RCX {
+        // The result can't be computed in RCX, so compute it in RAX instead.
+        push_reg64(buf, RAX);
+        push_reg64(buf, src2);
+        mov_reg64_reg64(buf, RAX, src1);
+        pop_reg64(buf, RCX);
+        shift_reg64_cl(buf, RAX);
+        mov_reg64_reg64(buf, RCX, RAX);
+        pop_reg64(buf, RAX);
+    } else {
+        push_reg64(buf, RCX);
+        push_reg64(buf, src2);
+        mov_reg64_reg64(buf, dst, src1);
+        pop_reg64(buf, RCX);
+        shift_reg64_cl(buf, dst);
+        pop_reg64(buf, RCX);
+    }
+}
+
 #[inline(always)]
 fn binop_reg64_reg64(
     op_code: u8,
@@ -1424,6 +1984,12 @@ fn and_reg64_imm8(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, imm: i8) {
     buf.extend(&[rex, 0x83, 0xE0 | dst_mod, imm as u8]);
 }
 
+/// `AND r/m64,r64` -> Bitwise logical and r64 to r/m64.
+#[inline(always)]
+fn and_reg64_reg64(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
+    binop_reg64_reg64(0x21, buf, dst, src);
+}
+
 /// `CMOVL r64,r/m64` -> Move if less (SF≠ OF).
 #[inline(always)]
 fn cmovl_reg64_reg64(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
@@ -1434,6 +2000,12 @@ fn cmovl_reg64_reg64(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64Ge
     buf.extend(&[rex, 0x0F, 0x4C, 0xC0 | dst_mod | src_mod]);
 }
 
+/// `CQO` -> Sign extend RAX into RDX:RAX.
+#[inline(always)]
+fn cqo(buf: &mut Vec<'_, u8>) {
+    buf.extend(&[REX_W, 0x99]);
+}
+
 /// `CMP r/m64,i32` -> Compare i32 to r/m64.
 #[inline(always)]
 fn cmp_reg64_imm32(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, imm: i32) {
@@ -1457,6 +2029,34 @@ fn test_reg64_reg64(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64Gen
     binop_reg64_reg64(0x85, buf, dst, src);
 }
 
+/// `DIV r/m64` -> Unsigned divide RDX:RAX by r/m64, with result stored in RAX := Quotient, RDX := Remainder.
+#[inline(always)]
+fn div_reg64(buf: &mut Vec<'_, u8>, src: X86_64GeneralReg) {
+    let rex = add_rm_extension(src, REX_W);
+    let src_mod = src as u8 % 8;
+    buf.extend(&[rex, 0xF7, 0xF0 | src_mod]);
+}
+
+/// `DIVSD xmm1,xmm2/m64` -> Divide low double-precision floating-point value in xmm1 by low double-precision floating-point value in xmm2/m64.
+#[inline(always)]
+fn divsd_freg64_freg64(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64FloatReg) {
+    sse_binop_freg_freg(0xF2, 0x5E, buf, dst, src);
+}
+
+/// `DIVSS xmm1,xmm2/m32` -> Divide low single-precision floating-point value in xmm1 by low single-precision floating-point value in xmm2/m32.
+#[inline(always)]
+fn divss_freg32_freg32(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64FloatReg) {
+    sse_binop_freg_freg(0xF3, 0x5E, buf, dst, src);
+}
+
+/// `IDIV r/m64` -> Signed divide RDX:RAX by r/m64, with result stored in RAX := Quotient, RDX := Remainder.
+#[inline(always)]
+fn idiv_reg64(buf: &mut Vec<'_, u8>, src: X86_64GeneralReg) {
+    let rex = add_rm_extension(src, REX_W);
+    let src_mod = src as u8 % 8;
+    buf.extend(&[rex, 0xF7, 0xF8 | src_mod]);
+}
+
 /// `IMUL r64,r/m64` -> Signed Multiply r/m64 to r64.
 #[inline(always)]
 fn imul_reg64_reg64(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
@@ -1482,6 +2082,30 @@ fn jne_imm32(buf: &mut Vec<'_, u8>, imm: i32) {
     buf.extend(&imm.to_le_bytes());
 }
 
+/// `LEA r64,m` -> Store effective address for m in register r64, where m is rip + offset.
+#[inline(always)]
+fn lea_reg64_rip_offset32(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, offset: u32) {
+    let rex = add_reg_extension(dst, REX_W);
+    let dst_mod = dst as u8 % 8;
+    buf.reserve(7);
+    buf.extend(&[rex, 0x8D, 0x05 | (dst_mod << 3)]);
+    buf.extend(&offset.to_le_bytes());
+}
+
+/// `MOV r/m32,r32` -> Move r32 to r/m32, zeroing the upper 32 bits of the 64 bit register.
+#[inline(always)]
+fn mov_reg32_reg32(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
+    let rex = add_rm_extension(dst, REX);
+    let rex = add_reg_extension(src, rex);
+    let dst_mod = dst as u8 % 8;
+    let src_mod = (src as u8 % 8) << 3;
+    buf.reserve(3);
+    if rex != REX {
+        buf.push(rex);
+    }
+    buf.extend(&[0x89, 0xC0 | dst_mod | src_mod]);
+}
+
 /// `MOV r/m64, imm32` -> Move imm32 sign extended to 64-bits to r/m64.
 #[inline(always)]
 fn mov_reg64_imm32(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, imm: i32) {
@@ -1587,6 +2211,199 @@ fn movzx_reg64_base8_offset32(
     buf.extend(&offset.to_le_bytes());
 }
 
+// helper function for moves between a register and memory referenced by a base + offset.
+// Byte operands always need the rex byte to use the low 8 bits of RSP, RBP, RSI, and RDI.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+fn base_offset32_op<T: RegTrait>(
+    buf: &mut Vec<'_, u8>,
+    prefix: Option<u8>,
+    rex: u8,
+    op_code: &[u8],
+    byte_operand: bool,
+    reg: T,
+    base: X86_64GeneralReg,
+    offset: i32,
+) {
+    let rex = add_rm_extension(base, rex);
+    let rex = add_reg_extension(reg, rex);
+    let reg_mod = (reg.value() % 8) << 3;
+    let base_mod = base as u8 % 8;
+    buf.reserve(11);
+    if let Some(prefix) = prefix {
+        buf.push(prefix);
+    }
+    if rex != REX || (byte_operand && reg.value() > 3) {
+        buf.push(rex);
+    }
+    buf.extend(op_code);
+    buf.push(0x80 | reg_mod | base_mod);
+    // Using RSP or R12 requires a secondary index byte.
+    if base == X86_64GeneralReg::RSP || base == X86_64GeneralReg::R12 {
+        buf.push(0x24);
+    }
+    buf.extend(&offset.to_le_bytes());
+}
+
+/// `MOVZX r64,r/m16` -> Move r/m16 with zero extention to r64, where m16 references a base + offset.
+#[inline(always)]
+fn movzx_reg64_base16_offset32(
+    buf: &mut Vec<'_, u8>,
+    dst: X86_64GeneralReg,
+    base: X86_64GeneralReg,
+    offset: i32,
+) {
+    base_offset32_op(buf, None, REX_W, &[0x0F, 0xB7], false, dst, base, offset);
+}
+
+/// `MOV r32,r/m32` -> Move r/m32 to r32, where m32 references a base + offset.
+/// The upper 32 bits of the register are zeroed.
+#[inline(always)]
+fn mov_reg32_base32_offset32(
+    buf: &mut Vec<'_, u8>,
+    dst: X86_64GeneralReg,
+    base: X86_64GeneralReg,
+    offset: i32,
+) {
+    base_offset32_op(buf, None, REX, &[0x8B], false, dst, base, offset);
+}
+
+/// `MOVSX r64,r/m8` -> Move r/m8 with sign extention to r64, where m8 references a base + offset.
+#[inline(always)]
+fn movsx_reg64_base8_offset32(
+    buf: &mut Vec<'_, u8>,
+    dst: X86_64GeneralReg,
+    base: X86_64GeneralReg,
+    offset: i32,
+) {
+    base_offset32_op(buf, None, REX_W, &[0x0F, 0xBE], false, dst, base, offset);
+}
+
+/// `MOVSX r64,r/m16` -> Move r/m16 with sign extention to r64, where m16 references a base + offset.
+#[inline(always)]
+fn movsx_reg64_base16_offset32(
+    buf: &mut Vec<'_, u8>,
+    dst: X86_64GeneralReg,
+    base: X86_64GeneralReg,
+    offset: i32,
+) {
+    base_offset32_op(buf, None, REX_W, &[0x0F, 0xBF], false, dst, base, offset);
+}
+
+/// `MOVSXD r64,r/m32` -> Move r/m32 with sign extention to r64, where m32 references a base + offset.
+#[inline(always)]
+fn movsxd_reg64_base32_offset32(
+    buf: &mut Vec<'_, u8>,
+    dst: X86_64GeneralReg,
+    base: X86_64GeneralReg,
+    offset: i32,
+) {
+    base_offset32_op(buf, None, REX_W, &[0x63], false, dst, base, offset);
+}
+
+/// `MOV r/m32,r32` -> Move r32 to r/m32, where m32 references a base + offset.
+#[inline(always)]
+fn mov_base32_offset32_reg32(
+    buf: &mut Vec<'_, u8>,
+    base: X86_64GeneralReg,
+    offset: i32,
+    src: X86_64GeneralReg,
+) {
+    base_offset32_op(buf, None, REX, &[0x89], false, src, base, offset);
+}
+
+/// `MOV r/m16,r16` -> Move r16 to r/m16, where m16 references a base + offset.
+#[inline(always)]
+fn mov_base16_offset32_reg16(
+    buf: &mut Vec<'_, u8>,
+    base: X86_64GeneralReg,
+    offset: i32,
+    src: X86_64GeneralReg,
+) {
+    base_offset32_op(buf, Some(0x66), REX, &[0x89], false, src, base, offset);
+}
+
+/// `MOV r/m8,r8` -> Move r8 to r/m8, where m8 references a base + offset.
+#[inline(always)]
+fn mov_base8_offset32_reg8(
+    buf: &mut Vec<'_, u8>,
+    base: X86_64GeneralReg,
+    offset: i32,
+    src: X86_64GeneralReg,
+) {
+    base_offset32_op(buf, None, REX, &[0x88], true, src, base, offset);
+}
+
+/// `MOVSS xmm1,r/m32` -> Move r/m32 to xmm1, where m32 references a base + offset.
+#[inline(always)]
+fn movss_freg32_base32_offset32(
+    buf: &mut Vec<'_, u8>,
+    dst: X86_64FloatReg,
+    base: X86_64GeneralReg,
+    offset: i32,
+) {
+    base_offset32_op(
+        buf,
+        Some(0xF3),
+        REX,
+        &[0x0F, 0x10],
+        false,
+        dst,
+        base,
+        offset,
+    );
+}
+
+/// `MOVSS r/m32,xmm1` -> Move xmm1 to r/m32, where m32 references a base + offset.
+#[inline(always)]
+fn movss_base32_offset32_freg32(
+    buf: &mut Vec<'_, u8>,
+    base: X86_64GeneralReg,
+    offset: i32,
+    src: X86_64FloatReg,
+) {
+    base_offset32_op(
+        buf,
+        Some(0xF3),
+        REX,
+        &[0x0F, 0x11],
+        false,
+        src,
+        base,
+        offset,
+    );
+}
+
+/// `MOVSX r64,r/m8` -> Move byte to quadword with sign-extension.
+#[inline(always)]
+fn movsx_reg64_reg8(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
+    extended_binop_reg64_reg64(0x0F, 0xBE, buf, src, dst);
+}
+
+/// `MOVSX r64,r/m16` -> Move word to quadword with sign-extension.
+#[inline(always)]
+fn movsx_reg64_reg16(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
+    extended_binop_reg64_reg64(0x0F, 0xBF, buf, src, dst);
+}
+
+/// `MOVSXD r64,r/m32` -> Move doubleword to quadword with sign-extension.
+#[inline(always)]
+fn movsxd_reg64_reg32(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
+    binop_reg64_reg64(0x63, buf, src, dst);
+}
+
+/// `MOVZX r64,r/m8` -> Move byte to quadword with zero-extension.
+#[inline(always)]
+fn movzx_reg64_reg8(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
+    extended_binop_reg64_reg64(0x0F, 0xB6, buf, src, dst);
+}
+
+/// `MOVZX r64,r/m16` -> Move word to quadword with zero-extension.
+#[inline(always)]
+fn movzx_reg64_reg16(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
+    extended_binop_reg64_reg64(0x0F, 0xB7, buf, src, dst);
+}
+
 /// `MOVSD xmm1,xmm2` -> Move scalar double-precision floating-point value from xmm2 to xmm1 register.
 /// This will not generate anything if dst and src are the same.
 #[inline(always)]
@@ -1725,6 +2542,18 @@ fn movsd_freg64_base64_offset32(
     buf.extend(&offset.to_le_bytes());
 }
 
+/// `MULSD xmm1,xmm2/m64` -> Multiply the low double-precision floating-point value in xmm2/m64 by low double-precision floating-point value in xmm1.
+#[inline(always)]
+fn mulsd_freg64_freg64(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64FloatReg) {
+    sse_binop_freg_freg(0xF2, 0x59, buf, dst, src);
+}
+
+/// `MULSS xmm1,xmm2/m32` -> Multiply the low single-precision floating-point value in xmm2/m32 by the low single-precision floating-point value in xmm1.
+#[inline(always)]
+fn mulss_freg32_freg32(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64FloatReg) {
+    sse_binop_freg_freg(0xF3, 0x59, buf, dst, src);
+}
+
 /// `NEG r/m64` -> Two's complement negate r/m64.
 #[inline(always)]
 fn neg_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
@@ -1733,6 +2562,12 @@ fn neg_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
     buf.extend(&[rex, 0xF7, 0xD8 | reg_mod]);
 }
 
+/// `OR r/m64,r64` -> Bitwise logical or r64 to r/m64.
+#[inline(always)]
+fn or_reg64_reg64(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
+    binop_reg64_reg64(0x09, buf, dst, src);
+}
+
 // helper function for `set*` instructions
 #[inline(always)]
 fn set_reg64_help(op_code: u8, buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
@@ -1825,26 +2660,58 @@ fn cvttss2si_reg64_freg64(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86
 
 /// `SETNE r/m64` -> Set byte if not equal (ZF=0).
 #[inline(always)]
-fn setne_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
-    set_reg64_help(0x95, buf, reg);
+fn setne_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
+    set_reg64_help(0x95, buf, reg);
+}
+
+/// `SETL r/m64` -> Set byte if less (SF≠ OF).
+#[inline(always)]
+fn setl_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
+    set_reg64_help(0x9c, buf, reg);
+}
+
+/// `SETLE r/m64` -> Set byte if less or equal (ZF=1 or SF≠ OF).
+#[inline(always)]
+fn setle_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
+    set_reg64_help(0x9e, buf, reg);
+}
+
+/// `SETGE r/m64` -> Set byte if greater or equal (SF=OF).
+#[inline(always)]
+fn setge_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
+    set_reg64_help(0x9d, buf, reg);
+}
+
+/// `SETG r/m64` -> Set byte if greater (ZF=0 and SF=OF).
+#[inline(always)]
+fn setg_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
+    set_reg64_help(0x9f, buf, reg);
+}
+
+// helper function for `SHL`, `SHR` and `SAR` instructions that shift by `CL`
+#[inline(always)]
+fn shift_reg64_cl_help(op_code: u8, buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg) {
+    let rex = add_rm_extension(dst, REX_W);
+    let dst_mod = dst as u8 % 8;
+    buf.extend(&[rex, 0xD3, op_code | dst_mod]);
 }
 
-/// `SETL r/m64` -> Set byte if less (SF≠ OF).
+/// `SAR r/m64,CL` -> Signed divide r/m64 by 2, CL times.
 #[inline(always)]
-fn setl_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
-    set_reg64_help(0x9c, buf, reg);
+fn sar_reg64_cl(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg) {
+    shift_reg64_cl_help(0xF8, buf, dst);
 }
 
-/// `SETLE r/m64` -> Set byte if less or equal (ZF=1 or SF≠ OF).
+/// `SHL r/m64,CL` -> Multiply r/m64 by 2, CL times.
 #[inline(always)]
-fn setle_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
-    set_reg64_help(0x9e, buf, reg);
+fn shl_reg64_cl(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg) {
+    shift_reg64_cl_help(0xE0, buf, dst);
 }
 
-/// `SETGE r/m64` -> Set byte if greater or equal (SF=OF).
+/// `SHR r/m64,CL` -> Unsigned divide r/m64 by 2, CL times.
 #[inline(always)]
-fn setge_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
-    set_reg64_help(0x9d, buf, reg);
+fn shr_reg64_cl(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg) {
+    shift_reg64_cl_help(0xE8, buf, dst);
 }
 
 /// `RET` -> Near return to calling procedure.
@@ -1853,6 +2720,18 @@ fn ret(buf: &mut Vec<'_, u8>) {
     buf.push(0xC3);
 }
 
+/// `SUBSD xmm1,xmm2/m64` -> Subtract the low double-precision floating-point value in xmm2/m64 from xmm1.
+#[inline(always)]
+fn subsd_freg64_freg64(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64FloatReg) {
+    sse_binop_freg_freg(0xF2, 0x5C, buf, dst, src);
+}
+
+/// `SUBSS xmm1,xmm2/m32` -> Subtract the low single-precision floating-point value in xmm2/m32 from xmm1.
+#[inline(always)]
+fn subss_freg32_freg32(buf: &mut Vec<'_, u8>, dst: X86_64FloatReg, src: X86_64FloatReg) {
+    sse_binop_freg_freg(0xF3, 0x5C, buf, dst, src);
+}
+
 /// `SUB r/m64, imm32` -> Subtract imm32 sign-extended to 64-bits from r/m64.
 #[inline(always)]
 fn sub_reg64_imm32(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, imm: i32) {
@@ -1896,7 +2775,6 @@ fn push_reg64(buf: &mut Vec<'_, u8>, reg: X86_64GeneralReg) {
 
 /// `XOR r/m64,r64` -> Xor r64 to r/m64.
 #[inline(always)]
-#[allow(dead_code)]
 fn xor_reg64_reg64(buf: &mut Vec<'_, u8>, dst: X86_64GeneralReg, src: X86_64GeneralReg) {
     binop_reg64_reg64(0x31, buf, dst, src);
 }
@@ -1931,6 +2809,48 @@ mod tests {
                 X86_64GeneralReg::R15 => "r15b",
             }
         }
+
+        fn low_16bits_string(&self) -> &str {
+            match self {
+                X86_64GeneralReg::RAX => "ax",
+                X86_64GeneralReg::RBX => "bx",
+                X86_64GeneralReg::RCX => "cx",
+                X86_64GeneralReg::RDX => "dx",
+                X86_64GeneralReg::RBP => "bp",
+                X86_64GeneralReg::RSP => "sp",
+                X86_64GeneralReg::RDI => "di",
+                X86_64GeneralReg::RSI => "si",
+                X86_64GeneralReg::R8 => "r8w",
+                X86_64GeneralReg::R9 => "r9w",
+                X86_64GeneralReg::R10 => "r10w",
+                X86_64GeneralReg::R11 => "r11w",
+                X86_64GeneralReg::R12 => "r12w",
+                X86_64GeneralReg::R13 => "r13w",
+                X86_64GeneralReg::R14 => "r14w",
+                X86_64GeneralReg::R15 => "r15w",
+            }
+        }
+
+        fn low_32bits_string(&self) -> &str {
+            match self {
+                X86_64GeneralReg::RAX => "eax",
+                X86_64GeneralReg::RBX => "ebx",
+                X86_64GeneralReg::RCX => "ecx",
+                X86_64GeneralReg::RDX => "edx",
+                X86_64GeneralReg::RBP => "ebp",
+                X86_64GeneralReg::RSP => "esp",
+                X86_64GeneralReg::RDI => "edi",
+                X86_64GeneralReg::RSI => "esi",
+                X86_64GeneralReg::R8 => "r8d",
+                X86_64GeneralReg::R9 => "r9d",
+                X86_64GeneralReg::R10 => "r10d",
+                X86_64GeneralReg::R11 => "r11d",
+                X86_64GeneralReg::R12 => "r12d",
+                X86_64GeneralReg::R13 => "r13d",
+                X86_64GeneralReg::R14 => "r14d",
+                X86_64GeneralReg::R15 => "r15d",
+            }
+        }
     }
     const TEST_I32: i32 = 0x12345678;
     const TEST_I64: i64 = 0x1234_5678_9ABC_DEF0;
@@ -2056,6 +2976,155 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_and_reg64_reg64() {
+        disassembler_test!(
+            and_reg64_reg64,
+            |reg1, reg2| format!("and {}, {}", reg1, reg2),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_cqo() {
+        disassembler_test!(cqo, || "cqo");
+    }
+
+    #[test]
+    fn test_div_reg64() {
+        disassembler_test!(div_reg64, |reg| format!("div {}", reg), ALL_GENERAL_REGS);
+    }
+
+    #[test]
+    fn test_divsd_freg64_freg64() {
+        disassembler_test!(
+            divsd_freg64_freg64,
+            |reg1, reg2| format!("divsd {}, {}", reg1, reg2),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_divss_freg32_freg32() {
+        disassembler_test!(
+            divss_freg32_freg32,
+            |reg1, reg2| format!("divss {}, {}", reg1, reg2),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_idiv_reg64() {
+        disassembler_test!(idiv_reg64, |reg| format!("idiv {}", reg), ALL_GENERAL_REGS);
+    }
+
+    #[test]
+    fn test_lea_reg64_rip_offset32() {
+        disassembler_test!(
+            lea_reg64_rip_offset32,
+            |reg, imm| format!("lea {}, [rip + 0x{:x}]", reg, imm),
+            ALL_GENERAL_REGS,
+            [TEST_I32 as u32]
+        );
+    }
+
+    #[test]
+    fn test_mov_reg32_reg32() {
+        disassembler_test!(
+            mov_reg32_reg32,
+            |reg1: X86_64GeneralReg, reg2: X86_64GeneralReg| format!(
+                "mov {}, {}",
+                reg1.low_32bits_string(),
+                reg2.low_32bits_string()
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_movsx_reg64_reg8() {
+        disassembler_test!(
+            movsx_reg64_reg8,
+            |reg1, reg2: X86_64GeneralReg| format!("movsx {}, {}", reg1, reg2.low_8bits_string()),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_movsx_reg64_reg16() {
+        disassembler_test!(
+            movsx_reg64_reg16,
+            |reg1, reg2: X86_64GeneralReg| format!("movsx {}, {}", reg1, reg2.low_16bits_string()),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_movsxd_reg64_reg32() {
+        disassembler_test!(
+            movsxd_reg64_reg32,
+            |reg1, reg2: X86_64GeneralReg| format!("movsxd {}, {}", reg1, reg2.low_32bits_string()),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_movzx_reg64_reg8() {
+        disassembler_test!(
+            movzx_reg64_reg8,
+            |reg1, reg2: X86_64GeneralReg| format!("movzx {}, {}", reg1, reg2.low_8bits_string()),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_movzx_reg64_reg16() {
+        disassembler_test!(
+            movzx_reg64_reg16,
+            |reg1, reg2: X86_64GeneralReg| format!("movzx {}, {}", reg1, reg2.low_16bits_string()),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_mulsd_freg64_freg64() {
+        disassembler_test!(
+            mulsd_freg64_freg64,
+            |reg1, reg2| format!("mulsd {}, {}", reg1, reg2),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_mulss_freg32_freg32() {
+        disassembler_test!(
+            mulss_freg32_freg32,
+            |reg1, reg2| format!("mulss {}, {}", reg1, reg2),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_or_reg64_reg64() {
+        disassembler_test!(
+            or_reg64_reg64,
+            |reg1, reg2| format!("or {}, {}", reg1, reg2),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS
+        );
+    }
+
     #[test]
     fn test_cmovl_reg64_reg64() {
         disassembler_test!(
@@ -2197,6 +3266,136 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_movzx_reg64_base16_offset32() {
+        disassembler_test!(
+            movzx_reg64_base16_offset32,
+            |reg1, reg2, imm| format!("movzx {}, word ptr [{} + 0x{:x}]", reg1, reg2, imm),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [TEST_I32]
+        );
+    }
+
+    #[test]
+    fn test_mov_reg32_base32_offset32() {
+        disassembler_test!(
+            mov_reg32_base32_offset32,
+            |reg1: X86_64GeneralReg, reg2, imm| format!(
+                "mov {}, dword ptr [{} + 0x{:x}]",
+                reg1.low_32bits_string(),
+                reg2,
+                imm
+            ),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [TEST_I32]
+        );
+    }
+
+    #[test]
+    fn test_movsx_reg64_base8_offset32() {
+        disassembler_test!(
+            movsx_reg64_base8_offset32,
+            |reg1, reg2, imm| format!("movsx {}, byte ptr [{} + 0x{:x}]", reg1, reg2, imm),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [TEST_I32]
+        );
+    }
+
+    #[test]
+    fn test_movsx_reg64_base16_offset32() {
+        disassembler_test!(
+            movsx_reg64_base16_offset32,
+            |reg1, reg2, imm| format!("movsx {}, word ptr [{} + 0x{:x}]", reg1, reg2, imm),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [TEST_I32]
+        );
+    }
+
+    #[test]
+    fn test_movsxd_reg64_base32_offset32() {
+        disassembler_test!(
+            movsxd_reg64_base32_offset32,
+            |reg1, reg2, imm| format!("movsxd {}, dword ptr [{} + 0x{:x}]", reg1, reg2, imm),
+            ALL_GENERAL_REGS,
+            ALL_GENERAL_REGS,
+            [TEST_I32]
+        );
+    }
+
+    #[test]
+    fn test_mov_base32_offset32_reg32() {
+        disassembler_test!(
+            mov_base32_offset32_reg32,
+            |reg1, imm, reg2: X86_64GeneralReg| format!(
+                "mov dword ptr [{} + 0x{:x}], {}",
+                reg1,
+                imm,
+                reg2.low_32bits_string()
+            ),
+            ALL_GENERAL_REGS,
+            [TEST_I32],
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_mov_base16_offset32_reg16() {
+        disassembler_test!(
+            mov_base16_offset32_reg16,
+            |reg1, imm, reg2: X86_64GeneralReg| format!(
+                "mov word ptr [{} + 0x{:x}], {}",
+                reg1,
+                imm,
+                reg2.low_16bits_string()
+            ),
+            ALL_GENERAL_REGS,
+            [TEST_I32],
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_mov_base8_offset32_reg8() {
+        disassembler_test!(
+            mov_base8_offset32_reg8,
+            |reg1, imm, reg2: X86_64GeneralReg| format!(
+                "mov byte ptr [{} + 0x{:x}], {}",
+                reg1,
+                imm,
+                reg2.low_8bits_string()
+            ),
+            ALL_GENERAL_REGS,
+            [TEST_I32],
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_movss_freg32_base32_offset32() {
+        disassembler_test!(
+            movss_freg32_base32_offset32,
+            |reg1, reg2, imm| format!("movss {}, dword ptr [{} + 0x{:x}]", reg1, reg2, imm),
+            ALL_FLOAT_REGS,
+            ALL_GENERAL_REGS,
+            [TEST_I32]
+        );
+    }
+
+    #[test]
+    fn test_movss_base32_offset32_freg32() {
+        disassembler_test!(
+            movss_base32_offset32_freg32,
+            |reg1, imm, reg2| format!("movss dword ptr [{} + 0x{:x}], {}", reg1, imm, reg2),
+            ALL_GENERAL_REGS,
+            [TEST_I32],
+            ALL_FLOAT_REGS
+        );
+    }
+
     #[test]
     fn test_movsd_freg64_freg64() {
         disassembler_test!(
@@ -2280,6 +3479,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_shift_reg64_cl_help() {
+        disassembler_test!(
+            |buf, reg| shift_reg64_cl_help(0xE0, buf, reg),
+            |reg| format!("shl {}, cl", reg),
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_sar_reg64_cl() {
+        disassembler_test!(
+            sar_reg64_cl,
+            |reg| format!("sar {}, cl", reg),
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_shl_reg64_cl() {
+        disassembler_test!(
+            shl_reg64_cl,
+            |reg| format!("shl {}, cl", reg),
+            ALL_GENERAL_REGS
+        );
+    }
+
+    #[test]
+    fn test_shr_reg64_cl() {
+        disassembler_test!(
+            shr_reg64_cl,
+            |reg| format!("shr {}, cl", reg),
+            ALL_GENERAL_REGS
+        );
+    }
+
     #[test]
     fn test_ret() {
         disassembler_test!(ret, || "ret");
@@ -2295,6 +3530,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_subsd_freg64_freg64() {
+        disassembler_test!(
+            subsd_freg64_freg64,
+            |reg1, reg2| format!("subsd {}, {}", reg1, reg2),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
+    #[test]
+    fn test_subss_freg32_freg32() {
+        disassembler_test!(
+            subss_freg32_freg32,
+            |reg1, reg2| format!("subss {}, {}", reg1, reg2),
+            ALL_FLOAT_REGS,
+            ALL_FLOAT_REGS
+        );
+    }
+
     #[test]
     fn test_pop_reg64() {
         disassembler_test!(pop_reg64, |reg| format!("pop {}", reg), ALL_GENERAL_REGS);
@@ -2304,4 +3559,35 @@ mod tests {
     fn test_push_reg64() {
         disassembler_test!(push_reg64, |reg| format!("push {}", reg), ALL_GENERAL_REGS);
     }
+
+    #[test]
+    fn test_eightbyte_classes() {
+        use EightbyteClass::*;
+
+        let f32_layout = Layout::Builtin(Builtin::Float(FloatWidth::F32));
+        let f64_layout = Layout::Builtin(Builtin::Float(FloatWidth::F64));
+        let bool_layout = Layout::Builtin(Builtin::Bool);
+
+        // What the zig builtins return from `add_with_overflow`
+        let checked_f64 = [f64_layout, bool_layout];
+        let checked_f32 = [f32_layout, bool_layout];
+        let two_f32 = [f32_layout, f32_layout];
+
+        assert_eq!(
+            X86_64SystemV::eightbyte_classes(&Layout::struct_no_name_order(&checked_f64)),
+            &[Sse, Integer]
+        );
+        assert_eq!(
+            X86_64SystemV::eightbyte_classes(&Layout::struct_no_name_order(&checked_f32)),
+            &[Integer]
+        );
+        assert_eq!(
+            X86_64SystemV::eightbyte_classes(&Layout::struct_no_name_order(&two_f32)),
+            &[Sse]
+        );
+        assert_eq!(
+            X86_64SystemV::eightbyte_classes(&Layout::Builtin(Builtin::Decimal)),
+            &[Integer, Integer]
+        );
+    }
 }
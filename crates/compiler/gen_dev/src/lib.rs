@@ -11,8 +11,8 @@ use roc_module::low_level::{LowLevel, LowLevelWrapperType};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::code_gen_help::CodeGenHelp;
 use roc_mono::ir::{
-    BranchInfo, CallType, Expr, JoinPointId, ListLiteralElement, Literal, Param, Proc, ProcLayout,
-    SelfRecursive, Stmt,
+    BranchInfo, CallType, Expr, HigherOrderLowLevel, JoinPointId, ListLiteralElement, Literal,
+    Param, Proc, ProcLayout, SelfRecursive, Stmt,
 };
 use roc_mono::layout::{Builtin, Layout, LayoutId, LayoutIds, TagIdIntType, UnionLayout};
 
@@ -55,6 +55,45 @@ pub enum Relocation {
     },
 }
 
+/// A proc that the zig builtins call through a function pointer.
+/// Zig passes every value by pointer, so the wrapper loads the values,
+/// calls the roc proc it wraps, and stores any result, like the wasm backend's higher order wrappers.
+#[derive(Debug, Clone)]
+pub struct WrapperProc<'a> {
+    symbol: Symbol,
+    name: String,
+    kind: WrapperKind<'a>,
+}
+
+#[derive(Debug, Clone)]
+enum WrapperKind<'a> {
+    /// `fn(data, a, .., out)`, the mapper of List.map and friends.
+    Mapper(PassedProc<'a>),
+    /// `fn(data, a, b) -> u8`, the comparison of List.sortWith.
+    Compare(PassedProc<'a>),
+    /// `fn(data, count)`, which increments the closure data `count` times.
+    /// Closure data without refcounted values has no inc proc.
+    IncN {
+        inc_proc: Option<String>,
+        layout: Layout<'a>,
+    },
+    /// `fn(elem)`, which decrements a refcounted list element.
+    Dec {
+        dec_proc: String,
+        layout: Layout<'a>,
+    },
+}
+
+/// The roc proc passed to a higher order builtin.
+#[derive(Debug, Clone)]
+struct PassedProc<'a> {
+    name: String,
+    arg_layouts: &'a [Layout<'a>],
+    ret_layout: Layout<'a>,
+    /// The closure data is passed as the last argument, if there is any.
+    closure_layout: Option<Layout<'a>>,
+}
+
 trait Backend<'a> {
     fn env(&self) -> &Env<'a>;
     fn interns(&self) -> &Interns;
@@ -80,6 +119,14 @@ trait Backend<'a> {
 
     fn helper_proc_symbols(&self) -> &Vec<'a, (Symbol, ProcLayout<'a>)>;
 
+    fn wrapper_procs_mut(&mut self) -> &mut Vec<'a, WrapperProc<'a>>;
+
+    fn wrapper_procs(&self) -> &Vec<'a, WrapperProc<'a>>;
+
+    /// build_wrapper_proc creates a wrapper proc that the zig builtins can call.
+    /// Returns the procedure bytes and its relocations.
+    fn build_wrapper_proc(&mut self, wrapper: &WrapperProc<'a>) -> (Vec<u8>, Vec<Relocation>);
+
     /// reset resets any registers or other values that may be occupied at the end of a procedure.
     /// It also passes basic procedure information to the builder for setup of the next function.
     fn reset(&mut self, name: String, is_self_recursive: SelfRecursive);
@@ -98,7 +145,7 @@ trait Backend<'a> {
     fn build_wrapped_jmp(&mut self) -> (&'a [u8], u64);
 
     /// build_proc creates a procedure and outputs it to the wrapped object writer.
-    /// Returns the procedure bytes, its relocations, and the names of the refcounting functions it references.
+    /// Returns the procedure bytes, its relocations, and the names of the refcounting and wrapper functions it references.
     fn build_proc(
         &mut self,
         proc: Proc<'a>,
@@ -123,6 +170,9 @@ trait Backend<'a> {
 
             helper_proc_names.push((*rc_proc_sym, name));
         }
+        for wrapper in self.wrapper_procs() {
+            helper_proc_names.push((wrapper.symbol, wrapper.name.clone()));
+        }
         let (bytes, relocs) = self.finalize();
         (bytes, relocs, helper_proc_names)
     }
@@ -162,6 +212,10 @@ trait Backend<'a> {
 
                 self.build_stmt(rc_stmt, ret_layout)
             }
+            Stmt::Coverage(_, following) => {
+                // coverage is only counted in the LLVM backend
+                self.build_stmt(following, ret_layout)
+            }
             Stmt::Switch {
                 cond_symbol,
                 cond_layout,
@@ -307,6 +361,9 @@ trait Backend<'a> {
                             layout,
                         )
                     }
+                    CallType::HigherOrder(higher_order) => {
+                        self.build_higher_order_lowlevel(sym, higher_order, layout)
+                    }
                     x => todo!("the call type, {:?}", x),
                 }
             }
@@ -358,7 +415,46 @@ trait Backend<'a> {
                 ..
             } => {
                 self.load_literal_symbols(arguments);
-                self.tag(sym, arguments, tag_layout, *tag_id);
+                self.tag(sym, arguments, tag_layout, *tag_id, None);
+            }
+            Expr::Reuse {
+                symbol,
+                tag_layout,
+                tag_id,
+                arguments,
+                ..
+            } => {
+                self.load_literal_symbols(arguments);
+                self.tag(sym, arguments, tag_layout, *tag_id, Some(*symbol));
+            }
+            Expr::Reset { symbol, .. } => {
+                let layout = *self.layout_map().get(symbol).unwrap();
+
+                // Expand the Reset into a call to a helper proc, like the Refcounting statement.
+                let (reset_expr, new_specializations) = {
+                    let (env, interns, rc_proc_gen) = self.env_interns_helpers_mut();
+                    let module_id = env.module_id;
+                    let ident_ids = interns.all_ident_ids.get_mut(&module_id).unwrap();
+
+                    rc_proc_gen.call_reset_refcount(ident_ids, layout, *symbol)
+                };
+
+                for spec in new_specializations.into_iter() {
+                    self.helper_proc_symbols_mut().push(spec);
+                }
+
+                self.build_expr(sym, &reset_expr, &layout);
+            }
+            Expr::ExprBox { symbol } => {
+                let inner_layout = match layout {
+                    Layout::Boxed(inner_layout) => inner_layout,
+                    x => internal_error!("ExprBox must have a Boxed layout, found: {:?}", x),
+                };
+                self.load_literal_symbols(&[*symbol]);
+                self.expr_box(sym, symbol, inner_layout);
+            }
+            Expr::ExprUnbox { symbol } => {
+                self.expr_unbox(sym, symbol, layout);
             }
             x => todo!("the expression, {:?}", x),
         }
@@ -574,13 +670,482 @@ trait Backend<'a> {
                 );
                 self.build_num_gte(sym, &args[0], &args[1], &arg_layouts[0])
             }
-            LowLevel::NumRound => self.build_fn_call(
+            LowLevel::NumRound => {
+                let intrinsic = match (arg_layouts[0], ret_layout) {
+                    (
+                        Layout::Builtin(Builtin::Float(FloatWidth::F32)),
+                        Layout::Builtin(Builtin::Int(int_width)),
+                    ) => &bitcode::NUM_ROUND_F32[*int_width],
+                    (
+                        Layout::Builtin(Builtin::Float(FloatWidth::F64)),
+                        Layout::Builtin(Builtin::Int(int_width)),
+                    ) => &bitcode::NUM_ROUND_F64[*int_width],
+                    x => internal_error!("NumRound is not defined for {:?}", x),
+                };
+                self.build_fn_call(sym, intrinsic.to_string(), args, arg_layouts, ret_layout)
+            }
+            LowLevel::NumCeiling => {
+                let intrinsic = match (arg_layouts[0], ret_layout) {
+                    (
+                        Layout::Builtin(Builtin::Float(FloatWidth::F32)),
+                        Layout::Builtin(Builtin::Int(int_width)),
+                    ) => &bitcode::NUM_CEILING_F32[*int_width],
+                    (
+                        Layout::Builtin(Builtin::Float(FloatWidth::F64)),
+                        Layout::Builtin(Builtin::Int(int_width)),
+                    ) => &bitcode::NUM_CEILING_F64[*int_width],
+                    x => internal_error!("NumCeiling is not defined for {:?}", x),
+                };
+                self.build_fn_call(sym, intrinsic.to_string(), args, arg_layouts, ret_layout)
+            }
+            LowLevel::NumFloor => {
+                let intrinsic = match (arg_layouts[0], ret_layout) {
+                    (
+                        Layout::Builtin(Builtin::Float(FloatWidth::F32)),
+                        Layout::Builtin(Builtin::Int(int_width)),
+                    ) => &bitcode::NUM_FLOOR_F32[*int_width],
+                    (
+                        Layout::Builtin(Builtin::Float(FloatWidth::F64)),
+                        Layout::Builtin(Builtin::Int(int_width)),
+                    ) => &bitcode::NUM_FLOOR_F64[*int_width],
+                    x => internal_error!("NumFloor is not defined for {:?}", x),
+                };
+                self.build_fn_call(sym, intrinsic.to_string(), args, arg_layouts, ret_layout)
+            }
+            LowLevel::NumAddWrap => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumAddWrap: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], arg_layouts[1],
+                    "NumAddWrap: expected all arguments of to have the same layout"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], *ret_layout,
+                    "NumAddWrap: expected to have the same argument and return layout"
+                );
+                self.build_num_add(sym, &args[0], &args[1], ret_layout)
+            }
+            LowLevel::NumSubWrap => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumSubWrap: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], arg_layouts[1],
+                    "NumSubWrap: expected all arguments of to have the same layout"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], *ret_layout,
+                    "NumSubWrap: expected to have the same argument and return layout"
+                );
+                self.build_num_sub(sym, &args[0], &args[1], ret_layout)
+            }
+            LowLevel::NumMulWrap => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumMulWrap: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], arg_layouts[1],
+                    "NumMulWrap: expected all arguments of to have the same layout"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], *ret_layout,
+                    "NumMulWrap: expected to have the same argument and return layout"
+                );
+                self.build_num_mul(sym, &args[0], &args[1], ret_layout)
+            }
+            LowLevel::NumDivUnchecked => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumDivUnchecked: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], arg_layouts[1],
+                    "NumDivUnchecked: expected all arguments of to have the same layout"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], *ret_layout,
+                    "NumDivUnchecked: expected to have the same argument and return layout"
+                );
+                self.build_num_div(sym, &args[0], &args[1], ret_layout)
+            }
+            LowLevel::NumRemUnchecked => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumRemUnchecked: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], arg_layouts[1],
+                    "NumRemUnchecked: expected all arguments of to have the same layout"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], *ret_layout,
+                    "NumRemUnchecked: expected to have the same argument and return layout"
+                );
+                self.build_num_rem(sym, &args[0], &args[1], ret_layout)
+            }
+            LowLevel::NumGt => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumGt: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], arg_layouts[1],
+                    "NumGt: expected all arguments of to have the same layout"
+                );
+                debug_assert_eq!(
+                    Layout::Builtin(Builtin::Bool),
+                    *ret_layout,
+                    "NumGt: expected to have return layout of type Bool"
+                );
+                self.build_num_gt(sym, &args[0], &args[1], &arg_layouts[0])
+            }
+            LowLevel::NumBitwiseAnd => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumBitwiseAnd: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], arg_layouts[1],
+                    "NumBitwiseAnd: expected all arguments of to have the same layout"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], *ret_layout,
+                    "NumBitwiseAnd: expected to have the same argument and return layout"
+                );
+                self.build_num_bitwise_and(sym, &args[0], &args[1], ret_layout)
+            }
+            LowLevel::NumBitwiseOr => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumBitwiseOr: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], arg_layouts[1],
+                    "NumBitwiseOr: expected all arguments of to have the same layout"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], *ret_layout,
+                    "NumBitwiseOr: expected to have the same argument and return layout"
+                );
+                self.build_num_bitwise_or(sym, &args[0], &args[1], ret_layout)
+            }
+            LowLevel::NumBitwiseXor => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumBitwiseXor: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], arg_layouts[1],
+                    "NumBitwiseXor: expected all arguments of to have the same layout"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], *ret_layout,
+                    "NumBitwiseXor: expected to have the same argument and return layout"
+                );
+                self.build_num_bitwise_xor(sym, &args[0], &args[1], ret_layout)
+            }
+            LowLevel::NumShiftLeftBy => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumShiftLeftBy: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[1], *ret_layout,
+                    "NumShiftLeftBy: expected to have the same argument and return layout"
+                );
+                // NOTE arguments are flipped; the shift amount comes first.
+                self.build_num_shift_left(sym, &args[1], &args[0], ret_layout)
+            }
+            LowLevel::NumShiftRightBy => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumShiftRightBy: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[1], *ret_layout,
+                    "NumShiftRightBy: expected to have the same argument and return layout"
+                );
+                // NOTE arguments are flipped; the shift amount comes first.
+                self.build_num_shift_right(sym, &args[1], &args[0], ret_layout)
+            }
+            LowLevel::NumShiftRightZfBy => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumShiftRightZfBy: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[1], *ret_layout,
+                    "NumShiftRightZfBy: expected to have the same argument and return layout"
+                );
+                // NOTE arguments are flipped; the shift amount comes first.
+                self.build_num_shift_right_zf(sym, &args[1], &args[0], ret_layout)
+            }
+            LowLevel::NumIntCast => {
+                debug_assert_eq!(
+                    1,
+                    args.len(),
+                    "NumIntCast: expected to have exactly one argument"
+                );
+                self.build_num_int_cast(sym, &args[0], &arg_layouts[0], ret_layout)
+            }
+            LowLevel::NumAddSaturated => match ret_layout {
+                Layout::Builtin(Builtin::Int(int_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_ADD_SATURATED_INT[*int_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                Layout::Builtin(Builtin::Decimal) => self.build_fn_call(
+                    sym,
+                    bitcode::DEC_ADD_SATURATED.to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                // Floats saturate at infinity by themselves.
+                Layout::Builtin(Builtin::Float(_)) => {
+                    self.build_num_add(sym, &args[0], &args[1], ret_layout)
+                }
+                x => internal_error!("NumAddSaturated is not defined for {:?}", x),
+            },
+            LowLevel::NumAddChecked => {
+                let intrinsic = match arg_layouts[0] {
+                    Layout::Builtin(Builtin::Int(int_width)) => {
+                        &bitcode::NUM_ADD_CHECKED_INT[int_width]
+                    }
+                    Layout::Builtin(Builtin::Float(float_width)) => {
+                        &bitcode::NUM_ADD_CHECKED_FLOAT[float_width]
+                    }
+                    Layout::Builtin(Builtin::Decimal) => bitcode::DEC_ADD_WITH_OVERFLOW,
+                    x => internal_error!("NumAddChecked is not defined for {:?}", x),
+                };
+                self.build_fn_call(sym, intrinsic.to_string(), args, arg_layouts, ret_layout)
+            }
+            LowLevel::NumSubSaturated => match ret_layout {
+                Layout::Builtin(Builtin::Int(int_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_SUB_SATURATED_INT[*int_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                Layout::Builtin(Builtin::Decimal) => self.build_fn_call(
+                    sym,
+                    bitcode::DEC_SUB_SATURATED.to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                // Floats saturate at infinity by themselves.
+                Layout::Builtin(Builtin::Float(_)) => {
+                    self.build_num_sub(sym, &args[0], &args[1], ret_layout)
+                }
+                x => internal_error!("NumSubSaturated is not defined for {:?}", x),
+            },
+            LowLevel::NumSubChecked => {
+                let intrinsic = match arg_layouts[0] {
+                    Layout::Builtin(Builtin::Int(int_width)) => {
+                        &bitcode::NUM_SUB_CHECKED_INT[int_width]
+                    }
+                    Layout::Builtin(Builtin::Float(float_width)) => {
+                        &bitcode::NUM_SUB_CHECKED_FLOAT[float_width]
+                    }
+                    Layout::Builtin(Builtin::Decimal) => bitcode::DEC_SUB_WITH_OVERFLOW,
+                    x => internal_error!("NumSubChecked is not defined for {:?}", x),
+                };
+                self.build_fn_call(sym, intrinsic.to_string(), args, arg_layouts, ret_layout)
+            }
+            LowLevel::NumMulSaturated => match ret_layout {
+                Layout::Builtin(Builtin::Int(int_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_MUL_SATURATED_INT[*int_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                Layout::Builtin(Builtin::Decimal) => self.build_fn_call(
+                    sym,
+                    bitcode::DEC_MUL_SATURATED.to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                // Floats saturate at infinity by themselves.
+                Layout::Builtin(Builtin::Float(_)) => {
+                    self.build_num_mul(sym, &args[0], &args[1], ret_layout)
+                }
+                x => internal_error!("NumMulSaturated is not defined for {:?}", x),
+            },
+            LowLevel::NumMulChecked => {
+                let intrinsic = match arg_layouts[0] {
+                    Layout::Builtin(Builtin::Int(int_width)) => {
+                        &bitcode::NUM_MUL_CHECKED_INT[int_width]
+                    }
+                    Layout::Builtin(Builtin::Float(float_width)) => {
+                        &bitcode::NUM_MUL_CHECKED_FLOAT[float_width]
+                    }
+                    Layout::Builtin(Builtin::Decimal) => bitcode::DEC_MUL_WITH_OVERFLOW,
+                    x => internal_error!("NumMulChecked is not defined for {:?}", x),
+                };
+                self.build_fn_call(sym, intrinsic.to_string(), args, arg_layouts, ret_layout)
+            }
+            LowLevel::NumDivCeilUnchecked => match ret_layout {
+                Layout::Builtin(Builtin::Int(int_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_DIV_CEIL[*int_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                x => internal_error!("NumDivCeilUnchecked is not defined for {:?}", x),
+            },
+            LowLevel::NumSin => match arg_layouts[0] {
+                Layout::Builtin(Builtin::Float(float_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_SIN[float_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                x => internal_error!("NumSin is not defined for {:?}", x),
+            },
+            LowLevel::NumCos => match arg_layouts[0] {
+                Layout::Builtin(Builtin::Float(float_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_COS[float_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                x => internal_error!("NumCos is not defined for {:?}", x),
+            },
+            LowLevel::NumLogUnchecked => match arg_layouts[0] {
+                Layout::Builtin(Builtin::Float(float_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_LOG[float_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                x => internal_error!("NumLogUnchecked is not defined for {:?}", x),
+            },
+            LowLevel::NumPow => match arg_layouts[0] {
+                Layout::Builtin(Builtin::Float(float_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_POW[float_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                x => internal_error!("NumPow is not defined for {:?}", x),
+            },
+            LowLevel::NumIsFinite => match arg_layouts[0] {
+                Layout::Builtin(Builtin::Float(float_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_IS_FINITE[float_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                x => internal_error!("NumIsFinite is not defined for {:?}", x),
+            },
+            LowLevel::NumSqrtUnchecked => match arg_layouts[0] {
+                Layout::Builtin(Builtin::Float(float_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_SQRT[float_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                x => internal_error!("NumSqrtUnchecked is not defined for {:?}", x),
+            },
+            LowLevel::NumIsMultipleOf => match arg_layouts[0] {
+                Layout::Builtin(Builtin::Int(int_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_IS_MULTIPLE_OF[int_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                x => internal_error!("NumIsMultipleOf is not defined for {:?}", x),
+            },
+            LowLevel::NumCompare => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "NumCompare: expected to have exactly two argument"
+                );
+                debug_assert_eq!(
+                    arg_layouts[0], arg_layouts[1],
+                    "NumCompare: expected all arguments of to have the same layout"
+                );
+                debug_assert_eq!(
+                    Layout::Builtin(Builtin::Int(IntWidth::U8)),
+                    *ret_layout,
+                    "NumCompare: expected to have return layout of type U8"
+                );
+                // The tag ids of [EQ, GT, LT] are 0, 1 and 2, which is (x != y) + (x < y).
+                self.build_neq(&Symbol::DEV_TMP, &args[0], &args[1], &arg_layouts[0]);
+                self.build_num_lt(&Symbol::DEV_TMP2, &args[0], &args[1], &arg_layouts[0]);
+                self.build_num_add(sym, &Symbol::DEV_TMP, &Symbol::DEV_TMP2, ret_layout);
+                self.free_symbol(&Symbol::DEV_TMP);
+                self.free_symbol(&Symbol::DEV_TMP2);
+            }
+            LowLevel::NumBytesToU16 => self.build_fn_call(
+                sym,
+                bitcode::NUM_BYTES_TO_U16.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::NumBytesToU32 => self.build_fn_call(
                 sym,
-                bitcode::NUM_ROUND_F64[IntWidth::I64].to_string(),
+                bitcode::NUM_BYTES_TO_U32.to_string(),
                 args,
                 arg_layouts,
                 ret_layout,
             ),
+            LowLevel::NumToStr => match arg_layouts[0] {
+                Layout::Builtin(Builtin::Int(int_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::STR_FROM_INT[int_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                Layout::Builtin(Builtin::Float(float_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::STR_FROM_FLOAT[float_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                Layout::Builtin(Builtin::Decimal) => self.build_fn_call(
+                    sym,
+                    bitcode::DEC_TO_STR.to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                x => internal_error!("NumToStr is not defined for {:?}", x),
+            },
             LowLevel::ListLen => {
                 debug_assert_eq!(
                     1,
@@ -605,28 +1170,323 @@ trait Backend<'a> {
                 );
                 self.build_list_replace_unsafe(sym, args, arg_layouts, ret_layout)
             }
-            LowLevel::StrConcat => self.build_fn_call(
-                sym,
-                bitcode::STR_CONCAT.to_string(),
-                args,
-                arg_layouts,
-                ret_layout,
-            ),
-            LowLevel::PtrCast => {
+            LowLevel::ListGetCapacity => {
                 debug_assert_eq!(
                     1,
                     args.len(),
-                    "RefCountGetPtr: expected to have exactly one argument"
+                    "ListGetCapacity: expected to have exactly one argument"
                 );
-                self.build_ptr_cast(sym, &args[0])
+                self.build_list_get_capacity(sym, &args[0])
             }
-            LowLevel::RefCountDec => self.build_fn_call(
-                sym,
-                bitcode::UTILS_DECREF.to_string(),
-                args,
-                arg_layouts,
-                ret_layout,
-            ),
+            LowLevel::ListWithCapacity => {
+                debug_assert_eq!(
+                    1,
+                    args.len(),
+                    "ListWithCapacity: expected to have exactly one argument"
+                );
+                self.build_list_with_capacity(sym, &args[0], &arg_layouts[0], ret_layout)
+            }
+            LowLevel::ListReserve => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "ListReserve: expected to have exactly two arguments"
+                );
+                self.build_list_reserve(sym, args, arg_layouts, ret_layout)
+            }
+            LowLevel::ListAppendUnsafe => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "ListAppendUnsafe: expected to have exactly two arguments"
+                );
+                self.build_list_append_unsafe(sym, args, arg_layouts, ret_layout)
+            }
+            LowLevel::ListPrepend => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "ListPrepend: expected to have exactly two arguments"
+                );
+                self.build_list_prepend(sym, args, arg_layouts, ret_layout)
+            }
+            LowLevel::ListConcat => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "ListConcat: expected to have exactly two arguments"
+                );
+                self.build_list_concat(sym, args, arg_layouts, ret_layout)
+            }
+            LowLevel::ListSwap => {
+                debug_assert_eq!(
+                    3,
+                    args.len(),
+                    "ListSwap: expected to have exactly three arguments"
+                );
+                self.build_list_swap(sym, args, arg_layouts, ret_layout)
+            }
+            LowLevel::ListSublist => {
+                debug_assert_eq!(
+                    3,
+                    args.len(),
+                    "ListSublist: expected to have exactly three arguments"
+                );
+                self.build_list_sublist(sym, args, arg_layouts, ret_layout)
+            }
+            LowLevel::ListDropAt => {
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "ListDropAt: expected to have exactly two arguments"
+                );
+                self.build_list_drop_at(sym, args, arg_layouts, ret_layout)
+            }
+            LowLevel::ListIsUnique => self.build_fn_call(
+                sym,
+                bitcode::LIST_IS_UNIQUE.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrConcat => self.build_fn_call(
+                sym,
+                bitcode::STR_CONCAT.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrJoinWith => self.build_fn_call(
+                sym,
+                bitcode::STR_JOIN_WITH.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrSplit => self.build_fn_call(
+                sym,
+                bitcode::STR_STR_SPLIT.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrStartsWith => self.build_fn_call(
+                sym,
+                bitcode::STR_STARTS_WITH.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrStartsWithScalar => self.build_fn_call(
+                sym,
+                bitcode::STR_STARTS_WITH_SCALAR.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrEndsWith => self.build_fn_call(
+                sym,
+                bitcode::STR_ENDS_WITH.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrCountGraphemes => self.build_fn_call(
+                sym,
+                bitcode::STR_COUNT_GRAPEHEME_CLUSTERS.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrCountUtf8Bytes => self.build_fn_call(
+                sym,
+                bitcode::STR_COUNT_UTF8_BYTES.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrGetCapacity => self.build_fn_call(
+                sym,
+                bitcode::STR_CAPACITY.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrToUtf8 => self.build_fn_call(
+                sym,
+                bitcode::STR_TO_UTF8.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrToScalars => self.build_fn_call(
+                sym,
+                bitcode::STR_TO_SCALARS.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrRepeat => self.build_fn_call(
+                sym,
+                bitcode::STR_REPEAT.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrTrim => self.build_fn_call(
+                sym,
+                bitcode::STR_TRIM.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrTrimLeft => self.build_fn_call(
+                sym,
+                bitcode::STR_TRIM_LEFT.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrTrimRight => self.build_fn_call(
+                sym,
+                bitcode::STR_TRIM_RIGHT.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrGetUnsafe => self.build_fn_call(
+                sym,
+                bitcode::STR_GET_UNSAFE.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrSubstringUnsafe => self.build_fn_call(
+                sym,
+                bitcode::STR_SUBSTRING_UNSAFE.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrReserve => self.build_fn_call(
+                sym,
+                bitcode::STR_RESERVE.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrAppendScalar => self.build_fn_call(
+                sym,
+                bitcode::STR_APPEND_SCALAR.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrGetScalarUnsafe => self.build_fn_call(
+                sym,
+                bitcode::STR_GET_SCALAR_UNSAFE.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
+            LowLevel::StrIsEmpty => {
+                debug_assert_eq!(
+                    1,
+                    args.len(),
+                    "StrIsEmpty: expected to have exactly one argument"
+                );
+                let u64_layout = Layout::Builtin(Builtin::Int(IntWidth::U64));
+                self.build_fn_call(
+                    &Symbol::DEV_TMP,
+                    bitcode::STR_NUMBER_OF_BYTES.to_string(),
+                    args,
+                    arg_layouts,
+                    &u64_layout,
+                );
+                self.load_literal(
+                    &Symbol::DEV_TMP2,
+                    &u64_layout,
+                    &Literal::Int(0i128.to_ne_bytes()),
+                );
+                self.build_eq(sym, &Symbol::DEV_TMP, &Symbol::DEV_TMP2, &u64_layout);
+                self.free_symbol(&Symbol::DEV_TMP);
+                self.free_symbol(&Symbol::DEV_TMP2);
+            }
+            LowLevel::StrFromInt => match arg_layouts[0] {
+                Layout::Builtin(Builtin::Int(int_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::STR_FROM_INT[int_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                x => internal_error!("StrFromInt: expected an integer layout, found {:?}", x),
+            },
+            LowLevel::StrFromFloat => match arg_layouts[0] {
+                Layout::Builtin(Builtin::Float(float_width)) => self.build_fn_call(
+                    sym,
+                    bitcode::STR_FROM_FLOAT[float_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                x => internal_error!("StrFromFloat: expected a float layout, found {:?}", x),
+            },
+            LowLevel::StrFromUtf8Range => {
+                debug_assert_eq!(
+                    3,
+                    args.len(),
+                    "StrFromUtf8Range: expected to have exactly three arguments"
+                );
+                // The zig builtin also takes an update mode, which is always immutable here.
+                let u8_layout = Layout::Builtin(Builtin::Int(IntWidth::U8));
+                self.load_literal(
+                    &Symbol::DEV_TMP,
+                    &u8_layout,
+                    &Literal::Int(0i128.to_ne_bytes()),
+                );
+                let fn_args =
+                    bumpalo::vec![in self.env().arena; args[0], args[1], args[2], Symbol::DEV_TMP];
+                let fn_arg_layouts = bumpalo::vec![in self.env().arena; arg_layouts[0], arg_layouts[1], arg_layouts[2], u8_layout];
+                self.build_fn_call(
+                    sym,
+                    bitcode::STR_FROM_UTF8_RANGE.to_string(),
+                    &fn_args,
+                    &fn_arg_layouts,
+                    ret_layout,
+                );
+                self.free_symbol(&Symbol::DEV_TMP);
+            }
+            LowLevel::StrToNum => {
+                let number_layout = match ret_layout {
+                    Layout::Struct { field_layouts, .. } => field_layouts[0],
+                    x => internal_error!("StrToNum: expected a struct layout, found {:?}", x),
+                };
+                let intrinsic = match number_layout {
+                    Layout::Builtin(Builtin::Int(int_width)) => &bitcode::STR_TO_INT[int_width],
+                    Layout::Builtin(Builtin::Float(float_width)) => {
+                        &bitcode::STR_TO_FLOAT[float_width]
+                    }
+                    Layout::Builtin(Builtin::Decimal) => bitcode::DEC_FROM_STR,
+                    x => internal_error!("StrToNum is not defined for {:?}", x),
+                };
+                self.build_fn_call(sym, intrinsic.to_string(), args, arg_layouts, ret_layout)
+            }
+            LowLevel::PtrCast => {
+                debug_assert_eq!(
+                    1,
+                    args.len(),
+                    "RefCountGetPtr: expected to have exactly one argument"
+                );
+                self.build_ptr_cast(sym, &args[0])
+            }
+            LowLevel::RefCountDec => self.build_fn_call(
+                sym,
+                bitcode::UTILS_DECREF.to_string(),
+                args,
+                arg_layouts,
+                ret_layout,
+            ),
             LowLevel::RefCountInc => self.build_fn_call(
                 sym,
                 bitcode::UTILS_INCREF.to_string(),
@@ -634,10 +1494,188 @@ trait Backend<'a> {
                 arg_layouts,
                 ret_layout,
             ),
-            x => todo!("low level, {:?}", x),
+            LowLevel::And => {
+                debug_assert_eq!(2, args.len(), "And: expected to have exactly two argument");
+                self.build_num_bitwise_and(sym, &args[0], &args[1], ret_layout)
+            }
+            LowLevel::Or => {
+                debug_assert_eq!(2, args.len(), "Or: expected to have exactly two argument");
+                self.build_num_bitwise_or(sym, &args[0], &args[1], ret_layout)
+            }
+            LowLevel::Not => {
+                debug_assert_eq!(1, args.len(), "Not: expected to have exactly one argument");
+                let bool_layout = Layout::Builtin(Builtin::Bool);
+                self.load_literal(&Symbol::DEV_TMP, &bool_layout, &Literal::Bool(false));
+                self.build_eq(sym, &args[0], &Symbol::DEV_TMP, &bool_layout);
+                self.free_symbol(&Symbol::DEV_TMP);
+            }
+            LowLevel::HashBytes => {
+                // Dict.hashBytesLowlevel : U64, List U8 -> U64
+                // The zig builtin takes the list first.
+                debug_assert_eq!(
+                    2,
+                    args.len(),
+                    "HashBytes: expected to have exactly two arguments"
+                );
+                let fn_args = bumpalo::vec![in self.env().arena; args[1], args[0]];
+                let fn_arg_layouts =
+                    bumpalo::vec![in self.env().arena; arg_layouts[1], arg_layouts[0]];
+                self.build_fn_call(
+                    sym,
+                    bitcode::DICT_HASH_BYTES.to_string(),
+                    &fn_args,
+                    &fn_arg_layouts,
+                    ret_layout,
+                )
+            }
+            LowLevel::NumToFloatCast => match (arg_layouts[0], ret_layout) {
+                (
+                    Layout::Builtin(Builtin::Int(int_width)),
+                    Layout::Builtin(Builtin::Float(FloatWidth::F32)),
+                ) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_INT_TO_F32[int_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                (
+                    Layout::Builtin(Builtin::Int(int_width)),
+                    Layout::Builtin(Builtin::Float(FloatWidth::F64)),
+                ) => self.build_fn_call(
+                    sym,
+                    bitcode::NUM_INT_TO_F64[int_width].to_string(),
+                    args,
+                    arg_layouts,
+                    ret_layout,
+                ),
+                (Layout::Builtin(Builtin::Float(_)), Layout::Builtin(Builtin::Float(_))) => {
+                    self.build_num_to_frac(sym, &args[0], &arg_layouts[0], ret_layout)
+                }
+                (
+                    Layout::Builtin(Builtin::Decimal),
+                    Layout::Builtin(Builtin::Float(float_width)),
+                ) => {
+                    // A Dec is an I128 scaled by 10^18, so convert the integer and scale it back down.
+                    let i128_layout = Layout::Builtin(Builtin::Int(IntWidth::I128));
+                    let f64_layout = Layout::Builtin(Builtin::Float(FloatWidth::F64));
+                    self.build_fn_call(
+                        &Symbol::DEV_TMP,
+                        bitcode::NUM_INT_TO_F64[IntWidth::I128].to_string(),
+                        args,
+                        &[i128_layout],
+                        &f64_layout,
+                    );
+                    self.load_literal(&Symbol::DEV_TMP2, &f64_layout, &Literal::Float(1e18));
+                    match float_width {
+                        FloatWidth::F64 => self.build_num_div(
+                            sym,
+                            &Symbol::DEV_TMP,
+                            &Symbol::DEV_TMP2,
+                            &f64_layout,
+                        ),
+                        FloatWidth::F32 => {
+                            self.build_num_div(
+                                &Symbol::DEV_TMP3,
+                                &Symbol::DEV_TMP,
+                                &Symbol::DEV_TMP2,
+                                &f64_layout,
+                            );
+                            self.build_num_to_frac(sym, &Symbol::DEV_TMP3, &f64_layout, ret_layout);
+                            self.free_symbol(&Symbol::DEV_TMP3);
+                        }
+                        FloatWidth::F128 => {
+                            internal_error!("NumToFloatCast: F128 is not supported")
+                        }
+                    }
+                    self.free_symbol(&Symbol::DEV_TMP);
+                    self.free_symbol(&Symbol::DEV_TMP2);
+                }
+                x => internal_error!("NumToFloatCast is not defined for {:?}", x),
+            },
+            LowLevel::NumToIntChecked => {
+                // The result is { value: N, out_of_bounds: Bool }, like the zig builtin returns.
+                let intrinsic = match (arg_layouts[0], ret_layout) {
+                    (
+                        Layout::Builtin(Builtin::Int(arg_width)),
+                        Layout::Struct {
+                            field_layouts: &[Layout::Builtin(Builtin::Int(ret_width)), ..],
+                            ..
+                        },
+                    ) => {
+                        if arg_width.is_signed() {
+                            &bitcode::NUM_INT_TO_INT_CHECKING_MAX_AND_MIN[ret_width][arg_width]
+                        } else {
+                            &bitcode::NUM_INT_TO_INT_CHECKING_MAX[ret_width][arg_width]
+                        }
+                    }
+                    x => internal_error!("NumToIntChecked is not defined for {:?}", x),
+                };
+                self.build_fn_call(sym, intrinsic.to_string(), args, arg_layouts, ret_layout)
+            }
+            LowLevel::NumToFloatChecked => {
+                // The result is { value: F, out_of_bounds: Bool }, like NumToIntChecked.
+                // The value is out of bounds if it does not fit in the float, which makes it infinite.
+                let float_width = match ret_layout {
+                    Layout::Struct {
+                        field_layouts: &[Layout::Builtin(Builtin::Float(float_width)), ..],
+                        ..
+                    } => float_width,
+                    x => internal_error!("NumToFloatChecked is not defined for {:?}", x),
+                };
+                let float_layout = Layout::Builtin(Builtin::Float(float_width));
+                let bool_layout = Layout::Builtin(Builtin::Bool);
+                self.build_run_low_level(
+                    &Symbol::DEV_TMP4,
+                    &LowLevel::NumToFloatCast,
+                    args,
+                    arg_layouts,
+                    &float_layout,
+                );
+                self.build_fn_call(
+                    &Symbol::DEV_TMP5,
+                    bitcode::NUM_IS_FINITE[float_width].to_string(),
+                    &[Symbol::DEV_TMP4],
+                    &[float_layout],
+                    &bool_layout,
+                );
+                self.load_literal(&Symbol::DEV_TMP3, &bool_layout, &Literal::Bool(false));
+                self.build_eq(
+                    &Symbol::DEV_TMP2,
+                    &Symbol::DEV_TMP5,
+                    &Symbol::DEV_TMP3,
+                    &bool_layout,
+                );
+                let fields = self.env().arena.alloc([Symbol::DEV_TMP4, Symbol::DEV_TMP2]);
+                self.create_struct(sym, ret_layout, fields);
+                self.free_symbol(&Symbol::DEV_TMP2);
+                self.free_symbol(&Symbol::DEV_TMP3);
+                self.free_symbol(&Symbol::DEV_TMP4);
+                self.free_symbol(&Symbol::DEV_TMP5);
+            }
+            LowLevel::ListMap
+            | LowLevel::ListMap2
+            | LowLevel::ListMap3
+            | LowLevel::ListMap4
+            | LowLevel::ListSortWith => {
+                internal_error!("these are higher order, and are handled elsewhere")
+            }
+            LowLevel::BoxExpr | LowLevel::UnboxExpr => {
+                internal_error!("The {:?} operation is turned into mono Expr", lowlevel)
+            }
+            LowLevel::Unreachable => self.build_unreachable(sym, ret_layout),
         }
     }
 
+    /// build_higher_order_lowlevel calls a higher order builtin, like List.map,
+    /// with a wrapper proc around the passed function.
+    fn build_higher_order_lowlevel(
+        &mut self,
+        dst: &Symbol,
+        higher_order: &HigherOrderLowLevel<'a>,
+        ret_layout: &Layout<'a>,
+    );
+
     /// Builds a builtin functions that do not map directly to a low level
     /// If the builtin is simple enough, it will be inlined.
     fn build_builtin(
@@ -670,15 +1708,13 @@ trait Backend<'a> {
                 self.build_eq(sym, &args[0], &Symbol::DEV_TMP, &arg_layouts[0]);
                 self.free_symbol(&Symbol::DEV_TMP)
             }
-            Symbol::LIST_GET | Symbol::LIST_SET | Symbol::LIST_REPLACE => {
-                // TODO: This is probably simple enough to be worth inlining.
+            _ => {
+                // Everything else, like List.get, is a regular proc of the builtin module.
+                // TODO: Some of these, like List.get, are probably simple enough to be worth inlining.
                 let layout_id = LayoutIds::default().get(func_sym, ret_layout);
                 let fn_name = self.symbol_to_string(func_sym, layout_id);
-                // Now that the arguments are needed, load them if they are literals.
-                self.load_literal_symbols(args);
                 self.build_fn_call(sym, fn_name, args, arg_layouts, ret_layout)
             }
-            _ => todo!("the function, {:?}", func_sym),
         }
     }
 
@@ -744,9 +1780,93 @@ trait Backend<'a> {
         arg_layout: &Layout<'a>,
     );
 
+    /// build_num_gt stores the result of `src1 > src2` into dst.
+    fn build_num_gt(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, arg_layout: &Layout<'a>);
+
+    /// build_num_div stores the result of `src1 / src2` into dst.
+    fn build_num_div(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, layout: &Layout<'a>);
+
+    /// build_num_rem stores the result of `src1 % src2` into dst.
+    fn build_num_rem(&mut self, dst: &Symbol, src1: &Symbol, src2: &Symbol, layout: &Layout<'a>);
+
+    /// build_num_bitwise_and stores the result of `src1 & src2` into dst.
+    fn build_num_bitwise_and(
+        &mut self,
+        dst: &Symbol,
+        src1: &Symbol,
+        src2: &Symbol,
+        layout: &Layout<'a>,
+    );
+
+    /// build_num_bitwise_or stores the result of `src1 | src2` into dst.
+    fn build_num_bitwise_or(
+        &mut self,
+        dst: &Symbol,
+        src1: &Symbol,
+        src2: &Symbol,
+        layout: &Layout<'a>,
+    );
+
+    /// build_num_bitwise_xor stores the result of `src1 ^ src2` into dst.
+    fn build_num_bitwise_xor(
+        &mut self,
+        dst: &Symbol,
+        src1: &Symbol,
+        src2: &Symbol,
+        layout: &Layout<'a>,
+    );
+
+    /// build_num_shift_left stores `value` shifted left by `amount` bits into dst.
+    fn build_num_shift_left(
+        &mut self,
+        dst: &Symbol,
+        value: &Symbol,
+        amount: &Symbol,
+        layout: &Layout<'a>,
+    );
+
+    /// build_num_shift_right stores `value` shifted right, keeping its sign, by `amount` bits into dst.
+    fn build_num_shift_right(
+        &mut self,
+        dst: &Symbol,
+        value: &Symbol,
+        amount: &Symbol,
+        layout: &Layout<'a>,
+    );
+
+    /// build_num_shift_right_zf stores `value` shifted right, filling with zeros, by `amount` bits into dst.
+    fn build_num_shift_right_zf(
+        &mut self,
+        dst: &Symbol,
+        value: &Symbol,
+        amount: &Symbol,
+        layout: &Layout<'a>,
+    );
+
+    /// build_num_int_cast casts the integer in src to the integer layout of dst.
+    fn build_num_int_cast(
+        &mut self,
+        dst: &Symbol,
+        src: &Symbol,
+        source_layout: &Layout<'a>,
+        target_layout: &Layout<'a>,
+    );
+
     /// build_list_len returns the length of a list.
     fn build_list_len(&mut self, dst: &Symbol, list: &Symbol);
 
+    /// build_list_get_capacity returns the capacity of a list.
+    fn build_list_get_capacity(&mut self, dst: &Symbol, list: &Symbol);
+
+    /// build_list_with_capacity returns an empty list with room for `capacity` elements.
+    fn build_list_with_capacity(
+        &mut self,
+        dst: &Symbol,
+        capacity: &Symbol,
+        capacity_layout: &Layout<'a>,
+        ret_layout: &Layout<'a>,
+    );
+
     /// build_list_get_unsafe loads the element from the list at the index.
     fn build_list_get_unsafe(
         &mut self,
@@ -765,9 +1885,75 @@ trait Backend<'a> {
         ret_layout: &Layout<'a>,
     );
 
+    /// build_list_reserve returns the list with room for at least `spare` more elements.
+    fn build_list_reserve(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    );
+
+    /// build_list_append_unsafe returns the list with the element added to the end, assuming there is capacity for it.
+    fn build_list_append_unsafe(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    );
+
+    /// build_list_prepend returns the list with the element added to the front.
+    fn build_list_prepend(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    );
+
+    /// build_list_concat returns the elements of the first list followed by the elements of the second.
+    fn build_list_concat(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    );
+
+    /// build_list_swap returns the list with the elements at the two indices swapped.
+    fn build_list_swap(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    );
+
+    /// build_list_sublist returns the `len` elements of the list starting at `start`.
+    fn build_list_sublist(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    );
+
+    /// build_list_drop_at returns the list without the element at the index.
+    fn build_list_drop_at(
+        &mut self,
+        dst: &Symbol,
+        args: &'a [Symbol],
+        arg_layouts: &[Layout<'a>],
+        ret_layout: &Layout<'a>,
+    );
+
     /// build_refcount_getptr loads the pointer to the reference count of src into dst.
     fn build_ptr_cast(&mut self, dst: &Symbol, src: &Symbol);
 
+    /// build_unreachable gives dst a value of the layout, in code that is never run.
+    fn build_unreachable(&mut self, dst: &Symbol, layout: &Layout<'a>);
+
     /// literal_map gets the map from symbol to literal and layout, used for lazy loading and literal folding.
     fn literal_map(&mut self) -> &mut MutMap<Symbol, (*const Literal<'a>, *const Layout<'a>)>;
 
@@ -825,14 +2011,22 @@ trait Backend<'a> {
     fn get_tag_id(&mut self, sym: &Symbol, structure: &Symbol, union_layout: &UnionLayout<'a>);
 
     /// tag sets the tag for a union.
+    /// Recursive unions are allocated on the heap, unless the allocation of `reuse` can be used.
     fn tag(
         &mut self,
         sym: &Symbol,
         args: &'a [Symbol],
         tag_layout: &UnionLayout<'a>,
         tag_id: TagIdIntType,
+        reuse: Option<Symbol>,
     );
 
+    /// expr_box allocates a box on the heap and moves the value into it.
+    fn expr_box(&mut self, sym: &Symbol, value: &Symbol, inner_layout: &Layout<'a>);
+
+    /// expr_unbox loads the value out of a box.
+    fn expr_unbox(&mut self, sym: &Symbol, boxed: &Symbol, inner_layout: &Layout<'a>);
+
     /// return_symbol moves a symbol to the correct return location for the backend and adds a jump to the end of the function.
     fn return_symbol(&mut self, sym: &Symbol, layout: &Layout<'a>);
 
@@ -977,6 +2171,9 @@ trait Backend<'a> {
                 self.set_last_seen(sym, stmt);
                 self.scan_ast(following);
             }
+            Stmt::Coverage(_, following) => {
+                self.scan_ast(following);
+            }
             Stmt::Join {
                 parameters,
                 body: continuation,
@@ -93,7 +93,7 @@ pub fn build_module<'a>(
                 aarch64::AArch64GeneralReg,
                 aarch64::AArch64FloatReg,
                 aarch64::AArch64Assembler,
-                aarch64::AArch64Call,
+                aarch64::AArch64AppleCall,
             >(env, TargetInfo::default_aarch64(), interns);
             build_object(
                 procedures,
@@ -144,18 +144,11 @@ fn generate_wrapper<'a, B: Backend<'a>>(
     };
     output.add_symbol(symbol);
     if let Some(sym_id) = output.symbol_id(name) {
-        let reloc = write::Relocation {
-            offset: offset + proc_offset,
-            size: 32,
-            kind: RelocationKind::PltRelative,
-            encoding: RelocationEncoding::X86Branch,
-            symbol: sym_id,
-            addend: -4,
-        };
-
-        match output.add_relocation(text_section, reloc) {
-            Ok(obj) => obj,
-            Err(e) => internal_error!("{:?}", e),
+        for reloc in function_relocations(output, proc_data, offset, proc_offset, sym_id) {
+            match output.add_relocation(text_section, reloc) {
+                Ok(obj) => obj,
+                Err(e) => internal_error!("{:?}", e),
+            }
         }
     } else {
         internal_error!("failed to find fn symbol for {:?}", wraps);
@@ -307,7 +300,34 @@ fn build_object<'a, B: Backend<'a>>(
         )
     }
 
-    // Relocations for all procedures (user code & helpers)
+    // Build the wrappers that the zig builtins call through function pointers.
+    // Their symbols were added by the procs that reference them.
+    let empty = bumpalo::collections::Vec::new_in(arena);
+    let wrapper_procs = std::mem::replace(backend.wrapper_procs_mut(), empty);
+    for wrapper in wrapper_procs {
+        let proc_id = match output.symbol_id(wrapper.name.as_bytes()) {
+            Some(proc_id) => proc_id,
+            None => internal_error!("failed to find the wrapper fn symbol {:?}", wrapper.name),
+        };
+        let section_id = match output.symbol(proc_id).section {
+            SymbolSection::Section(section_id) => section_id,
+            _ => internal_error!("the wrapper fn symbol {:?} has no section", wrapper.name),
+        };
+        let (proc_data, relocs) = backend.build_wrapper_proc(&wrapper);
+        add_proc_data(
+            &mut output,
+            &mut relocations,
+            data_section,
+            wrapper.name,
+            section_id,
+            proc_id,
+            &proc_data,
+            &relocs,
+            &[],
+        )
+    }
+
+    // Relocations for all procedures (user code, helpers & wrappers)
     for (section_id, reloc) in relocations {
         match output.add_relocation(section_id, reloc) {
             Ok(obj) => obj,
@@ -375,9 +395,35 @@ fn build_proc<'a, B: Backend<'a>>(
     proc_id: SymbolId,
     proc: Proc<'a>,
 ) {
-    let mut local_data_index = 0;
     let (proc_data, relocs, rc_proc_names) = backend.build_proc(proc, layout_ids);
-    let proc_offset = output.add_symbol_data(proc_id, section_id, &proc_data, 16);
+    add_proc_data(
+        output,
+        relocations,
+        data_section,
+        fn_name,
+        section_id,
+        proc_id,
+        &proc_data,
+        &relocs,
+        &rc_proc_names,
+    )
+}
+
+/// Adds the bytes of a procedure to its section, along with the relocations it needs.
+#[allow(clippy::too_many_arguments)]
+fn add_proc_data<'a>(
+    output: &mut Object,
+    relocations: &mut Vec<'a, (SectionId, object::write::Relocation)>,
+    data_section: SectionId,
+    fn_name: String,
+    section_id: SectionId,
+    proc_id: SymbolId,
+    proc_data: &[u8],
+    relocs: &[Relocation],
+    rc_proc_names: &[(symbol::Symbol, String)],
+) {
+    let mut local_data_index = 0;
+    let proc_offset = output.add_symbol_data(proc_id, section_id, proc_data, 16);
     for reloc in relocs.iter() {
         let elfreloc = match reloc {
             Relocation::LocalData { offset, data } => {
@@ -459,14 +505,12 @@ fn build_proc<'a, B: Backend<'a>>(
                     }
                 }
                 if let Some(sym_id) = output.symbol_id(name.as_bytes()) {
-                    write::Relocation {
-                        offset: offset + proc_offset,
-                        size: 32,
-                        kind: RelocationKind::PltRelative,
-                        encoding: RelocationEncoding::X86Branch,
-                        symbol: sym_id,
-                        addend: -4,
+                    for reloc in
+                        function_relocations(output, proc_data, *offset, proc_offset, sym_id)
+                    {
+                        relocations.push((section_id, reloc));
                     }
+                    continue;
                 } else {
                     internal_error!("failed to find fn symbol for {:?}", name);
                 }
@@ -476,3 +520,77 @@ fn build_proc<'a, B: Backend<'a>>(
         relocations.push((section_id, elfreloc));
     }
 }
+
+/// Builds the relocations that point the instruction at `offset` in `proc_data` to a function.
+/// On AArch64, a call or jump needs one relocation,
+/// but loading the address of a function is an `ADRP` and `ADD` pair that needs two.
+fn function_relocations(
+    output: &Object,
+    proc_data: &[u8],
+    offset: u64,
+    proc_offset: u64,
+    symbol: SymbolId,
+) -> std::vec::Vec<write::Relocation> {
+    match output.architecture() {
+        Architecture::X86_64 => vec![write::Relocation {
+            offset: offset + proc_offset,
+            size: 32,
+            kind: RelocationKind::PltRelative,
+            encoding: RelocationEncoding::X86Branch,
+            symbol,
+            addend: -4,
+        }],
+        Architecture::Aarch64 => {
+            let start = offset as usize;
+            let inst = u32::from_le_bytes(proc_data[start..start + 4].try_into().unwrap());
+            let macho = output.format() == BinaryFormat::MachO;
+            let kinds = if inst & 0x7C00_0000 == 0x1400_0000 {
+                // `B` or `BL`, the top bit is set for `BL`.
+                if macho {
+                    vec![RelocationKind::MachO {
+                        value: object::macho::ARM64_RELOC_BRANCH26,
+                        relative: true,
+                    }]
+                } else if inst & 0x8000_0000 != 0 {
+                    vec![RelocationKind::Elf(object::elf::R_AARCH64_CALL26)]
+                } else {
+                    vec![RelocationKind::Elf(object::elf::R_AARCH64_JUMP26)]
+                }
+            } else if inst & 0x9F00_0000 == 0x9000_0000 {
+                // `ADRP` followed by an `ADD`.
+                if macho {
+                    vec![
+                        RelocationKind::MachO {
+                            value: object::macho::ARM64_RELOC_PAGE21,
+                            relative: true,
+                        },
+                        RelocationKind::MachO {
+                            value: object::macho::ARM64_RELOC_PAGEOFF12,
+                            relative: false,
+                        },
+                    ]
+                } else {
+                    vec![
+                        RelocationKind::Elf(object::elf::R_AARCH64_ADR_PREL_PG_HI21),
+                        RelocationKind::Elf(object::elf::R_AARCH64_ADD_ABS_LO12_NC),
+                    ]
+                }
+            } else {
+                internal_error!("unexpected instruction at function relocation: {:#x}", inst);
+            };
+            kinds
+                .into_iter()
+                .enumerate()
+                .map(|(i, kind)| write::Relocation {
+                    offset: offset + proc_offset + 4 * i as u64,
+                    size: 32,
+                    kind,
+                    encoding: RelocationEncoding::Generic,
+                    symbol,
+                    addend: 0,
+                })
+                .collect()
+        }
+        arch => internal_error!("unsupported architecture for the dev backend: {:?}", arch),
+    }
+}
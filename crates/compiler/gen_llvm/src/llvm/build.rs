@@ -13,6 +13,9 @@ use crate::llvm::compare::{generic_eq, generic_neq};
 use crate::llvm::convert::{
     self, argument_type_from_layout, basic_type_from_builtin, basic_type_from_layout, zig_str_type,
 };
+use crate::llvm::debug_info::{
+    declare_proc_args, proc_location, proc_subprogram, set_statement_location, DebugSources,
+};
 use crate::llvm::expect::clone_to_shared_memory;
 use crate::llvm::refcounting::{
     build_reset, decrement_refcount_layout, increment_refcount_layout, PointerToRefcount,
@@ -52,8 +55,8 @@ use roc_error_macros::internal_error;
 use roc_module::low_level::LowLevel;
 use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::ir::{
-    BranchInfo, CallType, EntryPoint, HigherOrderLowLevel, JoinPointId, ListLiteralElement,
-    ModifyRc, OptLevel, ProcLayout,
+    BranchInfo, CallType, CoveragePoint, EntryPoint, HigherOrderLowLevel, JoinPointId,
+    ListLiteralElement, ModifyRc, OptLevel, ProcLayout,
 };
 use roc_mono::layout::{
     Builtin, CapturesNiche, LambdaName, LambdaSet, Layout, LayoutIds, RawFunctionLayout,
@@ -78,20 +81,23 @@ fn print_fn_verification_output() -> bool {
 #[macro_export]
 macro_rules! debug_info_init {
     ($env:expr, $function_value:expr) => {{
+        $crate::debug_info_init!($env, $function_value, $env.compile_unit.get_file(), 0, 0)
+    }};
+    ($env:expr, $function_value:expr, $file:expr, $line:expr, $column:expr) => {{
         use inkwell::debug_info::AsDIScope;
 
         let func_scope = $function_value.get_subprogram().expect("subprogram");
         let lexical_block = $env.dibuilder.create_lexical_block(
             /* scope */ func_scope.as_debug_info_scope(),
-            /* file */ $env.compile_unit.get_file(),
-            /* line_no */ 0,
-            /* column_no */ 0,
+            /* file */ $file,
+            /* line_no */ $line,
+            /* column_no */ $column,
         );
 
         let loc = $env.dibuilder.create_debug_location(
             $env.context,
-            /* line */ 0,
-            /* column */ 0,
+            /* line */ $line,
+            /* column */ $column,
             /* current_scope */ lexical_block.as_debug_info_scope(),
             /* inlined_at */ None,
         );
@@ -211,6 +217,8 @@ pub struct Env<'a, 'ctx, 'env> {
     pub builder: &'env Builder<'ctx>,
     pub dibuilder: &'env DebugInfoBuilder<'ctx>,
     pub compile_unit: &'env DICompileUnit<'ctx>,
+    /// Only present when emitting debug info that points at the Roc source
+    pub debug_sources: Option<&'env DebugSources<'ctx>>,
     pub module: &'ctx Module<'ctx>,
     pub interns: Interns,
     pub target_info: TargetInfo,
@@ -399,7 +407,7 @@ impl<'a, 'ctx, 'env> Env<'a, 'ctx, 'env> {
             /* language */ inkwell::debug_info::DWARFSourceLanguage::C,
             /* filename */ "roc_app",
             /* directory */ ".",
-            /* producer */ "my llvm compiler frontend",
+            /* producer */ "roc",
             /* is_optimized */ false,
             /* compiler command line flags */ "",
             /* runtime_ver */ 0,
@@ -2448,6 +2456,8 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
             for (symbol, expr, layout) in queue {
                 debug_assert!(layout != &Layout::RecursivePointer);
 
+                set_statement_location(env, parent, *symbol);
+
                 let val = build_exp_expr(
                     env,
                     layout_ids,
@@ -2686,6 +2696,12 @@ pub fn build_exp_stmt<'a, 'ctx, 'env>(
             context.i64_type().const_zero().into()
         }
 
+        Coverage(point, cont) => {
+            increment_coverage_counter(env, point);
+
+            build_exp_stmt(env, layout_ids, func_spec_solutions, scope, parent, cont)
+        }
+
         Refcounting(modify, cont) => {
             use ModifyRc::*;
 
@@ -3937,6 +3953,31 @@ pub fn build_setjmp_call<'a, 'ctx, 'env>(env: &Env<'a, 'ctx, 'env>) -> BasicValu
     }
 }
 
+/// Counts one more execution of the code at `point`. The counters are exported globals, so that
+/// `roc test --coverage` can read them once the expects have run.
+fn increment_coverage_counter<'a, 'ctx, 'env>(env: &Env<'a, 'ctx, 'env>, point: &CoveragePoint) {
+    let counter_type = env.context.i64_type();
+
+    let name = point.counter_name();
+    let global = env.module.get_global(&name).unwrap_or_else(|| {
+        let global = env.module.add_global(counter_type, None, &name);
+        global.set_initializer(&counter_type.const_zero());
+        global
+    });
+
+    let counter = global.as_pointer_value();
+    let count = env
+        .builder
+        .build_load(counter, "coverage_count")
+        .into_int_value();
+    let count = env.builder.build_int_add(
+        count,
+        counter_type.const_int(1, false),
+        "coverage_count_inc",
+    );
+    env.builder.build_store(counter, count);
+}
+
 /// Pointer to pointer of the panic message.
 pub fn get_panic_msg_ptr<'a, 'ctx, 'env>(env: &Env<'a, 'ctx, 'env>) -> PointerValue<'ctx> {
     let ptr_to_u8_ptr = env.context.i8_type().ptr_type(AddressSpace::Generic);
@@ -4477,7 +4518,7 @@ fn build_proc_header<'a, 'ctx, 'env>(
         Linkage::Internal,
     );
 
-    let subprogram = env.new_subprogram(&fn_name);
+    let subprogram = proc_subprogram(env, &fn_name, proc);
     fn_val.set_subprogram(subprogram);
 
     if env.exposed_to_host.contains(&symbol) {
@@ -4827,7 +4868,10 @@ pub fn build_proc<'a, 'ctx, 'env>(
 
     builder.position_at_end(entry);
 
-    debug_info_init!(env, fn_val);
+    match proc_location(env, proc) {
+        Some((file, line, column)) => debug_info_init!(env, fn_val, file, line, column),
+        None => debug_info_init!(env, fn_val),
+    }
 
     // Add args to scope
     for (arg_val, (layout, arg_symbol)) in fn_val.get_param_iter().zip(args) {
@@ -4850,6 +4894,8 @@ pub fn build_proc<'a, 'ctx, 'env>(
             builder.build_return(Some(&body));
         }
     }
+
+    declare_proc_args(env, fn_val, entry, proc);
 }
 
 pub fn verify_fn(fn_val: FunctionValue<'_>) {
@@ -6371,8 +6417,20 @@ fn run_low_level<'a, 'ctx, 'env>(
 
             BasicValueEnum::IntValue(bool_val)
         }
-        Hash => {
-            unimplemented!()
+        HashBytes => {
+            // Dict.hashBytesLowlevel : U64, List U8 -> U64
+            debug_assert_eq!(args.len(), 2);
+
+            let seed = load_symbol(scope, &args[0]);
+            let list = load_symbol(scope, &args[1]).into_struct_value();
+
+            call_list_bitcode_fn(
+                env,
+                &[list],
+                &[seed],
+                BitcodeReturns::Basic,
+                bitcode::DICT_HASH_BYTES,
+            )
         }
 
         ListMap | ListMap2 | ListMap3 | ListMap4 | ListSortWith => {
@@ -0,0 +1,277 @@
+//! Source-level debug information (DWARF) for the procedures of a Roc program.
+//!
+//! Every function we generate gets a `DISubprogram`, because LLVM will only attach locations
+//! to instructions in functions that have one. Normally those are placeholders that are stripped
+//! before the module is emitted. When the program is built with `--debug`, the `Env` carries
+//! `DebugSources`, and the subprograms of Roc procedures point at the source they came from,
+//! as do the instructions of the definitions in their bodies.
+use crate::llvm::build::Env;
+use crate::llvm::convert::basic_type_from_layout;
+use inkwell::basic_block::BasicBlock;
+use inkwell::debug_info::{
+    AsDIScope, DIFile, DIFlags, DIFlagsConstants, DISubprogram, DIType, DebugInfoBuilder,
+};
+use inkwell::values::{BasicValue, FunctionValue};
+use roc_collections::all::MutMap;
+use roc_module::symbol::{ModuleId, Symbol};
+use roc_mono::ir::Proc;
+use roc_mono::layout::{Builtin, Layout};
+use roc_region::all::{LineInfo, Region};
+use std::path::PathBuf;
+
+// DWARF base type encodings, see section 7.8 of the DWARF 4 standard
+const DW_ATE_BOOLEAN: u32 = 0x02;
+const DW_ATE_FLOAT: u32 = 0x04;
+const DW_ATE_SIGNED: u32 = 0x05;
+const DW_ATE_UNSIGNED: u32 = 0x07;
+
+// DWARF expression opcodes, see section 7.7.1 of the DWARF 4 standard
+const DW_OP_DEREF: i64 = 0x06;
+
+/// The source files of the modules in a program, so regions can be turned into lines.
+pub struct DebugSources<'ctx> {
+    files: MutMap<ModuleId, (DIFile<'ctx>, LineInfo)>,
+    /// Where the definitions in procedure bodies start, see `Proc::statement_regions`
+    statements: MutMap<Symbol, Region>,
+}
+
+impl<'ctx> DebugSources<'ctx> {
+    pub fn new<'a, 'b>(
+        dibuilder: &DebugInfoBuilder<'ctx>,
+        sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+        procs: impl IntoIterator<Item = &'b Proc<'a>>,
+    ) -> Self
+    where
+        'a: 'b,
+    {
+        let files = sources
+            .iter()
+            .map(|(module_id, (path, src))| {
+                // debuggers are not necessarily started from the directory we were run from
+                let path = path.canonicalize().unwrap_or_else(|_| path.clone());
+                let filename = path.file_name().unwrap_or_default().to_string_lossy();
+                let directory = match path.parent() {
+                    Some(parent) => parent.to_string_lossy(),
+                    None => ".".into(),
+                };
+
+                let file = dibuilder.create_file(&filename, &directory);
+
+                (*module_id, (file, LineInfo::new(src)))
+            })
+            .collect();
+
+        let statements = procs
+            .into_iter()
+            .flat_map(|proc| proc.statement_regions.iter().copied())
+            .collect();
+
+        Self { files, statements }
+    }
+
+    /// The file of the given module, and the 1-based line and column where `region` starts
+    pub fn location(
+        &self,
+        module_id: ModuleId,
+        region: Region,
+    ) -> Option<(DIFile<'ctx>, u32, u32)> {
+        let (file, line_info) = self.files.get(&module_id)?;
+        let line_column = line_info.convert_pos(region.start());
+
+        Some((*file, line_column.line + 1, line_column.column + 1))
+    }
+}
+
+/// Where a procedure is defined, if we emit debug info and know its source.
+/// Procedures the compiler generated (e.g. refcounting helpers) have no location.
+pub fn proc_location<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    proc: &Proc<'a>,
+) -> Option<(DIFile<'ctx>, u32, u32)> {
+    let debug_sources = env.debug_sources?;
+
+    if proc.region == Region::zero() {
+        return None;
+    }
+
+    debug_sources.location(proc.name.name().module_id(), proc.region)
+}
+
+/// Points the instructions built from now on at the definition that starts by binding `symbol`,
+/// if it is one.
+pub fn set_statement_location<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    fn_val: FunctionValue<'ctx>,
+    symbol: Symbol,
+) {
+    let debug_sources = match env.debug_sources {
+        Some(debug_sources) => debug_sources,
+        None => return,
+    };
+
+    let location = debug_sources
+        .statements
+        .get(&symbol)
+        .and_then(|region| debug_sources.location(symbol.module_id(), *region));
+
+    if let (Some((_, line, column)), Some(subprogram)) = (location, fn_val.get_subprogram()) {
+        let loc = env.dibuilder.create_debug_location(
+            env.context,
+            line,
+            column,
+            subprogram.as_debug_info_scope(),
+            None,
+        );
+
+        env.builder.set_current_debug_location(env.context, loc);
+    }
+}
+
+/// The subprogram of a Roc procedure. When we know where the procedure is defined, it gets
+/// the readable `Module.name` of the procedure and a signature based on its layouts.
+pub fn proc_subprogram<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    fn_name: &str,
+    proc: &Proc<'a>,
+) -> DISubprogram<'ctx> {
+    let (file, line, _) = match proc_location(env, proc) {
+        Some(location) => location,
+        None => return env.new_subprogram(fn_name),
+    };
+
+    let symbol = proc.name.name();
+    let readable_name = format!(
+        "{}.{}",
+        symbol.module_string(&env.interns),
+        symbol.as_str(&env.interns)
+    );
+
+    let return_type = ditype_from_layout(env, &proc.ret_layout);
+    let parameter_types: Vec<DIType<'ctx>> = proc
+        .args
+        .iter()
+        .map(|(layout, _)| ditype_from_layout(env, layout))
+        .collect();
+
+    let subroutine_type = env.dibuilder.create_subroutine_type(
+        file,
+        Some(return_type),
+        &parameter_types,
+        DIFlags::PUBLIC,
+    );
+
+    env.dibuilder.create_function(
+        /* scope */ file.as_debug_info_scope(),
+        /* func name */ &readable_name,
+        /* linkage_name */ Some(fn_name),
+        /* file */ file,
+        /* line_no */ line,
+        /* DIType */ subroutine_type,
+        /* is_local_to_unit */ true,
+        /* is_definition */ true,
+        /* scope_line */ line,
+        /* flags */ DIFlags::PUBLIC,
+        /* is_optimized */ false,
+    )
+}
+
+/// Describe the arguments of a Roc procedure to the debugger. Must be called after the body of
+/// `fn_val` is built, because the variable info is attached to the first instruction of `entry`.
+pub fn declare_proc_args<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    fn_val: FunctionValue<'ctx>,
+    entry: BasicBlock<'ctx>,
+    proc: &Proc<'a>,
+) {
+    let (file, line, column) = match proc_location(env, proc) {
+        Some(location) => location,
+        None => return,
+    };
+
+    let (subprogram, first_instruction) =
+        match (fn_val.get_subprogram(), entry.get_first_instruction()) {
+            (Some(subprogram), Some(instruction)) => (subprogram, instruction),
+            _ => return,
+        };
+
+    let scope = subprogram.as_debug_info_scope();
+    let loc = env
+        .dibuilder
+        .create_debug_location(env.context, line, column, scope, None);
+
+    for (arg_no, (arg_val, (layout, arg_symbol))) in
+        fn_val.get_param_iter().zip(proc.args).enumerate()
+    {
+        let variable = env.dibuilder.create_parameter_variable(
+            scope,
+            arg_symbol.as_str(&env.interns),
+            /* arg_no (1-based) */ arg_no as u32 + 1,
+            file,
+            line,
+            ditype_from_layout(env, layout),
+            /* always_preserve */ true,
+            DIFlags::ZERO,
+        );
+
+        // large values are passed by reference; the variable lives behind the pointer
+        let expression = if arg_val.get_type() == basic_type_from_layout(env, layout) {
+            None
+        } else {
+            Some(env.dibuilder.create_expression(vec![DW_OP_DEREF]))
+        };
+
+        env.dibuilder.insert_dbg_value_before(
+            arg_val.as_basic_value_enum(),
+            variable,
+            expression,
+            loc,
+            first_instruction,
+        );
+    }
+}
+
+/// A DWARF type for values of this layout. Numbers get their real encoding; other values are
+/// described as arrays of bytes of the right size, so a debugger can at least show their memory.
+pub fn ditype_from_layout<'a, 'ctx, 'env>(
+    env: &Env<'a, 'ctx, 'env>,
+    layout: &Layout<'a>,
+) -> DIType<'ctx> {
+    let size_in_bits = layout.stack_size(env.target_info) as u64 * 8;
+
+    let basic_type = |name: &str, encoding| {
+        env.dibuilder
+            .create_basic_type(name, size_in_bits, encoding, DIFlags::PUBLIC)
+            .unwrap()
+            .as_type()
+    };
+
+    match layout {
+        Layout::Builtin(Builtin::Bool) => basic_type("Bool", DW_ATE_BOOLEAN),
+        Layout::Builtin(Builtin::Int(int_width)) => {
+            let encoding = if int_width.is_signed() {
+                DW_ATE_SIGNED
+            } else {
+                DW_ATE_UNSIGNED
+            };
+
+            basic_type(&format!("{:?}", int_width), encoding)
+        }
+        Layout::Builtin(Builtin::Float(float_width)) => {
+            basic_type(&format!("{:?}", float_width), DW_ATE_FLOAT)
+        }
+        Layout::Builtin(Builtin::Decimal) => basic_type("Dec", DW_ATE_SIGNED),
+        _ => {
+            let byte_type = env
+                .dibuilder
+                .create_basic_type("U8", 8, DW_ATE_UNSIGNED, DIFlags::PUBLIC)
+                .unwrap()
+                .as_type();
+            let align_in_bits = layout.alignment_bytes(env.target_info) * 8;
+            let length = size_in_bits as i64 / 8;
+
+            env.dibuilder
+                .create_array_type(byte_type, size_in_bits, align_in_bits, &[0..length])
+                .as_type()
+        }
+    }
+}
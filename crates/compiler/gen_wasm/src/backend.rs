@@ -6,29 +6,31 @@ use roc_builtins::bitcode::{FloatWidth, IntWidth};
 use roc_collections::all::MutMap;
 use roc_error_macros::internal_error;
 use roc_module::low_level::{LowLevel, LowLevelWrapperType};
-use roc_module::symbol::{Interns, Symbol};
+use roc_module::symbol::{Interns, ModuleId, Symbol};
 use roc_mono::code_gen_help::{CodeGenHelp, HelperOp, REFCOUNT_MAX};
 use roc_mono::ir::{
     BranchInfo, CallType, Expr, JoinPointId, ListLiteralElement, Literal, ModifyRc, Param, Proc,
     ProcLayout, Stmt,
 };
 use roc_mono::layout::{Builtin, Layout, LayoutIds, TagIdIntType, UnionLayout};
+use roc_region::all::{LineColumn, LineInfo, Region};
 use roc_std::RocDec;
 
 use crate::layout::{CallConv, ReturnMethod, WasmLayout};
 use crate::low_level::{call_higher_order_lowlevel, LowLevelCall};
-use crate::storage::{AddressValue, Storage, StoredValue, StoredVarKind};
+use crate::storage::{AddressValue, StackMemoryLocation, Storage, StoredValue, StoredVarKind};
 use crate::wasm_module::linking::{DataSymbol, WasmObjectSymbol};
 use crate::wasm_module::sections::{
     ConstExpr, DataMode, DataSegment, Export, Global, GlobalType, Import, ImportDesc, Limits,
     MemorySection, NameSection,
 };
+use crate::wasm_module::source_map::SourceLocation;
 use crate::wasm_module::{
     code_builder, CodeBuilder, ExportType, LocalId, Signature, SymInfo, ValueType, WasmModule,
 };
 use crate::{
-    copy_memory, round_up_to_alignment, CopyMemoryConfig, Env, DEBUG_SETTINGS, MEMORY_NAME,
-    PTR_SIZE, PTR_TYPE, TARGET_INFO,
+    copy_memory, debug_source_files, round_up_to_alignment, CopyMemoryConfig, Env, DEBUG_SETTINGS,
+    MEMORY_NAME, PTR_SIZE, PTR_TYPE, TARGET_INFO,
 };
 
 #[derive(Clone, Copy, Debug)]
@@ -60,6 +62,8 @@ pub struct WasmBackend<'a> {
     host_lookup: Vec<'a, (&'a str, u32)>,
     helper_proc_gen: CodeGenHelp<'a>,
     can_relocate_heap: bool,
+    /// Index in the source map, and line offsets, of each module's source file (debug builds only)
+    source_files: MutMap<ModuleId, (u32, LineInfo)>,
 
     // Function-level data
     pub code_builder: CodeBuilder<'a>,
@@ -105,6 +109,12 @@ impl<'a> WasmBackend<'a> {
         module.link_host_to_app_calls(env.arena, host_to_app_map);
         module.code.code_builders.reserve(proc_lookup.len());
 
+        let source_files = debug_source_files(env)
+            .into_iter()
+            .enumerate()
+            .map(|(i, (module_id, _, src))| (module_id, (i as u32, LineInfo::new(src))))
+            .collect();
+
         let host_function_count = module.import.imports.len()
             + (module.code.dead_import_dummy_count + module.code.preloaded_count) as usize;
 
@@ -122,6 +132,7 @@ impl<'a> WasmBackend<'a> {
             host_lookup,
             helper_proc_gen,
             can_relocate_heap,
+            source_files,
 
             // Function-level data
             block_depth: 0,
@@ -384,13 +395,17 @@ impl<'a> WasmBackend<'a> {
             println!("\ngenerating procedure {:?}\n", proc.name);
         }
 
-        self.append_proc_debug_name(proc.name.name());
+        let wasm_fn_index = self.append_proc_debug_name(proc.name.name());
+        self.code_builder.source_location = self.proc_source_location(proc);
 
         self.start_proc(proc);
 
         self.stmt(&proc.body);
 
         self.finalize_proc();
+        if self.env.debug_sources.is_some() {
+            self.append_proc_local_names(wasm_fn_index);
+        }
         self.reset();
 
         if DEBUG_SETTINGS.proc_start_end {
@@ -455,7 +470,7 @@ impl<'a> WasmBackend<'a> {
         }
     }
 
-    fn append_proc_debug_name(&mut self, sym: Symbol) {
+    fn append_proc_debug_name(&mut self, sym: Symbol) -> u32 {
         let proc_index = self
             .proc_lookup
             .iter()
@@ -463,8 +478,70 @@ impl<'a> WasmBackend<'a> {
             .unwrap();
         let wasm_fn_index = self.fn_index_offset + proc_index as u32;
 
-        let name = String::from_str_in(sym.as_str(self.interns), self.env.arena).into_bump_str();
+        let name = if self.env.debug_sources.is_some() {
+            // In a stack trace, it's useful to know which module the function is from
+            bumpalo::format!(
+                in self.env.arena,
+                "{}.{}",
+                sym.module_string(self.interns),
+                sym.as_str(self.interns)
+            )
+            .into_bump_str()
+        } else {
+            String::from_str_in(sym.as_str(self.interns), self.env.arena).into_bump_str()
+        };
         self.module.names.append_function(wasm_fn_index, name);
+
+        wasm_fn_index
+    }
+
+    /// Name the Wasm locals after the Roc symbols stored in them, so debuggers can show them
+    fn append_proc_local_names(&mut self, wasm_fn_index: u32) {
+        let arena = self.env.arena;
+        let mut local_names =
+            Vec::with_capacity_in(self.storage.symbol_storage_map.len() + 2, arena);
+
+        for (sym, storage) in self.storage.symbol_storage_map.iter() {
+            let local_id = match storage {
+                StoredValue::Local { local_id, .. }
+                | StoredValue::StackMemory {
+                    location: StackMemoryLocation::PointerArg(local_id),
+                    ..
+                } => *local_id,
+                _ => continue,
+            };
+            let name = String::from_str_in(sym.as_str(self.interns), arena).into_bump_str();
+            local_names.push((local_id.0, name));
+        }
+        if let Some(LocalId(index)) = self.storage.return_var {
+            local_names.push((index, "return_value"));
+        }
+        if let Some(LocalId(index)) = self.storage.stack_frame_pointer {
+            local_names.push((index, "stack_frame_pointer"));
+        }
+
+        // Several symbols can share a local. Pick one consistently, so builds are reproducible.
+        local_names.sort_unstable();
+        local_names.dedup_by_key(|(index, _)| *index);
+
+        self.module.names.append_locals(wasm_fn_index, local_names);
+    }
+
+    /// Where a procedure was defined, if we're emitting debug info and it came from Roc source
+    fn proc_source_location(&self, proc: &Proc<'a>) -> Option<SourceLocation> {
+        if proc.region == Region::zero() {
+            return None;
+        }
+
+        let module_id = proc.name.name().module_id();
+        let (file, line_info) = self.source_files.get(&module_id)?;
+        let LineColumn { line, column } = line_info.convert_pos(proc.region.start());
+
+        Some(SourceLocation {
+            file: *file,
+            line,
+            column,
+        })
     }
 
     /// Build a wrapper around a Roc procedure so that it can be called from Zig builtins List.map*
@@ -691,6 +768,9 @@ impl<'a> WasmBackend<'a> {
 
             Stmt::Refcounting(modify, following) => self.stmt_refcounting(modify, following),
 
+            // coverage is only counted in the LLVM backend
+            Stmt::Coverage(_, following) => self.stmt(following),
+
             Stmt::Expect { .. } => todo!("expect is not implemented in the wasm backend"),
 
             Stmt::RuntimeError(msg) => self.stmt_runtime_error(msg),
@@ -8,6 +8,8 @@ pub mod wasm_module;
 pub mod wasm32_result;
 pub mod wasm32_sized;
 
+use std::path::PathBuf;
+
 use bitvec::prelude::BitVec;
 use bumpalo::collections::Vec;
 use bumpalo::{self, Bump};
@@ -21,6 +23,7 @@ use roc_target::TargetInfo;
 use wasm_module::parse::ParseError;
 
 use crate::backend::{ProcLookupData, ProcSource, WasmBackend};
+use crate::wasm_module::source_map::SourceFile;
 use crate::wasm_module::{Align, CodeBuilder, LocalId, ValueType, WasmModule};
 
 const TARGET_INFO: TargetInfo = TargetInfo::default_wasm32();
@@ -46,6 +49,9 @@ pub struct Env<'a> {
     pub module_id: ModuleId,
     pub exposed_to_host: MutSet<Symbol>,
     pub stack_bytes: u32,
+    /// Source code of each module, if we should emit debug info.
+    /// Debug builds get local variable names, module-qualified function names, and a source map.
+    pub debug_sources: Option<&'a MutMap<ModuleId, (PathBuf, Box<str>)>>,
 }
 
 impl Env<'_> {
@@ -75,10 +81,32 @@ pub fn build_app_binary<'a>(
     wasm_module.eliminate_dead_code(env.arena, called_preload_fns);
 
     let mut buffer = std::vec::Vec::with_capacity(wasm_module.size());
-    wasm_module.serialize(&mut buffer);
+    if env.debug_sources.is_some() {
+        let files = std::vec::Vec::from_iter(debug_source_files(env).into_iter().map(
+            |(_, path, content)| SourceFile {
+                path: path.display().to_string(),
+                content,
+            },
+        ));
+        wasm_module.serialize_with_source_map(&mut buffer, &files);
+    } else {
+        wasm_module.serialize(&mut buffer);
+    }
     buffer
 }
 
+/// The source files for debug info, in the order they're listed in the source map
+fn debug_source_files<'a>(env: &Env<'a>) -> std::vec::Vec<(ModuleId, &'a PathBuf, &'a str)> {
+    let mut files = std::vec::Vec::from_iter(
+        env.debug_sources
+            .into_iter()
+            .flat_map(|sources| sources.iter())
+            .map(|(module_id, (path, src))| (*module_id, path, &src[..])),
+    );
+    files.sort_by(|(_, a, _), (_, b, _)| a.cmp(b));
+    files
+}
+
 /// Generate an unserialized Wasm module
 /// Shared by all consumers of gen_wasm: roc_build, roc_repl_wasm, and test_gen
 /// (roc_repl_wasm and test_gen will add more generated code for a wrapper function
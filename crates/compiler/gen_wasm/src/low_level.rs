@@ -42,6 +42,13 @@ impl CodeGenNumType {
 
 const UPDATE_MODE_IMMUTABLE: i32 = 0;
 
+const ABS_PANIC_MSG: &str = "integer absolute overflowed because its argument is the minimum value";
+const NEG_PANIC_MSG: &str = "integer negation overflowed because its argument is the minimum value";
+
+/// Byte offsets of the two halves of a 128-bit number in memory (little-endian)
+const LOW_BITS: u32 = 0;
+const HIGH_BITS: u32 = 8;
+
 impl From<Layout<'_>> for CodeGenNumType {
     fn from(layout: Layout) -> CodeGenNumType {
         use CodeGenNumType::*;
@@ -802,8 +809,7 @@ impl<'a> LowLevelCall<'a> {
             NumAddWrap => match self.ret_layout {
                 Layout::Builtin(Builtin::Int(width)) => match width {
                     IntWidth::I128 | IntWidth::U128 => {
-                        // TODO: don't panic
-                        self.load_args_and_call_zig(backend, &bitcode::NUM_ADD_OR_PANIC_INT[width])
+                        self.load_args_and_call_zig(backend, &bitcode::NUM_ADD_WRAP_INT[width])
                     }
                     IntWidth::I64 | IntWidth::U64 => {
                         self.load_args(backend);
@@ -895,8 +901,7 @@ impl<'a> LowLevelCall<'a> {
             NumSubWrap => match self.ret_layout {
                 Layout::Builtin(Builtin::Int(width)) => match width {
                     IntWidth::I128 | IntWidth::U128 => {
-                        // TODO: don't panic
-                        self.load_args_and_call_zig(backend, &bitcode::NUM_SUB_OR_PANIC_INT[width])
+                        self.load_args_and_call_zig(backend, &bitcode::NUM_SUB_WRAP_INT[width])
                     }
                     IntWidth::I64 | IntWidth::U64 => {
                         self.load_args(backend);
@@ -985,8 +990,7 @@ impl<'a> LowLevelCall<'a> {
             NumMulWrap => match self.ret_layout {
                 Layout::Builtin(Builtin::Int(width)) => match width {
                     IntWidth::I128 | IntWidth::U128 => {
-                        // TODO: don't panic
-                        self.load_args_and_call_zig(backend, &bitcode::NUM_MUL_OR_PANIC_INT[width])
+                        self.load_args_and_call_zig(backend, &bitcode::NUM_MUL_WRAP_INT[width])
                     }
                     IntWidth::I64 | IntWidth::U64 => {
                         self.load_args(backend);
@@ -1052,6 +1056,14 @@ impl<'a> LowLevelCall<'a> {
                     x => internal_error!("NumMulChecked is not defined for {:?}", x),
                 }
             }
+            NumGt | NumGte | NumLt | NumLte | NumCompare
+                if matches!(
+                    CodeGenNumType::for_symbol(backend, self.arguments[0]),
+                    I128 | Decimal
+                ) =>
+            {
+                self.compare_num128(backend)
+            }
             NumGt => {
                 self.load_args(backend);
                 match CodeGenNumType::for_symbol(backend, self.arguments[0]) {
@@ -1211,6 +1223,12 @@ impl<'a> LowLevelCall<'a> {
                     }
                     F32 => backend.code_builder.f32_div(),
                     F64 => backend.code_builder.f64_div(),
+                    I128 => match self.ret_layout {
+                        Layout::Builtin(Builtin::Int(width)) => {
+                            self.load_args_and_call_zig(backend, &bitcode::NUM_DIV_TRUNC[width])
+                        }
+                        _ => panic_ret_type(),
+                    },
                     Decimal => self.load_args_and_call_zig(backend, bitcode::DEC_DIV),
                     x => todo!("{:?} for {:?}", self.lowlevel, x),
                 }
@@ -1224,9 +1242,27 @@ impl<'a> LowLevelCall<'a> {
 
             NumRemUnchecked => {
                 self.load_args(backend);
-                match CodeGenNumType::for_symbol(backend, self.arguments[0]) {
-                    I32 => backend.code_builder.i32_rem_s(),
-                    I64 => backend.code_builder.i64_rem_s(),
+                let is_signed = symbol_is_signed_int(backend, self.arguments[0]);
+                match self.ret_layout {
+                    Layout::Builtin(Builtin::Int(width)) => {
+                        match CodeGenNumType::from(self.ret_layout) {
+                            I32 => {
+                                if is_signed {
+                                    backend.code_builder.i32_rem_s()
+                                } else {
+                                    backend.code_builder.i32_rem_u()
+                                }
+                            }
+                            I64 => {
+                                if is_signed {
+                                    backend.code_builder.i64_rem_s()
+                                } else {
+                                    backend.code_builder.i64_rem_u()
+                                }
+                            }
+                            _ => self.load_args_and_call_zig(backend, &bitcode::NUM_REM[width]),
+                        }
+                    }
                     _ => todo!("{:?} for {:?}", self.lowlevel, self.ret_layout),
                 }
             }
@@ -1326,13 +1362,19 @@ impl<'a> LowLevelCall<'a> {
                         code_builder.get_local(tmp);
                     }
 
+                    I128 => match layout {
+                        Layout::Builtin(Builtin::Int(width)) => self
+                            .load_args_and_call_zig(backend, &bitcode::NUM_IS_MULTIPLE_OF[width]),
+                        _ => panic_ret_type(),
+                    },
+
                     _ => panic_ret_type(),
                 }
             }
+            NumAbs | NumNeg if matches!(CodeGenNumType::from(self.ret_layout), I128 | Decimal) => {
+                self.negate_num128(backend)
+            }
             NumAbs => {
-                const PANIC_MSG: &str =
-                    "integer absolute overflowed because its argument is the minimum value";
-
                 self.load_args(backend);
 
                 match CodeGenNumType::from(self.ret_layout) {
@@ -1343,7 +1385,7 @@ impl<'a> LowLevelCall<'a> {
                         backend.code_builder.i32_const(i32::MIN);
                         backend.code_builder.i32_eq();
                         backend.code_builder.if_();
-                        backend.stmt_runtime_error(PANIC_MSG);
+                        backend.stmt_runtime_error(ABS_PANIC_MSG);
                         backend.code_builder.end();
 
                         // x
@@ -1369,7 +1411,7 @@ impl<'a> LowLevelCall<'a> {
                         backend.code_builder.i64_const(i64::MIN);
                         backend.code_builder.i64_eq();
                         backend.code_builder.if_();
-                        backend.stmt_runtime_error(PANIC_MSG);
+                        backend.stmt_runtime_error(ABS_PANIC_MSG);
                         backend.code_builder.end();
 
                         // x
@@ -1394,16 +1436,13 @@ impl<'a> LowLevelCall<'a> {
                 }
             }
             NumNeg => {
-                const PANIC_MSG: &str =
-                    "integer negation overflowed because its argument is the minimum value";
-
                 self.load_args(backend);
                 match CodeGenNumType::from(self.ret_layout) {
                     I32 => {
                         backend.code_builder.i32_const(i32::MIN);
                         backend.code_builder.i32_eq();
                         backend.code_builder.if_();
-                        backend.stmt_runtime_error(PANIC_MSG);
+                        backend.stmt_runtime_error(NEG_PANIC_MSG);
                         backend.code_builder.end();
 
                         backend.code_builder.i32_const(0);
@@ -1414,7 +1453,7 @@ impl<'a> LowLevelCall<'a> {
                         backend.code_builder.i64_const(i64::MIN);
                         backend.code_builder.i64_eq();
                         backend.code_builder.if_();
-                        backend.stmt_runtime_error(PANIC_MSG);
+                        backend.stmt_runtime_error(NEG_PANIC_MSG);
                         backend.code_builder.end();
 
                         backend.code_builder.i64_const(0);
@@ -1474,6 +1513,9 @@ impl<'a> LowLevelCall<'a> {
                     (F64, F32) => backend.code_builder.f64_promote_f32(),
                     (F64, F64) => {}
 
+                    (F32, I128) => self.int128_to_float(backend, FloatWidth::F32),
+                    (F64, I128) => self.int128_to_float(backend, FloatWidth::F64),
+
                     _ => todo!("{:?}: {:?} -> {:?}", self.lowlevel, arg_type, ret_type),
                 }
             }
@@ -1488,10 +1530,10 @@ impl<'a> LowLevelCall<'a> {
                 let arg_type = CodeGenNumType::for_symbol(backend, self.arguments[0]);
                 let ret_type = CodeGenNumType::from(self.ret_layout);
 
-                let width = match ret_type {
-                    CodeGenNumType::I32 => IntWidth::I32,
-                    CodeGenNumType::I64 => IntWidth::I64,
-                    CodeGenNumType::I128 => todo!("{:?} for I128", self.lowlevel),
+                let width = match (ret_type, self.ret_layout) {
+                    (CodeGenNumType::I32, _) => IntWidth::I32,
+                    (CodeGenNumType::I64, _) => IntWidth::I64,
+                    (CodeGenNumType::I128, Layout::Builtin(Builtin::Int(width))) => width,
                     _ => internal_error!("Invalid return type for round: {:?}", ret_type),
                 };
 
@@ -1526,7 +1568,21 @@ impl<'a> LowLevelCall<'a> {
                     (I32, F64) => backend.code_builder.i32_trunc_s_f64(),
                     (I64, F32) => backend.code_builder.i64_trunc_s_f32(),
                     (I64, F64) => backend.code_builder.i64_trunc_s_f64(),
-                    (I128, _) => todo!("{:?} for I128", self.lowlevel),
+                    (I128, F32 | F64) => {
+                        // Zig's rounding function converts floats to 128-bit integers for us.
+                        // The float is already an integer, so it won't change its value.
+                        // The return address was pushed before the argument by `load_args`.
+                        let width = match self.ret_layout {
+                            Layout::Builtin(Builtin::Int(width)) => width,
+                            x => internal_error!("{:?} is not defined for {:?}", self.lowlevel, x),
+                        };
+                        let name = if arg_type == F32 {
+                            &bitcode::NUM_ROUND_F32[width]
+                        } else {
+                            &bitcode::NUM_ROUND_F64[width]
+                        };
+                        backend.call_host_fn_after_loading_args(name, 2, false);
+                    }
                     _ => panic_ret_type(),
                 }
             }
@@ -1539,10 +1595,10 @@ impl<'a> LowLevelCall<'a> {
                 debug_assert!(base_type == exponent_type);
                 debug_assert!(exponent_type == ret_type);
 
-                let width = match ret_type {
-                    CodeGenNumType::I32 => IntWidth::I32,
-                    CodeGenNumType::I64 => IntWidth::I64,
-                    CodeGenNumType::I128 => todo!("{:?} for I128", self.lowlevel),
+                let width = match (ret_type, self.ret_layout) {
+                    (CodeGenNumType::I32, _) => IntWidth::I32,
+                    (CodeGenNumType::I64, _) => IntWidth::I64,
+                    (CodeGenNumType::I128, Layout::Builtin(Builtin::Int(width))) => width,
                     _ => internal_error!("Invalid return type for pow: {:?}", ret_type),
                 };
 
@@ -1571,12 +1627,16 @@ impl<'a> LowLevelCall<'a> {
             },
             NumBytesToU16 => self.load_args_and_call_zig(backend, bitcode::NUM_BYTES_TO_U16),
             NumBytesToU32 => self.load_args_and_call_zig(backend, bitcode::NUM_BYTES_TO_U32),
+            NumBitwiseAnd | NumBitwiseXor | NumBitwiseOr
+                if CodeGenNumType::from(self.ret_layout) == I128 =>
+            {
+                self.bitwise_num128(backend)
+            }
             NumBitwiseAnd => {
                 self.load_args(backend);
                 match CodeGenNumType::from(self.ret_layout) {
                     I32 => backend.code_builder.i32_and(),
                     I64 => backend.code_builder.i64_and(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
                     _ => panic_ret_type(),
                 }
             }
@@ -1585,7 +1645,6 @@ impl<'a> LowLevelCall<'a> {
                 match CodeGenNumType::from(self.ret_layout) {
                     I32 => backend.code_builder.i32_xor(),
                     I64 => backend.code_builder.i64_xor(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
                     _ => panic_ret_type(),
                 }
             }
@@ -1594,10 +1653,24 @@ impl<'a> LowLevelCall<'a> {
                 match CodeGenNumType::from(self.ret_layout) {
                     I32 => backend.code_builder.i32_or(),
                     I64 => backend.code_builder.i64_or(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
                     _ => panic_ret_type(),
                 }
             }
+            NumShiftLeftBy | NumShiftRightBy | NumShiftRightZfBy
+                if CodeGenNumType::from(self.ret_layout) == I128 =>
+            {
+                let width = match self.ret_layout {
+                    Layout::Builtin(Builtin::Int(width)) => width,
+                    x => internal_error!("{:?} is not defined for {:?}", self.lowlevel, x),
+                };
+                // The Zig functions take their arguments in the same order as the low-level
+                let name = match self.lowlevel {
+                    NumShiftLeftBy => &bitcode::NUM_SHIFT_LEFT_BY[width],
+                    NumShiftRightBy => &bitcode::NUM_SHIFT_RIGHT_BY[width],
+                    _ => &bitcode::NUM_SHIFT_RIGHT_ZF_BY[width],
+                };
+                self.load_args_and_call_zig(backend, name);
+            }
             NumShiftLeftBy => {
                 // Swap order of arguments
                 backend.storage.load_symbols(
@@ -1607,7 +1680,6 @@ impl<'a> LowLevelCall<'a> {
                 match CodeGenNumType::from(self.ret_layout) {
                     I32 => backend.code_builder.i32_shl(),
                     I64 => backend.code_builder.i64_shl(),
-                    I128 => todo!("{:?} for I128", self.lowlevel),
                     _ => panic_ret_type(),
                 }
             }
@@ -1652,7 +1724,6 @@ impl<'a> LowLevelCall<'a> {
                             .load_symbols(&mut backend.code_builder, &[num, bits]);
                         backend.code_builder.i64_shr_s();
                     }
-                    I128 => todo!("{:?} for I128", self.lowlevel),
                     _ => panic_ret_type(),
                 }
             }
@@ -1694,10 +1765,15 @@ impl<'a> LowLevelCall<'a> {
                         );
                         backend.code_builder.i64_shr_u();
                     }
-                    I128 => todo!("{:?} for I128", self.lowlevel),
                     _ => panic_ret_type(),
                 }
             }
+            NumIntCast
+                if CodeGenNumType::from(self.ret_layout) == I128
+                    || CodeGenNumType::for_symbol(backend, self.arguments[0]) == I128 =>
+            {
+                self.int_cast_num128(backend)
+            }
             NumIntCast => {
                 self.load_args(backend);
                 let arg_layout = backend.storage.symbol_layouts[&self.arguments[0]];
@@ -1775,6 +1851,8 @@ impl<'a> LowLevelCall<'a> {
                             backend.code_builder.f64_convert_u_i64()
                         }
                     }
+                    (F32, I128) => self.int128_to_float(backend, FloatWidth::F32),
+                    (F64, I128) => self.int128_to_float(backend, FloatWidth::F64),
                     _ => todo!("{:?}: {:?} -> {:?}", self.lowlevel, arg_type, ret_type),
                 }
             }
@@ -1833,7 +1911,29 @@ impl<'a> LowLevelCall<'a> {
                 backend.storage.load_symbols(code_builder, self.arguments);
             }
 
-            Hash => todo!("{:?}", self.lowlevel),
+            HashBytes => {
+                // Dict.hashBytesLowlevel : U64, List U8 -> U64
+                let seed: Symbol = self.arguments[0];
+                let list: Symbol = self.arguments[1];
+
+                // Zig arguments              Wasm types
+                //  list: RocList,             i64, i32
+                //  seed: u64,                 i64
+
+                backend.storage.load_symbols_for_call(
+                    backend.env.arena,
+                    &mut backend.code_builder,
+                    &[list],
+                    self.ret_symbol,
+                    &WasmLayout::new(&self.ret_layout),
+                    CallConv::Zig,
+                );
+                backend
+                    .storage
+                    .load_symbols(&mut backend.code_builder, &[seed]);
+
+                backend.call_host_fn_after_loading_args(bitcode::DICT_HASH_BYTES, 3, true);
+            }
 
             Eq | NotEq => self.eq_or_neq(backend),
 
@@ -2008,6 +2108,227 @@ impl<'a> LowLevelCall<'a> {
         backend.code_builder.i32_and();
     }
 
+    /// Comparisons of 128-bit integers and Decs, which have the same representation.
+    /// Compares the high halves first, and only looks at the low halves if those are equal.
+    fn compare_num128(&self, backend: &mut WasmBackend<'a>) {
+        let [x, y] = [self.arguments[0], self.arguments[1]];
+        let is_signed = symbol_is_signed_int(backend, x)
+            || CodeGenNumType::for_symbol(backend, x) == CodeGenNumType::Decimal;
+
+        match self.lowlevel {
+            LowLevel::NumLt => lt_num128(backend, x, y, is_signed),
+            LowLevel::NumGt => lt_num128(backend, y, x, is_signed),
+            LowLevel::NumLte => {
+                lt_num128(backend, y, x, is_signed);
+                backend.code_builder.i32_eqz();
+            }
+            LowLevel::NumGte => {
+                lt_num128(backend, x, y, is_signed);
+                backend.code_builder.i32_eqz();
+            }
+            LowLevel::NumCompare => {
+                // (x != y) as u8 + (x < y) as u8, like the other number types
+                let locations = [num128_location(backend, x), num128_location(backend, y)];
+                Self::eq_num128_bytes(backend, locations);
+                backend.code_builder.i32_eqz();
+                lt_num128(backend, x, y, is_signed);
+                backend.code_builder.i32_add();
+            }
+            _ => internal_error!("{:?} is not a comparison", self.lowlevel),
+        }
+    }
+
+    /// Num.neg and Num.abs for 128-bit integers and Decs
+    /// Two's complement negation, with a borrow from the high half when the low half is nonzero
+    fn negate_num128(&self, backend: &mut WasmBackend<'a>) {
+        let x = self.arguments[0];
+        let is_abs = self.lowlevel == LowLevel::NumAbs;
+        let (ret_local, ret_offset) = self.ret_num128_location(backend);
+
+        if is_abs
+            && matches!(
+                self.ret_layout,
+                Layout::Builtin(Builtin::Int(IntWidth::U128))
+            )
+        {
+            for half in [LOW_BITS, HIGH_BITS] {
+                backend.code_builder.get_local(ret_local);
+                load_num128_half(backend, x, half);
+                backend
+                    .code_builder
+                    .i64_store(Align::Bytes8, ret_offset + half);
+            }
+            return;
+        }
+
+        // x == MIN
+        load_num128_half(backend, x, HIGH_BITS);
+        backend.code_builder.i64_const(i64::MIN);
+        backend.code_builder.i64_eq();
+        load_num128_half(backend, x, LOW_BITS);
+        backend.code_builder.i64_eqz();
+        backend.code_builder.i32_and();
+        backend.code_builder.if_();
+        backend.stmt_runtime_error(if is_abs { ABS_PANIC_MSG } else { NEG_PANIC_MSG });
+        backend.code_builder.end();
+
+        // Low half: 0 - lo
+        backend.code_builder.get_local(ret_local);
+        backend.code_builder.i64_const(0);
+        load_num128_half(backend, x, LOW_BITS);
+        backend.code_builder.i64_sub();
+        if is_abs {
+            // (x < 0) ? -lo : lo
+            load_num128_half(backend, x, LOW_BITS);
+            load_num128_half(backend, x, HIGH_BITS);
+            backend.code_builder.i64_const(0);
+            backend.code_builder.i64_lt_s();
+            backend.code_builder.select();
+        }
+        backend
+            .code_builder
+            .i64_store(Align::Bytes8, ret_offset + LOW_BITS);
+
+        // High half: 0 - hi - (lo != 0)
+        backend.code_builder.get_local(ret_local);
+        backend.code_builder.i64_const(0);
+        load_num128_half(backend, x, HIGH_BITS);
+        backend.code_builder.i64_sub();
+        load_num128_half(backend, x, LOW_BITS);
+        backend.code_builder.i64_const(0);
+        backend.code_builder.i64_ne();
+        backend.code_builder.i64_extend_u_i32();
+        backend.code_builder.i64_sub();
+        if is_abs {
+            // (x < 0) ? -hi : hi
+            load_num128_half(backend, x, HIGH_BITS);
+            load_num128_half(backend, x, HIGH_BITS);
+            backend.code_builder.i64_const(0);
+            backend.code_builder.i64_lt_s();
+            backend.code_builder.select();
+        }
+        backend
+            .code_builder
+            .i64_store(Align::Bytes8, ret_offset + HIGH_BITS);
+    }
+
+    /// Bitwise operations on 128-bit integers work on each half independently
+    fn bitwise_num128(&self, backend: &mut WasmBackend<'a>) {
+        let [x, y] = [self.arguments[0], self.arguments[1]];
+        let (ret_local, ret_offset) = self.ret_num128_location(backend);
+
+        for half in [LOW_BITS, HIGH_BITS] {
+            backend.code_builder.get_local(ret_local);
+            load_num128_half(backend, x, half);
+            load_num128_half(backend, y, half);
+            match self.lowlevel {
+                LowLevel::NumBitwiseAnd => backend.code_builder.i64_and(),
+                LowLevel::NumBitwiseXor => backend.code_builder.i64_xor(),
+                LowLevel::NumBitwiseOr => backend.code_builder.i64_or(),
+                _ => internal_error!("{:?} is not a bitwise operation", self.lowlevel),
+            }
+            backend
+                .code_builder
+                .i64_store(Align::Bytes8, ret_offset + half);
+        }
+    }
+
+    /// Num.intCast to or from a 128-bit integer
+    fn int_cast_num128(&self, backend: &mut WasmBackend<'a>) {
+        let arg = self.arguments[0];
+        let arg_layout = backend.storage.symbol_layouts[&arg];
+        let (arg_width, ret_width) = match (arg_layout, self.ret_layout) {
+            (Layout::Builtin(Builtin::Int(arg_w)), Layout::Builtin(Builtin::Int(ret_w))) => {
+                (arg_w, ret_w)
+            }
+            x => internal_error!("Num.intCast is not defined for {:?}", x),
+        };
+
+        match (
+            CodeGenNumType::from(self.ret_layout),
+            CodeGenNumType::from(arg_layout),
+        ) {
+            (CodeGenNumType::I128, CodeGenNumType::I128) => {
+                let (ret_local, ret_offset) = self.ret_num128_location(backend);
+                for half in [LOW_BITS, HIGH_BITS] {
+                    backend.code_builder.get_local(ret_local);
+                    load_num128_half(backend, arg, half);
+                    backend
+                        .code_builder
+                        .i64_store(Align::Bytes8, ret_offset + half);
+                }
+            }
+            (CodeGenNumType::I128, arg_type) => {
+                let (ret_local, ret_offset) = self.ret_num128_location(backend);
+                let load_arg_as_i64 = |backend: &mut WasmBackend<'a>| {
+                    backend
+                        .storage
+                        .load_symbols(&mut backend.code_builder, &[arg]);
+                    if arg_type == CodeGenNumType::I32 {
+                        if arg_width.is_signed() {
+                            backend.code_builder.i64_extend_s_i32();
+                        } else {
+                            backend.code_builder.i64_extend_u_i32();
+                        }
+                    }
+                };
+
+                backend.code_builder.get_local(ret_local);
+                load_arg_as_i64(backend);
+                backend
+                    .code_builder
+                    .i64_store(Align::Bytes8, ret_offset + LOW_BITS);
+
+                // The high half is all sign bits
+                backend.code_builder.get_local(ret_local);
+                if arg_width.is_signed() {
+                    load_arg_as_i64(backend);
+                    backend.code_builder.i64_const(63);
+                    backend.code_builder.i64_shr_s();
+                } else {
+                    backend.code_builder.i64_const(0);
+                }
+                backend
+                    .code_builder
+                    .i64_store(Align::Bytes8, ret_offset + HIGH_BITS);
+            }
+            (CodeGenNumType::I64, _) => load_num128_half(backend, arg, LOW_BITS),
+            (CodeGenNumType::I32, _) => {
+                load_num128_half(backend, arg, LOW_BITS);
+                backend.code_builder.i32_wrap_i64();
+                self.wrap_small_int(backend, ret_width);
+            }
+            (ret_type, arg_type) => {
+                internal_error!("{:?}: {:?} -> {:?}", self.lowlevel, arg_type, ret_type)
+            }
+        }
+    }
+
+    /// Convert a 128-bit integer to a float. Expects `load_args` to have been called already.
+    fn int128_to_float(&self, backend: &mut WasmBackend<'a>, float_width: FloatWidth) {
+        let arg_layout = backend.storage.symbol_layouts[&self.arguments[0]];
+        let name = match (arg_layout, float_width) {
+            (Layout::Builtin(Builtin::Int(width)), FloatWidth::F32) => {
+                &bitcode::NUM_INT_TO_F32[width]
+            }
+            (Layout::Builtin(Builtin::Int(width)), FloatWidth::F64) => {
+                &bitcode::NUM_INT_TO_F64[width]
+            }
+            x => internal_error!("Cannot convert {:?} to a float", x),
+        };
+        self.load_args_and_call_zig(backend, name);
+    }
+
+    /// The memory location of the result, for operations that return a 128-bit number
+    fn ret_num128_location(&self, backend: &WasmBackend<'a>) -> (LocalId, u32) {
+        match &self.ret_storage {
+            StoredValue::StackMemory { location, .. } => {
+                location.local_and_offset(backend.storage.stack_frame_pointer)
+            }
+            _ => internal_error!("128-bit numbers should always be stored in memory"),
+        }
+    }
+
     fn num_to_str(&self, backend: &mut WasmBackend<'a>) {
         let arg_layout = backend.storage.symbol_layouts[&self.arguments[0]];
         match arg_layout {
@@ -2033,6 +2354,46 @@ impl<'a> LowLevelCall<'a> {
     }
 }
 
+/// The memory location of a 128-bit number (I128, U128 or Dec)
+fn num128_location(backend: &WasmBackend<'_>, symbol: Symbol) -> StackMemoryLocation {
+    match backend.storage.get(&symbol) {
+        StoredValue::StackMemory { location, .. } => location.clone(),
+        _ => internal_error!("128-bit numbers should always be stored in memory"),
+    }
+}
+
+/// Load the low or high 64 bits of a 128-bit number onto the VM stack
+fn load_num128_half(backend: &mut WasmBackend<'_>, symbol: Symbol, half: u32) {
+    let location = num128_location(backend, symbol);
+    let (local_id, offset) = location.local_and_offset(backend.storage.stack_frame_pointer);
+    backend.code_builder.get_local(local_id);
+    backend.code_builder.i64_load(Align::Bytes8, offset + half);
+}
+
+/// Push `x < y` for two 128-bit numbers: the high halves decide, unless they're equal.
+/// Only the high half carries the sign; the low half is always compared as unsigned.
+fn lt_num128(backend: &mut WasmBackend<'_>, x: Symbol, y: Symbol, is_signed: bool) {
+    // x.hi < y.hi
+    load_num128_half(backend, x, HIGH_BITS);
+    load_num128_half(backend, y, HIGH_BITS);
+    if is_signed {
+        backend.code_builder.i64_lt_s();
+    } else {
+        backend.code_builder.i64_lt_u();
+    }
+
+    // x.hi == y.hi && x.lo < y.lo
+    load_num128_half(backend, x, HIGH_BITS);
+    load_num128_half(backend, y, HIGH_BITS);
+    backend.code_builder.i64_eq();
+    load_num128_half(backend, x, LOW_BITS);
+    load_num128_half(backend, y, LOW_BITS);
+    backend.code_builder.i64_lt_u();
+    backend.code_builder.i32_and();
+
+    backend.code_builder.i32_or();
+}
+
 /// Helper for NumIsFinite op, and also part of Eq/NotEq
 fn num_is_finite(backend: &mut WasmBackend<'_>, argument: Symbol) {
     use StoredValue::*;
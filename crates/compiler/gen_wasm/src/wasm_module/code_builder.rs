@@ -7,6 +7,7 @@ use roc_module::symbol::Symbol;
 
 use super::opcodes::{OpCode, OpCode::*};
 use super::serialize::{SerialBuffer, Serialize};
+use super::source_map::SourceLocation;
 use crate::{
     round_up_to_alignment, DEBUG_SETTINGS, FRAME_ALIGNMENT_BYTES, STACK_POINTER_GLOBAL_ID,
 };
@@ -182,6 +183,9 @@ pub struct CodeBuilder<'a> {
     /// Relocations for calls to JS imports
     /// When we remove unused imports, the live ones are re-indexed
     import_relocations: Vec<'a, (usize, u32)>,
+
+    /// Where the function was defined in Roc source code, for the source map (debug builds only)
+    pub source_location: Option<SourceLocation>,
 }
 
 impl<'a> Serialize for CodeBuilder<'a> {
@@ -209,6 +213,7 @@ impl<'a> CodeBuilder<'a> {
             inner_length: Vec::with_capacity_in(5, arena),
             vm_block_stack,
             import_relocations: Vec::with_capacity_in(0, arena),
+            source_location: None,
         }
     }
 
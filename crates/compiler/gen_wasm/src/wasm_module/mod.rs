@@ -4,6 +4,7 @@ pub mod opcodes;
 pub mod parse;
 pub mod sections;
 pub mod serialize;
+pub mod source_map;
 
 use std::iter::repeat;
 
@@ -21,9 +22,10 @@ use self::parse::{Parse, ParseError};
 use self::sections::{
     CodeSection, DataSection, ElementSection, ExportSection, FunctionSection, GlobalSection,
     ImportDesc, ImportSection, MemorySection, NameSection, OpaqueSection, Section, SectionId,
-    TableSection, TypeSection,
+    SourceMappingUrlSection, TableSection, TypeSection,
 };
 use self::serialize::{SerialBuffer, Serialize};
+use self::source_map::{SourceFile, SourceMap};
 
 /// A representation of the WebAssembly binary file format
 /// https://webassembly.github.io/spec/core/binary/modules.html
@@ -57,6 +59,28 @@ impl<'a> WasmModule<'a> {
 
     /// Serialize the module to bytes
     pub fn serialize<T: SerialBuffer>(&self, buffer: &mut T) {
+        self.serialize_sections(buffer);
+    }
+
+    /// Serialize the module to bytes, with a source map for the Roc functions.
+    /// The source map is embedded as a data URL, so the module can't get out of sync with it.
+    pub fn serialize_with_source_map<T: SerialBuffer>(&self, buffer: &mut T, files: &[SourceFile]) {
+        let roc_fn_offsets = self.serialize_sections(buffer);
+
+        let mappings = roc_fn_offsets
+            .into_iter()
+            .zip(self.code.code_builders.iter())
+            .map(|(offset, code_builder)| (offset, code_builder.source_location))
+            .collect();
+
+        let source_map = SourceMap { files, mappings };
+        let url = source_map.to_data_url();
+        SourceMappingUrlSection { url: &url }.serialize(buffer);
+    }
+
+    /// Serialize all sections, returning the offset of each Roc function from the start of the module
+    fn serialize_sections<T: SerialBuffer>(&self, buffer: &mut T) -> std::vec::Vec<u32> {
+        let module_start = buffer.size();
         buffer.append_u8(0);
         buffer.append_slice("asm".as_bytes());
         buffer.write_unencoded_u32(Self::WASM_VERSION);
@@ -72,9 +96,16 @@ impl<'a> WasmModule<'a> {
         self.export.serialize(buffer);
         self.start.serialize(buffer);
         self.element.serialize(buffer);
-        self.code.serialize(buffer);
+        let roc_fn_offsets = self
+            .code
+            .serialize_with_roc_fn_offsets(buffer)
+            .into_iter()
+            .map(|offset| (offset - module_start) as u32)
+            .collect();
         self.data.serialize(buffer);
         self.names.serialize(buffer);
+
+        roc_fn_offsets
     }
 
     /// Module size in bytes (assuming no linker data)
@@ -310,6 +341,12 @@ impl<'a> WasmModule<'a> {
         }
 
         self.code.preloaded_bytes = buffer;
+
+        // The host's local variable names don't apply to the dummies
+        self.names.local_names.retain(|(fn_index, _)| {
+            let i = *fn_index;
+            i < host_fn_min || i >= host_fn_max || live_flags[i as usize]
+        });
     }
 
     fn trace_live_host_functions<I: Iterator<Item = u32>>(
@@ -1235,6 +1235,16 @@ impl<'a> CodeSection<'a> {
 
 impl<'a> Serialize for CodeSection<'a> {
     fn serialize<T: SerialBuffer>(&self, buffer: &mut T) {
+        self.serialize_with_roc_fn_offsets(buffer);
+    }
+}
+
+impl<'a> CodeSection<'a> {
+    /// Serialize the section, returning the buffer position where each Roc function starts
+    pub fn serialize_with_roc_fn_offsets<T: SerialBuffer>(
+        &self,
+        buffer: &mut T,
+    ) -> std::vec::Vec<usize> {
         let header_indices = write_section_header(buffer, SectionId::Code);
         buffer.encode_u32(
             self.dead_import_dummy_count + self.preloaded_count + self.code_builders.len() as u32,
@@ -1253,11 +1263,15 @@ impl<'a> Serialize for CodeSection<'a> {
         buffer.append_slice(&self.preloaded_bytes[first_fn_start..]);
 
         // Roc functions
+        let mut roc_fn_offsets = std::vec::Vec::with_capacity(self.code_builders.len());
         for code_builder in self.code_builders.iter() {
+            roc_fn_offsets.push(buffer.size());
             code_builder.serialize(buffer);
         }
 
         update_section_size(buffer, header_indices);
+
+        roc_fn_offsets
     }
 }
 
@@ -1462,6 +1476,8 @@ enum NameSubSections {
 
 pub struct NameSection<'a> {
     pub function_names: Vec<'a, (u32, &'a str)>,
+    /// Names of local variables, for each function that has them. Sorted by function index.
+    pub local_names: Vec<'a, (u32, Vec<'a, (u32, &'a str)>)>,
 }
 
 impl<'a> NameSection<'a> {
@@ -1469,19 +1485,37 @@ impl<'a> NameSection<'a> {
     const NAME: &'static str = "name";
 
     pub fn size(&self) -> usize {
-        self.function_names
+        let function_names_size: usize = self
+            .function_names
             .iter()
             .map(|(_, s)| MAX_SIZE_ENCODED_U32 + s.len())
-            .sum()
+            .sum();
+
+        let local_names_size: usize = self
+            .local_names
+            .iter()
+            .flat_map(|(_, locals)| locals.iter())
+            .map(|(_, s)| MAX_SIZE_ENCODED_U32 + s.len())
+            .sum();
+
+        function_names_size + local_names_size + 2 * MAX_SIZE_ENCODED_U32 * self.local_names.len()
     }
 
     pub fn append_function(&mut self, index: u32, name: &'a str) {
         self.function_names.push((index, name));
     }
 
+    /// Append the local variable names of a function. `locals` must be sorted by local index.
+    pub fn append_locals(&mut self, fn_index: u32, locals: Vec<'a, (u32, &'a str)>) {
+        if !locals.is_empty() {
+            self.local_names.push((fn_index, locals));
+        }
+    }
+
     pub fn empty(arena: &'a Bump) -> Self {
         NameSection {
             function_names: bumpalo::vec![in arena],
+            local_names: bumpalo::vec![in arena],
         }
     }
 
@@ -1507,7 +1541,38 @@ impl<'a> NameSection<'a> {
         let mut function_names = Vec::from_iter_in(names, arena);
         function_names.sort_by_key(|(idx, _name)| *idx);
 
-        NameSection { function_names }
+        NameSection {
+            function_names,
+            local_names: bumpalo::vec![in arena],
+        }
+    }
+
+    fn write_subsection_header<T: SerialBuffer>(
+        buffer: &mut T,
+        id: NameSubSections,
+    ) -> SectionHeaderIndices {
+        buffer.append_u8(id as u8);
+        let size_index = buffer.reserve_padded_u32();
+        let body_index = buffer.size();
+        SectionHeaderIndices {
+            size_index,
+            body_index,
+        }
+    }
+
+    fn parse_name_map(
+        arena: &'a Bump,
+        module_bytes: &[u8],
+        cursor: &mut usize,
+    ) -> Result<Vec<'a, (u32, &'a str)>, ParseError> {
+        let count = u32::parse((), module_bytes, cursor)?;
+        let mut name_map = Vec::with_capacity_in(count as usize, arena);
+        for _ in 0..count {
+            let index = u32::parse((), module_bytes, cursor)?;
+            let name = <&'a str>::parse(arena, module_bytes, cursor)?;
+            name_map.push((index, name));
+        }
+        Ok(name_map)
     }
 }
 
@@ -1538,44 +1603,35 @@ impl<'a> Parse<&'a Bump> for NameSection<'a> {
             return Ok(Self::empty(arena));
         }
 
-        // Find function names subsection
-        let mut found_function_names = false;
-        for _possible_subsection_id in 0..2 {
+        let mut section = Self::empty(arena);
+
+        // Subsections are optional, but must be in order of ID
+        while *cursor < section_end {
             let subsection_id = module_bytes[*cursor];
             *cursor += 1;
-            let subsection_size = u32::parse((), module_bytes, cursor)?;
+            let subsection_size = u32::parse((), module_bytes, cursor)? as usize;
+            let subsection_end = *cursor + subsection_size;
+
             if subsection_id == NameSubSections::FunctionNames as u8 {
-                found_function_names = true;
-                break;
+                section.function_names = Self::parse_name_map(arena, module_bytes, cursor)?;
+            } else if subsection_id == NameSubSections::LocalNames as u8 {
+                let count = u32::parse((), module_bytes, cursor)?;
+                section.local_names.reserve(count as usize);
+                for _ in 0..count {
+                    let fn_index = u32::parse((), module_bytes, cursor)?;
+                    let locals = Self::parse_name_map(arena, module_bytes, cursor)?;
+                    section.local_names.push((fn_index, locals));
+                }
             }
-            *cursor += subsection_size as usize;
-            if *cursor >= section_end {
+
+            if *cursor > subsection_end || subsection_end > section_end {
                 return Err(ParseError {
                     message: "Failed to parse Name section".into(),
                     offset: *cursor,
                 });
             }
+            *cursor = subsection_end;
         }
-        if !found_function_names {
-            return Err(ParseError {
-                message: "Failed to parse Name section".into(),
-                offset: *cursor,
-            });
-        }
-
-        let count = u32::parse((), module_bytes, cursor)?;
-        let mut section = NameSection {
-            function_names: Vec::with_capacity_in(count as usize, arena),
-        };
-
-        // Function names
-        for _ in 0..count {
-            let index = u32::parse((), module_bytes, cursor)?;
-            let name = <&'a str>::parse(arena, module_bytes, cursor)?;
-            section.function_names.push((index, name));
-        }
-
-        *cursor = section_end;
 
         Ok(section)
     }
@@ -1583,24 +1639,31 @@ impl<'a> Parse<&'a Bump> for NameSection<'a> {
 
 impl<'a> Serialize for NameSection<'a> {
     fn serialize<T: SerialBuffer>(&self, buffer: &mut T) {
-        if !self.function_names.is_empty() {
-            let header_indices = write_custom_section_header(buffer, Self::NAME);
-
-            let subsection_id = NameSubSections::FunctionNames as u8;
-            subsection_id.serialize(buffer);
+        if self.function_names.is_empty() && self.local_names.is_empty() {
+            return;
+        }
 
-            let subsection_size_index = buffer.encode_padded_u32(0);
-            let subsection_start = buffer.size();
+        let header_indices = write_custom_section_header(buffer, Self::NAME);
 
+        if !self.function_names.is_empty() {
+            let subsection_indices =
+                Self::write_subsection_header(buffer, NameSubSections::FunctionNames);
             self.function_names.serialize(buffer);
+            update_section_size(buffer, subsection_indices);
+        }
 
-            buffer.overwrite_padded_u32(
-                subsection_size_index,
-                (buffer.size() - subsection_start) as u32,
-            );
-
-            update_section_size(buffer, header_indices);
+        if !self.local_names.is_empty() {
+            let subsection_indices =
+                Self::write_subsection_header(buffer, NameSubSections::LocalNames);
+            buffer.encode_u32(self.local_names.len() as u32);
+            for (fn_index, locals) in self.local_names.iter() {
+                buffer.encode_u32(*fn_index);
+                locals.serialize(buffer);
+            }
+            update_section_size(buffer, subsection_indices);
         }
+
+        update_section_size(buffer, header_indices);
     }
 }
 
@@ -1612,10 +1675,41 @@ impl<'a> Debug for NameSection<'a> {
             writeln!(f, "  {:4}: {}", index, name)?;
         }
 
+        for (fn_index, locals) in self.local_names.iter() {
+            writeln!(f, "  locals of {}:", fn_index)?;
+            for (index, name) in locals.iter() {
+                writeln!(f, "    {:4}: {}", index, name)?;
+            }
+        }
+
         Ok(())
     }
 }
 
+/*******************************************************************
+ *
+ * Source mapping URL section
+ * https://github.com/WebAssembly/tool-conventions/blob/main/Debugging.md#source-maps
+ *
+ *******************************************************************/
+
+/// Tells a browser where to find the source map for the module
+pub struct SourceMappingUrlSection<'a> {
+    pub url: &'a str,
+}
+
+impl<'a> SourceMappingUrlSection<'a> {
+    const NAME: &'static str = "sourceMappingURL";
+}
+
+impl<'a> Serialize for SourceMappingUrlSection<'a> {
+    fn serialize<T: SerialBuffer>(&self, buffer: &mut T) {
+        let header_indices = write_custom_section_header(buffer, Self::NAME);
+        self.url.serialize(buffer);
+        update_section_size(buffer, header_indices);
+    }
+}
+
 /*******************************************************************
  *
  * Unit tests
@@ -1670,4 +1764,28 @@ mod tests {
         }
         test_assert_types_preload(arena, &section);
     }
+
+    #[test]
+    fn test_name_section() {
+        let arena = &Bump::new();
+        let mut original = NameSection::empty(arena);
+        original.append_function(0, "roc_alloc");
+        original.append_function(1, "Main.main");
+        original.append_locals(
+            1,
+            bumpalo::vec![in arena; (0, "x"), (2, "stack_frame_pointer")],
+        );
+        original.append_locals(2, bumpalo::vec![in arena]);
+
+        let mut serialized = Vec::with_capacity_in(original.size(), arena);
+        original.serialize(&mut serialized);
+
+        let mut cursor = 0;
+        let preloaded = NameSection::parse(arena, &serialized, &mut cursor).unwrap();
+
+        assert_eq!(cursor, serialized.len());
+        assert_eq!(original.function_names, preloaded.function_names);
+        assert_eq!(original.local_names, preloaded.local_names);
+        assert_eq!(preloaded.local_names.len(), 1);
+    }
 }
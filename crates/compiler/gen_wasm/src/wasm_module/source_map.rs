@@ -0,0 +1,220 @@
+//! Source maps, so that browser devtools can show Roc source code for a Wasm module.
+//! https://sourcemaps.info/spec.html
+//!
+//! A Wasm module has no lines, so in the generated "file" everything is on line 0,
+//! and the column of a mapping is a byte offset from the start of the module.
+
+/// A 0-based position in one of the source files of a `SourceMap`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub file: u32,
+    pub line: u32,
+    pub column: u32,
+}
+
+#[derive(Debug)]
+pub struct SourceFile<'a> {
+    pub path: String,
+    pub content: &'a str,
+}
+
+#[derive(Debug)]
+pub struct SourceMap<'a> {
+    pub files: &'a [SourceFile<'a>],
+    /// Module byte offsets in increasing order, each mapped to the source of the code that starts there.
+    /// `None` means we don't know where the code came from (e.g. compiler-generated helpers).
+    pub mappings: Vec<(u32, Option<SourceLocation>)>,
+}
+
+impl<'a> SourceMap<'a> {
+    pub fn to_json(&self) -> String {
+        let mut json =
+            String::with_capacity(64 + self.files.iter().map(|f| f.content.len()).sum::<usize>());
+
+        json.push_str("{\"version\":3,\"sources\":[");
+        for (i, file) in self.files.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            push_json_string(&mut json, &file.path);
+        }
+
+        json.push_str("],\"sourcesContent\":[");
+        for (i, file) in self.files.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            push_json_string(&mut json, file.content);
+        }
+
+        json.push_str("],\"names\":[],\"mappings\":\"");
+        self.push_mappings(&mut json);
+        json.push_str("\"}");
+
+        json
+    }
+
+    /// The JSON source map, encoded so that a module can refer to it without a separate file
+    pub fn to_data_url(&self) -> String {
+        let mut url = String::from("data:application/json;base64,");
+        push_base64(&mut url, self.to_json().as_bytes());
+        url
+    }
+
+    /// All mappings are on line 0, so they're one comma-separated list of segments.
+    /// Each field of a segment is relative to the same field in the previous segment.
+    fn push_mappings(&self, out: &mut String) {
+        let mut prev_offset = 0;
+        let mut prev_location = SourceLocation {
+            file: 0,
+            line: 0,
+            column: 0,
+        };
+
+        for (i, (offset, maybe_location)) in self.mappings.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            push_vlq(out, *offset as i64 - prev_offset as i64);
+            prev_offset = *offset;
+
+            if let Some(location) = maybe_location {
+                push_vlq(out, location.file as i64 - prev_location.file as i64);
+                push_vlq(out, location.line as i64 - prev_location.line as i64);
+                push_vlq(out, location.column as i64 - prev_location.column as i64);
+                prev_location = *location;
+            }
+        }
+    }
+}
+
+const BASE64_DIGITS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Base64 VLQ: the sign goes in the lowest bit, then 5 bits per digit, with a continuation bit
+fn push_vlq(out: &mut String, value: i64) {
+    let mut vlq = if value < 0 {
+        ((-value as u64) << 1) | 1
+    } else {
+        (value as u64) << 1
+    };
+
+    loop {
+        let mut digit = (vlq & 0b11111) as usize;
+        vlq >>= 5;
+        if vlq > 0 {
+            digit |= 0b100000;
+        }
+        out.push(BASE64_DIGITS[digit] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+fn push_base64(out: &mut String, bytes: &[u8]) {
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0] as usize;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as usize;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as usize;
+
+        out.push(BASE64_DIGITS[b0 >> 2] as char);
+        out.push(BASE64_DIGITS[((b0 & 0b11) << 4) | (b1 >> 4)] as char);
+        if chunk.len() > 1 {
+            out.push(BASE64_DIGITS[((b1 & 0b1111) << 2) | (b2 >> 6)] as char);
+        } else {
+            out.push('=');
+        }
+        if chunk.len() > 2 {
+            out.push(BASE64_DIGITS[b2 & 0b111111] as char);
+        } else {
+            out.push('=');
+        }
+    }
+}
+
+fn push_json_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vlq() {
+        let encode = |value| {
+            let mut out = String::new();
+            push_vlq(&mut out, value);
+            out
+        };
+        assert_eq!(encode(0), "A");
+        assert_eq!(encode(1), "C");
+        assert_eq!(encode(-1), "D");
+        assert_eq!(encode(15), "e");
+        assert_eq!(encode(16), "gB");
+        assert_eq!(encode(-16), "hB");
+        assert_eq!(encode(1000), "w+B");
+    }
+
+    #[test]
+    fn test_base64() {
+        let encode = |bytes: &[u8]| {
+            let mut out = String::new();
+            push_base64(&mut out, bytes);
+            out
+        };
+        assert_eq!(encode(b""), "");
+        assert_eq!(encode(b"f"), "Zg==");
+        assert_eq!(encode(b"fo"), "Zm8=");
+        assert_eq!(encode(b"foo"), "Zm9v");
+        assert_eq!(encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_source_map_json() {
+        let files = [SourceFile {
+            path: "main.roc".into(),
+            content: "app \"hello\"\n",
+        }];
+        let source_map = SourceMap {
+            files: &files,
+            mappings: vec![
+                (100, None),
+                (
+                    120,
+                    Some(SourceLocation {
+                        file: 0,
+                        line: 3,
+                        column: 0,
+                    }),
+                ),
+                (
+                    150,
+                    Some(SourceLocation {
+                        file: 0,
+                        line: 1,
+                        column: 4,
+                    }),
+                ),
+            ],
+        };
+
+        assert_eq!(
+            source_map.to_json(),
+            r#"{"version":3,"sources":["main.roc"],"sourcesContent":["app \"hello\"\n"],"names":[],"mappings":"oG,oBAGA,8BAFI"}"#
+        );
+    }
+}
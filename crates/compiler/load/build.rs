@@ -1,3 +1,4 @@
+use std::io::Write;
 use std::path::PathBuf;
 
 use bumpalo::Bump;
@@ -23,6 +24,8 @@ const MODULES: &[(ModuleId, &str)] = &[
     (ModuleId::ENCODE, "Encode.roc"),
     (ModuleId::DECODE, "Decode.roc"),
     (ModuleId::JSON, "Json.roc"),
+    (ModuleId::HASH, "Hash.roc"),
+    (ModuleId::ARBITRARY, "Arbitrary.roc"),
 ];
 
 fn main() {
@@ -76,7 +79,15 @@ fn write_subs_for_module(module_id: ModuleId, filename: &str) {
         let subs = module.solved.inner();
         let exposed_vars_by_symbol: Vec<_> = module.exposed_to_host.into_iter().collect();
 
+        let mut abilities_store = Vec::new();
+        module.abilities_store.serialize(&mut abilities_store);
+
+        // The abilities store goes after the subs, followed by its length, so that the subs
+        // still start at the beginning of the file.
         let mut file = std::fs::File::create(&output_path).unwrap();
         subs.serialize(&exposed_vars_by_symbol, &mut file).unwrap();
+        file.write_all(&abilities_store).unwrap();
+        file.write_all(&(abilities_store.len() as u64).to_le_bytes())
+            .unwrap();
     }
 }
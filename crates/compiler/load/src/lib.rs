@@ -1,10 +1,12 @@
 use bumpalo::Bump;
+use roc_can::abilities::AbilitiesStore;
 use roc_can::module::ExposedByModule;
 use roc_collections::all::MutMap;
-use roc_module::symbol::{ModuleId, Symbol};
+use roc_load_internal::file::CachedModule;
+use roc_module::symbol::ModuleId;
 use roc_reporting::report::RenderTarget;
 use roc_target::TargetInfo;
-use roc_types::subs::{Subs, Variable};
+use roc_types::subs::Subs;
 use std::path::PathBuf;
 
 const SKIP_SUBS_CACHE: bool = {
@@ -14,10 +16,11 @@ const SKIP_SUBS_CACHE: bool = {
     }
 };
 
+pub use roc_can::property::PropertyTestConfig;
 pub use roc_load_internal::docs;
 pub use roc_load_internal::file::{
-    EntryPoint, ExecutionMode, Expectations, LoadConfig, LoadResult, LoadStart, LoadedModule,
-    LoadingProblem, MonomorphizedModule, Phase, Threading,
+    default_cache_dir, EntryPoint, ExecutionMode, Expectations, LoadConfig, LoadResult, LoadStart,
+    LoadedModule, LoadingProblem, MonomorphizedModule, Phase, TestConfig, Threading,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -51,6 +54,7 @@ pub fn load_single_threaded<'a>(
         cached_subs,
         render,
         exec_mode,
+        None,
     )
 }
 
@@ -143,13 +147,25 @@ const SET: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/Set.dat")) as &[_];
 const BOX: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/Box.dat")) as &[_];
 const NUM: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/Num.dat")) as &[_];
 
-fn deserialize_help(bytes: &[u8]) -> (Subs, Vec<(Symbol, Variable)>) {
-    let (subs, slice) = Subs::deserialize(bytes);
-
-    (subs, slice.to_vec())
+/// The build script writes the subs of a module, then its abilities store, then the length of
+/// the abilities store as a little-endian `u64`.
+fn deserialize_help(bytes: &[u8]) -> CachedModule {
+    let (rest, store_length) = bytes.split_at(bytes.len() - 8);
+    let store_length = u64::from_le_bytes(store_length.try_into().unwrap()) as usize;
+    let (subs_bytes, store_bytes) = rest.split_at(rest.len() - store_length);
+
+    let (subs, slice) = Subs::deserialize(subs_bytes);
+    let abilities_store = AbilitiesStore::deserialize(store_bytes, &subs)
+        .expect("the abilities store of a builtin module could not be deserialized");
+
+    CachedModule {
+        exposed_vars_by_symbol: slice.to_vec(),
+        subs,
+        abilities_store,
+    }
 }
 
-fn read_cached_subs() -> MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)> {
+fn read_cached_subs() -> MutMap<ModuleId, CachedModule> {
     let mut output = MutMap::default();
 
     // Wasm seems to re-order definitions between build time and runtime, but only in release mode.
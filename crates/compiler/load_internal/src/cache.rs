@@ -0,0 +1,818 @@
+//! An on-disk cache of the solved types of interface modules, so that modules which did not
+//! change since the last run do not have to be parsed, canonicalized and solved again.
+//!
+//! Entries are keyed by a hash of the module's source, the sources of all modules it transitively
+//! imports, and the ids those modules were given. Cached types refer to identifiers by index, so an
+//! entry also records the identifiers known to each of those modules when it was canonicalized, and
+//! is only used when they are still the same.
+//!
+//! Besides the solved types, an entry holds what importers of the module need from its
+//! canonicalization: its identifiers, the aliases it makes available, and its abilities.
+//!
+//! An entry starts with a header of [`MAGIC`], the length of the rest of the entry and its checksum.
+//! Entries that do not match their header, for example because they were cut short, are ignored.
+use roc_can::abilities::{AbilitiesStore, PendingAbilitiesStore};
+use roc_collections::all::WyHash;
+use roc_collections::{MutMap, SendMap};
+use roc_module::ident::{Lowercase, TagName, Uppercase};
+use roc_module::symbol::{IdentIds, IdentIdsByModule, Interns, ModuleId, Symbol};
+use roc_region::all::{Loc, Position, Region};
+use roc_types::subs::{Subs, Variable};
+use roc_types::types::{
+    Alias, AliasCommon, AliasKind, AliasVar, LambdaSet, OptAbleType, RecordField, Type,
+    TypeExtension,
+};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Bump this whenever the layout of cache entries changes.
+const CACHE_VERSION: u32 = 4;
+
+const MAGIC: [u8; 8] = *b"roctypes";
+
+/// The magic number, the length of the rest of the entry, and its checksum.
+const HEADER_SIZE: usize = 24;
+
+/// When the cache grows past this many bytes, its oldest entries are removed.
+const MAX_CACHE_SIZE: u64 = 512 * 1024 * 1024;
+
+/// The solved types of a module, as they come out of solving.
+#[derive(Debug)]
+pub struct CachedModule {
+    pub subs: Subs,
+    pub exposed_vars_by_symbol: Vec<(Symbol, Variable)>,
+    pub abilities_store: AbilitiesStore,
+}
+
+/// What importers of a cached module need from it, in place of canonicalizing it.
+#[derive(Debug)]
+pub struct CachedExports {
+    /// All identifiers of the module, including the ones it does not expose.
+    pub ident_ids: IdentIds,
+    /// The aliases importers can see: the exposed aliases of the module,
+    /// and the ones it got from its own imports.
+    pub aliases: MutMap<Symbol, (bool, Alias)>,
+    /// The abilities importers can see, with their declared implementations.
+    pub abilities_store: PendingAbilitiesStore,
+}
+
+/// The identifiers a module had when a cache entry was written:
+/// how many there were, and a hash of their names in order.
+pub type IdentsSeen = (ModuleId, u32, u64);
+
+#[derive(Debug, Clone)]
+pub struct TypeCache {
+    dir: PathBuf,
+    /// Entries are only valid for the exact compiler that wrote them,
+    /// because they contain the in-memory layout of its types.
+    compiler_hash: u64,
+    /// Whether an entry was written since the cache was opened.
+    written: Arc<AtomicBool>,
+}
+
+impl TypeCache {
+    /// Returns `None` when the running compiler cannot be identified, in which case nothing is cached.
+    pub fn new(dir: PathBuf) -> Option<Self> {
+        let exe = std::env::current_exe().ok()?;
+        let metadata = std::fs::metadata(&exe).ok()?;
+
+        let mut hasher = WyHash::default();
+        CACHE_VERSION.hash(&mut hasher);
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        exe.hash(&mut hasher);
+        metadata.len().hash(&mut hasher);
+        metadata.modified().ok()?.hash(&mut hasher);
+
+        Some(Self {
+            dir,
+            compiler_hash: hasher.finish(),
+            written: Default::default(),
+        })
+    }
+
+    /// Keeps the cache from growing past its maximum size, if any entries were written to it.
+    pub fn evict_if_written(&self) {
+        if self.written.load(Ordering::Relaxed) {
+            // The cache still works when it can not be cleaned up, it just keeps growing.
+            let _ = self.evict(MAX_CACHE_SIZE);
+        }
+    }
+
+    /// The key of a module, given the keys of the modules it imports.
+    pub fn module_key(
+        &self,
+        module_id: ModuleId,
+        path: &Path,
+        source: &[u8],
+        import_keys: impl IntoIterator<Item = u64>,
+    ) -> u64 {
+        let mut hasher = WyHash::default();
+        self.compiler_hash.hash(&mut hasher);
+        module_id.hash(&mut hasher);
+        path.hash(&mut hasher);
+        source.hash(&mut hasher);
+        for key in import_keys {
+            key.hash(&mut hasher);
+        }
+
+        hasher.finish()
+    }
+
+    fn entry_path(&self, key: u64) -> PathBuf {
+        self.dir.join(format!("{:016x}.dat", key))
+    }
+
+    pub fn read(
+        &self,
+        key: u64,
+        ident_ids_by_module: &IdentIdsByModule,
+    ) -> Option<(CachedModule, CachedExports)> {
+        let bytes = std::fs::read(self.entry_path(key)).ok()?;
+        let mut reader = Reader(checked_payload(&bytes)?);
+
+        for _ in 0..reader.u32()? {
+            let module_id = ModuleId::from_u32(reader.u32()?)?;
+            let count = reader.u32()?;
+            let hash = reader.u64()?;
+
+            let ident_ids = ident_ids_by_module.get(&module_id)?;
+            if hash_idents(ident_ids, count) != Some(hash) {
+                return None;
+            }
+        }
+
+        let subs_bytes = reader.bytes()?;
+
+        // Subs are read in place, so their bytes must be aligned like the largest values in them.
+        // The checksum in the header makes sure they are exactly the bytes that were written.
+        let subs_words = aligned_words(subs_bytes);
+        let subs_bytes = unsafe {
+            std::slice::from_raw_parts(subs_words.as_ptr() as *const u8, subs_bytes.len())
+        };
+        let (subs, exposed_vars_by_symbol) = Subs::deserialize(subs_bytes);
+        let exposed_vars_by_symbol = exposed_vars_by_symbol.to_vec();
+        let abilities_store = AbilitiesStore::deserialize(reader.bytes()?, &subs)?;
+
+        let mut ident_ids = IdentIds::default();
+        for _ in 0..reader.u32()? {
+            ident_ids.add_str(reader.str()?);
+        }
+
+        let aliases = reader.many(|reader| {
+            let symbol = reader.symbol()?;
+            let exposed = reader.bool()?;
+
+            Some((symbol, (exposed, reader.alias()?)))
+        })?;
+
+        let pending_abilities_store = PendingAbilitiesStore::deserialize(reader.0)?;
+
+        let cached = CachedModule {
+            subs,
+            exposed_vars_by_symbol,
+            abilities_store,
+        };
+        let exports = CachedExports {
+            ident_ids,
+            aliases,
+            abilities_store: pending_abilities_store,
+        };
+
+        Some((cached, exports))
+    }
+
+    fn write(&self, key: u64, idents_seen: &[IdentsSeen], entry_bytes: Vec<u8>) -> io::Result<()> {
+        let mut payload = Vec::with_capacity(entry_bytes.len() + 4 + 16 * idents_seen.len());
+        payload.extend((idents_seen.len() as u32).to_le_bytes());
+        for (module_id, count, hash) in idents_seen {
+            payload.extend(module_id.to_u32().to_le_bytes());
+            payload.extend(count.to_le_bytes());
+            payload.extend(hash.to_le_bytes());
+        }
+        payload.extend(entry_bytes);
+
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + payload.len());
+        bytes.extend(MAGIC);
+        bytes.extend((payload.len() as u64).to_le_bytes());
+        bytes.extend(checksum(&payload).to_le_bytes());
+        bytes.extend(payload);
+
+        // Write to a temporary file first, so that concurrent builds never read half an entry.
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.entry_path(key);
+        let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        std::fs::write(&tmp_path, bytes)?;
+        std::fs::rename(tmp_path, path)?;
+
+        self.written.store(true, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Removes the least recently written entries until the cache takes up at most `max_size` bytes.
+    /// This also clears out the entries of compilers that are no longer used.
+    fn evict(&self, max_size: u64) -> io::Result<()> {
+        let mut entries = Vec::new();
+        let mut total_size = 0;
+        for entry in std::fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if metadata.is_file() {
+                total_size += metadata.len();
+                entries.push((metadata.modified()?, metadata.len(), entry.path()));
+            }
+        }
+
+        entries.sort();
+        for (_, size, path) in entries {
+            if total_size <= max_size {
+                break;
+            }
+
+            // Another build may have removed it already.
+            let _ = std::fs::remove_file(path);
+            total_size -= size;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the part of an entry after its header, if the header matches it.
+fn checked_payload(bytes: &[u8]) -> Option<&[u8]> {
+    let mut reader = Reader(bytes);
+
+    if reader.take(MAGIC.len())? != MAGIC {
+        return None;
+    }
+
+    let length = reader.u64()?;
+    let checksum_written = reader.u64()?;
+    let payload = reader.0;
+
+    if payload.len() as u64 == length && checksum(payload) == checksum_written {
+        Some(payload)
+    } else {
+        None
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u64 {
+    let mut hasher = WyHash::default();
+    hasher.write(bytes);
+
+    hasher.finish()
+}
+
+fn aligned_words(bytes: &[u8]) -> Vec<u64> {
+    bytes
+        .chunks(8)
+        .map(|chunk| {
+            let mut word = [0; 8];
+            word[..chunk.len()].copy_from_slice(chunk);
+
+            u64::from_ne_bytes(word)
+        })
+        .collect()
+}
+
+/// Reads an entry front to back, returning `None` instead of reading past its end.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, length: usize) -> Option<&'a [u8]> {
+        if length > self.0.len() {
+            return None;
+        }
+
+        let (taken, rest) = self.0.split_at(length);
+        self.0 = rest;
+
+        Some(taken)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.take(4)?;
+
+        Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        let low = self.u32()? as u64;
+        let high = self.u32()? as u64;
+
+        Some(low | high << 32)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn bool(&mut self) -> Option<bool> {
+        match self.u8()? {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        let length = usize::try_from(self.u64()?).ok()?;
+
+        self.take(length)
+    }
+
+    fn str(&mut self) -> Option<&'a str> {
+        let length = self.u32()? as usize;
+
+        std::str::from_utf8(self.take(length)?).ok()
+    }
+
+    fn many<T, C: FromIterator<T>>(
+        &mut self,
+        mut read: impl FnMut(&mut Self) -> Option<T>,
+    ) -> Option<C> {
+        let length = self.u32()?;
+
+        (0..length).map(|_| read(self)).collect()
+    }
+
+    fn symbol(&mut self) -> Option<Symbol> {
+        let module_id = ModuleId::from_u32(self.u32()?)?;
+
+        Some(Interns::from_index(module_id, self.u32()?))
+    }
+
+    fn opt_symbol(&mut self) -> Option<Option<Symbol>> {
+        if self.bool()? {
+            Some(Some(self.symbol()?))
+        } else {
+            Some(None)
+        }
+    }
+
+    fn region(&mut self) -> Option<Region> {
+        let start = self.u32()?;
+        let end = self.u32()?;
+
+        Some(Region::new(Position::new(start), Position::new(end)))
+    }
+
+    fn variable(&mut self) -> Option<Variable> {
+        // Safety: just like when aliases are passed between the modules of a single run, their
+        // variables are only placeholders to substitute, and are never looked up in any subs.
+        Some(unsafe { Variable::from_index(self.u32()?) })
+    }
+
+    fn types(&mut self) -> Option<Vec<Type>> {
+        self.many(Self::typ)
+    }
+
+    fn typ(&mut self) -> Option<Type> {
+        let typ = match self.u8()? {
+            0 => Type::EmptyRec,
+            1 => Type::EmptyTagUnion,
+            2 => {
+                let arguments = self.types()?;
+                let closure = self.typ()?;
+                let ret = self.typ()?;
+
+                Type::Function(arguments, Box::new(closure), Box::new(ret))
+            }
+            3 => {
+                let fields: SendMap<_, _> = self.many(|reader| {
+                    let name = Lowercase::from(reader.str()?);
+                    let field = match reader.u8()? {
+                        0 => RecordField::Demanded(reader.typ()?),
+                        1 => RecordField::Required(reader.typ()?),
+                        2 => RecordField::Optional(reader.typ()?),
+                        3 => RecordField::RigidOptional(reader.typ()?),
+                        _ => return None,
+                    };
+
+                    Some((name, field))
+                })?;
+
+                Type::Record(fields, self.ext()?)
+            }
+            4 => {
+                let tags = self.tags()?;
+
+                Type::TagUnion(tags, self.ext()?)
+            }
+            5 => {
+                let recursion_var = self.variable()?;
+                let tags = self.tags()?;
+
+                Type::RecursiveTagUnion(recursion_var, tags, self.ext()?)
+            }
+            6 => Type::DelayedAlias(AliasCommon {
+                symbol: self.symbol()?,
+                type_arguments: self.types()?,
+                lambda_set_variables: self.lambda_sets()?,
+            }),
+            7 => {
+                let symbol = self.symbol()?;
+                let type_arguments = self.many(|reader| {
+                    Some(OptAbleType {
+                        typ: reader.typ()?,
+                        opt_ability: reader.opt_symbol()?,
+                    })
+                })?;
+                let lambda_set_variables = self.lambda_sets()?;
+                let actual = Box::new(self.typ()?);
+                let kind = self.alias_kind()?;
+
+                Type::Alias {
+                    symbol,
+                    type_arguments,
+                    lambda_set_variables,
+                    actual,
+                    kind,
+                }
+            }
+            8 => {
+                let symbol = self.symbol()?;
+                let arguments = self.types()?;
+
+                Type::Apply(symbol, arguments, self.region()?)
+            }
+            9 => Type::Variable(self.variable()?),
+            _ => return None,
+        };
+
+        Some(typ)
+    }
+
+    fn ext(&mut self) -> Option<TypeExtension> {
+        if self.bool()? {
+            Some(TypeExtension::Open(Box::new(self.typ()?)))
+        } else {
+            Some(TypeExtension::Closed)
+        }
+    }
+
+    fn tags(&mut self) -> Option<Vec<(TagName, Vec<Type>)>> {
+        self.many(|reader| {
+            let name = TagName(Uppercase::from(reader.str()?));
+
+            Some((name, reader.types()?))
+        })
+    }
+
+    fn lambda_sets(&mut self) -> Option<Vec<LambdaSet>> {
+        self.many(|reader| Some(LambdaSet(reader.typ()?)))
+    }
+
+    fn alias_kind(&mut self) -> Option<AliasKind> {
+        if self.bool()? {
+            Some(AliasKind::Opaque)
+        } else {
+            Some(AliasKind::Structural)
+        }
+    }
+
+    fn alias(&mut self) -> Option<Alias> {
+        let region = self.region()?;
+        let type_variables = self.many(|reader| {
+            let region = reader.region()?;
+            let var = AliasVar {
+                name: Lowercase::from(reader.str()?),
+                var: reader.variable()?,
+                opt_bound_ability: reader.opt_symbol()?,
+            };
+
+            Some(Loc::at(region, var))
+        })?;
+        let lambda_set_variables = self.lambda_sets()?;
+        let recursion_variables = self.many(Self::variable)?;
+        let typ = self.typ()?;
+        let kind = self.alias_kind()?;
+
+        Some(Alias {
+            region,
+            type_variables,
+            lambda_set_variables,
+            recursion_variables,
+            typ,
+            kind,
+        })
+    }
+}
+
+/// Writes the parts of an entry that [`Reader`] reads.
+///
+/// Writing a type returns `None` when it can not be cached.
+/// These types only appear in modules with errors, which are not cached anyway.
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn u8(&mut self, byte: u8) {
+        self.0.push(byte);
+    }
+
+    fn u32(&mut self, word: u32) {
+        self.0.extend(word.to_le_bytes());
+    }
+
+    fn len(&mut self, length: usize) {
+        self.u32(length as u32);
+    }
+
+    fn bool(&mut self, b: bool) {
+        self.u8(b as u8);
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0.extend((bytes.len() as u64).to_le_bytes());
+        self.0.extend(bytes);
+    }
+
+    fn str(&mut self, string: &str) {
+        self.len(string.len());
+        self.0.extend(string.as_bytes());
+    }
+
+    fn symbol(&mut self, symbol: Symbol) {
+        self.u32(symbol.module_id().to_u32());
+        self.u32(symbol.ident_id().index() as u32);
+    }
+
+    fn opt_symbol(&mut self, opt_symbol: Option<Symbol>) {
+        self.bool(opt_symbol.is_some());
+        if let Some(symbol) = opt_symbol {
+            self.symbol(symbol);
+        }
+    }
+
+    fn region(&mut self, region: Region) {
+        self.u32(region.start().offset);
+        self.u32(region.end().offset);
+    }
+
+    fn variable(&mut self, var: Variable) {
+        self.u32(var.index());
+    }
+
+    fn types(&mut self, types: &[Type]) -> Option<()> {
+        self.len(types.len());
+        types.iter().try_for_each(|typ| self.typ(typ))
+    }
+
+    fn typ(&mut self, typ: &Type) -> Option<()> {
+        match typ {
+            Type::EmptyRec => self.u8(0),
+            Type::EmptyTagUnion => self.u8(1),
+            Type::Function(arguments, closure, ret) => {
+                self.u8(2);
+                self.types(arguments)?;
+                self.typ(closure)?;
+                self.typ(ret)?;
+            }
+            Type::Record(fields, ext) => {
+                self.u8(3);
+                self.len(fields.len());
+                for (name, field) in fields.iter() {
+                    self.str(name.as_str());
+                    let (tag, typ) = match field {
+                        RecordField::Demanded(typ) => (0, typ),
+                        RecordField::Required(typ) => (1, typ),
+                        RecordField::Optional(typ) => (2, typ),
+                        RecordField::RigidOptional(typ) => (3, typ),
+                    };
+                    self.u8(tag);
+                    self.typ(typ)?;
+                }
+                self.ext(ext)?;
+            }
+            Type::TagUnion(tags, ext) => {
+                self.u8(4);
+                self.tags(tags)?;
+                self.ext(ext)?;
+            }
+            Type::RecursiveTagUnion(recursion_var, tags, ext) => {
+                self.u8(5);
+                self.variable(*recursion_var);
+                self.tags(tags)?;
+                self.ext(ext)?;
+            }
+            Type::DelayedAlias(AliasCommon {
+                symbol,
+                type_arguments,
+                lambda_set_variables,
+            }) => {
+                self.u8(6);
+                self.symbol(*symbol);
+                self.types(type_arguments)?;
+                self.lambda_sets(lambda_set_variables)?;
+            }
+            Type::Alias {
+                symbol,
+                type_arguments,
+                lambda_set_variables,
+                actual,
+                kind,
+            } => {
+                self.u8(7);
+                self.symbol(*symbol);
+                self.len(type_arguments.len());
+                for argument in type_arguments {
+                    self.typ(&argument.typ)?;
+                    self.opt_symbol(argument.opt_ability);
+                }
+                self.lambda_sets(lambda_set_variables)?;
+                self.typ(actual)?;
+                self.alias_kind(*kind);
+            }
+            Type::Apply(symbol, arguments, region) => {
+                self.u8(8);
+                self.symbol(*symbol);
+                self.types(arguments)?;
+                self.region(*region);
+            }
+            Type::Variable(var) => {
+                self.u8(9);
+                self.variable(*var);
+            }
+            Type::FunctionOrTagUnion(..)
+            | Type::ClosureTag { .. }
+            | Type::UnspecializedLambdaSet { .. }
+            | Type::HostExposedAlias { .. }
+            | Type::RangedNumber(_)
+            | Type::Erroneous(_) => return None,
+        }
+
+        Some(())
+    }
+
+    fn ext(&mut self, ext: &TypeExtension) -> Option<()> {
+        match ext {
+            TypeExtension::Open(typ) => {
+                self.bool(true);
+                self.typ(typ)
+            }
+            TypeExtension::Closed => {
+                self.bool(false);
+                Some(())
+            }
+        }
+    }
+
+    fn tags(&mut self, tags: &[(TagName, Vec<Type>)]) -> Option<()> {
+        self.len(tags.len());
+        tags.iter().try_for_each(|(name, arguments)| {
+            self.str(name.0.as_str());
+            self.types(arguments)
+        })
+    }
+
+    fn lambda_sets(&mut self, lambda_sets: &[LambdaSet]) -> Option<()> {
+        self.len(lambda_sets.len());
+        lambda_sets
+            .iter()
+            .try_for_each(|lambda_set| self.typ(lambda_set.as_inner()))
+    }
+
+    fn alias_kind(&mut self, kind: AliasKind) {
+        self.bool(kind == AliasKind::Opaque);
+    }
+
+    fn alias(&mut self, alias: &Alias) -> Option<()> {
+        let Alias {
+            region,
+            type_variables,
+            lambda_set_variables,
+            recursion_variables,
+            typ,
+            kind,
+        } = alias;
+
+        self.region(*region);
+        self.len(type_variables.len());
+        for loc_var in type_variables {
+            self.region(loc_var.region);
+            self.str(loc_var.value.name.as_str());
+            self.variable(loc_var.value.var);
+            self.opt_symbol(loc_var.value.opt_bound_ability);
+        }
+        self.lambda_sets(lambda_set_variables)?;
+        self.len(recursion_variables.len());
+        for var in recursion_variables {
+            self.variable(*var);
+        }
+        self.typ(typ)?;
+        self.alias_kind(*kind);
+
+        Some(())
+    }
+}
+
+/// A module that was not in the cache, to be written to it once it is solved.
+#[derive(Debug)]
+pub struct PendingEntry {
+    pub cache: TypeCache,
+    pub key: u64,
+    pub idents_seen: Vec<IdentsSeen>,
+}
+
+impl PendingEntry {
+    /// Skips types that can not be reused by a later run.
+    pub fn write(
+        &self,
+        subs: &Subs,
+        exposed_vars_by_symbol: &[(Symbol, Variable)],
+        abilities_store: &AbilitiesStore,
+        exports: &CachedExports,
+    ) -> io::Result<()> {
+        if !subs.problems.is_empty() || references_derived_module(subs) {
+            return Ok(());
+        }
+
+        let mut writer = Writer(Vec::new());
+
+        let mut subs_bytes = Vec::new();
+        subs.serialize(exposed_vars_by_symbol, &mut subs_bytes)?;
+        writer.bytes(&subs_bytes);
+
+        let mut abilities_bytes = Vec::new();
+        abilities_store.serialize(&mut abilities_bytes);
+        writer.bytes(&abilities_bytes);
+
+        let CachedExports {
+            ident_ids,
+            aliases,
+            abilities_store: pending_abilities_store,
+        } = exports;
+
+        writer.len(ident_ids.len());
+        for (_, ident) in ident_ids.ident_strs() {
+            writer.str(ident);
+        }
+
+        writer.len(aliases.len());
+        for (symbol, (exposed, alias)) in aliases {
+            writer.symbol(*symbol);
+            writer.bool(*exposed);
+            if writer.alias(alias).is_none() {
+                return Ok(());
+            }
+        }
+
+        pending_abilities_store.serialize(&mut writer.0);
+
+        self.cache.write(self.key, &self.idents_seen, writer.0)
+    }
+}
+
+/// Records the identifiers the given modules have right now, to validate a cache entry against later.
+pub fn idents_seen(
+    ident_ids_by_module: &IdentIdsByModule,
+    modules: impl IntoIterator<Item = ModuleId>,
+) -> Vec<IdentsSeen> {
+    modules
+        .into_iter()
+        .filter_map(|module_id| {
+            let ident_ids = ident_ids_by_module.get(&module_id)?;
+            let count = ident_ids.len() as u32;
+
+            Some((module_id, count, hash_idents(ident_ids, count)?))
+        })
+        .collect()
+}
+
+fn hash_idents(ident_ids: &roc_module::symbol::IdentIds, count: u32) -> Option<u64> {
+    if ident_ids.len() < count as usize {
+        return None;
+    }
+
+    let mut hasher = WyHash::default();
+    for (_, ident) in ident_ids.ident_strs().take(count as usize) {
+        ident.hash(&mut hasher);
+    }
+
+    Some(hasher.finish())
+}
+
+/// Types that refer to functions of the derived modules can not be cached,
+/// because those functions are only created on demand while solving.
+fn references_derived_module(subs: &Subs) -> bool {
+    subs.closure_names.iter().any(|symbol| {
+        matches!(
+            symbol.module_id(),
+            ModuleId::DERIVED_SYNTH | ModuleId::DERIVED_GEN
+        )
+    })
+}
+
+/// The default location of the cache, following the XDG base directory conventions.
+pub fn default_cache_dir() -> Option<PathBuf> {
+    let cache_home = match std::env::var_os("XDG_CACHE_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME")?).join(".cache"),
+    };
+
+    Some(cache_home.join("roc").join("types"))
+}
@@ -7,6 +7,7 @@ use roc_module::symbol::IdentIds;
 use roc_parse::ast::AssignedField;
 use roc_parse::ast::{self, ExtractSpaces, TypeHeader};
 use roc_parse::ast::{CommentOrNewline, TypeDef, ValueDef};
+use roc_region::all::Region;
 
 // Documentation generation requirements
 
@@ -37,6 +38,8 @@ pub struct DocDef {
     pub type_vars: Vec<String>,
     pub type_annotation: TypeAnnotation,
     pub docs: Option<String>,
+    /// Where the def is in the module's source code
+    pub region: Region,
 }
 
 #[derive(Debug, Clone)]
@@ -160,6 +163,7 @@ fn generate_entry_docs<'a>(
         scratchpad.extend(spaces_before);
 
         let docs = comments_or_new_lines_to_docs(&scratchpad);
+        let region = defs.regions[index];
 
         match either_index.split() {
             Err(value_index) => match &defs.value_defs[value_index.index()] {
@@ -173,6 +177,7 @@ fn generate_entry_docs<'a>(
                                 type_annotation: type_to_docs(false, loc_ann.value),
                                 type_vars: Vec::new(),
                                 docs,
+                                region,
                             };
                             acc.push(DocEntry::DocDef(doc_def));
                         }
@@ -192,6 +197,7 @@ fn generate_entry_docs<'a>(
                                 type_annotation: type_to_docs(false, ann_type.value),
                                 type_vars: Vec::new(),
                                 docs,
+                                region,
                             };
                             acc.push(DocEntry::DocDef(doc_def));
                         }
@@ -226,6 +232,7 @@ fn generate_entry_docs<'a>(
                         type_annotation: type_to_docs(false, ann.value),
                         type_vars,
                         docs,
+                        region,
                     };
                     acc.push(DocEntry::DocDef(doc_def));
                 }
@@ -247,6 +254,7 @@ fn generate_entry_docs<'a>(
                         type_annotation: TypeAnnotation::NoTypeAnn,
                         type_vars,
                         docs,
+                        region,
                     };
                     acc.push(DocEntry::DocDef(doc_def));
                 }
@@ -285,6 +293,7 @@ fn generate_entry_docs<'a>(
                         type_annotation: TypeAnnotation::Ability { members },
                         type_vars,
                         docs,
+                        region,
                     };
                     acc.push(DocEntry::DocDef(doc_def));
                 }
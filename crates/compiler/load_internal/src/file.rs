@@ -1,3 +1,4 @@
+use crate::cache::{self, TypeCache};
 use crate::docs::ModuleDocumentation;
 use bumpalo::Bump;
 use crossbeam::channel::{bounded, Sender};
@@ -11,8 +12,9 @@ use roc_can::expr::Declarations;
 use roc_can::expr::PendingDerives;
 use roc_can::module::{
     canonicalize_module_defs, ExposedByModule, ExposedForModule, ExposedModuleTypes, Module,
-    ResolvedImplementations,
+    ResolvedImplementations, RigidVariables,
 };
+use roc_can::property::PropertyTestConfig;
 use roc_collections::{default_hasher, BumpMap, MutMap, MutSet, VecMap, VecSet};
 use roc_constrain::module::constrain_module;
 use roc_debug_flags::dbg_do;
@@ -27,7 +29,7 @@ use roc_late_solve::{AbilitiesView, WorldAbilities};
 use roc_module::ident::{Ident, ModuleName, QualifiedModuleName};
 use roc_module::symbol::{
     IdentIds, IdentIdsByModule, Interns, ModuleId, ModuleIds, PQModuleName, PackageModuleIds,
-    PackageQualified, Symbol,
+    PackageQualified, Symbol, DERIVED_CODE_HELPERS,
 };
 use roc_mono::ir::{
     CapturedSymbols, ExternalSpecializations, PartialProc, Proc, ProcLayout, Procs, ProcsBase,
@@ -58,6 +60,7 @@ use std::str::from_utf8_unchecked;
 use std::sync::Arc;
 use std::{env, fs};
 
+pub use crate::cache::{default_cache_dir, CachedModule};
 use crate::work::Dependencies;
 pub use crate::work::Phase;
 
@@ -123,22 +126,47 @@ pub struct LoadConfig {
     pub render: RenderTarget,
     pub threading: Threading,
     pub exec_mode: ExecutionMode,
+    /// Where to cache the solved types of interface modules between runs; `None` disables the cache.
+    pub cache_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum ExecutionMode {
-    Test,
+    Test(TestConfig),
     Check,
     Executable,
 }
 
+/// How `roc test` compiles the expects it runs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TestConfig {
+    pub property_tests: PropertyTestConfig,
+    /// Count how often each function and branch runs, for `roc test --coverage`
+    pub coverage: bool,
+}
+
 impl ExecutionMode {
     fn goal_phase(&self) -> Phase {
         match self {
-            ExecutionMode::Test | ExecutionMode::Executable => Phase::MakeSpecializations,
+            ExecutionMode::Test(_) | ExecutionMode::Executable => Phase::MakeSpecializations,
             ExecutionMode::Check => Phase::SolveTypes,
         }
     }
+
+    /// Property tests are type checked in every mode, but only run with this config in tests.
+    fn property_test_config(&self) -> PropertyTestConfig {
+        match self {
+            ExecutionMode::Test(config) => config.property_tests,
+            ExecutionMode::Check | ExecutionMode::Executable => PropertyTestConfig::default(),
+        }
+    }
+
+    fn instrument_coverage(&self) -> bool {
+        match self {
+            ExecutionMode::Test(config) => config.coverage,
+            ExecutionMode::Check | ExecutionMode::Executable => false,
+        }
+    }
 }
 
 /// Struct storing various intermediate stages by their ModuleId
@@ -166,6 +194,13 @@ struct ModuleCache<'a> {
     type_problems: MutMap<ModuleId, Vec<TypeError>>,
 
     sources: MutMap<ModuleId, (PathBuf, &'a str)>,
+
+    /// Keys of modules in the on-disk type cache
+    type_cache_keys: MutMap<ModuleId, u64>,
+    /// Modules to write to the on-disk type cache once they are solved
+    type_cache_entries: MutMap<ModuleId, cache::PendingEntry>,
+    /// Modules found in the on-disk type cache, which are not parsed or canonicalized
+    type_cache_hits: MutSet<ModuleId>,
 }
 
 impl Default for ModuleCache<'_> {
@@ -193,6 +228,8 @@ impl Default for ModuleCache<'_> {
             ENCODE,
             DECODE,
             JSON,
+            HASH,
+            ARBITRARY,
         }
 
         Self {
@@ -213,12 +250,145 @@ impl Default for ModuleCache<'_> {
             type_problems: Default::default(),
             sources: Default::default(),
             expectations: Default::default(),
+            type_cache_keys: Default::default(),
+            type_cache_entries: Default::default(),
+            type_cache_hits: Default::default(),
         }
     }
 }
 
 type SharedIdentIdsByModule = Arc<Mutex<roc_module::symbol::IdentIdsByModule>>;
 
+/// Looks the module up in the on-disk type cache. When it is not there,
+/// remembers to write its types to the cache once they are solved.
+fn lookup_type_cache(
+    module_cache: &mut ModuleCache,
+    ident_ids_by_module: &SharedIdentIdsByModule,
+    type_cache: &TypeCache,
+    module_id: ModuleId,
+    key: u64,
+) -> Option<(CachedModule, cache::CachedExports)> {
+    let mut modules = vec![module_id];
+    let mut index = 0;
+    while let Some(module) = modules.get(index) {
+        if let Some(imports) = module_cache.imports.get(module) {
+            let new_imports: Vec<_> = imports
+                .iter()
+                .filter(|imported| !modules.contains(imported))
+                .copied()
+                .collect();
+            modules.extend(new_imports);
+        }
+        index += 1;
+    }
+
+    let ident_ids_by_module = ident_ids_by_module.lock();
+
+    let found = type_cache.read(key, &ident_ids_by_module);
+
+    if found.is_none() {
+        let entry = cache::PendingEntry {
+            cache: type_cache.clone(),
+            key,
+            idents_seen: cache::idents_seen(&ident_ids_by_module, modules),
+        };
+        module_cache.type_cache_entries.insert(module_id, entry);
+    }
+
+    found
+}
+
+/// The key of a module in the type cache, which changes whenever the module
+/// or anything it (transitively) imports changes.
+///
+/// Modules are only parsed after the modules they import, which have their keys by then.
+/// Returns `None` if one of them does not have a key anyway.
+fn type_cache_key(
+    module_cache: &mut ModuleCache,
+    type_cache: &TypeCache,
+    header: &ModuleHeader,
+) -> Option<u64> {
+    let module_id = header.module_id;
+
+    let mut import_keys = match module_cache.imports.get(&module_id) {
+        Some(imports) => imports
+            .iter()
+            .map(|imported| module_cache.type_cache_keys.get(imported).copied())
+            .collect::<Option<Vec<_>>>()?,
+        None => Vec::new(),
+    };
+
+    // sorted, so that the key does not depend on the order of the imports
+    import_keys.sort_unstable();
+
+    let key = type_cache.module_key(
+        module_id,
+        &header.module_path,
+        header.parse_state.original_bytes(),
+        import_keys,
+    );
+
+    module_cache.type_cache_keys.insert(module_id, key);
+
+    Some(key)
+}
+
+/// Marks a phase of a module as done without running it, and starts the work that was waiting on it.
+fn skip_phase<'a>(
+    module_id: ModuleId,
+    phase: Phase,
+    arena: &'a Bump,
+    state: &mut State<'a>,
+) -> Vec<BuildTask<'a>> {
+    state
+        .dependencies
+        .notify(module_id, phase)
+        .into_iter()
+        .flat_map(|(module_id, phase)| start_phase(module_id, phase, arena, state))
+        .collect()
+}
+
+/// The stand-in for the canonicalized module of an interface found in the type cache.
+/// Solving it just takes its types from the cache.
+fn cached_constrained_module(
+    module_id: ModuleId,
+    exports: cache::CachedExports,
+    exposed_symbols: VecSet<Symbol>,
+    module_timing: ModuleTiming,
+) -> ConstrainedModule {
+    let cache::CachedExports {
+        ident_ids,
+        aliases,
+        abilities_store,
+    } = exports;
+
+    let module = Module {
+        module_id,
+        exposed_imports: MutMap::default(),
+        exposed_symbols,
+        referenced_values: VecSet::default(),
+        referenced_types: VecSet::default(),
+        aliases,
+        rigid_variables: RigidVariables::default(),
+        abilities_store,
+        loc_expects: VecMap::default(),
+    };
+
+    ConstrainedModule {
+        module,
+        declarations: Declarations::new(),
+        // the imports were all used, because modules with warnings are not cached
+        imported_modules: MutMap::default(),
+        constraints: Constraints::new(),
+        constraint: ConstraintSoa::True,
+        ident_ids,
+        var_store: VarStore::default(),
+        dep_idents: IdentIdsByModule::default(),
+        module_timing,
+        pending_derives: PendingDerives::default(),
+    }
+}
+
 fn start_phase<'a>(
     module_id: ModuleId,
     phase: Phase,
@@ -268,12 +438,79 @@ fn start_phase<'a>(
                 }
             }
             Phase::Parse => {
-                // parse the file
                 let header = state.module_cache.headers.remove(&module_id).unwrap();
 
+                if let Some(type_cache) = state.type_cache.clone() {
+                    let key = type_cache_key(&mut state.module_cache, &type_cache, &header);
+
+                    // builtins have their own cache
+                    let found = match (key, module_id.is_builtin(), &header.module_name) {
+                        (Some(key), false, ModuleNameEnum::Interface(_)) => lookup_type_cache(
+                            &mut state.module_cache,
+                            &state.ident_ids_by_module,
+                            &type_cache,
+                            module_id,
+                            key,
+                        ),
+                        _ => None,
+                    };
+
+                    if let Some((cached, exports)) = found {
+                        state.cached_subs.lock().insert(module_id, cached);
+
+                        // When only checking types, nothing needs more of the module than its
+                        // importers do, so it does not have to be parsed or canonicalized.
+                        // The source was valid when the entry was written, because it parsed.
+                        let source = std::str::from_utf8(header.parse_state.original_bytes());
+                        if let (Phase::SolveTypes, false, Ok(src)) =
+                            (state.goal_phase(), module_id == state.root_id, source)
+                        {
+                            let exposed_symbols = state
+                                .exposed_symbols_by_module
+                                .get(&module_id)
+                                .expect("Could not find listener ID in exposed_symbols_by_module")
+                                .clone();
+
+                            state
+                                .module_cache
+                                .aliases
+                                .insert(module_id, exports.aliases.clone());
+                            state
+                                .module_cache
+                                .pending_abilities
+                                .insert(module_id, exports.abilities_store.clone());
+
+                            let constrained_module = cached_constrained_module(
+                                module_id,
+                                exports,
+                                exposed_symbols,
+                                header.module_timing,
+                            );
+                            state
+                                .module_cache
+                                .constrained
+                                .insert(module_id, constrained_module);
+
+                            state
+                                .module_cache
+                                .sources
+                                .insert(module_id, (header.module_path, src));
+                            state.module_cache.type_cache_hits.insert(module_id);
+
+                            return skip_phase(module_id, Phase::Parse, arena, state);
+                        }
+                    }
+                }
+
+                // parse the file
                 BuildTask::Parse { header }
             }
             Phase::CanonicalizeAndConstrain => {
+                if state.module_cache.type_cache_hits.remove(&module_id) {
+                    // already constrained from the type cache
+                    return skip_phase(module_id, Phase::CanonicalizeAndConstrain, arena, state);
+                }
+
                 // canonicalize the file
                 let parsed = state.module_cache.parsed.remove(&module_id).unwrap();
 
@@ -378,6 +615,7 @@ fn start_phase<'a>(
                     aliases,
                     abilities_store,
                     skip_constraint_gen,
+                    property_test_config: state.exec_mode.property_test_config(),
                 }
             }
 
@@ -400,6 +638,17 @@ fn start_phase<'a>(
 
                 let derived_module = SharedDerivedModule::clone(&state.derived_module);
 
+                // Modules with canonicalization problems are loaded again, so that they are reported.
+                let has_can_problems = matches!(
+                    state.module_cache.can_problems.get(&module_id),
+                    Some(problems) if !problems.is_empty()
+                );
+                let type_cache_entry = state
+                    .module_cache
+                    .type_cache_entries
+                    .remove(&module_id)
+                    .filter(|_| !has_can_problems);
+
                 BuildTask::solve_module(
                     module,
                     ident_ids,
@@ -413,6 +662,7 @@ fn start_phase<'a>(
                     dep_idents,
                     declarations,
                     state.cached_subs.clone(),
+                    type_cache_entry,
                     derived_module,
                 )
             }
@@ -504,24 +754,8 @@ fn start_phase<'a>(
                             procs_base,
                             layout_cache,
                             module_timing,
-                            abilities_store,
                         } = found_specializations;
 
-                        let our_exposed_types = state
-                            .exposed_types
-                            .get(&module_id)
-                            .unwrap_or_else(|| {
-                                internal_error!("Exposed types for {:?} missing", module_id)
-                            })
-                            .clone();
-
-                        // Add our abilities to the world.
-                        state.world_abilities.insert(
-                            module_id,
-                            abilities_store,
-                            our_exposed_types.exposed_types_storage_subs,
-                        );
-
                         (ident_ids, subs, procs_base, layout_cache, module_timing)
                     } else {
                         let LateSpecializationsModule {
@@ -558,6 +792,7 @@ fn start_phase<'a>(
 
                 BuildTask::MakeSpecializations {
                     module_id,
+                    execution_mode: state.exec_mode,
                     ident_ids,
                     subs,
                     procs_base,
@@ -677,7 +912,6 @@ struct FoundSpecializationsModule<'a> {
     procs_base: ProcsBase<'a>,
     subs: Subs,
     module_timing: ModuleTiming,
-    abilities_store: AbilitiesStore,
 }
 
 #[derive(Debug)]
@@ -699,6 +933,8 @@ pub struct MonomorphizedModule<'a> {
     pub type_problems: MutMap<ModuleId, Vec<TypeError>>,
     pub procedures: MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
     pub toplevel_expects: VecMap<Symbol, Region>,
+    /// The top-level expects that are property tests
+    pub property_expects: VecSet<Symbol>,
     pub entry_point: EntryPoint<'a>,
     pub exposed_to_host: ExposedToHost,
     pub sources: MutMap<ModuleId, (PathBuf, Box<str>)>,
@@ -804,6 +1040,7 @@ enum Msg<'a> {
         module_timing: ModuleTiming,
         abilities_store: AbilitiesStore,
         toplevel_expects: VecMap<Symbol, Region>,
+        property_expects: VecSet<Symbol>,
     },
     MadeSpecializations {
         module_id: ModuleId,
@@ -891,6 +1128,7 @@ struct State<'a> {
     pub dependencies: Dependencies<'a>,
     pub procedures: MutMap<(Symbol, ProcLayout<'a>), Proc<'a>>,
     pub toplevel_expects: VecMap<Symbol, Region>,
+    pub property_expects: VecSet<Symbol>,
     pub exposed_to_host: ExposedToHost,
 
     /// This is the "final" list of IdentIds, after canonicalization and constraint gen
@@ -926,11 +1164,13 @@ struct State<'a> {
 
     make_specializations_pass: MakeSpecializationsPass,
 
-    // cached subs (used for builtin modules, could include packages in the future too)
+    // cached subs (used for builtin modules, and interface modules found in the type cache)
     cached_subs: CachedSubs,
+
+    type_cache: Option<TypeCache>,
 }
 
-type CachedSubs = Arc<Mutex<MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)>>>;
+type CachedSubs = Arc<Mutex<MutMap<ModuleId, CachedModule>>>;
 
 impl<'a> State<'a> {
     fn goal_phase(&self) -> Phase {
@@ -944,14 +1184,16 @@ impl<'a> State<'a> {
         exposed_types: ExposedByModule,
         arc_modules: Arc<Mutex<PackageModuleIds<'a>>>,
         ident_ids_by_module: SharedIdentIdsByModule,
-        cached_subs: MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)>,
+        cached_subs: MutMap<ModuleId, CachedModule>,
         render: RenderTarget,
         number_of_workers: usize,
         exec_mode: ExecutionMode,
+        cache_dir: Option<PathBuf>,
     ) -> Self {
         let arc_shorthands = Arc::new(Mutex::new(MutMap::default()));
 
-        let dependencies = Dependencies::new(exec_mode.goal_phase());
+        let type_cache = cache_dir.and_then(TypeCache::new);
+        let dependencies = Dependencies::new(exec_mode.goal_phase(), type_cache.is_some());
 
         Self {
             root_id,
@@ -964,6 +1206,7 @@ impl<'a> State<'a> {
             dependencies,
             procedures: MutMap::default(),
             toplevel_expects: VecMap::default(),
+            property_expects: VecSet::default(),
             exposed_to_host: ExposedToHost::default(),
             exposed_types,
             arc_modules,
@@ -980,6 +1223,7 @@ impl<'a> State<'a> {
             exec_mode,
             make_specializations_pass: MakeSpecializationsPass::Pass(1),
             world_abilities: Default::default(),
+            type_cache,
         }
     }
 }
@@ -1075,6 +1319,7 @@ enum BuildTask<'a> {
         aliases: MutMap<Symbol, Alias>,
         abilities_store: PendingAbilitiesStore,
         skip_constraint_gen: bool,
+        property_test_config: PropertyTestConfig,
     },
     Solve {
         module: Module,
@@ -1088,6 +1333,7 @@ enum BuildTask<'a> {
         declarations: Declarations,
         dep_idents: IdentIdsByModule,
         cached_subs: CachedSubs,
+        type_cache_entry: Option<cache::PendingEntry>,
         derived_module: SharedDerivedModule,
     },
     BuildPendingSpecializations {
@@ -1106,6 +1352,7 @@ enum BuildTask<'a> {
     },
     MakeSpecializations {
         module_id: ModuleId,
+        execution_mode: ExecutionMode,
         ident_ids: IdentIds,
         subs: Subs,
         procs_base: ProcsBase<'a>,
@@ -1190,6 +1437,7 @@ pub fn load_and_typecheck_str<'a>(
         render,
         threading,
         exec_mode: ExecutionMode::Check,
+        cache_dir: None,
     };
 
     match load(arena, load_start, exposed_types, cached_subs, load_config)? {
@@ -1284,7 +1532,7 @@ impl<'a> LoadStart<'a> {
                     return Err(LoadingProblem::FormattedReport(buf));
                 }
                 Err(LoadingProblem::FileProblem { filename, error }) => {
-                    let buf = to_file_problem_report(&filename, error);
+                    let buf = to_file_problem_report(&filename, error, render);
                     return Err(LoadingProblem::FormattedReport(buf));
                 }
                 Err(e) => return Err(e),
@@ -1401,7 +1649,7 @@ pub fn load<'a>(
     arena: &'a Bump,
     load_start: LoadStart<'a>,
     exposed_types: ExposedByModule,
-    cached_subs: MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)>,
+    cached_subs: MutMap<ModuleId, CachedModule>,
     load_config: LoadConfig,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     enum Threads {
@@ -1437,6 +1685,7 @@ pub fn load<'a>(
             cached_subs,
             load_config.render,
             load_config.exec_mode,
+            load_config.cache_dir,
         ),
         Threads::Many(threads) => load_multi_threaded(
             arena,
@@ -1447,6 +1696,7 @@ pub fn load<'a>(
             load_config.render,
             threads,
             load_config.exec_mode,
+            load_config.cache_dir,
         ),
     }
 }
@@ -1458,9 +1708,10 @@ pub fn load_single_threaded<'a>(
     load_start: LoadStart<'a>,
     exposed_types: ExposedByModule,
     target_info: TargetInfo,
-    cached_subs: MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)>,
+    cached_subs: MutMap<ModuleId, CachedModule>,
     render: RenderTarget,
     exec_mode: ExecutionMode,
+    cache_dir: Option<PathBuf>,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let LoadStart {
         arc_modules,
@@ -1488,6 +1739,7 @@ pub fn load_single_threaded<'a>(
         render,
         number_of_workers,
         exec_mode,
+        cache_dir,
     );
 
     // We'll add tasks to this, and then worker threads will take tasks from it.
@@ -1557,6 +1809,10 @@ fn state_thread_step<'a>(
                     // We're done! There should be no more messages pending.
                     debug_assert!(msg_rx.is_empty());
 
+                    if let Some(type_cache) = &state.type_cache {
+                        type_cache.evict_if_written();
+                    }
+
                     let exposed_aliases_by_symbol = exposed_aliases_by_symbol
                         .into_iter()
                         .map(|(k, (_, v))| (k, v))
@@ -1583,12 +1839,16 @@ fn state_thread_step<'a>(
                     // We're done! There should be no more messages pending.
                     debug_assert!(msg_rx.is_empty());
 
+                    if let Some(type_cache) = &state.type_cache {
+                        type_cache.evict_if_written();
+                    }
+
                     let monomorphized = finish_specialization(state, subs, exposed_to_host)?;
 
                     Ok(ControlFlow::Break(LoadResult::Monomorphized(monomorphized)))
                 }
                 Msg::FailedToReadFile { filename, error } => {
-                    let buf = to_file_problem_report(&filename, error);
+                    let buf = to_file_problem_report(&filename, error, state.render);
                     Err(LoadingProblem::FormattedReport(buf))
                 }
 
@@ -1659,10 +1919,11 @@ fn load_multi_threaded<'a>(
     load_start: LoadStart<'a>,
     exposed_types: ExposedByModule,
     target_info: TargetInfo,
-    cached_subs: MutMap<ModuleId, (Subs, Vec<(Symbol, Variable)>)>,
+    cached_subs: MutMap<ModuleId, CachedModule>,
     render: RenderTarget,
     available_threads: usize,
     exec_mode: ExecutionMode,
+    cache_dir: Option<PathBuf>,
 ) -> Result<LoadResult<'a>, LoadingProblem<'a>> {
     let LoadStart {
         arc_modules,
@@ -1705,6 +1966,7 @@ fn load_multi_threaded<'a>(
         render,
         num_workers,
         exec_mode,
+        cache_dir,
     );
 
     // an arena for every worker, stored in an arena-allocated bumpalo vec to make the lifetimes work
@@ -2209,6 +2471,22 @@ fn update<'a>(
                 header
                     .imported_modules
                     .insert(ModuleId::ENCODE, Region::zero());
+
+                header
+                    .package_qualified_imported_modules
+                    .insert(PackageQualified::Unqualified(ModuleId::HASH));
+
+                header
+                    .imported_modules
+                    .insert(ModuleId::HASH, Region::zero());
+
+                header
+                    .package_qualified_imported_modules
+                    .insert(PackageQualified::Unqualified(ModuleId::ARBITRARY));
+
+                header
+                    .imported_modules
+                    .insert(ModuleId::ARBITRARY, Region::zero());
             }
 
             state
@@ -2442,6 +2720,7 @@ fn update<'a>(
                         .typechecked
                         .insert(module_id, typechecked);
                 } else {
+                    state.declarations_by_id.insert(module_id, decls);
                     state.constrained_ident_ids.insert(module_id, ident_ids);
                     state.timings.insert(module_id, module_timing);
                 }
@@ -2460,12 +2739,14 @@ fn update<'a>(
             module_timing,
             abilities_store,
             toplevel_expects,
+            property_expects,
         } => {
             log!("found specializations for {:?}", module_id);
 
             let subs = solved_subs.into_inner();
 
             state.toplevel_expects.extend(toplevel_expects);
+            state.property_expects.extend(property_expects);
 
             state
                 .module_cache
@@ -2474,13 +2755,27 @@ fn update<'a>(
                 .or_default()
                 .extend(procs_base.module_thunks.iter().copied());
 
+            let our_exposed_types = state
+                .exposed_types
+                .get(&module_id)
+                .unwrap_or_else(|| internal_error!("Exposed types for {:?} missing", module_id))
+                .clone();
+
+            // Add our abilities to the world. This happens before any module makes
+            // specializations, because importers make theirs before the modules they import,
+            // and may need to resolve members of abilities defined there.
+            state.world_abilities.insert(
+                module_id,
+                abilities_store,
+                our_exposed_types.exposed_types_storage_subs,
+            );
+
             let found_specializations_module = FoundSpecializationsModule {
                 ident_ids,
                 layout_cache,
                 procs_base,
                 subs,
                 module_timing,
-                abilities_store,
             };
 
             state
@@ -2778,6 +3073,7 @@ fn finish_specialization(
 
     let State {
         toplevel_expects,
+        property_expects,
         procedures,
         module_cache,
         output_path,
@@ -2802,7 +3098,7 @@ fn finish_specialization(
 
     let entry_point = {
         match exec_mode {
-            ExecutionMode::Test => EntryPoint::Test,
+            ExecutionMode::Test(_) => EntryPoint::Test,
             ExecutionMode::Executable => {
                 let path_to_platform = {
                     use PlatformPath::*;
@@ -2815,7 +3111,8 @@ fn finish_specialization(
                         }
                         Valid(To::NewPackage(p_or_p)) => p_or_p,
                         other => {
-                            let buf = to_missing_platform_report(state.root_id, other);
+                            let buf =
+                                to_missing_platform_report(state.root_id, other, state.render);
                             return Err(LoadingProblem::FormattedReport(buf));
                         }
                     };
@@ -2876,6 +3173,7 @@ fn finish_specialization(
         sources,
         timings: state.timings,
         toplevel_expects,
+        property_expects,
     })
 }
 
@@ -3132,6 +3430,8 @@ fn load_module<'a>(
         "Encode", ModuleId::ENCODE
         "Decode", ModuleId::DECODE
         "Json", ModuleId::JSON
+        "Hash", ModuleId::HASH
+        "Arbitrary", ModuleId::ARBITRARY
     }
 
     let (filename, opt_shorthand) = module_name_to_path(src_dir, module_name, arc_shorthands);
@@ -3951,6 +4251,7 @@ impl<'a> BuildTask<'a> {
         dep_idents: IdentIdsByModule,
         declarations: Declarations,
         cached_subs: CachedSubs,
+        type_cache_entry: Option<cache::PendingEntry>,
         derived_module: SharedDerivedModule,
     ) -> Self {
         let exposed_by_module = exposed_types.retain_modules(imported_modules.keys());
@@ -3971,6 +4272,7 @@ impl<'a> BuildTask<'a> {
             dep_idents,
             module_timing,
             cached_subs,
+            type_cache_entry,
             derived_module,
         }
     }
@@ -4220,11 +4522,15 @@ fn run_solve_solve(
                 })
         };
 
-        // Expose anything that is explicitly exposed by the header, or is a specialization of an
-        // ability.
+        // Expose anything that is explicitly exposed by the header, is a specialization of an
+        // ability, or is a helper that derived implementations need the type of.
         let exposed_vars_by_symbol: Vec<_> = solved_env
             .vars_by_symbol()
-            .filter(|(k, _)| exposed_symbols.contains(k) || is_specialization_symbol(*k))
+            .filter(|(k, _)| {
+                exposed_symbols.contains(k)
+                    || is_specialization_symbol(*k)
+                    || DERIVED_CODE_HELPERS.contains(k)
+            })
             .collect();
 
         (
@@ -4258,6 +4564,7 @@ fn run_solve<'a>(
     decls: Declarations,
     dep_idents: IdentIdsByModule,
     cached_subs: CachedSubs,
+    type_cache_entry: Option<cache::PendingEntry>,
     derived_module: SharedDerivedModule,
 ) -> Msg<'a> {
     let solve_start = Instant::now();
@@ -4271,10 +4578,27 @@ fn run_solve<'a>(
     let loc_expects = std::mem::take(&mut module.loc_expects);
     let module = module;
 
+    let cached = cached_subs.lock().remove(&module_id);
     let (solved_subs, solved_implementations, exposed_vars_by_symbol, problems, abilities_store) = {
-        if module_id.is_builtin() {
-            match cached_subs.lock().remove(&module_id) {
-                None => run_solve_solve(
+        match cached {
+            None => {
+                // what importers see of this module, for when they find it in the type cache
+                let exports = type_cache_entry.as_ref().map(|_| cache::CachedExports {
+                    ident_ids: ident_ids.clone(),
+                    aliases: module
+                        .aliases
+                        .iter()
+                        .filter(|(symbol, (exposed, _))| {
+                            *exposed || symbol.module_id() != module_id
+                        })
+                        .map(|(symbol, (exposed, alias))| (*symbol, (*exposed, alias.clone())))
+                        .collect(),
+                    abilities_store: module
+                        .abilities_store
+                        .closure_from_imported(&module.exposed_symbols),
+                });
+
+                let solved = run_solve_solve(
                     exposed_for_module,
                     constraints,
                     constraint,
@@ -4282,29 +4606,39 @@ fn run_solve<'a>(
                     var_store,
                     module,
                     derived_module,
-                ),
-                Some((subs, exposed_vars_by_symbol)) => {
-                    (
-                        Solved(subs),
-                        // TODO(abilities) cache abilities for builtins
-                        VecMap::default(),
-                        exposed_vars_by_symbol.to_vec(),
-                        vec![],
-                        // TODO(abilities) cache abilities for builtins
-                        AbilitiesStore::default(),
-                    )
+                );
+
+                if let (Some(entry), Some(exports)) = (type_cache_entry, exports) {
+                    let (solved_subs, _, exposed_vars_by_symbol, problems, abilities_store) =
+                        &solved;
+
+                    // Modules with problems are solved again, so that the problems are reported.
+                    if problems.is_empty() {
+                        // The error is only logged in debug builds.
+                        if let Err(_err) = entry.write(
+                            solved_subs.inner(),
+                            exposed_vars_by_symbol,
+                            abilities_store,
+                            &exports,
+                        ) {
+                            log!("could not cache the types of {:?}: {}", module_id, _err);
+                        }
+                    }
                 }
+
+                solved
             }
-        } else {
-            run_solve_solve(
-                exposed_for_module,
-                constraints,
-                constraint,
-                pending_derives,
-                var_store,
-                module,
-                derived_module,
-            )
+            Some(CachedModule {
+                subs,
+                exposed_vars_by_symbol,
+                abilities_store,
+            }) => (
+                Solved(subs),
+                extract_module_owned_implementations(module_id, &abilities_store),
+                exposed_vars_by_symbol,
+                vec![],
+                abilities_store,
+            ),
         }
     };
 
@@ -4404,6 +4738,7 @@ fn canonicalize_and_constrain<'a>(
     imported_abilities_state: PendingAbilitiesStore,
     parsed: ParsedModule<'a>,
     skip_constraint_gen: bool,
+    property_test_config: PropertyTestConfig,
 ) -> CanAndCon {
     let canonicalize_start = Instant::now();
 
@@ -4440,6 +4775,7 @@ fn canonicalize_and_constrain<'a>(
         exposed_imports,
         &exposed_symbols,
         &symbols_from_requires,
+        property_test_config,
         &mut var_store,
     );
 
@@ -4520,7 +4856,12 @@ fn canonicalize_and_constrain<'a>(
             Vacant(vacant) => {
                 let should_include_builtin = matches!(
                     name.module_id(),
-                    ModuleId::ENCODE | ModuleId::DECODE | ModuleId::DICT | ModuleId::SET
+                    ModuleId::ENCODE
+                        | ModuleId::DECODE
+                        | ModuleId::DICT
+                        | ModuleId::SET
+                        | ModuleId::HASH
+                        | ModuleId::ARBITRARY
                 );
 
                 if !name.is_builtin() || should_include_builtin {
@@ -4663,6 +5004,7 @@ fn ident_from_exposed(entry: &Spaced<'_, ExposedName<'_>>) -> Ident {
 fn make_specializations<'a>(
     arena: &'a Bump,
     home: ModuleId,
+    execution_mode: ExecutionMode,
     mut ident_ids: IdentIds,
     mut subs: Subs,
     procs_base: ProcsBase<'a>,
@@ -4689,6 +5031,8 @@ fn make_specializations<'a>(
         abilities: AbilitiesView::World(&world_abilities),
         exposed_by_module,
         derived_module: &derived_module,
+        instrument_coverage: execution_mode.instrument_coverage(),
+        statement_regions: Vec::new(),
     };
 
     let mut procs = Procs::new_in(arena);
@@ -4757,6 +5101,7 @@ fn build_pending_specializations<'a>(
 
     let mut module_thunks = bumpalo::collections::Vec::new_in(arena);
     let mut toplevel_expects = VecMap::default();
+    let mut property_expects = VecSet::default();
 
     let mut procs_base = ProcsBase {
         partial_procs: BumpMap::default(),
@@ -4783,6 +5128,8 @@ fn build_pending_specializations<'a>(
         abilities: AbilitiesView::Module(&abilities_store),
         exposed_by_module,
         derived_module: &derived_module,
+        instrument_coverage: execution_mode.instrument_coverage(),
+        statement_regions: Vec::new(),
     };
 
     // Add modules' decls to Procs
@@ -4847,6 +5194,7 @@ fn build_pending_specializations<'a>(
                     pattern_symbols: &[],
                     // This is a top-level definition, so it cannot capture anything
                     captured_symbols: CapturedSymbols::None,
+                    region: body.region,
                     body: body.value,
                     body_var: expr_var,
                     // This is a 0-arity thunk, so it cannot be recursive
@@ -4985,6 +5333,7 @@ fn build_pending_specializations<'a>(
                     pattern_symbols: &[],
                     // This is a top-level definition, so it cannot capture anything
                     captured_symbols: CapturedSymbols::None,
+                    region: body.region,
                     body: body.value,
                     body_var: expr_var,
                     // This is a 0-arity thunk, so it cannot be recursive
@@ -4999,7 +5348,7 @@ fn build_pending_specializations<'a>(
             Expectation => {
                 // skip expectations if we're not going to run them
                 match execution_mode {
-                    ExecutionMode::Test => { /* fall through */ }
+                    ExecutionMode::Test(_) => { /* fall through */ }
                     ExecutionMode::Check | ExecutionMode::Executable => continue,
                 }
 
@@ -5047,6 +5396,10 @@ fn build_pending_specializations<'a>(
                     );
                 }
 
+                if roc_can::property::is_property_expect(&body) {
+                    property_expects.insert(symbol);
+                }
+
                 let body = roc_can::expr::toplevel_expect_to_inline_expect(body);
 
                 let proc = PartialProc {
@@ -5055,6 +5408,7 @@ fn build_pending_specializations<'a>(
                     pattern_symbols: &[],
                     // This is a top-level definition, so it cannot capture anything
                     captured_symbols: CapturedSymbols::None,
+                    region: body.region,
                     body: body.value,
                     body_var: expr_var,
                     // This is a 0-arity thunk, so it cannot be recursive
@@ -5073,7 +5427,7 @@ fn build_pending_specializations<'a>(
             ExpectationFx => {
                 // skip expectations if we're not going to run them
                 match execution_mode {
-                    ExecutionMode::Test => { /* fall through */ }
+                    ExecutionMode::Test(_) => { /* fall through */ }
                     ExecutionMode::Check | ExecutionMode::Executable => continue,
                 }
 
@@ -5129,6 +5483,7 @@ fn build_pending_specializations<'a>(
                     pattern_symbols: &[],
                     // This is a top-level definition, so it cannot capture anything
                     captured_symbols: CapturedSymbols::None,
+                    region: body.region,
                     body: body.value,
                     body_var: expr_var,
                     // This is a 0-arity thunk, so it cannot be recursive
@@ -5162,6 +5517,7 @@ fn build_pending_specializations<'a>(
         module_timing,
         abilities_store,
         toplevel_expects,
+        property_expects,
     }
 }
 
@@ -5216,6 +5572,9 @@ fn load_derived_partial_procs<'a>(
             abilities: AbilitiesView::World(world_abilities),
             exposed_by_module,
             derived_module,
+            // the derived implementations of abilities aren't user code
+            instrument_coverage: false,
+            statement_regions: Vec::new(),
         };
 
         let partial_proc = match derived_expr {
@@ -5253,6 +5612,8 @@ fn load_derived_partial_procs<'a>(
                     body_var: derived_expr_var,
                     // This is a 0-arity thunk, so it cannot be recursive
                     is_self_recursive: false,
+                    // Derived implementations have no source of their own
+                    region: Region::zero(),
                 }
             }
         };
@@ -5306,6 +5667,7 @@ fn run_task<'a>(
             aliases,
             abilities_store,
             skip_constraint_gen,
+            property_test_config,
         } => {
             let can_and_con = canonicalize_and_constrain(
                 arena,
@@ -5316,6 +5678,7 @@ fn run_task<'a>(
                 abilities_store,
                 parsed,
                 skip_constraint_gen,
+                property_test_config,
             );
 
             Ok(Msg::CanonicalizedAndConstrained(can_and_con))
@@ -5332,6 +5695,7 @@ fn run_task<'a>(
             declarations,
             dep_idents,
             cached_subs,
+            type_cache_entry,
             derived_module,
         } => Ok(run_solve(
             module,
@@ -5345,6 +5709,7 @@ fn run_task<'a>(
             declarations,
             dep_idents,
             cached_subs,
+            type_cache_entry,
             derived_module,
         )),
         BuildPendingSpecializations {
@@ -5378,6 +5743,7 @@ fn run_task<'a>(
         )),
         MakeSpecializations {
             module_id,
+            execution_mode,
             ident_ids,
             subs,
             procs_base,
@@ -5390,6 +5756,7 @@ fn run_task<'a>(
         } => Ok(make_specializations(
             arena,
             module_id,
+            execution_mode,
             ident_ids,
             subs,
             procs_base,
@@ -5410,7 +5777,7 @@ fn run_task<'a>(
     Ok(())
 }
 
-fn to_file_problem_report(filename: &Path, error: io::ErrorKind) -> String {
+fn to_file_problem_report(filename: &Path, error: io::ErrorKind, render: RenderTarget) -> String {
     use roc_reporting::report::{Report, RocDocAllocator, Severity, DEFAULT_PALETTE};
     use ven_pretty::DocAllocator;
 
@@ -5482,7 +5849,7 @@ fn to_file_problem_report(filename: &Path, error: io::ErrorKind) -> String {
 
     let mut buf = String::new();
     let palette = DEFAULT_PALETTE;
-    report.render_color_terminal(&mut buf, &alloc, &palette);
+    report.render(render, &mut buf, &alloc, &palette);
 
     buf
 }
@@ -5531,7 +5898,11 @@ fn to_parse_problem_report<'a>(
     buf
 }
 
-fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> String {
+fn to_missing_platform_report(
+    module_id: ModuleId,
+    other: PlatformPath,
+    render: RenderTarget,
+) -> String {
     use roc_reporting::report::{Report, RocDocAllocator, Severity, DEFAULT_PALETTE};
     use ven_pretty::DocAllocator;
     use PlatformPath::*;
@@ -5610,7 +5981,7 @@ fn to_missing_platform_report(module_id: ModuleId, other: PlatformPath) -> Strin
 
     let palette = DEFAULT_PALETTE;
     let mut buf = String::new();
-    report.render_color_terminal(&mut buf, &alloc, &palette);
+    report.render(render, &mut buf, &alloc, &palette);
 
     buf
 }
@@ -101,15 +101,20 @@ pub struct Dependencies<'a> {
     status: MutMap<Job<'a>, Status>,
 
     make_specializations_dependents: MakeSpecializationsDependents,
+
+    /// Whether modules are only parsed after the modules they import, so that the type cache can
+    /// be checked before parsing them.
+    parse_after_imports: bool,
 }
 
 impl<'a> Dependencies<'a> {
-    pub fn new(goal_phase: Phase) -> Self {
+    pub fn new(goal_phase: Phase, parse_after_imports: bool) -> Self {
         let mut deps = Self {
             waiting_for: Default::default(),
             notifies: Default::default(),
             status: Default::default(),
             make_specializations_dependents: Default::default(),
+            parse_after_imports,
         };
 
         if goal_phase >= Phase::MakeSpecializations {
@@ -144,7 +149,12 @@ impl<'a> Dependencies<'a> {
 
             // to parse and generate constraints, the headers of all dependencies must be loaded!
             // otherwise, we don't know whether an imported symbol is actually exposed
-            self.add_dependency_help(module_id, dep, Phase::Parse, Phase::LoadHeader);
+            if self.parse_after_imports {
+                // the type cache key of a module is made from the keys of its imports
+                self.add_dependency(module_id, dep, Phase::Parse);
+            } else {
+                self.add_dependency_help(module_id, dep, Phase::Parse, Phase::LoadHeader);
+            }
 
             // to canonicalize a module, all its dependencies must be canonicalized
             self.add_dependency(module_id, dep, Phase::CanonicalizeAndConstrain);
@@ -37,6 +37,16 @@ fn load_and_typecheck(
     filename: PathBuf,
     exposed_types: ExposedByModule,
     target_info: TargetInfo,
+) -> Result<LoadedModule, LoadingProblem> {
+    load_and_typecheck_cached(arena, filename, exposed_types, target_info, None)
+}
+
+fn load_and_typecheck_cached(
+    arena: &Bump,
+    filename: PathBuf,
+    exposed_types: ExposedByModule,
+    target_info: TargetInfo,
+    cache_dir: Option<PathBuf>,
 ) -> Result<LoadedModule, LoadingProblem> {
     use LoadResult::*;
 
@@ -46,6 +56,7 @@ fn load_and_typecheck(
         render: RenderTarget::Generic,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Check,
+        cache_dir,
     };
 
     match roc_load_internal::file::load(
@@ -481,12 +492,12 @@ fn load_astar() {
     expect_types(
         loaded_module,
         hashmap! {
-            "findPath" => "{ costFunction : position, position -> F64, end : position, moveFunction : position -> Set position, start : position } -> Result (List position) [KeyNotFound]*",
-            "initialModel" => "position -> Model position",
-            "reconstructPath" => "Dict position position, position -> List position",
-            "updateCost" => "position, position, Model position -> Model position",
-            "cheapestOpen" => "(position -> F64), Model position -> Result position [KeyNotFound]*",
-            "astar" => "(position, position -> F64), (position -> Set position), position, Model position -> [Err [KeyNotFound]*, Ok (List position)]*",
+            "findPath" => "{ costFunction : position, position -> F64, end : position, moveFunction : position -> Set position, start : position } -> Result (List position) [KeyNotFound]* | position has Hash",
+            "initialModel" => "position -> Model position | position has Hash",
+            "reconstructPath" => "Dict position position, position -> List position | position has Hash",
+            "updateCost" => "position, position, Model position -> Model position | position has Hash",
+            "cheapestOpen" => "(position -> F64), Model position -> Result position [KeyNotFound]* | position has Hash",
+            "astar" => "(position, position -> F64), (position -> Set position), position, Model position -> [Err [KeyNotFound]*, Ok (List position)]* | position has Hash",
         },
     );
 }
@@ -527,6 +538,282 @@ fn iface_dep_types() {
     );
 }
 
+#[test]
+fn iface_dep_types_from_type_cache() {
+    let cache_dir = roc_test_utils::TmpDir::new("tmp/iface_dep_types_from_type_cache");
+    let filename = fixtures_dir()
+        .join("interface_with_deps")
+        .join("Primary.roc");
+
+    let cache_entries = || {
+        let mut entries: Vec<_> = std::fs::read_dir(cache_dir.path())
+            .unwrap()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                (entry.path(), entry.metadata().unwrap().modified().unwrap())
+            })
+            .collect();
+        entries.sort();
+        entries
+    };
+
+    // The first load fills the cache; the second one only reads from it.
+    let mut entries_after_first_load = None;
+    for _ in 0..2 {
+        let arena = Bump::new();
+        let loaded = load_and_typecheck_cached(
+            &arena,
+            filename.clone(),
+            Default::default(),
+            TARGET_INFO,
+            Some(cache_dir.path().to_path_buf()),
+        );
+        let loaded_module = loaded.expect("Test module failed to load");
+
+        let entries = cache_entries();
+        assert!(!entries.is_empty());
+        assert_eq!(
+            *entries_after_first_load.get_or_insert(entries.clone()),
+            entries
+        );
+
+        expect_types(
+            loaded_module,
+            hashmap! {
+                "blah2" => "Float *",
+                "blah3" => "Str",
+                "str" => "Str",
+                "alwaysThree" => "* -> Float *",
+                "identity" => "a -> a",
+                "z" => "Float *",
+                "w" => "Dep1.Identity {}",
+                "succeed" => "a -> Dep1.Identity a",
+                "yay" => "Res.Res {} err",
+                "withDefault" => "Res.Res a err, a -> a",
+            },
+        );
+    }
+}
+
+fn load_with_type_cache(filename: &std::path::Path, cache_dir: &std::path::Path) -> LoadedModule {
+    let arena = Bump::new();
+    let loaded = load_and_typecheck_cached(
+        &arena,
+        filename.to_path_buf(),
+        Default::default(),
+        TARGET_INFO,
+        Some(cache_dir.to_path_buf()),
+    );
+
+    loaded.expect("Test module failed to load")
+}
+
+#[test]
+fn type_cache_skips_unchanged_modules() {
+    let cache_dir = roc_test_utils::TmpDir::new("tmp/type_cache_skips_unchanged_modules");
+    let filename = fixtures_dir()
+        .join("interface_with_deps")
+        .join("Primary.roc");
+
+    // Whether Dep1 was canonicalized, rather than taken from the cache as a whole.
+    let canonicalized_dep1 = || {
+        let loaded = load_with_type_cache(&filename, cache_dir.path());
+        let dep1 = loaded.interns.module_ids.get_id(&"Dep1".into()).unwrap();
+
+        assert!(loaded.sources.contains_key(&dep1));
+
+        !loaded.declarations_by_id[&dep1].is_empty()
+    };
+
+    assert!(canonicalized_dep1());
+    assert!(!canonicalized_dep1());
+}
+
+#[test]
+fn abilities_from_type_cache() {
+    let dir = roc_test_utils::TmpDir::new("tmp/abilities_from_type_cache");
+    let cache_dir = dir.path().join("cache");
+    let write = |name: &str, source: &str| {
+        std::fs::write(dir.path().join(name), source).unwrap();
+    };
+
+    write(
+        "Greeting.roc",
+        indoc!(
+            r#"
+            interface Greeting
+                exposes [Greet, greet, Person, person]
+                imports []
+
+            Greet has
+                greet : a -> Str | a has Greet
+
+            Person := Str has [Greet { greet: greetPerson }]
+
+            greetPerson = \@Person name -> "Hello, \(name)!"
+
+            person : Str -> Person
+            person = @Person
+            "#
+        ),
+    );
+    write(
+        "Main.roc",
+        indoc!(
+            r#"
+            interface Main
+                exposes [hello, greetAny]
+                imports [Greeting.{ Greet, greet }]
+
+            hello = greet (Greeting.person "Roc")
+
+            greetAny : a -> Str | a has Greet
+            greetAny = \x -> greet x
+            "#
+        ),
+    );
+
+    // Main changes between the loads, so that it is solved again with what the cache has of Greeting.
+    let main = dir.path().join("Main.roc");
+    for _ in 0..2 {
+        expect_types(
+            load_with_type_cache(&main, &cache_dir),
+            hashmap! {
+                "hello" => "Str",
+                "greetAny" => "a -> Str | a has Greeting.Greet",
+            },
+        );
+
+        std::fs::write(
+            &main,
+            std::fs::read_to_string(&main).unwrap() + "\n# changed\n",
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn type_cache_not_reused_after_import_changes() {
+    let dir = roc_test_utils::TmpDir::new("tmp/type_cache_not_reused_after_import_changes");
+    let cache_dir = dir.path().join("cache");
+    let write = |name: &str, source: &str| {
+        std::fs::write(dir.path().join(name), source).unwrap();
+    };
+
+    write(
+        "Middle.roc",
+        indoc!(
+            r#"
+            interface Middle
+                exposes [value]
+                imports [Dep]
+
+            value = Dep.value
+            "#
+        ),
+    );
+    write(
+        "Main.roc",
+        indoc!(
+            r#"
+            interface Main
+                exposes [answer]
+                imports [Middle]
+
+            answer = Middle.value
+            "#
+        ),
+    );
+
+    let main = dir.path().join("Main.roc");
+    for (dep_value, expected) in [("1", "Num *"), ("\"one\"", "Str")] {
+        write(
+            "Dep.roc",
+            &format!(
+                "interface Dep\n    exposes [value]\n    imports []\n\nvalue = {}\n",
+                dep_value
+            ),
+        );
+
+        // Middle did not change, but the module it imports did, so its entry must not be used.
+        expect_types(
+            load_with_type_cache(&main, &cache_dir),
+            hashmap! { "answer" => expected },
+        );
+    }
+}
+
+#[test]
+fn type_cache_ignores_damaged_entries() {
+    let cache_dir = roc_test_utils::TmpDir::new("tmp/type_cache_ignores_damaged_entries");
+    let filename = fixtures_dir()
+        .join("interface_with_deps")
+        .join("WithBuiltins.roc");
+
+    load_with_type_cache(&filename, cache_dir.path());
+
+    // Cut every entry short, as if the disk filled up while writing it.
+    let entries: Vec<_> = std::fs::read_dir(cache_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert!(!entries.is_empty());
+    for entry in entries {
+        let bytes = std::fs::read(&entry).unwrap();
+        std::fs::write(&entry, &bytes[..bytes.len() / 2]).unwrap();
+    }
+
+    expect_types(
+        load_with_type_cache(&filename, cache_dir.path()),
+        hashmap! {
+            "floatTest" => "F64",
+            "divisionFn" => "Float a, Float a -> Float a",
+            "x" => "Float *",
+            "divisionTest" => "F64",
+            "intTest" => "I64",
+            "constantNum" => "Num *",
+            "divisionTest" => "F64",
+            "divDep1ByDep2" => "Float a",
+            "fromDep2" => "Float *",
+        },
+    );
+}
+
+#[test]
+fn type_cache_evicts_oldest_entries() {
+    let cache_dir = roc_test_utils::TmpDir::new("tmp/type_cache_evicts_oldest_entries");
+    let filename = fixtures_dir().join("interface_with_deps").join("Dep2.roc");
+
+    load_with_type_cache(&filename, cache_dir.path());
+    let entries: Vec<_> = std::fs::read_dir(cache_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+
+    // An old entry that takes the cache past its maximum size, without taking up any disk space.
+    let old_entry = cache_dir.path().join("0000000000000000.dat");
+    std::fs::File::create(&old_entry)
+        .unwrap()
+        .set_len(1024 * 1024 * 1024)
+        .unwrap();
+
+    // Nothing is written when everything is found in the cache, so nothing is evicted either.
+    load_with_type_cache(&filename, cache_dir.path());
+    assert!(old_entry.exists());
+
+    for entry in entries {
+        std::fs::remove_file(entry).unwrap();
+    }
+
+    load_with_type_cache(&filename, cache_dir.path());
+
+    assert!(!old_entry.exists());
+    assert!(std::fs::read_dir(cache_dir.path())
+        .unwrap()
+        .next()
+        .is_some());
+}
+
 #[test]
 fn app_dep_types() {
     let subs_by_module = Default::default();
@@ -872,3 +1159,91 @@ fn issue_2863_module_type_does_not_exist() {
         Ok(_) => unreachable!("we expect failure here"),
     }
 }
+
+#[test]
+fn property_expects_typecheck() {
+    let modules = vec![(
+        "Properties",
+        indoc!(
+            r#"
+                interface Properties exposes [] imports []
+
+                expect \list, n -> List.len (List.takeFirst (List.prepend list 0u8) n) <= n
+
+                expect \{ x, y } -> Num.addWrap x y == Num.addWrap y x + 0u32
+
+                expect \shape ->
+                    when shape is
+                        Circle radius -> radius >= 0u8
+                        Square -> True
+                "#
+        ),
+    )];
+
+    assert!(multiple_modules("property_expects_typecheck", modules).is_ok());
+}
+
+#[test]
+fn property_expects_are_marked() {
+    use std::io::Write;
+
+    let arena = Bump::new();
+    let dir = roc_test_utils::TmpDir::new("tmp/property_expects_are_marked");
+    let file_path = dir.path().join("Properties.roc");
+    let mut file = std::fs::File::create(&file_path).unwrap();
+    writeln!(
+        file,
+        "{}",
+        indoc!(
+            r#"
+                interface Properties exposes [] imports []
+
+                expect 1 + 1 == 2u8
+
+                expect \n -> Num.addWrap n 0u8 == n
+                "#
+        )
+    )
+    .unwrap();
+
+    let load_start = LoadStart::from_path(&arena, file_path, RenderTarget::Generic).unwrap();
+    let load_config = LoadConfig {
+        target_info: TARGET_INFO,
+        render: RenderTarget::Generic,
+        threading: Threading::Single,
+        exec_mode: ExecutionMode::Test(Default::default()),
+        cache_dir: None,
+    };
+
+    let loaded = match roc_load_internal::file::load(
+        &arena,
+        load_start,
+        Default::default(),
+        Default::default(),
+        load_config,
+    ) {
+        Ok(LoadResult::Monomorphized(module)) => module,
+        Ok(LoadResult::TypeChecked(_)) => unreachable!("tests are monomorphized"),
+        Err(problem) => panic!("{:?}", problem),
+    };
+
+    let home = loaded.module_id;
+    let expects_in_home = loaded
+        .toplevel_expects
+        .keys()
+        .filter(|symbol| symbol.module_id() == home);
+    assert_eq!(expects_in_home.count(), 2);
+
+    // only the expect of a function is a property test
+    let line_info = LineInfo::new(&loaded.sources[&home].1);
+    let property_lines: Vec<_> = loaded
+        .property_expects
+        .iter()
+        .filter(|symbol| symbol.module_id() == home)
+        .map(|symbol| {
+            let region = loaded.toplevel_expects.get(symbol).unwrap();
+            line_info.convert_region(*region).start.line
+        })
+        .collect();
+    assert_eq!(property_lines, vec![4]);
+}
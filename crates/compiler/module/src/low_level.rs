@@ -103,7 +103,7 @@ pub enum LowLevel {
     And,
     Or,
     Not,
-    Hash,
+    HashBytes,
     PtrCast,
     RefCountInc,
     RefCountDec,
@@ -207,7 +207,6 @@ macro_rules! map_symbol_to_lowlevel {
                 LowLevel::NumDivUnchecked => unreachable!(),
 
                 // these are used internally and not tied to a symbol
-                LowLevel::Hash => unimplemented!(),
                 LowLevel::PtrCast => unimplemented!(),
                 LowLevel::RefCountInc => unimplemented!(),
                 LowLevel::RefCountDec => unimplemented!(),
@@ -311,5 +310,6 @@ map_symbol_to_lowlevel! {
     And <= BOOL_AND,
     Or <= BOOL_OR,
     Not <= BOOL_NOT,
+    HashBytes <= DICT_HASH_BYTES_LOWLEVEL,
     Unreachable <= LIST_UNREACHABLE,
 }
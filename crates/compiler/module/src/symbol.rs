@@ -50,6 +50,18 @@ const PRETTY_PRINT_DEBUG_SYMBOLS: bool = true;
 pub const DERIVABLE_ABILITIES: &[(Symbol, &[Symbol])] = &[
     (Symbol::ENCODE_ENCODING, &[Symbol::ENCODE_TO_ENCODER]),
     (Symbol::DECODE_DECODING, &[Symbol::DECODE_DECODER]),
+    (Symbol::HASH_HASH_ABILITY, &[Symbol::HASH_HASH]),
+    (
+        Symbol::ARBITRARY_ARBITRARY_ABILITY,
+        &[Symbol::ARBITRARY_ARBITRARY],
+    ),
+];
+
+/// Builtins that derived implementations refer to, but that their modules don't expose.
+pub const DERIVED_CODE_HELPERS: &[Symbol] = &[
+    Symbol::DECODE_MISSING_FIELD,
+    Symbol::DECODE_MISSING_PAYLOAD,
+    Symbol::DECODE_UNKNOWN_TAG,
 ];
 
 /// In Debug builds only, Symbol has a name() method that lets
@@ -360,6 +372,15 @@ impl ModuleId {
         (self.0.get() - 1) as usize
     }
 
+    pub const fn to_u32(self) -> u32 {
+        self.0.get()
+    }
+
+    /// The inverse of [`ModuleId::to_u32`], or `None` if the number can not be a module id.
+    pub fn from_u32(id: u32) -> Option<Self> {
+        NonZeroU32::new(id).map(ModuleId)
+    }
+
     #[cfg(any(debug_assertions, feature = "debug-symbols"))]
     pub fn register_debug_idents(self, ident_ids: &IdentIds) {
         let mut all = DEBUG_IDENT_IDS_BY_MODULE_ID.lock().expect("Failed to acquire lock for Debug interning into DEBUG_MODULE_ID_NAMES, presumably because a thread panicked.");
@@ -1342,6 +1363,7 @@ define_builtins! {
 
         15 DICT_WITH_CAPACITY: "withCapacity"
         16 DICT_CAPACITY: "capacity"
+        17 DICT_HASH_BYTES_LOWLEVEL: "hashBytesLowlevel"
     }
     9 SET: "Set" => {
         0 SET_SET: "Set" // the Set.Set type alias
@@ -1421,10 +1443,61 @@ define_builtins! {
         23 DECODE_DECODE_WITH: "decodeWith"
         24 DECODE_FROM_BYTES_PARTIAL: "fromBytesPartial"
         25 DECODE_FROM_BYTES: "fromBytes"
+        26 DECODE_RECORD: "record"
+        27 DECODE_TAG: "tag"
+        28 DECODE_DECODE_ERROR_INFO: "DecodeErrorInfo"
+        29 DECODE_DECODE_PATH_SEGMENT: "DecodePathSegment"
+        30 DECODE_MISSING_FIELD: "missingField"
+        31 DECODE_MISSING_PAYLOAD: "missingPayload"
+        32 DECODE_UNKNOWN_TAG: "unknownTag"
     }
     13 JSON: "Json" => {
         0 JSON_JSON: "Json"
     }
-
-    num_modules: 14 // Keep this count up to date by hand! (TODO: see the mut_map! macro for how we could determine this count correctly in the macro)
+    14 HASH: "Hash" => {
+        0 HASH_HASH_ABILITY: "Hash"
+        1 HASH_HASH: "hash"
+        2 HASH_HASHER: "Hasher"
+        3 HASH_ADD_BYTES: "addBytes"
+        4 HASH_ADD_U8: "addU8"
+        5 HASH_ADD_U16: "addU16"
+        6 HASH_ADD_U32: "addU32"
+        7 HASH_ADD_U64: "addU64"
+        8 HASH_ADD_U128: "addU128"
+        9 HASH_ADD_I8: "addI8"
+        10 HASH_ADD_I16: "addI16"
+        11 HASH_ADD_I32: "addI32"
+        12 HASH_ADD_I64: "addI64"
+        13 HASH_ADD_I128: "addI128"
+        14 HASH_COMPLETE: "complete"
+        15 HASH_HASH_STR_BYTES: "hashStrBytes"
+        16 HASH_HASH_LIST: "hashList"
+        17 HASH_HASH_NAT: "hashNat"
+    }
+    15 ARBITRARY: "Arbitrary" => {
+        0 ARBITRARY_ARBITRARY_ABILITY: "Arbitrary"
+        1 ARBITRARY_ARBITRARY: "arbitrary"
+        2 ARBITRARY_RNG: "Rng"
+        3 ARBITRARY_CHECK: "check"
+        4 ARBITRARY_DRAW: "draw"
+        5 ARBITRARY_CHOOSE_TAG: "chooseTag"
+        6 ARBITRARY_LIST: "list"
+        7 ARBITRARY_STR: "str"
+        8 ARBITRARY_U8: "u8"
+        9 ARBITRARY_U16: "u16"
+        10 ARBITRARY_U32: "u32"
+        11 ARBITRARY_U64: "u64"
+        12 ARBITRARY_U128: "u128"
+        13 ARBITRARY_I8: "i8"
+        14 ARBITRARY_I16: "i16"
+        15 ARBITRARY_I32: "i32"
+        16 ARBITRARY_I64: "i64"
+        17 ARBITRARY_I128: "i128"
+        18 ARBITRARY_NAT: "nat"
+        19 ARBITRARY_F32: "f32"
+        20 ARBITRARY_F64: "f64"
+        21 ARBITRARY_DEC: "dec"
+    }
+
+    num_modules: 16 // Keep this count up to date by hand! (TODO: see the mut_map! macro for how we could determine this count correctly in the macro)
 }
@@ -308,7 +308,7 @@ impl<'a> ParamMap<'a> {
                     stack.push(cont);
                 }
 
-                Expect { remainder, .. } => stack.push(remainder),
+                Expect { remainder, .. } | Coverage(_, remainder) => stack.push(remainder),
 
                 Switch {
                     branches,
@@ -816,7 +816,7 @@ impl<'a> BorrowInfState<'a> {
                 self.collect_stmt(param_map, default_branch.1);
             }
 
-            Expect { remainder, .. } => {
+            Expect { remainder, .. } | Coverage(_, remainder) => {
                 self.collect_stmt(param_map, remainder);
             }
 
@@ -926,7 +926,7 @@ pub fn lowlevel_borrow_signature(arena: &Bump, op: LowLevel) -> &[bool] {
         StrToUtf8 => arena.alloc_slice_copy(&[owned]),
         StrRepeat => arena.alloc_slice_copy(&[borrowed, irrelevant]),
         StrFromInt | StrFromFloat => arena.alloc_slice_copy(&[irrelevant]),
-        Hash => arena.alloc_slice_copy(&[borrowed, irrelevant]),
+        HashBytes => arena.alloc_slice_copy(&[irrelevant, borrowed]),
 
         ListIsUnique => arena.alloc_slice_copy(&[borrowed]),
 
@@ -987,7 +987,7 @@ fn call_info_stmt<'a>(arena: &'a Bump, stmt: &Stmt<'a>, info: &mut CallInfo<'a>)
                 stack.push(default_branch.1);
             }
 
-            Expect { remainder, .. } => stack.push(remainder),
+            Expect { remainder, .. } | Coverage(_, remainder) => stack.push(remainder),
 
             Refcounting(_, _) => unreachable!("these have not been introduced yet"),
 
@@ -2,6 +2,7 @@ use bumpalo::collections::vec::Vec;
 use bumpalo::Bump;
 use roc_module::low_level::LowLevel;
 use roc_module::symbol::{IdentIds, ModuleId, Symbol};
+use roc_region::all::Region;
 use roc_target::TargetInfo;
 
 use crate::ir::{
@@ -351,6 +352,8 @@ impl<'a> CodeGenHelp<'a> {
             is_self_recursive: SelfRecursive::NotSelfRecursive,
             must_own_arguments: false,
             host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+            region: Region::zero(),
+            statement_regions: &[],
         });
 
         proc_symbol
@@ -108,6 +108,8 @@ pub fn occurring_variables(stmt: &Stmt<'_>) -> (MutSet<Symbol>, MutSet<Symbol>)
                 stack.push(cont);
             }
 
+            Coverage(_, cont) => stack.push(cont),
+
             Expect {
                 condition,
                 remainder,
@@ -1199,6 +1201,12 @@ impl<'a> Context<'a> {
                 (expect, b_live_vars)
             }
 
+            Coverage(point, cont) => {
+                let (b, b_live_vars) = self.visit_stmt(codegen, cont);
+
+                (self.arena.alloc(Coverage(*point, b)), b_live_vars)
+            }
+
             RuntimeError(_) | Refcounting(_, _) => (stmt, MutSet::default()),
         }
     }
@@ -1302,6 +1310,8 @@ pub fn collect_stmt(
             collect_stmt(cont, jp_live_vars, vars)
         }
 
+        Coverage(_, cont) => collect_stmt(cont, jp_live_vars, vars),
+
         Expect {
             condition,
             remainder,
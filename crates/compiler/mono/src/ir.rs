@@ -10,7 +10,7 @@ use roc_builtins::bitcode::{FloatWidth, IntWidth};
 use roc_can::abilities::SpecializationId;
 use roc_can::expr::{AnnotatedMark, ClosureData, IntValue};
 use roc_can::module::ExposedByModule;
-use roc_collections::all::{default_hasher, BumpMap, BumpMapDefault, MutMap};
+use roc_collections::all::{default_hasher, BumpMap, BumpMapDefault, MutMap, MutSet};
 use roc_collections::VecMap;
 use roc_debug_flags::dbg_do;
 #[cfg(debug_assertions)]
@@ -208,6 +208,8 @@ pub struct PartialProc<'a> {
     pub body: roc_can::expr::Expr,
     pub body_var: Variable,
     pub is_self_recursive: bool,
+    /// Where the body of this function is defined in its module's source
+    pub region: Region,
 }
 
 impl<'a> PartialProc<'a> {
@@ -222,6 +224,7 @@ impl<'a> PartialProc<'a> {
         ret_var: Variable,
     ) -> PartialProc<'a> {
         let number_of_arguments = loc_args.len();
+        let region = loc_body.region;
 
         match patterns_to_when(env, loc_args, ret_var, loc_body) {
             Ok((_, pattern_symbols, body)) => {
@@ -237,6 +240,7 @@ impl<'a> PartialProc<'a> {
                     body: body.value,
                     body_var: ret_var,
                     is_self_recursive,
+                    region,
                 }
             }
 
@@ -254,6 +258,7 @@ impl<'a> PartialProc<'a> {
                     body: roc_can::expr::Expr::RuntimeError(error.value),
                     body_var: ret_var,
                     is_self_recursive: false,
+                    region,
                 }
             }
         }
@@ -312,6 +317,11 @@ pub struct Proc<'a> {
     pub is_self_recursive: SelfRecursive,
     pub must_own_arguments: bool,
     pub host_exposed_layouts: HostExposedLayouts<'a>,
+    /// Where this procedure is defined in its module's source, or `Region::zero()` for
+    /// procedures the compiler generated
+    pub region: Region,
+    /// Where the definitions in the body start in the source, by the first symbol their code binds
+    pub statement_regions: &'a [(Symbol, Region)],
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -1077,6 +1087,7 @@ impl<'a> Procs<'a> {
                                         annotation,
                                         pattern_symbols,
                                         captured_symbols,
+                                        region: body.region,
                                         body: body.value,
                                         body_var: ret_var,
                                         is_self_recursive,
@@ -1113,6 +1124,7 @@ impl<'a> Procs<'a> {
                                     annotation,
                                     pattern_symbols,
                                     captured_symbols,
+                                    region: body.region,
                                     body: body.value,
                                     body_var: ret_var,
                                     is_self_recursive,
@@ -1184,7 +1196,8 @@ impl<'a> Procs<'a> {
         }
 
         // If this is an imported symbol, let its home module make this specialization
-        if env.is_imported_symbol(name.name()) {
+        if env.is_imported_symbol(name.name()) || env.is_unloaded_derived_symbol(name.name(), self)
+        {
             add_needed_external(self, env, fn_var, name);
             return;
         }
@@ -1305,9 +1318,37 @@ pub struct Env<'a, 'i> {
     pub abilities: AbilitiesView<'i>,
     pub exposed_by_module: &'i ExposedByModule,
     pub derived_module: &'i SharedDerivedModule,
+    /// Whether to count how often each function and branch runs, for `roc test --coverage`
+    pub instrument_coverage: bool,
+    /// Where the definitions lowered so far start in the source, see `Proc::statement_regions`
+    pub statement_regions: std::vec::Vec<(Symbol, Region)>,
 }
 
 impl<'a, 'i> Env<'a, 'i> {
+    /// Counts the executions of `stmt` as executions of the code at `region`, if coverage is
+    /// instrumented. Code the compiler generated, and the code of builtins, isn't counted.
+    fn count_coverage(&self, region: Region, stmt: Stmt<'a>) -> Stmt<'a> {
+        if self.instrument_coverage && !self.home.is_builtin() && region != Region::zero() {
+            let point = CoveragePoint {
+                module_id: self.home,
+                region,
+            };
+
+            Stmt::Coverage(point, self.arena.alloc(stmt))
+        } else {
+            stmt
+        }
+    }
+
+    /// Remembers that `stmt`, the code of a definition, starts at `region` in the source.
+    fn record_statement_region(&mut self, region: Region, stmt: &Stmt<'a>) {
+        if let Stmt::Let(symbol, ..) = stmt {
+            if region != Region::zero() {
+                self.statement_regions.push((*symbol, region));
+            }
+        }
+    }
+
     pub fn unique_symbol(&mut self) -> Symbol {
         let ident_id = self.ident_ids.gen_unique();
 
@@ -1348,6 +1389,11 @@ impl<'a, 'i> Env<'a, 'i> {
         self.home == ModuleId::DERIVED_GEN
             && symbol.module_id() == ModuleId::DERIVED_SYNTH
             && !procs.partial_procs.contains_key(symbol)
+            && self
+                .derived_module
+                .lock()
+                .expect("derived module unavailable")
+                .is_derived_impl(symbol)
     }
 
     /// Unifies two variables and performs lambda set compaction.
@@ -1428,6 +1474,8 @@ pub enum Stmt<'a> {
     },
     Ret(Symbol),
     Refcounting(ModifyRc, &'a Stmt<'a>),
+    /// Count one more execution of the code at this point, for `roc test --coverage`
+    Coverage(CoveragePoint, &'a Stmt<'a>),
     Expect {
         condition: Symbol,
         region: Region,
@@ -1450,6 +1498,82 @@ pub enum Stmt<'a> {
     RuntimeError(&'a str),
 }
 
+/// A function or branch in the source whose executions are counted by `roc test --coverage`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CoveragePoint {
+    pub module_id: ModuleId,
+    pub region: Region,
+}
+
+impl CoveragePoint {
+    /// The name of the global that counts the executions of this point. Every specialization of
+    /// the same code shares the counter.
+    pub fn counter_name(&self) -> String {
+        format!(
+            "roc__coverage_{}_{}_{}",
+            self.module_id.to_u32(),
+            self.region.start().offset,
+            self.region.end().offset
+        )
+    }
+}
+
+/// The points instrumented by `roc test --coverage` in the given procedures, without duplicates.
+/// Also returns which of them are the entry points of functions, with the function's name.
+pub fn coverage_points<'a, 'b>(
+    procs: impl IntoIterator<Item = &'b Proc<'a>>,
+) -> (
+    std::vec::Vec<CoveragePoint>,
+    std::vec::Vec<(CoveragePoint, Symbol)>,
+)
+where
+    'a: 'b,
+{
+    let mut points = std::vec::Vec::new();
+    let mut functions = std::vec::Vec::new();
+    let mut seen = MutSet::default();
+
+    for proc in procs {
+        let mut stack = vec![&proc.body];
+
+        while let Some(stmt) = stack.pop() {
+            use Stmt::*;
+
+            match stmt {
+                Let(_, _, _, cont) | Refcounting(_, cont) => stack.push(cont),
+                Coverage(point, cont) => {
+                    // every specialization of a function has the same points
+                    if seen.insert(*point) {
+                        if point.region == proc.region {
+                            functions.push((*point, proc.name.name()));
+                        }
+                        points.push(*point);
+                    }
+                    stack.push(cont);
+                }
+                Expect { remainder, .. } => stack.push(remainder),
+                Switch {
+                    branches,
+                    default_branch,
+                    ..
+                } => {
+                    stack.extend(branches.iter().map(|(_, _, branch)| branch));
+                    stack.push(default_branch.1);
+                }
+                Join {
+                    body, remainder, ..
+                } => {
+                    stack.push(body);
+                    stack.push(remainder);
+                }
+                Ret(_) | Jump(_, _) | RuntimeError(_) => {}
+            }
+        }
+    }
+
+    (points, functions)
+}
+
 /// in the block below, symbol `scrutinee` is assumed be be of shape `tag_id`
 #[derive(Clone, Debug, PartialEq)]
 pub enum BranchInfo<'a> {
@@ -2012,6 +2136,11 @@ impl<'a> Stmt<'a> {
                 .append(alloc.hardline())
                 .append(cont.to_doc(alloc)),
 
+            Coverage(point, cont) => alloc
+                .text(format!("count {:?};", point.region))
+                .append(alloc.hardline())
+                .append(cont.to_doc(alloc)),
+
             Expect {
                 condition,
                 remainder,
@@ -2330,13 +2459,14 @@ fn from_can_let<'a>(
                 lower_rest!(variable, new_outer)
             }
             _ => {
+                let region = def.loc_pattern.region;
                 let rest = lower_rest!(variable, cont.value);
 
                 // Remove all the requested symbol specializations now, since this is the
                 // def site and hence we won't need them any higher up.
                 let mut needed_specializations = procs.symbol_specializations.remove(*symbol);
 
-                match needed_specializations.len() {
+                let stmt = match needed_specializations.len() {
                     0 => {
                         // We don't need any specializations, that means this symbol is never
                         // referenced.
@@ -2408,7 +2538,11 @@ fn from_can_let<'a>(
 
                         stmt
                     }
-                }
+                };
+
+                env.record_statement_region(region, &stmt);
+
+                stmt
             }
         };
     }
@@ -2905,6 +3039,8 @@ fn generate_runtime_error_function<'a>(
         is_self_recursive: SelfRecursive::NotSelfRecursive,
         must_own_arguments: false,
         host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+        region: Region::zero(),
+        statement_regions: &[],
     }
 }
 
@@ -2953,6 +3089,7 @@ fn specialize_external<'a>(
 
     let body = partial_proc.body.clone();
     let body_var = partial_proc.body_var;
+    let partial_proc_region = partial_proc.region;
 
     // determine the layout of aliases/rigids exposed to the host
     let host_exposed_layouts = if host_exposed_variables.is_empty() {
@@ -3015,8 +3152,12 @@ fn specialize_external<'a>(
                         closure_data_layout: None,
                         ret_layout: *return_layout,
                         is_self_recursive: SelfRecursive::NotSelfRecursive,
-                        must_own_arguments: false,
+                        // The host hands the arguments and the closure over to Roc,
+                        // so Roc must drop them no matter how they are used.
+                        must_own_arguments: true,
                         host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+                        region: Region::zero(),
+                        statement_regions: &[],
                     };
 
                     let top_level = ProcLayout::new(
@@ -3044,6 +3185,8 @@ fn specialize_external<'a>(
                         is_self_recursive: SelfRecursive::NotSelfRecursive,
                         must_own_arguments: false,
                         host_exposed_layouts: HostExposedLayouts::NotHostExposed,
+                        region: Region::zero(),
+                        statement_regions: &[],
                     };
 
                     let top_level =
@@ -3069,7 +3212,14 @@ fn specialize_external<'a>(
         }
     };
 
+    // Procedures specialized while lowering this body record their own statements.
+    let regions_start = env.statement_regions.len();
     let mut specialized_body = from_can(env, body_var, body, procs, layout_cache);
+    specialized_body = env.count_coverage(partial_proc_region, specialized_body);
+    let statement_regions = env
+        .arena
+        .alloc_slice_copy(&env.statement_regions[regions_start..]);
+    env.statement_regions.truncate(regions_start);
 
     match specialized {
         SpecializedLayout::FunctionPointerBody {
@@ -3105,6 +3255,8 @@ fn specialize_external<'a>(
                 is_self_recursive: recursivity,
                 must_own_arguments: false,
                 host_exposed_layouts,
+                region: partial_proc_region,
+                statement_regions,
             };
 
             Ok(proc)
@@ -3287,6 +3439,8 @@ fn specialize_external<'a>(
                 is_self_recursive: recursivity,
                 must_own_arguments: false,
                 host_exposed_layouts,
+                region: partial_proc_region,
+                statement_regions,
             };
 
             Ok(proc)
@@ -4093,6 +4247,7 @@ pub fn with_hole<'a>(
                             assigned,
                             terminator,
                         );
+                        stmt = env.count_coverage(final_else.region, stmt);
 
                         for (loc_cond, loc_then) in branches.into_iter().rev() {
                             let branching_symbol = env.unique_symbol();
@@ -4106,6 +4261,7 @@ pub fn with_hole<'a>(
                                 assigned,
                                 terminator,
                             );
+                            let then = env.count_coverage(loc_then.region, then);
 
                             stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -4138,6 +4294,7 @@ pub fn with_hole<'a>(
                             assigned_in_jump,
                             terminator,
                         );
+                        stmt = env.count_coverage(final_else.region, stmt);
 
                         for (loc_cond, loc_then) in branches.into_iter().rev() {
                             let branching_symbol = possible_reuse_symbol_or_specialize(
@@ -4157,6 +4314,7 @@ pub fn with_hole<'a>(
                                 assigned_in_jump,
                                 terminator,
                             );
+                            let then = env.count_coverage(loc_then.region, then);
 
                             stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -5263,6 +5421,7 @@ fn late_resolve_ability_specialization<'a>(
                         // The immediate is an ability member itself, so it must be resolved!
                         late_resolve_ability_specialization(env, imm, None, specialization_var)
                     }
+                    roc_derive_key::Derived::SingleLambdaSetImmediate(imm) => imm,
                     roc_derive_key::Derived::Key(derive_key) => {
                         let mut derived_module = env
                             .derived_module
@@ -5958,7 +6117,9 @@ pub fn from_can<'a>(
                 .from_var(env.arena, cond_var, env.subs)
                 .expect("invalid cond_layout");
 
+            let final_else_region = final_else.region;
             let mut stmt = from_can(env, branch_var, final_else.value, procs, layout_cache);
+            stmt = env.count_coverage(final_else_region, stmt);
 
             for (loc_cond, loc_then) in branches.into_iter().rev() {
                 let branching_symbol = possible_reuse_symbol_or_specialize(
@@ -5969,6 +6130,7 @@ pub fn from_can<'a>(
                     cond_var,
                 );
                 let then = from_can(env, branch_var, loc_then.value, procs, layout_cache);
+                let then = env.count_coverage(loc_then.region, then);
 
                 stmt = cond(env, branching_symbol, cond_layout, then, stmt, ret_layout);
 
@@ -6069,7 +6231,7 @@ fn to_opt_branches<'a>(
 ) -> std::vec::Vec<(
     Pattern<'a>,
     Option<Loc<roc_can::expr::Expr>>,
-    roc_can::expr::Expr,
+    Loc<roc_can::expr::Expr>,
 )> {
     debug_assert!(!branches.is_empty());
 
@@ -6104,7 +6266,8 @@ fn to_opt_branches<'a>(
                             loc_expr = Loc::at(region, new_expr);
                         }
 
-                        loc_expr
+                        // the branch is still where its body is, e.g. for coverage
+                        Loc::at(when_branch.value.region, loc_expr.value)
                     } else {
                         // This pattern is degenerate; when it's reached we must emit a runtime
                         // error.
@@ -6114,14 +6277,14 @@ fn to_opt_branches<'a>(
                     };
 
                     // TODO remove clone?
-                    opt_branches.push((mono_pattern, when_branch.guard.clone(), loc_expr.value));
+                    opt_branches.push((mono_pattern, when_branch.guard.clone(), loc_expr));
                 }
                 Err(runtime_error) => {
                     // TODO remove clone?
                     opt_branches.push((
                         Pattern::Underscore,
                         when_branch.guard.clone(),
-                        roc_can::expr::Expr::RuntimeError(runtime_error),
+                        Loc::at_zero(roc_can::expr::Expr::RuntimeError(runtime_error)),
                     ));
                 }
             }
@@ -6134,7 +6297,9 @@ fn to_opt_branches<'a>(
         opt_branches.push((
             Pattern::Underscore,
             None,
-            roc_can::expr::Expr::RuntimeError(roc_problem::can::RuntimeError::NonExhaustivePattern),
+            Loc::at_zero(roc_can::expr::Expr::RuntimeError(
+                roc_problem::can::RuntimeError::NonExhaustivePattern,
+            )),
         ));
     }
 
@@ -6175,17 +6340,26 @@ fn from_can_when<'a>(
     let arena = env.arena;
     let it = opt_branches
         .into_iter()
-        .map(|(pattern, opt_guard, can_expr)| {
+        .map(|(pattern, opt_guard, loc_expr)| {
             let branch_stmt = match join_point {
-                None => from_can(env, expr_var, can_expr, procs, layout_cache),
+                None => from_can(env, expr_var, loc_expr.value, procs, layout_cache),
                 Some(id) => {
                     let symbol = env.unique_symbol();
                     let arguments = bumpalo::vec![in env.arena; symbol].into_bump_slice();
                     let jump = env.arena.alloc(Stmt::Jump(id, arguments));
 
-                    with_hole(env, can_expr, expr_var, procs, layout_cache, symbol, jump)
+                    with_hole(
+                        env,
+                        loc_expr.value,
+                        expr_var,
+                        procs,
+                        layout_cache,
+                        symbol,
+                        jump,
+                    )
                 }
             };
+            let branch_stmt = env.count_coverage(loc_expr.region, branch_stmt);
 
             use crate::decision_tree::Guard;
             if let Some(loc_expr) = opt_guard {
@@ -6362,6 +6536,10 @@ fn substitute_in_stmt_help<'a>(
                 None => None,
             }
         }
+        Coverage(point, cont) => match substitute_in_stmt_help(arena, cont, subs) {
+            Some(cont) => Some(arena.alloc(Coverage(*point, cont))),
+            None => None,
+        },
 
         Expect {
             condition,
@@ -7084,7 +7262,9 @@ fn can_reuse_symbol<'a>(
 
     if arguments.contains(&symbol) {
         Value(symbol)
-    } else if env.is_imported_symbol(symbol) {
+    } else if env.is_imported_symbol(symbol) || env.is_unloaded_derived_symbol(symbol, procs) {
+        // Like imported symbols, derived implementations that haven't been loaded yet are
+        // specialized in a later pass.
         Imported(symbol)
     } else if procs.partial_procs.contains_key(symbol) {
         LocalFunction(symbol)
@@ -7256,7 +7436,10 @@ fn specialize_symbol<'a>(
     match procs.get_partial_proc(original) {
         None => {
             match arg_var {
-                Some(arg_var) if env.is_imported_symbol(original) => {
+                Some(arg_var)
+                    if env.is_imported_symbol(original)
+                        || env.is_unloaded_derived_symbol(original, procs) =>
+                {
                     let raw = match layout_cache.raw_from_var(env.arena, arg_var, env.subs) {
                         Ok(v) => v,
                         Err(e) => return_on_layout_error_help!(env, e, "specialize_symbol"),
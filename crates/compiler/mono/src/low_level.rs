@@ -128,5 +128,5 @@ enum FirstOrder {
     And,
     Or,
     Not,
-    Hash,
+    HashBytes,
 }
@@ -191,6 +191,17 @@ fn function_s<'a, 'i>(
             }
         }
 
+        Coverage(point, continuation) => {
+            let continuation: &Stmt = *continuation;
+            let new_continuation = function_s(env, w, c, continuation);
+
+            if std::ptr::eq(continuation, new_continuation) || continuation == new_continuation {
+                stmt
+            } else {
+                arena.alloc(Coverage(*point, new_continuation))
+            }
+        }
+
         Expect {
             condition,
             region,
@@ -412,6 +423,11 @@ fn function_d_main<'a, 'i>(
                 (arena.alloc(refcounting), found)
             }
         }
+        Coverage(point, continuation) => {
+            let (b, found) = function_d_main(env, x, c, continuation);
+
+            (arena.alloc(Coverage(*point, b)), found)
+        }
 
         Expect {
             condition,
@@ -598,6 +614,12 @@ fn function_r<'a, 'i>(env: &mut Env<'a, 'i>, stmt: &'a Stmt<'a>) -> &'a Stmt<'a>
             arena.alloc(Refcounting(*modify_rc, b))
         }
 
+        Coverage(point, continuation) => {
+            let b = function_r(env, continuation);
+
+            arena.alloc(Coverage(*point, b))
+        }
+
         Expect {
             condition,
             region,
@@ -648,6 +670,7 @@ fn has_live_var<'a>(jp_live_vars: &JPLiveVarMap, stmt: &'a Stmt<'a>, needle: Sym
         Refcounting(modify_rc, cont) => {
             modify_rc.get_symbol() == needle || has_live_var(jp_live_vars, cont, needle)
         }
+        Coverage(_, cont) => has_live_var(jp_live_vars, cont, needle),
         Expect {
             condition,
             remainder,
@@ -249,6 +249,18 @@ fn insert_jumps<'a>(
             }
         }
 
+        Coverage(point, cont) => match insert_jumps(
+            arena,
+            cont,
+            goal_id,
+            needle,
+            needle_arguments,
+            needle_result,
+        ) {
+            Some(cont) => Some(arena.alloc(Coverage(*point, cont))),
+            None => None,
+        },
+
         Expect {
             condition,
             region,
@@ -268,6 +268,17 @@ impl ObligationCache {
                 var,
             )),
 
+            Symbol::HASH_HASH_ABILITY => {
+                Some(DeriveHash::is_derivable(self, abilities_store, subs, var))
+            }
+
+            Symbol::ARBITRARY_ARBITRARY_ABILITY => Some(DeriveArbitrary::is_derivable(
+                self,
+                abilities_store,
+                subs,
+                var,
+            )),
+
             _ => None,
         };
 
@@ -408,8 +419,13 @@ impl ObligationCache {
 }
 
 #[inline(always)]
-#[rustfmt::skip]
 fn is_builtin_number_alias(symbol: Symbol) -> bool {
+    is_builtin_int_alias(symbol) || is_builtin_float_alias(symbol) || is_builtin_dec_alias(symbol)
+}
+
+#[inline(always)]
+#[rustfmt::skip]
+fn is_builtin_int_alias(symbol: Symbol) -> bool {
     matches!(symbol,
           Symbol::NUM_U8   | Symbol::NUM_UNSIGNED8
         | Symbol::NUM_U16  | Symbol::NUM_UNSIGNED16
@@ -422,12 +438,23 @@ fn is_builtin_number_alias(symbol: Symbol) -> bool {
         | Symbol::NUM_I64  | Symbol::NUM_SIGNED64
         | Symbol::NUM_I128 | Symbol::NUM_SIGNED128
         | Symbol::NUM_NAT  | Symbol::NUM_NATURAL
-        | Symbol::NUM_F32  | Symbol::NUM_BINARY32
+    )
+}
+
+#[inline(always)]
+#[rustfmt::skip]
+fn is_builtin_float_alias(symbol: Symbol) -> bool {
+    matches!(symbol,
+          Symbol::NUM_F32  | Symbol::NUM_BINARY32
         | Symbol::NUM_F64  | Symbol::NUM_BINARY64
-        | Symbol::NUM_DEC  | Symbol::NUM_DECIMAL,
     )
 }
 
+#[inline(always)]
+fn is_builtin_dec_alias(symbol: Symbol) -> bool {
+    matches!(symbol, Symbol::NUM_DEC | Symbol::NUM_DECIMAL)
+}
+
 enum DerivableError {
     NotDerivable(Variable),
 }
@@ -452,6 +479,12 @@ trait DerivableVisitor {
         Err(DerivableError::NotDerivable(var))
     }
 
+    /// An unbound extension variable of a record or tag union, like the `a` in `{ x : U8 }a`.
+    #[inline(always)]
+    fn visit_flex_ext(var: Variable) -> Result<(), DerivableError> {
+        Self::visit_flex(var)
+    }
+
     #[inline(always)]
     fn visit_flex_able(var: Variable, ability: Symbol) -> Result<(), DerivableError> {
         if ability != Self::ABILITY {
@@ -525,6 +558,12 @@ trait DerivableVisitor {
         Err(DerivableError::NotDerivable(var))
     }
 
+    /// A floating-point number whose precision may not be known yet, like the type of `1.0`.
+    #[inline(always)]
+    fn visit_floating_point(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
     #[inline(always)]
     fn is_derivable(
         obligation_cache: &mut ObligationCache,
@@ -541,6 +580,15 @@ trait DerivableVisitor {
             };
         }
 
+        macro_rules! push_ext {
+            ($ext:expr) => {
+                match subs.get_content_without_compacting($ext) {
+                    Content::FlexVar(_) => Self::visit_flex_ext($ext)?,
+                    _ => stack.push($ext),
+                }
+            };
+        }
+
         while let Some(var) = stack.pop() {
             if seen_recursion_vars.contains(&var) {
                 continue;
@@ -584,7 +632,7 @@ trait DerivableVisitor {
                         let descend = Self::visit_record(var)?;
                         if descend.0 {
                             push_var_slice!(fields.variables());
-                            stack.push(ext);
+                            push_ext!(ext);
                         }
                     }
                     TagUnion(tags, ext) => {
@@ -593,13 +641,13 @@ trait DerivableVisitor {
                             for i in tags.variables() {
                                 push_var_slice!(subs[i]);
                             }
-                            stack.push(ext);
+                            push_ext!(ext);
                         }
                     }
                     FunctionOrTagUnion(_tag_name, _fn_name, ext) => {
                         let descend = Self::visit_function_or_tag_union(var)?;
                         if descend.0 {
-                            stack.push(ext);
+                            push_ext!(ext);
                         }
                     }
                     RecursiveTagUnion(rec, tags, ext) => {
@@ -609,7 +657,7 @@ trait DerivableVisitor {
                             for i in tags.variables() {
                                 push_var_slice!(subs[i]);
                             }
-                            stack.push(ext);
+                            push_ext!(ext);
                         }
                     }
                     EmptyRecord => Self::visit_empty_record(var)?,
@@ -618,7 +666,7 @@ trait DerivableVisitor {
                     Erroneous(_) => return Err(NotDerivable(var)),
                 },
                 Alias(
-                    Symbol::NUM_NUM | Symbol::NUM_INTEGER | Symbol::NUM_FLOATINGPOINT,
+                    Symbol::NUM_NUM | Symbol::NUM_INTEGER,
                     _alias_variables,
                     real_var,
                     AliasKind::Opaque,
@@ -626,6 +674,12 @@ trait DerivableVisitor {
                     // Numbers: always decay until a ground is hit.
                     stack.push(real_var);
                 }
+                Alias(Symbol::NUM_FLOATINGPOINT, _alias_variables, real_var, AliasKind::Opaque) => {
+                    let descend = Self::visit_floating_point(var)?;
+                    if descend.0 {
+                        stack.push(real_var);
+                    }
+                }
                 Alias(opaque, _alias_variables, _real_var, AliasKind::Opaque) => {
                     if obligation_cache
                         .check_opaque_and_read(abilities_store, opaque, Self::ABILITY)
@@ -796,6 +850,158 @@ impl DerivableVisitor for DeriveDecoding {
     }
 }
 
+struct DeriveHash;
+impl DerivableVisitor for DeriveHash {
+    const ABILITY: Symbol = Symbol::HASH_HASH_ABILITY;
+
+    #[inline(always)]
+    fn is_derivable_builtin_opaque(symbol: Symbol) -> bool {
+        // Floats and decimals are not hashable, since equal values may have different bit
+        // representations (and NaN is not equal to itself).
+        is_builtin_int_alias(symbol)
+    }
+
+    #[inline(always)]
+    fn visit_recursion(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_apply(var: Variable, symbol: Symbol) -> Result<Descend, DerivableError> {
+        if matches!(symbol, Symbol::LIST_LIST | Symbol::STR_STR) {
+            Ok(Descend(true))
+        } else {
+            Err(DerivableError::NotDerivable(var))
+        }
+    }
+
+    #[inline(always)]
+    fn visit_record(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_recursive_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_function_or_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_empty_record(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_empty_tag_union(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_alias(var: Variable, symbol: Symbol) -> Result<Descend, DerivableError> {
+        if is_builtin_float_alias(symbol) || is_builtin_dec_alias(symbol) {
+            Err(DerivableError::NotDerivable(var))
+        } else if is_builtin_int_alias(symbol) {
+            Ok(Descend(false))
+        } else {
+            Ok(Descend(true))
+        }
+    }
+
+    #[inline(always)]
+    fn visit_ranged_number(_var: Variable, _range: NumericRange) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_floating_point(var: Variable) -> Result<Descend, DerivableError> {
+        Err(DerivableError::NotDerivable(var))
+    }
+}
+
+struct DeriveArbitrary;
+impl DerivableVisitor for DeriveArbitrary {
+    const ABILITY: Symbol = Symbol::ARBITRARY_ARBITRARY_ABILITY;
+
+    /// Records destructured by a pattern, like the argument of `\{ x, y } -> x == y`, are open;
+    /// only the fields and tags we know about are generated.
+    #[inline(always)]
+    fn visit_flex_ext(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn is_derivable_builtin_opaque(symbol: Symbol) -> bool {
+        is_builtin_number_alias(symbol)
+    }
+
+    #[inline(always)]
+    fn visit_recursion(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_apply(var: Variable, symbol: Symbol) -> Result<Descend, DerivableError> {
+        if matches!(symbol, Symbol::LIST_LIST | Symbol::STR_STR) {
+            Ok(Descend(true))
+        } else {
+            Err(DerivableError::NotDerivable(var))
+        }
+    }
+
+    #[inline(always)]
+    fn visit_record(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_recursive_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_function_or_tag_union(_var: Variable) -> Result<Descend, DerivableError> {
+        Ok(Descend(true))
+    }
+
+    #[inline(always)]
+    fn visit_empty_record(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_empty_tag_union(_var: Variable) -> Result<(), DerivableError> {
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn visit_alias(_var: Variable, symbol: Symbol) -> Result<Descend, DerivableError> {
+        if is_builtin_number_alias(symbol) {
+            Ok(Descend(false))
+        } else {
+            Ok(Descend(true))
+        }
+    }
+
+    #[inline(always)]
+    fn visit_ranged_number(_var: Variable, _range: NumericRange) -> Result<(), DerivableError> {
+        Ok(())
+    }
+}
+
 /// Determines what type implements an ability member of a specialized signature, given the
 /// [MustImplementAbility] constraints of the signature.
 pub fn type_implementing_specialization(
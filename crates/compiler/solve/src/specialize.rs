@@ -601,6 +601,7 @@ enum SpecializationTypeKey {
     Opaque(Symbol),
     Derived(DeriveKey),
     Immediate(Symbol),
+    SingleLambdaSetImmediate(Symbol),
 }
 
 enum SpecializeDecision {
@@ -623,22 +624,33 @@ fn make_specialization_decision<P: Phase>(
     use SpecializationTypeKey::*;
     match subs.get_content_without_compacting(var) {
         Alias(opaque, _, _, AliasKind::Opaque) if opaque.module_id() != ModuleId::NUM => {
-            if P::IS_LATE {
+            let impl_key = ImplKey {
+                opaque: *opaque,
+                ability_member,
+            };
+            let is_derived =
+                phase.with_module_abilities_store(opaque.module_id(), |abilities_store| {
+                    matches!(
+                        abilities_store.get_implementation(impl_key),
+                        Some(MemberImpl::Derived)
+                    )
+                });
+
+            if is_derived {
+                // A derived implementation for an opaque type is the derived implementation of
+                // the type it wraps.
+                make_derived_specialization_decision(subs, var, ability_member)
+            } else if P::IS_LATE {
                 SpecializeDecision::Specialize(Opaque(*opaque))
             } else {
                 // Solving within a module.
                 phase.with_module_abilities_store(opaque.module_id(), |abilities_store| {
-                    let impl_key = ImplKey {
-                        opaque: *opaque,
-                        ability_member,
-                    };
                     match abilities_store.get_implementation(impl_key) {
                         None => {
                             // Doesn't specialize; an error will already be reported for this.
                             SpecializeDecision::Drop
                         }
                         Some(MemberImpl::Error | MemberImpl::Derived) => {
-                            // TODO: probably not right, we may want to choose a derive decision!
                             SpecializeDecision::Specialize(Opaque(*opaque))
                         }
                         Some(MemberImpl::Impl(specialization_symbol)) => {
@@ -655,32 +667,8 @@ fn make_specialization_decision<P: Phase>(
             }
         }
         Structure(_) | Alias(_, _, _, _) => {
-            let builtin = match ability_member.try_into() {
-                Ok(builtin) => builtin,
-                Err(_) => return SpecializeDecision::Drop,
-            };
-
             // This is a structural type, find the derived ability function it should use.
-            match roc_derive_key::Derived::builtin(builtin, subs, var) {
-                Ok(derived) => match derived {
-                    roc_derive_key::Derived::Immediate(imm) => {
-                        SpecializeDecision::Specialize(Immediate(imm))
-                    }
-                    roc_derive_key::Derived::Key(derive_key) => {
-                        SpecializeDecision::Specialize(Derived(derive_key))
-                    }
-                },
-                Err(DeriveError::UnboundVar) => {
-                    // not specialized yet, but that also means that it can't possibly be derivable
-                    // at this point?
-                    // TODO: is this right? Revisit if it causes us problems in the future.
-                    SpecializeDecision::Drop
-                }
-                Err(DeriveError::Underivable) => {
-                    // we should have reported an error for this; drop the lambda set.
-                    SpecializeDecision::Drop
-                }
-            }
+            make_derived_specialization_decision(subs, var, ability_member)
         }
         Error => SpecializeDecision::Drop,
         FlexAbleVar(_, _)
@@ -695,6 +683,43 @@ fn make_specialization_decision<P: Phase>(
     }
 }
 
+fn make_derived_specialization_decision(
+    subs: &Subs,
+    var: Variable,
+    ability_member: Symbol,
+) -> SpecializeDecision {
+    use SpecializationTypeKey::*;
+
+    let builtin = match ability_member.try_into() {
+        Ok(builtin) => builtin,
+        Err(_) => return SpecializeDecision::Drop,
+    };
+
+    match roc_derive_key::Derived::builtin(builtin, subs, var) {
+        Ok(derived) => match derived {
+            roc_derive_key::Derived::Immediate(imm) => {
+                SpecializeDecision::Specialize(Immediate(imm))
+            }
+            roc_derive_key::Derived::SingleLambdaSetImmediate(imm) => {
+                SpecializeDecision::Specialize(SingleLambdaSetImmediate(imm))
+            }
+            roc_derive_key::Derived::Key(derive_key) => {
+                SpecializeDecision::Specialize(Derived(derive_key))
+            }
+        },
+        Err(DeriveError::UnboundVar) => {
+            // not specialized yet, but that also means that it can't possibly be derivable
+            // at this point?
+            // TODO: is this right? Revisit if it causes us problems in the future.
+            SpecializeDecision::Drop
+        }
+        Err(DeriveError::Underivable) => {
+            // we should have reported an error for this; drop the lambda set.
+            SpecializeDecision::Drop
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn get_specialization_lambda_set_ambient_function<P: Phase>(
     subs: &mut Subs,
@@ -785,5 +810,28 @@ fn get_specialization_lambda_set_ambient_function<P: Phase>(
 
             Ok(immediate_lambda_set_at_region)
         }
+
+        SpecializationTypeKey::SingleLambdaSetImmediate(imm) => {
+            let module_id = imm.module_id();
+            debug_assert!(module_id.is_builtin());
+
+            let module_types = &derived_env
+                .exposed_types
+                .get(&module_id)
+                .unwrap()
+                .exposed_types_storage_subs;
+
+            // The immediate has only one lambda set, so the region must point to it, and the
+            // type of the function itself is the ambient function of that lambda set.
+            debug_assert_eq!(lset_region, 1);
+            let storage_var = module_types.stored_vars_by_symbol.get(&imm).unwrap();
+            let imported = module_types
+                .storage_subs
+                .export_variable_to(subs, *storage_var);
+
+            roc_types::subs::instantiate_rigids(subs, imported.variable);
+
+            Ok(imported.variable)
+        }
     }
 }
@@ -371,6 +371,9 @@ mod solve_expr {
 
         let known_specializations = abilities_store.iter_declared_implementations().filter_map(
             |(impl_key, member_impl)| match member_impl {
+                // Builtin opaques (like the hasher `Dict` uses) may implement abilities whose
+                // members this module never sees; they are not what these tests are about.
+                _ if impl_key.opaque.module_id().is_builtin() => None,
                 MemberImpl::Impl(impl_symbol) => {
                     let specialization = abilities_store.specialization_info(*impl_symbol).expect(
                         "declared implementations should be resolved conclusively after solving",
@@ -3463,7 +3466,7 @@ mod solve_expr {
                 Dict.insert
                 "#
             ),
-            "Dict k v, k, v -> Dict k v",
+            "Dict k v, k, v -> Dict k v | k has Hash",
         );
     }
 
@@ -3724,7 +3727,7 @@ mod solve_expr {
         infer_eq_without_problem(
             indoc!(
                 r#"
-                reconstructPath : Dict position position, position -> List position
+                reconstructPath : Dict position position, position -> List position | position has Hash.Hash
                 reconstructPath = \cameFrom, goal ->
                     when Dict.get cameFrom goal is
                         Err KeyNotFound ->
@@ -3736,7 +3739,7 @@ mod solve_expr {
                 reconstructPath
                 "#
             ),
-            "Dict position position, position -> List position",
+            "Dict position position, position -> List position | position has Hash",
         );
     }
 
@@ -6143,6 +6146,62 @@ mod solve_expr {
         )
     }
 
+    #[test]
+    fn ability_constrained_in_non_member_check_through_pattern() {
+        infer_eq_without_problem(
+            indoc!(
+                r#"
+                app "test" provides [hashFirst] to "./platform"
+
+                Hash has
+                    hash : a -> U64 | a has Hash
+
+                hashFirst : [Pair a a] -> U64 | a has Hash
+                hashFirst = \pair ->
+                    when pair is
+                        Pair x _ -> hash x
+                "#
+            ),
+            "[Pair a a] -> U64 | a has Hash",
+        )
+    }
+
+    #[test]
+    fn ability_constrained_in_non_member_check_through_record() {
+        infer_eq_without_problem(
+            indoc!(
+                r#"
+                app "test" provides [hashField] to "./platform"
+
+                Hash has
+                    hash : a -> U64 | a has Hash
+
+                hashField : { field : a } -> U64 | a has Hash
+                hashField = \{ field } -> hash field
+                "#
+            ),
+            "{ field : a } -> U64 | a has Hash",
+        )
+    }
+
+    #[test]
+    fn ability_constrained_in_non_member_check_through_list() {
+        infer_eq_without_problem(
+            indoc!(
+                r#"
+                app "test" provides [hashAll] to "./platform"
+
+                Hash has
+                    hash : a -> U64 | a has Hash
+
+                hashAll : List a -> List U64 | a has Hash
+                hashAll = \list -> List.map list hash
+                "#
+            ),
+            "List a -> List U64 | a has Hash",
+        )
+    }
+
     #[test]
     fn ability_constrained_in_non_member_infer() {
         infer_eq_without_problem(
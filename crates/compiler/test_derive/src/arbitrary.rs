@@ -0,0 +1,257 @@
+#![cfg(test)]
+// Even with #[allow(non_snake_case)] on individual idents, rust-analyzer issues diagnostics.
+// See https://github.com/rust-lang/rust-analyzer/issues/6541.
+// For the `v!` macro we use uppercase variables when constructing tag unions.
+#![allow(non_snake_case)]
+
+use crate::{test_hash_eq, test_hash_neq, util::derive_test, v};
+use insta::assert_snapshot;
+use roc_derive_key::{
+    arbitrary::FlatArbitraryKey, DeriveBuiltin::Arbitrary, DeriveError, DeriveKey, Derived,
+};
+use roc_module::symbol::Symbol;
+use roc_types::subs::{Subs, Variable};
+
+// {{{ hash tests
+
+test_hash_eq! {
+    Arbitrary,
+
+    same_record:
+        v!({ a: v!(U8), }), v!({ a: v!(U8), })
+    same_record_fields_diff_types:
+        v!({ a: v!(U8), }), v!({ a: v!(STR), })
+    same_record_fields_any_order:
+        v!({ a: v!(U8), b: v!(U8), c: v!(U8), }),
+        v!({ c: v!(U8), a: v!(U8), b: v!(U8), })
+    explicit_empty_record_and_implicit_empty_record:
+        v!(EMPTY_RECORD), v!({})
+
+    same_tag_union:
+        v!([ A v!(U8) v!(STR), B v!(STR) ]), v!([ A v!(U8) v!(STR), B v!(STR) ])
+    same_tag_union_tags_diff_types:
+        v!([ A v!(U8) v!(U8), B v!(U8) ]), v!([ A v!(STR) v!(STR), B v!(STR) ])
+    same_tag_union_tags_any_order:
+        v!([ A v!(U8) v!(U8), B v!(U8), C ]), v!([ C, B v!(STR), A v!(STR) v!(STR) ])
+
+    same_recursive_tag_union:
+        v!([ Nil, Cons v!(^lst)] as lst), v!([ Nil, Cons v!(^lst)] as lst)
+    same_tag_union_and_recursive_tag_union_fields:
+        v!([ Nil, Cons v!(STR)]), v!([ Nil, Cons v!(^lst)] as lst)
+
+    alias_eq_real_type:
+        v!(Symbol::BOOL_BOOL => v!([ True, False ])), v!([False, True])
+    opaque_eq_real_type:
+        v!(@Symbol::BOOL_BOOL => v!([ True, False ])), v!([False, True])
+}
+
+test_hash_neq! {
+    Arbitrary,
+
+    different_record_fields:
+        v!({ a: v!(U8), }), v!({ b: v!(U8), })
+    record_empty_vs_nonempty:
+        v!(EMPTY_RECORD), v!({ a: v!(U8), })
+
+    different_tag_union_tags:
+        v!([ A v!(U8) ]), v!([ B v!(U8) ])
+    different_recursive_tag_union_tags:
+        v!([ Nil, Cons v!(^lst) ] as lst), v!([ Nil, Next v!(^lst) ] as lst)
+}
+
+// }}} hash tests
+
+// {{{ deriver tests
+
+#[test]
+fn generators() {
+    fn check_generator<S>(synth: S, generator: Symbol)
+    where
+        S: FnOnce(&mut Subs) -> Variable,
+    {
+        let mut subs = Subs::new();
+        let var = synth(&mut subs);
+
+        assert_eq!(
+            Derived::builtin(Arbitrary, &subs, var),
+            Ok(Derived::Key(DeriveKey::Arbitrary(
+                FlatArbitraryKey::Generator(generator)
+            )))
+        );
+    }
+
+    check_generator(v!(U8), Symbol::ARBITRARY_U8);
+    check_generator(v!(U16), Symbol::ARBITRARY_U16);
+    check_generator(v!(U32), Symbol::ARBITRARY_U32);
+    check_generator(v!(U64), Symbol::ARBITRARY_U64);
+    check_generator(v!(U128), Symbol::ARBITRARY_U128);
+    check_generator(v!(I8), Symbol::ARBITRARY_I8);
+    check_generator(v!(I16), Symbol::ARBITRARY_I16);
+    check_generator(v!(I32), Symbol::ARBITRARY_I32);
+    check_generator(v!(I64), Symbol::ARBITRARY_I64);
+    check_generator(v!(I128), Symbol::ARBITRARY_I128);
+    check_generator(v!(NAT), Symbol::ARBITRARY_NAT);
+    check_generator(v!(F32), Symbol::ARBITRARY_F32);
+    check_generator(v!(F64), Symbol::ARBITRARY_F64);
+    check_generator(v!(DEC), Symbol::ARBITRARY_DEC);
+    check_generator(v!(STR), Symbol::ARBITRARY_STR);
+    check_generator(v!(Symbol::LIST_LIST v!(U8)), Symbol::ARBITRARY_LIST);
+}
+
+#[test]
+fn generator_u8() {
+    derive_test(Arbitrary, v!(U8), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for U8
+        # Rng -[[arbitrary_u8(0)]]-> { rng : Rng, value : U8 }
+        # { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 } -[[arbitrary_u8(0)]]-> { rng : { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 }, value : U8 }
+        # Specialization lambda sets:
+        #   @<1>: [[arbitrary_u8(0)]]
+        #Derived.arbitrary_u8 = \#Derived.rng -> Arbitrary.u8 #Derived.rng
+        "###
+        )
+    })
+}
+
+#[test]
+fn generator_list() {
+    derive_test(Arbitrary, v!(Symbol::LIST_LIST v!(U8)), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for List U8
+        # Rng -[[arbitrary_list(0)]]-> { rng : Rng, value : List a } | a has Arbitrary
+        # { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 } -[[arbitrary_list(0)]]-> { rng : { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 }, value : List a } | a has Arbitrary
+        # Specialization lambda sets:
+        #   @<1>: [[arbitrary_list(0)]]
+        #Derived.arbitrary_list = \#Derived.rng -> Arbitrary.list #Derived.rng
+        "###
+        )
+    })
+}
+
+#[test]
+fn empty_tag_union_is_underivable() {
+    let mut subs = Subs::new();
+    let var: Variable = v!(EMPTY_TAG_UNION)(&mut subs);
+
+    assert_eq!(
+        Derived::builtin(Arbitrary, &subs, var),
+        Err(DeriveError::Underivable)
+    );
+}
+
+#[test]
+fn empty_record() {
+    derive_test(Arbitrary, v!(EMPTY_RECORD), |golden| {
+        assert_snapshot!(golden, @r###"
+            # derived for {}
+            # Rng -[[arbitrary_{}(0)]]-> { rng : Rng, value : {} }
+            # { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 } -[[arbitrary_{}(0)]]-> { rng : { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 }, value : {} }
+            # Specialization lambda sets:
+            #   @<1>: [[arbitrary_{}(0)]]
+            #Derived.arbitrary_{} = \#Derived.rng -> { value: {}, rng: #Derived.rng, }
+        "###
+        )
+    })
+}
+
+#[test]
+fn record_2() {
+    derive_test(Arbitrary, v!({ a: v!(U8), b: v!(STR), }), |golden| {
+        assert_snapshot!(golden, @r###"
+            # derived for { a : U8, b : Str }
+            # Rng -[[arbitrary_{a,b}(0)]]-> { rng : Rng, value : { a : a, b : a1 } } | a has Arbitrary, a1 has Arbitrary
+            # { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 } -[[arbitrary_{a,b}(0)]]-> { rng : { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 }, value : { a : a, b : a1 } } | a has Arbitrary, a1 has Arbitrary
+            # Specialization lambda sets:
+            #   @<1>: [[arbitrary_{a,b}(0)]]
+            #Derived.arbitrary_{a,b} =
+              \#Derived.rng ->
+                #Derived.a = Arbitrary.arbitrary #Derived.rng
+                #Derived.b = Arbitrary.arbitrary #Derived.a.rng
+                { value: { b: #Derived.b.value, a: #Derived.a.value, }, rng: #Derived.b.rng,
+                }
+        "###
+        )
+    })
+}
+
+#[test]
+fn tag_one_label_two_args() {
+    derive_test(Arbitrary, v!([ A v!(U8) v!(STR) ]), |golden| {
+        assert_snapshot!(golden, @r###"
+            # derived for [A U8 Str]
+            # Rng -[[arbitrary_[A 2](0)]]-> { rng : Rng, value : [A a a1] } | a has Arbitrary, a1 has Arbitrary
+            # { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 } -[[arbitrary_[A 2](0)]]-> { rng : { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 }, value : [A a a1] } | a has Arbitrary, a1 has Arbitrary
+            # Specialization lambda sets:
+            #   @<1>: [[arbitrary_[A 2](0)]]
+            #Derived.arbitrary_[A 2] =
+              \#Derived.rng ->
+                #Derived.x1 = Arbitrary.arbitrary #Derived.rng
+                #Derived.x2 = Arbitrary.arbitrary #Derived.x1.rng
+                { value: A #Derived.x1.value #Derived.x2.value, rng: #Derived.x2.rng, }
+        "###
+        )
+    })
+}
+
+#[test]
+fn tag_two_labels() {
+    derive_test(
+        Arbitrary,
+        v!([ A v!(U8) v!(STR) v!(U16), B v!(STR) ]),
+        |golden| {
+            assert_snapshot!(golden, @r###"
+                # derived for [A U8 Str U16, B Str]
+                # Rng -[[arbitrary_[A 3,B 1](0)]]-> { rng : Rng, value : [A a a1 a2, B a3] } | a has Arbitrary, a1 has Arbitrary, a2 has Arbitrary, a3 has Arbitrary
+                # { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 } -[[arbitrary_[A 3,B 1](0)]]-> { rng : { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 }, value : [A a a1 a2, B a3] } | a has Arbitrary, a1 has Arbitrary, a2 has Arbitrary, a3 has Arbitrary
+                # Specialization lambda sets:
+                #   @<1>: [[arbitrary_[A 3,B 1](0)]]
+                #Derived.arbitrary_[A 3,B 1] =
+                  \#Derived.rng ->
+                    #Derived.tag = Arbitrary.chooseTag #Derived.rng 2
+                    when #Derived.tag.value is
+                      0 ->
+                        #Derived.x1 = Arbitrary.arbitrary #Derived.tag.rng
+                        { value: B #Derived.x1.value, rng: #Derived.x1.rng, }
+                      _ ->
+                        #Derived.x12 = Arbitrary.arbitrary #Derived.tag.rng
+                        #Derived.x2 = Arbitrary.arbitrary #Derived.x12.rng
+                        #Derived.x3 = Arbitrary.arbitrary #Derived.x2.rng
+                        {
+                          value: A #Derived.x12.value #Derived.x2.value #Derived.x3.value,
+                          rng: #Derived.x3.rng,
+                        }
+            "###
+            )
+        },
+    )
+}
+
+#[test]
+fn recursive_tag_union() {
+    derive_test(
+        Arbitrary,
+        v!([ Nil, Cons v!(U8) v!(^lst) ] as lst),
+        |golden| {
+            assert_snapshot!(golden, @r###"
+            # derived for [Cons U8 $rec, Nil] as $rec
+            # Rng -[[arbitrary_[Cons 2,Nil 0](0)]]-> { rng : Rng, value : [Cons a a1, Nil] } | a has Arbitrary, a1 has Arbitrary
+            # { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 } -[[arbitrary_[Cons 2,Nil 0](0)]]-> { rng : { budget : Nat, choices : List U64, random : [False, True], replay : List U64, state : U64 }, value : [Cons a a1, Nil] } | a has Arbitrary, a1 has Arbitrary
+            # Specialization lambda sets:
+            #   @<1>: [[arbitrary_[Cons 2,Nil 0](0)]]
+            #Derived.arbitrary_[Cons 2,Nil 0] =
+              \#Derived.rng ->
+                #Derived.tag = Arbitrary.chooseTag #Derived.rng 2
+                when #Derived.tag.value is
+                  0 -> { value: Nil, rng: #Derived.tag.rng, }
+                  _ ->
+                    #Derived.x1 = Arbitrary.arbitrary #Derived.tag.rng
+                    #Derived.x2 = Arbitrary.arbitrary #Derived.x1.rng
+                    { value: Cons #Derived.x1.value #Derived.x2.value, rng: #Derived.x2.rng,
+                    }
+        "###
+            )
+        },
+    )
+}
+
+// }}} deriver tests
@@ -5,6 +5,7 @@
 #![allow(non_snake_case)]
 
 use crate::{
+    test_hash_eq, test_hash_neq,
     util::{check_immediate, derive_test},
     v,
 };
@@ -14,6 +15,54 @@ use roc_types::subs::Variable;
 
 use roc_derive_key::DeriveBuiltin::Decoder;
 
+// {{{ hash tests
+
+test_hash_eq! {
+    Decoder,
+
+    same_record:
+        v!({ a: v!(U8), }), v!({ a: v!(U8), })
+    same_record_fields_diff_types:
+        v!({ a: v!(U8), }), v!({ a: v!(STR), })
+    same_record_fields_any_order:
+        v!({ a: v!(U8), b: v!(U8), c: v!(U8), }),
+        v!({ c: v!(U8), a: v!(U8), b: v!(U8), })
+    explicit_empty_record_and_implicit_empty_record:
+        v!(EMPTY_RECORD), v!({})
+
+    same_tag_union:
+        v!([ A v!(U8) v!(STR), B v!(STR) ]), v!([ A v!(U8) v!(STR), B v!(STR) ])
+    same_tag_union_tags_diff_types:
+        v!([ A v!(U8) v!(U8), B v!(U8) ]), v!([ A v!(STR) v!(STR), B v!(STR) ])
+    same_tag_union_tags_any_order:
+        v!([ A v!(U8) v!(U8), B v!(U8), C ]), v!([ C, B v!(STR), A v!(STR) v!(STR) ])
+    explicit_empty_tag_union_and_implicit_empty_tag_union:
+        v!(EMPTY_TAG_UNION), v!([])
+
+    list_list_diff_types:
+        v!(Symbol::LIST_LIST v!(STR)), v!(Symbol::LIST_LIST v!(U8))
+}
+
+test_hash_neq! {
+    Decoder,
+
+    different_record_fields:
+        v!({ a: v!(U8), }), v!({ b: v!(U8), })
+    record_empty_vs_nonempty:
+        v!(EMPTY_RECORD), v!({ a: v!(U8), })
+
+    different_tag_union_tags:
+        v!([ A v!(U8) ]), v!([ B v!(U8) ])
+    same_tag_union_tags_diff_arity:
+        v!([ A v!(U8) ]), v!([ A v!(U8) v!(U8) ])
+    tag_union_empty_vs_nonempty:
+        v!(EMPTY_TAG_UNION), v!([ B v!(U8) ])
+}
+
+// }}} hash tests
+
+// {{{ deriver tests
+
 #[test]
 fn immediates() {
     check_immediate(Decoder, v!(U8), Symbol::DECODE_U8);
@@ -38,7 +87,7 @@ fn list() {
         assert_snapshot!(golden, @r###"
         # derived for List Str
         # Decoder (List val) fmt | fmt has DecoderFormatting, val has Decoding
-        # List U8, fmt -[[custom(3)]]-> { rest : List U8, result : [Err [TooShort], Ok (List val)] } | fmt has DecoderFormatting, val has Decoding
+        # List U8, fmt -[[custom(3)]]-> { rest : List U8, result : [Err [TooShort { expected : Str, found : Str, offset : Nat, path : List [Field Str, Index Nat] }, Unexpected { expected : Str, found : Str, offset : Nat, path : List [Field Str, Index Nat] }], Ok (List val)] } | fmt has DecoderFormatting, val has Decoding
         # Specialization lambda sets:
         #   @<1>: [[custom(3)]]
         #Derived.decoder_list =
@@ -49,3 +98,161 @@ fn list() {
         )
     })
 }
+
+#[test]
+fn record_2() {
+    derive_test(Decoder, v!({a: v!(U8), b: v!(STR),}), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for { a : U8, b : Str }
+        # Decoder { a : val, b : val1 } fmt | fmt has DecoderFormatting, val has Decoding, val1 has Decoding
+        # List U8, fmt -[[custom3(27)]]-> { rest : List U8, result : [Err [TooShort { expected : Str, found : Str, offset : Nat, path : List [Field Str, Index Nat] }, Unexpected { expected : Str, found : Str, offset : Nat, path : List [Field Str, Index Nat] }], Ok { a : val, b : val1 }] } | fmt has DecoderFormatting, val has Decoding, val1 has Decoding
+        # Specialization lambda sets:
+        #   @<1>: [[custom3(27)]]
+        #Derived.decoder_{a,b} =
+          Decode.custom
+            \#Derived.bytes, #Derived.fmt ->
+              Decode.decodeWith
+                #Derived.bytes
+                (Decode.record
+                  { b: Err NoField, a: Err NoField, }
+                  \#Derived.state, #Derived.field ->
+                    when #Derived.field is
+                      "a" ->
+                        Keep (Decode.custom
+                          \#Derived.bytes2, #Derived.fmt2 ->
+                            when Decode.decodeWith
+                                #Derived.bytes2
+                                Decode.decoder
+                                #Derived.fmt2 is
+                              {rest, result} ->
+                                {
+                                  result: when #Derived.result is
+                                      Ok #Derived.val ->
+                                        Ok { #Derived.state & a: Ok #Derived.val, }
+                                      Err #Derived.e -> Err #Derived.e,
+                                  rest: #Derived.rest,
+                                })
+                      "b" ->
+                        Keep (Decode.custom
+                          \#Derived.bytes3, #Derived.fmt3 ->
+                            when Decode.decodeWith
+                                #Derived.bytes3
+                                Decode.decoder
+                                #Derived.fmt3 is
+                              {rest, result} ->
+                                {
+                                  result: when #Derived.result2 is
+                                      Ok #Derived.val2 ->
+                                        Ok { #Derived.state & b: Ok #Derived.val2, }
+                                      Err #Derived.e2 -> Err #Derived.e2,
+                                  rest: #Derived.rest2,
+                                })
+                      _ -> Skip
+                  \#Derived.state2 ->
+                    when #Derived.state2 is
+                      {a: Ok #Derived.a, b: Ok #Derived.b} ->
+                        Ok { b: #Derived.b, a: #Derived.a, }
+                      {a: Err _} -> Err (Decode.missingField "a")
+                      _ -> Err (Decode.missingField "b"))
+                #Derived.fmt
+        "###
+        )
+    })
+}
+
+#[test]
+fn tag_union_2() {
+    derive_test(Decoder, v!([A v!(U8) v!(STR), B v!(STR)]), |golden| {
+        assert_snapshot!(golden, @r###"
+        # derived for [A U8 Str, B Str]
+        # Decoder [A val val1, B val1] fmt | fmt has DecoderFormatting, val has Decoding, val1 has Decoding
+        # List U8, fmt -[[custom4(38)]]-> { rest : List U8, result : [Err [TooShort { expected : Str, found : Str, offset : Nat, path : List [Field Str, Index Nat] }, Unexpected { expected : Str, found : Str, offset : Nat, path : List [Field Str, Index Nat] }], Ok [A val val1, B val1]] } | fmt has DecoderFormatting, val has Decoding, val1 has Decoding
+        # Specialization lambda sets:
+        #   @<1>: [[custom4(38)]]
+        #Derived.decoder_[A 2,B 1] =
+          Decode.custom
+            \#Derived.bytes, #Derived.fmt ->
+              Decode.decodeWith
+                #Derived.bytes
+                (Decode.tag
+                  { A_1: Err NoField, B_0: Err NoField, A_0: Err NoField, }
+                  \#Derived.state, #Derived.name, #Derived.index ->
+                    when #Derived.name is
+                      "A" ->
+                        when #Derived.index is
+                          0 ->
+                            Keep (Decode.custom
+                              \#Derived.bytes2, #Derived.fmt2 ->
+                                when Decode.decodeWith
+                                    #Derived.bytes2
+                                    Decode.decoder
+                                    #Derived.fmt2 is
+                                  {rest, result} ->
+                                    {
+                                      result: when #Derived.result is
+                                          Ok #Derived.val ->
+                                            Ok {
+                                            #Derived.state & A_0: Ok #Derived.val,
+                                            }
+                                          Err #Derived.e -> Err #Derived.e,
+                                      rest: #Derived.rest,
+                                    })
+                          1 ->
+                            Keep (Decode.custom
+                              \#Derived.bytes3, #Derived.fmt3 ->
+                                when Decode.decodeWith
+                                    #Derived.bytes3
+                                    Decode.decoder
+                                    #Derived.fmt3 is
+                                  {rest, result} ->
+                                    {
+                                      result: when #Derived.result2 is
+                                          Ok #Derived.val2 ->
+                                            Ok {
+                                            #Derived.state & A_1: Ok #Derived.val2,
+                                            }
+                                          Err #Derived.e2 -> Err #Derived.e2,
+                                      rest: #Derived.rest2,
+                                    })
+                          _ -> Skip
+                      "B" ->
+                        when #Derived.index is
+                          0 ->
+                            Keep (Decode.custom
+                              \#Derived.bytes4, #Derived.fmt4 ->
+                                when Decode.decodeWith
+                                    #Derived.bytes4
+                                    Decode.decoder
+                                    #Derived.fmt4 is
+                                  {rest, result} ->
+                                    {
+                                      result: when #Derived.result3 is
+                                          Ok #Derived.val3 ->
+                                            Ok {
+                                            #Derived.state & B_0: Ok #Derived.val3,
+                                            }
+                                          Err #Derived.e3 -> Err #Derived.e3,
+                                      rest: #Derived.rest3,
+                                    })
+                          _ -> Skip
+                      _ -> Skip
+                  \#Derived.state2, #Derived.name2 ->
+                    when #Derived.name2 is
+                      "A" ->
+                        when #Derived.state2 is
+                          {A_0: Ok #Derived.A_0, A_1: Ok #Derived.A_1} ->
+                            Ok (A #Derived.A_0 #Derived.A_1)
+                          {A_0: Err _} -> Err (Decode.missingPayload "A" 0)
+                          _ -> Err (Decode.missingPayload "A" 1)
+                      "B" ->
+                        when #Derived.state2 is
+                          {B_0: Ok #Derived.B_0} -> Ok (B #Derived.B_0)
+                          _ -> Err (Decode.missingPayload "B" 0)
+                      _ -> Err (Decode.unknownTag #Derived.name2))
+                #Derived.fmt
+        "###
+        )
+    })
+}
+
+// }}} deriver tests
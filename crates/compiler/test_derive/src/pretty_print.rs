@@ -131,7 +131,10 @@ fn expr<'a>(c: &Ctx, p: EPrec, f: &'a Arena<'a>, e: &'a Expr) -> DocBuilder<'a,
             )
             .group(),
         LetRec(_, _, _) => todo!(),
-        LetNonRec(_, _) => todo!(),
+        LetNonRec(loc_def, body) => def(c, f, loc_def)
+            .append(f.hardline())
+            .append(expr(c, Free, f, &body.value))
+            .group(),
         Call(fun, args, _) => {
             let (_, fun, _, _) = &**fun;
             maybe_paren!(
@@ -200,8 +203,52 @@ fn expr<'a>(c: &Ctx, p: EPrec, f: &'a Arena<'a>, e: &'a Expr) -> DocBuilder<'a,
             f.text(format!("@{}", opaque_name.as_str(c.interns)))
         }
         Accessor(_) => todo!(),
-        Update { .. } => todo!(),
-        Tag { .. } => todo!(),
+        Update {
+            symbol, updates, ..
+        } => f
+            .reflow("{")
+            .append(f.line())
+            .append(f.text(format!(
+                "{}.{} &",
+                symbol.module_string(c.interns),
+                symbol.as_str(c.interns),
+            )))
+            .append(
+                f.concat(updates.iter().map(|(name, field)| {
+                    let field = f
+                        .text(name.as_str())
+                        .append(f.reflow(": "))
+                        .append(expr(c, Free, f, &field.loc_expr.value))
+                        .nest(2)
+                        .group();
+                    f.line().append(field).append(",")
+                }))
+                .nest(2)
+                .group(),
+            )
+            .append(f.line())
+            .append(f.text("}"))
+            .group(),
+        Tag {
+            name, arguments, ..
+        } if arguments.is_empty() => f.text(name.0.as_str()),
+        Tag {
+            name, arguments, ..
+        } => maybe_paren!(
+            Free,
+            p,
+            f.text(name.0.as_str())
+                .append(f.space())
+                .append(
+                    f.intersperse(
+                        arguments
+                            .iter()
+                            .map(|(_, le)| expr(c, CallArg, f, &le.value)),
+                        f.space(),
+                    )
+                )
+                .group()
+        ),
         ZeroArgumentTag { .. } => todo!(),
         OpaqueRef { .. } => todo!(),
         Expect { .. } => todo!(),
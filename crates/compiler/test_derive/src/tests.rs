@@ -1,7 +1,9 @@
 #![cfg(test)]
 
+mod arbitrary;
 mod decoding;
 mod encoding;
+mod hash;
 
 mod pretty_print;
 mod util;
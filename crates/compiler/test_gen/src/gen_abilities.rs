@@ -914,6 +914,211 @@ fn decode_list_of_strings() {
     )
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_record_two_fields() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result { first : Str, second : Str } _
+                decoded = Str.toUtf8 "{\"second\":\"b\",\"skipped\":[1,{}],\"first\":\"a\"}" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok { first, second } -> Str.concat first second
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("ab"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_record_skipped_field_with_escapes() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result { first : Str } _
+                decoded = Str.toUtf8 "{\"skipped\":\"say \\\"hi\\\" ]}\",\"other\":\"dir\\\\\",\"first\":\"a\"}" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok { first } -> first
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("a"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_record_missing_field() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result { first : Str, second : Str } _
+                decoded = Str.toUtf8 "{\"first\":\"a\"}" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok _ -> "<bad>"
+                    Err (TooShort info) -> "too short: \(describe info)"
+                    Err (Unexpected info) -> describe info
+                    Err (Leftover _) -> "<bad>"
+
+            describe = \{ offset, path, expected, found } ->
+                segments =
+                    List.map path \segment ->
+                        when segment is
+                            Field name -> ".\(name)"
+                            Index index -> "[\(Num.toStr index)]"
+                pathStr = if List.isEmpty path then "." else Str.joinWith segments ""
+
+                "\(pathStr) at \(Num.toStr offset): expected \(expected), found \(found)"
+            "#
+        ),
+        RocStr::from(".second at 12: expected a value for this field, found no value"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_error_in_list_element() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result (List U8) _
+                decoded = Str.toUtf8 "[1,x]" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok _ -> "<bad>"
+                    Err (TooShort info) -> "too short: \(describe info)"
+                    Err (Unexpected info) -> describe info
+                    Err (Leftover _) -> "<bad>"
+
+            describe = \{ offset, path, expected, found } ->
+                segments =
+                    List.map path \segment ->
+                        when segment is
+                            Field name -> ".\(name)"
+                            Index index -> "[\(Num.toStr index)]"
+                pathStr = if List.isEmpty path then "." else Str.joinWith segments ""
+
+                "\(pathStr) at \(Num.toStr offset): expected \(expected), found \(found)"
+            "#
+        ),
+        RocStr::from("[1] at 3: expected a U8, found `x`"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_error_in_nested_record() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result { a : { b : U8 } } _
+                decoded = Str.toUtf8 "{\"a\":{\"b\":\"x\"}}" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok _ -> "<bad>"
+                    Err (TooShort info) -> "too short: \(describe info)"
+                    Err (Unexpected info) -> describe info
+                    Err (Leftover _) -> "<bad>"
+
+            describe = \{ offset, path, expected, found } ->
+                segments =
+                    List.map path \segment ->
+                        when segment is
+                            Field name -> ".\(name)"
+                            Index index -> "[\(Num.toStr index)]"
+                pathStr = if List.isEmpty path then "." else Str.joinWith segments ""
+
+                "\(pathStr) at \(Num.toStr offset): expected \(expected), found \(found)"
+            "#
+        ),
+        RocStr::from(".a.b at 10: expected a U8, found `\"x\"`"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn decode_error_unterminated_list() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Decode, Json] provides [main] to "./platform"
+
+            main =
+                decoded : Result (List Str) _
+                decoded = Str.toUtf8 "[\"a\",\"b\"" |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok _ -> "<bad>"
+                    Err (TooShort info) -> "too short: \(describe info)"
+                    Err (Unexpected info) -> describe info
+                    Err (Leftover _) -> "<bad>"
+
+            describe = \{ offset, path, expected, found } ->
+                segments =
+                    List.map path \segment ->
+                        when segment is
+                            Field name -> ".\(name)"
+                            Index index -> "[\(Num.toStr index)]"
+                pathStr = if List.isEmpty path then "." else Str.joinWith segments ""
+
+                "\(pathStr) at \(Num.toStr offset): expected \(expected), found \(found)"
+            "#
+        ),
+        RocStr::from("too short: . at 8: expected `,` or `]`, found the end of the input"),
+        RocStr
+    )
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn encode_then_decode_tag_union() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            app "test" imports [Encode, Decode, Json] provides [main] to "./platform"
+
+            main =
+                value : [A Str Str, B Str, C]
+                value = A "a" "b"
+
+                decoded : Result [A Str Str, B Str, C] _
+                decoded = Encode.toBytes value Json.toUtf8 |> Decode.fromBytes Json.fromUtf8
+
+                when decoded is
+                    Ok (A x y) -> Str.concat x y
+                    _ -> "<bad>"
+            "#
+        ),
+        RocStr::from("ab"),
+        RocStr
+    )
+}
+
 #[test]
 #[cfg(all(any(feature = "gen-llvm", feature = "gen-wasm")))]
 fn encode_then_decode_list_of_strings() {
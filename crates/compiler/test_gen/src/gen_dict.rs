@@ -1,10 +1,10 @@
-#![cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#![cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 
 #[cfg(feature = "gen-llvm")]
 use crate::helpers::llvm::assert_evals_to;
 
-// #[cfg(feature = "gen-dev")]
-// use crate::helpers::dev::assert_evals_to;
+#[cfg(feature = "gen-dev")]
+use crate::helpers::dev::assert_evals_to;
 
 #[cfg(feature = "gen-wasm")]
 use crate::helpers::wasm::assert_evals_to;
@@ -13,7 +13,7 @@ use indoc::indoc;
 use roc_std::{RocList, RocStr};
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn dict_empty_len() {
     assert_evals_to!(
         indoc!(
@@ -27,7 +27,7 @@ fn dict_empty_len() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn dict_insert_empty() {
     assert_evals_to!(
         indoc!(
@@ -43,7 +43,7 @@ fn dict_insert_empty() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn dict_empty_contains() {
     assert_evals_to!(
         indoc!(
@@ -60,7 +60,7 @@ fn dict_empty_contains() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn dict_nonempty_contains() {
     assert_evals_to!(
         indoc!(
@@ -77,7 +77,7 @@ fn dict_nonempty_contains() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn dict_empty_remove() {
     assert_evals_to!(
         indoc!(
@@ -96,7 +96,7 @@ fn dict_empty_remove() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn dict_nonempty_remove() {
     assert_evals_to!(
         indoc!(
@@ -116,7 +116,7 @@ fn dict_nonempty_remove() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn dict_nonempty_get() {
     assert_evals_to!(
         indoc!(
@@ -324,7 +324,7 @@ fn big_str_values() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn unit_values() {
     assert_evals_to!(
         indoc!(
@@ -346,7 +346,7 @@ fn unit_values() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn single() {
     assert_evals_to!(
         indoc!(
@@ -364,7 +364,7 @@ fn single() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn insert_all() {
     assert_evals_to!(
         indoc!(
@@ -402,7 +402,7 @@ fn insert_all_prefer_first() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn keep_shared() {
     assert_evals_to!(
         indoc!(
@@ -465,7 +465,7 @@ fn keep_shared_prefer_first() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn remove_all() {
     assert_evals_to!(
         indoc!(
@@ -497,7 +497,7 @@ fn remove_all() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn remove_all_prefer_first() {
     assert_evals_to!(
         indoc!(
@@ -528,7 +528,7 @@ fn remove_all_prefer_first() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn walk_sum_keys() {
     assert_evals_to!(
         indoc!(
@@ -549,3 +549,146 @@ fn walk_sum_keys() {
         i64
     );
 }
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
+fn many_inserts_and_removes() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            dict : Dict.Dict I64 I64
+            dict =
+                List.range 0 1000
+                    |> List.walk Dict.empty (\d, k -> Dict.insert d k (k * 2))
+
+            removed =
+                List.range 0 500
+                    |> List.walk dict (\d, k -> Dict.remove d (k * 2))
+
+            when (Dict.get removed 999, Dict.contains removed 998, Dict.len removed) is
+                (Ok v, False, n) -> v + Num.toI64 n
+                _ -> -1
+            "#
+        ),
+        1998 + 500,
+        i64
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
+fn record_keys() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            dict : Dict.Dict { x : I64, y : Str } I64
+            dict =
+                Dict.empty
+                    |> Dict.insert { x: 1, y: "a" } 1
+                    |> Dict.insert { x: 1, y: "b" } 2
+                    |> Dict.insert { x: 2, y: "a" } 3
+                    |> Dict.insert { x: 1, y: "a" } 4
+
+            when Dict.get dict { x: 1, y: "a" } is
+                Ok v -> v + Num.toI64 (Dict.len dict)
+                Err KeyNotFound -> -1
+            "#
+        ),
+        7,
+        i64
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
+fn tag_keys() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            dict : Dict.Dict [Red, Green, Custom Str] Str
+            dict =
+                Dict.empty
+                    |> Dict.insert Red "red"
+                    |> Dict.insert (Custom "cyan") "cyan"
+                    |> Dict.insert Green "green"
+                    |> Dict.remove Red
+
+            Dict.values dict
+            "#
+        ),
+        RocList::from_slice(&[RocStr::from("green"), RocStr::from("cyan")]),
+        RocList<RocStr>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
+fn eq_after_same_operations() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            build : {} -> Dict.Dict I64 Str
+            build = \{} ->
+                Dict.empty
+                    |> Dict.insert 1 "one"
+                    |> Dict.insert 2 "two"
+                    |> Dict.insert 3 "three"
+                    |> Dict.remove 1
+
+            build {} == build {}
+            "#
+        ),
+        true,
+        bool
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
+fn eq_depends_on_insertion_order() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            a : Dict.Dict I64 Str
+            a = Dict.empty |> Dict.insert 1 "one" |> Dict.insert 2 "two"
+
+            b : Dict.Dict I64 Str
+            b = Dict.empty |> Dict.insert 2 "two" |> Dict.insert 1 "one"
+
+            a == b
+            "#
+        ),
+        false,
+        bool
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
+fn eq_ignores_how_dict_was_built() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            a : Dict.Dict I64 Str
+            a = Dict.empty |> Dict.insert 1 "one" |> Dict.insert 2 "two"
+
+            b : Dict.Dict I64 Str
+            b = Dict.withCapacity 100 |> Dict.insert 1 "one" |> Dict.insert 2 "two"
+
+            filled : Dict.Dict I64 Str
+            filled = List.walk (List.range 1 20) Dict.empty (\dict, n -> Dict.insert dict n "")
+
+            # removing entries shrinks the buckets back down
+            c : Dict.Dict I64 Str
+            c =
+                List.walk (List.range 3 20) filled Dict.remove
+                |> Dict.insert 1 "one"
+                |> Dict.insert 2 "two"
+
+            (a == b, a == c)
+            "#
+        ),
+        (true, true),
+        (bool, bool)
+    );
+}
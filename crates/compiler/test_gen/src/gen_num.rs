@@ -1286,7 +1286,7 @@ fn tan() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn bitwise_and() {
     assert_evals_to!("Num.bitwiseAnd 20 20", 20, i64);
     assert_evals_to!("Num.bitwiseAnd 25 10", 8, i64);
@@ -1294,7 +1294,7 @@ fn bitwise_and() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn bitwise_xor() {
     assert_evals_to!("Num.bitwiseXor 20 20", 0, i64);
     assert_evals_to!("Num.bitwiseXor 15 14", 1, i64);
@@ -1303,7 +1303,7 @@ fn bitwise_xor() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn bitwise_or() {
     assert_evals_to!("Num.bitwiseOr 1 1", 1, i64);
     assert_evals_to!("Num.bitwiseOr 1 2", 3, i64);
@@ -1370,7 +1370,7 @@ fn lte_u64() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn gt_u64() {
     assert_evals_to!("2u64 > 1u64", true, bool);
     assert_evals_to!("2u64 > 2u64", false, bool);
@@ -1408,7 +1408,7 @@ fn lte_i64() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn gt_i64() {
     assert_evals_to!("2 > 1", true, bool);
     assert_evals_to!("2 > 2", false, bool);
@@ -1713,7 +1713,7 @@ fn float_to_float() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn int_compare() {
     assert_evals_to!("Num.compare 0 1", RocOrder::Lt, RocOrder);
     assert_evals_to!("Num.compare 1 1", RocOrder::Eq, RocOrder);
@@ -1735,13 +1735,13 @@ fn pow() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn ceiling() {
     assert_evals_to!("Num.ceiling 1.1", 2, i64);
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn floor() {
     assert_evals_to!("Num.floor 1.9", 1, i64);
 }
@@ -1774,7 +1774,7 @@ fn int_add_overflow() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn int_add_checked() {
     assert_evals_to!(
         indoc!(
@@ -1816,7 +1816,7 @@ fn int_add_wrap() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn float_add_checked_pass() {
     assert_evals_to!(
         indoc!(
@@ -1832,7 +1832,7 @@ fn float_add_checked_pass() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn float_add_checked_fail() {
     assert_evals_to!(
         indoc!(
@@ -1905,7 +1905,7 @@ fn float_sub_overflow() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn int_sub_checked() {
     assert_evals_to!(
         indoc!(
@@ -1933,7 +1933,7 @@ fn int_sub_checked() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn float_sub_checked() {
     assert_evals_to!(
         indoc!(
@@ -2033,7 +2033,7 @@ fn int_mul_wrap() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn int_mul_checked() {
     assert_evals_to!(
         indoc!(
@@ -2061,7 +2061,7 @@ fn int_mul_checked() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn float_mul_checked() {
     assert_evals_to!(
         indoc!(
@@ -2089,7 +2089,7 @@ fn float_mul_checked() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn shift_left_by() {
     assert_evals_to!("Num.shiftLeftBy 0 0b0000_0001", 0b0000_0001, i64);
     assert_evals_to!("Num.shiftLeftBy 1 0b0000_0001", 0b0000_0010, i64);
@@ -2188,6 +2188,164 @@ fn max_i128() {
     );
 }
 
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn i128_compare() {
+    assert_evals_to!("1i128 < 2i128", true, bool);
+    assert_evals_to!("-1i128 < 1i128", true, bool);
+    assert_evals_to!("Num.minI128 < Num.maxI128", true, bool);
+    assert_evals_to!("-1i128 > -2i128", true, bool);
+    assert_evals_to!("18_446_744_073_709_551_616i128 > 1i128", true, bool);
+    assert_evals_to!("3i128 <= 3i128", true, bool);
+    assert_evals_to!("3i128 >= 4i128", false, bool);
+    assert_evals_to!("Num.compare -5i128 5i128", RocOrder::Lt, RocOrder);
+    assert_evals_to!("Num.compare 5i128 5i128", RocOrder::Eq, RocOrder);
+    assert_evals_to!("Num.compare 5i128 -5i128", RocOrder::Gt, RocOrder);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn u128_compare() {
+    assert_evals_to!("1u128 < 2u128", true, bool);
+    assert_evals_to!("Num.maxU128 > 1u128", true, bool);
+    assert_evals_to!(
+        "18_446_744_073_709_551_615u128 < 18_446_744_073_709_551_616u128",
+        true,
+        bool
+    );
+    assert_evals_to!("Num.compare Num.maxU128 0u128", RocOrder::Gt, RocOrder);
+}
+
+#[test]
+#[cfg(any(feature = "gen-wasm"))]
+fn dec_compare() {
+    assert_evals_to!("1.5dec < 2.5dec", true, bool);
+    assert_evals_to!("-1.5dec < 0.5dec", true, bool);
+    assert_evals_to!("0.5dec >= 0.5dec", true, bool);
+    assert_evals_to!("Num.compare -0.1dec 0.1dec", RocOrder::Lt, RocOrder);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn i128_div_rem() {
+    assert_evals_to!(
+        "100_000_000_000_000_000_000i128 // 7i128",
+        14_285_714_285_714_285_714,
+        i128
+    );
+    assert_evals_to!("-100i128 // 7i128", -14, i128);
+    assert_evals_to!("-100i128 % 7i128", -2, i128);
+    assert_evals_to!("Num.maxU128 // 3u128", u128::MAX / 3, u128);
+    assert_evals_to!(
+        "Num.isMultipleOf 36_893_488_147_419_103_232i128 2i128",
+        true,
+        bool
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn i128_neg_abs() {
+    assert_evals_to!("Num.neg 1i128", -1, i128);
+    assert_evals_to!(
+        "Num.neg -18_446_744_073_709_551_616i128",
+        18_446_744_073_709_551_616,
+        i128
+    );
+    assert_evals_to!("Num.neg Num.maxI128", -i128::MAX, i128);
+    assert_evals_to!(
+        "Num.abs -18_446_744_073_709_551_617i128",
+        18_446_744_073_709_551_617,
+        i128
+    );
+    assert_evals_to!("Num.abs 42i128", 42, i128);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[should_panic(
+    expected = r#"Roc failed with message: "integer negation overflowed because its argument is the minimum value"#
+)]
+fn neg_min_i128_overflow() {
+    assert_evals_to!("Num.neg Num.minI128", 0, i128);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn i128_bitwise() {
+    assert_evals_to!("Num.bitwiseAnd Num.maxU128 0xF0u128", 0xF0, u128);
+    assert_evals_to!(
+        "Num.bitwiseOr (Num.shiftLeftBy 64 1u128) 1u128",
+        (1 << 64) | 1,
+        u128
+    );
+    assert_evals_to!("Num.bitwiseXor -1i128 0i128", -1, i128);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn i128_shift() {
+    assert_evals_to!("Num.shiftLeftBy 100 1i128", 1 << 100, i128);
+    assert_evals_to!(
+        "Num.shiftRightBy 64 -36_893_488_147_419_103_232i128",
+        -2,
+        i128
+    );
+    assert_evals_to!("Num.shiftRightZfBy 127 Num.maxU128", 1, u128);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn i128_wrapping_arithmetic() {
+    assert_evals_to!("Num.addWrap Num.maxI128 1i128", i128::MIN, i128);
+    assert_evals_to!("Num.subWrap 0u128 1u128", u128::MAX, u128);
+    assert_evals_to!("Num.mulWrap Num.maxI128 2i128", -2, i128);
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn round_to_i128() {
+    assert_evals_to!(
+        indoc!(
+            r#"
+            n : I128
+            n = Num.round 2.5
+            n
+            "#
+        ),
+        3,
+        i128
+    );
+    assert_evals_to!(
+        indoc!(
+            r#"
+            n : I128
+            n = Num.ceiling -1.5
+            n
+            "#
+        ),
+        -1,
+        i128
+    );
+    assert_evals_to!(
+        indoc!(
+            r#"
+            n : I128
+            n = Num.floor -1.5
+            n
+            "#
+        ),
+        -2,
+        i128
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+fn pow_int_i128() {
+    assert_evals_to!("Num.powInt 2i128 100i128", 1 << 100, i128);
+}
+
 #[test]
 #[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
 fn min_i64() {
@@ -2507,12 +2665,12 @@ num_conversion_tests! {
     "Num.toI64", i64, (
         to_i64_same_width, "15u64", 15, ["gen-wasm"]
         to_i64_extend, "15i8", 15, ["gen-wasm"]
-        to_i64_truncate, "115i128", 115
-        to_i64_truncate_wraps, "10_000_000_000_000_000_000i128", -8446744073709551616
+        to_i64_truncate, "115i128", 115, ["gen-wasm"]
+        to_i64_truncate_wraps, "10_000_000_000_000_000_000i128", -8446744073709551616, ["gen-wasm"]
     )
     "Num.toI128", i128, (
-        to_i128_same_width, "15u128", 15
-        to_i128_extend, "15i8", 15
+        to_i128_same_width, "15u128", 15, ["gen-wasm"]
+        to_i128_extend, "15i8", 15, ["gen-wasm"]
     )
     "Num.toU8", u8, (
         to_u8_same_width, "15i8", 15, ["gen-wasm"]
@@ -2534,29 +2692,29 @@ num_conversion_tests! {
     "Num.toU64", u64, (
         to_u64_same_width, "15i64", 15, ["gen-wasm"]
         to_u64_extend, "15i8", 15, ["gen-wasm"]
-        to_u64_truncate, "115i128", 115
-        to_u64_truncate_wraps, "10_000_000_000_000_000_000_000i128", 1864712049423024128
+        to_u64_truncate, "115i128", 115, ["gen-wasm"]
+        to_u64_truncate_wraps, "10_000_000_000_000_000_000_000i128", 1864712049423024128, ["gen-wasm"]
     )
     "Num.toU128", u128, (
-        to_u128_same_width, "15i128", 15
-        to_u128_extend, "15i8", 15
+        to_u128_same_width, "15i128", 15, ["gen-wasm"]
+        to_u128_extend, "15i8", 15, ["gen-wasm"]
     )
     "Num.toNat", usize, (
         to_nat_same_width, "15i64", 15, ["gen-wasm"]
         to_nat_extend, "15i8", 15, ["gen-wasm"]
-        to_nat_truncate, "115i128", 115
+        to_nat_truncate, "115i128", 115, ["gen-wasm"]
     )
     "Num.toF32", f32, (
         to_f32_from_i8, "15i8", 15.0
         to_f32_from_i16, "15i16", 15.0
         to_f32_from_i32, "15i32", 15.0
         to_f32_from_i64, "15i64", 15.0
-        to_f32_from_i128, "15i128", 15.0
+        to_f32_from_i128, "15i128", 15.0, ["gen-wasm"]
         to_f32_from_u8, "15u8", 15.0
         to_f32_from_u16, "15u16", 15.0
         to_f32_from_u32, "15u32", 15.0
         to_f32_from_u64, "15u64", 15.0
-        to_f32_from_u128, "15u128", 15.0
+        to_f32_from_u128, "15u128", 15.0, ["gen-wasm"]
         to_f32_from_nat, "15nat", 15.0
         to_f32_from_f32, "1.5f32", 1.5
         to_f32_from_f64, "1.5f64", 1.5
@@ -2566,12 +2724,12 @@ num_conversion_tests! {
         to_f64_from_i16, "15i16", 15.0
         to_f64_from_i32, "15i32", 15.0
         to_f64_from_i64, "15i64", 15.0
-        to_f64_from_i128, "15i128", 15.0
+        to_f64_from_i128, "15i128", 15.0, ["gen-wasm"]
         to_f64_from_u8, "15u8", 15.0
         to_f64_from_u16, "15u16", 15.0
         to_f64_from_u32, "15u32", 15.0
         to_f64_from_u64, "15u64", 15.0
-        to_f64_from_u128, "15u128", 15.0
+        to_f64_from_u128, "15u128", 15.0, ["gen-wasm"]
         to_f64_from_nat, "15nat", 15.0
         to_f64_from_f32, "1.5f32", 1.5
         to_f64_from_f64, "1.5f64", 1.5
@@ -2724,7 +2882,7 @@ to_int_checked_tests! {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn is_multiple_of_signed() {
     // true
     assert_evals_to!("Num.isMultipleOf 5 1", true, bool);
@@ -2741,7 +2899,7 @@ fn is_multiple_of_signed() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn is_multiple_of_unsigned() {
     // true
     assert_evals_to!("Num.isMultipleOf 5u8 1", true, bool);
@@ -3201,7 +3359,7 @@ fn num_to_str_f64() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn num_to_str_dec() {
     use roc_std::RocStr;
 
@@ -3528,7 +3686,7 @@ fn monomorphized_ints_aliased() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn to_float_f32() {
     assert_evals_to!(
         indoc!(
@@ -3547,7 +3705,7 @@ fn to_float_f32() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn to_float_f64() {
     assert_evals_to!(
         indoc!(
@@ -3654,7 +3812,7 @@ fn dec_no_decimal() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm", feature = "gen-wasm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-wasm", feature = "gen-dev"))]
 fn ceiling_to_u32() {
     assert_evals_to!(
         indoc!(
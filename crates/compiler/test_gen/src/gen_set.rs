@@ -10,7 +10,7 @@ use crate::helpers::llvm::assert_evals_to;
 // use crate::helpers::wasm::assert_evals_to;
 
 use indoc::indoc;
-use roc_std::RocList;
+use roc_std::{RocList, RocStr};
 
 #[test]
 #[cfg(any(feature = "gen-llvm"))]
@@ -63,6 +63,22 @@ fn single_to_list() {
         RocList<i64>
     );
 
+    assert_evals_to!(
+        indoc!(
+            r#"
+            Set.toList (Set.single "foo")
+            "#
+        ),
+        RocList::from_slice(&[RocStr::from("foo")]),
+        RocList<RocStr>
+    );
+}
+
+#[test]
+#[cfg(any(feature = "gen-llvm"))]
+#[should_panic(expected = "Mistakes were made")]
+fn single_float_is_not_hashable() {
+    // Floats don't implement `Hash`, so they can't be in a `Set`.
     assert_evals_to!(
         indoc!(
             r#"
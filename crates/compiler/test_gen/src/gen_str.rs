@@ -463,7 +463,7 @@ fn str_concat_empty() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn small_str_is_empty() {
     assert_evals_to!(r#"Str.isEmpty "abc""#, false, bool);
 }
@@ -479,7 +479,7 @@ fn big_str_is_empty() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn empty_str_is_empty() {
     assert_evals_to!(r#"Str.isEmpty """#, true, bool);
 }
@@ -524,7 +524,7 @@ fn str_count_graphemes_small_str() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn str_count_graphemes_three_js() {
     assert_evals_to!(r#"Str.countGraphemes "JJJ""#, 3, usize);
 }
@@ -560,18 +560,18 @@ fn str_starts_with_different_big_str() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn str_starts_with_same_small_str() {
     assert_evals_to!(r#"Str.startsWith "1234" "1234""#, true, bool);
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn str_starts_with_different_small_str() {
     assert_evals_to!(r#"Str.startsWith "1234" "12""#, true, bool);
 }
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn str_starts_with_false_small_str() {
     assert_evals_to!(r#"Str.startsWith "1234" "23""#, false, bool);
 }
@@ -1031,7 +1031,7 @@ fn str_from_utf8_range_count_too_high_for_start() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn str_repeat_small_stays_small() {
     assert_evals_to!(
         indoc!(r#"Str.repeat "Roc" 3"#),
@@ -1069,19 +1069,19 @@ fn str_repeat_empty_string() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn str_repeat_zero_times() {
     assert_evals_to!(indoc!(r#"Str.repeat "Roc" 0"#), RocStr::from(""), RocStr);
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn str_trim_empty_string() {
     assert_evals_to!(indoc!(r#"Str.trim """#), RocStr::from(""), RocStr);
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn str_trim_small_blank_string() {
     assert_evals_to!(indoc!(r#"Str.trim " ""#), RocStr::from(""), RocStr);
 }
@@ -1174,7 +1174,7 @@ fn str_trim_small_to_small_shared() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn str_trim_left_small_blank_string() {
     assert_evals_to!(indoc!(r#"Str.trimLeft " ""#), RocStr::from(""), RocStr);
 }
@@ -1267,7 +1267,7 @@ fn str_trim_left_small_to_small_shared() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn str_trim_right_small_blank_string() {
     assert_evals_to!(indoc!(r#"Str.trimRight " ""#), RocStr::from(""), RocStr);
 }
@@ -1533,7 +1533,7 @@ fn str_to_u8() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn str_to_f64() {
     assert_evals_to!(
         indoc!(
@@ -1550,7 +1550,7 @@ fn str_to_f64() {
 }
 
 #[test]
-#[cfg(any(feature = "gen-llvm"))]
+#[cfg(any(feature = "gen-llvm", feature = "gen-dev"))]
 fn str_to_f32() {
     assert_evals_to!(
         indoc!(
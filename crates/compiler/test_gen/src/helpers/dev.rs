@@ -51,10 +51,11 @@ pub fn helper(
     }
 
     let load_config = LoadConfig {
-        target_info: roc_target::TargetInfo::default_x86_64(),
+        target_info: roc_target::TargetInfo::from(&target_lexicon::Triple::host()),
         render: roc_reporting::report::RenderTarget::ColorTerminal,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Executable,
+        cache_dir: None,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -71,6 +71,7 @@ fn create_llvm_module<'a>(
         render: RenderTarget::ColorTerminal,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Executable,
+        cache_dir: None,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -214,6 +215,7 @@ fn create_llvm_module<'a>(
         builder: &builder,
         dibuilder: &dibuilder,
         compile_unit: &compile_unit,
+        debug_sources: None,
         context,
         interns,
         module,
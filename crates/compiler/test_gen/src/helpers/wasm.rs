@@ -89,6 +89,7 @@ fn compile_roc_to_wasm_bytes<'a, T: Wasm32Result>(
         render: roc_reporting::report::RenderTarget::ColorTerminal,
         threading: Threading::Single,
         exec_mode: ExecutionMode::Executable,
+        cache_dir: None,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -123,6 +124,7 @@ fn compile_roc_to_wasm_bytes<'a, T: Wasm32Result>(
         module_id,
         exposed_to_host,
         stack_bytes: roc_gen_wasm::Env::DEFAULT_STACK_BYTES,
+        debug_sources: None,
     };
 
     let host_module = roc_gen_wasm::parse_host(env.arena, host_bytes).unwrap_or_else(|e| {
@@ -98,6 +98,7 @@ fn compiles_to_ir(test_name: &str, src: &str) {
         threading: Threading::Single,
         render: roc_reporting::report::RenderTarget::Generic,
         exec_mode: ExecutionMode::Executable,
+        cache_dir: None,
     };
     let loaded = roc_load::load_and_monomorphize_from_str(
         arena,
@@ -77,6 +77,8 @@ struct SubsHeader {
     record_fields: u64,
     variable_slices: u64,
     unspecialized_lambda_sets: u64,
+    uls_of_var: u64,
+    uls_of_var_lambda_sets: u64,
     exposed_vars_by_symbol: u64,
 }
 
@@ -95,6 +97,8 @@ impl SubsHeader {
             record_fields: subs.record_fields.len() as u64,
             variable_slices: subs.variable_slices.len() as u64,
             unspecialized_lambda_sets: subs.unspecialized_lambda_sets.len() as u64,
+            uls_of_var: subs.uls_of_var.len() as u64,
+            uls_of_var_lambda_sets: subs.uls_of_var.0.values().map(|set| set.len() as u64).sum(),
             exposed_vars_by_symbol: exposed_vars_by_symbol as u64,
         }
     }
@@ -144,6 +148,7 @@ impl Subs {
         written = Self::serialize_slice(&self.record_fields, writer, written)?;
         written = Self::serialize_slice(&self.variable_slices, writer, written)?;
         written = Self::serialize_slice(&self.unspecialized_lambda_sets, writer, written)?;
+        written = Self::serialize_uls_of_var(&self.uls_of_var, writer, written)?;
         written = Self::serialize_slice(exposed_vars_by_symbol, writer, written)?;
 
         Ok(written)
@@ -169,6 +174,26 @@ impl Subs {
         Self::serialize_slice(&buf, writer, written)
     }
 
+    /// Each variable, with the number of lambda sets that depend on it, followed by all of those
+    /// lambda sets in the same order.
+    fn serialize_uls_of_var(
+        uls_of_var: &UlsOfVar,
+        writer: &mut impl std::io::Write,
+        written: usize,
+    ) -> std::io::Result<usize> {
+        let mut vars: Vec<(Variable, u32)> = Vec::with_capacity(uls_of_var.len());
+        let mut lambda_sets: Vec<Variable> = Vec::new();
+
+        for (var, set) in uls_of_var.0.iter() {
+            vars.push((*var, set.len() as u32));
+            lambda_sets.extend(set.iter().copied());
+        }
+
+        let written = Self::serialize_slice(&vars, writer, written)?;
+
+        Self::serialize_slice(&lambda_sets, writer, written)
+    }
+
     /// Global tag names can be heap-allocated
     fn serialize_tag_names(
         tag_names: &[TagName],
@@ -229,6 +254,12 @@ impl Subs {
             Self::deserialize_slice(bytes, header.variable_slices as usize, offset);
         let (unspecialized_lambda_sets, offset) =
             Self::deserialize_slice(bytes, header.unspecialized_lambda_sets as usize, offset);
+        let (uls_of_var, offset) = Self::deserialize_uls_of_var(
+            bytes,
+            header.uls_of_var as usize,
+            header.uls_of_var_lambda_sets as usize,
+            offset,
+        );
         let (exposed_vars_by_symbol, _) =
             Self::deserialize_slice(bytes, header.exposed_vars_by_symbol as usize, offset);
 
@@ -244,7 +275,7 @@ impl Subs {
                 unspecialized_lambda_sets: unspecialized_lambda_sets.to_vec(),
                 tag_name_cache: Default::default(),
                 problems: Default::default(),
-                uls_of_var: Default::default(),
+                uls_of_var,
             },
             exposed_vars_by_symbol,
         )
@@ -271,6 +302,25 @@ impl Subs {
         (lowercases, offset)
     }
 
+    fn deserialize_uls_of_var(
+        bytes: &[u8],
+        length: usize,
+        lambda_sets_length: usize,
+        offset: usize,
+    ) -> (UlsOfVar, usize) {
+        let (vars, offset) = Self::deserialize_slice::<(Variable, u32)>(bytes, length, offset);
+        let (lambda_sets, offset) =
+            Self::deserialize_slice::<Variable>(bytes, lambda_sets_length, offset);
+
+        let mut uls_of_var = UlsOfVar::default();
+        let mut lambda_sets = lambda_sets.iter().copied();
+        for (var, count) in vars {
+            uls_of_var.extend(*var, lambda_sets.by_ref().take(*count as usize));
+        }
+
+        (uls_of_var, offset)
+    }
+
     fn deserialize_tag_names(bytes: &[u8], length: usize, offset: usize) -> (Vec<TagName>, usize) {
         let (slices, mut offset) =
             Self::deserialize_slice::<SerializedTagName>(bytes, length, offset);
@@ -5360,3 +5410,27 @@ pub fn get_member_lambda_sets_at_region(subs: &Subs, var: Variable, target_regio
 
     internal_error!("No lambda set at region {} found", target_region);
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn serialization_keeps_uls_of_var() {
+        let mut subs = Subs::new();
+        let var = subs.fresh_unnamed_flex_var();
+        let lambda_set1 = subs.fresh_unnamed_flex_var();
+        let lambda_set2 = subs.fresh_unnamed_flex_var();
+        subs.uls_of_var.extend(var, [lambda_set1, lambda_set2]);
+
+        let mut bytes = Vec::new();
+        subs.serialize(&[], &mut bytes).unwrap();
+
+        let (mut subs, _) = Subs::deserialize(&bytes);
+        let dependents: Vec<Variable> = subs
+            .remove_dependent_unspecialized_lambda_sets(var)
+            .collect();
+
+        assert_eq!(dependents, [lambda_set1, lambda_set2]);
+    }
+}
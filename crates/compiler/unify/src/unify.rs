@@ -2702,8 +2702,10 @@ fn unify_rigid_able<M: MetaCollector>(
 ) -> Outcome<M> {
     match other {
         FlexVar(_) => {
-            // If the other is flex, rigid wins!
-            merge(env, ctx, RigidVar(*name))
+            // If the other is flex, rigid wins, and keeps its ability bound! This mirrors how
+            // `unify_flex` treats a rigid able var on the other side; dropping the bound here
+            // would make the result depend on the order in which the two were unified.
+            merge(env, ctx, RigidAbleVar(*name, ability))
         }
         FlexAbleVar(_, other_ability) => {
             if ability == *other_ability {
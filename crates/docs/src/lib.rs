@@ -8,11 +8,13 @@ use roc_code_markup::markup::nodes::MarkupNode;
 use roc_code_markup::slow_pool::SlowPool;
 use roc_highlight::highlight_parser::{highlight_defs, highlight_expr};
 use roc_load::docs::DocEntry::DocDef;
-use roc_load::docs::{DocEntry, TypeAnnotation};
+use roc_load::docs::{DocEntry, Documentation, TypeAnnotation};
 use roc_load::docs::{ModuleDocumentation, RecordField};
 use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
 use roc_module::symbol::{IdentIdsByModule, Interns, ModuleId};
+use roc_parse::ast::{ExtractSpaces, Module};
 use roc_parse::ident::{parse_ident, Ident};
+use roc_parse::module::parse_header;
 use roc_parse::state::State;
 use roc_region::all::Region;
 use std::fs;
@@ -20,21 +22,195 @@ use std::path::{Path, PathBuf};
 
 mod docs_error;
 mod html;
+mod package;
+mod search;
+mod source;
+
+pub use package::{read_package_root, PackageRoot};
+pub use search::search_index_entry;
+pub use source::syntax_highlight_source;
+
+pub const BUILD_DIR: &str = "./generated-docs";
+
+/// Links to builtin types go here, unless the builtins are documented along with the package.
+const BUILTINS_URL: &str = "https://www.roc-lang.org/builtins/";
+
+/// A package in the generated docs. Its docs are in the `path` directory of the site, e.g. in
+/// `json/` for a package that the documented package calls `json`, and at the root of the site
+/// for the documented package itself.
+struct PackageDocs {
+    path: String,
+    documentation: Documentation,
+    /// The modules to document. A load can have docs for more modules than the one it started
+    /// from, so this is the index of the load in `documentation.modules`, and the module's id.
+    documented: Vec<(usize, ModuleId)>,
+    /// The shorthand and location of each package it depends on
+    packages: Vec<(String, String)>,
+}
 
-const BUILD_DIR: &str = "./generated-docs";
+impl PackageDocs {
+    fn modules(&self) -> impl Iterator<Item = (&LoadedModule, ModuleId, &ModuleDocumentation)> {
+        self.documented
+            .iter()
+            .filter_map(move |(index, module_id)| {
+                let loaded_module = &self.documentation.modules[*index];
+
+                loaded_module
+                    .documentation
+                    .get(module_id)
+                    .map(|module_docs| (loaded_module, *module_id, module_docs))
+            })
+    }
+}
 
 pub fn generate_docs_html(filenames: Vec<PathBuf>) {
     let build_dir = Path::new(BUILD_DIR);
     let loaded_modules = load_modules_for_files(filenames);
 
+    let documented = loaded_modules
+        .iter()
+        .enumerate()
+        .map(|(index, loaded_module)| (index, loaded_module.module_id))
+        .collect();
+
     // TODO: get info from a package module; this is all hardcoded for now.
-    let mut package = roc_load::docs::Documentation {
-        name: "roc/builtins".to_string(),
-        version: "1.0.0".to_string(),
-        docs: "Package introduction or README.".to_string(),
-        modules: loaded_modules,
+    let package = PackageDocs {
+        path: String::new(),
+        documentation: Documentation {
+            name: "roc/builtins".to_string(),
+            version: "1.0.0".to_string(),
+            docs: "Package introduction or README.".to_string(),
+            modules: loaded_modules,
+        },
+        documented,
+        packages: Vec::new(),
+    };
+
+    write_docs(build_dir, &[package]);
+
+    println!("🎉 Docs generated in {}", build_dir.display());
+}
+
+/// Generate the docs for a platform and the packages it depends on, as one site with a landing
+/// page for each package.
+pub fn generate_package_docs_html(root: &PackageRoot, build_dir: &Path) {
+    let mut packages = Vec::new();
+
+    load_package(root, String::new(), &mut Vec::new(), &mut packages);
+
+    write_docs(build_dir, &packages);
+
+    for package in packages.iter() {
+        let package_dir = build_dir.join(&package.path);
+        let landing_page = template_html(package).replace(
+            "<!-- Module Docs -->",
+            &render_package_landing_page(package, &packages),
+        );
+
+        fs::write(package_dir.join("index.html"), landing_page)
+            .expect("TODO gracefully handle failing to write the package's index.html");
+    }
+
+    println!("🎉 Docs generated in {}", build_dir.display());
+}
+
+/// Load the package's modules, and those of the packages it depends on, which get a directory in
+/// the site named after their shorthand. Packages that aren't on disk, like the ones at a url, are
+/// only listed on the landing page.
+fn load_package(
+    root: &PackageRoot,
+    path: String,
+    visited: &mut Vec<PathBuf>,
+    packages: &mut Vec<PackageDocs>,
+) {
+    visited.push(canonicalize(&root.root_file));
+
+    let root_dir = root.root_file.parent().unwrap_or_else(|| Path::new("."));
+
+    // The platform's own modules can import each other through the platform's shorthand, e.g.
+    // `pf.Effect`, which only works when they're loaded from the root module.
+    let mut modules = load_modules_for_files(vec![root.root_file.clone()]);
+
+    let unloaded_files = root
+        .module_files
+        .iter()
+        .filter(|file| find_loaded_module(&modules, file).is_none())
+        .cloned()
+        .collect();
+
+    modules.extend(load_modules_for_files(unloaded_files));
+
+    let documented = if root.module_files.is_empty() {
+        let root_dir = canonicalize(root_dir);
+        let mut documented: Vec<(usize, ModuleId)> = modules[0]
+            .documentation
+            .keys()
+            .filter(|module_id| match modules[0].sources.get(module_id) {
+                Some((path, _)) => {
+                    !module_id.is_builtin() && canonicalize(path).starts_with(&root_dir)
+                }
+                None => false,
+            })
+            .map(|module_id| (0, *module_id))
+            .collect();
+
+        documented.sort_by_key(|(_, module_id)| &modules[0].documentation[module_id].name);
+
+        documented
+    } else {
+        root.module_files
+            .iter()
+            .filter_map(|file| find_loaded_module(&modules, file))
+            .collect()
     };
 
+    packages.push(PackageDocs {
+        path: path.clone(),
+        documentation: Documentation {
+            name: root.name.clone(),
+            version: String::new(),
+            docs: root.readme.clone().unwrap_or_default(),
+            modules,
+        },
+        documented,
+        packages: root.packages.clone(),
+    });
+
+    for (shorthand, location) in root.packages.iter() {
+        if let Some(dependency) = read_package_root(&root_dir.join(location)) {
+            if !visited.contains(&canonicalize(&dependency.root_file)) {
+                let path = format!("{}{}/", path, shorthand);
+
+                load_package(&dependency, path, visited, packages);
+            }
+        }
+    }
+}
+
+/// The load, and the id, of the module in `file`
+fn find_loaded_module(modules: &[LoadedModule], file: &Path) -> Option<(usize, ModuleId)> {
+    let file = canonicalize(file);
+
+    modules
+        .iter()
+        .enumerate()
+        .find_map(|(index, loaded_module)| {
+            loaded_module
+                .documentation
+                .keys()
+                .find(|module_id| match loaded_module.sources.get(module_id) {
+                    Some((path, _)) => canonicalize(path) == file,
+                    None => false,
+                })
+                .map(|module_id| (index, *module_id))
+        })
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn write_docs(build_dir: &Path, packages: &[PackageDocs]) {
     if !build_dir.exists() {
         fs::create_dir_all(build_dir).expect("TODO gracefully handle unable to create build dir");
     }
@@ -58,66 +234,236 @@ pub fn generate_docs_html(filenames: Vec<PathBuf>) {
     )
     .expect("TODO gracefully handle failing to make the favicon");
 
-    let template_html = include_str!("./static/index.html")
+    fs::write(
+        build_dir.join(search::SEARCH_INDEX_FILENAME),
+        render_package_search_index(packages),
+    )
+    .expect("TODO gracefully handle failing to make the search index");
+
+    // Write each package's module docs html file
+    for (package_index, package) in packages.iter().enumerate() {
+        let template_html = template_html(package);
+
+        for (loaded_module, module_id, module_docs) in package.modules() {
+            let module_dir = build_dir
+                .join(&package.path)
+                .join(module_docs.name.replace('.', "/").as_str());
+
+            fs::create_dir_all(&module_dir)
+                .expect("TODO gracefully handle not being able to create the module dir");
+
+            let links = Links {
+                packages,
+                current_package: package_index,
+                loaded_module,
+                home: module_id,
+                scope: &module_docs.scope,
+            };
+
+            let rendered_module = template_html.replace(
+                "<!-- Module Docs -->",
+                render_module_documentation(module_docs, loaded_module, &links).as_str(),
+            );
+
+            fs::write(module_dir.join("index.html"), rendered_module)
+                .expect("TODO gracefully handle failing to write index.html inside module's dir");
+
+            if let Some((_, src)) = loaded_module.sources.get(&module_id) {
+                let rendered_source = template_html.replace(
+                    "<!-- Module Docs -->",
+                    render_module_source(module_docs, src, &links).as_str(),
+                );
+
+                fs::write(module_dir.join(source::SOURCE_FILENAME), rendered_source)
+                    .expect("TODO gracefully handle failing to write the module's source page");
+            }
+        }
+    }
+}
+
+/// The page template with everything filled in except the main content, which is the same for
+/// every page of a package.
+fn template_html(package: &PackageDocs) -> String {
+    let package_url = package_url(package);
+
+    include_str!("./static/index.html")
         .replace("<!-- search.js -->", "/search.js")
         .replace("<!-- styles.css -->", "/styles.css")
         .replace("<!-- favicon.svg -->", "/favicon.svg")
         .replace(
             "<!-- Module links -->",
-            render_sidebar(package.modules.iter().flat_map(|loaded_module| {
-                loaded_module
-                    .documentation
-                    .iter()
-                    .filter_map(move |(module_id, module)| {
-                        // TODO it seems this `documentation` dictionary has entries for
-                        // every module, but only the current module has any info in it.
-                        // We disregard the others, but probably this shouldn't bother
-                        // being a hash map in the first place if only one of its entries
-                        // actually has interesting information in it?
-                        if *module_id == loaded_module.module_id {
-                            let exposed_values = loaded_module
-                                .exposed_values
-                                .iter()
-                                .map(|symbol| symbol.as_str(&loaded_module.interns).to_string())
-                                .collect::<Vec<String>>();
-
-                            Some((module, exposed_values))
-                        } else {
-                            None
-                        }
-                    })
-            }))
+            render_sidebar(
+                &package_url,
+                package.modules().map(|(loaded_module, module_id, module)| {
+                    (module, exposed_names(loaded_module, module_id))
+                }),
+            )
             .as_str(),
-        );
+        )
+        .replace(
+            "<!-- Package Name and Version -->",
+            render_name_and_version(
+                package.documentation.name.as_str(),
+                package.documentation.version.as_str(),
+                &package_url,
+            )
+            .as_str(),
+        )
+}
 
-    // Write each package's module docs html file
-    for loaded_module in package.modules.iter_mut() {
-        for (module_id, module_docs) in loaded_module.documentation.iter() {
-            if *module_id == loaded_module.module_id {
-                let module_dir = build_dir.join(module_docs.name.replace('.', "/").as_str());
-
-                fs::create_dir_all(&module_dir)
-                    .expect("TODO gracefully handle not being able to create the module dir");
-
-                let rendered_module = template_html
-                    .replace(
-                        "<!-- Package Name and Version -->",
-                        render_name_and_version(package.name.as_str(), package.version.as_str())
-                            .as_str(),
-                    )
-                    .replace(
-                        "<!-- Module Docs -->",
-                        render_module_documentation(module_docs, loaded_module).as_str(),
-                    );
+/// e.g. "/" for the documented package, or "/json/" for a package it depends on
+fn package_url(package: &PackageDocs) -> String {
+    let mut url = base_url();
 
-                fs::write(module_dir.join("index.html"), rendered_module).expect(
-                    "TODO gracefully handle failing to write index.html inside module's dir",
-                );
+    url.push_str(&package.path);
+
+    url
+}
+
+/// The names of the values and types the module exposes
+fn exposed_names(loaded_module: &LoadedModule, module_id: ModuleId) -> Vec<String> {
+    // The load only has the exposed values, so the types come from the module's header.
+    match loaded_module.sources.get(&module_id) {
+        Some((_, src)) => header_exposed_names(src),
+        None if module_id == loaded_module.module_id => loaded_module
+            .exposed_values
+            .iter()
+            .map(|symbol| symbol.as_str(&loaded_module.interns).to_string())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// The names an interface or hosted module exposes in its header.
+fn header_exposed_names(src: &str) -> Vec<String> {
+    let arena = Bump::new();
+
+    let exposes = match parse_header(&arena, State::new(src.as_bytes())) {
+        Ok((Module::Interface { header }, _)) => header.exposes,
+        Ok((Module::Hosted { header }, _)) => header.exposes,
+        _ => return Vec::new(),
+    };
+
+    exposes
+        .items
+        .iter()
+        .map(|exposed| exposed.value.extract_spaces().item.as_str().to_string())
+        .collect()
+}
+
+/// Finds the docs for the names used in a module's docs. They can be in any package of the site,
+/// or in the builtins.
+struct Links<'a> {
+    packages: &'a [PackageDocs],
+    /// The package the module is in. Its modules take precedence over modules with the same name
+    /// in other packages.
+    current_package: usize,
+    loaded_module: &'a LoadedModule,
+    /// The module whose docs the links are in
+    home: ModuleId,
+    scope: &'a Scope,
+}
+
+impl<'a> Links<'a> {
+    /// Finds the docs of a module, given its id in the load the links are in. Other packages were
+    /// loaded separately, so their ids for the same module differ; it is the module whose source
+    /// is the same file there. Builtins have the same id in every load.
+    fn find_module(
+        &self,
+        module_id: ModuleId,
+    ) -> Option<(
+        &'a PackageDocs,
+        &'a LoadedModule,
+        ModuleId,
+        &'a ModuleDocumentation,
+    )> {
+        let path = self
+            .loaded_module
+            .sources
+            .get(&module_id)
+            .map(|(path, _)| canonicalize(path));
+        let is_same_module = |loaded_module: &LoadedModule, id: ModuleId| {
+            if std::ptr::eq(loaded_module, self.loaded_module) || module_id.is_builtin() {
+                id == module_id
+            } else {
+                match (&path, loaded_module.sources.get(&id)) {
+                    (Some(path), Some((other_path, _))) => canonicalize(other_path) == *path,
+                    _ => false,
+                }
             }
+        };
+
+        let current_package = &self.packages[self.current_package];
+        let other_packages = self
+            .packages
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != self.current_package)
+            .map(|(_, package)| package);
+
+        std::iter::once(current_package)
+            .chain(other_packages)
+            .find_map(|package| {
+                package
+                    .modules()
+                    .find(|(loaded_module, id, _)| is_same_module(loaded_module, *id))
+                    .map(|(loaded_module, id, module)| (package, loaded_module, id, module))
+            })
+    }
+
+    /// The url of a module's docs, e.g. "/Str"
+    fn module_url(&self, module_id: ModuleId) -> Option<String> {
+        match self.find_module(module_id) {
+            Some((package, _, _, module)) => {
+                let mut url = package_url(package);
+
+                url.push_str(module.name.as_str());
+
+                Some(url)
+            }
+            None if module_id.is_builtin() => Some(format!(
+                "{}{}",
+                BUILTINS_URL,
+                self.loaded_module.interns.module_name(module_id).as_str()
+            )),
+            None => None,
         }
     }
 
-    println!("🎉 Docs generated in {}", build_dir.display());
+    /// The url of the docs for a type in a type annotation, e.g. `Str` or `Json.Decoder`
+    fn type_url(&self, type_name: &str) -> Option<String> {
+        let interns = &self.loaded_module.interns;
+
+        let (module_id, ident) = match type_name.rsplit_once('.') {
+            Some((module_name, ident)) => (interns.module_ids.get_id(&module_name.into())?, ident),
+            None => {
+                let symbol = self.scope.lookup_str(type_name, Region::zero()).ok()?;
+
+                (symbol.module_id(), type_name)
+            }
+        };
+
+        match self.find_module(module_id) {
+            Some((package, loaded_module, id, module)) => {
+                let is_documented = exposed_names(loaded_module, id)
+                    .iter()
+                    .any(|name| name == ident);
+
+                if is_documented {
+                    Some(format!("{}{}#{}", package_url(package), module.name, ident))
+                } else {
+                    None
+                }
+            }
+            None if module_id.is_builtin() => Some(format!(
+                "{}{}#{}",
+                BUILTINS_URL,
+                interns.module_name(module_id).as_str(),
+                ident
+            )),
+            None => None,
+        }
+    }
 }
 
 // converts plain-text code to highlighted html
@@ -166,6 +512,7 @@ pub fn syntax_highlight_top_level_defs(code_str: &str) -> DocsResult<String> {
 fn render_module_documentation(
     module: &ModuleDocumentation,
     loaded_module: &LoadedModule,
+    links: &Links,
 ) -> String {
     let mut buf = String::new();
 
@@ -178,7 +525,10 @@ fn render_module_documentation(
         .as_str(),
     );
 
-    let exposed_values = loaded_module.exposed_values_str();
+    let exposed_names = exposed_names(loaded_module, links.home);
+    let exposed_values: Vec<&str> = exposed_names.iter().map(String::as_str).collect();
+    let source = loaded_module.sources.get(&links.home);
+    let module_url = links.module_url(links.home).unwrap_or_default();
 
     for entry in &module.entries {
         let mut should_render_entry = true;
@@ -219,7 +569,25 @@ fn render_module_documentation(
                         }
                     }
 
-                    type_annotation_to_html(0, &mut content, type_ann);
+                    type_annotation_to_html(0, &mut content, type_ann, links);
+
+                    if let Some((_, src)) = source {
+                        let source_href = format!(
+                            "{}/{}#L{}",
+                            module_url,
+                            source::SOURCE_FILENAME,
+                            source::line_number(src, doc_def.region.start().offset)
+                        );
+
+                        content.push_str(
+                            html_to_string(
+                                "a",
+                                vec![("class", "source-link"), ("href", source_href.as_str())],
+                                "source",
+                            )
+                            .as_str(),
+                        );
+                    }
 
                     buf.push_str(
                         html_to_string(
@@ -237,6 +605,7 @@ fn render_module_documentation(
                                 &module.scope,
                                 docs.to_string(),
                                 loaded_module,
+                                links,
                             )
                             .as_str(),
                         );
@@ -250,6 +619,7 @@ fn render_module_documentation(
                         &module.scope,
                         docs.to_string(),
                         loaded_module,
+                        links,
                     );
                     buf.push_str(markdown.as_str());
                 }
@@ -260,6 +630,106 @@ fn render_module_documentation(
     buf
 }
 
+fn render_module_source(module: &ModuleDocumentation, src: &str, links: &Links) -> String {
+    let mut buf = String::new();
+
+    let module_url = links.module_url(links.home).unwrap_or_default();
+
+    buf.push_str(
+        html_to_string(
+            "h2",
+            vec![("class", "module-name")],
+            html_to_string(
+                "a",
+                vec![("href", module_url.as_str())],
+                module.name.as_str(),
+            )
+            .as_str(),
+        )
+        .as_str(),
+    );
+
+    buf.push_str(source::render_source(src).as_str());
+
+    buf
+}
+
+/// The package's README, and links to its modules and to the packages it depends on.
+fn render_package_landing_page(package: &PackageDocs, packages: &[PackageDocs]) -> String {
+    let mut buf = String::new();
+
+    if !package.documentation.docs.is_empty() {
+        let parser = pulldown_cmark::Parser::new(&package.documentation.docs);
+
+        pulldown_cmark::html::push_html(&mut buf, parser);
+    }
+
+    let mut module_links = String::new();
+
+    for (_, _, module) in package.modules() {
+        let mut href = package_url(package);
+
+        href.push_str(module.name.as_str());
+
+        module_links.push_str(
+            html_to_string(
+                "li",
+                vec![],
+                html_to_string("a", vec![("href", href.as_str())], module.name.as_str()).as_str(),
+            )
+            .as_str(),
+        );
+    }
+
+    buf.push_str(html_to_string("h2", vec![], "Modules").as_str());
+    buf.push_str(
+        html_to_string(
+            "ul",
+            vec![("class", "package-links")],
+            module_links.as_str(),
+        )
+        .as_str(),
+    );
+
+    if !package.packages.is_empty() {
+        let mut package_links = String::new();
+
+        for (shorthand, location) in package.packages.iter() {
+            let path = format!("{}{}/", package.path, shorthand);
+            let mut content = html_to_string("code", vec![], shorthand.as_str());
+
+            content.push(' ');
+
+            // Packages that weren't on disk have no docs in the site.
+            match packages.iter().find(|dependency| dependency.path == path) {
+                Some(dependency) => content.push_str(
+                    html_to_string(
+                        "a",
+                        vec![("href", package_url(dependency).as_str())],
+                        dependency.documentation.name.as_str(),
+                    )
+                    .as_str(),
+                ),
+                None => content.push_str(location.as_str()),
+            }
+
+            package_links.push_str(html_to_string("li", vec![], content.as_str()).as_str());
+        }
+
+        buf.push_str(html_to_string("h2", vec![], "Packages").as_str());
+        buf.push_str(
+            html_to_string(
+                "ul",
+                vec![("class", "package-links")],
+                package_links.as_str(),
+            )
+            .as_str(),
+        );
+    }
+
+    buf
+}
+
 fn html_to_string(tag_name: &str, attrs: Vec<(&str, &str)>, content: &str) -> String {
     let mut buf = String::new();
 
@@ -319,21 +789,23 @@ fn base_url() -> String {
     }
 }
 
-fn render_name_and_version(name: &str, version: &str) -> String {
+fn render_name_and_version(name: &str, version: &str, package_url: &str) -> String {
     let mut buf = String::new();
-    let mut url_str = base_url();
-
-    url_str.push_str(name);
 
     buf.push_str(
         html_to_string(
             "h1",
             vec![("class", "pkg-full-name")],
-            html_to_string("a", vec![("href", url_str.as_str())], name).as_str(),
+            html_to_string("a", vec![("href", package_url)], name).as_str(),
         )
         .as_str(),
     );
 
+    // Platforms don't have versions yet.
+    if version.is_empty() {
+        return buf;
+    }
+
     let mut versions_url_str = base_url();
 
     versions_url_str.push('/');
@@ -354,6 +826,7 @@ fn render_name_and_version(name: &str, version: &str) -> String {
 }
 
 fn render_sidebar<'a, I: Iterator<Item = (&'a ModuleDocumentation, Vec<String>)>>(
+    package_url: &str,
     modules: I,
 ) -> String {
     let mut buf = String::new();
@@ -364,7 +837,7 @@ fn render_sidebar<'a, I: Iterator<Item = (&'a ModuleDocumentation, Vec<String>)>
         let name = module.name.as_str();
 
         let href = {
-            let mut href_buf = base_url();
+            let mut href_buf = package_url.to_string();
             href_buf.push_str(name);
             href_buf
         };
@@ -427,6 +900,60 @@ fn render_sidebar<'a, I: Iterator<Item = (&'a ModuleDocumentation, Vec<String>)>
     buf
 }
 
+/// The search index of the values and types the modules expose, as JSON
+pub fn render_search_index(loaded_modules: &[LoadedModule]) -> String {
+    let mut entries = Vec::new();
+    let url = base_url();
+
+    for loaded_module in loaded_modules {
+        let module_id = loaded_module.module_id;
+
+        if let Some(module) = loaded_module.documentation.get(&module_id) {
+            push_search_index_entries(&mut entries, &url, loaded_module, module_id, module);
+        }
+    }
+
+    serde_json::Value::Array(entries).to_string()
+}
+
+fn render_package_search_index(packages: &[PackageDocs]) -> String {
+    let mut entries = Vec::new();
+
+    for package in packages {
+        let package_url = package_url(package);
+
+        for (loaded_module, module_id, module) in package.modules() {
+            push_search_index_entries(&mut entries, &package_url, loaded_module, module_id, module);
+        }
+    }
+
+    serde_json::Value::Array(entries).to_string()
+}
+
+fn push_search_index_entries(
+    entries: &mut Vec<serde_json::Value>,
+    package_url: &str,
+    loaded_module: &LoadedModule,
+    module_id: ModuleId,
+    module: &ModuleDocumentation,
+) {
+    let exposed_names = exposed_names(loaded_module, module_id);
+
+    for entry in &module.entries {
+        if let DocEntry::DocDef(doc_def) = entry {
+            if exposed_names.contains(&doc_def.name) {
+                let mut url = package_url.to_string();
+
+                url.push_str(module.name.as_str());
+                url.push('#');
+                url.push_str(doc_def.name.as_str());
+
+                entries.push(search_index_entry(module.name.as_str(), doc_def, &url));
+            }
+        }
+    }
+}
+
 pub fn load_modules_for_files(filenames: Vec<PathBuf>) -> Vec<LoadedModule> {
     let arena = Bump::new();
     let mut modules = Vec::with_capacity(filenames.len());
@@ -437,6 +964,7 @@ pub fn load_modules_for_files(filenames: Vec<PathBuf>) -> Vec<LoadedModule> {
             render: roc_reporting::report::RenderTarget::ColorTerminal,
             threading: Threading::AllAvailable,
             exec_mode: ExecutionMode::Check,
+            cache_dir: None,
         };
         match roc_load::load_and_typecheck(&arena, filename, Default::default(), load_config) {
             Ok(loaded) => modules.push(loaded),
@@ -464,7 +992,12 @@ fn new_line(buf: &mut String) {
 }
 
 // html is written to buf
-fn type_annotation_to_html(indent_level: usize, buf: &mut String, type_ann: &TypeAnnotation) {
+fn type_annotation_to_html(
+    indent_level: usize,
+    buf: &mut String,
+    type_ann: &TypeAnnotation,
+    links: &Links,
+) {
     let is_multiline = should_be_multiline(type_ann);
     match type_ann {
         TypeAnnotation::TagUnion { tags, extension } => {
@@ -497,7 +1030,7 @@ fn type_annotation_to_html(indent_level: usize, buf: &mut String, type_ann: &Typ
 
                 for type_value in &tag.values {
                     buf.push(' ');
-                    type_annotation_to_html(next_indent_level, buf, type_value);
+                    type_annotation_to_html(next_indent_level, buf, type_value, links);
                 }
 
                 if is_multiline {
@@ -517,20 +1050,25 @@ fn type_annotation_to_html(indent_level: usize, buf: &mut String, type_ann: &Typ
 
             buf.push(']');
 
-            type_annotation_to_html(indent_level, buf, extension);
+            type_annotation_to_html(indent_level, buf, extension, links);
         }
         TypeAnnotation::BoundVariable(var_name) => {
             buf.push_str(var_name);
         }
         TypeAnnotation::Apply { name, parts } => {
+            let name_html = match links.type_url(name) {
+                Some(url) => html_to_string("a", vec![("href", url.as_str())], name),
+                None => name.to_string(),
+            };
+
             if parts.is_empty() {
-                buf.push_str(name);
+                buf.push_str(&name_html);
             } else {
                 buf.push('(');
-                buf.push_str(name);
+                buf.push_str(&name_html);
                 for part in parts {
                     buf.push(' ');
-                    type_annotation_to_html(indent_level, buf, part);
+                    type_annotation_to_html(indent_level, buf, part, links);
                 }
                 buf.push(')');
             }
@@ -573,13 +1111,13 @@ fn type_annotation_to_html(indent_level: usize, buf: &mut String, type_ann: &Typ
                         type_annotation, ..
                     } => {
                         buf.push_str(" : ");
-                        type_annotation_to_html(next_indent_level, buf, type_annotation);
+                        type_annotation_to_html(next_indent_level, buf, type_annotation, links);
                     }
                     RecordField::OptionalField {
                         type_annotation, ..
                     } => {
                         buf.push_str(" ? ");
-                        type_annotation_to_html(next_indent_level, buf, type_annotation);
+                        type_annotation_to_html(next_indent_level, buf, type_annotation, links);
                     }
                     RecordField::LabelOnly { .. } => {}
                 }
@@ -601,7 +1139,7 @@ fn type_annotation_to_html(indent_level: usize, buf: &mut String, type_ann: &Typ
 
             buf.push('}');
 
-            type_annotation_to_html(indent_level, buf, extension);
+            type_annotation_to_html(indent_level, buf, extension, links);
         }
         TypeAnnotation::Function { args, output } => {
             let mut peekable_args = args.iter().peekable();
@@ -613,7 +1151,7 @@ fn type_annotation_to_html(indent_level: usize, buf: &mut String, type_ann: &Typ
                     indent(buf, indent_level + 1);
                 }
 
-                type_annotation_to_html(indent_level, buf, arg);
+                type_annotation_to_html(indent_level, buf, arg, links);
 
                 if peekable_args.peek().is_some() {
                     buf.push_str(", ");
@@ -633,7 +1171,7 @@ fn type_annotation_to_html(indent_level: usize, buf: &mut String, type_ann: &Typ
                 next_indent_level += 1;
             }
 
-            type_annotation_to_html(next_indent_level, buf, output);
+            type_annotation_to_html(next_indent_level, buf, output, links);
         }
         TypeAnnotation::Ability { members: _ } => {
             // TODO(abilities): fill me in
@@ -726,15 +1264,15 @@ struct DocUrl {
 }
 
 fn doc_url<'a>(
-    home: ModuleId,
     exposed_values: &[&str],
     dep_idents: &IdentIdsByModule,
     scope: &Scope,
     interns: &'a Interns,
     mut module_name: &'a str,
     ident: &str,
+    links: &Links,
 ) -> DocUrl {
-    if module_name.is_empty() {
+    let module_id = if module_name.is_empty() {
         // This is an unqualified lookup, so look for the ident
         // in scope!
         match scope.lookup_str(ident, Region::zero()) {
@@ -744,6 +1282,8 @@ fn doc_url<'a>(
                 // module - for example, if this is in scope from an
                 // unqualified import.
                 module_name = symbol.module_string(interns);
+
+                symbol.module_id()
             }
             Err(_) => {
                 // TODO return Err here
@@ -758,7 +1298,7 @@ fn doc_url<'a>(
             Some(module_id) => {
                 // You can do qualified lookups on your own module, e.g.
                 // if I'm in the Foo module, I can do a `Foo.bar` lookup.
-                if module_id == home {
+                if module_id == links.home {
                     // Check to see if the value is exposed in this module.
                     // If it's not exposed, then we can't link to it!
                     if !exposed_values.contains(&ident) {
@@ -776,9 +1316,8 @@ fn doc_url<'a>(
                         Some(_) => {
                             // This is a valid symbol for this dependency,
                             // so proceed using the current module's name.
-                            //
-                            // TODO: In the future, this is where we'll
-                            // incorporate the package name into the link.
+                            // The link will go to whichever package
+                            // (or the builtins) documents that module.
                         }
                         _ => {
                             // TODO return Err here
@@ -788,20 +1327,26 @@ fn doc_url<'a>(
                         }
                     }
                 }
+
+                module_id
             }
             None => {
                 // TODO return Err here
                 panic!("Tried to generate a doc link for `{}.{}` but the `{}` module was not imported!", module_name, ident, module_name);
             }
         }
-    }
-
-    let mut url = base_url();
+    };
 
     // Example:
     //
     // module_name: "Str", ident: "join" => "/Str#join"
-    url.push_str(module_name);
+    let mut url = links.module_url(module_id).unwrap_or_else(|| {
+        let mut url = base_url();
+
+        url.push_str(module_name);
+
+        url
+    });
     url.push('#');
     url.push_str(ident);
 
@@ -816,6 +1361,7 @@ fn markdown_to_html(
     scope: &Scope,
     markdown: String,
     loaded_module: &LoadedModule,
+    links: &Links,
 ) -> String {
     use pulldown_cmark::{BrokenLink, CodeBlockKind, CowStr, Event, LinkType, Tag::*};
 
@@ -842,13 +1388,13 @@ fn markdown_to_html(
                         match iter.next() {
                             Some(symbol_name) if iter.next().is_none() => {
                                 let DocUrl { url, title } = doc_url(
-                                    loaded_module.module_id,
                                     exposed_values,
                                     &loaded_module.dep_idents,
                                     scope,
                                     &loaded_module.interns,
                                     module_name,
                                     symbol_name,
+                                    links,
                                 );
 
                                 Some((url.into(), title.into()))
@@ -865,13 +1411,13 @@ fn markdown_to_html(
                         // This looks like a tag name, but it could
                         // be a type alias that's in scope, e.g. [I64]
                         let DocUrl { url, title } = doc_url(
-                            loaded_module.module_id,
                             exposed_values,
                             &loaded_module.dep_idents,
                             scope,
                             &loaded_module.interns,
                             "",
                             type_name,
+                            links,
                         );
 
                         Some((url.into(), title.into()))
@@ -0,0 +1,90 @@
+//! Finding the modules of a platform from its root module, so the whole platform can be documented
+//! as one package, together with the packages it depends on.
+//!
+//! Platforms are the only packages with a root module in their own header for now. A directory
+//! of `.roc` files is still documented file by file.
+use bumpalo::Bump;
+use roc_parse::ast::{ExtractSpaces, Module};
+use roc_parse::module::parse_header;
+use roc_parse::state::State;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const ROOT_FILENAME: &str = "main.roc";
+const README_FILENAME: &str = "README.md";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageRoot {
+    /// The name in the root module's header, e.g. `"examples/cli"`
+    pub name: String,
+    pub root_file: PathBuf,
+    /// The files of the modules the header exposes. If it exposes none, the platform's modules
+    /// that the root module imports are documented instead, because apps can import any of them.
+    pub module_files: Vec<PathBuf>,
+    /// The shorthand and location of each package in the header's `packages`
+    pub packages: Vec<(String, String)>,
+    /// The contents of the README next to the root module, if there is one
+    pub readme: Option<String>,
+}
+
+/// The package that `path` is the root module of, or the package of the `main.roc` in the
+/// directory `path`.
+pub fn read_package_root(path: &Path) -> Option<PackageRoot> {
+    let root_file = if path.is_dir() {
+        path.join(ROOT_FILENAME)
+    } else {
+        path.to_path_buf()
+    };
+
+    let src = fs::read_to_string(&root_file).ok()?;
+    let arena = Bump::new();
+
+    let header = match parse_header(&arena, State::new(src.as_bytes())) {
+        Ok((Module::Platform { header }, _)) => header,
+        _ => return None,
+    };
+
+    let root_dir = root_file.parent().unwrap_or_else(|| Path::new("."));
+
+    let module_files = header
+        .exposes
+        .items
+        .iter()
+        .map(|exposed| module_file(root_dir, exposed.value.extract_spaces().item.as_str()))
+        .collect();
+
+    let packages = header
+        .packages
+        .items
+        .iter()
+        .map(|entry| {
+            let entry = entry.value.extract_spaces().item;
+
+            (
+                entry.shorthand.to_string(),
+                entry.package_name.value.as_str().to_string(),
+            )
+        })
+        .collect();
+
+    Some(PackageRoot {
+        name: header.name.value.as_str().to_string(),
+        module_files,
+        packages,
+        readme: fs::read_to_string(root_dir.join(README_FILENAME)).ok(),
+        root_file,
+    })
+}
+
+/// `Foo.Bar` is in `Foo/Bar.roc`
+fn module_file(root_dir: &Path, module_name: &str) -> PathBuf {
+    let mut path = root_dir.to_path_buf();
+
+    for part in module_name.split('.') {
+        path.push(part);
+    }
+
+    path.set_extension("roc");
+
+    path
+}
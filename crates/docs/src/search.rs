@@ -0,0 +1,245 @@
+//! The search index, which `search.js` fetches the first time someone searches the docs.
+//!
+//! Every exposed value and type gets an entry with its type signature and the text of its doc
+//! comment. For type-directed search, the arguments and output of a signature are also written
+//! out separately, with their type variables renamed to `a`, `b`, `c`... in the order they first
+//! appear. That way `List elem -> Nat` and `List a -> Nat` are the same signature.
+use roc_load::docs::{DocDef, RecordField, TypeAnnotation};
+use serde_json::{json, Value};
+
+pub(crate) const SEARCH_INDEX_FILENAME: &str = "search-index.json";
+
+/// The search index entry for an exposed def, which is documented at `url`.
+pub fn search_index_entry(module_name: &str, doc_def: &DocDef, url: &str) -> Value {
+    let signature = type_to_string(&doc_def.type_annotation, Parens::NotNeeded, &mut |var| {
+        var.to_string()
+    });
+
+    let mut vars = Vec::new();
+    let mut normalize = |var: &str| normalized_var(&mut vars, var);
+
+    let (args, output) = match &doc_def.type_annotation {
+        // Types can be searched for by name, but type-directed search is only for values.
+        _ if is_type_name(&doc_def.name) => (Vec::new(), String::new()),
+        TypeAnnotation::Function { args, output } => {
+            let args = args
+                .iter()
+                .map(|arg| type_to_string(arg, Parens::InFunction, &mut normalize))
+                .collect();
+
+            (
+                args,
+                type_to_string(output, Parens::InFunction, &mut normalize),
+            )
+        }
+        other => (
+            Vec::new(),
+            type_to_string(other, Parens::NotNeeded, &mut normalize),
+        ),
+    };
+
+    json!({
+        "module": module_name,
+        "name": doc_def.name,
+        "url": url,
+        "typeVars": doc_def.type_vars,
+        "signature": signature,
+        "args": args,
+        "output": output,
+        "docs": doc_def.docs.as_deref().map(markdown_to_text).unwrap_or_default(),
+    })
+}
+
+fn is_type_name(name: &str) -> bool {
+    name.starts_with(char::is_uppercase)
+}
+
+fn normalized_var(vars: &mut Vec<String>, var: &str) -> String {
+    let index = match vars.iter().position(|seen| seen == var) {
+        Some(index) => index,
+        None => {
+            vars.push(var.to_string());
+
+            vars.len() - 1
+        }
+    };
+
+    if index < 26 {
+        ((b'a' + index as u8) as char).to_string()
+    } else {
+        format!("a{}", index)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Parens {
+    NotNeeded,
+    InFunction,
+    InApply,
+}
+
+fn type_to_string(
+    type_ann: &TypeAnnotation,
+    parens: Parens,
+    var_name: &mut dyn FnMut(&str) -> String,
+) -> String {
+    let mut buf = String::new();
+
+    write_type(&mut buf, type_ann, parens, var_name);
+
+    buf
+}
+
+fn write_type(
+    buf: &mut String,
+    type_ann: &TypeAnnotation,
+    parens: Parens,
+    var_name: &mut dyn FnMut(&str) -> String,
+) {
+    match type_ann {
+        TypeAnnotation::TagUnion { tags, extension } => {
+            buf.push('[');
+
+            for (index, tag) in tags.iter().enumerate() {
+                if index > 0 {
+                    buf.push_str(", ");
+                }
+
+                buf.push_str(&tag.name);
+
+                for value in &tag.values {
+                    buf.push(' ');
+                    write_type(buf, value, Parens::InApply, var_name);
+                }
+            }
+
+            buf.push(']');
+
+            write_type(buf, extension, Parens::InApply, var_name);
+        }
+        TypeAnnotation::Function { args, output } => {
+            if parens != Parens::NotNeeded {
+                buf.push('(');
+            }
+
+            for (index, arg) in args.iter().enumerate() {
+                if index > 0 {
+                    buf.push_str(", ");
+                }
+
+                write_type(buf, arg, Parens::InFunction, var_name);
+            }
+
+            buf.push_str(" -> ");
+
+            write_type(buf, output, Parens::InFunction, var_name);
+
+            if parens != Parens::NotNeeded {
+                buf.push(')');
+            }
+        }
+        TypeAnnotation::ObscuredTagUnion => {
+            buf.push_str("[@..]");
+        }
+        TypeAnnotation::ObscuredRecord => {
+            buf.push_str("{ @.. }");
+        }
+        TypeAnnotation::BoundVariable(var) => {
+            buf.push_str(&var_name(var));
+        }
+        TypeAnnotation::Apply { name, parts } => {
+            let needs_parens = parens == Parens::InApply && !parts.is_empty();
+
+            if needs_parens {
+                buf.push('(');
+            }
+
+            buf.push_str(name);
+
+            for part in parts {
+                buf.push(' ');
+                write_type(buf, part, Parens::InApply, var_name);
+            }
+
+            if needs_parens {
+                buf.push(')');
+            }
+        }
+        TypeAnnotation::Record { fields, extension } => {
+            if fields.is_empty() {
+                buf.push_str("{}");
+            } else {
+                buf.push_str("{ ");
+
+                for (index, field) in fields.iter().enumerate() {
+                    if index > 0 {
+                        buf.push_str(", ");
+                    }
+
+                    match field {
+                        RecordField::RecordField {
+                            name,
+                            type_annotation,
+                        } => {
+                            buf.push_str(name);
+                            buf.push_str(" : ");
+                            write_type(buf, type_annotation, Parens::NotNeeded, var_name);
+                        }
+                        RecordField::OptionalField {
+                            name,
+                            type_annotation,
+                        } => {
+                            buf.push_str(name);
+                            buf.push_str(" ? ");
+                            write_type(buf, type_annotation, Parens::NotNeeded, var_name);
+                        }
+                        RecordField::LabelOnly { name } => {
+                            buf.push_str(name);
+                        }
+                    }
+                }
+
+                buf.push_str(" }");
+            }
+
+            write_type(buf, extension, Parens::InApply, var_name);
+        }
+        TypeAnnotation::Ability { members } => {
+            buf.push_str("has [");
+
+            for (index, member) in members.iter().enumerate() {
+                if index > 0 {
+                    buf.push_str(", ");
+                }
+
+                buf.push_str(&member.name);
+            }
+
+            buf.push(']');
+        }
+        TypeAnnotation::Wildcard => {
+            buf.push('*');
+        }
+        TypeAnnotation::NoTypeAnn => {}
+    }
+}
+
+/// The text of a doc comment, without the markdown.
+fn markdown_to_text(markdown: &str) -> String {
+    use pulldown_cmark::{Event, Parser, Tag};
+
+    let mut text = String::new();
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Text(string) | Event::Code(string) => text.push_str(&string),
+            Event::End(
+                Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link(..) | Tag::Image(..),
+            ) => {}
+            Event::SoftBreak | Event::HardBreak | Event::End(_) => text.push(' '),
+            _ => {}
+        }
+    }
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,152 @@
+//! Source pages, which show a module's code with syntax highlighting and line numbers, so every
+//! documented def can link to the line it is defined on.
+//!
+//! The highlighting uses the tokenizer from `roc_highlight` rather than its parser. Everything
+//! between the tokens (spaces, newlines and comments) is kept as it is, so the page shows the
+//! file exactly as it was written, even if parts of it don't parse.
+use roc_highlight::tokenizer::{full_tokenize, Token};
+
+pub(crate) const SOURCE_FILENAME: &str = "source.html";
+
+/// The module's source code as html, with a `<span>` around each token. The text of the html is
+/// exactly `src`.
+pub fn syntax_highlight_source(src: &str) -> String {
+    let token_table = full_tokenize(src);
+    let mut buf = String::with_capacity(src.len() * 2);
+    let mut offset = 0;
+
+    let tokens = token_table
+        .tokens
+        .iter()
+        .zip(token_table.offsets.iter())
+        .zip(token_table.lengths.iter());
+
+    for ((token, start), length) in tokens {
+        let start = *start;
+        let end = start + *length;
+
+        // Indents and dedents take up no space. The tokenizer never goes back over text it already
+        // tokenized, but if it did, we'd rather show some text unhighlighted than twice.
+        if *length == 0
+            || start < offset
+            || end > src.len()
+            || !src.is_char_boundary(start)
+            || !src.is_char_boundary(end)
+        {
+            continue;
+        }
+
+        push_between_tokens(&mut buf, &src[offset..start]);
+
+        let text = &src[start..end];
+
+        match token_class(*token) {
+            Some(class) => push_span(&mut buf, class, text),
+            None => push_escaped(&mut buf, text),
+        }
+
+        offset = end;
+    }
+
+    push_between_tokens(&mut buf, &src[offset..]);
+
+    buf
+}
+
+/// The html for a source page: the line numbers, which can be linked to as e.g. `#L12`, next to
+/// the highlighted code.
+pub(crate) fn render_source(src: &str) -> String {
+    let mut line_numbers = String::new();
+
+    for line in 1..=src.lines().count().max(1) {
+        line_numbers.push_str(&format!(
+            "<a id=\"L{}\" href=\"#L{}\">{}</a>\n",
+            line, line, line
+        ));
+    }
+
+    format!(
+        "<div class=\"source-code\"><pre class=\"source-line-numbers\">{}</pre><pre class=\"source-lines\"><code>{}</code></pre></div>",
+        line_numbers,
+        syntax_highlight_source(src)
+    )
+}
+
+/// The line the byte `offset` is on, counting from 1 like the line numbers on a source page.
+pub(crate) fn line_number(src: &str, offset: u32) -> usize {
+    let offset = (offset as usize).min(src.len());
+
+    src.as_bytes()[..offset]
+        .iter()
+        .filter(|byte| **byte == b'\n')
+        .count()
+        + 1
+}
+
+/// Spaces and newlines are written as they are, and comments get their own span.
+fn push_between_tokens(buf: &mut String, text: &str) {
+    let mut rest = text;
+
+    while let Some(start) = rest.find('#') {
+        push_escaped(buf, &rest[..start]);
+
+        let comment = &rest[start..];
+        let end = comment.find('\n').unwrap_or(comment.len());
+        let class = if comment.starts_with("##") {
+            "docs-comment"
+        } else {
+            "comment"
+        };
+
+        push_span(buf, class, &comment[..end]);
+
+        rest = &comment[end..];
+    }
+
+    push_escaped(buf, rest);
+}
+
+fn token_class(token: Token) -> Option<&'static str> {
+    use Token::*;
+
+    match token {
+        LowercaseIdent => Some("lowercase-ident"),
+        UppercaseIdent => Some("uppercase-ident"),
+        KeywordIf | KeywordThen | KeywordElse | KeywordWhen | KeywordAs | KeywordIs
+        | KeywordExpect | KeywordApp | KeywordInterface | KeywordPackages | KeywordImports
+        | KeywordProvides | KeywordTo | KeywordExposes | KeywordEffects | KeywordPlatform
+        | KeywordRequires => Some("keyword-ident"),
+        String => Some("string"),
+        NumberBase | Number => Some("number"),
+        OpenParen | CloseParen | OpenCurly | CloseCurly | OpenSquare | CloseSquare => {
+            Some("bracket")
+        }
+        Comma => Some("comma"),
+        Colon | OpPlus | OpMinus | OpSlash | OpPercent | OpCaret | OpGreaterThan | OpLessThan
+        | OpAssignment | OpPizza | OpEquals | OpNotEquals | OpGreaterThanOrEq | OpLessThanOrEq
+        | OpAnd | OpOr | OpDoubleSlash | OpBackpassing | QuestionMark | Ampersand | Pipe | Bang
+        | LambdaStart | Arrow | FatArrow | Asterisk => Some("operator"),
+        MalformedIdent | OpenIndent | CloseIndent | SameIndent | TodoNextThing | Malformed
+        | MalformedOperator | Underscore | Dot | SpaceDot => None,
+    }
+}
+
+fn push_span(buf: &mut String, class: &str, text: &str) {
+    buf.push_str("<span class=\"syntax-");
+    buf.push_str(class);
+    buf.push_str("\">");
+    push_escaped(buf, text);
+    buf.push_str("</span>");
+}
+
+fn push_escaped(buf: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '<' => buf.push_str("&lt;"),
+            '>' => buf.push_str("&gt;"),
+            '&' => buf.push_str("&amp;"),
+            '"' => buf.push_str("&quot;"),
+            _ => buf.push(ch),
+        }
+    }
+}
@@ -1,16 +1,33 @@
 (() => {
   let sidebar = document.getElementById("sidebar-nav");
   let searchBox = document.getElementById("module-search");
+  let searchResults = document.getElementById("search-results");
 
-  function search() {
-    let text = searchBox.value.toLowerCase(); // Search is case-insensitive.
+  // The index is next to this script. It can be big, so we only fetch it once someone searches.
+  let searchIndexUrl = new URL("search-index.json", document.currentScript.src);
+  let searchIndex = null;
+
+  const MAX_RESULTS = 30;
+
+  function loadSearchIndex() {
+    if (searchIndex === null) {
+      searchIndex = fetch(searchIndexUrl)
+        .then((response) => response.json())
+        .catch(() => []);
+    }
+
+    return searchIndex;
+  }
 
+  function filterSidebar(text) {
     if (text === "") {
       // Un-hide everything
       sidebar.querySelectorAll(".sidebar-entry a").forEach((entry) => entry.classList.remove("hidden"));
 
       // Re-hide all the sub-entries except for those of the current module
-      let currentModuleName = document.querySelector('.module-name').textContent;
+      // Landing pages have no current module
+      let currentModule = document.querySelector('.module-name');
+      let currentModuleName = currentModule === null ? null : currentModule.textContent;
 
       sidebar.querySelectorAll(".sidebar-entry").forEach((entry) => {
         let entryName = entry.querySelector('.sidebar-module-link').textContent;
@@ -41,6 +58,256 @@
     }
   }
 
+  // How well the query's characters match the name, in order but not necessarily next to each
+  // other, e.g. "wth" matches "walkUntil" and "withCapacity". Higher is better; null is no match.
+  function fuzzyScore(query, name) {
+    let lowerName = name.toLowerCase();
+    let score = 0;
+    let nameIndex = 0;
+    let previousMatch = -2;
+
+    for (let char of query) {
+      let found = lowerName.indexOf(char, nameIndex);
+
+      if (found === -1) {
+        return null;
+      }
+
+      if (found === previousMatch + 1) {
+        score += 3;
+      }
+
+      // The start of a word, e.g. the "C" in "withCapacity", or the name after "List."
+      if (found === 0 || name[found - 1] === "." || name[found] !== lowerName[found]) {
+        score += 2;
+      }
+
+      score -= (found - nameIndex) * 0.1;
+      previousMatch = found;
+      nameIndex = found + 1;
+    }
+
+    if (lowerName === query) {
+      score += 20;
+    } else if (lowerName.startsWith(query)) {
+      score += 10;
+    }
+
+    return score;
+  }
+
+  function nameScore(query, entry) {
+    let scores = [
+      fuzzyScore(query, entry.name),
+      fuzzyScore(query, entry.module + "." + entry.name),
+    ].filter((score) => score !== null);
+
+    if (scores.length > 0) {
+      return Math.max(...scores);
+    }
+
+    // Fall back on the text of the doc comments, which only match whole words.
+    let docs = entry.docs.toLowerCase();
+    let words = query.split(/\s+/).filter((word) => word !== "");
+
+    if (words.length > 0 && words.every((word) => docs.includes(word))) {
+      return 0;
+    }
+
+    return null;
+  }
+
+  // Splits a type into names and symbols, so `List (List a)` and `List(List a)` are the same.
+  function tokenize(type) {
+    return type.match(/[A-Za-z_][A-Za-z0-9_]*(\.[A-Za-z_][A-Za-z0-9_]*)*|\S/g) || [];
+  }
+
+  // Splits the tokens at the commas and arrows that aren't nested in brackets.
+  function splitTopLevel(tokens, separator) {
+    let parts = [[]];
+    let depth = 0;
+
+    for (let index = 0; index < tokens.length; index++) {
+      let token = tokens[index];
+
+      if (token === "(" || token === "[" || token === "{") {
+        depth += 1;
+      } else if (token === ")" || token === "]" || token === "}") {
+        depth -= 1;
+      }
+
+      if (depth === 0 && separator === "->" && token === "-" && tokens[index + 1] === ">") {
+        parts.push([]);
+        index += 1;
+      } else if (depth === 0 && token === separator) {
+        parts.push([]);
+      } else {
+        parts[parts.length - 1].push(token);
+      }
+    }
+
+    return parts;
+  }
+
+  // Renames the type variables to a, b, c... in the order they first appear, the same way the
+  // index does. Lowercase names are type variables, unless they are record field names.
+  function normalizeTypes(tokenLists) {
+    let vars = [];
+
+    return tokenLists.map((tokens) =>
+      tokens
+        .map((token, index) => {
+          let isVar = /^[a-z]/.test(token) && !token.includes(".") && tokens[index + 1] !== ":" && tokens[index + 1] !== "?";
+
+          if (!isVar) {
+            return token;
+          }
+
+          if (!vars.includes(token)) {
+            vars.push(token);
+          }
+
+          let varIndex = vars.indexOf(token);
+
+          return varIndex < 26 ? String.fromCharCode(97 + varIndex) : "a" + varIndex;
+        })
+        .join(" ")
+    );
+  }
+
+  // A query like `List a -> Nat`. Either side of the arrow can be left out.
+  function parseTypeQuery(text) {
+    let sides = splitTopLevel(tokenize(text), "->");
+
+    if (sides.length !== 2) {
+      return null;
+    }
+
+    let args = sides[0].length === 0 ? [] : splitTopLevel(sides[0], ",");
+    let normalized = normalizeTypes(args.concat([sides[1]]));
+
+    return {
+      args: normalized.slice(0, args.length),
+      output: sides[1].length === 0 ? null : normalized[args.length],
+    };
+  }
+
+  function typeScore(query, entry) {
+    if (entry.output === "") {
+      return null;
+    }
+
+    let args = entry.args.map((arg) => tokenize(arg).join(" "));
+    let output = tokenize(entry.output).join(" ");
+
+    if (query.output !== null && query.output !== output) {
+      return null;
+    }
+
+    if (query.args.length === 0) {
+      return 1;
+    }
+
+    if (query.args.length === args.length && query.args.every((arg, index) => arg === args[index])) {
+      return 10;
+    }
+
+    let remaining = args.slice();
+    let allFound = query.args.every((arg) => {
+      let index = remaining.indexOf(arg);
+
+      if (index === -1) {
+        return false;
+      }
+
+      remaining.splice(index, 1);
+
+      return true;
+    });
+
+    if (!allFound) {
+      return null;
+    }
+
+    // The same arguments in a different order, or some of the arguments
+    return remaining.length === 0 ? 8 : 5;
+  }
+
+  function renderResult(entry) {
+    let result = document.createElement("a");
+    result.classList.add("search-result");
+    result.href = entry.url;
+
+    let name = document.createElement("span");
+    name.classList.add("search-result-name");
+    name.textContent = [entry.module + "." + entry.name].concat(entry.typeVars).join(" ");
+    result.appendChild(name);
+
+    if (entry.signature !== "") {
+      let signature = document.createElement("code");
+      signature.classList.add("search-result-signature");
+      signature.textContent = " : " + entry.signature;
+      result.appendChild(signature);
+    }
+
+    if (entry.docs !== "") {
+      let docs = document.createElement("p");
+      docs.classList.add("search-result-docs");
+      docs.textContent = entry.docs.length > 120 ? entry.docs.slice(0, 120) + "…" : entry.docs;
+      result.appendChild(docs);
+    }
+
+    return result;
+  }
+
+  function showResults(text, entries) {
+    // The index may have taken a while to load, and the query changed in the meantime.
+    if (searchBox.value.trim().toLowerCase() !== text) {
+      return;
+    }
+
+    let typeQuery = text.includes("->") ? parseTypeQuery(searchBox.value.trim()) : null;
+
+    let results = entries
+      .map((entry) => ({
+        entry: entry,
+        score: typeQuery === null ? nameScore(text, entry) : typeScore(typeQuery, entry),
+      }))
+      .filter((result) => result.score !== null)
+      .sort((a, b) => b.score - a.score)
+      .slice(0, MAX_RESULTS);
+
+    searchResults.replaceChildren(...results.map((result) => renderResult(result.entry)));
+
+    if (results.length === 0) {
+      let empty = document.createElement("p");
+      empty.classList.add("search-result-empty");
+      empty.textContent = "No results";
+      searchResults.appendChild(empty);
+    }
+
+    searchResults.classList.remove("hidden");
+  }
+
+  function search() {
+    let text = searchBox.value.trim().toLowerCase(); // Search is case-insensitive.
+
+    if (text === "") {
+      searchResults.classList.add("hidden");
+      searchResults.replaceChildren();
+      filterSidebar(text);
+      return;
+    }
+
+    // Type queries don't look like any module or entry names.
+    if (!text.includes("->")) {
+      filterSidebar(text);
+    }
+
+    loadSearchIndex().then((entries) => showResults(text, entries));
+  }
+
+  searchBox.addEventListener("focus", loadSearchIndex);
   searchBox.addEventListener("input", search);
 
   search();
@@ -0,0 +1,13 @@
+interface Shape
+    exposes [Shape, area]
+    imports []
+
+## A shape on the plane
+Shape : [Circle F64, Rect F64 F64]
+
+## The area of a shape
+area : Shape -> F64
+area = \shape ->
+    when shape is
+        Circle radius -> 3.14 * radius * radius
+        Rect width height -> width * height
@@ -0,0 +1,13 @@
+interface Greeting
+    exposes [Greeting, greet, shout]
+    imports []
+
+## A friendly message
+Greeting : Str
+
+## Greet someone by their name.
+greet : Str -> Greeting
+greet = \name -> Str.concat "Hello, " name
+
+shout : Greeting -> Str
+shout = \greeting -> Str.concat greeting "!"
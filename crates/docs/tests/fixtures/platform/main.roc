@@ -0,0 +1,9 @@
+platform "test/greetings"
+    requires {} { main : Str }
+    exposes [Greeting]
+    packages { util: "util/main.roc" }
+    imports [Greeting]
+    provides [mainForHost]
+
+mainForHost : Str
+mainForHost = Greeting.greet main
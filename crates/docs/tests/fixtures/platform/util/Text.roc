@@ -0,0 +1,9 @@
+interface Text
+    exposes [Text, concat]
+    imports []
+
+## Some text
+Text : Str
+
+concat : Text, Text -> Text
+concat = \first, second -> Str.concat first second
@@ -0,0 +1,9 @@
+platform "test/util"
+    requires {} { main : Str }
+    exposes [Text]
+    packages {}
+    imports []
+    provides [mainForHost]
+
+mainForHost : Str
+mainForHost = main
@@ -0,0 +1,98 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[cfg(test)]
+mod package_docs {
+    use roc_docs::{generate_package_docs_html, read_package_root, syntax_highlight_source};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    fn fixture(path: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join(path)
+    }
+
+    fn read(build_dir: &Path, path: &str) -> String {
+        fs::read_to_string(build_dir.join(path))
+            .unwrap_or_else(|err| panic!("Could not read {}: {}", path, err))
+    }
+
+    #[test]
+    fn platform_root() {
+        let root = read_package_root(&fixture("platform")).unwrap();
+
+        assert_eq!(root.name, "test/greetings");
+        assert_eq!(root.root_file, fixture("platform").join("main.roc"));
+        assert_eq!(
+            root.module_files,
+            vec![fixture("platform").join("Greeting.roc")]
+        );
+        assert_eq!(
+            root.packages,
+            vec![("util".to_string(), "util/main.roc".to_string())]
+        );
+        assert!(root.readme.unwrap().starts_with("# Greetings"));
+    }
+
+    #[test]
+    fn interface_is_not_a_package_root() {
+        assert_eq!(read_package_root(&fixture("platform/Greeting.roc")), None);
+        assert_eq!(read_package_root(&fixture("does-not-exist")), None);
+    }
+
+    #[test]
+    fn highlighted_source() {
+        assert_eq!(
+            syntax_highlight_source("## Docs\nx = \"<a>\" # note\n"),
+            "<span class=\"syntax-docs-comment\">## Docs</span>\n\
+             <span class=\"syntax-lowercase-ident\">x</span> \
+             <span class=\"syntax-operator\">=</span> \
+             <span class=\"syntax-string\">&quot;&lt;a&gt;&quot;</span> \
+             <span class=\"syntax-comment\"># note</span>\n"
+        );
+    }
+
+    #[test]
+    fn platform_site() {
+        // Unique to this process, so that concurrent test runs don't share the directory.
+        let build_dir =
+            std::env::temp_dir().join(format!("roc_docs_package_docs_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&build_dir);
+
+        // Loading the platform needs more stack than the test threads have.
+        let root = read_package_root(&fixture("platform")).unwrap();
+        let thread_build_dir = build_dir.clone();
+
+        std::thread::Builder::new()
+            .stack_size(256 * 1024 * 1024)
+            .spawn(move || generate_package_docs_html(&root, &thread_build_dir))
+            .unwrap()
+            .join()
+            .unwrap();
+
+        let landing_page = read(&build_dir, "index.html");
+        assert!(landing_page.contains("A platform for <em>friendly</em> programs."));
+        assert!(landing_page.contains("href=\"/Greeting\""));
+        assert!(landing_page.contains("href=\"/util/\""));
+
+        let greeting = read(&build_dir, "Greeting/index.html");
+        assert!(greeting.contains("<h3 id=\"Greeting\""));
+        assert!(greeting.contains("href=\"/Greeting#Greeting\""));
+        assert!(greeting.contains("href=\"https://www.roc-lang.org/builtins/Str#Str\""));
+        assert!(greeting.contains("href=\"/Greeting/source.html#L9\""));
+
+        let source = read(&build_dir, "Greeting/source.html");
+        assert!(source.contains("<a id=\"L9\" href=\"#L9\">9</a>"));
+        assert!(source.contains("<span class=\"syntax-lowercase-ident\">greet</span>"));
+
+        let text = read(&build_dir, "util/Text/index.html");
+        assert!(text.contains("<h3 id=\"Text\""));
+        assert!(text.contains("href=\"/util/Text#Text\""));
+
+        assert!(read(&build_dir, "util/index.html").contains("href=\"/util/Text\""));
+
+        let _ = fs::remove_dir_all(&build_dir);
+    }
+}
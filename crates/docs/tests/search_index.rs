@@ -0,0 +1,156 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[cfg(test)]
+mod search_index {
+    use roc_docs::{load_modules_for_files, render_search_index, search_index_entry};
+    use roc_load::docs::{DocDef, RecordField, TypeAnnotation};
+    use roc_region::all::Region;
+    use serde_json::{json, Value};
+    use std::path::Path;
+
+    fn apply(name: &str, parts: Vec<TypeAnnotation>) -> TypeAnnotation {
+        TypeAnnotation::Apply {
+            name: name.to_string(),
+            parts,
+        }
+    }
+
+    fn var(name: &str) -> TypeAnnotation {
+        TypeAnnotation::BoundVariable(name.to_string())
+    }
+
+    fn function(args: Vec<TypeAnnotation>, output: TypeAnnotation) -> TypeAnnotation {
+        TypeAnnotation::Function {
+            args,
+            output: Box::new(output),
+        }
+    }
+
+    fn doc_def(name: &str, type_annotation: TypeAnnotation, docs: Option<&str>) -> DocDef {
+        DocDef {
+            name: name.to_string(),
+            type_vars: Vec::new(),
+            type_annotation,
+            docs: docs.map(|docs| docs.to_string()),
+            region: Region::zero(),
+        }
+    }
+
+    #[test]
+    fn function_entry() {
+        // walk : List elem, state, (state, elem -> state) -> state
+        let walk = doc_def(
+            "walk",
+            function(
+                vec![
+                    apply("List", vec![var("elem")]),
+                    var("state"),
+                    function(vec![var("state"), var("elem")], var("state")),
+                ],
+                var("state"),
+            ),
+            Some(
+                "Build a value using each element in the list.\n\n>>> List.walk [1, 2] 0 Num.add\n",
+            ),
+        );
+
+        assert_eq!(
+            search_index_entry("List", &walk, "/List#walk"),
+            json!({
+                "module": "List",
+                "name": "walk",
+                "url": "/List#walk",
+                "typeVars": [],
+                "signature": "List elem, state, (state, elem -> state) -> state",
+                "args": ["List a", "b", "(b, a -> b)"],
+                "output": "b",
+                "docs": "Build a value using each element in the list. List.walk [1, 2] 0 Num.add",
+            })
+        );
+    }
+
+    #[test]
+    fn nested_types_entry() {
+        // first : List (List a) -> Result { first : a } [ListWasEmpty]*
+        let first = doc_def(
+            "first",
+            function(
+                vec![apply("List", vec![apply("List", vec![var("a")])])],
+                apply(
+                    "Result",
+                    vec![
+                        TypeAnnotation::Record {
+                            fields: vec![RecordField::RecordField {
+                                name: "first".to_string(),
+                                type_annotation: var("a"),
+                            }],
+                            extension: Box::new(TypeAnnotation::NoTypeAnn),
+                        },
+                        TypeAnnotation::TagUnion {
+                            tags: vec![roc_load::docs::Tag {
+                                name: "ListWasEmpty".to_string(),
+                                values: Vec::new(),
+                            }],
+                            extension: Box::new(TypeAnnotation::Wildcard),
+                        },
+                    ],
+                ),
+            ),
+            None,
+        );
+
+        let entry = search_index_entry("List", &first, "/List#first");
+
+        assert_eq!(
+            entry["signature"],
+            json!("List (List a) -> Result { first : a } [ListWasEmpty]*")
+        );
+        assert_eq!(entry["docs"], json!(""));
+    }
+
+    #[test]
+    fn type_entry() {
+        let mut dict = doc_def(
+            "Dict",
+            TypeAnnotation::ObscuredRecord,
+            Some("A [dictionary](https://en.wikipedia.org/wiki/Associative_array)."),
+        );
+        dict.type_vars = vec!["k".to_string(), "v".to_string()];
+
+        let entry = search_index_entry("Dict", &dict, "/Dict#Dict");
+
+        // Types are found by their names, not by type-directed search
+        assert_eq!(entry["typeVars"], json!(["k", "v"]));
+        assert_eq!(entry["args"], json!([]));
+        assert_eq!(entry["output"], json!(""));
+        assert_eq!(entry["docs"], json!("A dictionary."));
+    }
+
+    #[test]
+    fn module_index_has_exposed_types() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("Shape.roc");
+
+        // Loading the module needs more stack than the test threads have.
+        let index = std::thread::Builder::new()
+            .stack_size(256 * 1024 * 1024)
+            .spawn(move || render_search_index(&load_modules_for_files(vec![path])))
+            .unwrap()
+            .join()
+            .unwrap();
+
+        let index: Value = serde_json::from_str(&index).unwrap();
+        let entries = index.as_array().unwrap();
+        let shape = entries
+            .iter()
+            .find(|entry| entry["name"] == json!("Shape"))
+            .expect("the exposed type is in the index");
+
+        assert_eq!(shape["module"], json!("Shape"));
+        assert_eq!(shape["docs"], json!("A shape on the plane"));
+        assert!(entries.iter().any(|entry| entry["name"] == json!("area")));
+    }
+}
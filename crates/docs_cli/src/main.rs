@@ -1,5 +1,5 @@
 use clap::{Arg, Command};
-use roc_docs::generate_docs_html;
+use roc_docs::{generate_docs_html, generate_package_docs_html, read_package_root, BUILD_DIR};
 use std::fs::{self, FileType};
 use std::io;
 use std::path::{Path, PathBuf};
@@ -13,15 +13,26 @@ fn main() -> io::Result<()> {
             Arg::new(DIRECTORY_OR_FILES)
                 .multiple_values(true)
                 .required(true)
-                .help("The directory or files to build documentation for")
+                .help("The directory or files to build documentation for. A platform's main.roc, or a directory that has one, is documented as a whole package, along with the packages it depends on.")
                 .allow_invalid_utf8(true),
         )
         .get_matches();
 
+    let values = matches.values_of_os(DIRECTORY_OR_FILES).unwrap();
+
+    // A platform's main.roc, or a directory that has one, is documented as a package.
+    if values.len() == 1 {
+        if let Some(package_root) = read_package_root(Path::new(values.clone().next().unwrap())) {
+            generate_package_docs_html(&package_root, Path::new(BUILD_DIR));
+
+            return Ok(());
+        }
+    }
+
     let mut roc_files = Vec::new();
 
     // Populate roc_files
-    for os_str in matches.values_of_os(DIRECTORY_OR_FILES).unwrap() {
+    for os_str in values {
         let metadata = fs::metadata(os_str)?;
         roc_files_recursive(os_str, metadata.file_type(), &mut roc_files)?;
     }
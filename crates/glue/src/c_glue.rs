@@ -0,0 +1,1186 @@
+use crate::rust_glue::{max_pointer_tagged_variants, tagged_pointer_bitmask};
+use crate::types::{RocNum, RocTagUnion, RocType, TypeId, Types};
+use indexmap::IndexMap;
+use roc_collections::MutSet;
+use roc_mono::layout::round_up_to_alignment;
+use roc_target::{Architecture, TargetInfo};
+use std::fmt::{self, Write};
+
+pub static HEADER: &[u8] = include_bytes!("../templates/header.h");
+const INDENT: &str = "    ";
+
+/// Declarations, keyed by the name of what they declare. A declaration can differ between
+/// targets (e.g. in its padding), so each variant records which targets it applies to.
+type Decls = IndexMap<String, IndexMap<String, Vec<TargetInfo>>>;
+
+/// C needs everything declared before it is used, so declarations go in sections.
+#[derive(Default)]
+struct Sections {
+    /// `typedef struct Foo Foo;` for every struct and union, so they can point to each other
+    typedefs: Decls,
+    /// Enumerations, and the discriminants of tag unions
+    discriminants: Decls,
+    /// Tag unions that are represented as a pointer to their payload
+    pointers: Decls,
+    /// All other types, each one after the types it contains
+    definitions: Decls,
+    /// Refcount functions can be mutually recursive, so they are all declared up front
+    prototypes: Decls,
+    functions: Decls,
+    entry_points: Decls,
+}
+
+struct Env<'a> {
+    target_info: TargetInfo,
+    types: &'a Types,
+    added: MutSet<TypeId>,
+    /// The functions the host calls, as opposed to functions stored in values
+    entry_points: MutSet<TypeId>,
+    sections: &'a mut Sections,
+}
+
+/// A type that C glue can not be generated for yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    pub type_name: String,
+    pub reason: &'static str,
+}
+
+impl fmt::Display for Unsupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "C glue can not be generated for {} yet, because {}.",
+            self.type_name, self.reason
+        )
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Op {
+    Incref,
+    Decref,
+}
+
+impl Op {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Op::Incref => "incref",
+            Op::Decref => "decref",
+        }
+    }
+}
+
+fn add_decl(decls: &mut Decls, name: &str, target_info: TargetInfo, body: String) {
+    let variants = decls.entry(name.to_string()).or_default();
+    let targets = variants.entry(body).or_default();
+
+    // Anonymous types like results can occur more than once.
+    if !targets.contains(&target_info) {
+        targets.push(target_info);
+    }
+}
+
+pub fn emit(types_and_targets: &[(Types, TargetInfo)]) -> Result<String, Unsupported> {
+    let mut sections = Sections::default();
+
+    for (types, target_info) in types_and_targets {
+        let mut env = Env {
+            target_info: *target_info,
+            types,
+            added: MutSet::default(),
+            entry_points: types.entry_points().iter().map(|(_, id)| *id).collect(),
+            sections: &mut sections,
+        };
+
+        for id in types.ids() {
+            add_type(&mut env, id)?;
+        }
+
+        for (name, id) in types.entry_points() {
+            add_entry_point(&mut env, name, *id);
+        }
+    }
+
+    let targets: Vec<TargetInfo> = types_and_targets
+        .iter()
+        .map(|(_, target_info)| *target_info)
+        .collect();
+    let mut buf = String::new();
+
+    for decls in [
+        sections.typedefs,
+        sections.discriminants,
+        sections.pointers,
+        sections.definitions,
+        sections.prototypes,
+        sections.functions,
+    ] {
+        write_decls(&mut buf, decls, &targets);
+    }
+
+    if !sections.entry_points.is_empty() {
+        buf.push_str("\n#ifdef __cplusplus\nextern \"C\" {\n#endif\n");
+        write_decls(&mut buf, sections.entry_points, &targets);
+        buf.push_str("\n#ifdef __cplusplus\n}\n#endif\n");
+    }
+
+    Ok(buf)
+}
+
+fn write_decls(buf: &mut String, decls: Decls, targets: &[TargetInfo]) {
+    for (_, variants) in decls {
+        for (decl, decl_targets) in variants {
+            buf.push('\n');
+
+            // Only guard declarations that don't apply to every target.
+            if decl_targets.len() == targets.len() {
+                buf.push_str(&decl);
+                buf.push('\n');
+            } else {
+                let conditions: Vec<String> = decl_targets
+                    .iter()
+                    .map(|target_info| {
+                        format!("defined({})", arch_to_macro(target_info.architecture))
+                    })
+                    .collect();
+
+                writeln!(buf, "#if {}", conditions.join(" || ")).unwrap();
+                buf.push_str(&decl);
+                buf.push_str("\n#endif\n");
+            }
+        }
+    }
+}
+
+fn arch_to_macro(architecture: Architecture) -> &'static str {
+    match architecture {
+        Architecture::Aarch32 => "__arm__",
+        Architecture::Aarch64 => "__aarch64__",
+        Architecture::Wasm32 => "__wasm32__",
+        Architecture::X86_32 => "__i386__",
+        Architecture::X86_64 => "__x86_64__",
+    }
+}
+
+fn add_type(env: &mut Env, id: TypeId) -> Result<(), Unsupported> {
+    if !env.added.insert(id) || is_zero_sized(id, env.types) {
+        return Ok(());
+    }
+
+    let types = env.types;
+
+    // C needs the types a type contains to be defined first.
+    for dep in contained_types(types.get_type(id)) {
+        add_type(env, dep)?;
+    }
+
+    match types.get_type(id) {
+        RocType::RocStr
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::RocList(_)
+        | RocType::RocBox(_)
+        | RocType::EmptyTagUnion
+        | RocType::Unit => {
+            // These are declared in the header.
+        }
+        RocType::RocDict(_, _) => {
+            return Err(Unsupported {
+                type_name: "Dict".to_string(),
+                reason: "the header has no declarations for Dict",
+            });
+        }
+        RocType::RocSet(_) => {
+            return Err(Unsupported {
+                type_name: "Set".to_string(),
+                reason: "the header has no declarations for Set",
+            });
+        }
+        RocType::RecursivePointer(_) => {
+            // This is a pointer to a recursive tag union, which gets declared on its own.
+        }
+        RocType::Function { name, .. } => {
+            // The functions the host calls are declared as entry points.
+            if !env.entry_points.contains(&id) {
+                return Err(Unsupported {
+                    type_name: name.clone(),
+                    reason:
+                        "functions stored in values need a caller, which is not generated for C",
+                });
+            }
+        }
+        RocType::RocResult(ok_id, err_id) => add_result(env, id, *ok_id, *err_id),
+        RocType::Struct { name, fields } => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|(label, field_id)| (label.to_string(), *field_id))
+                .collect();
+
+            add_struct(env, id, name, &fields);
+        }
+        RocType::TagUnionPayload { name, fields } => {
+            // Tag union payloads have numbered fields, so we prefix them
+            // with an "f" because C doesn't allow struct fields to be numbers.
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|(index, field_id)| (format!("f{index}"), *field_id))
+                .collect();
+
+            add_struct(env, id, name, &fields);
+        }
+        RocType::TagUnion(RocTagUnion::SingleTagStruct {
+            name,
+            payload_fields,
+            ..
+        }) => {
+            let fields: Vec<_> = payload_fields
+                .iter()
+                .enumerate()
+                .map(|(index, field_id)| (format!("f{index}"), *field_id))
+                .collect();
+
+            add_struct(env, id, name, &fields);
+        }
+        RocType::TagUnion(RocTagUnion::Enumeration { name, tags, size }) => {
+            add_enumeration(env, name, tags.iter().map(String::as_str), *size);
+        }
+        RocType::TagUnion(RocTagUnion::NonRecursive {
+            name,
+            tags,
+            discriminant_size,
+            discriminant_offset,
+        }) => add_non_recursive(
+            env,
+            id,
+            name,
+            tags,
+            *discriminant_size,
+            *discriminant_offset,
+        ),
+        RocType::TagUnion(RocTagUnion::Recursive {
+            name,
+            tags,
+            discriminant_size,
+            ..
+        }) => add_recursive(env, id, name, tags, *discriminant_size, None)?,
+        RocType::TagUnion(RocTagUnion::NullableWrapped {
+            name,
+            index_of_null_tag,
+            tags,
+            discriminant_size,
+            ..
+        }) => add_recursive(
+            env,
+            id,
+            name,
+            tags,
+            *discriminant_size,
+            Some(*index_of_null_tag as usize),
+        )?,
+        RocType::TagUnion(RocTagUnion::NonNullableUnwrapped {
+            name,
+            tag_name,
+            payload,
+        }) => add_unwrapped(env, id, name, tag_name, None, *payload),
+        RocType::TagUnion(RocTagUnion::NullableUnwrapped {
+            name,
+            null_tag,
+            non_null_tag,
+            non_null_payload,
+            ..
+        }) => add_unwrapped(
+            env,
+            id,
+            name,
+            non_null_tag,
+            Some(null_tag),
+            *non_null_payload,
+        ),
+    }
+
+    Ok(())
+}
+
+/// The types stored directly inside values of this type, rather than behind a pointer.
+fn contained_types(typ: &RocType) -> Vec<TypeId> {
+    match typ {
+        RocType::RocResult(ok_id, err_id) => vec![*ok_id, *err_id],
+        RocType::Struct { fields, .. } => fields.iter().map(|(_, id)| *id).collect(),
+        RocType::TagUnionPayload { fields, .. } => fields.iter().map(|(_, id)| *id).collect(),
+        RocType::TagUnion(RocTagUnion::SingleTagStruct { payload_fields, .. }) => {
+            payload_fields.clone()
+        }
+        RocType::TagUnion(RocTagUnion::NonRecursive { tags, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { tags, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { tags, .. }) => {
+            tags.iter().filter_map(|(_, payload)| *payload).collect()
+        }
+        RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { payload, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped {
+            non_null_payload: payload,
+            ..
+        }) => vec![*payload],
+        RocType::RocStr
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::RocList(_)
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocBox(_)
+        | RocType::TagUnion(RocTagUnion::Enumeration { .. })
+        | RocType::EmptyTagUnion
+        | RocType::RecursivePointer(_)
+        | RocType::Function { .. }
+        | RocType::Unit => Vec::new(),
+    }
+}
+
+fn add_entry_point(env: &mut Env, name: &str, id: TypeId) {
+    let types = env.types;
+
+    // The host passes a pointer for the return value, followed by pointers to the arguments.
+    let mut params = Vec::new();
+
+    match types.get_type(id) {
+        RocType::Function { args, ret, .. } => {
+            params.push(format!("{} *ret", type_name(*ret, types)));
+
+            for (index, arg_id) in args.iter().enumerate() {
+                params.push(format!("{} *arg{index}", type_name(*arg_id, types)));
+            }
+        }
+        _ => {
+            params.push(format!("{} *ret", type_name(id, types)));
+        }
+    }
+
+    let decl = format!("void roc__{name}_1_exposed_generic({});", params.join(", "));
+
+    add_decl(&mut env.sections.entry_points, name, env.target_info, decl);
+}
+
+fn add_typedef(env: &mut Env, name: &str, kind: &str) {
+    add_decl(
+        &mut env.sections.typedefs,
+        name,
+        env.target_info,
+        format!("typedef {kind} {name} {name};"),
+    );
+}
+
+fn add_function(env: &mut Env, name: &str, body: String) {
+    add_decl(&mut env.sections.functions, name, env.target_info, body);
+}
+
+fn add_struct(env: &mut Env, id: TypeId, name: &str, fields: &[(String, TypeId)]) {
+    let types = env.types;
+    let mut buf = format!("struct {name} {{\n");
+
+    for (label, field_id) in fields {
+        // C doesn't allow zero-sized fields, and Roc doesn't store them anyway.
+        if !is_zero_sized(*field_id, types) {
+            writeln!(buf, "{INDENT}{} {label};", type_name(*field_id, types)).unwrap();
+        }
+    }
+
+    buf.push_str("};");
+
+    add_typedef(env, name, "struct");
+    add_decl(&mut env.sections.definitions, name, env.target_info, buf);
+
+    let refcount_body = |op| {
+        fields
+            .iter()
+            .map(|(label, field_id)| {
+                refcount_stmts(op, &format!("self->{label}"), *field_id, types, 0)
+            })
+            .collect()
+    };
+
+    add_refcount_functions(
+        env,
+        id,
+        name,
+        refcount_body(Op::Incref),
+        refcount_body(Op::Decref),
+    );
+}
+
+/// Adds a type with one constant per tag, which is also how discriminants are represented.
+/// Returns the name of the type.
+fn add_enumeration<'a, I: Iterator<Item = &'a str>>(
+    env: &mut Env,
+    name: &str,
+    tags: I,
+    size: u32,
+) -> String {
+    let mut buf = format!("typedef {} {name};\n\nenum {{\n", discriminant_type(size));
+
+    for (index, tag_name) in tags.enumerate() {
+        writeln!(buf, "{INDENT}{name}_{tag_name} = {index},").unwrap();
+    }
+
+    buf.push_str("};");
+
+    add_decl(&mut env.sections.discriminants, name, env.target_info, buf);
+
+    name.to_string()
+}
+
+fn add_result(env: &mut Env, id: TypeId, ok_id: TypeId, err_id: TypeId) {
+    let types = env.types;
+    let name = type_name(id, types);
+
+    // Results are laid out like any other non-recursive tag union: the payload, then the
+    // discriminant. Tags are sorted alphabetically, so Err is 0 and Ok is 1.
+    let discriminant_offset = round_up_to_alignment(
+        types
+            .size_rounded_to_alignment(ok_id)
+            .max(types.size_rounded_to_alignment(err_id)),
+        types.align(ok_id).max(types.align(err_id)),
+    );
+
+    let mut buf = format!("union {name} {{\n");
+
+    for (label, payload_id) in [("ok", ok_id), ("err", err_id)] {
+        if !is_zero_sized(payload_id, types) {
+            writeln!(buf, "{INDENT}{} {label};", type_name(payload_id, types)).unwrap();
+        }
+    }
+
+    write!(
+        buf,
+        "{INDENT}uint8_t _sizer[{}];\n}};",
+        types.size_rounded_to_alignment(id)
+    )
+    .unwrap();
+
+    add_typedef(env, &name, "union");
+    add_decl(&mut env.sections.definitions, &name, env.target_info, buf);
+
+    add_function(
+        env,
+        &format!("{name}_is_ok"),
+        format!(
+            r#"static inline bool {name}_is_ok(const {name} *self) {{
+    return ((const uint8_t *)self)[{discriminant_offset}] == 1;
+}}"#
+        ),
+    );
+
+    for (label, payload_id, discriminant) in [("ok", ok_id, 1), ("err", err_id, 0)] {
+        let (param, assign) = if is_zero_sized(payload_id, types) {
+            ("void".to_string(), String::new())
+        } else {
+            (
+                format!("{} payload", type_name(payload_id, types)),
+                format!("{INDENT}self.{label} = payload;\n"),
+            )
+        };
+
+        add_function(
+            env,
+            &format!("{name}_{label}"),
+            format!(
+                r#"static inline {name} {name}_{label}({param}) {{
+    {name} self;
+
+{assign}    ((uint8_t *)&self)[{discriminant_offset}] = {discriminant};
+
+    return self;
+}}"#
+            ),
+        );
+    }
+
+    let refcount_body = |op| {
+        let ok_stmts = refcount_stmts(op, "self->ok", ok_id, types, 0);
+        let err_stmts = refcount_stmts(op, "self->err", err_id, types, 0);
+
+        match (ok_stmts.is_empty(), err_stmts.is_empty()) {
+            (false, false) => format!(
+                "if ({name}_is_ok(self)) {{\n{}}} else {{\n{}}}\n",
+                indent(&ok_stmts, 1),
+                indent(&err_stmts, 1)
+            ),
+            (false, true) => format!("if ({name}_is_ok(self)) {{\n{}}}\n", indent(&ok_stmts, 1)),
+            (true, false) => format!("if (!{name}_is_ok(self)) {{\n{}}}\n", indent(&err_stmts, 1)),
+            (true, true) => String::new(),
+        }
+    };
+
+    let incref_body = refcount_body(Op::Incref);
+    let decref_body = refcount_body(Op::Decref);
+
+    add_refcount_functions(env, id, &name, incref_body, decref_body);
+}
+
+fn add_non_recursive(
+    env: &mut Env,
+    id: TypeId,
+    name: &str,
+    tags: &[(String, Option<TypeId>)],
+    discriminant_size: u32,
+    discriminant_offset: u32,
+) {
+    let types = env.types;
+    let discriminant_name = add_enumeration(
+        env,
+        &format!("discriminant_{name}"),
+        tags.iter().map(|(tag_name, _)| tag_name.as_str()),
+        discriminant_size,
+    );
+
+    let mut buf = format!("union {name} {{\n");
+
+    for (tag_name, payload_id) in payloads(tags, types) {
+        writeln!(buf, "{INDENT}{} {tag_name};", type_name(payload_id, types)).unwrap();
+    }
+
+    // The discriminant is stored in the bytes after the largest payload.
+    write!(
+        buf,
+        "{INDENT}uint8_t _sizer[{}];\n}};",
+        types.size_rounded_to_alignment(id)
+    )
+    .unwrap();
+
+    add_typedef(env, name, "union");
+    add_decl(&mut env.sections.definitions, name, env.target_info, buf);
+
+    add_function(
+        env,
+        &format!("{name}_discriminant"),
+        format!(
+            r#"static inline {discriminant_name} {name}_discriminant(const {name} *self) {{
+    {discriminant_name} discriminant;
+
+    memcpy(&discriminant, (const char *)self + {discriminant_offset}, sizeof(discriminant));
+
+    return discriminant;
+}}"#
+        ),
+    );
+
+    add_function(
+        env,
+        &format!("{name}_set_discriminant"),
+        format!(
+            r#"static inline void {name}_set_discriminant({name} *self, {discriminant_name} discriminant) {{
+    memcpy((char *)self + {discriminant_offset}, &discriminant, sizeof(discriminant));
+}}"#
+        ),
+    );
+
+    for (tag_name, opt_payload) in tags {
+        let (param, assign) = match payload(*opt_payload, types) {
+            Some(payload_id) => (
+                format!("{} payload", type_name(payload_id, types)),
+                format!("{INDENT}self.{tag_name} = payload;\n"),
+            ),
+            None => ("void".to_string(), String::new()),
+        };
+
+        add_function(
+            env,
+            &format!("{name}_new_{tag_name}"),
+            format!(
+                r#"static inline {name} {name}_new_{tag_name}({param}) {{
+    {name} self;
+
+{assign}    {name}_set_discriminant(&self, {discriminant_name}_{tag_name});
+
+    return self;
+}}"#
+            ),
+        );
+    }
+
+    for (tag_name, payload_id) in payloads(tags, types) {
+        let payload_name = type_name(payload_id, types);
+
+        add_function(
+            env,
+            &format!("{name}_get_{tag_name}"),
+            format!(
+                r#"// Only valid if the discriminant is {discriminant_name}_{tag_name}.
+static inline {payload_name} {name}_get_{tag_name}(const {name} *self) {{
+    return self->{tag_name};
+}}"#
+            ),
+        );
+    }
+
+    let refcount_body = |op| {
+        let switch = switch_refcount_stmts(op, &discriminant_name, tags, "self->", types);
+
+        if switch.is_empty() {
+            String::new()
+        } else {
+            format!("switch ({name}_discriminant(self)) {{\n{switch}default:\n{INDENT}break;\n}}\n")
+        }
+    };
+
+    add_refcount_functions(
+        env,
+        id,
+        name,
+        refcount_body(Op::Incref),
+        refcount_body(Op::Decref),
+    );
+}
+
+/// Recursive tag unions are a pointer to a heap-allocated union of their payloads, with the
+/// discriminant stored in the unused bits at the end of the pointer. Nullable ones represent
+/// the tag at `index_of_null_tag` with a null pointer.
+fn add_recursive(
+    env: &mut Env,
+    id: TypeId,
+    name: &str,
+    tags: &[(String, Option<TypeId>)],
+    discriminant_size: u32,
+    index_of_null_tag: Option<usize>,
+) -> Result<(), Unsupported> {
+    let types = env.types;
+    let target_info = env.target_info;
+
+    if tags.len() > max_pointer_tagged_variants(target_info.architecture) {
+        return Err(Unsupported {
+            type_name: name.to_string(),
+            reason: "its discriminant does not fit in the unused bits of a pointer on every target",
+        });
+    }
+
+    let bitmask = tagged_pointer_bitmask(target_info.architecture);
+    let union_name = format!("union_{name}");
+    let discriminant_name = add_enumeration(
+        env,
+        &format!("discriminant_{name}"),
+        tags.iter().map(|(tag_name, _)| tag_name.as_str()),
+        discriminant_size,
+    );
+
+    add_typedef(env, name, "struct");
+    add_typedef(env, &union_name, "union");
+
+    add_decl(
+        &mut env.sections.pointers,
+        name,
+        target_info,
+        format!("struct {name} {{\n{INDENT}{union_name} *pointer;\n}};"),
+    );
+
+    let mut buf = format!("union {union_name} {{\n");
+
+    for (tag_name, payload_id) in payloads(tags, types) {
+        writeln!(buf, "{INDENT}{} {tag_name};", type_name(payload_id, types)).unwrap();
+    }
+
+    buf.push_str("};");
+
+    add_decl(&mut env.sections.definitions, &union_name, target_info, buf);
+
+    let null_check = match index_of_null_tag {
+        Some(index) => format!(
+            "{INDENT}if (self->pointer == NULL) {{\n{INDENT}{INDENT}return {discriminant_name}_{};\n{INDENT}}}\n\n",
+            tags[index].0
+        ),
+        None => String::new(),
+    };
+
+    add_function(
+        env,
+        &format!("{name}_discriminant"),
+        format!(
+            r#"static inline {discriminant_name} {name}_discriminant(const {name} *self) {{
+{null_check}    // The discriminant is stored in the unused bits at the end of the pointer
+    return ({discriminant_name})((uintptr_t)self->pointer & {bitmask:#x});
+}}"#
+        ),
+    );
+
+    add_function(
+        env,
+        &format!("{name}_union_pointer"),
+        format!(
+            r#"static inline {union_name} *{name}_union_pointer(const {name} *self) {{
+    return ({union_name} *)((uintptr_t)self->pointer & ~(uintptr_t){bitmask:#x});
+}}"#
+        ),
+    );
+
+    for (index, (tag_name, opt_payload)) in tags.iter().enumerate() {
+        let body = if Some(index) == index_of_null_tag {
+            format!(
+                r#"static inline {name} {name}_new_{tag_name}(void) {{
+    {name} self;
+
+    self.pointer = NULL;
+
+    return self;
+}}"#
+            )
+        } else {
+            let (param, assign) = match payload(*opt_payload, types) {
+                Some(payload_id) => (
+                    format!("{} payload", type_name(payload_id, types)),
+                    format!("{INDENT}pointer->{tag_name} = payload;\n"),
+                ),
+                None => ("void".to_string(), String::new()),
+            };
+
+            format!(
+                r#"static inline {name} {name}_new_{tag_name}({param}) {{
+    {union_name} *pointer = ({union_name} *)roc_alloc_refcounted(sizeof({union_name}), ROC_ALIGNOF({union_name}));
+    {name} self;
+
+{assign}    self.pointer = ({union_name} *)((uintptr_t)pointer | {discriminant_name}_{tag_name});
+
+    return self;
+}}"#
+            )
+        };
+
+        add_function(env, &format!("{name}_new_{tag_name}"), body);
+    }
+
+    for (tag_name, payload_id) in payloads(tags, types) {
+        let payload_name = type_name(payload_id, types);
+
+        add_function(
+            env,
+            &format!("{name}_get_{tag_name}"),
+            format!(
+                r#"// Only valid if the discriminant is {discriminant_name}_{tag_name}.
+static inline {payload_name} {name}_get_{tag_name}(const {name} *self) {{
+    return {name}_union_pointer(self)->{tag_name};
+}}"#
+            ),
+        );
+    }
+
+    let incref_body = format!("roc_incref({name}_union_pointer(self));\n");
+
+    let switch = switch_refcount_stmts(Op::Decref, &discriminant_name, tags, "pointer->", types);
+    let release = if switch.is_empty() {
+        String::new()
+    } else {
+        let switch = format!(
+            "switch ({name}_discriminant(self)) {{\n{switch}default:\n{INDENT}break;\n}}\n\n"
+        );
+
+        indent(&switch, 1)
+    };
+
+    let decref_body = format!(
+        r#"{union_name} *pointer = {name}_union_pointer(self);
+
+if (roc_decref_is_last(pointer)) {{
+{release}    roc_dealloc_refcounted(pointer, ROC_ALIGNOF({union_name}));
+}}
+"#
+    );
+
+    add_refcount_functions(env, id, name, incref_body, decref_body);
+
+    Ok(())
+}
+
+/// Tag unions that only need a pointer to their one payload: either they have a single tag,
+/// or the other tag has no payload and is represented by a null pointer.
+fn add_unwrapped(
+    env: &mut Env,
+    id: TypeId,
+    name: &str,
+    tag_name: &str,
+    null_tag: Option<&str>,
+    payload_id: TypeId,
+) {
+    let types = env.types;
+    let target_info = env.target_info;
+    let payload_name = type_name(payload_id, types);
+
+    add_typedef(env, name, "struct");
+
+    add_decl(
+        &mut env.sections.pointers,
+        name,
+        target_info,
+        format!("struct {name} {{\n{INDENT}{payload_name} *pointer;\n}};"),
+    );
+
+    if let Some(null_tag) = null_tag {
+        let mut tag_names = [null_tag, tag_name];
+
+        tag_names.sort_unstable();
+
+        let discriminant_name = add_enumeration(
+            env,
+            &format!("discriminant_{name}"),
+            tag_names.into_iter(),
+            1,
+        );
+
+        add_function(
+            env,
+            &format!("{name}_discriminant"),
+            format!(
+                r#"static inline {discriminant_name} {name}_discriminant(const {name} *self) {{
+    return self->pointer == NULL ? {discriminant_name}_{null_tag} : {discriminant_name}_{tag_name};
+}}"#
+            ),
+        );
+
+        add_function(
+            env,
+            &format!("{name}_new_{null_tag}"),
+            format!(
+                r#"static inline {name} {name}_new_{null_tag}(void) {{
+    {name} self;
+
+    self.pointer = NULL;
+
+    return self;
+}}"#
+            ),
+        );
+    }
+
+    add_function(
+        env,
+        &format!("{name}_new_{tag_name}"),
+        format!(
+            r#"static inline {name} {name}_new_{tag_name}({payload_name} payload) {{
+    {name} self;
+
+    self.pointer = ({payload_name} *)roc_alloc_refcounted(sizeof({payload_name}), ROC_ALIGNOF({payload_name}));
+    *self.pointer = payload;
+
+    return self;
+}}"#
+        ),
+    );
+
+    add_function(
+        env,
+        &format!("{name}_get_{tag_name}"),
+        format!(
+            r#"static inline {payload_name} {name}_get_{tag_name}(const {name} *self) {{
+    return *self->pointer;
+}}"#
+        ),
+    );
+
+    let incref_body = "roc_incref(self->pointer);\n".to_string();
+
+    let payload_stmts = refcount_stmts(Op::Decref, "(*self->pointer)", payload_id, types, 0);
+    let release = if payload_stmts.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", indent(&payload_stmts, 1))
+    };
+
+    let decref_body = format!(
+        r#"if (roc_decref_is_last(self->pointer)) {{
+{release}    roc_dealloc_refcounted(self->pointer, ROC_ALIGNOF({payload_name}));
+}}
+"#
+    );
+
+    add_refcount_functions(env, id, name, incref_body, decref_body);
+}
+
+/// The tags whose payloads are actually stored, along with those payloads.
+fn payloads<'a>(
+    tags: &'a [(String, Option<TypeId>)],
+    types: &'a Types,
+) -> impl Iterator<Item = (&'a str, TypeId)> + 'a {
+    tags.iter().filter_map(|(tag_name, opt_payload)| {
+        payload(*opt_payload, types).map(|payload_id| (tag_name.as_str(), payload_id))
+    })
+}
+
+fn payload(opt_payload: Option<TypeId>, types: &Types) -> Option<TypeId> {
+    opt_payload.filter(|payload_id| !is_zero_sized(*payload_id, types))
+}
+
+/// The cases of a `switch` on the discriminant, for the tags whose payloads are refcounted.
+fn switch_refcount_stmts(
+    op: Op,
+    discriminant_name: &str,
+    tags: &[(String, Option<TypeId>)],
+    prefix: &str,
+    types: &Types,
+) -> String {
+    let mut buf = String::new();
+
+    for (tag_name, payload_id) in payloads(tags, types) {
+        let stmts = refcount_stmts(op, &format!("{prefix}{tag_name}"), payload_id, types, 0);
+
+        if !stmts.is_empty() {
+            writeln!(buf, "case {discriminant_name}_{tag_name}:").unwrap();
+            buf.push_str(&indent(&stmts, 1));
+            writeln!(buf, "{INDENT}break;").unwrap();
+        }
+    }
+
+    buf
+}
+
+fn add_refcount_functions(
+    env: &mut Env,
+    id: TypeId,
+    name: &str,
+    incref_body: String,
+    decref_body: String,
+) {
+    if !is_refcounted(id, env.types) {
+        return;
+    }
+
+    let target_info = env.target_info;
+
+    add_decl(
+        &mut env.sections.prototypes,
+        name,
+        target_info,
+        format!(
+            "static inline void {name}_incref(const {name} *self);\nstatic inline void {name}_decref(const {name} *self);"
+        ),
+    );
+
+    for (op, body) in [(Op::Incref, incref_body), (Op::Decref, decref_body)] {
+        let op = op.as_str();
+
+        add_function(
+            env,
+            &format!("{name}_{op}"),
+            format!(
+                "static inline void {name}_{op}(const {name} *self) {{\n{}}}",
+                indent(&body, 1)
+            ),
+        );
+    }
+}
+
+/// Statements that change the refcounts of the value `lvalue`, e.g. `self->name`.
+/// Nested loops over list elements need their own index variable, hence the `depth`.
+fn refcount_stmts(op: Op, lvalue: &str, id: TypeId, types: &Types, depth: usize) -> String {
+    if !is_refcounted(id, types) {
+        return String::new();
+    }
+
+    let op_str = op.as_str();
+
+    match types.get_type(id) {
+        RocType::RocStr => format!("roc_str_{op_str}(&{lvalue});\n"),
+        RocType::RocList(elem_id) => match op {
+            Op::Incref => format!("roc_list_incref(&{lvalue});\n"),
+            Op::Decref if is_refcounted(*elem_id, types) => {
+                let elem_name = type_name(*elem_id, types);
+                let index = format!("i{depth}");
+                let elem_stmts = refcount_stmts(
+                    op,
+                    &format!("(({elem_name} *){lvalue}.elements)[{index}]"),
+                    *elem_id,
+                    types,
+                    depth + 1,
+                );
+
+                format!(
+                    r#"if (roc_decref_is_last({lvalue}.elements)) {{
+    for (size_t {index} = 0; {index} < {lvalue}.len; {index}++) {{
+{}    }}
+
+    roc_dealloc_refcounted({lvalue}.elements, {});
+}}
+"#,
+                    indent(&elem_stmts, 2),
+                    alignment(*elem_id, types)
+                )
+            }
+            Op::Decref => format!(
+                "roc_list_decref(&{lvalue}, {});\n",
+                alignment(*elem_id, types)
+            ),
+        },
+        RocType::RocBox(elem_id) => match op {
+            Op::Incref => format!("roc_incref({lvalue}.contents);\n"),
+            Op::Decref => {
+                let elem_name = type_name(*elem_id, types);
+                let elem_stmts = refcount_stmts(
+                    op,
+                    &format!("(*({elem_name} *){lvalue}.contents)"),
+                    *elem_id,
+                    types,
+                    depth,
+                );
+                let release = if elem_stmts.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}\n", indent(&elem_stmts, 1))
+                };
+
+                format!(
+                    r#"if (roc_decref_is_last({lvalue}.contents)) {{
+{release}    roc_dealloc_refcounted({lvalue}.contents, {});
+}}
+"#,
+                    alignment(*elem_id, types)
+                )
+            }
+        },
+        RocType::RecursivePointer(content_id) => {
+            format!(
+                "{}_{op_str}({});\n",
+                type_name(*content_id, types),
+                address_of(lvalue)
+            )
+        }
+        _ => format!(
+            "{}_{op_str}({});\n",
+            type_name(id, types),
+            address_of(lvalue)
+        ),
+    }
+}
+
+fn address_of(lvalue: &str) -> String {
+    match lvalue
+        .strip_prefix("(*")
+        .and_then(|pointer| pointer.strip_suffix(')'))
+    {
+        Some(pointer) => pointer.to_string(),
+        None => format!("&{lvalue}"),
+    }
+}
+
+/// Whether values of this type point to refcounted heap allocations.
+fn is_refcounted(id: TypeId, types: &Types) -> bool {
+    match types.get_type(id) {
+        RocType::RocStr
+        | RocType::RocList(_)
+        | RocType::RocDict(_, _)
+        | RocType::RocSet(_)
+        | RocType::RocBox(_)
+        | RocType::RecursivePointer(_)
+        | RocType::TagUnion(RocTagUnion::Recursive { .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { .. }) => true,
+        RocType::Bool
+        | RocType::Num(_)
+        | RocType::EmptyTagUnion
+        | RocType::Unit
+        | RocType::Function { .. }
+        | RocType::TagUnion(RocTagUnion::Enumeration { .. }) => false,
+        RocType::RocResult(ok_id, err_id) => {
+            is_refcounted(*ok_id, types) || is_refcounted(*err_id, types)
+        }
+        RocType::Struct { fields, .. } => fields
+            .iter()
+            .any(|(_, field_id)| is_refcounted(*field_id, types)),
+        RocType::TagUnionPayload { fields, .. } => fields
+            .iter()
+            .any(|(_, field_id)| is_refcounted(*field_id, types)),
+        RocType::TagUnion(RocTagUnion::SingleTagStruct { payload_fields, .. }) => payload_fields
+            .iter()
+            .any(|field_id| is_refcounted(*field_id, types)),
+        RocType::TagUnion(RocTagUnion::NonRecursive { tags, .. }) => tags
+            .iter()
+            .filter_map(|(_, opt_payload)| *opt_payload)
+            .any(|payload_id| is_refcounted(payload_id, types)),
+    }
+}
+
+fn is_zero_sized(id: TypeId, types: &Types) -> bool {
+    types.size_ignoring_alignment(id) == 0
+}
+
+fn alignment(id: TypeId, types: &Types) -> String {
+    if is_zero_sized(id, types) {
+        "1".to_string()
+    } else {
+        format!("ROC_ALIGNOF({})", type_name(id, types))
+    }
+}
+
+/// Discriminants are unsigned integers of the given size in bytes.
+fn discriminant_type(size: u32) -> &'static str {
+    match size {
+        // Single-tag unions have no discriminant, but it's simpler to still give them one.
+        0 | 1 => "uint8_t",
+        2 => "uint16_t",
+        4 => "uint32_t",
+        8 => "uint64_t",
+        _ => unreachable!("Invalid discriminant size {}", size),
+    }
+}
+
+/// Indents every nonempty line by the given number of levels.
+fn indent(stmts: &str, levels: usize) -> String {
+    let mut buf = String::with_capacity(stmts.len());
+
+    for line in stmts.lines() {
+        if !line.is_empty() {
+            for _ in 0..levels {
+                buf.push_str(INDENT);
+            }
+
+            buf.push_str(line);
+        }
+
+        buf.push('\n');
+    }
+
+    buf
+}
+
+fn type_name(id: TypeId, types: &Types) -> String {
+    // C has no zero-sized types, so these only ever show up behind a pointer.
+    if is_zero_sized(id, types) {
+        return "void".to_string();
+    }
+
+    match types.get_type(id) {
+        RocType::Unit | RocType::EmptyTagUnion => "void".to_string(),
+        RocType::RocStr => "RocStr".to_string(),
+        RocType::Bool => "bool".to_string(),
+        RocType::Num(RocNum::U8) => "uint8_t".to_string(),
+        RocType::Num(RocNum::U16) => "uint16_t".to_string(),
+        RocType::Num(RocNum::U32) => "uint32_t".to_string(),
+        RocType::Num(RocNum::U64) => "uint64_t".to_string(),
+        RocType::Num(RocNum::U128) => "RocU128".to_string(),
+        RocType::Num(RocNum::I8) => "int8_t".to_string(),
+        RocType::Num(RocNum::I16) => "int16_t".to_string(),
+        RocType::Num(RocNum::I32) => "int32_t".to_string(),
+        RocType::Num(RocNum::I64) => "int64_t".to_string(),
+        RocType::Num(RocNum::I128) => "RocI128".to_string(),
+        RocType::Num(RocNum::F32) => "float".to_string(),
+        RocType::Num(RocNum::F64) => "double".to_string(),
+        RocType::Num(RocNum::F128) => "RocF128".to_string(),
+        RocType::Num(RocNum::Dec) => "RocDec".to_string(),
+        RocType::RocDict(_, _) | RocType::RocSet(_) => {
+            unreachable!("add_type rejects Dict and Set before their names are needed")
+        }
+        RocType::RocList(_) => "RocList".to_string(),
+        RocType::RocBox(_) => "RocBox".to_string(),
+        RocType::RocResult(ok_id, err_id) => format!(
+            "RocResult_{}_{}",
+            type_name(*ok_id, types),
+            type_name(*err_id, types)
+        ),
+        RocType::Struct { name, .. }
+        | RocType::TagUnionPayload { name, .. }
+        | RocType::TagUnion(RocTagUnion::NonRecursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Recursive { name, .. })
+        | RocType::TagUnion(RocTagUnion::Enumeration { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { name, .. })
+        | RocType::TagUnion(RocTagUnion::SingleTagStruct { name, .. }) => name.clone(),
+        RocType::RecursivePointer(content) => type_name(*content, types),
+        RocType::Function { name, .. } => name.clone(),
+    }
+}
@@ -1,7 +1,8 @@
+pub mod c_glue;
 pub mod enums;
 pub mod load;
 pub mod rust_glue;
 pub mod structs;
 pub mod types;
 
-pub use load::generate;
+pub use load::{generate, Lang};
@@ -1,9 +1,10 @@
-use crate::rust_glue;
 use crate::types::{Env, Types};
+use crate::{c_glue, rust_glue};
 use bumpalo::Bump;
 use roc_load::{ExecutionMode, LoadConfig, LoadedModule, LoadingProblem, Threading};
 use roc_reporting::report::RenderTarget;
 use roc_target::{Architecture, OperatingSystem, TargetInfo};
+use roc_types::subs::Variable;
 use std::fs::File;
 use std::io::{self, ErrorKind, Write};
 use std::path::{Path, PathBuf};
@@ -11,9 +12,70 @@ use std::process;
 use strum::IntoEnumIterator;
 use target_lexicon::Triple;
 
-pub fn generate(input_path: &Path, output_path: &Path) -> io::Result<i32> {
+/// The languages `roc glue` can generate glue code for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    Rust,
+    C,
+}
+
+impl Lang {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Lang::Rust => "rust",
+            Lang::C => "c",
+        }
+    }
+
+    /// NOTE keep up to date!
+    pub const OPTIONS: &'static [&'static str] = &[Lang::Rust.as_str(), Lang::C.as_str()];
+
+    /// The language of a glue file with the given extension, if we know it.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "rs" => Some(Lang::Rust),
+            "h" => Some(Lang::C),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Lang {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for Lang {
+    type Err = String;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "rust" => Ok(Lang::Rust),
+            "c" => Ok(Lang::C),
+            _ => Err(format!(
+                "`roc glue` does not know how to generate {}",
+                string
+            )),
+        }
+    }
+}
+
+pub fn generate(input_path: &Path, output_path: &Path, lang: Lang) -> io::Result<i32> {
     match load_types(input_path.to_path_buf(), Threading::AllAvailable) {
         Ok(types_and_targets) => {
+            let (header, body) = match lang {
+                Lang::Rust => (rust_glue::HEADER, rust_glue::emit(&types_and_targets)),
+                Lang::C => match c_glue::emit(&types_and_targets) {
+                    Ok(body) => (c_glue::HEADER, body),
+                    Err(unsupported) => {
+                        eprintln!("{}", unsupported);
+
+                        return Ok(1);
+                    }
+                },
+            };
+
             let mut file = File::create(output_path).unwrap_or_else(|err| {
                 eprintln!(
                     "Unable to create output file {} - {:?}",
@@ -24,8 +86,7 @@ pub fn generate(input_path: &Path, output_path: &Path) -> io::Result<i32> {
                 process::exit(1);
             });
 
-            let mut buf = std::str::from_utf8(rust_glue::HEADER).unwrap().to_string();
-            let body = rust_glue::emit(&types_and_targets);
+            let mut buf = std::str::from_utf8(header).unwrap().to_string();
 
             buf.push_str(&body);
 
@@ -88,6 +149,7 @@ pub fn load_types(
             render: RenderTarget::Generic,
             threading,
             exec_mode: ExecutionMode::Check,
+            cache_dir: None,
         },
     )
     .unwrap_or_else(|problem| match problem {
@@ -115,26 +177,32 @@ pub fn load_types(
         );
     }
 
-    let variables = (0..decls.len()).filter_map(|index| {
-        use roc_can::expr::DeclarationTag::*;
-
-        match decls.declarations[index] {
-            Value | Function(_) | Recursive(_) | TailRecursive(_) => Some(decls.variables[index]),
-            Destructure(_) => {
-                // figure out if we need to export non-identifier defs - when would that
-                // happen?
-                None
+    let entry_points: Vec<(String, Variable)> = (0..decls.len())
+        .filter_map(|index| {
+            use roc_can::expr::DeclarationTag::*;
+
+            match decls.declarations[index] {
+                Value | Function(_) | Recursive(_) | TailRecursive(_) => {
+                    let name = decls.symbols[index].value.as_str(&interns).to_string();
+
+                    Some((name, decls.variables[index]))
+                }
+                Destructure(_) => {
+                    // figure out if we need to export non-identifier defs - when would that
+                    // happen?
+                    None
+                }
+                MutualRecursion { .. } => {
+                    // handled by future iterations
+                    None
+                }
+                Expectation | ExpectationFx => {
+                    // not publicly visible
+                    None
+                }
             }
-            MutualRecursion { .. } => {
-                // handled by future iterations
-                None
-            }
-            Expectation | ExpectationFx => {
-                // not publicly visible
-                None
-            }
-        }
-    });
+        })
+        .collect();
 
     let types_and_targets = Architecture::iter()
         .map(|arch| {
@@ -144,7 +212,10 @@ pub fn load_types(
             };
             let mut env = Env::new(arena, subs, &mut interns, target_info);
 
-            (env.vars_to_types(variables.clone()), target_info)
+            (
+                env.entry_points_to_types(entry_points.iter().cloned()),
+                target_info,
+            )
         })
         .collect();
 
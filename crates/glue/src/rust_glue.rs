@@ -294,12 +294,129 @@ fn add_type(target_info: TargetInfo, id: TypeId, types: &Types, impls: &mut Impl
             // This is recursively pointing to a type that should already have been added,
             // so no extra work needs to happen.
         }
-        RocType::Function { .. } => {
-            // TODO actually generate glue functions!
+        RocType::Function {
+            name,
+            caller: Some(caller),
+            args,
+            ret,
+            captures,
+        } => add_function(
+            name,
+            caller,
+            target_info,
+            id,
+            args,
+            *ret,
+            *captures,
+            types,
+            impls,
+        ),
+        RocType::Function { caller: None, .. } => {
+            // Without a caller, the host has no way to run this function,
+            // so there's nothing to generate.
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn add_function(
+    name: &str,
+    caller: &str,
+    target_info: TargetInfo,
+    fn_id: TypeId,
+    args: &[TypeId],
+    ret: TypeId,
+    captures: Option<TypeId>,
+    types: &Types,
+    impls: &mut Impls,
+) {
+    // The host can't look inside the closure, but dropping it has to drop the values it captured.
+    // It derives nothing, because copying it would drop those values twice.
+    {
+        let body = match captures {
+            Some(captures_id) => {
+                let captures_type_name = type_name(captures_id, types);
+
+                format!(
+                    r#"#[repr(transparent)]
+pub struct {name} {{
+    captures: {captures_type_name},
+}}"#
+                )
+            }
+            None => {
+                let size = types.size_ignoring_alignment(fn_id);
+                // Closures which capture nothing are zero-sized, and have an alignment of 0.
+                let align = types.align(fn_id).max(1);
+
+                format!(
+                    r#"#[repr(C, align({align}))]
+pub struct {name} {{
+    closure_data: [u8; {size}],
+}}"#
+                )
+            }
+        };
+
+        add_decl(impls, None, target_info, body);
+    }
+
+    // The `call` method, which runs the closure by way of its caller function.
+    {
+        let ret_type_name = type_name(ret, types);
+        let mut params = vec!["self".to_string()];
+        let mut extern_params = Vec::with_capacity(args.len() + 2);
+        let mut owned_args = String::new();
+        let mut arg_ptrs = String::new();
+
+        for (index, arg_id) in args.iter().enumerate() {
+            let arg_type_name = type_name(*arg_id, types);
+
+            params.push(format!("arg{index}: {arg_type_name}"));
+            extern_params.push(format!("arg{index}: *const {arg_type_name}"));
+
+            // Roc takes ownership of the arguments, so they must not be dropped here.
+            writeln!(
+                owned_args,
+                "{INDENT}{INDENT}let arg{index} = core::mem::ManuallyDrop::new(arg{index});"
+            )
+            .unwrap();
+            write!(arg_ptrs, "&*arg{index}, ").unwrap();
+        }
+
+        extern_params.push(format!("closure_data: *const {name}"));
+        extern_params.push(format!("output: *mut {ret_type_name}"));
+
+        let params = params.join(", ");
+        let extern_params = extern_params.join(", ");
+
+        add_decl(
+            impls,
+            Some(format!("impl {name}")),
+            target_info,
+            format!(
+                r#"/// Calls this Roc function with the given arguments.
+    /// Roc takes ownership of the arguments, and of the values this function captured.
+    pub fn call({params}) -> {ret_type_name} {{
+        extern "C" {{
+            #[link_name = "{caller}"]
+            fn caller({extern_params});
+        }}
+
+{owned_args}        let closure_data = core::mem::ManuallyDrop::new(self);
+        let mut output = core::mem::MaybeUninit::uninit();
+
+        unsafe {{
+            caller({arg_ptrs}&*closure_data, output.as_mut_ptr());
+
+            output.assume_init()
+        }}
+    }}"#
+            ),
+        );
+    }
+}
+
 fn add_single_tag_struct(
     name: &str,
     tag_name: &str,
@@ -326,7 +443,7 @@ fn add_single_tag_struct(
             false,
         );
 
-        let mut body = format!("#[repr(C)]\n{derive}\npub struct {name} ");
+        let mut body = format!("#[repr(C)]\n{derive}pub struct {name} ");
 
         if payload_fields.is_empty() {
             // A single tag with no payload is a zero-sized unit type, so
@@ -885,7 +1002,8 @@ pub struct {name} {{
                     | RocType::RocBox(_)
                     | RocType::TagUnion(_)
                     | RocType::RocResult(_, _)
-                    | RocType::RecursivePointer { .. } => {
+                    | RocType::RecursivePointer { .. }
+                    | RocType::Function { .. } => {
                         owned_ret_type = type_name(*payload_id, types);
                         borrowed_ret_type = format!("&{}", owned_ret_type);
                         owned_ret = "payload".to_string();
@@ -919,7 +1037,6 @@ pub struct {name} {{
                         payload_args = answer.payload_args;
                         args_to_payload = answer.args_to_payload;
                     }
-                    RocType::Function { .. } => todo!(),
                 };
 
                 {
@@ -1508,7 +1625,8 @@ pub struct {name} {{
                             | RocType::TagUnion(_)
                             | RocType::RocResult(_, _)
                             | RocType::Struct { .. }
-                            | RocType::RecursivePointer { .. } => {
+                            | RocType::RecursivePointer { .. }
+                            | RocType::Function { .. } => {
                                 format!(".field({deref_str}{actual_self}.{tag_name})")
                             }
                             RocType::TagUnionPayload { fields, .. } => {
@@ -1523,7 +1641,6 @@ pub struct {name} {{
 
                                 buf.join("\n")
                             }
-                            RocType::Function { .. } => todo!(),
                         };
 
                         format!(
@@ -1588,7 +1705,7 @@ fn add_enumeration<I: ExactSizeIterator<Item = S>, S: AsRef<str> + Display>(
     let repr_bits = tag_bytes * 8;
 
     // e.g. "#[repr(u8)]\npub enum Foo {\n"
-    let mut buf = format!("{derive}\n#[repr(u{repr_bits})]\npub enum {name} {{\n");
+    let mut buf = format!("{derive}#[repr(u{repr_bits})]\npub enum {name} {{\n");
 
     // Debug impls should never vary by target_info.
     let mut debug_buf = format!(
@@ -1631,7 +1748,7 @@ fn add_struct<S: Display>(
     } else {
         "C"
     };
-    let mut buf = format!("{derive}\n#[repr({repr})]\n{pub_str}struct {name} {{\n");
+    let mut buf = format!("{derive}#[repr({repr})]\n{pub_str}struct {name} {{\n");
 
     for (label, type_id) in fields {
         let type_str = type_name(*type_id, types);
@@ -1704,7 +1821,13 @@ fn type_name(id: TypeId, types: &Types) -> String {
 /// This explicitly asks for whether to include Debug because in the very specific
 /// case of a struct that's a payload for a recursive tag union, typ.has_enumeration()
 /// will return true, but actually we want to derive Debug here anyway.
+///
+/// The returned attribute ends in a newline, and is empty for types which contain a function.
 fn derive_str(typ: &RocType, types: &Types, include_debug: bool) -> String {
+    if has_function(typ, types) {
+        return String::new();
+    }
+
     let mut buf = "#[derive(Clone, ".to_string();
 
     if !cannot_derive_copy(typ, types) {
@@ -1723,7 +1846,7 @@ fn derive_str(typ: &RocType, types: &Types, include_debug: bool) -> String {
         buf.push_str("Eq, Ord, Hash, ");
     }
 
-    buf.push_str("PartialEq, PartialOrd)]");
+    buf.push_str("PartialEq, PartialOrd)]\n");
 
     buf
 }
@@ -1815,7 +1938,8 @@ pub struct {name} {{
             | RocType::RocBox(_)
             | RocType::RocResult(_, _)
             | RocType::TagUnion(_)
-            | RocType::RecursivePointer { .. } => {
+            | RocType::RecursivePointer { .. }
+            | RocType::Function { .. } => {
                 owned_ret_type = type_name(non_null_payload, types);
                 borrowed_ret_type = format!("&{}", owned_ret_type);
                 payload_args = format!("arg: {owned_ret_type}");
@@ -1845,7 +1969,6 @@ pub struct {name} {{
                 owned_ret_type = answer.owned_ret_type;
                 borrowed_ret_type = answer.borrowed_ret_type;
             }
-            RocType::Function { .. } => todo!(),
         };
 
         // Add a convenience constructor function for the tag with the payload, e.g.
@@ -2064,7 +2187,8 @@ pub struct {name} {{
             | RocType::RocBox(_)
             | RocType::RocResult(_, _)
             | RocType::TagUnion(_)
-            | RocType::RecursivePointer { .. } => {
+            | RocType::RecursivePointer { .. }
+            | RocType::Function { .. } => {
                 format!(
                     r#"f.debug_tuple("{non_null_tag}").field(&*{extra_deref}self.pointer).finish()"#
                 )
@@ -2088,7 +2212,6 @@ pub struct {name} {{
 
                 buf.join(&format!("\n{INDENT}{INDENT}{INDENT}{INDENT}{INDENT}"))
             }
-            RocType::Function { .. } => todo!(),
         };
 
         let body = format!(
@@ -2127,7 +2250,7 @@ fn write_indents(indentations: usize, buf: &mut String) {
     }
 }
 
-fn max_pointer_tagged_variants(architecture: Architecture) -> usize {
+pub(crate) fn max_pointer_tagged_variants(architecture: Architecture) -> usize {
     match architecture {
         // On a 64-bit system, pointers have 3 bits that are unused, so return 2^3 = 8
         Architecture::X86_64 | Architecture::Aarch64 => 8,
@@ -2137,7 +2260,7 @@ fn max_pointer_tagged_variants(architecture: Architecture) -> usize {
 }
 
 #[inline(always)]
-fn tagged_pointer_bitmask(architecture: Architecture) -> u8 {
+pub(crate) fn tagged_pointer_bitmask(architecture: Architecture) -> u8 {
     match architecture {
         // On a 64-bit system, pointers have 3 bits that are unused
         Architecture::X86_64 | Architecture::Aarch64 => 0b0000_0111,
@@ -2350,6 +2473,69 @@ fn cannot_derive_copy(roc_type: &RocType, types: &Types) -> bool {
     }
 }
 
+/// Useful when determining whether to derive anything at all in a Rust type,
+/// because functions can't be cloned or compared.
+fn has_function(roc_type: &RocType, types: &Types) -> bool {
+    has_function_help(roc_type, types, &[])
+}
+
+fn has_function_help(roc_type: &RocType, types: &Types, do_not_recurse: &[TypeId]) -> bool {
+    match roc_type {
+        RocType::Function { .. } => true,
+        RocType::Unit
+        | RocType::EmptyTagUnion
+        | RocType::RocStr
+        | RocType::Bool
+        | RocType::Num(_)
+        | RocType::TagUnion(RocTagUnion::Enumeration { .. }) => false,
+        RocType::RocList(id) | RocType::RocSet(id) | RocType::RocBox(id) => {
+            has_function_help(types.get_type(*id), types, do_not_recurse)
+        }
+        RocType::RocResult(ok_id, err_id) => {
+            has_function_help(types.get_type(*ok_id), types, do_not_recurse)
+                || has_function_help(types.get_type(*err_id), types, do_not_recurse)
+        }
+        RocType::RocDict(key_id, val_id) => {
+            has_function_help(types.get_type(*key_id), types, do_not_recurse)
+                || has_function_help(types.get_type(*val_id), types, do_not_recurse)
+        }
+        RocType::Struct { fields, .. } => fields
+            .iter()
+            .any(|(_, type_id)| has_function_help(types.get_type(*type_id), types, do_not_recurse)),
+        RocType::TagUnionPayload { fields, .. } => fields
+            .iter()
+            .any(|(_, type_id)| has_function_help(types.get_type(*type_id), types, do_not_recurse)),
+        RocType::TagUnion(RocTagUnion::SingleTagStruct { payload_fields, .. }) => payload_fields
+            .iter()
+            .any(|type_id| has_function_help(types.get_type(*type_id), types, do_not_recurse)),
+        RocType::TagUnion(RocTagUnion::Recursive { tags, .. })
+        | RocType::TagUnion(RocTagUnion::NonRecursive { tags, .. })
+        | RocType::TagUnion(RocTagUnion::NullableWrapped { tags, .. }) => {
+            tags.iter().any(|(_, payloads)| {
+                payloads
+                    .iter()
+                    .any(|id| has_function_help(types.get_type(*id), types, do_not_recurse))
+            })
+        }
+        RocType::TagUnion(RocTagUnion::NonNullableUnwrapped { payload, .. })
+        | RocType::TagUnion(RocTagUnion::NullableUnwrapped {
+            non_null_payload: payload,
+            ..
+        })
+        | RocType::RecursivePointer(payload) => {
+            if do_not_recurse.contains(payload) {
+                false
+            } else {
+                let mut do_not_recurse: Vec<TypeId> = do_not_recurse.into();
+
+                do_not_recurse.push(*payload);
+
+                has_function_help(types.get_type(*payload), types, &do_not_recurse)
+            }
+        }
+    }
+}
+
 /// Useful when determining whether to derive Eq, Ord, and Hash in a Rust type.
 fn has_float(roc_type: &RocType, types: &Types) -> bool {
     has_float_help(roc_type, types, &[])
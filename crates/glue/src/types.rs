@@ -17,7 +17,7 @@ use roc_mono::layout::{
 };
 use roc_target::TargetInfo;
 use roc_types::{
-    subs::{Content, FlatType, GetSubsSlice, Subs, UnionLabels, UnionTags, Variable},
+    subs::{Content, FlatType, GetSubsSlice, LambdaSet, Subs, UnionLabels, UnionTags, Variable},
     types::RecordField,
 };
 use std::fmt::Display;
@@ -49,6 +49,9 @@ pub struct Types {
     /// This is important for declaration order in C; we need to output a
     /// type declaration earlier in the file than where it gets referenced by another type.
     deps: VecMap<TypeId, Vec<TypeId>>,
+
+    /// The top-level declarations the host can call, by name.
+    entry_points: Vec<(String, TypeId)>,
     target: TargetInfo,
 }
 
@@ -61,6 +64,7 @@ impl Types {
             sizes: Vec::new(),
             aligns: Vec::new(),
             deps: VecMap::with_capacity(cap),
+            entry_points: Vec::with_capacity(cap),
         }
     }
 
@@ -272,11 +276,13 @@ impl Types {
                     name: name_a,
                     args: args_a,
                     ret: ret_a,
+                    ..
                 },
                 Function {
                     name: name_b,
                     args: args_b,
                     ret: ret_b,
+                    ..
                 },
             ) => {
                 // for functions, the name is actually important because two functions
@@ -364,6 +370,14 @@ impl Types {
         self.deps.get_or_insert(id, Vec::new).push(depends_on);
     }
 
+    pub fn add_entry_point(&mut self, name: String, id: TypeId) {
+        self.entry_points.push((name, id));
+    }
+
+    pub fn entry_points(&self) -> &[(String, TypeId)] {
+        &self.entry_points
+    }
+
     pub fn get_type(&self, id: TypeId) -> &RocType {
         match self.types.get(id.0) {
             Some(typ) => typ,
@@ -454,10 +468,18 @@ pub enum RocType {
     /// this would be the field of Cons containing the (recursive) StrConsList type,
     /// and the TypeId is the TypeId of StrConsList itself.
     RecursivePointer(TypeId),
+    /// A function value, which at runtime is its closure data (the values it captured).
     Function {
         name: String,
+        /// The symbol the host calls to run this closure, e.g. `roc__mainForHost_1__Fx_caller`.
+        /// Only functions which the platform names using `as` (e.g. `(Str -> Str) as Fx`)
+        /// are exposed to the host like this.
+        caller: Option<String>,
         args: Vec<TypeId>,
         ret: TypeId,
+        /// The values the function captured, which the host has to drop unless it calls the function.
+        /// This is `None` if there is nothing to drop.
+        captures: Option<TypeId>,
     },
     /// A zero-sized type, such as an empty record or a single-tag union with no payload
     Unit,
@@ -601,6 +623,8 @@ pub struct Env<'a> {
     enum_names: Enums,
     pending_recursive_types: VecMap<TypeId, Variable>,
     known_recursive_types: VecMap<Variable, TypeId>,
+    /// The name of the entry point whose types are currently being added
+    entry_point: String,
     target: TargetInfo,
 }
 
@@ -619,19 +643,24 @@ impl<'a> Env<'a> {
             enum_names: Default::default(),
             pending_recursive_types: Default::default(),
             known_recursive_types: Default::default(),
+            entry_point: String::new(),
             layout_cache: LayoutCache::new(target),
             target,
         }
     }
 
-    pub fn vars_to_types<I>(&mut self, variables: I) -> Types
+    pub fn entry_points_to_types<I>(&mut self, entry_points: I) -> Types
     where
-        I: Iterator<Item = Variable>,
+        I: Iterator<Item = (String, Variable)>,
     {
-        let mut types = Types::with_capacity(variables.size_hint().0, self.target);
+        let mut types = Types::with_capacity(entry_points.size_hint().0, self.target);
+
+        for (name, var) in entry_points {
+            self.entry_point.clone_from(&name);
+
+            let id = self.add_type(var, &mut types);
 
-        for var in variables {
-            self.add_type(var, &mut types);
+            types.add_entry_point(name, id);
         }
 
         self.resolve_pending_recursive_types(&mut types);
@@ -729,10 +758,19 @@ fn add_type_help<'a>(
 
             add_tag_union(env, opt_name, tags, var, types, layout, Some(rec_root))
         }
-        Content::Structure(FlatType::Apply(symbol, _)) => match layout {
+        Content::Structure(FlatType::Apply(symbol, args)) => match layout {
             Layout::Builtin(builtin) => {
                 add_builtin_type(env, builtin, var, opt_name, types, layout)
             }
+            Layout::Boxed(elem_layout) if *symbol == Symbol::BOX_BOX_TYPE => {
+                let elem_var = env.subs.get_subs_slice(*args)[0];
+                let elem_id = add_type_help(env, *elem_layout, elem_var, None, types);
+                let type_id = types.add_anonymous(RocType::RocBox(elem_id), layout);
+
+                types.depends(type_id, elem_id);
+
+                type_id
+            }
             _ => {
                 if symbol.is_builtin() {
                     todo!(
@@ -771,19 +809,46 @@ fn add_type_help<'a>(
                 add_type_help(env, ret_layout, *ret_var, None, types)
             };
 
-            let name = format!("TODO_roc_function_{:?}", closure_var);
+            let (name, caller) = match opt_name {
+                Some(sym) => {
+                    let name = sym.as_str(env.interns).to_string();
+                    // This is the name the backends give the closure caller, e.g. `roc__mainForHost_1__Fx_caller`
+                    // (the module name of a platform is empty).
+                    let caller = format!(
+                        "roc__{}_1_{}_{}_caller",
+                        env.entry_point,
+                        sym.module_string(env.interns),
+                        name
+                    );
+
+                    (name, Some(caller))
+                }
+                None => (format!("TODO_roc_function_{:?}", closure_var), None),
+            };
+            // Only the host drops the captures of functions it calls through a caller.
+            // Others, like entry points, are exposed to the host directly.
+            let captures = match caller {
+                Some(_) => add_captures(env, &name, *closure_var, layout, types),
+                None => None,
+            };
             let fn_type_id = types.add_named(
                 name.clone(),
                 RocType::Function {
                     name,
+                    caller,
                     args: arg_type_ids.clone(),
                     ret: ret_type_id,
+                    captures,
                 },
                 layout,
             );
 
             types.depends(fn_type_id, ret_type_id);
 
+            if let Some(captures_id) = captures {
+                types.depends(fn_type_id, captures_id);
+            }
+
             for arg_type_id in arg_type_ids {
                 types.depends(fn_type_id, arg_type_id);
             }
@@ -856,6 +921,44 @@ fn add_type_help<'a>(
                             }
                         }
                     }
+                    Layout::Struct { .. } if *name == Symbol::DICT_DICT => {
+                        let type_vars = env.subs.get_subs_slice(alias_vars.type_variables());
+                        debug_assert_eq!(type_vars.len(), 2);
+
+                        let key_var = type_vars[0];
+                        let key_layout =
+                            env.layout_cache.from_var(env.arena, key_var, subs).unwrap();
+                        let key_id = add_type_help(env, key_layout, key_var, None, types);
+
+                        let val_var = type_vars[1];
+                        let val_layout =
+                            env.layout_cache.from_var(env.arena, val_var, subs).unwrap();
+                        let val_id = add_type_help(env, val_layout, val_var, None, types);
+
+                        let type_id = types.add_anonymous(RocType::RocDict(key_id, val_id), layout);
+
+                        types.depends(type_id, key_id);
+                        types.depends(type_id, val_id);
+
+                        type_id
+                    }
+                    Layout::Struct { .. } if *name == Symbol::SET_SET => {
+                        let type_vars = env.subs.get_subs_slice(alias_vars.type_variables());
+                        debug_assert_eq!(type_vars.len(), 1);
+
+                        let elem_var = type_vars[0];
+                        let elem_layout = env
+                            .layout_cache
+                            .from_var(env.arena, elem_var, subs)
+                            .unwrap();
+                        let elem_id = add_type_help(env, elem_layout, elem_var, None, types);
+
+                        let type_id = types.add_anonymous(RocType::RocSet(elem_id), layout);
+
+                        types.depends(type_id, elem_id);
+
+                        type_id
+                    }
                     _ => {
                         unreachable!()
                     }
@@ -889,6 +992,75 @@ fn add_type_help<'a>(
     }
 }
 
+/// Adds the type of the values captured by a function, which is how the function is represented at runtime.
+fn add_captures<'a>(
+    env: &mut Env<'a>,
+    name: &str,
+    closure_var: Variable,
+    layout: Layout<'a>,
+    types: &mut Types,
+) -> Option<TypeId> {
+    let runtime_layout = match layout {
+        Layout::LambdaSet(lambda_set) => lambda_set.runtime_representation(),
+        _ => return None,
+    };
+
+    let mut lambdas: Vec<(Symbol, Vec<Variable>)> =
+        match env.subs.get_content_without_compacting(closure_var) {
+            Content::LambdaSet(LambdaSet { solved, .. }) => solved
+                .iter_from_subs(env.subs)
+                .map(|(symbol, capture_vars)| (*symbol, capture_vars.to_vec()))
+                .collect(),
+            _ => return None,
+        };
+
+    // Sort the lambdas the way their layout does, because that determines their tag ids.
+    lambdas.sort_by_key(|(symbol, _)| *symbol);
+
+    let name = format!("{}_Captures", name);
+
+    match runtime_layout {
+        // A single lambda is represented by the values it captured.
+        Layout::Struct { field_layouts, .. } if lambdas.len() == 1 && !field_layouts.is_empty() => {
+            let (_, capture_vars) = lambdas.pop().unwrap();
+            let fields = capture_vars.into_iter().enumerate();
+
+            Some(add_struct(
+                env,
+                name,
+                fields,
+                types,
+                runtime_layout,
+                |name, fields| RocType::TagUnionPayload { name, fields },
+            ))
+        }
+        // Several lambdas are represented as a tag union, with a tag for each of them.
+        Layout::Union(union_layout @ UnionLayout::NonRecursive(_)) => {
+            let tags = lambdas
+                .into_iter()
+                .enumerate()
+                .map(|(index, (_, capture_vars))| (format!("Lambda{}", index), capture_vars))
+                .collect();
+            let tags = tags_to_types(&name, tags, env, types, runtime_layout, false);
+            let discriminant_size = Discriminant::from_number_of_tags(tags.len())
+                .stack_size()
+                .max(1);
+            let discriminant_offset = union_layout.tag_id_offset(env.target).unwrap();
+            let tag_union = RocTagUnion::NonRecursive {
+                name: name.clone(),
+                tags,
+                discriminant_size,
+                discriminant_offset,
+            };
+
+            Some(types.add_named(name, RocType::TagUnion(tag_union), runtime_layout))
+        }
+        // Nothing was captured, or only whether one of several lambdas was picked.
+        // TODO recursive lambda sets are still opaque to the host, so their captures are never dropped.
+        _ => None,
+    }
+}
+
 fn add_builtin_type<'a>(
     env: &mut Env<'a>,
     builtin: Builtin<'a>,
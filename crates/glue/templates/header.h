@@ -0,0 +1,190 @@
+// ⚠️ GENERATED CODE ⚠️ - this entire file was generated by the `roc glue` CLI command
+
+#pragma once
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+#ifdef __cplusplus
+#define ROC_ALIGNAS(n) alignas(n)
+#define ROC_ALIGNOF(type) alignof(type)
+extern "C" {
+#else
+#define ROC_ALIGNAS(n) _Alignas(n)
+#define ROC_ALIGNOF(type) _Alignof(type)
+#endif
+
+// These must be provided by the host.
+void *roc_alloc(size_t size, unsigned int alignment);
+void *roc_realloc(void *ptr, size_t new_size, size_t old_size, unsigned int alignment);
+void roc_dealloc(void *ptr, unsigned int alignment);
+
+#ifdef __cplusplus
+}
+#endif
+
+// The refcount of a heap allocation is stored just before its contents. A refcount of
+// zero means the allocation is read-only (e.g. a constant), so it is never freed.
+#define ROC_REFCOUNT_ONE INTPTR_MIN
+#define ROC_REFCOUNT_READONLY 0
+
+static inline size_t roc_refcount_prefix(unsigned int alignment) {
+    return alignment > sizeof(intptr_t) ? alignment : sizeof(intptr_t);
+}
+
+static inline intptr_t *roc_refcount_ptr(void *data) {
+    return (intptr_t *)data - 1;
+}
+
+/// Allocates `size` bytes with room for a refcount, which starts out at 1.
+static inline void *roc_alloc_refcounted(size_t size, unsigned int alignment) {
+    size_t prefix = roc_refcount_prefix(alignment);
+    char *allocation = (char *)roc_alloc(prefix + size, alignment);
+    void *data = allocation + prefix;
+
+    *roc_refcount_ptr(data) = ROC_REFCOUNT_ONE;
+
+    return data;
+}
+
+static inline void roc_dealloc_refcounted(void *data, unsigned int alignment) {
+    roc_dealloc((char *)data - roc_refcount_prefix(alignment), alignment);
+}
+
+static inline void roc_incref(void *data) {
+    if (data != NULL && *roc_refcount_ptr(data) != ROC_REFCOUNT_READONLY) {
+        *roc_refcount_ptr(data) += 1;
+    }
+}
+
+/// Decrements the refcount, and returns true if this was the last reference. In that case
+/// the caller must release whatever the allocation refers to and then deallocate it.
+static inline bool roc_decref_is_last(void *data) {
+    if (data == NULL || *roc_refcount_ptr(data) == ROC_REFCOUNT_READONLY) {
+        return false;
+    } else if (*roc_refcount_ptr(data) == ROC_REFCOUNT_ONE) {
+        return true;
+    } else {
+        *roc_refcount_ptr(data) -= 1;
+
+        return false;
+    }
+}
+
+typedef struct RocStr {
+    char *bytes;
+    size_t len;
+    size_t capacity;
+} RocStr;
+
+typedef struct RocList {
+    void *elements;
+    size_t len;
+    size_t capacity;
+} RocList;
+
+typedef struct RocBox {
+    void *contents;
+} RocBox;
+
+typedef struct RocI128 {
+    ROC_ALIGNAS(16) uint8_t bytes[16];
+} RocI128;
+
+typedef struct RocU128 {
+    ROC_ALIGNAS(16) uint8_t bytes[16];
+} RocU128;
+
+typedef struct RocDec {
+    uint8_t bytes[16];
+} RocDec;
+
+typedef struct RocF128 {
+    ROC_ALIGNAS(16) uint8_t bytes[16];
+} RocF128;
+
+/// Small strings are stored inline, in the bytes of the struct itself. Their length is in the
+/// last byte, and the highest bit of the capacity is set.
+static inline bool roc_str_is_small(const RocStr *str) {
+    return (intptr_t)str->capacity < 0;
+}
+
+static inline size_t roc_str_len(const RocStr *str) {
+    if (roc_str_is_small(str)) {
+        return ((const uint8_t *)str)[sizeof(RocStr) - 1] ^ 0x80;
+    } else {
+        return str->len;
+    }
+}
+
+/// The bytes of the string, which are not nul-terminated.
+static inline const char *roc_str_bytes(const RocStr *str) {
+    if (roc_str_is_small(str)) {
+        return (const char *)str;
+    } else {
+        return str->bytes;
+    }
+}
+
+static inline RocStr roc_str_from(const char *bytes, size_t len) {
+    RocStr str;
+
+    if (len < sizeof(RocStr)) {
+        memset(&str, 0, sizeof(RocStr));
+        memcpy(&str, bytes, len);
+        ((uint8_t *)&str)[sizeof(RocStr) - 1] = (uint8_t)len | 0x80;
+    } else {
+        str.bytes = (char *)roc_alloc_refcounted(len, 1);
+        str.len = len;
+        str.capacity = len;
+
+        memcpy(str.bytes, bytes, len);
+    }
+
+    return str;
+}
+
+static inline void roc_str_incref(const RocStr *str) {
+    if (!roc_str_is_small(str)) {
+        roc_incref(str->bytes);
+    }
+}
+
+static inline void roc_str_decref(const RocStr *str) {
+    if (!roc_str_is_small(str) && roc_decref_is_last(str->bytes)) {
+        roc_dealloc_refcounted(str->bytes, 1);
+    }
+}
+
+static inline size_t roc_list_len(const RocList *list) {
+    return list->len;
+}
+
+static inline void *roc_list_elements(const RocList *list) {
+    return list->elements;
+}
+
+/// Allocates an empty list with room for `capacity` elements.
+static inline RocList roc_list_with_capacity(size_t capacity, size_t element_size, unsigned int alignment) {
+    RocList list;
+
+    list.elements = capacity == 0 ? NULL : roc_alloc_refcounted(capacity * element_size, alignment);
+    list.len = 0;
+    list.capacity = capacity;
+
+    return list;
+}
+
+static inline void roc_list_incref(const RocList *list) {
+    roc_incref(list->elements);
+}
+
+/// Only for lists whose elements are not refcounted themselves; the generated
+/// `_decref` functions release the elements of other lists first.
+static inline void roc_list_decref(const RocList *list, unsigned int alignment) {
+    if (roc_decref_is_last(list->elements)) {
+        roc_dealloc_refcounted(list->elements, alignment);
+    }
+}
@@ -0,0 +1,17 @@
+#include "test_glue.h"
+#include "roc_host.h"
+
+int main(void) {
+    MyRcd record;
+
+    roc__mainForHost_1_exposed_generic(&record);
+
+    // Only the low 64 bits of the U128 are in use, and every target we test is little-endian.
+    uint64_t b;
+
+    memcpy(&b, record.b.bytes, sizeof(b));
+
+    printf("Record was: { a: %llu, b: %llu }\n", (unsigned long long)record.a, (unsigned long long)b);
+
+    return 0;
+}
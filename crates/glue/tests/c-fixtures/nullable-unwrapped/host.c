@@ -0,0 +1,53 @@
+#include "test_glue.h"
+#include "roc_host.h"
+
+static void print_list(StrConsList list) {
+    int depth = 0;
+
+    while (StrConsList_discriminant(&list) == discriminant_StrConsList_Cons) {
+        StrConsList_Cons cons = StrConsList_get_Cons(&list);
+
+        printf("Cons \"%.*s\" ", (int)roc_str_len(&cons.f0), roc_str_bytes(&cons.f0));
+
+        list = cons.f1;
+
+        if (StrConsList_discriminant(&list) == discriminant_StrConsList_Cons) {
+            printf("(");
+            depth++;
+        }
+    }
+
+    printf("Nil");
+
+    for (; depth > 0; depth--) {
+        printf(")");
+    }
+}
+
+static void print_tag_union(const char *description, StrConsList list) {
+    printf("%s: ", description);
+    print_list(list);
+    printf("\n");
+}
+
+int main(void) {
+    StrConsList list;
+
+    roc__mainForHost_1_exposed_generic(&list);
+
+    print_tag_union("tag_union was", list);
+
+    StrConsList_Cons cons = { roc_str_from("small str", 9), StrConsList_new_Nil() };
+    StrConsList small = StrConsList_new_Cons(cons);
+
+    print_tag_union("`Cons \"small str\" Nil` is", small);
+
+    StrConsList nil = StrConsList_new_Nil();
+
+    print_tag_union("`Nil` is", nil);
+
+    StrConsList_decref(&list);
+    StrConsList_decref(&small);
+
+    return 0;
+}
@@ -0,0 +1,63 @@
+#include "test_glue.h"
+#include "roc_host.h"
+
+static void print_tag_union(const char *description, const NonRecursive *tag_union) {
+    printf("%s: ", description);
+
+    switch (NonRecursive_discriminant(tag_union)) {
+    case discriminant_NonRecursive_Foo: {
+        RocStr str = NonRecursive_get_Foo(tag_union);
+
+        printf("Foo \"%.*s\"\n", (int)roc_str_len(&str), roc_str_bytes(&str));
+        break;
+    }
+    case discriminant_NonRecursive_Bar: {
+        // Only the low 64 bits are in use, and every target we test is little-endian.
+        RocU128 bar = NonRecursive_get_Bar(tag_union);
+        uint64_t low;
+
+        memcpy(&low, bar.bytes, sizeof(low));
+
+        printf("Bar %llu\n", (unsigned long long)low);
+        break;
+    }
+    case discriminant_NonRecursive_Blah:
+        printf("Blah %d\n", (int)NonRecursive_get_Blah(tag_union));
+        break;
+    case discriminant_NonRecursive_Baz:
+        printf("Baz\n");
+        break;
+    }
+}
+
+int main(void) {
+    NonRecursive tag_union;
+
+    roc__mainForHost_1_exposed_generic(&tag_union);
+
+    const char *small = "small str";
+    const char *big = "A long enough string to not be small";
+    RocU128 bar;
+
+    memset(&bar, 0, sizeof(bar));
+    bar.bytes[0] = 123;
+
+    NonRecursive small_foo = NonRecursive_new_Foo(roc_str_from(small, strlen(small)));
+    NonRecursive big_foo = NonRecursive_new_Foo(roc_str_from(big, strlen(big)));
+    NonRecursive bar_union = NonRecursive_new_Bar(bar);
+    NonRecursive baz = NonRecursive_new_Baz();
+    NonRecursive blah = NonRecursive_new_Blah(456);
+
+    print_tag_union("tag_union was", &tag_union);
+    print_tag_union("`Foo \"small str\"` is", &small_foo);
+    print_tag_union("`Foo \"A long enough string to not be small\"` is", &big_foo);
+    print_tag_union("`Bar 123` is", &bar_union);
+    print_tag_union("`Baz` is", &baz);
+    print_tag_union("`Blah 456` is", &blah);
+
+    NonRecursive_decref(&tag_union);
+    NonRecursive_decref(&small_foo);
+    NonRecursive_decref(&big_foo);
+
+    return 0;
+}
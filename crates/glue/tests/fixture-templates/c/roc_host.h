@@ -0,0 +1,34 @@
+// ⚠️ READ THIS BEFORE MODIFYING THIS FILE! ⚠️
+//
+// This file is a fixture template. If the file you're looking at is
+// in the fixture-templates/ directory, then you're all set - go ahead
+// and modify it, and it will modify all the fixture tests.
+//
+// If this file is in the c-fixtures/ directory, on the other hand, then
+// it is gitignored and will be overwritten the next time tests run.
+// So you probably don't want to modify it by hand! Instead, modify the
+// file with the same name in the fixture-templates/ directory.
+
+// Externs required by the Roc app. Include this from host.c only.
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+void *roc_alloc(size_t size, unsigned int alignment) { return malloc(size); }
+
+void *roc_realloc(void *ptr, size_t new_size, size_t old_size, unsigned int alignment) {
+    return realloc(ptr, new_size);
+}
+
+void roc_dealloc(void *ptr, unsigned int alignment) { free(ptr); }
+
+void roc_panic(void *ptr, unsigned int alignment) {
+    char *msg = (char *)ptr;
+    fprintf(stderr, "Roc hit a panic: %s\n", msg);
+    exit(1);
+}
+
+void *roc_memcpy(void *dest, const void *src, size_t n) { return memcpy(dest, src, n); }
+
+void *roc_memset(void *str, int c, size_t n) { return memset(str, c, n); }
@@ -0,0 +1,11 @@
+app "app"
+    packages { pf: "platform.roc" }
+    imports []
+    provides [main] to pf
+
+main =
+    # Both of these are allocated on the heap, so the closure has to keep them alive.
+    greeting = "Hello to everyone who is here today, and welcome: "
+    guests = ["Alice", "Bob"]
+
+    { greet: \name -> Str.concat greeting (Str.joinWith (List.append guests name) ", "), count: 2 }
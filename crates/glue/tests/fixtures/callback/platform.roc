@@ -0,0 +1,9 @@
+platform "test-platform"
+    requires {} { main : _ }
+    exposes []
+    packages {}
+    imports []
+    provides [mainForHost]
+
+mainForHost : { greet : (Str -> Str) as Greet, count : U64 }
+mainForHost = main
@@ -0,0 +1,23 @@
+app "app"
+    packages { pf: "platform.roc" }
+    imports []
+    provides [main] to pf
+
+main = \populations ->
+    describe = \city ->
+        when Dict.get populations city is
+            Ok population -> "\(city): \(Num.toStr population)"
+            Err KeyNotFound -> "\(city): unknown"
+
+    updated =
+        populations
+        |> Dict.remove "London"
+        |> Dict.insert "Rome" 2873000
+        |> Dict.insert "Delhi" 16800000
+
+    report =
+        ["Shanghai", "Amsterdam", "Rome"]
+        |> List.map describe
+        |> Str.joinWith ", "
+
+    { updated, report }
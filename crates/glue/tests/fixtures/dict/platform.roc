@@ -0,0 +1,12 @@
+platform "test-platform"
+    requires {} { main : _ }
+    exposes []
+    packages {}
+    imports []
+    provides [mainForHost]
+
+Census : { updated : Dict Str U64, report : Str }
+
+# The dictionary is boxed, so that the host can pass it by pointer.
+mainForHost : Box (Dict Str U64) -> Census
+mainForHost = \populations -> main (Box.unbox populations)
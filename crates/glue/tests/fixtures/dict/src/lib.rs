@@ -0,0 +1,96 @@
+mod test_glue;
+
+use roc_std::{RocBox, RocDict, RocStr};
+
+extern "C" {
+    #[link_name = "roc__mainForHost_1_exposed_generic"]
+    fn roc_main(_: *mut test_glue::Census, _: RocBox<RocDict<RocStr, u64>>);
+}
+
+#[no_mangle]
+pub extern "C" fn rust_main() -> i32 {
+    // Roc looks up and removes these entries using the buckets built here.
+    let populations: RocDict<RocStr, u64> = [
+        ("London", 8_961_989),
+        ("Philadelphia", 1_603_797),
+        ("Shanghai", 24_870_895),
+        ("Delhi", 16_787_941),
+        ("Amsterdam", 872_680),
+    ]
+    .into_iter()
+    .map(|(city, population)| (RocStr::from(city), population))
+    .collect();
+
+    let census = unsafe {
+        let mut ret: core::mem::MaybeUninit<test_glue::Census> = core::mem::MaybeUninit::uninit();
+
+        roc_main(ret.as_mut_ptr(), RocBox::new(populations));
+
+        ret.assume_init()
+    };
+
+    println!("Roc found {}", census.report);
+
+    // The other way around, the host looks up entries using the buckets Roc built.
+    let lookup = |city: &str| census.updated.get(&RocStr::from(city)).copied();
+
+    println!(
+        "Host found Rome: {:?}, Delhi: {:?}, London: {:?}",
+        lookup("Rome"),
+        lookup("Delhi"),
+        lookup("London")
+    );
+    println!("Cities: {:?}", census.updated.keys().collect::<Vec<_>>());
+
+    // Exit code
+    0
+}
+
+// Externs required by roc_std and by the Roc app
+
+use core::ffi::c_void;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_alloc(size: usize, _alignment: u32) -> *mut c_void {
+    return libc::malloc(size);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_realloc(
+    c_ptr: *mut c_void,
+    new_size: usize,
+    _old_size: usize,
+    _alignment: u32,
+) -> *mut c_void {
+    return libc::realloc(c_ptr, new_size);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_dealloc(c_ptr: *mut c_void, _alignment: u32) {
+    return libc::free(c_ptr);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_panic(c_ptr: *mut c_void, tag_id: u32) {
+    match tag_id {
+        0 => {
+            let slice = CStr::from_ptr(c_ptr as *const c_char);
+            let string = slice.to_str().unwrap();
+            eprintln!("Roc hit a panic: {}", string);
+            std::process::exit(1);
+        }
+        _ => todo!(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_memcpy(dst: *mut c_void, src: *mut c_void, n: usize) -> *mut c_void {
+    libc::memcpy(dst, src, n)
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn roc_memset(dst: *mut c_void, c: i32, n: usize) -> *mut c_void {
+    libc::memset(dst, c, n)
+}
@@ -0,0 +1,641 @@
+#[macro_use]
+extern crate pretty_assertions;
+
+#[macro_use]
+extern crate indoc;
+
+mod helpers;
+
+#[cfg(test)]
+mod test_gen_c {
+    use crate::helpers::{generate_c_bindings, unsupported_in_c};
+    use roc_glue::c_glue;
+    use roc_glue::types::{RocNum, RocType, Types};
+    use roc_mono::layout::{Builtin, Layout};
+    use roc_target::TargetInfo;
+
+    #[test]
+    fn basic_record_aliased() {
+        let module = indoc!(
+            r#"
+            MyRcd : { a : U64, b : I128 }
+
+            main : MyRcd
+            main = { a: 1u64, b: 2i128 }
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                typedef struct MyRcd MyRcd;
+
+                struct MyRcd {
+                    RocI128 b;
+                    uint64_t a;
+                };
+
+                #ifdef __cplusplus
+                extern "C" {
+                #endif
+
+                void roc__main_1_exposed_generic(MyRcd *ret);
+
+                #ifdef __cplusplus
+                }
+                #endif
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn enumeration() {
+        let module = indoc!(
+            r#"
+            Enumeration : [Blah, Foo, Bar]
+
+            main : Enumeration
+            main = Foo
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                typedef uint8_t Enumeration;
+
+                enum {
+                    Enumeration_Bar = 0,
+                    Enumeration_Blah = 1,
+                    Enumeration_Foo = 2,
+                };
+
+                #ifdef __cplusplus
+                extern "C" {
+                #endif
+
+                void roc__main_1_exposed_generic(Enumeration *ret);
+
+                #ifdef __cplusplus
+                }
+                #endif
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn non_recursive_union() {
+        let module = indoc!(
+            r#"
+            NonRecursive : [Foo Str, Bar U128, Blah I32, Baz]
+
+            main : NonRecursive
+            main = Foo "blah"
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                typedef union NonRecursive NonRecursive;
+
+                typedef uint8_t discriminant_NonRecursive;
+
+                enum {
+                    discriminant_NonRecursive_Bar = 0,
+                    discriminant_NonRecursive_Baz = 1,
+                    discriminant_NonRecursive_Blah = 2,
+                    discriminant_NonRecursive_Foo = 3,
+                };
+
+                #if defined(__arm__) || defined(__wasm32__) || defined(__i386__)
+                union NonRecursive {
+                    RocU128 Bar;
+                    int32_t Blah;
+                    RocStr Foo;
+                    uint8_t _sizer[32];
+                };
+                #endif
+
+                #if defined(__aarch64__) || defined(__x86_64__)
+                union NonRecursive {
+                    RocU128 Bar;
+                    int32_t Blah;
+                    RocStr Foo;
+                    uint8_t _sizer[48];
+                };
+                #endif
+
+                static inline void NonRecursive_incref(const NonRecursive *self);
+                static inline void NonRecursive_decref(const NonRecursive *self);
+
+                #if defined(__arm__) || defined(__wasm32__) || defined(__i386__)
+                static inline discriminant_NonRecursive NonRecursive_discriminant(const NonRecursive *self) {
+                    discriminant_NonRecursive discriminant;
+
+                    memcpy(&discriminant, (const char *)self + 16, sizeof(discriminant));
+
+                    return discriminant;
+                }
+                #endif
+
+                #if defined(__aarch64__) || defined(__x86_64__)
+                static inline discriminant_NonRecursive NonRecursive_discriminant(const NonRecursive *self) {
+                    discriminant_NonRecursive discriminant;
+
+                    memcpy(&discriminant, (const char *)self + 32, sizeof(discriminant));
+
+                    return discriminant;
+                }
+                #endif
+
+                #if defined(__arm__) || defined(__wasm32__) || defined(__i386__)
+                static inline void NonRecursive_set_discriminant(NonRecursive *self, discriminant_NonRecursive discriminant) {
+                    memcpy((char *)self + 16, &discriminant, sizeof(discriminant));
+                }
+                #endif
+
+                #if defined(__aarch64__) || defined(__x86_64__)
+                static inline void NonRecursive_set_discriminant(NonRecursive *self, discriminant_NonRecursive discriminant) {
+                    memcpy((char *)self + 32, &discriminant, sizeof(discriminant));
+                }
+                #endif
+
+                static inline NonRecursive NonRecursive_new_Bar(RocU128 payload) {
+                    NonRecursive self;
+
+                    self.Bar = payload;
+                    NonRecursive_set_discriminant(&self, discriminant_NonRecursive_Bar);
+
+                    return self;
+                }
+
+                static inline NonRecursive NonRecursive_new_Baz(void) {
+                    NonRecursive self;
+
+                    NonRecursive_set_discriminant(&self, discriminant_NonRecursive_Baz);
+
+                    return self;
+                }
+
+                static inline NonRecursive NonRecursive_new_Blah(int32_t payload) {
+                    NonRecursive self;
+
+                    self.Blah = payload;
+                    NonRecursive_set_discriminant(&self, discriminant_NonRecursive_Blah);
+
+                    return self;
+                }
+
+                static inline NonRecursive NonRecursive_new_Foo(RocStr payload) {
+                    NonRecursive self;
+
+                    self.Foo = payload;
+                    NonRecursive_set_discriminant(&self, discriminant_NonRecursive_Foo);
+
+                    return self;
+                }
+
+                // Only valid if the discriminant is discriminant_NonRecursive_Bar.
+                static inline RocU128 NonRecursive_get_Bar(const NonRecursive *self) {
+                    return self->Bar;
+                }
+
+                // Only valid if the discriminant is discriminant_NonRecursive_Blah.
+                static inline int32_t NonRecursive_get_Blah(const NonRecursive *self) {
+                    return self->Blah;
+                }
+
+                // Only valid if the discriminant is discriminant_NonRecursive_Foo.
+                static inline RocStr NonRecursive_get_Foo(const NonRecursive *self) {
+                    return self->Foo;
+                }
+
+                static inline void NonRecursive_incref(const NonRecursive *self) {
+                    switch (NonRecursive_discriminant(self)) {
+                    case discriminant_NonRecursive_Foo:
+                        roc_str_incref(&self->Foo);
+                        break;
+                    default:
+                        break;
+                    }
+                }
+
+                static inline void NonRecursive_decref(const NonRecursive *self) {
+                    switch (NonRecursive_discriminant(self)) {
+                    case discriminant_NonRecursive_Foo:
+                        roc_str_decref(&self->Foo);
+                        break;
+                    default:
+                        break;
+                    }
+                }
+
+                #ifdef __cplusplus
+                extern "C" {
+                #endif
+
+                void roc__main_1_exposed_generic(NonRecursive *ret);
+
+                #ifdef __cplusplus
+                }
+                #endif
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn nullable_unwrapped_union() {
+        let module = indoc!(
+            r#"
+            StrConsList : [Nil, Cons Str StrConsList]
+
+            main : StrConsList
+            main = Cons "Hello, " (Cons "World!" Nil)
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                typedef struct StrConsList_Cons StrConsList_Cons;
+
+                typedef struct StrConsList StrConsList;
+
+                typedef uint8_t discriminant_StrConsList;
+
+                enum {
+                    discriminant_StrConsList_Cons = 0,
+                    discriminant_StrConsList_Nil = 1,
+                };
+
+                struct StrConsList {
+                    StrConsList_Cons *pointer;
+                };
+
+                struct StrConsList_Cons {
+                    RocStr f0;
+                    StrConsList f1;
+                };
+
+                static inline void StrConsList_Cons_incref(const StrConsList_Cons *self);
+                static inline void StrConsList_Cons_decref(const StrConsList_Cons *self);
+
+                static inline void StrConsList_incref(const StrConsList *self);
+                static inline void StrConsList_decref(const StrConsList *self);
+
+                static inline void StrConsList_Cons_incref(const StrConsList_Cons *self) {
+                    roc_str_incref(&self->f0);
+                    StrConsList_incref(&self->f1);
+                }
+
+                static inline void StrConsList_Cons_decref(const StrConsList_Cons *self) {
+                    roc_str_decref(&self->f0);
+                    StrConsList_decref(&self->f1);
+                }
+
+                static inline discriminant_StrConsList StrConsList_discriminant(const StrConsList *self) {
+                    return self->pointer == NULL ? discriminant_StrConsList_Nil : discriminant_StrConsList_Cons;
+                }
+
+                static inline StrConsList StrConsList_new_Nil(void) {
+                    StrConsList self;
+
+                    self.pointer = NULL;
+
+                    return self;
+                }
+
+                static inline StrConsList StrConsList_new_Cons(StrConsList_Cons payload) {
+                    StrConsList self;
+
+                    self.pointer = (StrConsList_Cons *)roc_alloc_refcounted(sizeof(StrConsList_Cons), ROC_ALIGNOF(StrConsList_Cons));
+                    *self.pointer = payload;
+
+                    return self;
+                }
+
+                static inline StrConsList_Cons StrConsList_get_Cons(const StrConsList *self) {
+                    return *self->pointer;
+                }
+
+                static inline void StrConsList_incref(const StrConsList *self) {
+                    roc_incref(self->pointer);
+                }
+
+                static inline void StrConsList_decref(const StrConsList *self) {
+                    if (roc_decref_is_last(self->pointer)) {
+                        StrConsList_Cons_decref(self->pointer);
+
+                        roc_dealloc_refcounted(self->pointer, ROC_ALIGNOF(StrConsList_Cons));
+                    }
+                }
+
+                #ifdef __cplusplus
+                extern "C" {
+                #endif
+
+                void roc__main_1_exposed_generic(StrConsList *ret);
+
+                #ifdef __cplusplus
+                }
+                #endif
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn nested_lists() {
+        let module = indoc!(
+            r#"
+            Rcd : { names : List Str, tags : List (List Str) }
+
+            main : Rcd
+            main = { names: [], tags: [] }
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                typedef struct Rcd Rcd;
+
+                struct Rcd {
+                    RocList names;
+                    RocList tags;
+                };
+
+                static inline void Rcd_incref(const Rcd *self);
+                static inline void Rcd_decref(const Rcd *self);
+
+                static inline void Rcd_incref(const Rcd *self) {
+                    roc_list_incref(&self->names);
+                    roc_list_incref(&self->tags);
+                }
+
+                static inline void Rcd_decref(const Rcd *self) {
+                    if (roc_decref_is_last(self->names.elements)) {
+                        for (size_t i0 = 0; i0 < self->names.len; i0++) {
+                            roc_str_decref(&((RocStr *)self->names.elements)[i0]);
+                        }
+
+                        roc_dealloc_refcounted(self->names.elements, ROC_ALIGNOF(RocStr));
+                    }
+                    if (roc_decref_is_last(self->tags.elements)) {
+                        for (size_t i0 = 0; i0 < self->tags.len; i0++) {
+                            if (roc_decref_is_last(((RocList *)self->tags.elements)[i0].elements)) {
+                                for (size_t i1 = 0; i1 < ((RocList *)self->tags.elements)[i0].len; i1++) {
+                                    roc_str_decref(&((RocStr *)((RocList *)self->tags.elements)[i0].elements)[i1]);
+                                }
+
+                                roc_dealloc_refcounted(((RocList *)self->tags.elements)[i0].elements, ROC_ALIGNOF(RocStr));
+                            }
+                        }
+
+                        roc_dealloc_refcounted(self->tags.elements, ROC_ALIGNOF(RocList));
+                    }
+                }
+
+                #ifdef __cplusplus
+                extern "C" {
+                #endif
+
+                void roc__main_1_exposed_generic(Rcd *ret);
+
+                #ifdef __cplusplus
+                }
+                #endif
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn function_entry_point() {
+        let module = indoc!(
+            r#"
+            main : Str -> Result Str I32
+            main = \s -> Ok s
+        "#
+        );
+
+        assert_eq!(
+            generate_c_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                typedef union RocResult_RocStr_int32_t RocResult_RocStr_int32_t;
+
+                #if defined(__arm__) || defined(__wasm32__) || defined(__i386__)
+                union RocResult_RocStr_int32_t {
+                    RocStr ok;
+                    int32_t err;
+                    uint8_t _sizer[16];
+                };
+                #endif
+
+                #if defined(__aarch64__) || defined(__x86_64__)
+                union RocResult_RocStr_int32_t {
+                    RocStr ok;
+                    int32_t err;
+                    uint8_t _sizer[32];
+                };
+                #endif
+
+                static inline void RocResult_RocStr_int32_t_incref(const RocResult_RocStr_int32_t *self);
+                static inline void RocResult_RocStr_int32_t_decref(const RocResult_RocStr_int32_t *self);
+
+                #if defined(__arm__) || defined(__wasm32__) || defined(__i386__)
+                static inline bool RocResult_RocStr_int32_t_is_ok(const RocResult_RocStr_int32_t *self) {
+                    return ((const uint8_t *)self)[12] == 1;
+                }
+                #endif
+
+                #if defined(__aarch64__) || defined(__x86_64__)
+                static inline bool RocResult_RocStr_int32_t_is_ok(const RocResult_RocStr_int32_t *self) {
+                    return ((const uint8_t *)self)[24] == 1;
+                }
+                #endif
+
+                #if defined(__arm__) || defined(__wasm32__) || defined(__i386__)
+                static inline RocResult_RocStr_int32_t RocResult_RocStr_int32_t_ok(RocStr payload) {
+                    RocResult_RocStr_int32_t self;
+
+                    self.ok = payload;
+                    ((uint8_t *)&self)[12] = 1;
+
+                    return self;
+                }
+                #endif
+
+                #if defined(__aarch64__) || defined(__x86_64__)
+                static inline RocResult_RocStr_int32_t RocResult_RocStr_int32_t_ok(RocStr payload) {
+                    RocResult_RocStr_int32_t self;
+
+                    self.ok = payload;
+                    ((uint8_t *)&self)[24] = 1;
+
+                    return self;
+                }
+                #endif
+
+                #if defined(__arm__) || defined(__wasm32__) || defined(__i386__)
+                static inline RocResult_RocStr_int32_t RocResult_RocStr_int32_t_err(int32_t payload) {
+                    RocResult_RocStr_int32_t self;
+
+                    self.err = payload;
+                    ((uint8_t *)&self)[12] = 0;
+
+                    return self;
+                }
+                #endif
+
+                #if defined(__aarch64__) || defined(__x86_64__)
+                static inline RocResult_RocStr_int32_t RocResult_RocStr_int32_t_err(int32_t payload) {
+                    RocResult_RocStr_int32_t self;
+
+                    self.err = payload;
+                    ((uint8_t *)&self)[24] = 0;
+
+                    return self;
+                }
+                #endif
+
+                static inline void RocResult_RocStr_int32_t_incref(const RocResult_RocStr_int32_t *self) {
+                    if (RocResult_RocStr_int32_t_is_ok(self)) {
+                        roc_str_incref(&self->ok);
+                    }
+                }
+
+                static inline void RocResult_RocStr_int32_t_decref(const RocResult_RocStr_int32_t *self) {
+                    if (RocResult_RocStr_int32_t_is_ok(self)) {
+                        roc_str_decref(&self->ok);
+                    }
+                }
+
+                #ifdef __cplusplus
+                extern "C" {
+                #endif
+
+                void roc__main_1_exposed_generic(RocResult_RocStr_int32_t *ret, RocStr *arg0);
+
+                #ifdef __cplusplus
+                }
+                #endif
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn f128() {
+        // Roc code can not produce an F128 yet, but glue already supports it.
+        let target_info = TargetInfo::default_x86_64();
+        let mut types = Types::with_capacity(1, target_info);
+        let id = types.add_anonymous(
+            RocType::Num(RocNum::F128),
+            Layout::Builtin(Builtin::Float(roc_builtins::bitcode::FloatWidth::F128)),
+        );
+        types.add_entry_point("main".to_string(), id);
+
+        assert_eq!(
+            c_glue::emit(&[(types, target_info)]).unwrap(),
+            indoc!(
+                r#"
+
+                #ifdef __cplusplus
+                extern "C" {
+                #endif
+
+                void roc__main_1_exposed_generic(RocF128 *ret);
+
+                #ifdef __cplusplus
+                }
+                #endif
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn dict_unsupported() {
+        let module = indoc!(
+            r#"
+            main : { scores : Dict Str U64 }
+            main = { scores: Dict.empty }
+        "#
+        );
+
+        assert_eq!(
+            unsupported_in_c(module).to_string(),
+            "C glue can not be generated for Dict yet, because the header has no declarations for Dict."
+        );
+    }
+
+    #[test]
+    fn set_unsupported() {
+        let module = indoc!(
+            r#"
+            main : { tags : Set Str }
+            main = { tags: Set.empty }
+        "#
+        );
+
+        assert_eq!(
+            unsupported_in_c(module).to_string(),
+            "C glue can not be generated for Set yet, because the header has no declarations for Set."
+        );
+    }
+
+    #[test]
+    fn recursive_union_with_many_tags_unsupported() {
+        let module = indoc!(
+            r#"
+            Expr : [A Expr, B Expr, C Expr, D Expr, E Expr, F Expr, G Expr, H Expr, I Expr, Leaf]
+
+            main : Expr
+            main = A Leaf
+        "#
+        );
+
+        assert_eq!(unsupported_in_c(module).type_name, "Expr",);
+    }
+
+    #[test]
+    fn record_with_callback_unsupported() {
+        let module = indoc!(
+            r#"
+            main : { greet : (Str -> Str) as Greet, count : U64 }
+            main =
+                greeting = "Hello, "
+
+                { greet: \name -> Str.concat greeting name, count: 42 }
+        "#
+        );
+
+        assert_eq!(
+            unsupported_in_c(module).to_string(),
+            "C glue can not be generated for Greet yet, because functions stored in values need a caller, which is not generated for C."
+        );
+    }
+}
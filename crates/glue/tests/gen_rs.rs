@@ -233,4 +233,127 @@ mod test_gen_rs {
             )
         );
     }
+
+    #[test]
+    fn record_with_dict_and_set() {
+        let module = indoc!(
+            r#"
+            MyRcd : { scores : Dict Str U64, tags : Set Str }
+
+            main : MyRcd
+            main = { scores: Dict.empty, tags: Set.empty }
+        "#
+        );
+
+        assert_eq!(
+            generate_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                #[cfg(any(
+                    target_arch = "arm",
+                    target_arch = "aarch64",
+                    target_arch = "wasm32",
+                    target_arch = "x86",
+                    target_arch = "x86_64"
+                ))]
+                #[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
+                #[repr(C)]
+                pub struct MyRcd {
+                    pub scores: roc_std::RocDict<roc_std::RocStr, u64>,
+                    pub tags: roc_std::RocSet<roc_std::RocStr>,
+                }
+            "#
+            )
+        );
+    }
+
+    #[test]
+    fn record_with_callback() {
+        let module = indoc!(
+            r#"
+            main : { greet : (Str -> Str) as Greet, count : U64 }
+            main =
+                greeting = "Hello, "
+
+                { greet: \name -> Str.concat greeting name, count: 42 }
+        "#
+        );
+
+        assert_eq!(
+            generate_bindings(module)
+                .strip_prefix('\n')
+                .unwrap_or_default(),
+            indoc!(
+                r#"
+                #[cfg(any(
+                    target_arch = "arm",
+                    target_arch = "aarch64",
+                    target_arch = "wasm32",
+                    target_arch = "x86",
+                    target_arch = "x86_64"
+                ))]
+                #[derive(Clone, Debug, Default, Eq, Ord, Hash, PartialEq, PartialOrd)]
+                #[repr(transparent)]
+                struct Greet_Captures {
+                    pub f0: roc_std::RocStr,
+                }
+
+                #[cfg(any(
+                    target_arch = "arm",
+                    target_arch = "aarch64",
+                    target_arch = "wasm32",
+                    target_arch = "x86",
+                    target_arch = "x86_64"
+                ))]
+                #[repr(C)]
+                pub struct R1 {
+                    pub count: u64,
+                    pub greet: Greet,
+                }
+
+                #[cfg(any(
+                    target_arch = "arm",
+                    target_arch = "aarch64",
+                    target_arch = "wasm32",
+                    target_arch = "x86",
+                    target_arch = "x86_64"
+                ))]
+                #[repr(transparent)]
+                pub struct Greet {
+                    captures: Greet_Captures,
+                }
+
+                impl Greet {
+                    #[cfg(any(
+                        target_arch = "arm",
+                        target_arch = "aarch64",
+                        target_arch = "wasm32",
+                        target_arch = "x86",
+                        target_arch = "x86_64"
+                    ))]
+                    /// Calls this Roc function with the given arguments.
+                    /// Roc takes ownership of the arguments, and of the values this function captured.
+                    pub fn call(self, arg0: roc_std::RocStr) -> roc_std::RocStr {
+                        extern "C" {
+                            #[link_name = "roc__main_1__Greet_caller"]
+                            fn caller(arg0: *const roc_std::RocStr, closure_data: *const Greet, output: *mut roc_std::RocStr);
+                        }
+
+                        let arg0 = core::mem::ManuallyDrop::new(arg0);
+                        let closure_data = core::mem::ManuallyDrop::new(self);
+                        let mut output = core::mem::MaybeUninit::uninit();
+
+                        unsafe {
+                            caller(&*arg0, &*closure_data, output.as_mut_ptr());
+
+                            output.assume_init()
+                        }
+                    }
+                }
+            "#
+            )
+        );
+    }
 }
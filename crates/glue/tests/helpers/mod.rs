@@ -1,6 +1,8 @@
 use roc_glue::load::load_types;
-use roc_glue::rust_glue;
+use roc_glue::types::Types;
+use roc_glue::{c_glue, rust_glue};
 use roc_load::Threading;
+use roc_target::TargetInfo;
 use std::env;
 use std::fs::File;
 use std::io::Write;
@@ -8,6 +10,21 @@ use std::path::PathBuf;
 
 #[allow(dead_code)]
 pub fn generate_bindings(decl_src: &str) -> String {
+    rust_glue::emit(&load_platform_types(decl_src))
+}
+
+#[allow(dead_code)]
+pub fn generate_c_bindings(decl_src: &str) -> String {
+    c_glue::emit(&load_platform_types(decl_src))
+        .unwrap_or_else(|unsupported| panic!("{}", unsupported))
+}
+
+#[allow(dead_code)]
+pub fn unsupported_in_c(decl_src: &str) -> c_glue::Unsupported {
+    c_glue::emit(&load_platform_types(decl_src)).expect_err("C glue was generated")
+}
+
+fn load_platform_types(decl_src: &str) -> Vec<(Types, TargetInfo)> {
     use tempfile::tempdir;
 
     let mut src = indoc!(
@@ -25,7 +42,7 @@ pub fn generate_bindings(decl_src: &str) -> String {
 
     src.push_str(decl_src);
 
-    let pairs = {
+    {
         let dir = tempdir().expect("Unable to create tempdir");
         let filename = PathBuf::from("platform.roc");
         let file_path = dir.path().join(filename);
@@ -38,20 +55,18 @@ pub fn generate_bindings(decl_src: &str) -> String {
         dir.close().expect("Unable to close tempdir");
 
         result.expect("had problems loading")
-    };
-
-    rust_glue::emit(&pairs)
+    }
 }
 
 #[allow(dead_code)]
-pub fn fixtures_dir(dir_name: &str) -> PathBuf {
+pub fn fixtures_dir(fixtures: &str, dir_name: &str) -> PathBuf {
     let mut path = root_dir();
 
-    // Descend into cli/tests/fixtures/{dir_name}
+    // Descend into glue/tests/{fixtures}/{dir_name}
     path.push("crates");
     path.push("glue");
     path.push("tests");
-    path.push("fixtures");
+    path.push(fixtures);
     path.push(dir_name);
 
     path
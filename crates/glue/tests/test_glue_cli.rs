@@ -30,15 +30,21 @@ mod glue_cli_run {
     /// has had a corresponding test generated in the previous step. This test
     /// will fail if we ever add a new .roc file to fixtures/ and forget to
     /// add a test for it here!
+    ///
+    /// Each language has its own fixtures directory, and a fixture-templates/ directory
+    /// with the files every fixture in it needs, as well as its own name for the glue file.
     macro_rules! fixtures {
-        ($($test_name:ident:$fixture_dir:expr => $ends_with:expr,)+) => {
+        (
+            $all_fixtures_have_tests:ident, $fixtures:expr, $template:expr, $glue_file:expr;
+            $($test_name:ident:$fixture_dir:expr => $ends_with:expr,)+
+        ) => {
             $(
                 #[test]
                 #[allow(non_snake_case)]
                 fn $test_name() {
-                    let dir = fixtures_dir($fixture_dir);
+                    let dir = fixtures_dir($fixtures, $fixture_dir);
 
-                    generate_glue_for(&dir, std::iter::empty());
+                    generate_glue_for(&dir, $template, $glue_file, std::iter::empty());
                     let out = run_app(&dir.join("app.roc"), std::iter::empty());
 
                     assert!(out.status.success());
@@ -53,7 +59,7 @@ mod glue_cli_run {
             )*
 
             #[test]
-            fn all_fixtures_have_tests() {
+            fn $all_fixtures_have_tests() {
                 use roc_collections::VecSet;
 
                 let mut all_fixtures: VecSet<String> = VecSet::default();
@@ -62,12 +68,14 @@ mod glue_cli_run {
                     all_fixtures.insert($fixture_dir.to_string());
                 )*
 
-                check_for_tests(&mut all_fixtures);
+                check_for_tests($fixtures, &mut all_fixtures);
             }
         }
     }
 
     fixtures! {
+        all_fixtures_have_tests, "fixtures", "rust", "src/test_glue.rs";
+
         basic_record:"basic-record" => "Record was: MyRcd { b: 42, a: 1995 }\n",
         nested_record:"nested-record" => "Record was: Outer { y: \"foo\", z: [1, 2], x: Inner { b: 24.0, a: 5 } }\n",
         enumeration:"enumeration" => "tag_union was: MyEnum::Foo, Bar is: MyEnum::Bar, Baz is: MyEnum::Baz\n",
@@ -116,12 +124,40 @@ mod glue_cli_run {
         list_recursive_union:"list-recursive-union" => indoc!(r#"
             rbt was: Rbt { default: Job::Job(R1 { command: Command::Command(R2 { args: [], tool: Tool::SystemTool("test") }), inputFiles: ["foo"], job: [] }) }
         "#),
+        callback:"callback" => indoc!(r#"
+            count was 2
+            greet returned "Hello to everyone who is here today, and welcome: Alice, Bob, World!"
+        "#),
+        dict:"dict" => indoc!(r#"
+            Roc found Shanghai: 24870895, Amsterdam: 872680, Rome: unknown
+            Host found Rome: Some(2873000), Delhi: Some(16800000), London: None
+            Cities: ["Amsterdam", "Philadelphia", "Shanghai", "Delhi", "Rome"]
+        "#),
+    }
+
+    fixtures! {
+        all_c_fixtures_have_tests, "c-fixtures", "c", "test_glue.h";
+
+        c_basic_record:"basic-record" => "Record was: { a: 1995, b: 42 }\n",
+        c_union_with_padding:"union-with-padding" => indoc!(r#"
+            tag_union was: Foo "This is a test"
+            `Foo "small str"` is: Foo "small str"
+            `Foo "A long enough string to not be small"` is: Foo "A long enough string to not be small"
+            `Bar 123` is: Bar 123
+            `Baz` is: Baz
+            `Blah 456` is: Blah 456
+        "#),
+        c_nullable_unwrapped:"nullable-unwrapped" => indoc!(r#"
+            tag_union was: Cons "World!" (Cons "Hello " Nil)
+            `Cons "small str" Nil` is: Cons "small str" Nil
+            `Nil` is: Nil
+        "#),
     }
 
-    fn check_for_tests(all_fixtures: &mut roc_collections::VecSet<String>) {
+    fn check_for_tests(fixtures_name: &str, all_fixtures: &mut roc_collections::VecSet<String>) {
         use roc_collections::VecSet;
 
-        let fixtures = fixtures_dir("");
+        let fixtures = fixtures_dir(fixtures_name, "");
         let entries = std::fs::read_dir(fixtures.as_path()).unwrap_or_else(|err| {
             panic!(
                 "Error trying to read {} as a fixtures directory: {}",
@@ -150,10 +186,12 @@ mod glue_cli_run {
 
     fn generate_glue_for<'a, I: IntoIterator<Item = &'a str>>(
         platform_dir: &'a Path,
+        template: &str,
+        glue_file: &str,
         args: I,
     ) -> Out {
         let platform_module_path = platform_dir.join("platform.roc");
-        let glue_file = platform_dir.join("src").join("test_glue.rs");
+        let glue_file = platform_dir.join(glue_file);
         let fixture_templates_dir = platform_dir
             .parent()
             .unwrap()
@@ -161,8 +199,8 @@ mod glue_cli_run {
             .unwrap()
             .join("fixture-templates");
 
-        // Copy the rust template from the templates directory into the fixture dir.
-        dircpy::CopyBuilder::new(fixture_templates_dir.join("rust"), platform_dir)
+        // Copy the language's template from the templates directory into the fixture dir.
+        dircpy::CopyBuilder::new(fixture_templates_dir.join(template), platform_dir)
             .overwrite(true) // overwrite any files that were already present
             .run()
             .unwrap();
@@ -170,10 +208,10 @@ mod glue_cli_run {
         // Delete the glue file to make sure we're actually regenerating it!
         if glue_file.exists() {
             fs::remove_file(&glue_file)
-                .expect("Unable to remove test_glue.rs in order to regenerate it in the test");
+                .expect("Unable to remove the glue file in order to regenerate it in the test");
         }
 
-        // Generate a fresh test_glue.rs for this platform
+        // Generate a fresh glue file for this platform
         let glue_out = run_glue(
             // converting these all to String avoids lifetime issues
             std::iter::once("glue".to_string()).chain(
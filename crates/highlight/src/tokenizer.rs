@@ -183,7 +183,7 @@ fn consume_all_tokens(state: &mut LexState, bytes: &[u8], consumer: &mut impl Co
                 continue;
             }
             b'"' => lex_string(bytes),
-            b => todo!("handle {:?}", b as char),
+            _ => lex_malformed(bytes),
         };
 
         consumer.token(token, i, len);
@@ -393,10 +393,7 @@ fn lex_operator(bytes: &[u8]) -> (Token, usize) {
         b"//" => Token::OpDoubleSlash,
         b"->" => Token::Arrow,
         b"<-" => Token::OpBackpassing,
-        op => {
-            dbg!(std::str::from_utf8(op).unwrap());
-            Token::MalformedOperator
-        }
+        _ => Token::MalformedOperator,
     };
     (tok, i)
 }
@@ -482,12 +479,27 @@ fn lex_string(bytes: &[u8]) -> (Token, usize) {
         }
     }
 
-    assert_eq!(bytes[i], b'"');
-    i += 1;
+    // A string that is still being typed runs until the end of the input.
+    if i < bytes.len() {
+        i += 1;
+    }
 
     (Token::String, i)
 }
 
+/// Skips a character that no token can start with, e.g. a tab or a non-ASCII letter,
+/// so that code that is being edited can still be tokenized.
+fn lex_malformed(bytes: &[u8]) -> (Token, usize) {
+    let len = match bytes[0] {
+        0xF0..=0xFF => 4,
+        0xE0..=0xEF => 3,
+        0xC0..=0xDF => 2,
+        _ => 1,
+    };
+
+    (Token::Malformed, len.min(bytes.len()))
+}
+
 #[cfg(test)]
 mod test_tokenizer {
     use super::Token;
@@ -647,4 +659,20 @@ b = 6"#,
             ]
         );
     }
+
+    #[test]
+    fn test_tokenization_unfinished_code() {
+        let tokens = tokenize("x =\tπ \"abc");
+
+        assert_eq!(
+            tokens,
+            [
+                T::LowercaseIdent,
+                T::OpAssignment,
+                T::Malformed,
+                T::Malformed,
+                T::String
+            ]
+        );
+    }
 }
@@ -0,0 +1,171 @@
+use object::elf;
+use roc_error_macros::internal_error;
+
+// The first entry of the plt resolves symbols lazily, the entries for functions come after it.
+pub const PLT_HEADER_SIZE: u64 = 0x20;
+
+const NOP: u32 = 0xd503_201f;
+const B: u32 = 0x1400_0000;
+
+/// The target of a `b` or `bl` instruction at `address`, if `inst` is one.
+pub fn branch_target(inst: u32, address: u64) -> Option<u64> {
+    // `bl` only differs from `b` in the top bit.
+    if inst & 0x7c00_0000 != B {
+        return None;
+    }
+
+    // Sign extend the 26 bit immediate, which counts instructions.
+    let imm26 = ((inst << 6) as i32 >> 6) as i64;
+
+    Some((address as i64 + imm26 * 4) as u64)
+}
+
+/// Set the offset of a `b` or `bl` instruction.
+pub fn set_branch_offset(inst: u32, offset: i64) -> u32 {
+    if offset % 4 != 0 || !(-(1 << 27)..(1 << 27)).contains(&offset) {
+        internal_error!("Branch offset out of range for aarch64: {:+x}", offset);
+    }
+
+    (inst & 0xfc00_0000) | ((offset >> 2) as u32 & 0x03ff_ffff)
+}
+
+/// Code that jumps from a plt entry straight to `offset`, filling the whole entry.
+pub fn plt_jump(offset: i64) -> [u8; 16] {
+    let mut code = [0; 16];
+    code[..4].copy_from_slice(&set_branch_offset(B, offset).to_le_bytes());
+    for nop in code[4..].chunks_exact_mut(4) {
+        nop.copy_from_slice(&NOP.to_le_bytes());
+    }
+
+    code
+}
+
+fn set_adrp_pages(inst: u32, pages: i64) -> u32 {
+    if !(-(1 << 20)..(1 << 20)).contains(&pages) {
+        internal_error!("Page offset out of range for aarch64 adrp: {:+x}", pages);
+    }
+
+    let immlo = pages as u32 & 0b11;
+    let immhi = (pages >> 2) as u32 & 0x7_ffff;
+
+    (inst & 0x9f00_001f) | (immlo << 29) | (immhi << 5)
+}
+
+fn set_imm12(inst: u32, imm12: u32) -> u32 {
+    (inst & !(0xfff << 10)) | ((imm12 & 0xfff) << 10)
+}
+
+fn page(address: i64) -> i64 {
+    address & !0xfff
+}
+
+/// Apply a relocation of type `r_type` to the instruction `inst` at `address`,
+/// where `target` is the address of the symbol plus the addend.
+///
+/// The app never gets a global offset table, so loads from it are relaxed to compute the address directly.
+pub fn relocate(inst: u32, r_type: u32, address: u64, target: i64) -> u32 {
+    let address = address as i64;
+
+    match r_type {
+        elf::R_AARCH64_CALL26 | elf::R_AARCH64_JUMP26 => set_branch_offset(inst, target - address),
+        elf::R_AARCH64_ADR_PREL_PG_HI21 | elf::R_AARCH64_ADR_GOT_PAGE => {
+            set_adrp_pages(inst, (page(target) - page(address)) >> 12)
+        }
+        elf::R_AARCH64_ADD_ABS_LO12_NC => set_imm12(inst, target as u32),
+        elf::R_AARCH64_LDST8_ABS_LO12_NC => set_imm12(inst, target as u32),
+        elf::R_AARCH64_LDST16_ABS_LO12_NC => set_imm12(inst, (target as u32 & 0xfff) >> 1),
+        elf::R_AARCH64_LDST32_ABS_LO12_NC => set_imm12(inst, (target as u32 & 0xfff) >> 2),
+        elf::R_AARCH64_LDST64_ABS_LO12_NC => set_imm12(inst, (target as u32 & 0xfff) >> 3),
+        elf::R_AARCH64_LDST128_ABS_LO12_NC => set_imm12(inst, (target as u32 & 0xfff) >> 4),
+        elf::R_AARCH64_LD64_GOT_LO12_NC => {
+            // Turn `ldr xt, [xn, :got_lo12:sym]` into `add xt, xn, :lo12:sym`.
+            const ADD_IMM_64: u32 = 0x9100_0000;
+            if inst & 0xffc0_0000 != 0xf940_0000 {
+                internal_error!(
+                    "Expected a 64 bit ldr for a got relocation, found {:#x}",
+                    inst
+                );
+            }
+
+            set_imm12(ADD_IMM_64 | (inst & 0x3ff), target as u32)
+        }
+        x => {
+            internal_error!("Relocation type not yet supported for aarch64: {}", x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The expected encodings follow the A64 instruction set reference.
+
+    #[test]
+    fn branch_target_of_bl() {
+        // bl #-0x10
+        assert_eq!(branch_target(0x97ff_fffc, 0x1010), Some(0x1000));
+        // b #0x40
+        assert_eq!(branch_target(0x1400_0010, 0x1000), Some(0x1040));
+        // add x0, x0, #0x1
+        assert_eq!(branch_target(0x9100_0400, 0x1000), None);
+    }
+
+    #[test]
+    fn branch_offset_round_trips() {
+        let bl = set_branch_offset(0x9400_0000, -0x2000);
+        assert_eq!(bl >> 26, 0x9400_0000 >> 26);
+        assert_eq!(branch_target(bl, 0x10_0000), Some(0x10_0000 - 0x2000));
+    }
+
+    #[test]
+    fn plt_jump_is_branch_then_nops() {
+        let code = plt_jump(0x40);
+        assert_eq!(code[..4], 0x1400_0010u32.to_le_bytes());
+        assert_eq!(code[12..], NOP.to_le_bytes());
+    }
+
+    #[test]
+    fn adrp_and_add() {
+        // adrp x0, 0x0
+        let adrp = relocate(
+            0x9000_0000,
+            elf::R_AARCH64_ADR_PREL_PG_HI21,
+            0x1004,
+            0x23_4567,
+        );
+        // adrp x0, #0x233000
+        assert_eq!(adrp, 0xf000_1180);
+
+        // add x0, x0, #0x0
+        let add = relocate(
+            0x9100_0000,
+            elf::R_AARCH64_ADD_ABS_LO12_NC,
+            0x1008,
+            0x23_4567,
+        );
+        // add x0, x0, #0x567
+        assert_eq!(add, 0x9115_9c00);
+    }
+
+    #[test]
+    fn got_load_is_relaxed_to_add() {
+        // ldr x1, [x2]
+        let add = relocate(0xf940_0041, elf::R_AARCH64_LD64_GOT_LO12_NC, 0x1008, 0x2010);
+        // add x1, x2, #0x10
+        assert_eq!(add, 0x9100_4041);
+    }
+
+    #[test]
+    fn ldst64_is_scaled() {
+        // ldr x0, [x0]
+        let ldr = relocate(
+            0xf940_0000,
+            elf::R_AARCH64_LDST64_ABS_LO12_NC,
+            0x1008,
+            0x2018,
+        );
+        // ldr x0, [x0, #0x18]
+        assert_eq!(ldr, 0xf940_0c00);
+    }
+}
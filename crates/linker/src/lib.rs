@@ -5,11 +5,12 @@ use object::write;
 use object::{elf, endian, macho};
 use object::{
     Architecture, BinaryFormat, CompressedFileRange, CompressionFormat, Endianness, LittleEndian,
-    NativeEndian, Object, ObjectSection, ObjectSymbol, RelocationKind, RelocationTarget, Section,
-    SectionIndex, SectionKind, Symbol, SymbolFlags, SymbolIndex, SymbolKind, SymbolScope,
-    SymbolSection,
+    NativeEndian, Object, ObjectSection, ObjectSymbol, RelocationEncoding, RelocationKind,
+    RelocationTarget, Section, SectionIndex, SectionKind, Symbol, SymbolFlags, SymbolIndex,
+    SymbolKind, SymbolScope, SymbolSection,
 };
 use roc_build::link::{rebuild_host, LinkType};
+use roc_builtins::bitcode;
 use roc_collections::all::MutMap;
 use roc_error_macros::{internal_error, user_error};
 use roc_mono::ir::OptLevel;
@@ -20,12 +21,13 @@ use std::fs::{self, File};
 use std::io::{BufReader, BufWriter};
 use std::mem;
 use std::os::raw::c_char;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::{Duration, Instant};
 use target_lexicon::Triple;
 use tempfile::Builder;
 
+mod aarch64;
 mod metadata;
 use metadata::VirtualOffset;
 
@@ -35,6 +37,54 @@ const MIN_SECTION_ALIGNMENT: usize = 0x40;
 const PLT_ADDRESS_OFFSET: u64 = 0x10;
 const STUB_ADDRESS_OFFSET: u64 = 0x06;
 
+/// What differs between the instruction sets surgical linking supports for ELF.
+struct ElfMachine {
+    e_machine: u16,
+    plt_header_size: u64,
+    r_relative: u32,
+    r_irelative: u32,
+    r_glob_dat: u32,
+    r_jump_slot: u32,
+    r_abs64: u32,
+}
+
+impl ElfMachine {
+    fn from_header(data: &[u8]) -> Self {
+        let header = load_struct_inplace::<elf::FileHeader64<LittleEndian>>(data, 0);
+
+        match header.e_machine.get(NativeEndian) {
+            elf::EM_X86_64 => ElfMachine {
+                e_machine: elf::EM_X86_64,
+                plt_header_size: PLT_ADDRESS_OFFSET,
+                r_relative: elf::R_X86_64_RELATIVE,
+                r_irelative: elf::R_X86_64_IRELATIVE,
+                r_glob_dat: elf::R_X86_64_GLOB_DAT,
+                r_jump_slot: elf::R_X86_64_JUMP_SLOT,
+                r_abs64: elf::R_X86_64_64,
+            },
+            elf::EM_AARCH64 => ElfMachine {
+                e_machine: elf::EM_AARCH64,
+                plt_header_size: aarch64::PLT_HEADER_SIZE,
+                r_relative: elf::R_AARCH64_RELATIVE,
+                r_irelative: elf::R_AARCH64_IRELATIVE,
+                r_glob_dat: elf::R_AARCH64_GLOB_DAT,
+                r_jump_slot: elf::R_AARCH64_JUMP_SLOT,
+                r_abs64: elf::R_AARCH64_ABS64,
+            },
+            e_machine => {
+                internal_error!(
+                    "Surgical linking does not support ELF machine type {}",
+                    e_machine
+                );
+            }
+        }
+    }
+
+    fn is_aarch64(&self) -> bool {
+        self.e_machine == elf::EM_AARCH64
+    }
+}
+
 struct ElfDynamicDeps {
     got_app_syms: Vec<(String, usize)>,
     got_sections: Vec<(usize, usize)>,
@@ -57,9 +107,10 @@ pub fn supported(link_type: LinkType, target: &Triple) -> bool {
     matches!(
         (link_type, target),
         (
-            LinkType::Executable,
+            LinkType::Executable | LinkType::Dylib,
             Triple {
-                architecture: target_lexicon::Architecture::X86_64,
+                architecture: target_lexicon::Architecture::X86_64
+                    | target_lexicon::Architecture::Aarch64(_),
                 operating_system: target_lexicon::OperatingSystem::Linux,
                 binary_format: target_lexicon::BinaryFormat::Elf,
                 ..
@@ -72,6 +123,16 @@ pub fn supported(link_type: LinkType, target: &Triple) -> bool {
     )
 }
 
+/// The metadata of executable and shared library hosts is kept apart,
+/// so that building with `--lib` does not clobber the preprocessed executable host.
+fn metadata_path(host_input_path: &Path, link_type: LinkType) -> PathBuf {
+    match link_type {
+        LinkType::Executable => host_input_path.with_file_name("metadata"),
+        LinkType::Dylib => host_input_path.with_file_name("metadata_lib"),
+        LinkType::None => internal_error!("Surgical linking requires something to link"),
+    }
+}
+
 pub fn build_and_preprocess_host(
     opt_level: OptLevel,
     target: &Triple,
@@ -79,12 +140,21 @@ pub fn build_and_preprocess_host(
     preprocessed_host_path: &Path,
     exposed_to_host: Vec<String>,
     exported_closure_types: Vec<String>,
+    link_type: LinkType,
 ) {
     let dummy_lib = host_input_path.with_file_name("libapp.so");
     generate_dynamic_lib(target, exposed_to_host, exported_closure_types, &dummy_lib);
-    rebuild_host(opt_level, target, host_input_path, Some(&dummy_lib));
-    let dynhost = host_input_path.with_file_name("dynhost");
-    let metadata = host_input_path.with_file_name("metadata");
+    let dynhost = match link_type {
+        LinkType::Executable => rebuild_host(opt_level, target, host_input_path, Some(&dummy_lib)),
+        LinkType::Dylib => {
+            let host_obj = rebuild_host(opt_level, target, host_input_path, None);
+            let dynhost = host_input_path.with_file_name("dynhost.so");
+            link_shared_host(target, &host_obj, &dummy_lib, &dynhost);
+            dynhost
+        }
+        LinkType::None => internal_error!("Surgical linking requires something to link"),
+    };
+    let metadata = metadata_path(host_input_path, link_type);
     // let prehost = host_input_path.with_file_name("preprocessedhost");
 
     preprocess(
@@ -103,8 +173,9 @@ pub fn link_preprocessed_host(
     host_input_path: &Path,
     roc_app_obj: &Path,
     binary_path: &Path,
+    link_type: LinkType,
 ) {
-    let metadata = host_input_path.with_file_name("metadata");
+    let metadata = metadata_path(host_input_path, link_type);
     surgery(
         roc_app_obj.to_str().unwrap(),
         metadata.to_str().unwrap(),
@@ -138,6 +209,7 @@ fn generate_dynamic_lib(
     };
     let obj_arch = match target.architecture {
         target_lexicon::Architecture::X86_64 => Architecture::X86_64,
+        target_lexicon::Architecture::Aarch64(_) => Architecture::Aarch64,
         _ => {
             // We should have verified this via supported() before calling this function
             unreachable!()
@@ -233,6 +305,56 @@ fn generate_dynamic_lib(
     }
 }
 
+/// Link the host object into a shared library that depends on the dummy app library,
+/// which is the shared library equivalent of the `dynhost` executable.
+///
+/// This runs the system `ld`, so the host can only be linked for the machine we're running on.
+pub fn link_shared_host(
+    target: &Triple,
+    host_obj: &Path,
+    dummy_lib_path: &Path,
+    dynhost_path: &Path,
+) {
+    let native = Triple::host();
+
+    if (target.architecture, target.operating_system)
+        != (native.architecture, native.operating_system)
+    {
+        user_error!(
+            "Surgically linking a shared library host for {} only works when compiling on that target, but this machine is {}.\nTry again with --linker=legacy",
+            target,
+            native
+        );
+    }
+
+    // libc is left undefined, it will come from whatever process loads the library.
+    let output = Command::new("ld")
+        .args(&[
+            "-shared",
+            "--eh-frame-hdr",
+            host_obj.to_str().unwrap(),
+            &bitcode::get_builtins_host_obj_path(),
+            dummy_lib_path.to_str().unwrap(),
+            "-o",
+            dynhost_path.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+
+    if !output.status.success() {
+        match std::str::from_utf8(&output.stderr) {
+            Ok(stderr) => panic!(
+                "Failed to link shared library host - stderr of the `ld` command was:\n{}",
+                stderr
+            ),
+            Err(utf8_err) => panic!(
+                "Failed to link shared library host - stderr of the `ld` command was invalid utf8 ({:?})",
+                utf8_err
+            ),
+        }
+    }
+}
+
 // TODO: Most of this file is a mess of giant functions just to check if things work.
 // Clean it all up and refactor nicely.
 pub fn preprocess(
@@ -359,6 +481,7 @@ pub fn preprocess(
 
     match target.binary_format {
         target_lexicon::BinaryFormat::Elf => {
+            let machine = ElfMachine::from_header(exec_data);
             let plt_relocs = (match exec_obj.dynamic_relocations() {
                 Some(relocs) => relocs,
                 None => {
@@ -366,7 +489,7 @@ pub fn preprocess(
                 }
             })
             .filter_map(|(_, reloc)| {
-                if let RelocationKind::Elf(7) = reloc.kind() {
+                if reloc.kind() == RelocationKind::Elf(machine.r_jump_slot) {
                     Some(reloc)
                 } else {
                     None
@@ -375,8 +498,10 @@ pub fn preprocess(
             for (i, reloc) in plt_relocs.enumerate() {
                 for symbol in app_syms.iter() {
                     if reloc.target() == RelocationTarget::Symbol(symbol.index()) {
-                        let func_address = (i as u64 + 1) * PLT_ADDRESS_OFFSET + plt_address;
-                        let func_offset = (i as u64 + 1) * PLT_ADDRESS_OFFSET + plt_offset;
+                        let plt_entry_offset =
+                            machine.plt_header_size + i as u64 * PLT_ADDRESS_OFFSET;
+                        let func_address = plt_entry_offset + plt_address;
+                        let func_offset = plt_entry_offset + plt_offset;
                         app_func_addresses.insert(func_address, symbol.name().unwrap());
                         md.plt_addresses.insert(
                             symbol.name().unwrap().to_string(),
@@ -590,6 +715,44 @@ pub fn preprocess(
                 internal_error!("Failed to load text section, {:+x?}: {}", sec, err);
             }
         };
+        if let target_lexicon::Architecture::Aarch64(_) = target.architecture {
+            // Every instruction is 4 bytes, and calls only go through `b` and `bl`.
+            for (i, word) in data.chunks_exact(4).enumerate() {
+                let address = sec.address() + 4 * i as u64;
+                let inst = u32::from_le_bytes(<[u8; 4]>::try_from(word).unwrap());
+                let func_name = match aarch64::branch_target(inst, address)
+                    .and_then(|target| app_func_addresses.get(&target))
+                {
+                    Some(func_name) => func_name,
+                    None => continue,
+                };
+                if compressed {
+                    internal_error!(
+                        "Surgical linking does not work with compressed text sections: {:+x?}",
+                        sec
+                    );
+                }
+
+                let offset = address - sec.address() + file_offset;
+                if verbose {
+                    println!(
+                        "Found branch from {:+x} to {}, need to surgically replace it at file offset {:+x}",
+                        address, func_name, offset
+                    );
+                }
+                // The offset of aarch64 branches is relative to the branch itself.
+                md.surgeries
+                    .get_mut(*func_name)
+                    .unwrap()
+                    .push(metadata::SurgeryEntry {
+                        file_offset: offset,
+                        virtual_offset: VirtualOffset::Relative(address),
+                        size: 4,
+                    });
+            }
+            continue;
+        }
+
         let mut decoder = Decoder::with_ip(64, &data, sec.address(), DecoderOptions::NONE);
         let mut inst = Instruction::default();
 
@@ -1419,7 +1582,9 @@ fn gen_elf_le(
     shared_lib_index: usize,
     verbose: bool,
 ) -> (MmapMut, File) {
+    let machine = ElfMachine::from_header(exec_data);
     let exec_header = load_struct_inplace::<elf::FileHeader64<LittleEndian>>(exec_data, 0);
+    let e_type = exec_header.e_type.get(NativeEndian);
     let ph_offset = exec_header.e_phoff.get(NativeEndian);
     let ph_ent_size = exec_header.e_phentsize.get(NativeEndian);
     let ph_num = exec_header.e_phnum.get(NativeEndian);
@@ -1427,8 +1592,15 @@ fn gen_elf_le(
     let sh_ent_size = exec_header.e_shentsize.get(NativeEndian);
     let sh_num = exec_header.e_shnum.get(NativeEndian);
 
+    // Position independent executables and shared libraries are both ET_DYN.
+    // Everything below works the same for them, since it only shifts addresses relative to the load address.
+    if e_type != elf::ET_EXEC && e_type != elf::ET_DYN {
+        user_error!("The host must be an executable or a shared library, but its ELF type is {}\nProbably input the wrong file as the host", e_type);
+    }
+
     if verbose {
         println!();
+        println!("ELF Type: {}", e_type);
         println!("PH Offset: {:+x}", ph_offset);
         println!("PH Entry Size: {}", ph_ent_size);
         println!("PH Entry Count: {}", ph_num);
@@ -1468,6 +1640,7 @@ fn gen_elf_le(
     );
     let mut first_load_found = false;
     let mut virtual_shift_start = 0;
+    let mut has_interpreter = false;
     for ph in program_headers.iter() {
         let p_type = ph.p_type.get(NativeEndian);
         if p_type == elf::PT_LOAD && ph.p_offset.get(NativeEndian) == 0 {
@@ -1475,7 +1648,12 @@ fn gen_elf_le(
             md.load_align_constraint = ph.p_align.get(NativeEndian);
             virtual_shift_start = physical_shift_start + ph.p_vaddr.get(NativeEndian);
         }
+        if p_type == elf::PT_INTERP {
+            has_interpreter = true;
+        }
     }
+    // Position independent executables are ET_DYN too, but unlike shared libraries they request an interpreter.
+    let is_shared_library = e_type == elf::ET_DYN && !has_interpreter;
     if !first_load_found {
         user_error!("Executable does not load any data at 0x00000000\nProbably input the wrong file as the executable");
     }
@@ -1596,7 +1774,8 @@ fn gen_elf_le(
                 rel.r_offset = endian::U64::new(LittleEndian, r_offset + md.added_byte_count);
                 // Deal with potential adjusts to absolute jumps.
                 // TODO: Verify other relocation types.
-                if rel.r_type(LittleEndian, false) == elf::R_X86_64_RELATIVE {
+                let r_type = rel.r_type(LittleEndian, false);
+                if r_type == machine.r_relative || r_type == machine.r_irelative {
                     let r_addend = rel.r_addend.get(LittleEndian);
                     rel.r_addend
                         .set(LittleEndian, r_addend + md.added_byte_count as i64);
@@ -1604,11 +1783,18 @@ fn gen_elf_le(
             }
             // If the relocation goes to a roc function, we need to surgically link it and change it to relative.
             let r_type = rel.r_type(NativeEndian, false);
-            if r_type == elf::R_X86_64_GLOB_DAT {
+            if r_type == machine.r_glob_dat || r_type == machine.r_abs64 {
                 let r_sym = rel.r_sym(NativeEndian, false);
                 for (name, index) in got_app_syms.iter() {
                     if *index as u32 == r_sym {
-                        rel.set_r_info(LittleEndian, false, 0, elf::R_X86_64_RELATIVE);
+                        // The addend is overwritten with the address of the function during surgery.
+                        if rel.r_addend.get(NativeEndian) != 0 {
+                            internal_error!(
+                                "Relocations with an offset from app functions are not yet supported: {}",
+                                name
+                            );
+                        }
+                        rel.set_r_info(LittleEndian, false, 0, machine.r_relative);
                         let addend_addr = sec_offset as usize
                             + i * mem::size_of::<elf::Rela64<LittleEndian>>()
                             // This 16 skips the first 2 fields and gets to the addend field.
@@ -1623,6 +1809,18 @@ fn gen_elf_le(
                             });
                     }
                 }
+            } else if r_type == machine.r_jump_slot {
+                // The plt entries of app functions are replaced with direct jumps during surgery,
+                // so their jump slots are never used. Detach them from the app symbols,
+                // otherwise binding them at load time fails, because the app functions are not in the hash tables.
+                let r_sym = rel.r_sym(NativeEndian, false);
+                if md
+                    .dynamic_symbol_indices
+                    .values()
+                    .any(|index| *index as u32 == r_sym)
+                {
+                    rel.set_r_info(LittleEndian, false, 0, machine.r_jump_slot);
+                }
             }
         }
     }
@@ -1693,6 +1891,27 @@ fn gen_elf_le(
         }
     }
 
+    // Update dynamic symbol table entries for shift for extra program headers.
+    // Shared libraries export all of their functions through these, executables are left as they are.
+    if is_shared_library {
+        let dynsym_offset = md.dynamic_symbol_table_section_offset + md.added_byte_count;
+        let dynsym_size = md.dynamic_symbol_table_size as usize;
+
+        let dynamic_symbols = load_structs_inplace_mut::<elf::Sym64<LittleEndian>>(
+            &mut out_mmap,
+            dynsym_offset as usize,
+            dynsym_size / mem::size_of::<elf::Sym64<LittleEndian>>(),
+        );
+
+        for sym in dynamic_symbols {
+            let shndx = sym.st_shndx.get(NativeEndian);
+            let addr = sym.st_value.get(NativeEndian);
+            if shndx != elf::SHN_UNDEF && shndx != elf::SHN_ABS && virtual_shift_start <= addr {
+                sym.st_value = endian::U64::new(LittleEndian, addr + md.added_byte_count);
+            }
+        }
+    }
+
     // Update all data in the global offset table.
     for (offset, size) in got_sections {
         let global_offsets = load_structs_inplace_mut::<endian::U64<LittleEndian>>(
@@ -1868,6 +2087,7 @@ fn scan_elf_dynamic_deps(
         }
     };
     md.dynamic_symbol_table_section_offset = dynsym_offset as u64;
+    md.dynamic_symbol_table_size = dynsym_sec.size();
 
     let mut got_sections: Vec<(usize, usize)> = vec![];
     for sec in exec_obj
@@ -1887,6 +2107,7 @@ fn scan_elf_dynamic_deps(
         }
     }
 
+    let machine = ElfMachine::from_header(exec_data);
     let got_app_syms: Vec<(String, usize)> = (match exec_obj.dynamic_relocations() {
         Some(relocs) => relocs,
         None => {
@@ -1895,7 +2116,14 @@ fn scan_elf_dynamic_deps(
         }
     })
     .filter_map(|(_, reloc)| {
-        if let RelocationKind::Elf(6) = reloc.kind() {
+        // Shared libraries also load the address of app functions with absolute relocations,
+        // for example to store them in a table of function pointers.
+        let loads_address = match reloc.kind() {
+            RelocationKind::Elf(r_type) => r_type == machine.r_glob_dat,
+            RelocationKind::Absolute => reloc.size() == 64,
+            _ => false,
+        };
+        if loads_address {
             for symbol in app_syms.iter() {
                 if reloc.target() == RelocationTarget::Symbol(symbol.index()) {
                     return Some((symbol.name().unwrap().to_string(), symbol.index().0));
@@ -2474,6 +2702,7 @@ pub fn surgery_elf(
     if !elf64 || !litte_endian {
         internal_error!("Only 64bit little endian elf currently supported for surgery");
     }
+    let machine = ElfMachine::from_header(exec_mmap);
     let exec_header = load_struct_inplace::<elf::FileHeader64<LittleEndian>>(exec_mmap, 0);
 
     let ph_offset = exec_header.e_phoff.get(NativeEndian);
@@ -2672,6 +2901,36 @@ pub fn surgery_elf(
                     if let Some(target_offset) = target_offset {
                         let virt_base = section_virtual_offset as usize + rel.0 as usize;
                         let base = section_offset as usize + rel.0 as usize;
+                        // Relocations in aarch64 code are spread over the bits of an instruction.
+                        let aarch64_r_type = match (rel.1.kind(), rel.1.encoding()) {
+                            (RelocationKind::PltRelative, RelocationEncoding::AArch64Call) => {
+                                Some(elf::R_AARCH64_CALL26)
+                            }
+                            (RelocationKind::Elf(r_type), _) if machine.is_aarch64() => {
+                                Some(r_type)
+                            }
+                            _ => None,
+                        };
+                        if let Some(r_type) = aarch64_r_type {
+                            let inst = u32::from_le_bytes(
+                                <[u8; 4]>::try_from(&exec_mmap[base..base + 4]).unwrap(),
+                            );
+                            let inst = aarch64::relocate(
+                                inst,
+                                r_type,
+                                virt_base as u64,
+                                target_offset + rel.1.addend(),
+                            );
+                            if verbose {
+                                println!(
+                                    "\t\tRelocation base location: {:+x} (virt: {:+x})",
+                                    base, virt_base
+                                );
+                                println!("\t\tFinal relocated instruction: {:#x}", inst);
+                            }
+                            exec_mmap[base..base + 4].copy_from_slice(&inst.to_le_bytes());
+                            continue;
+                        }
                         let target: i64 = match rel.1.kind() {
                             RelocationKind::Relative | RelocationKind::PltRelative => {
                                 target_offset - virt_base as i64 + rel.1.addend()
@@ -2833,6 +3092,18 @@ pub fn surgery_elf(
                 VirtualOffset::Absolute => 0,
             };
             match s.size {
+                4 if machine.is_aarch64() => {
+                    let target = func_virt_offset as i64 - surgery_virt_offset;
+                    if verbose {
+                        println!("\tTarget Jump: {:+x}", target);
+                    }
+                    let inst_offset = (s.file_offset + md.added_byte_count) as usize;
+                    let inst = u32::from_le_bytes(
+                        <[u8; 4]>::try_from(&exec_mmap[inst_offset..inst_offset + 4]).unwrap(),
+                    );
+                    let data = aarch64::set_branch_offset(inst, target).to_le_bytes();
+                    exec_mmap[inst_offset..inst_offset + 4].copy_from_slice(&data);
+                }
                 4 => {
                     let target = (func_virt_offset as i64 - surgery_virt_offset) as i32;
                     if verbose {
@@ -2864,18 +3135,28 @@ pub fn surgery_elf(
         if let Some((plt_off, plt_vaddr)) = md.plt_addresses.get(func_name) {
             let plt_off = (*plt_off + md.added_byte_count) as usize;
             let plt_vaddr = *plt_vaddr + md.added_byte_count;
-            let jmp_inst_len = 5;
-            let target =
-                (func_virt_offset as i64 - (plt_vaddr as i64 + jmp_inst_len as i64)) as i32;
-            if verbose {
-                println!("\tPLT: {:+x}, {:+x}", plt_off, plt_vaddr);
-                println!("\tTarget Jump: {:+x}", target);
-            }
-            let data = target.to_le_bytes();
-            exec_mmap[plt_off] = 0xE9;
-            exec_mmap[plt_off + 1..plt_off + jmp_inst_len].copy_from_slice(&data);
-            for i in jmp_inst_len..PLT_ADDRESS_OFFSET as usize {
-                exec_mmap[plt_off + i] = 0x90;
+            if machine.is_aarch64() {
+                let target = func_virt_offset as i64 - plt_vaddr as i64;
+                if verbose {
+                    println!("\tPLT: {:+x}, {:+x}", plt_off, plt_vaddr);
+                    println!("\tTarget Jump: {:+x}", target);
+                }
+                exec_mmap[plt_off..plt_off + PLT_ADDRESS_OFFSET as usize]
+                    .copy_from_slice(&aarch64::plt_jump(target));
+            } else {
+                let jmp_inst_len = 5;
+                let target =
+                    (func_virt_offset as i64 - (plt_vaddr as i64 + jmp_inst_len as i64)) as i32;
+                if verbose {
+                    println!("\tPLT: {:+x}, {:+x}", plt_off, plt_vaddr);
+                    println!("\tTarget Jump: {:+x}", target);
+                }
+                let data = target.to_le_bytes();
+                exec_mmap[plt_off] = 0xE9;
+                exec_mmap[plt_off + 1..plt_off + jmp_inst_len].copy_from_slice(&data);
+                for i in jmp_inst_len..PLT_ADDRESS_OFFSET as usize {
+                    exec_mmap[plt_off + i] = 0x90;
+                }
             }
         }
 
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize, PartialEq, Debug)]
 pub enum VirtualOffset {
     Absolute,
+    // The address the offset is relative to.
+    // On x86_64 that is the next instruction, on aarch64 it is the branch instruction being patched.
     Relative(u64),
 }
 
@@ -32,6 +34,7 @@ pub struct Metadata {
     pub last_vaddr: u64,
     pub dynamic_section_offset: u64,
     pub dynamic_symbol_table_section_offset: u64,
+    pub dynamic_symbol_table_size: u64,
     pub symbol_table_section_offset: u64,
     pub symbol_table_size: u64,
     pub macho_cmd_loc: u64,
@@ -0,0 +1,91 @@
+//! Surgically link hosts cross-compiled for aarch64 Linux.
+//!
+//! The linked binaries are never executed, so these tests also run on x86_64 machines.
+//! Instead, the patched instructions are checked to branch into the app.
+
+mod helpers;
+
+#[cfg(test)]
+mod aarch64 {
+    use crate::helpers::{link, Host, APP_FUNCTION};
+    use object::{elf, Object, ObjectSection, ObjectSymbol, RelocationKind};
+    use std::path::Path;
+    use std::str::FromStr;
+    use target_lexicon::Triple;
+
+    /// Returns the linked binary.
+    fn link_aarch64(dir: &Path, host_type: Host) -> Vec<u8> {
+        let triple = Triple::from_str("aarch64-unknown-linux-gnu").unwrap();
+        let (_host, linked) = link(&triple, dir, host_type);
+
+        linked
+    }
+
+    fn branch_target(inst: u32, address: u64) -> Option<u64> {
+        if inst & 0x7c00_0000 != 0x1400_0000 {
+            return None;
+        }
+
+        let imm26 = ((inst << 6) as i32 >> 6) as i64;
+
+        Some((address as i64 + imm26 * 4) as u64)
+    }
+
+    fn branch_targets(data: &[u8], address: u64) -> Vec<u64> {
+        data.chunks_exact(4)
+            .enumerate()
+            .filter_map(|(i, word)| {
+                let inst = u32::from_le_bytes([word[0], word[1], word[2], word[3]]);
+                branch_target(inst, address + 4 * i as u64)
+            })
+            .collect()
+    }
+
+    fn check_linked(data: &[u8]) {
+        let obj = object::File::parse(data).unwrap();
+
+        let app_function = obj
+            .dynamic_symbols()
+            .find(|sym| sym.name() == Ok(APP_FUNCTION))
+            .unwrap();
+        assert!(!app_function.is_undefined());
+        let app_address = app_function.address();
+
+        // The direct call from `main` is patched to branch into the app.
+        let main = obj.symbols().find(|sym| sym.name() == Ok("main")).unwrap();
+        let text = obj.section_by_name(".text").unwrap();
+        let main_start = (main.address() - text.address()) as usize;
+        let main_code = &text.data().unwrap()[main_start..main_start + main.size() as usize];
+        assert!(branch_targets(main_code, main.address()).contains(&app_address));
+
+        // The plt entry is replaced by a branch into the app, in case a call to it was missed.
+        let plt = obj.section_by_name(".plt").unwrap();
+        assert!(branch_targets(plt.data().unwrap(), plt.address()).contains(&app_address));
+
+        // The function pointer is now relative to where the binary is loaded.
+        let relocations: Vec<_> = obj.dynamic_relocations().unwrap().collect();
+        assert!(relocations.iter().any(|(_, reloc)| {
+            reloc.kind() == RelocationKind::Elf(elf::R_AARCH64_RELATIVE)
+                && reloc.addend() == app_address as i64
+        }));
+        assert!(relocations.iter().all(|(_, reloc)| {
+            reloc.kind() != RelocationKind::Elf(elf::R_AARCH64_GLOB_DAT)
+                && reloc.kind() != RelocationKind::Absolute
+                || reloc.target() != object::RelocationTarget::Symbol(app_function.index())
+        }));
+    }
+
+    #[test]
+    fn executable_host() {
+        let dir = tempfile::tempdir().unwrap();
+
+        check_linked(&link_aarch64(dir.path(), Host::Executable));
+    }
+
+    #[test]
+    fn shared_library_host() {
+        let dir = tempfile::tempdir().unwrap();
+
+        check_linked(&link_aarch64(dir.path(), Host::SharedLibrary));
+    }
+}
@@ -0,0 +1,12 @@
+// Stands in for the object file of a Roc app.
+extern void *roc_alloc(unsigned long size, unsigned int alignment);
+
+static const long answers[4] = {1, 2, 3, 42};
+static void *last_allocation;
+void *(*allocator)(unsigned long, unsigned int);
+
+long roc__mainForHost_1_exposed(void) {
+    allocator = roc_alloc;
+    last_allocation = roc_alloc(8, 8);
+    return answers[3];
+}
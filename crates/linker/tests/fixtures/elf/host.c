@@ -0,0 +1,14 @@
+#include <stdio.h>
+#include <stdlib.h>
+
+extern long roc__mainForHost_1_exposed(void);
+
+// Loading the address of an app function goes through the global offset table.
+long (*const roc_main)(void) = roc__mainForHost_1_exposed;
+
+void *roc_alloc(unsigned long size, unsigned int alignment) { return malloc(size); }
+
+int main(void) {
+    printf("%ld %ld\n", roc__mainForHost_1_exposed(), roc_main());
+    return 0;
+}
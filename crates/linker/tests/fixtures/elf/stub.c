@@ -0,0 +1,2 @@
+// Stands in for the dummy app the host is dynamically linked against during preprocessing.
+long roc__mainForHost_1_exposed(void) { return 0; }
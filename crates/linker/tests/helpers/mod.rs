@@ -0,0 +1,119 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use target_lexicon::Triple;
+
+pub const APP_FUNCTION: &str = "roc__mainForHost_1_exposed";
+
+pub enum Host {
+    Executable,
+    SharedLibrary,
+}
+
+fn zig_executable() -> String {
+    match std::env::var("ROC_ZIG") {
+        Ok(path) => path,
+        Err(_) => "zig".into(),
+    }
+}
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("elf")
+        .join(name)
+}
+
+fn zig_cc(triple: &Triple, args: &[&str], out: &Path) {
+    let zig_target = format!("{}-linux-gnu", triple.architecture);
+    let output = Command::new(zig_executable())
+        .args(["cc", "-target", &zig_target, "-fPIC"])
+        .args(args)
+        .arg("-o")
+        .arg(out)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "zig cc failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Build the fixture host and app for `triple`, then preprocess and surgically link them.
+/// Returns the host before and after linking.
+pub fn link(triple: &Triple, dir: &Path, host_type: Host) -> (Vec<u8>, Vec<u8>) {
+    let app = dir.join("app.o");
+    let dummy_lib = dir.join("libapp.so");
+    let host = dir.join("dynhost");
+    let metadata = dir.join("metadata");
+    let out = dir.join("out");
+
+    zig_cc(triple, &["-c", fixture("app.c").to_str().unwrap()], &app);
+    zig_cc(
+        triple,
+        &[
+            "-shared",
+            "-Wl,-soname,libapp.so",
+            fixture("stub.c").to_str().unwrap(),
+        ],
+        &dummy_lib,
+    );
+
+    let host_source = fixture("host.c");
+    let native = Triple::host();
+    match host_type {
+        Host::Executable => zig_cc(
+            triple,
+            &[
+                "-pie",
+                host_source.to_str().unwrap(),
+                dummy_lib.to_str().unwrap(),
+            ],
+            &host,
+        ),
+        Host::SharedLibrary
+            if (triple.architecture, triple.operating_system)
+                == (native.architecture, native.operating_system) =>
+        {
+            // This is how `roc build --lib` links the host.
+            let host_obj = dir.join("host.o");
+            zig_cc(triple, &["-c", host_source.to_str().unwrap()], &host_obj);
+            roc_linker::link_shared_host(triple, &host_obj, &dummy_lib, &host);
+        }
+        Host::SharedLibrary => {
+            // `roc build --lib` can't link hosts for other targets, so let zig cross-compile it.
+            zig_cc(
+                triple,
+                &[
+                    "-shared",
+                    host_source.to_str().unwrap(),
+                    dummy_lib.to_str().unwrap(),
+                ],
+                &host,
+            );
+        }
+    }
+
+    roc_linker::preprocess(
+        triple,
+        host.to_str().unwrap(),
+        metadata.to_str().unwrap(),
+        out.to_str().unwrap(),
+        &dummy_lib,
+        false,
+        false,
+    );
+    roc_linker::surgery(
+        app.to_str().unwrap(),
+        metadata.to_str().unwrap(),
+        out.to_str().unwrap(),
+        false,
+        false,
+        triple,
+    );
+
+    (fs::read(host).unwrap(), fs::read(out).unwrap())
+}
@@ -0,0 +1,120 @@
+//! Surgically link executable and shared library hosts for x86_64 Linux.
+//!
+//! The linked binaries are never executed.
+//! Instead, the relocations and dynamic symbols are checked to point at the app.
+
+mod helpers;
+
+#[cfg(test)]
+mod x86_64 {
+    use crate::helpers::{link, Host, APP_FUNCTION};
+    use object::{elf, Object, ObjectSection, ObjectSymbol, RelocationKind, RelocationTarget};
+    use std::path::Path;
+    use std::str::FromStr;
+    use target_lexicon::Triple;
+
+    fn link_x86_64(dir: &Path, host_type: Host) -> (Vec<u8>, Vec<u8>) {
+        link(
+            &Triple::from_str("x86_64-unknown-linux-gnu").unwrap(),
+            dir,
+            host_type,
+        )
+    }
+
+    fn call_targets(data: &[u8], address: u64) -> Vec<u64> {
+        data.windows(5)
+            .enumerate()
+            .filter(|(_, inst)| inst[0] == 0xe8)
+            .map(|(i, inst)| {
+                let rel32 = i32::from_le_bytes([inst[1], inst[2], inst[3], inst[4]]) as i64;
+                (address as i64 + i as i64 + 5 + rel32) as u64
+            })
+            .collect()
+    }
+
+    fn check_linked(data: &[u8]) {
+        let obj = object::File::parse(data).unwrap();
+
+        let app_function = obj
+            .dynamic_symbols()
+            .find(|sym| sym.name() == Ok(APP_FUNCTION))
+            .unwrap();
+        assert!(!app_function.is_undefined());
+        let app_address = app_function.address();
+
+        // The direct call from `main` is patched to call into the app.
+        let main = obj.symbols().find(|sym| sym.name() == Ok("main")).unwrap();
+        let text = obj.section_by_name(".text").unwrap();
+        let main_start = (main.address() - text.address()) as usize;
+        let main_code = &text.data().unwrap()[main_start..main_start + main.size() as usize];
+        assert!(call_targets(main_code, main.address()).contains(&app_address));
+
+        let relocations: Vec<_> = obj.dynamic_relocations().unwrap().collect();
+
+        // The function pointer is now relative to where the binary is loaded.
+        assert!(relocations.iter().any(|(_, reloc)| {
+            reloc.kind() == RelocationKind::Elf(elf::R_X86_64_RELATIVE)
+                && reloc.addend() == app_address as i64
+        }));
+
+        // No relocation refers to the app function anymore, in particular not the jump slot of its plt entry.
+        assert!(relocations
+            .iter()
+            .all(|(_, reloc)| reloc.target() != RelocationTarget::Symbol(app_function.index())));
+        assert!(relocations
+            .iter()
+            .any(|(_, reloc)| reloc.kind() == RelocationKind::Elf(elf::R_X86_64_JUMP_SLOT)));
+    }
+
+    fn defined_dynamic_symbols(obj: &object::File) -> Vec<(String, u64)> {
+        obj.dynamic_symbols()
+            .filter(|sym| !sym.is_undefined() && sym.name() != Ok(APP_FUNCTION))
+            .map(|sym| (sym.name().unwrap().to_string(), sym.address()))
+            .collect()
+    }
+
+    #[test]
+    fn executable_host() {
+        let dir = tempfile::tempdir().unwrap();
+        let (host, linked) = link_x86_64(dir.path(), Host::Executable);
+
+        check_linked(&linked);
+
+        // The dynamic symbols of the executable itself are left untouched.
+        let host = object::File::parse(&*host).unwrap();
+        let linked = object::File::parse(&*linked).unwrap();
+        assert_eq!(
+            defined_dynamic_symbols(&linked),
+            defined_dynamic_symbols(&host)
+        );
+    }
+
+    #[test]
+    fn shared_library_host() {
+        let dir = tempfile::tempdir().unwrap();
+        let (host, linked) = link_x86_64(dir.path(), Host::SharedLibrary);
+
+        check_linked(&linked);
+
+        let host = object::File::parse(&*host).unwrap();
+        let linked = object::File::parse(&*linked).unwrap();
+
+        // The functions exported by the host moved along with the rest of the code,
+        // so their dynamic symbols are shifted to the same addresses as the static ones.
+        let exported = defined_dynamic_symbols(&linked);
+        assert!(exported.iter().any(|(name, _)| name == "roc_alloc"));
+        for (name, address) in exported {
+            if let Some(sym) = linked.symbols().find(|sym| sym.name() == Ok(name.as_str())) {
+                assert_eq!(address, sym.address(), "{} was not shifted", name);
+            }
+        }
+
+        // The indices of the dynamic symbols are kept, which the relocations of the host rely on.
+        let names = |obj: &object::File| -> Vec<String> {
+            obj.dynamic_symbols()
+                .map(|sym| sym.name().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(names(&linked), names(&host));
+    }
+}
@@ -0,0 +1,340 @@
+//! Type checking a document, and answering questions about the result.
+
+use crate::convert::Lines;
+use bumpalo::Bump;
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Hover, HoverContents, Location,
+    MarkupContent, MarkupKind, Position, Range, Url,
+};
+use roc_can::traverse::{find_closest_type_at, find_definition_of, find_symbol_at};
+use roc_load::{LoadedModule, LoadingProblem};
+use roc_module::symbol::Interns;
+use roc_region::all::{LineColumn, LineColumnRegion, LineInfo};
+use roc_reporting::report::{
+    can_problem, parse_problem, type_problem, RenderTarget, Report, RocDocAllocator, Severity,
+};
+use roc_target::TargetInfo;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+/// The diagnostics for each file that has any.
+pub type Diagnostics = HashMap<Url, Vec<Diagnostic>>;
+
+pub struct Analysis {
+    /// [None] if the module could not be type checked, e.g. because it does not parse.
+    pub loaded: Option<LoadedModule>,
+    pub diagnostics: Diagnostics,
+}
+
+/// Loads and type checks the module at `path`, whose source is `src`, and the modules it imports.
+pub fn analyze(uri: &Url, path: &Path, src: &str) -> Analysis {
+    let mut diagnostics = Diagnostics::default();
+
+    // The document itself always gets an entry, so its old diagnostics are cleared.
+    diagnostics.insert(uri.clone(), Vec::new());
+
+    let arena = Bump::new();
+    let src_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    // The compiler still panics on some programs it cannot handle yet. That should not take
+    // down the whole language server.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        roc_load::load_and_typecheck_str(
+            &arena,
+            path.to_path_buf(),
+            src,
+            src_dir,
+            Default::default(),
+            TargetInfo::default_x86_64(),
+            RenderTarget::Json,
+        )
+    }));
+
+    let loaded = match result {
+        Ok(Ok(mut loaded)) => {
+            problem_diagnostics(&mut loaded, uri, path, &mut diagnostics);
+
+            Some(loaded)
+        }
+        Ok(Err(problem)) => {
+            loading_problem_diagnostics(problem, uri, path, src, &mut diagnostics);
+
+            None
+        }
+        Err(_) => {
+            let message = "The compiler crashed while checking this module.";
+
+            push_whole_file_diagnostic(uri, message.to_string(), &mut diagnostics);
+
+            None
+        }
+    };
+
+    Analysis {
+        loaded,
+        diagnostics,
+    }
+}
+
+/// The URI of `file`, which is the document's own URI if it is the document being analyzed.
+fn file_uri(file: &Path, uri: &Url, path: &Path) -> Option<Url> {
+    if file == path {
+        Some(uri.clone())
+    } else {
+        Url::from_file_path(file).ok()
+    }
+}
+
+fn problem_diagnostics(
+    loaded: &mut LoadedModule,
+    uri: &Url,
+    path: &Path,
+    diagnostics: &mut Diagnostics,
+) {
+    for (home, (file, src)) in loaded.sources.iter() {
+        let file_uri = match file_uri(file, uri, path) {
+            Some(file_uri) => file_uri,
+            None => continue,
+        };
+
+        let src_lines: Vec<&str> = src.split('\n').collect();
+        let line_info = LineInfo::new(src);
+        let lines = Lines::new(src);
+        let alloc = RocDocAllocator::new(&src_lines, *home, &loaded.interns);
+
+        let mut file_diagnostics = Vec::new();
+
+        for problem in loaded.can_problems.remove(home).unwrap_or_default() {
+            let report = can_problem(&alloc, &line_info, file.clone(), problem);
+
+            file_diagnostics.push(report_diagnostic(report, &file_uri, &lines));
+        }
+
+        for problem in loaded.type_problems.remove(home).unwrap_or_default() {
+            if let Some(report) = type_problem(&alloc, &line_info, file.clone(), problem) {
+                file_diagnostics.push(report_diagnostic(report, &file_uri, &lines));
+            }
+        }
+
+        if !file_diagnostics.is_empty() {
+            diagnostics
+                .entry(file_uri)
+                .or_default()
+                .extend(file_diagnostics);
+        }
+    }
+}
+
+fn report_diagnostic(report: Report, uri: &Url, lines: &Lines) -> Diagnostic {
+    let severity = severity(report.severity);
+    let title = report.title.clone();
+
+    let mut message = String::new();
+    let mut regions = report.render_message(&mut message).into_iter();
+
+    let range = regions
+        .next()
+        .map(|region| lines.lsp_range(region))
+        .unwrap_or_default();
+
+    let related = regions
+        .map(|region| DiagnosticRelatedInformation {
+            location: Location::new(uri.clone(), lines.lsp_range(region)),
+            message: title.clone(),
+        })
+        .collect();
+
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        source: Some("roc".to_string()),
+        message: format!("{}\n\n{}", title, message.trim()),
+        related_information: Some(related),
+        ..Default::default()
+    }
+}
+
+fn severity(severity: Severity) -> DiagnosticSeverity {
+    match severity {
+        Severity::RuntimeError => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+    }
+}
+
+fn loading_problem_diagnostics(
+    problem: LoadingProblem,
+    uri: &Url,
+    path: &Path,
+    src: &str,
+    diagnostics: &mut Diagnostics,
+) {
+    match problem {
+        LoadingProblem::ParsingFailed(problem) => {
+            // The module header did not parse, so there are no module ids yet.
+            let mut interns = Interns::default();
+            let home = interns.module_ids.get_or_insert(&"".into());
+
+            let src_lines: Vec<&str> = src.split('\n').collect();
+            let line_info = LineInfo::new(src);
+            let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+
+            let report = parse_problem(&alloc, &line_info, path.to_path_buf(), 0, problem);
+            let diagnostic = report_diagnostic(report, uri, &Lines::new(src));
+
+            diagnostics.entry(uri.clone()).or_default().push(diagnostic);
+        }
+        LoadingProblem::FormattedReport(reports) => {
+            // We asked for JSON, so this is one report per line.
+            for line in reports.lines().filter(|line| !line.is_empty()) {
+                match serde_json::from_str(line) {
+                    Ok(report) => json_report_diagnostic(&report, uri, path, src, diagnostics),
+                    Err(_) => push_whole_file_diagnostic(uri, line.to_string(), diagnostics),
+                }
+            }
+        }
+        LoadingProblem::FileProblem { filename, error } => {
+            let message = format!("Could not read {}: {:?}", filename.display(), error);
+
+            push_whole_file_diagnostic(uri, message, diagnostics);
+        }
+        other => {
+            let message = format!("Could not load this module: {:?}", other);
+
+            push_whole_file_diagnostic(uri, message, diagnostics);
+        }
+    }
+}
+
+/// A report that was already rendered as JSON by `roc_reporting`.
+fn json_report_diagnostic(
+    report: &serde_json::Value,
+    uri: &Url,
+    path: &Path,
+    src: &str,
+    diagnostics: &mut Diagnostics,
+) {
+    let file = report["file"].as_str().map(PathBuf::from);
+
+    // Reports about imported modules need that module's source to convert columns.
+    let (file_uri, file_src) = match file {
+        Some(file) if file != path => {
+            match (file_uri(&file, uri, path), std::fs::read_to_string(&file)) {
+                (Some(file_uri), Ok(file_src)) => (file_uri, file_src),
+                _ => (uri.clone(), src.to_string()),
+            }
+        }
+        _ => (uri.clone(), src.to_string()),
+    };
+
+    let lines = Lines::new(&file_src);
+    let json_range =
+        |region: &serde_json::Value| json_region(region).map(|region| lines.lsp_range(region));
+
+    let range = json_range(&report["region"]).unwrap_or_default();
+    let title = report["title"].as_str().unwrap_or_default();
+
+    let related = report["related"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(json_range)
+        .map(|range| DiagnosticRelatedInformation {
+            location: Location::new(file_uri.clone(), range),
+            message: title.to_string(),
+        })
+        .collect();
+
+    let severity = match report["severity"].as_str() {
+        Some("warning") => Severity::Warning,
+        _ => Severity::RuntimeError,
+    };
+
+    let diagnostic = Diagnostic {
+        range,
+        severity: Some(self::severity(severity)),
+        source: Some("roc".to_string()),
+        message: format!(
+            "{}\n\n{}",
+            title,
+            report["message"].as_str().unwrap_or_default()
+        ),
+        related_information: Some(related),
+        ..Default::default()
+    };
+
+    diagnostics.entry(file_uri).or_default().push(diagnostic);
+}
+
+/// JSON reports count lines and columns from 1.
+fn json_region(region: &serde_json::Value) -> Option<LineColumnRegion> {
+    let line_column = |json: &serde_json::Value| {
+        Some(LineColumn {
+            line: json["line"].as_u64()?.checked_sub(1)? as u32,
+            column: json["column"].as_u64()?.checked_sub(1)? as u32,
+        })
+    };
+
+    Some(LineColumnRegion {
+        start: line_column(&region["start"])?,
+        end: line_column(&region["end"])?,
+    })
+}
+
+fn push_whole_file_diagnostic(uri: &Url, message: String, diagnostics: &mut Diagnostics) {
+    let diagnostic = Diagnostic {
+        range: Range::new(Position::new(0, 0), Position::new(0, 0)),
+        severity: Some(DiagnosticSeverity::ERROR),
+        source: Some("roc".to_string()),
+        message,
+        ..Default::default()
+    };
+
+    diagnostics.entry(uri.clone()).or_default().push(diagnostic);
+}
+
+/// The type of the innermost expression at `position`.
+pub fn hover(loaded: &mut LoadedModule, lines: &Lines, position: Position) -> Option<Hover> {
+    let decls = loaded.declarations_by_id.get(&loaded.module_id)?;
+    let (region, var) = find_closest_type_at(lines.roc_position(position), decls)?;
+
+    let typ = name_and_print_var(
+        var,
+        loaded.solved.inner_mut(),
+        loaded.module_id,
+        &loaded.interns,
+        DebugPrint::NOTHING,
+    );
+
+    Some(Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value: format!("```roc\n{}\n```", typ),
+        }),
+        range: Some(lines.lsp_range_of_region(region)),
+    })
+}
+
+/// Where the symbol at `position` is defined, if that is in a module we have the source of.
+pub fn definition(
+    loaded: &LoadedModule,
+    uri: &Url,
+    path: &Path,
+    lines: &Lines,
+    position: Position,
+) -> Option<Location> {
+    let decls = loaded.declarations_by_id.get(&loaded.module_id)?;
+    let symbol = find_symbol_at(lines.roc_position(position), decls)?.value;
+
+    let home = symbol.module_id();
+    let region = find_definition_of(symbol, loaded.declarations_by_id.get(&home)?)?;
+
+    let (file, src) = loaded.sources.get(&home)?;
+    let file_uri = file_uri(file, uri, path)?;
+
+    Some(Location::new(
+        file_uri,
+        Lines::new(src).lsp_range_of_region(region),
+    ))
+}
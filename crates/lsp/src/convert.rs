@@ -0,0 +1,94 @@
+//! The compiler counts columns in bytes, but the language server protocol counts them in UTF-16
+//! code units. These helpers convert between the two.
+
+use lsp_types::{Position, Range};
+use roc_region::all::{self, LineColumn, LineColumnRegion, LineInfo, Region};
+
+/// The lines of a source file, for converting positions in it.
+pub struct Lines<'a> {
+    lines: Vec<&'a str>,
+    line_info: LineInfo,
+}
+
+impl<'a> Lines<'a> {
+    pub fn new(src: &'a str) -> Self {
+        Lines {
+            lines: src.split('\n').collect(),
+            line_info: LineInfo::new(src),
+        }
+    }
+
+    fn line(&self, line: u32) -> &'a str {
+        self.lines.get(line as usize).copied().unwrap_or("")
+    }
+
+    /// The length of the line in UTF-16 code units.
+    pub fn line_length(&self, line: u32) -> u32 {
+        self.line(line).encode_utf16().count() as u32
+    }
+
+    pub fn lsp_position(&self, lc: LineColumn) -> Position {
+        let line = self.line(lc.line);
+        let prefix = line.get(..lc.column as usize).unwrap_or(line);
+
+        Position::new(lc.line, prefix.encode_utf16().count() as u32)
+    }
+
+    pub fn lsp_range(&self, region: LineColumnRegion) -> Range {
+        Range::new(
+            self.lsp_position(region.start),
+            self.lsp_position(region.end),
+        )
+    }
+
+    pub fn lsp_range_of_region(&self, region: Region) -> Range {
+        self.lsp_range(self.line_info.convert_region(region))
+    }
+
+    pub fn lsp_position_of_offset(&self, offset: usize) -> Position {
+        self.lsp_position(self.line_info.convert_offset(offset as u32))
+    }
+
+    /// A position that is past the end of its line, or of the source, is moved to the end of it.
+    pub fn line_column(&self, position: Position) -> LineColumn {
+        let last_line = self.lines.len() as u32 - 1;
+
+        if position.line > last_line {
+            return LineColumn {
+                line: last_line,
+                column: self.line(last_line).len() as u32,
+            };
+        }
+
+        let line = self.line(position.line);
+        let mut utf16_column = 0;
+        let mut column = line.len();
+
+        for (byte_index, c) in line.char_indices() {
+            if utf16_column >= position.character as usize {
+                column = byte_index;
+                break;
+            }
+
+            utf16_column += c.len_utf16();
+        }
+
+        LineColumn {
+            line: position.line,
+            column: column as u32,
+        }
+    }
+
+    pub fn roc_position(&self, position: Position) -> all::Position {
+        self.line_info
+            .convert_line_column(self.line_column(position))
+    }
+
+    /// The range covering the whole source.
+    pub fn full_range(&self) -> Range {
+        let last_line = self.lines.len() as u32 - 1;
+        let end = Position::new(last_line, self.line_length(last_line));
+
+        Range::new(Position::new(0, 0), end)
+    }
+}
@@ -0,0 +1,70 @@
+//! Document formatting, the same way `roc format` does it, and formatting of selections.
+
+use crate::convert::Lines;
+use bumpalo::Bump;
+use lsp_types::{Range, TextEdit};
+use roc_fmt::def::fmt_defs;
+use roc_fmt::module::fmt_module;
+use roc_fmt::{Ast, Buf};
+use roc_parse::{
+    module::{self, module_defs},
+    parser::{Parser, SyntaxError},
+    state::State,
+};
+use roc_region::all::Region;
+
+fn parse_all<'a>(arena: &'a Bump, src: &'a str) -> Result<Ast<'a>, SyntaxError<'a>> {
+    let (module, state) = module::parse_header(arena, State::new(src.as_bytes()))
+        .map_err(|e| SyntaxError::Header(e.problem))?;
+
+    let (_, defs, _) = module_defs().parse(arena, state).map_err(|(_, e, _)| e)?;
+
+    Ok(Ast { module, defs })
+}
+
+/// The edits that format `src`, or [None] if it does not parse.
+pub fn format(src: &str) -> Option<Vec<TextEdit>> {
+    let arena = Bump::new();
+    let ast = parse_all(&arena, src).ok()?;
+
+    let mut buf = Buf::new_in(&arena);
+
+    fmt_module(&mut buf, &ast.module);
+    fmt_defs(&mut buf, &ast.defs, 0);
+    buf.fmt_end_of_file();
+
+    let formatted = buf.as_str();
+
+    if formatted == src {
+        Some(Vec::new())
+    } else {
+        // Replacing the whole document is simplest, and editors keep the cursor in place anyway.
+        let range = Lines::new(src).full_range();
+
+        Some(vec![TextEdit::new(range, formatted.to_string())])
+    }
+}
+
+/// The edits that format the top-level defs in `range`, or [None] if `src` does not parse.
+pub fn format_range(src: &str, range: Range) -> Option<Vec<TextEdit>> {
+    let arena = Bump::new();
+    let lines = Lines::new(src);
+    let region = Region::new(
+        lines.roc_position(range.start),
+        lines.roc_position(range.end),
+    );
+
+    let edits = roc_fmt::range::format_range(&arena, src, region).ok()?;
+
+    Some(
+        edits
+            .iter()
+            .map(|edit| {
+                TextEdit::new(
+                    lines.lsp_range_of_region(edit.region),
+                    edit.new_text.to_string(),
+                )
+            })
+            .collect(),
+    )
+}
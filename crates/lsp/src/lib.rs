@@ -0,0 +1,286 @@
+//! A language server for Roc.
+//!
+//! Editors talk to it with the Language Server Protocol, usually over stdio (see `main.rs`).
+//! Every time a document changes it is loaded and type checked again, and the problems the
+//! compiler finds are published as diagnostics. The result of the last check answers hover and
+//! go-to-definition requests. Formatting and semantic tokens only need the document's text.
+mod analysis;
+mod convert;
+mod format;
+mod semantic_tokens;
+
+use analysis::Analysis;
+use convert::Lines;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as LspNotification, PublishDiagnostics,
+};
+use lsp_types::request::{
+    Formatting, GotoDefinition, HoverRequest, RangeFormatting, Request as LspRequest,
+    SemanticTokensFullRequest,
+};
+use lsp_types::{
+    GotoDefinitionResponse, HoverProviderCapability, OneOf, PublishDiagnosticsParams,
+    SemanticTokens, SemanticTokensFullOptions, SemanticTokensOptions, SemanticTokensResult,
+    SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, Url,
+};
+use roc_load::LoadedModule;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::PathBuf;
+
+pub fn server_capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        document_range_formatting_provider: Some(OneOf::Left(true)),
+        semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
+            SemanticTokensOptions {
+                legend: semantic_tokens::legend(),
+                full: Some(SemanticTokensFullOptions::Bool(true)),
+                ..Default::default()
+            },
+        )),
+        ..Default::default()
+    }
+}
+
+/// Runs the server until the client shuts it down, or the connection closes.
+pub fn run(connection: &Connection) -> Result<(), Box<dyn Error + Sync + Send>> {
+    connection.initialize(serde_json::to_value(server_capabilities())?)?;
+
+    let mut server = Server::default();
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+
+                let response = server.handle_request(request);
+
+                connection.sender.send(Message::Response(response))?;
+            }
+            Message::Notification(notification) => {
+                for notification in server.handle_notification(notification) {
+                    connection
+                        .sender
+                        .send(Message::Notification(notification))?;
+                }
+            }
+            Message::Response(_) => {
+                // We never send requests to the client, so there is nothing to do with responses.
+            }
+        }
+    }
+
+    Ok(())
+}
+
+struct Document {
+    path: PathBuf,
+    text: String,
+    /// [None] if the document could not be type checked, e.g. because it does not parse.
+    loaded: Option<LoadedModule>,
+    /// The files we last published diagnostics for on behalf of this document.
+    diagnosed: Vec<Url>,
+}
+
+#[derive(Default)]
+struct Server {
+    documents: HashMap<Url, Document>,
+}
+
+impl Server {
+    fn handle_request(&mut self, request: Request) -> Response {
+        let id = request.id.clone();
+
+        match request.method.as_str() {
+            HoverRequest::METHOD => self.respond::<HoverRequest>(request, |server, params| {
+                let position = params.text_document_position_params;
+                let document = server.documents.get_mut(&position.text_document.uri)?;
+                let lines = Lines::new(&document.text);
+
+                analysis::hover(document.loaded.as_mut()?, &lines, position.position)
+            }),
+            GotoDefinition::METHOD => self.respond::<GotoDefinition>(request, |server, params| {
+                let position = params.text_document_position_params;
+                let uri = position.text_document.uri;
+                let document = server.documents.get(&uri)?;
+                let lines = Lines::new(&document.text);
+
+                let location = analysis::definition(
+                    document.loaded.as_ref()?,
+                    &uri,
+                    &document.path,
+                    &lines,
+                    position.position,
+                )?;
+
+                Some(GotoDefinitionResponse::Scalar(location))
+            }),
+            Formatting::METHOD => self.respond::<Formatting>(request, |server, params| {
+                let document = server.documents.get(&params.text_document.uri)?;
+
+                format::format(&document.text)
+            }),
+            RangeFormatting::METHOD => {
+                self.respond::<RangeFormatting>(request, |server, params| {
+                    let document = server.documents.get(&params.text_document.uri)?;
+
+                    format::format_range(&document.text, params.range)
+                })
+            }
+            SemanticTokensFullRequest::METHOD => {
+                self.respond::<SemanticTokensFullRequest>(request, |server, params| {
+                    let document = server.documents.get(&params.text_document.uri)?;
+
+                    Some(SemanticTokensResult::Tokens(SemanticTokens {
+                        result_id: None,
+                        data: semantic_tokens::semantic_tokens(&document.text),
+                    }))
+                })
+            }
+            _ => Response::new_err(
+                id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unsupported request: {}", request.method),
+            ),
+        }
+    }
+
+    fn respond<R>(
+        &mut self,
+        request: Request,
+        handler: impl FnOnce(&mut Self, R::Params) -> R::Result,
+    ) -> Response
+    where
+        R: LspRequest,
+    {
+        let id: RequestId = request.id.clone();
+
+        match serde_json::from_value(request.params) {
+            Ok(params) => Response::new_ok(id, handler(self, params)),
+            Err(error) => Response::new_err(id, ErrorCode::InvalidParams as i32, error.to_string()),
+        }
+    }
+
+    /// Returns the notifications to send back to the client.
+    fn handle_notification(&mut self, notification: Notification) -> Vec<Notification> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                match notification_params::<DidOpenTextDocument>(notification) {
+                    Some(params) => {
+                        let document = params.text_document;
+
+                        self.update(document.uri, document.version, document.text)
+                    }
+                    None => Vec::new(),
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                match notification_params::<DidChangeTextDocument>(notification) {
+                    // We only sync full documents, so the last change has the new text.
+                    Some(mut params) => match params.content_changes.pop() {
+                        Some(change) => self.update(
+                            params.text_document.uri,
+                            params.text_document.version,
+                            change.text,
+                        ),
+                        None => Vec::new(),
+                    },
+                    None => Vec::new(),
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                match notification_params::<DidCloseTextDocument>(notification) {
+                    Some(params) => match self.documents.remove(&params.text_document.uri) {
+                        Some(document) => document
+                            .diagnosed
+                            .into_iter()
+                            .map(|uri| publish_diagnostics(uri, Vec::new(), None))
+                            .collect(),
+                        None => Vec::new(),
+                    },
+                    None => Vec::new(),
+                }
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Checks the new text of a document, and returns the diagnostics to publish.
+    fn update(&mut self, uri: Url, version: i32, text: String) -> Vec<Notification> {
+        let path = uri
+            .to_file_path()
+            .unwrap_or_else(|()| PathBuf::from(uri.path()));
+
+        let Analysis {
+            loaded,
+            diagnostics,
+        } = analysis::analyze(&uri, &path, &text);
+
+        let previously_diagnosed = self
+            .documents
+            .remove(&uri)
+            .map(|document| document.diagnosed)
+            .unwrap_or_default();
+
+        let mut notifications = Vec::new();
+
+        // Clear the diagnostics of files that no longer have any problems.
+        for stale in previously_diagnosed {
+            if !diagnostics.contains_key(&stale) {
+                notifications.push(publish_diagnostics(stale, Vec::new(), None));
+            }
+        }
+
+        let diagnosed = diagnostics.keys().cloned().collect();
+
+        for (file_uri, file_diagnostics) in diagnostics {
+            let file_version = if file_uri == uri { Some(version) } else { None };
+
+            notifications.push(publish_diagnostics(
+                file_uri,
+                file_diagnostics,
+                file_version,
+            ));
+        }
+
+        self.documents.insert(
+            uri,
+            Document {
+                path,
+                text,
+                loaded,
+                diagnosed,
+            },
+        );
+
+        notifications
+    }
+}
+
+/// Notifications can't be answered with an error, so ones we can't read are ignored.
+fn notification_params<N: LspNotification>(notification: Notification) -> Option<N::Params> {
+    serde_json::from_value(notification.params).ok()
+}
+
+fn publish_diagnostics(
+    uri: Url,
+    diagnostics: Vec<lsp_types::Diagnostic>,
+    version: Option<i32>,
+) -> Notification {
+    let params = PublishDiagnosticsParams {
+        uri,
+        diagnostics,
+        version,
+    };
+
+    Notification::new(PublishDiagnostics::METHOD.to_string(), params)
+}
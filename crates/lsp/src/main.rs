@@ -0,0 +1,14 @@
+use lsp_server::Connection;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
+    // Stdout is the connection to the editor, so anything we log goes to stderr.
+    let (connection, io_threads) = Connection::stdio();
+
+    roc_lsp::run(&connection)?;
+
+    drop(connection);
+    io_threads.join()?;
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! Semantic tokens, from the tokenizer used for syntax highlighting.
+
+use crate::convert::Lines;
+use lsp_types::{SemanticToken, SemanticTokenType, SemanticTokensLegend};
+use roc_highlight::tokenizer::{full_tokenize, Token};
+
+/// The token types we report, in the order of the legend we send to the client.
+const TOKEN_TYPES: [SemanticTokenType; 6] = [
+    SemanticTokenType::KEYWORD,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::NUMBER,
+    SemanticTokenType::STRING,
+    SemanticTokenType::OPERATOR,
+];
+
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TOKEN_TYPES.to_vec(),
+        token_modifiers: Vec::new(),
+    }
+}
+
+/// The index of the token's type in the legend, if it is highlighted at all.
+fn token_type(token: Token) -> Option<u32> {
+    use Token::*;
+
+    let token_type = match token {
+        KeywordIf | KeywordThen | KeywordElse | KeywordWhen | KeywordAs | KeywordIs
+        | KeywordExpect | KeywordApp | KeywordInterface | KeywordPackages | KeywordImports
+        | KeywordProvides | KeywordTo | KeywordExposes | KeywordEffects | KeywordPlatform
+        | KeywordRequires => SemanticTokenType::KEYWORD,
+        LowercaseIdent => SemanticTokenType::VARIABLE,
+        UppercaseIdent => SemanticTokenType::TYPE,
+        NumberBase | Number => SemanticTokenType::NUMBER,
+        String => SemanticTokenType::STRING,
+        OpPlus | OpMinus | OpSlash | OpPercent | OpCaret | OpGreaterThan | OpLessThan
+        | OpAssignment | OpPizza | OpEquals | OpNotEquals | OpGreaterThanOrEq | OpLessThanOrEq
+        | OpAnd | OpOr | OpDoubleSlash | OpBackpassing | Bang | LambdaStart | Arrow | FatArrow
+        | Ampersand | Pipe | Asterisk => SemanticTokenType::OPERATOR,
+        _ => return None,
+    };
+
+    TOKEN_TYPES
+        .iter()
+        .position(|typ| *typ == token_type)
+        .map(|index| index as u32)
+}
+
+/// The tokens of `src`, encoded relative to each other as the protocol requires.
+pub fn semantic_tokens(src: &str) -> Vec<SemanticToken> {
+    let lines = Lines::new(src);
+    let table = full_tokenize(src);
+
+    let mut tokens = Vec::with_capacity(table.tokens.len());
+    let mut previous = lsp_types::Position::new(0, 0);
+
+    for ((token, offset), length) in table
+        .tokens
+        .iter()
+        .zip(table.offsets.iter())
+        .zip(table.lengths.iter())
+    {
+        let token_type = match token_type(*token) {
+            Some(token_type) => token_type,
+            None => continue,
+        };
+
+        let start = lines.lsp_position_of_offset(*offset);
+        let end = lines.lsp_position_of_offset(offset + length);
+
+        // Tokens may not span lines, so a multiline string is only highlighted on its first line.
+        let length = if end.line == start.line {
+            end.character - start.character
+        } else {
+            lines.line_length(start.line) - start.character
+        };
+
+        if length == 0 {
+            continue;
+        }
+
+        let delta_line = start.line - previous.line;
+        let delta_start = if delta_line == 0 {
+            start.character - previous.character
+        } else {
+            start.character
+        };
+
+        tokens.push(SemanticToken {
+            delta_line,
+            delta_start,
+            length,
+            token_type,
+            token_modifiers_bitset: 0,
+        });
+
+        previous = start;
+    }
+
+    tokens
+}
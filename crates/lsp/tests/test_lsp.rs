@@ -0,0 +1,360 @@
+//! Drives the language server with scripted JSON-RPC messages, the way an editor would.
+
+#[macro_use]
+extern crate indoc;
+#[macro_use]
+extern crate pretty_assertions;
+
+#[cfg(test)]
+mod test_lsp {
+    use lsp_server::{Connection, Message};
+    use serde_json::{json, Value};
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    struct Client {
+        connection: Connection,
+        server: JoinHandle<()>,
+        next_id: u64,
+    }
+
+    impl Client {
+        fn start() -> Self {
+            let (connection, server_connection) = Connection::memory();
+
+            let server = thread::spawn(move || {
+                roc_lsp::run(&server_connection).unwrap();
+            });
+
+            let mut client = Client {
+                connection,
+                server,
+                next_id: 0,
+            };
+
+            client.request("initialize", json!({ "capabilities": {} }));
+            client.notify("initialized", json!({}));
+
+            client
+        }
+
+        fn send(&self, message: Value) {
+            let message: Message = serde_json::from_value(message).unwrap();
+
+            self.connection.sender.send(message).unwrap();
+        }
+
+        fn receive(&self) -> Value {
+            // Type checking a module also loads the builtins, which takes a while in debug builds.
+            let message = self
+                .connection
+                .receiver
+                .recv_timeout(Duration::from_secs(120))
+                .expect("the server did not answer");
+
+            serde_json::to_value(message).unwrap()
+        }
+
+        fn notify(&self, method: &str, params: Value) {
+            self.send(json!({ "method": method, "params": params }));
+        }
+
+        /// Sends a request and returns the result of its response.
+        fn request(&mut self, method: &str, params: Value) -> Value {
+            self.next_id += 1;
+
+            self.send(json!({ "id": self.next_id, "method": method, "params": params }));
+
+            let response = self.receive();
+
+            assert_eq!(response["id"], json!(self.next_id));
+            assert_eq!(response["error"], Value::Null);
+
+            response["result"].clone()
+        }
+
+        fn shutdown(mut self) {
+            self.request("shutdown", Value::Null);
+            self.notify("exit", Value::Null);
+
+            self.server.join().unwrap();
+        }
+    }
+
+    fn uri() -> String {
+        let path = std::env::temp_dir().join("roc_lsp_test").join("Test.roc");
+
+        lsp_types::Url::from_file_path(path).unwrap().to_string()
+    }
+
+    fn open(client: &Client, src: &str) -> Value {
+        client.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": { "uri": uri(), "languageId": "roc", "version": 1, "text": src },
+            }),
+        );
+
+        client.receive()
+    }
+
+    fn position(line: u32, character: u32) -> Value {
+        json!({
+            "textDocument": { "uri": uri() },
+            "position": { "line": line, "character": character },
+        })
+    }
+
+    fn range(start: (u32, u32), end: (u32, u32)) -> Value {
+        json!({
+            "start": { "line": start.0, "character": start.1 },
+            "end": { "line": end.0, "character": end.1 },
+        })
+    }
+
+    const DOUBLE: &str = indoc!(
+        r#"
+        interface Test
+            exposes [double]
+            imports []
+
+        double = \n -> n  *  2
+        "#
+    );
+
+    #[test]
+    fn initialize() {
+        let (connection, server_connection) = Connection::memory();
+        let server = thread::spawn(move || roc_lsp::run(&server_connection).unwrap());
+        let mut client = Client {
+            connection,
+            server,
+            next_id: 0,
+        };
+
+        let result = client.request("initialize", json!({ "capabilities": {} }));
+
+        assert_eq!(result["capabilities"]["textDocumentSync"], json!(1));
+        assert_eq!(result["capabilities"]["hoverProvider"], json!(true));
+        assert_eq!(result["capabilities"]["definitionProvider"], json!(true));
+        assert_eq!(
+            result["capabilities"]["documentFormattingProvider"],
+            json!(true)
+        );
+        assert_eq!(
+            result["capabilities"]["documentRangeFormattingProvider"],
+            json!(true)
+        );
+        assert_eq!(
+            result["capabilities"]["semanticTokensProvider"]["legend"]["tokenTypes"],
+            json!(["keyword", "variable", "type", "number", "string", "operator"])
+        );
+
+        client.notify("initialized", json!({}));
+        client.shutdown();
+    }
+
+    #[test]
+    fn diagnostics() {
+        let client = Client::start();
+
+        let notification = open(
+            &client,
+            indoc!(
+                r#"
+                interface Test
+                    exposes [greeting]
+                    imports []
+
+                greeting = Str.concat "Hello, " 42
+                "#
+            ),
+        );
+
+        assert_eq!(
+            notification["method"],
+            json!("textDocument/publishDiagnostics")
+        );
+        assert_eq!(notification["params"]["uri"], json!(uri()));
+        assert_eq!(notification["params"]["version"], json!(1));
+
+        let diagnostics = notification["params"]["diagnostics"].as_array().unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["range"], range((4, 32), (4, 34)));
+        assert_eq!(diagnostics[0]["severity"], json!(1));
+        assert_eq!(diagnostics[0]["source"], json!("roc"));
+        assert!(diagnostics[0]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("TYPE MISMATCH"));
+
+        // Fixing the problem clears the diagnostic.
+        client.notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": uri(), "version": 2 },
+                "contentChanges": [{ "text": DOUBLE }],
+            }),
+        );
+
+        let notification = client.receive();
+
+        assert_eq!(notification["params"]["version"], json!(2));
+        assert_eq!(notification["params"]["diagnostics"], json!([]));
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn syntax_error() {
+        let client = Client::start();
+
+        let notification = open(
+            &client,
+            indoc!(
+                r#"
+                interface Test
+                    exposes [x]
+                    imports []
+
+                x = (1
+                "#
+            ),
+        );
+
+        let diagnostics = notification["params"]["diagnostics"].as_array().unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["severity"], json!(1));
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn hover() {
+        let mut client = Client::start();
+
+        open(&client, DOUBLE);
+
+        // The `n` in the body of `double`
+        let result = client.request("textDocument/hover", position(4, 15));
+
+        assert_eq!(
+            result,
+            json!({
+                "contents": { "kind": "markdown", "value": "```roc\nNum *\n```" },
+                "range": range((4, 15), (4, 16)),
+            })
+        );
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn definition() {
+        let mut client = Client::start();
+
+        open(&client, DOUBLE);
+
+        let result = client.request("textDocument/definition", position(4, 15));
+
+        assert_eq!(
+            result,
+            json!({ "uri": uri(), "range": range((4, 10), (4, 11)) })
+        );
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn formatting() {
+        let mut client = Client::start();
+
+        open(&client, DOUBLE);
+
+        let result = client.request(
+            "textDocument/formatting",
+            json!({
+                "textDocument": { "uri": uri() },
+                "options": { "tabSize": 4, "insertSpaces": true },
+            }),
+        );
+
+        assert_eq!(
+            result,
+            json!([{
+                "range": range((0, 0), (5, 0)),
+                "newText": DOUBLE.replace("n  *  2", "n * 2"),
+            }])
+        );
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn range_formatting() {
+        let mut client = Client::start();
+
+        let src = indoc!(
+            r#"
+            interface Test
+                exposes [double, triple]
+                imports []
+
+            double = \n -> n  *  2
+
+            triple = \n -> n  *  3
+            "#
+        );
+
+        open(&client, src);
+
+        let result = client.request(
+            "textDocument/rangeFormatting",
+            json!({
+                "textDocument": { "uri": uri() },
+                "range": range((6, 16), (6, 16)),
+                "options": { "tabSize": 4, "insertSpaces": true },
+            }),
+        );
+
+        // Only `triple` is formatted
+        assert_eq!(
+            result,
+            json!([{
+                "range": range((6, 0), (6, 22)),
+                "newText": "triple = \\n -> n * 3",
+            }])
+        );
+
+        client.shutdown();
+    }
+
+    #[test]
+    fn semantic_tokens() {
+        let mut client = Client::start();
+
+        open(&client, "x = \"π\" # π\ny = 1\n");
+
+        let result = client.request(
+            "textDocument/semanticTokens/full",
+            json!({ "textDocument": { "uri": uri() } }),
+        );
+
+        // `x`, `=`, the string, then on the next line `y`, `=`, `1`
+        assert_eq!(
+            result["data"],
+            json!([
+                0, 0, 1, 1, 0, //
+                0, 2, 1, 5, 0, //
+                0, 2, 3, 4, 0, //
+                1, 0, 1, 1, 0, //
+                0, 2, 1, 5, 0, //
+                0, 2, 1, 3, 0, //
+            ])
+        );
+
+        client.shutdown();
+    }
+}
@@ -18,9 +18,10 @@ use roc_gen_llvm::{run_jit_function, run_jit_function_dynamic_type};
 use roc_load::{EntryPoint, MonomorphizedModule};
 use roc_mono::ir::OptLevel;
 use roc_parse::ast::Expr;
-use roc_parse::parser::{EExpr, ELambda, SyntaxError};
+use roc_parse::parser::{EExpr, ELambda};
 use roc_repl_eval::eval::jit_to_ast;
-use roc_repl_eval::gen::{compile_to_mono, format_answer, ReplOutput};
+use roc_repl_eval::gen::{format_answer, ReplOutput};
+use roc_repl_eval::session::{ReplAction, ReplSession, HELP};
 use roc_repl_eval::{ReplApp, ReplAppMemory};
 use roc_reporting::report::DEFAULT_PALETTE;
 use roc_std::RocStr;
@@ -104,9 +105,13 @@ impl Validator for InputValidator {
     fn validate(&self, ctx: &mut ValidationContext) -> rustyline::Result<ValidationResult> {
         if ctx.input().is_empty() {
             Ok(ValidationResult::Incomplete)
+        } else if ctx.input().ends_with('\n') {
+            // A blank line ends the input, e.g. definitions to keep for later inputs
+            Ok(ValidationResult::Valid(None))
         } else {
             let arena = bumpalo::Bump::new();
-            let state = roc_parse::state::State::new(ctx.input().trim().as_bytes());
+            let input = ctx.input().trim();
+            let state = roc_parse::state::State::new(input.as_bytes());
 
             match roc_parse::expr::parse_loc_expr(0, &arena, state) {
                 // Special case some syntax errors to allow for multi-line inputs
@@ -115,6 +120,10 @@ impl Validator for InputValidator {
                 | Err((_, EExpr::Lambda(ELambda::Body(_, _), _), _)) => {
                     Ok(ValidationResult::Incomplete)
                 }
+                // The input stops where an expression should start, e.g. `when x is`
+                Err((_, EExpr::Start(pos), _)) if pos.offset as usize == input.len() => {
+                    Ok(ValidationResult::Incomplete)
+                }
                 _ => Ok(ValidationResult::Valid(None)),
             }
         }
@@ -235,6 +244,7 @@ pub fn mono_module_to_dylib<'a>(
         builder: &builder,
         dibuilder: &dibuilder,
         compile_unit: &compile_unit,
+        debug_sources: None,
         context: &context,
         interns,
         module,
@@ -295,20 +305,13 @@ pub fn mono_module_to_dylib<'a>(
 }
 
 fn gen_and_eval_llvm<'a>(
-    src: &str,
+    arena: &'a Bump,
+    mut loaded: MonomorphizedModule<'a>,
     target: Triple,
     opt_level: OptLevel,
-) -> Result<ReplOutput, SyntaxError<'a>> {
-    let arena = Bump::new();
+) -> ReplOutput {
     let target_info = TargetInfo::from(&target);
 
-    let mut loaded = match compile_to_mono(&arena, src, target_info, DEFAULT_PALETTE) {
-        Ok(x) => x,
-        Err(prob_strings) => {
-            return Ok(ReplOutput::Problems(prob_strings));
-        }
-    };
-
     debug_assert_eq!(loaded.exposed_to_host.values.len(), 1);
     let (main_fn_symbol, main_fn_var) = loaded.exposed_to_host.values.iter().next().unwrap();
     let main_fn_symbol = *main_fn_symbol;
@@ -327,22 +330,22 @@ fn gen_and_eval_llvm<'a>(
     let (_, main_fn_layout) = match loaded.procedures.keys().find(|(s, _)| *s == main_fn_symbol) {
         Some(layout) => *layout,
         None => {
-            return Ok(ReplOutput::NoProblems {
+            return ReplOutput::NoProblems {
                 expr: "<function>".to_string(),
                 expr_type: expr_type_str,
-            });
+            };
         }
     };
 
     let interns = loaded.interns.clone();
 
     let (lib, main_fn_name, subs) =
-        mono_module_to_dylib(&arena, target, loaded, opt_level).expect("we produce a valid Dylib");
+        mono_module_to_dylib(arena, target, loaded, opt_level).expect("we produce a valid Dylib");
 
     let mut app = CliApp { lib };
 
     let res_answer = jit_to_ast(
-        &arena,
+        arena,
         &mut app,
         main_fn_name,
         main_fn_layout,
@@ -352,23 +355,27 @@ fn gen_and_eval_llvm<'a>(
         target_info,
     );
 
-    let formatted = format_answer(&arena, res_answer, expr_type_str);
-    Ok(formatted)
+    format_answer(arena, res_answer, expr_type_str)
 }
 
-fn eval_and_format<'a>(src: &str) -> Result<String, SyntaxError<'a>> {
-    let format_output = |output| match output {
+fn eval_and_format(session: &mut ReplSession, src: &str) -> String {
+    let arena = Bump::new();
+    let target = Triple::host();
+    let target_info = TargetInfo::from(&target);
+
+    let output = match session.step(&arena, src, target_info, &DEFAULT_PALETTE) {
+        ReplAction::Eval(loaded) => gen_and_eval_llvm(&arena, *loaded, target, OptLevel::Normal),
+        ReplAction::Problems(lines) => ReplOutput::Problems(lines),
+        ReplAction::Print(text) if text.is_empty() => return text,
+        ReplAction::Print(text) => return format!("\n{}", text),
+    };
+
+    match output {
         ReplOutput::NoProblems { expr, expr_type } => {
             format!("\n{} {}:{} {}", expr, PINK, END_COL, expr_type)
         }
         ReplOutput::Problems(lines) => format!("\n{}\n", lines.join("\n\n")),
-    };
-
-    gen_and_eval_llvm(src, Triple::host(), OptLevel::Normal).map(format_output)
-}
-
-fn report_parse_error(fail: SyntaxError) {
-    println!("TODO Gracefully report parse error in repl: {:?}", fail);
+    }
 }
 
 pub fn main() -> io::Result<()> {
@@ -381,6 +388,7 @@ pub fn main() -> io::Result<()> {
     print!("{}{}", WELCOME_MESSAGE, INSTRUCTIONS);
 
     let mut prev_line_blank = false;
+    let mut session = ReplSession::default();
     let mut editor = Editor::<ReplHelper>::new();
     let repl_helper = ReplHelper::new();
     editor.set_helper(Some(repl_helper));
@@ -405,14 +413,7 @@ pub fn main() -> io::Result<()> {
                         } else if prev_line_blank {
                             // After two blank lines in a row, give up and try parsing it
                             // even though it's going to fail. This way you don't get stuck.
-                            match eval_and_format(pending_src.as_str()) {
-                                Ok(output) => {
-                                    println!("{}", output);
-                                }
-                                Err(fail) => {
-                                    report_parse_error(fail);
-                                }
-                            }
+                            println!("{}", eval_and_format(&mut session, pending_src.as_str()));
 
                             pending_src.clear();
                         } else {
@@ -423,7 +424,7 @@ pub fn main() -> io::Result<()> {
                         }
                     }
                     ":help" => {
-                        println!("Use :exit or :q to exit.");
+                        println!("\n{}\n  :exit, :q               exit the repl", HELP);
                     }
                     ":exit" => {
                         break;
@@ -432,29 +433,19 @@ pub fn main() -> io::Result<()> {
                         break;
                     }
                     _ => {
-                        let result = if pending_src.is_empty() {
-                            eval_and_format(trim_line)
+                        let output = if pending_src.is_empty() {
+                            eval_and_format(&mut session, trim_line)
                         } else {
                             pending_src.push('\n');
                             pending_src.push_str(trim_line);
 
-                            eval_and_format(pending_src.as_str())
+                            eval_and_format(&mut session, pending_src.as_str())
                         };
 
-                        match result {
-                            Ok(output) => {
-                                println!("{}", output);
-                                pending_src.clear();
-                            }
-                            //                            Err(Fail {
-                            //                                reason: FailReason::Eof(_),
-                            //                                ..
-                            //                            }) => {}
-                            Err(fail) => {
-                                report_parse_error(fail);
-                                pending_src.clear();
-                            }
+                        if !output.is_empty() {
+                            println!("{}", output);
                         }
+                        pending_src.clear();
                     }
                 }
             }
@@ -1,6 +1,9 @@
 use bumpalo::Bump;
-use roc_load::{ExecutionMode, LoadConfig, Threading};
+use roc_collections::all::MutMap;
+use roc_load::{ExecutionMode, LoadConfig, LoadedModule, Threading};
+use roc_module::symbol::{Interns, ModuleId};
 use roc_reporting::report::Palette;
+use roc_solve_problem::TypeError;
 use std::path::PathBuf;
 
 use roc_fmt::annotation::Formattable;
@@ -44,14 +47,12 @@ pub fn format_answer(
 
 pub fn compile_to_mono<'a>(
     arena: &'a Bump,
-    src: &str,
+    module_src: &'a str,
+    src_dir: PathBuf,
     target_info: TargetInfo,
-    palette: Palette,
+    palette: &Palette,
 ) -> Result<MonomorphizedModule<'a>, Vec<String>> {
     let filename = PathBuf::from("");
-    let src_dir = PathBuf::from("fake/test/path");
-
-    let module_src = arena.alloc(promote_expr_to_module(src));
 
     let exposed_types = Default::default();
     let loaded = roc_load::load_and_monomorphize_from_str(
@@ -65,6 +66,7 @@ pub fn compile_to_mono<'a>(
             render: roc_reporting::report::RenderTarget::ColorTerminal,
             threading: Threading::Single,
             exec_mode: ExecutionMode::Executable,
+            cache_dir: None,
         },
     );
 
@@ -79,6 +81,62 @@ pub fn compile_to_mono<'a>(
     };
 
     let MonomorphizedModule {
+        module_id,
+        interns,
+        sources,
+        can_problems,
+        type_problems,
+        ..
+    } = &mut loaded;
+
+    let lines = report_problems(
+        *module_id,
+        sources,
+        interns,
+        can_problems,
+        type_problems,
+        palette,
+    );
+
+    if !lines.is_empty() {
+        Err(lines)
+    } else {
+        Ok(loaded)
+    }
+}
+
+/// Like `compile_to_mono`, but stops after type checking, for when we only need types.
+pub fn type_check<'a>(
+    arena: &'a Bump,
+    module_src: &'a str,
+    src_dir: PathBuf,
+    target_info: TargetInfo,
+    palette: &Palette,
+) -> Result<LoadedModule, Vec<String>> {
+    let filename = PathBuf::from("");
+
+    let loaded = roc_load::load_and_typecheck_str(
+        arena,
+        filename,
+        module_src,
+        src_dir,
+        Default::default(),
+        target_info,
+        roc_reporting::report::RenderTarget::ColorTerminal,
+    );
+
+    let mut loaded = match loaded {
+        Ok(v) => v,
+        Err(LoadingProblem::FormattedReport(report)) => {
+            return Err(vec![report]);
+        }
+        Err(e) => {
+            panic!("error while loading module: {:?}", e)
+        }
+    };
+
+    let LoadedModule {
+        module_id,
         interns,
         sources,
         can_problems,
@@ -86,19 +144,54 @@ pub fn compile_to_mono<'a>(
         ..
     } = &mut loaded;
 
+    let lines = report_problems(
+        *module_id,
+        sources,
+        interns,
+        can_problems,
+        type_problems,
+        palette,
+    );
+
+    if !lines.is_empty() {
+        Err(lines)
+    } else {
+        Ok(loaded)
+    }
+}
+
+fn report_problems(
+    app_module_id: ModuleId,
+    sources: &MutMap<ModuleId, (PathBuf, Box<str>)>,
+    interns: &Interns,
+    can_problems: &mut MutMap<ModuleId, Vec<roc_problem::can::Problem>>,
+    type_problems: &mut MutMap<ModuleId, Vec<TypeError>>,
+    palette: &Palette,
+) -> Vec<String> {
     let mut lines = Vec::new();
 
     for (home, (module_path, src)) in sources.iter() {
-        let can_probs = can_problems.remove(home).unwrap_or_default();
+        let mut can_probs = can_problems.remove(home).unwrap_or_default();
         let type_probs = type_problems.remove(home).unwrap_or_default();
 
+        if *home == app_module_id {
+            // The definitions and imports of a REPL session need not all be used by every input
+            can_probs.retain(|problem| {
+                !matches!(
+                    problem,
+                    roc_problem::can::Problem::UnusedDef(..)
+                        | roc_problem::can::Problem::UnusedImport(..)
+                )
+            });
+        }
+
         let error_count = can_probs.len() + type_probs.len();
 
         if error_count == 0 {
             continue;
         }
 
-        let line_info = LineInfo::new(module_src);
+        let line_info = LineInfo::new(src);
         let src_lines: Vec<&str> = src.split('\n').collect();
 
         // Report parsing and canonicalization problems
@@ -108,7 +201,7 @@ pub fn compile_to_mono<'a>(
             let report = can_problem(&alloc, &line_info, module_path.clone(), problem);
             let mut buf = String::new();
 
-            report.render_color_terminal(&mut buf, &alloc, &palette);
+            report.render_color_terminal(&mut buf, &alloc, palette);
 
             lines.push(buf);
         }
@@ -117,30 +210,12 @@ pub fn compile_to_mono<'a>(
             if let Some(report) = type_problem(&alloc, &line_info, module_path.clone(), problem) {
                 let mut buf = String::new();
 
-                report.render_color_terminal(&mut buf, &alloc, &palette);
+                report.render_color_terminal(&mut buf, &alloc, palette);
 
                 lines.push(buf);
             }
         }
     }
 
-    if !lines.is_empty() {
-        Err(lines)
-    } else {
-        Ok(loaded)
-    }
-}
-
-fn promote_expr_to_module(src: &str) -> String {
-    let mut buffer =
-        String::from("app \"app\" provides [replOutput] to \"./platform\"\n\nreplOutput =\n");
-
-    for line in src.lines() {
-        // indent the body!
-        buffer.push_str("    ");
-        buffer.push_str(line);
-        buffer.push('\n');
-    }
-
-    buffer
+    lines
 }
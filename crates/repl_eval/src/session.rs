@@ -0,0 +1,451 @@
+//! A REPL session: the top-level definitions entered so far and the modules imported with
+//! `:load`, which every later input can use. The CLI and web REPLs share this, and only differ
+//! in how they run the compiled code.
+use bumpalo::Bump;
+use roc_load::MonomorphizedModule;
+use roc_parse::ast::{ExtractSpaces, Module, Pattern, TypeDef, ValueDef};
+use roc_parse::expr::parse_loc_expr;
+use roc_parse::module::{module_defs, parse_header};
+use roc_parse::parser::{EExpr, Parser};
+use roc_parse::state::State;
+use roc_reporting::report::Palette;
+use roc_target::TargetInfo;
+use roc_types::pretty_print::{name_and_print_var, DebugPrint};
+use std::path::{Path, PathBuf};
+
+use crate::gen::{compile_to_mono, type_check};
+
+pub const HELP: &str =
+    "Enter an expression to evaluate it, or a definition to use it in later inputs.
+End a definition with a blank line.
+
+  :type <expr>            show the type of an expression, without evaluating it
+  :load path/Module.roc   import the values an interface module exposes
+  :defs                   list the definitions and loaded modules
+  :reset                  forget all definitions and loaded modules";
+
+/// What the REPL should do in response to one input
+pub enum ReplAction<'a> {
+    /// Run `replOutput` and show its value and type
+    Eval(Box<MonomorphizedModule<'a>>),
+    /// Show this text
+    Print(String),
+    /// Show these problems with the input
+    Problems(Vec<String>),
+}
+
+#[derive(Debug, Default)]
+pub struct ReplSession {
+    /// The top-level definitions entered so far, in order
+    past_defs: Vec<PastDef>,
+    /// The modules imported with `:load`, in order
+    imported_modules: Vec<ImportedModule>,
+    /// The directory of the loaded modules, where the compiler also looks for their imports
+    src_dir: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+struct PastDef {
+    /// The names this definition introduces, which later definitions of them replace
+    names: Vec<String>,
+    src: String,
+}
+
+#[derive(Debug, Clone)]
+struct ImportedModule {
+    name: String,
+    exposes: Vec<String>,
+}
+
+impl ImportedModule {
+    /// The entry for this module in the `imports` of an app header
+    fn import(&self) -> String {
+        format!("{}.{{ {} }}", self.name, self.exposes.join(", "))
+    }
+}
+
+impl ReplSession {
+    /// Handle one complete input: a command, definitions or an expression.
+    pub fn step<'a>(
+        &mut self,
+        arena: &'a Bump,
+        input: &str,
+        target_info: TargetInfo,
+        palette: &Palette,
+    ) -> ReplAction<'a> {
+        let input = input.trim();
+
+        if let Some(command) = input.strip_prefix(':') {
+            let (name, arg) = match command.split_once(char::is_whitespace) {
+                Some((name, arg)) => (name, arg.trim()),
+                None => (command, ""),
+            };
+
+            return match name {
+                "help" => ReplAction::Print(HELP.to_string()),
+                "type" if arg.is_empty() => ReplAction::Print(
+                    "Use :type <expr> to see the type of an expression.".to_string(),
+                ),
+                "type" => self.type_of(arena, arg, target_info, palette),
+                "load" if arg.is_empty() => ReplAction::Print(
+                    "Use :load path/Module.roc to import the values a module exposes.".to_string(),
+                ),
+                "load" => self.load(arena, Path::new(arg), target_info, palette),
+                "defs" => ReplAction::Print(self.describe()),
+                "reset" => {
+                    *self = Self::default();
+
+                    ReplAction::Print("Forgot all definitions and loaded modules.".to_string())
+                }
+                _ => ReplAction::Print(format!(
+                    "There is no :{} command. Use :help to see the commands.",
+                    name
+                )),
+            };
+        }
+
+        match parse_defs(arena, input) {
+            Some((names, shown)) => self.define(arena, input, names, shown, target_info, palette),
+            None => {
+                let module_src = module_src(&self.imported_modules, &self.past_defs(None), input);
+
+                match compile_to_mono(
+                    arena,
+                    arena.alloc_str(&module_src),
+                    self.src_dir(),
+                    target_info,
+                    palette,
+                ) {
+                    Ok(mono) => ReplAction::Eval(Box::new(mono)),
+                    Err(problems) => ReplAction::Problems(problems),
+                }
+            }
+        }
+    }
+
+    /// Add definitions to the session, if they compile, and show the value of the last one.
+    fn define<'a>(
+        &mut self,
+        arena: &'a Bump,
+        src: &str,
+        names: Vec<String>,
+        shown: Option<String>,
+        target_info: TargetInfo,
+        palette: &Palette,
+    ) -> ReplAction<'a> {
+        let mut defs = self.past_defs(Some(&names));
+        defs.push(src);
+
+        let action = match &shown {
+            Some(name) => {
+                let module_src = module_src(&self.imported_modules, &defs, name);
+
+                match compile_to_mono(
+                    arena,
+                    arena.alloc_str(&module_src),
+                    self.src_dir(),
+                    target_info,
+                    palette,
+                ) {
+                    Ok(mono) => ReplAction::Eval(Box::new(mono)),
+                    Err(problems) => return ReplAction::Problems(problems),
+                }
+            }
+            None => {
+                // only types or annotations, so there is no value to show
+                let module_src = module_src(&self.imported_modules, &defs, "{}");
+
+                match type_check(
+                    arena,
+                    arena.alloc_str(&module_src),
+                    self.src_dir(),
+                    target_info,
+                    palette,
+                ) {
+                    Ok(_) => ReplAction::Print(String::new()),
+                    Err(problems) => return ReplAction::Problems(problems),
+                }
+            }
+        };
+
+        // top-level names can't be shadowed, so a new definition replaces the old one
+        self.past_defs
+            .retain(|def| !def.names.iter().any(|name| names.contains(name)));
+        self.past_defs.push(PastDef {
+            names,
+            src: src.to_string(),
+        });
+
+        action
+    }
+
+    fn type_of<'a>(
+        &self,
+        arena: &'a Bump,
+        expr: &str,
+        target_info: TargetInfo,
+        palette: &Palette,
+    ) -> ReplAction<'a> {
+        let module_src = module_src(&self.imported_modules, &self.past_defs(None), expr);
+
+        let mut loaded = match type_check(
+            arena,
+            arena.alloc_str(&module_src),
+            self.src_dir(),
+            target_info,
+            palette,
+        ) {
+            Ok(loaded) => loaded,
+            Err(problems) => return ReplAction::Problems(problems),
+        };
+
+        debug_assert_eq!(loaded.exposed_to_host.len(), 1);
+        let var = *loaded.exposed_to_host.values().next().unwrap();
+
+        ReplAction::Print(name_and_print_var(
+            var,
+            loaded.solved.inner_mut(),
+            loaded.module_id,
+            &loaded.interns,
+            DebugPrint::NOTHING,
+        ))
+    }
+
+    /// Import everything an interface module exposes, if the session still compiles with it.
+    fn load<'a>(
+        &mut self,
+        arena: &'a Bump,
+        path: &Path,
+        target_info: TargetInfo,
+        palette: &Palette,
+    ) -> ReplAction<'a> {
+        let src = match std::fs::read_to_string(path) {
+            Ok(src) => arena.alloc_str(&src),
+            Err(error) => {
+                return ReplAction::Problems(vec![format!(
+                    "I could not read {}: {}",
+                    path.display(),
+                    error
+                )])
+            }
+        };
+
+        let imported = match parse_header(arena, State::new(src.as_bytes())) {
+            Ok((Module::Interface { header }, _)) => ImportedModule {
+                name: header.name.value.as_str().to_string(),
+                exposes: header
+                    .exposes
+                    .iter()
+                    .map(|exposed| exposed.value.extract_spaces().item.as_str().to_string())
+                    .collect(),
+            },
+            Ok(_) => {
+                return ReplAction::Problems(vec![format!(
+                    "{} is not an interface module, so there is nothing to import from it.",
+                    path.display()
+                )])
+            }
+            Err(_) => {
+                return ReplAction::Problems(vec![format!(
+                    "I could not parse the header of {}.",
+                    path.display()
+                )])
+            }
+        };
+
+        let dir = match path.parent() {
+            Some(dir) if dir != Path::new("") => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        if let Some(src_dir) = &self.src_dir {
+            if *src_dir != dir {
+                return ReplAction::Problems(vec![format!(
+                    "The modules you load must all be in the same directory, and {} is not in {}.",
+                    path.display(),
+                    src_dir.display()
+                )]);
+            }
+        }
+
+        // loading a module again replaces it, e.g. to pick up changes to it
+        let mut imported_modules: Vec<ImportedModule> = self
+            .imported_modules
+            .iter()
+            .filter(|module| module.name != imported.name)
+            .cloned()
+            .collect();
+        imported_modules.push(imported.clone());
+
+        let module_src = module_src(&imported_modules, &self.past_defs(None), "{}");
+
+        match type_check(
+            arena,
+            arena.alloc_str(&module_src),
+            dir.clone(),
+            target_info,
+            palette,
+        ) {
+            Ok(_) => {
+                self.imported_modules = imported_modules;
+                self.src_dir = Some(dir);
+
+                ReplAction::Print(format!("Loaded {}", imported.import()))
+            }
+            Err(problems) => ReplAction::Problems(problems),
+        }
+    }
+
+    /// The definitions and loaded modules, as `:defs` shows them
+    fn describe(&self) -> String {
+        if self.past_defs.is_empty() && self.imported_modules.is_empty() {
+            return "Nothing is defined yet.".to_string();
+        }
+
+        let mut buf = String::new();
+
+        for module in &self.imported_modules {
+            buf.push_str(&module.import());
+            buf.push('\n');
+        }
+
+        if !self.imported_modules.is_empty() && !self.past_defs.is_empty() {
+            buf.push('\n');
+        }
+
+        for def in &self.past_defs {
+            buf.push_str(&def.src);
+            buf.push('\n');
+        }
+
+        buf.pop();
+
+        buf
+    }
+
+    /// The sources of the past definitions, leaving out those of the given names
+    fn past_defs(&self, replaced: Option<&[String]>) -> Vec<&str> {
+        self.past_defs
+            .iter()
+            .filter(|def| match replaced {
+                Some(names) => !def.names.iter().any(|name| names.contains(name)),
+                None => true,
+            })
+            .map(|def| def.src.as_str())
+            .collect()
+    }
+
+    fn src_dir(&self) -> PathBuf {
+        self.src_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("fake/test/path"))
+    }
+}
+
+/// An app module with the given imports and definitions, providing the value of `expr` as
+/// `replOutput`
+fn module_src(imported_modules: &[ImportedModule], defs: &[&str], expr: &str) -> String {
+    let mut buffer = String::from("app \"app\" ");
+
+    if !imported_modules.is_empty() {
+        let imports: Vec<String> = imported_modules
+            .iter()
+            .map(ImportedModule::import)
+            .collect();
+
+        buffer.push_str(&format!("imports [{}] ", imports.join(", ")));
+    }
+
+    buffer.push_str("provides [replOutput] to \"./platform\"\n\n");
+
+    for def in defs {
+        buffer.push_str(def);
+        buffer.push('\n');
+    }
+
+    if !defs.is_empty() {
+        buffer.push('\n');
+    }
+
+    buffer.push_str("replOutput =\n");
+
+    for line in expr.lines() {
+        // indent the body!
+        buffer.push_str("    ");
+        buffer.push_str(line);
+        buffer.push('\n');
+    }
+
+    buffer
+}
+
+/// If the input is only definitions, without an expression at the end, returns the names they
+/// introduce and the last value they define.
+fn parse_defs(arena: &Bump, input: &str) -> Option<(Vec<String>, Option<String>)> {
+    match parse_loc_expr(0, arena, State::new(input.as_bytes())) {
+        Err((_, EExpr::DefMissingFinalExpr(_), _))
+        | Err((_, EExpr::DefMissingFinalExpr2(_, _), _)) => {}
+        _ => return None,
+    }
+
+    let (_, defs, _) = module_defs()
+        .parse(arena, State::new(input.as_bytes()))
+        .ok()?;
+
+    let mut names = Vec::new();
+    let mut shown = None;
+
+    for def in defs.defs() {
+        match def {
+            Ok(TypeDef::Alias { header, .. }) | Ok(TypeDef::Opaque { header, .. }) => {
+                names.push(header.name.value.to_string());
+            }
+            Ok(TypeDef::Ability {
+                header, members, ..
+            }) => {
+                names.push(header.name.value.to_string());
+                names.extend(
+                    members
+                        .iter()
+                        .map(|member| member.name.value.extract_spaces().item.to_string()),
+                );
+            }
+            Err(ValueDef::Annotation(pattern, _)) => {
+                pattern_names(&pattern.value, &mut names);
+            }
+            Err(ValueDef::Body(pattern, _))
+            | Err(ValueDef::AnnotatedBody {
+                body_pattern: pattern,
+                ..
+            }) => {
+                let first = names.len();
+                pattern_names(&pattern.value, &mut names);
+                shown = names[first..].last().cloned().or(shown);
+            }
+            Err(ValueDef::Expect { .. }) | Err(ValueDef::ExpectFx { .. }) => {}
+        }
+    }
+
+    Some((names, shown))
+}
+
+fn pattern_names(pattern: &Pattern, names: &mut Vec<String>) {
+    match pattern {
+        Pattern::Identifier(name) | Pattern::OptionalField(name, _) => names.push(name.to_string()),
+        Pattern::Apply(_, arguments) => {
+            for argument in arguments.iter() {
+                pattern_names(&argument.value, names);
+            }
+        }
+        Pattern::RecordDestructure(fields) => {
+            for field in fields.iter() {
+                pattern_names(&field.value, names);
+            }
+        }
+        Pattern::RequiredField(_, pattern) => pattern_names(&pattern.value, names),
+        Pattern::SpaceBefore(pattern, _) | Pattern::SpaceAfter(pattern, _) => {
+            pattern_names(pattern, names)
+        }
+        _ => {}
+    }
+}
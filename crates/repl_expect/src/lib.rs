@@ -80,7 +80,7 @@ mod test {
     use indoc::indoc;
     use pretty_assertions::assert_eq;
     use roc_gen_llvm::{llvm::build::LlvmBackendMode, run_roc::RocCallResult, run_roc_dylib};
-    use roc_load::{ExecutionMode, LoadConfig, Threading};
+    use roc_load::{ExecutionMode, LoadConfig, TestConfig, Threading};
     use roc_reporting::report::RenderTarget;
     use target_lexicon::Triple;
 
@@ -108,7 +108,8 @@ mod test {
             target_info,
             render: RenderTarget::ColorTerminal,
             threading: Threading::Single,
-            exec_mode: ExecutionMode::Test,
+            exec_mode: ExecutionMode::Test(TestConfig::default()),
+            cache_dir: None,
         };
         let loaded = roc_load::load_and_monomorphize_from_str(
             arena,
@@ -148,7 +149,7 @@ mod test {
         unsafe { set_shared_buffer((shared_buffer.as_mut_ptr(), BUFFER_SIZE), &mut result) };
 
         let mut writer = Vec::with_capacity(1024);
-        let (_failed, _passed) = crate::run::run_expects(
+        let _results = crate::run::run_expects(
             &mut writer,
             RenderTarget::ColorTerminal,
             arena,
@@ -834,4 +835,48 @@ mod test {
             ),
         );
     }
+
+    #[test]
+    fn property_pass() {
+        run_expect_test(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect \list -> List.len (List.append list 0u8) == List.len list + 1
+                "#
+            ),
+            "",
+        );
+    }
+
+    #[test]
+    fn property_shrinks_counterexample() {
+        run_expect_test(
+            indoc!(
+                r#"
+                app "test" provides [main] to "./platform"
+
+                main = 0
+
+                expect \n -> n < 10u8
+                "#
+            ),
+            indoc!(
+                r#"
+                This expectation failed:
+
+                5│  expect \n -> n < 10u8
+                    ^^^^^^^^^^^^^^^^^^^^^
+
+                When it failed, these variables had these values:
+
+                n : U8
+                n = 10
+                "#
+            ),
+        );
+    }
 }
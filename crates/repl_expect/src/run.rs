@@ -6,12 +6,33 @@ use roc_collections::{MutSet, VecMap};
 use roc_gen_llvm::llvm::{build::LlvmBackendMode, externs::add_default_roc_externs};
 use roc_load::{EntryPoint, Expectations, MonomorphizedModule};
 use roc_module::symbol::{Interns, ModuleId, Symbol};
-use roc_mono::ir::OptLevel;
+use roc_mono::ir::{CoveragePoint, OptLevel};
 use roc_region::all::Region;
 use roc_reporting::{error::expect::Renderer, report::RenderTarget};
 use roc_target::TargetInfo;
+use std::time::{Duration, Instant};
 use target_lexicon::Triple;
 
+/// What happened when a top-level `expect` ran.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpectOutcome {
+    Passed,
+    /// The expectation was false. Contains the rendered failure reports.
+    Failed(String),
+    /// The expectation crashed while running. Contains the rendered report of the crash.
+    Crashed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ExpectResult {
+    pub symbol: Symbol,
+    pub region: Region,
+    pub duration: Duration,
+    pub outcome: ExpectOutcome,
+}
+
+/// Runs the given top-level expects in order. The reports of failures are written to `writer` as
+/// soon as they happen, and also returned as part of the result of each expect.
 #[allow(clippy::too_many_arguments)]
 pub fn run_expects<W: std::io::Write>(
     writer: &mut W,
@@ -22,13 +43,11 @@ pub fn run_expects<W: std::io::Write>(
     expectations: &mut VecMap<ModuleId, Expectations>,
     shared_ptr: *mut u8,
     expects: bumpalo::collections::Vec<'_, ToplevelExpect<'_>>,
-) -> std::io::Result<(usize, usize)> {
-    let mut failed = 0;
-    let mut passed = 0;
+) -> std::io::Result<Vec<ExpectResult>> {
+    let mut results = Vec::with_capacity(expects.len());
 
     for expect in expects {
         let result = run_expect(
-            writer,
             render_target,
             arena,
             interns,
@@ -38,18 +57,17 @@ pub fn run_expects<W: std::io::Write>(
             expect,
         )?;
 
-        match result {
-            true => passed += 1,
-            false => failed += 1,
+        if let ExpectOutcome::Failed(report) | ExpectOutcome::Crashed(report) = &result.outcome {
+            writeln!(writer, "{}", report)?;
         }
+
+        results.push(result);
     }
 
-    Ok((failed, passed))
+    Ok(results)
 }
 
-#[allow(clippy::too_many_arguments)]
-fn run_expect<W: std::io::Write>(
-    writer: &mut W,
+fn run_expect(
     render_target: RenderTarget,
     arena: &Bump,
     interns: &Interns,
@@ -57,16 +75,18 @@ fn run_expect<W: std::io::Write>(
     expectations: &mut VecMap<ModuleId, Expectations>,
     shared_ptr: *mut u8,
     expect: ToplevelExpect<'_>,
-) -> std::io::Result<bool> {
+) -> std::io::Result<ExpectResult> {
     use roc_gen_llvm::try_run_jit_function;
 
     let sequence = ExpectSequence::new(shared_ptr.cast());
 
+    let start = Instant::now();
     let result: Result<(), String> = try_run_jit_function!(lib, expect.name, (), |v: ()| v);
+    let duration = start.elapsed();
 
     let shared_memory_ptr: *const u8 = shared_ptr.cast();
 
-    if result.is_err() || sequence.count_failures() > 0 {
+    let outcome = if result.is_err() || sequence.count_failures() > 0 {
         let module_id = expect.symbol.module_id();
         let data = expectations.get_mut(&module_id).unwrap();
 
@@ -76,14 +96,18 @@ fn run_expect<W: std::io::Write>(
 
         let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
 
+        let mut report = Vec::new();
+
         if let Err(roc_panic_message) = result {
-            renderer.render_panic(writer, &roc_panic_message, expect.region)?;
+            renderer.render_panic(&mut report, &roc_panic_message, expect.region)?;
+
+            ExpectOutcome::Crashed(String::from_utf8_lossy(&report).into_owned())
         } else {
             let mut offset = ExpectSequence::START_OFFSET;
 
             for _ in 0..sequence.count_failures() {
                 offset += render_expect_failure(
-                    writer,
+                    &mut report,
                     &renderer,
                     arena,
                     Some(expect),
@@ -93,18 +117,41 @@ fn run_expect<W: std::io::Write>(
                     offset,
                 )?;
             }
+
+            ExpectOutcome::Failed(String::from_utf8_lossy(&report).into_owned())
         }
+    } else {
+        ExpectOutcome::Passed
+    };
 
-        writeln!(writer)?;
+    Ok(ExpectResult {
+        symbol: expect.symbol,
+        region: expect.region,
+        duration,
+        outcome,
+    })
+}
 
-        Ok(false)
-    } else {
-        Ok(true)
-    }
+/// How often the code at each of the given points ran, according to the counters that the
+/// expects built for `roc test --coverage` increment. A point whose counter was optimized away
+/// never ran.
+pub fn read_coverage_counters(lib: &libloading::Library, points: &[CoveragePoint]) -> Vec<u64> {
+    points
+        .iter()
+        .map(|point| {
+            let name = point.counter_name();
+
+            match unsafe { lib.get::<*const u64>(name.as_bytes()) } {
+                Ok(counter) => unsafe { **counter },
+                Err(_) => 0,
+            }
+        })
+        .collect()
 }
 
 pub fn roc_dev_expect(
     writer: &mut impl std::io::Write,
+    render_target: RenderTarget,
     arena: &Bump,
     expectations: &mut VecMap<ModuleId, Expectations>,
     interns: &Interns,
@@ -117,14 +164,7 @@ pub fn roc_dev_expect(
     let filename = data.path.to_owned();
     let source = std::fs::read_to_string(&data.path).unwrap();
 
-    let renderer = Renderer::new(
-        arena,
-        interns,
-        RenderTarget::ColorTerminal,
-        module_id,
-        filename,
-        &source,
-    );
+    let renderer = Renderer::new(arena, interns, render_target, module_id, filename, &source);
 
     render_expect_failure(
         writer,
@@ -286,6 +326,7 @@ pub fn expect_mono_module_to_dylib<'a>(
         builder: &builder,
         dibuilder: &dibuilder,
         compile_unit: &compile_unit,
+        debug_sources: None,
         context: &context,
         interns,
         module,
@@ -1228,3 +1228,119 @@ fn record_of_poly_function_and_string() {
         r#"{ a: <function>, b: "b" } : { a : * -> Str, b : Str }"#,
     );
 }
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn definitions_persist_between_inputs() {
+    expect_success(
+        indoc!(
+            r#"
+            x = 5
+
+            x + 1
+            "#
+        ),
+        "5 : Num *\n\n6 : Num *",
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn definition_replaces_earlier_one() {
+    expect_success(
+        indoc!(
+            r#"
+            x = 5
+
+            x = "five"
+
+            x
+            "#
+        ),
+        "5 : Num *\n\n\"five\" : Str\n\n\"five\" : Str",
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn list_definitions() {
+    expect_success(
+        indoc!(
+            r#"
+            x = 5
+
+            addX = \n -> n + x
+
+            :defs
+            "#
+        ),
+        "5 : Num *\n\n<function> : Num a -> Num a\n\nx = 5\naddX = \\n -> n + x",
+    );
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn reset_forgets_definitions() {
+    expect_failure(
+        indoc!(
+            r#"
+            x = 5
+
+            :reset
+            x
+            "#
+        ),
+        indoc!(
+            r#"
+            ── UNRECOGNIZED NAME ───────────────────────────────────────────────────────────
+
+            Nothing is named `x` in this scope.
+
+            4│      x
+                    ^
+
+            Did you mean one of these?
+
+                U8
+                Box
+                Ok
+                I8
+            "#
+        ),
+    );
+}
+
+#[test]
+fn type_command() {
+    expect_success(r#":type \n -> n + 1"#, "Num a -> Num a");
+}
+
+#[cfg(not(feature = "wasm"))]
+#[test]
+fn load_module() {
+    let input = format!(
+        "{}\n{}",
+        concat!(
+            ":load ",
+            env!("CARGO_MANIFEST_DIR"),
+            "/fixtures/Greeting.roc"
+        ),
+        r#"greet "Roc""#
+    );
+
+    expect_success(&input, "Loaded Greeting.{ greet }\n\n\"Hello, Roc!\" : Str");
+}
+
+#[test]
+fn decode_error() {
+    expect_success(
+        indoc!(
+            r#"
+                err : Decode.DecodeError
+                err = Unexpected { offset: 3, path: [Field "a", Field "b"], expected: "a value for this field", found: "no value" }
+                err
+                "#
+        ),
+        r#"Unexpected { expected: "a value for this field", found: "no value", offset: 3, path: [Field "a", Field "b"] } : DecodeError"#,
+    );
+}
@@ -1,4 +1,5 @@
 use bumpalo::{collections::vec::Vec, Bump};
+use std::cell::RefCell;
 use std::mem::size_of;
 
 use roc_collections::all::MutSet;
@@ -7,7 +8,8 @@ use roc_load::MonomorphizedModule;
 use roc_parse::ast::Expr;
 use roc_repl_eval::{
     eval::jit_to_ast,
-    gen::{compile_to_mono, format_answer, ReplOutput},
+    gen::{format_answer, ReplOutput},
+    session::{ReplAction, ReplSession},
     ReplApp, ReplAppMemory,
 };
 use roc_reporting::report::DEFAULT_PALETTE_HTML;
@@ -18,6 +20,11 @@ use crate::{js_create_app, js_get_result_and_memory, js_run_app};
 
 const WRAPPER_NAME: &str = "wrapper";
 
+thread_local! {
+    /// The definitions and loaded modules, kept from one input to the next
+    static SESSION: RefCell<ReplSession> = RefCell::new(ReplSession::default());
+}
+
 pub struct WasmReplApp<'a> {
     arena: &'a Bump,
 }
@@ -175,9 +182,15 @@ pub async fn entrypoint_from_js(src: String) -> Result<String, String> {
 
     // Compile the app
     let target_info = TargetInfo::default_wasm32();
-    let mono = match compile_to_mono(arena, &src, target_info, DEFAULT_PALETTE_HTML) {
-        Ok(m) => m,
-        Err(messages) => return Err(messages.join("\n\n")),
+    let action = SESSION.with(|session| {
+        session
+            .borrow_mut()
+            .step(arena, &src, target_info, &DEFAULT_PALETTE_HTML)
+    });
+    let mono = match action {
+        ReplAction::Eval(m) => *m,
+        ReplAction::Print(text) => return Ok(text),
+        ReplAction::Problems(messages) => return Err(messages.join("\n\n")),
     };
 
     let MonomorphizedModule {
@@ -214,6 +227,7 @@ pub async fn entrypoint_from_js(src: String) -> Result<String, String> {
             arena,
             module_id,
             stack_bytes: roc_gen_wasm::Env::DEFAULT_STACK_BYTES,
+            debug_sources: None,
             exposed_to_host: exposed_to_host
                 .values
                 .keys()
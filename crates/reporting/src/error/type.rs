@@ -376,6 +376,17 @@ fn underivable_hint<'b>(
                 alloc.symbol_qualified(ability),
             ])),
         ]))),
+        ErrorType::Alias(
+            Symbol::NUM_FLOATINGPOINT | Symbol::NUM_F32 | Symbol::NUM_F64 | Symbol::NUM_DEC,
+            ..,
+        ) if ability == Symbol::HASH_HASH_ABILITY => Some(alloc.note("").append(alloc.concat([
+            alloc.symbol_unqualified(ability),
+            alloc.reflow(" cannot be generated for fractional numbers like "),
+            alloc.symbol_unqualified(Symbol::NUM_F64),
+            alloc.reflow(" or "),
+            alloc.symbol_unqualified(Symbol::NUM_DEC),
+            alloc.reflow("."),
+        ]))),
         ErrorType::Alias(symbol, _, _, AliasKind::Opaque) => {
             Some(alloc.tip().append(alloc.concat([
                 alloc.symbol_unqualified(*symbol),
@@ -115,6 +115,8 @@ pub enum Severity {
 pub enum RenderTarget {
     ColorTerminal,
     Generic,
+    /// One JSON object per report, on a line of its own, for tools to consume.
+    Json,
 }
 
 /// A textual report.
@@ -136,6 +138,7 @@ impl<'b> Report<'b> {
         match target {
             RenderTarget::Generic => self.render_ci(buf, alloc),
             RenderTarget::ColorTerminal => self.render_color_terminal(buf, alloc, palette),
+            RenderTarget::Json => self.render_json(buf),
         }
     }
 
@@ -164,6 +167,51 @@ impl<'b> Report<'b> {
             .expect(err_msg);
     }
 
+    /// Render as a single line of JSON, followed by a newline.
+    ///
+    /// The object has the report's `severity`, `title` and `file`, the `message` as it would be
+    /// rendered for CI (without the header), and the source regions the message points at: the
+    /// first one is the `region`, any others are `related`. Lines and columns are 1-based, and the
+    /// end of a region is just past its last character.
+    pub fn render_json(self, buf: &mut String) {
+        let severity = self.severity;
+        let title = self.title.clone();
+        let filename = self.filename.clone();
+
+        let mut message = String::new();
+        let mut regions = self
+            .render_message(&mut message)
+            .into_iter()
+            .map(region_to_json);
+
+        let json = serde_json::json!({
+            "severity": match severity {
+                Severity::RuntimeError => "error",
+                Severity::Warning => "warning",
+            },
+            "title": title,
+            "file": filename.to_string_lossy(),
+            "region": regions.next(),
+            "message": message.trim_end(),
+            "related": regions.collect::<Vec<_>>(),
+        });
+
+        buf.push_str(&json.to_string());
+        buf.push('\n');
+    }
+
+    /// Render the message as for CI, but without the header, and return the source regions it
+    /// points at in the order they appear. The first one is where the problem is.
+    pub fn render_message(self, buf: &mut String) -> Vec<LineColumnRegion> {
+        let err_msg = "<buffer is not a utf-8 encoded string>";
+
+        let mut write = RegionWrite::new(buf);
+
+        self.doc.1.render_raw(70, &mut write).expect(err_msg);
+
+        write.regions
+    }
+
     pub fn pretty(self, alloc: &'b RocDocAllocator<'b>) -> RocDocBuilder<'b> {
         if self.title.is_empty() {
             self.doc
@@ -183,6 +231,13 @@ impl<'b> Report<'b> {
     }
 }
 
+fn region_to_json(region: LineColumnRegion) -> serde_json::Value {
+    serde_json::json!({
+        "start": { "line": region.start.line + 1, "column": region.start.column + 1 },
+        "end": { "line": region.end.line + 1, "column": region.end.column + 1 },
+    })
+}
+
 /// This struct is a combination of several things
 /// 1. A set of StyleCodes suitable for the platform we're running on (web or terminal)
 /// 2. A set of colors we decided to use
@@ -621,7 +676,13 @@ impl<'a> RocDocAllocator<'a> {
             result = result.append(highlight_line);
         }
 
-        result.annotate(Annotation::CodeBlock)
+        if sub_region1 != sub_region2 {
+            result = result.annotate(Annotation::SourceRegion(sub_region2));
+        }
+
+        result
+            .annotate(Annotation::SourceRegion(sub_region1))
+            .annotate(Annotation::CodeBlock)
     }
 
     pub fn region_with_subregion(
@@ -714,7 +775,7 @@ impl<'a> RocDocAllocator<'a> {
             result = result.append(highlight_line);
         }
 
-        result
+        result.annotate(Annotation::SourceRegion(sub_region))
     }
 
     pub fn region(&'a self, region: LineColumnRegion) -> DocBuilder<'a, Self, Annotation> {
@@ -821,6 +882,8 @@ pub enum Annotation {
     Tip,
     Header,
     ParserSuggestion,
+    /// The part of the source a snippet points at; only rendered by `RenderTarget::Json`.
+    SourceRegion(LineColumnRegion),
 }
 
 /// Render with minimal formatting
@@ -859,6 +922,21 @@ impl<'a, W> ColorWrite<'a, W> {
     }
 }
 
+/// Render with minimal formatting, collecting the source regions the report points at
+struct RegionWrite<W> {
+    ci: CiWrite<W>,
+    regions: Vec<LineColumnRegion>,
+}
+
+impl<W> RegionWrite<W> {
+    fn new(upstream: W) -> RegionWrite<W> {
+        RegionWrite {
+            ci: CiWrite::new(upstream),
+            regions: vec![],
+        }
+    }
+}
+
 impl<W> Render for CiWrite<W>
 where
     W: fmt::Write,
@@ -946,6 +1024,40 @@ where
     }
 }
 
+impl<W> Render for RegionWrite<W>
+where
+    W: fmt::Write,
+{
+    type Error = fmt::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<usize, fmt::Error> {
+        self.ci.write_str(s)
+    }
+
+    fn write_str_all(&mut self, s: &str) -> fmt::Result {
+        self.ci.write_str_all(s)
+    }
+}
+
+impl<W> RenderAnnotated<Annotation> for RegionWrite<W>
+where
+    W: fmt::Write,
+{
+    fn push_annotation(&mut self, annotation: &Annotation) -> Result<(), Self::Error> {
+        if let Annotation::SourceRegion(region) = annotation {
+            if !self.regions.contains(region) {
+                self.regions.push(*region);
+            }
+        }
+
+        self.ci.push_annotation(annotation)
+    }
+
+    fn pop_annotation(&mut self) -> Result<(), Self::Error> {
+        self.ci.pop_annotation()
+    }
+}
+
 impl<'a, W> Render for ColorWrite<'a, W>
 where
     W: fmt::Write,
@@ -1025,7 +1137,8 @@ where
             ParserSuggestion => {
                 self.write_str(self.palette.parser_suggestion)?;
             }
-            TypeBlock | InlineTypeBlock | Tag | RecordField => { /* nothing yet */ }
+            TypeBlock | InlineTypeBlock | Tag | RecordField | SourceRegion(_) => { /* nothing yet */
+            }
         }
         self.style_stack.push(*annotation);
         Ok(())
@@ -1043,7 +1156,9 @@ where
                     self.write_str(self.palette.reset)?;
                 }
 
-                TypeBlock | InlineTypeBlock | Tag | Opaque | RecordField => { /* nothing yet */ }
+                TypeBlock | InlineTypeBlock | Tag | Opaque | RecordField | SourceRegion(_) => {
+                    /* nothing yet */
+                }
             },
         }
         Ok(())
@@ -88,6 +88,7 @@ mod test_reporting {
                 render: RenderTarget::Generic,
                 threading: Threading::Single,
                 exec_mode: ExecutionMode::Check,
+                cache_dir: None,
             };
             let result =
                 roc_load::load_and_typecheck(arena, full_file_path, exposed_types, load_config);
@@ -767,6 +768,45 @@ mod test_reporting {
         );
     }
 
+    #[test]
+    fn report_as_json() {
+        let src: &str = indoc!(
+            r#"
+                x = 1
+
+                if "foo" then x else 3
+            "#
+        );
+
+        let arena = Bump::new();
+        let (type_problems, _can_problems, home, interns) =
+            infer_expr_help(&arena, src).expect("parse error");
+
+        let src_lines: Vec<&str> = src.split('\n').collect();
+        let lines = LineInfo::new(src);
+        let alloc = RocDocAllocator::new(&src_lines, home, &interns);
+
+        let mut buf = String::new();
+        for problem in type_problems {
+            if let Some(report) = type_problem(
+                &alloc,
+                &lines,
+                filename_from_string(r"/code/proj/Main.roc"),
+                problem,
+            ) {
+                report.render(RenderTarget::Json, &mut buf, &alloc, &DEFAULT_PALETTE);
+            }
+        }
+
+        assert_eq!(
+            buf,
+            concat!(
+                r#"{"file":"/code/proj/Main.roc","message":"This `if` condition needs to be a Bool:\n\n3│  if \"foo\" then x else 3\n       ^^^^^\n\nRight now it’s a string of type:\n\n    Str\n\nBut I need every `if` condition to evaluate to a Bool—either `True` or\n`False`.","region":{"end":{"column":9,"line":3},"start":{"column":4,"line":3}},"related":[],"severity":"error","title":"TYPE MISMATCH"}"#,
+                "\n"
+            )
+        );
+    }
+
     test_report!(
         if_condition_not_bool,
         indoc!(
@@ -3616,7 +3656,7 @@ mod test_reporting {
         Set
         List
         Dict
-        Result
+        Hash
 
     ── SYNTAX PROBLEM ──────────────────────────────────────── /code/proj/Main.roc ─
 
@@ -10436,4 +10476,33 @@ All branches in an `if` must have the same type!
     Tip: Looks like the b field is missing.
     "###
     );
+
+    test_report!(
+        set_of_floats_is_not_hashable,
+        indoc!(
+            r#"
+            Set.single 1.0
+            "#
+        ),
+        @r###"
+    ── TYPE MISMATCH ───────────────────────────────────────── /code/proj/Main.roc ─
+
+    This expression has a type that does not implement the abilities it's expected to:
+
+    4│      Set.single 1.0
+                       ^^^
+
+    Roc can't generate an implementation of the `Hash.Hash` ability for
+
+        Frac a
+
+    In particular, an implementation for
+
+        FloatingPoint a
+
+    cannot be generated.
+
+    Note: `Hash` cannot be generated for fractional numbers like `F64` or `Dec`.
+    "###
+    );
 }
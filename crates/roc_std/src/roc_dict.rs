@@ -0,0 +1,473 @@
+#![deny(unsafe_op_in_unsafe_fn)]
+
+use crate::roc_hasher::{hash_key, RocHash};
+use crate::roc_list::RocList;
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    iter::FromIterator,
+    mem::{self, ManuallyDrop},
+};
+
+#[cfg(feature = "serde")]
+use core::marker::PhantomData;
+#[cfg(feature = "serde")]
+use serde::{
+    de::{Deserializer, MapAccess, Visitor},
+    ser::{SerializeMap, Serializer},
+    Deserialize, Serialize,
+};
+
+/// Roc's `Dict k v`, which keeps its entries in a list (in insertion order, unless entries
+/// were removed) along with `buckets`, a hash table that Roc uses to find entries by key.
+///
+/// The host fills in the buckets the same way Roc does, which is why looking up keys
+/// needs them to implement `RocHash`.
+#[repr(C)]
+pub struct RocDict<K, V> {
+    buckets: RocList<usize>,
+    data: RocList<RocDictItem<K, V>>,
+}
+
+impl<K, V> RocDict<K, V> {
+    pub fn empty() -> Self {
+        Self {
+            buckets: RocList::empty(),
+            data: RocList::empty(),
+        }
+    }
+
+    /// Create an empty RocDict with enough space preallocated to store
+    /// the requested number of entries, like Roc's `Dict.withCapacity`.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buckets: RocList::empty(),
+            data: RocList::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Iterates over the entries, in the same order as Roc's `Dict.walk`.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            items: self.data.as_slice().iter(),
+        }
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: RocHash + PartialEq + ?Sized,
+    {
+        match self.find_slot(key) {
+            Slot::Found(data_index) => Some(self.data[data_index].value()),
+            Slot::Vacant => None,
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: RocHash + PartialEq + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Like `findSlot` in `Dict.roc`, finds the entry of the given key.
+    fn find_slot<Q>(&self, key: &Q) -> Slot
+    where
+        K: Borrow<Q>,
+        Q: RocHash + PartialEq + ?Sized,
+    {
+        if self.buckets.is_empty() {
+            return Slot::Vacant;
+        }
+
+        let mut bucket_index = home_bucket(self.buckets.len(), key);
+
+        loop {
+            match self.buckets[bucket_index] {
+                EMPTY_BUCKET => return Slot::Vacant,
+                slot => {
+                    if self.data[slot - 1].key().borrow() == key {
+                        return Slot::Found(slot - 1);
+                    }
+                }
+            }
+
+            bucket_index = next_bucket(self.buckets.len(), bucket_index);
+        }
+    }
+}
+
+impl<K, V> Default for RocDict<K, V> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<K, V> Clone for RocDict<K, V> {
+    fn clone(&self) -> Self {
+        // Cloning the lists increments their reference counts.
+        Self {
+            buckets: self.buckets.clone(),
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<K: RocHash + PartialEq, V> RocDict<K, V> {
+    /// Like Roc's `Dict.insert`, but only for dictionaries that nothing else references,
+    /// because they are still being built.
+    pub(crate) fn insert_unique(&mut self, key: K, value: V) {
+        match self.find_slot(&key) {
+            Slot::Found(data_index) => {
+                self.data.as_mut_slice_unique()[data_index] = RocDictItem::new(key, value);
+            }
+            Slot::Vacant => {
+                self.data.push_unique(RocDictItem::new(key, value));
+
+                let new_len = self.data.len();
+
+                if bucket_count_for(new_len) != self.buckets.len() {
+                    self.rehash(bucket_count_for(new_len));
+                } else {
+                    let mut buckets = mem::take(&mut self.buckets);
+                    self.place_entry(buckets.as_mut_slice_unique(), new_len - 1);
+                    self.buckets = buckets;
+                }
+            }
+        }
+    }
+
+    /// Builds the buckets for the entries from scratch, like `rehash` in `Dict.roc`.
+    fn rehash(&mut self, bucket_count: usize) {
+        let mut buckets = empty_buckets(bucket_count);
+
+        for data_index in 0..self.data.len() {
+            self.place_entry(buckets.as_mut_slice_unique(), data_index);
+        }
+
+        self.buckets = buckets;
+    }
+
+    /// Puts the entry at `data_index` into the buckets, like `placeEntry` in `Dict.roc`.
+    /// Roc's `==` compares the buckets, so they have to end up exactly where Roc puts them:
+    /// entries that are further from their home bucket go first, and entries that are
+    /// just as far go in the order of their index.
+    fn place_entry(&self, buckets: &mut [usize], data_index: usize) {
+        let bucket_count = buckets.len();
+        let mut slot = data_index + 1;
+        let mut bucket_index = home_bucket(bucket_count, self.data[data_index].key());
+        let mut distance = 0;
+
+        loop {
+            let resident = buckets[bucket_index];
+
+            if resident == EMPTY_BUCKET {
+                buckets[bucket_index] = slot;
+                return;
+            }
+
+            let resident_home = home_bucket(bucket_count, self.data[resident - 1].key());
+            let resident_distance =
+                (bucket_index + bucket_count - resident_home) & (bucket_count - 1);
+
+            if resident_distance < distance || (resident_distance == distance && resident > slot) {
+                // This entry takes the bucket, and the resident moves further along.
+                buckets[bucket_index] = slot;
+                slot = resident;
+                distance = resident_distance;
+            }
+
+            bucket_index = next_bucket(bucket_count, bucket_index);
+            distance += 1;
+        }
+    }
+}
+
+/// Where a key is in a dictionary: the index of its entry, if it has one.
+enum Slot {
+    Found(usize),
+    Vacant,
+}
+
+/// An empty bucket holds 0, an occupied one holds the index of its entry plus one.
+const EMPTY_BUCKET: usize = 0;
+
+/// The number of buckets a non-empty dictionary has at least.
+const MIN_BUCKET_COUNT: usize = 8;
+
+/// Whether `n` entries would exceed the maximum load factor (7/8) of `bucket_count` buckets.
+fn exceeds_max_load(n: usize, bucket_count: usize) -> bool {
+    n * 8 > bucket_count * 7
+}
+
+/// The smallest number of buckets that can hold `n` entries.
+fn bucket_count_for(n: usize) -> usize {
+    if n == 0 {
+        return 0;
+    }
+
+    let mut bucket_count = MIN_BUCKET_COUNT;
+
+    while exceeds_max_load(n, bucket_count) {
+        bucket_count *= 2;
+    }
+
+    bucket_count
+}
+
+fn empty_buckets(bucket_count: usize) -> RocList<usize> {
+    let mut buckets = RocList::with_capacity(bucket_count);
+
+    for _ in 0..bucket_count {
+        buckets.push_unique(EMPTY_BUCKET);
+    }
+
+    buckets
+}
+
+/// The bucket where probing for the given key starts. The number of buckets is a power of two.
+fn home_bucket<Q: RocHash + ?Sized>(bucket_count: usize, key: &Q) -> usize {
+    (hash_key(key) & (bucket_count as u64 - 1)) as usize
+}
+
+fn next_bucket(bucket_count: usize, bucket_index: usize) -> usize {
+    (bucket_index + 1) & (bucket_count - 1)
+}
+
+impl<K: RocHash + PartialEq, V> FromIterator<(K, V)> for RocDict<K, V> {
+    /// Like Roc's `Dict.insert`, a key that appears more than once keeps the position
+    /// of its first appearance, but is replaced along with its value by its last one.
+    fn from_iter<I>(into: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let iter = into.into_iter();
+        let mut dict = Self::with_capacity(iter.size_hint().0);
+
+        for (key, value) in iter {
+            dict.insert_unique(key, value);
+        }
+
+        dict
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a RocDict<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct Iter<'a, K, V> {
+    items: core::slice::Iter<'a, RocDictItem<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next().map(|item| (item.key(), item.value()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+
+/// Like Roc's `==` on dictionaries, this takes the order of the entries into account.
+impl<K: PartialEq, V: PartialEq> PartialEq for RocDict<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for RocDict<K, V> {}
+
+impl<K: PartialOrd, V: PartialOrd> PartialOrd for RocDict<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        // If one is longer than the other, use that as the ordering.
+        match self.len().partial_cmp(&other.len()) {
+            Some(Ordering::Equal) => {}
+            ord => return ord,
+        }
+
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<K: Ord, V: Ord> Ord for RocDict<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // If one is longer than the other, use that as the ordering.
+        match self.len().cmp(&other.len()) {
+            Ordering::Equal => {}
+            ord => return ord,
+        }
+
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<K: Hash, V: Hash> Hash for RocDict<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+
+        for entry in self.iter() {
+            entry.hash(state);
+        }
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for RocDict<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An entry of a dictionary, which in Roc is a `Pair k v`. Roc stores the fields of a
+/// tag's payload in order of decreasing alignment (keeping their order if the alignments
+/// are the same), so the value comes first if its alignment is larger than the key's.
+#[repr(C)]
+union RocDictItem<K, V> {
+    key_first: ManuallyDrop<KeyFirst<K, V>>,
+    value_first: ManuallyDrop<ValueFirst<K, V>>,
+}
+
+#[repr(C)]
+struct KeyFirst<K, V> {
+    key: K,
+    value: V,
+}
+
+#[repr(C)]
+struct ValueFirst<K, V> {
+    value: V,
+    key: K,
+}
+
+impl<K, V> RocDictItem<K, V> {
+    fn key_is_first() -> bool {
+        mem::align_of::<K>() >= mem::align_of::<V>()
+    }
+
+    fn new(key: K, value: V) -> Self {
+        if Self::key_is_first() {
+            Self {
+                key_first: ManuallyDrop::new(KeyFirst { key, value }),
+            }
+        } else {
+            Self {
+                value_first: ManuallyDrop::new(ValueFirst { value, key }),
+            }
+        }
+    }
+
+    fn key(&self) -> &K {
+        if Self::key_is_first() {
+            unsafe { &self.key_first.key }
+        } else {
+            unsafe { &self.value_first.key }
+        }
+    }
+
+    fn value(&self) -> &V {
+        if Self::key_is_first() {
+            unsafe { &self.key_first.value }
+        } else {
+            unsafe { &self.value_first.value }
+        }
+    }
+}
+
+impl<K, V> Drop for RocDictItem<K, V> {
+    fn drop(&mut self) {
+        if Self::key_is_first() {
+            unsafe { ManuallyDrop::drop(&mut self.key_first) };
+        } else {
+            unsafe { ManuallyDrop::drop(&mut self.value_first) };
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<K: Serialize, V: Serialize> Serialize for RocDict<K, V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (key, value) in self {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> Deserialize<'de> for RocDict<K, V>
+where
+    K: Deserialize<'de> + RocHash + PartialEq,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(RocDictVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+struct RocDictVisitor<K, V> {
+    marker: PhantomData<(K, V)>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> Visitor<'de> for RocDictVisitor<K, V>
+where
+    K: Deserialize<'de> + RocHash + PartialEq,
+    V: Deserialize<'de>,
+{
+    type Value = RocDict<K, V>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a map")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut dict = RocDict::with_capacity(map.size_hint().unwrap_or(0));
+
+        while let Some((key, value)) = map.next_entry()? {
+            dict.insert_unique(key, value);
+        }
+
+        Ok(dict)
+    }
+}
@@ -0,0 +1,299 @@
+use crate::{roc_list::RocList, roc_str::RocStr, I128, U128};
+
+/// The hasher Roc's `Dict` uses for its keys (`LowLevelHasher` in `Dict.roc`), so that the
+/// host can put dictionary entries into the same buckets Roc would.
+///
+/// Byte sequences are hashed with wyhash, as in the builtins' `hash.zig`, while integers are
+/// mixed in directly, using the same mixing function wyhash uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RocHasher {
+    state: u64,
+}
+
+impl RocHasher {
+    /// The seed of `Dict.roc`'s `defaultSeed`.
+    const DEFAULT_SEED: u64 = 0x526F_6352_616E_643F;
+
+    pub fn new() -> Self {
+        Self {
+            state: Self::DEFAULT_SEED,
+        }
+    }
+
+    pub fn add_bytes(&mut self, bytes: &[u8]) {
+        self.state = wyhash(self.state, bytes);
+    }
+
+    pub fn add_u8(&mut self, n: u8) {
+        self.add_u64(n as u64)
+    }
+
+    pub fn add_u16(&mut self, n: u16) {
+        self.add_u64(n as u64)
+    }
+
+    pub fn add_u32(&mut self, n: u32) {
+        self.add_u64(n as u64)
+    }
+
+    pub fn add_u64(&mut self, n: u64) {
+        self.state = mum(self.state ^ PRIMES[0], n ^ PRIMES[1]);
+    }
+
+    pub fn add_u128(&mut self, n: u128) {
+        self.add_u64(n as u64);
+        self.add_u64((n >> 64) as u64);
+    }
+
+    pub fn add_i8(&mut self, n: i8) {
+        self.add_u8(n as u8)
+    }
+
+    pub fn add_i16(&mut self, n: i16) {
+        self.add_u16(n as u16)
+    }
+
+    pub fn add_i32(&mut self, n: i32) {
+        self.add_u32(n as u32)
+    }
+
+    pub fn add_i64(&mut self, n: i64) {
+        self.add_u64(n as u64)
+    }
+
+    pub fn add_i128(&mut self, n: i128) {
+        self.add_u128(n as u128)
+    }
+
+    /// Like Roc's `Hash.complete`.
+    pub fn finish(&self) -> u64 {
+        mum(self.state, PRIMES[2])
+    }
+}
+
+impl Default for RocHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Values that hash like their Roc counterparts do with Roc's `Hash` ability.
+/// Keys of a `RocDict` have to implement this for the host to look them up.
+pub trait RocHash {
+    fn roc_hash(&self, hasher: &mut RocHasher);
+}
+
+macro_rules! roc_hash_ints {
+    ($($ty:ty => $add:ident),* $(,)?) => {
+        $(
+            impl RocHash for $ty {
+                fn roc_hash(&self, hasher: &mut RocHasher) {
+                    hasher.$add(*self)
+                }
+            }
+        )*
+    };
+}
+
+roc_hash_ints! {
+    u8 => add_u8,
+    u16 => add_u16,
+    u32 => add_u32,
+    u64 => add_u64,
+    u128 => add_u128,
+    i8 => add_i8,
+    i16 => add_i16,
+    i32 => add_i32,
+    i64 => add_i64,
+    i128 => add_i128,
+}
+
+/// Like Roc's `Nat`, which is always hashed as a `U64`.
+impl RocHash for usize {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        hasher.add_u64(*self as u64)
+    }
+}
+
+impl RocHash for U128 {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        hasher.add_u128(u128::from(*self))
+    }
+}
+
+impl RocHash for I128 {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        hasher.add_i128(i128::from(*self))
+    }
+}
+
+/// Roc's `Bool` is the tag union `[False, True]`, which hashes its discriminant.
+impl RocHash for bool {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        hasher.add_u8(*self as u8)
+    }
+}
+
+impl RocHash for str {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        self.len().roc_hash(hasher);
+        hasher.add_bytes(self.as_bytes());
+    }
+}
+
+impl RocHash for RocStr {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        self.as_str().roc_hash(hasher)
+    }
+}
+
+impl<T: RocHash> RocHash for [T] {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        self.len().roc_hash(hasher);
+
+        for elem in self {
+            elem.roc_hash(hasher);
+        }
+    }
+}
+
+impl<T: RocHash> RocHash for RocList<T> {
+    fn roc_hash(&self, hasher: &mut RocHasher) {
+        self.as_slice().roc_hash(hasher)
+    }
+}
+
+/// What Roc's `Dict` uses to pick the bucket of a key.
+pub(crate) fn hash_key<K: RocHash + ?Sized>(key: &K) -> u64 {
+    let mut hasher = RocHasher::new();
+
+    key.roc_hash(&mut hasher);
+
+    hasher.finish()
+}
+
+// The rest of this is a port of `WyhashStateless.hash` from the builtins' `hash.zig`.
+
+const PRIMES: [u64; 5] = [
+    0xa0761d6478bd642f,
+    0xe7037ed1a0b428db,
+    0x8ebc6af09c88c6e3,
+    0x589965cc75374cc3,
+    0x1d8e4e27c47d124f,
+];
+
+fn read_bytes(bytes: usize, data: &[u8]) -> u64 {
+    data[..bytes]
+        .iter()
+        .rev()
+        .fold(0, |n, byte| (n << 8) | *byte as u64)
+}
+
+fn read_8bytes_swapped(data: &[u8]) -> u64 {
+    (read_bytes(4, data) << 32) | read_bytes(4, &data[4..])
+}
+
+/// Reads up to 8 bytes the way `hash.zig` does for the last few bytes of a message,
+/// which is not always little-endian.
+fn read_up_to_8(data: &[u8]) -> u64 {
+    match data.len() {
+        0 => 0,
+        1 => read_bytes(1, data),
+        2 => read_bytes(2, data),
+        3 => (read_bytes(2, data) << 8) | read_bytes(1, &data[2..]),
+        4 => read_bytes(4, data),
+        5 => (read_bytes(4, data) << 8) | read_bytes(1, &data[4..]),
+        6 => (read_bytes(4, data) << 16) | read_bytes(2, &data[4..]),
+        7 => {
+            (read_bytes(4, data) << 24)
+                | (read_bytes(2, &data[4..]) << 8)
+                | read_bytes(1, &data[6..])
+        }
+        _ => read_8bytes_swapped(data),
+    }
+}
+
+fn mum(a: u64, b: u64) -> u64 {
+    let r = (a as u128) * (b as u128);
+
+    ((r >> 64) ^ r) as u64
+}
+
+fn mix0(a: u64, b: u64, seed: u64) -> u64 {
+    mum(a ^ seed ^ PRIMES[0], b ^ seed ^ PRIMES[1])
+}
+
+fn mix1(a: u64, b: u64, seed: u64) -> u64 {
+    mum(a ^ seed ^ PRIMES[2], b ^ seed ^ PRIMES[3])
+}
+
+fn wyhash(mut seed: u64, input: &[u8]) -> u64 {
+    let aligned_len = input.len() - (input.len() % 32);
+    let (rounds, rest) = input.split_at(aligned_len);
+
+    for round in rounds.chunks_exact(32) {
+        seed = mix0(read_bytes(8, round), read_bytes(8, &round[8..]), seed)
+            ^ mix1(
+                read_bytes(8, &round[16..]),
+                read_bytes(8, &round[24..]),
+                seed,
+            );
+    }
+
+    // Past the first 16 bytes, the rest is mixed in with `mix1` rather than `mix0`.
+    // An 8 byte chunk is paired with `primes[4]`, unless more bytes follow it.
+    let seed = match rest.len() {
+        0 => seed,
+        1..=8 => mix0(read_up_to_8(rest), PRIMES[4], seed),
+        9..=16 => mix0(read_8bytes_swapped(rest), read_up_to_8(&rest[8..]), seed),
+        17..=24 => {
+            mix0(
+                read_8bytes_swapped(rest),
+                read_8bytes_swapped(&rest[8..]),
+                seed,
+            ) ^ mix1(read_up_to_8(&rest[16..]), PRIMES[4], seed)
+        }
+        _ => {
+            mix0(
+                read_8bytes_swapped(rest),
+                read_8bytes_swapped(&rest[8..]),
+                seed,
+            ) ^ mix1(
+                read_8bytes_swapped(&rest[16..]),
+                read_up_to_8(&rest[24..]),
+                seed,
+            )
+        }
+    };
+
+    mum(seed ^ input.len() as u64, PRIMES[4])
+}
+
+#[cfg(test)]
+mod test {
+    use super::wyhash;
+
+    #[test]
+    fn wyhash_test_vectors() {
+        // The test vectors of `hash.zig`
+        assert_eq!(wyhash(0, b""), 0x0);
+        assert_eq!(wyhash(1, b"a"), 0xbed235177f41d328);
+        assert_eq!(wyhash(2, b"abc"), 0xbe348debe59b27c3);
+        assert_eq!(wyhash(3, b"message digest"), 0x37320f657213a290);
+        assert_eq!(wyhash(4, b"abcdefghijklmnopqrstuvwxyz"), 0xd0b270e1d8a7019c);
+        assert_eq!(
+            wyhash(
+                5,
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789"
+            ),
+            0x602a1894d3bbfe7f
+        );
+        assert_eq!(
+            wyhash(
+                6,
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890"
+            ),
+            0x829e9c148b75970e
+        );
+    }
+}
@@ -176,7 +176,7 @@ where
 
             if is_unique {
                 // If we have enough capacity, we can add to the existing elements in-place.
-                if self.capacity() >= slice.len() {
+                if self.capacity() >= new_len {
                     elements
                 } else {
                     // There wasn't enough capacity, so we need a new allocation.
@@ -266,6 +266,8 @@ impl<T> RocList<T> {
 
                         if new_alloc == old_alloc {
                             // We successfully reallocated in-place; we're done!
+                            self.capacity = new_len;
+
                             return;
                         } else {
                             // We got back a different allocation; copy the existing elements
@@ -327,6 +329,40 @@ impl<T> RocList<T> {
         });
     }
 
+    /// Appends an element to a list which nothing else references, such as one that is
+    /// still being built. Shared lists must never be mutated in place!
+    pub(crate) fn push_unique(&mut self, elem: T) {
+        debug_assert!(self.storage().map_or(true, |storage| storage.is_unique()));
+
+        if self.length == self.capacity {
+            self.reserve(cmp::max(self.capacity / 2, 1));
+        }
+
+        unsafe {
+            // `reserve` always leaves the list with an allocation.
+            let elements = self.elements.unwrap().as_ptr();
+
+            elements.add(self.length).write(ManuallyDrop::new(elem));
+        }
+
+        self.length += 1;
+    }
+
+    /// Gives mutable access to the elements of a list which nothing else references, such
+    /// as one that is still being built. Shared lists must never be mutated in place!
+    pub(crate) fn as_mut_slice_unique(&mut self) -> &mut [T] {
+        debug_assert!(self.storage().map_or(true, |storage| storage.is_unique()));
+
+        if let Some(elements) = self.elements {
+            let elements =
+                ptr::slice_from_raw_parts_mut(elements.as_ptr().cast::<T>(), self.length);
+
+            unsafe { &mut *elements }
+        } else {
+            &mut []
+        }
+    }
+
     /// Replace self with a new version, without letting `drop` run in between.
     fn update_to(&mut self, mut updated: Self) {
         // We want to replace `self` with `updated` in a way that makes sure
@@ -0,0 +1,156 @@
+use crate::roc_dict::{self, RocDict};
+use crate::roc_hasher::RocHash;
+use core::{
+    borrow::Borrow,
+    fmt::{self, Debug},
+    iter::FromIterator,
+};
+
+#[cfg(feature = "serde")]
+use core::marker::PhantomData;
+#[cfg(feature = "serde")]
+use serde::{
+    de::{Deserializer, SeqAccess, Visitor},
+    ser::{SerializeSeq, Serializer},
+    Deserialize, Serialize,
+};
+
+/// Roc's `Set k`, which is a `Dict k {}` under the hood. See `RocDict` for how the host
+/// builds these and looks things up in them.
+#[repr(transparent)]
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RocSet<T>(RocDict<T, ()>);
+
+impl<T> RocSet<T> {
+    pub fn empty() -> Self {
+        Self(RocDict::empty())
+    }
+
+    /// Create an empty RocSet with enough space preallocated to store
+    /// the requested number of elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(RocDict::with_capacity(capacity))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterates over the elements, in the same order as Roc's `Set.walk`.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            entries: self.0.iter(),
+        }
+    }
+
+    pub fn contains<Q>(&self, elem: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: RocHash + PartialEq + ?Sized,
+    {
+        self.0.contains_key(elem)
+    }
+}
+
+impl<T: RocHash + PartialEq> FromIterator<T> for RocSet<T> {
+    fn from_iter<I>(into: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Self(into.into_iter().map(|elem| (elem, ())).collect())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RocSet<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub struct Iter<'a, T> {
+    entries: roc_dict::Iter<'a, T, ()>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|(elem, _)| elem)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<T: Debug> Debug for RocSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for RocSet<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for elem in self {
+            seq.serialize_element(elem)?;
+        }
+        seq.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Deserialize<'de> for RocSet<T>
+where
+    T: Deserialize<'de> + RocHash + PartialEq,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(RocSetVisitor {
+            marker: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+struct RocSetVisitor<T> {
+    marker: PhantomData<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> Visitor<'de> for RocSetVisitor<T>
+where
+    T: Deserialize<'de> + RocHash + PartialEq,
+{
+    type Value = RocSet<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut set = RocSet::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(elem) = seq.next_element()? {
+            set.0.insert_unique(elem, ());
+        }
+
+        Ok(set)
+    }
+}
@@ -59,7 +59,7 @@ pub unsafe extern "C" fn roc_memset(dst: *mut c_void, c: i32, n: usize) -> *mut
 
 #[cfg(test)]
 mod test_roc_std {
-    use roc_std::{RocBox, RocDec, RocList, RocResult, RocStr};
+    use roc_std::{RocBox, RocDec, RocDict, RocList, RocResult, RocSet, RocStr};
 
     fn roc_str_byte_representation(string: &RocStr) -> [u8; RocStr::SIZE] {
         unsafe { core::mem::transmute_copy(string) }
@@ -221,6 +221,130 @@ mod test_roc_std {
         assert_eq!(from_iter, from_slice);
     }
 
+    #[test]
+    fn dict_from_iter() {
+        let dict: RocDict<RocStr, i64> =
+            [("one".into(), 1), ("two".into(), 2)].into_iter().collect();
+
+        assert_eq!(dict.len(), 2);
+        assert_eq!(dict.get(&RocStr::from("one")), Some(&1));
+        assert_eq!(dict.get(&RocStr::from("two")), Some(&2));
+        assert_eq!(dict.get(&RocStr::from("three")), None);
+    }
+
+    #[test]
+    fn dict_from_iter_duplicate_keys() {
+        let dict = RocDict::from_iter([(1u8, "a"), (2, "b"), (1, "c")]);
+
+        assert_eq!(dict.len(), 2);
+        assert_eq!(dict.iter().collect::<Vec<_>>(), [(&1, &"c"), (&2, &"b")]);
+    }
+
+    #[test]
+    fn dict_entry_layout() {
+        // The value comes first when its alignment is larger than the key's.
+        let dict = RocDict::from_iter([(7u8, 42u64)]);
+        let layout: [usize; 6] = unsafe { core::mem::transmute_copy(&dict) };
+        let entry = layout[3] as *const u64;
+
+        assert_eq!(
+            core::mem::size_of::<RocDict<u8, u64>>(),
+            6 * core::mem::size_of::<usize>()
+        );
+        assert_eq!(unsafe { (*entry, *(entry.add(1) as *const u8)) }, (42, 7));
+        assert_eq!(dict.get(&7), Some(&42));
+    }
+
+    #[test]
+    fn dict_buckets() {
+        // Filtering hides the number of entries, so the buckets grow as they are inserted.
+        let dict: RocDict<u64, u64> = (0..100).filter(|n| n % 3 != 0).map(|n| (n, n)).collect();
+        let layout: [usize; 6] = unsafe { core::mem::transmute_copy(&dict) };
+        let buckets = unsafe { core::slice::from_raw_parts(layout[0] as *const usize, layout[1]) };
+
+        // Like Roc, a bucket holds the index of its entry plus one.
+        let mut slots: Vec<usize> = buckets.iter().copied().filter(|slot| *slot != 0).collect();
+        slots.sort_unstable();
+
+        assert_eq!(buckets.len(), 128);
+        assert_eq!(slots, (1..=dict.len()).collect::<Vec<_>>());
+        assert!((0..100).all(|n| dict.contains_key(&n) == (n % 3 != 0)));
+    }
+
+    #[test]
+    fn dict_buckets_ignore_capacity() {
+        // Roc's `==` compares the buckets, so they can't depend on how the dict was built.
+        let buckets = |dict: &RocDict<u64, u64>| {
+            let layout: [usize; 6] = unsafe { core::mem::transmute_copy(dict) };
+            unsafe { core::slice::from_raw_parts(layout[0] as *const usize, layout[1]) }.to_vec()
+        };
+        let exact: RocDict<u64, u64> = (0..100).map(|n| (n * 7, n)).collect();
+        let grown: RocDict<u64, u64> = (0..100).filter(|_| true).map(|n| (n * 7, n)).collect();
+
+        assert_eq!(buckets(&exact), buckets(&grown));
+    }
+
+    #[test]
+    fn dict_str_keys() {
+        let cities = ["London", "Philadelphia", "Shanghai", "Delhi", "Amsterdam"];
+        let dict: RocDict<RocStr, usize> = cities
+            .iter()
+            .enumerate()
+            .map(|(index, city)| (RocStr::from(*city), index))
+            .collect();
+
+        for (index, city) in cities.iter().enumerate() {
+            assert_eq!(dict.get(&RocStr::from(*city)), Some(&index));
+        }
+
+        assert_eq!(dict.get(&RocStr::from("Rome")), None);
+    }
+
+    #[test]
+    fn dict_eq_and_debug() {
+        let dict = RocDict::from_iter([(1u8, 2u8), (3, 4)]);
+
+        assert_eq!(dict.clone(), dict);
+        assert_ne!(dict, RocDict::from_iter([(3u8, 4u8), (1, 2)]));
+        assert_eq!(format!("{:?}", dict), "{1: 2, 3: 4}");
+    }
+
+    #[test]
+    fn set_from_iter() {
+        let set = RocSet::from_iter([3u32, 1, 3, 2]);
+
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&1));
+        assert!(!set.contains(&4));
+        assert_eq!(set.iter().copied().collect::<Vec<_>>(), [3, 1, 2]);
+        assert_eq!(format!("{:?}", set), "{3, 1, 2}");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn dict_serde_roundtrip() {
+        let orig = RocDict::from_iter([(RocStr::from("one"), 1u8), (RocStr::from("two"), 2)]);
+
+        let serialized = serde_json::to_string(&orig).expect("failed to serialize dict");
+        let deserialized = serde_json::from_str::<RocDict<RocStr, u8>>(&serialized)
+            .expect("failed to deserialize dict");
+
+        assert_eq!(serialized, r#"{"one":1,"two":2}"#);
+        assert_eq!(orig, deserialized);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn set_serde_roundtrip() {
+        let orig = RocSet::from_iter(1u16..100);
+
+        let serialized = serde_json::to_string(&orig).expect("failed to serialize set");
+        let deserialized =
+            serde_json::from_str::<RocSet<u16>>(&serialized).expect("failed to deserialize set");
+
+        assert_eq!(orig, deserialized);
+    }
+
     #[test]
     fn roc_result_to_rust_result() {
         let greeting = "Hello, World!";